@@ -24,10 +24,12 @@ use shared::events::{ClientEvent, Params};
 /// |-----------------|------------|------------------------|
 /// | `CHAT_MESSAGE`  | ✅ да       | `CHAT`                 |
 /// | `DIRECT_MESSAGE`| ✅ да       | `CHAT`                 |
-/// | `NOTE_UPSERT`, `NOTE_DELETE` | ✅ да | `NOTE`         |
+/// | `NOTE_UPSERT`, `NOTE_DELETE`, `NOTE_EDIT`, `NOTE_CARET` | ✅ да | `NOTE` |
 /// | `SYNC_SNAPSHOT` | ✅ да       | `SYNC`                 |
+/// | `CHARACTER_SHEET_UPSERT`, `CHARACTER_SHEET_DELETE`, `CHARACTER_SHEET_SECRETS`, `SHEET_TEMPLATE_UPSERT` | ✅ да | `CHARACTER` |
 /// | `FILE_ANNOUNCE`, `FILE_REQUEST`, `FILE_ABORT` | ✅ да | `FILE_CONTROL` |
 /// | `FILE_CHUNK`    | ✅ да       | `FILE_CHUNK`           |
+/// | `HANDOUT_SHOW`, `HANDOUT_DELETE` | ✅ да | `HANDOUT`   |
 /// | Все остальные   | ❌ нет      | —                      |
 ///
 /// ### Процесс обмена ключами (X25519 + ChaCha20-Poly1305)
@@ -61,6 +63,7 @@ use shared::events::{ClientEvent, Params};
 /// - **`PRESENCE_REQUEST`** — запрос списка пользователей в комнате.
 /// - **`PRESENCE_RESPONSE`** — ответ на `PRESENCE_REQUEST`.
 /// - **`PRESENCE_ANNOUNCE`** — широковещательное объявление о присутствии.
+/// - **`PRESENCE_LEAVE`** — рассылается сервером при отключении пользователя; от клиента отклоняется.
 ///
 /// ### Чат и личные сообщения
 /// - **`CHAT_MESSAGE`** *(зашифровано)* — публичное сообщение в чат комнаты.
@@ -70,15 +73,49 @@ use shared::events::{ClientEvent, Params};
 ///
 /// ### Заметки
 /// - **`NOTE_UPSERT`** *(зашифровано)* — создание / обновление заметки.
-///   Видимость: `Public` (все), `Private` (только автор), `Direct(nick)` (личная).
+///   Видимость: `Public` (все), `Private` (только автор), `Direct(nick)` (личная),
+///   `Group({ name, members })` (автор и перечисленные участники). Личные и групповые
+///   заметки шифруются отдельным ключом, который передаётся только получателям.
+///   Необязательные поля `folder` (вложенные папки через `/`), `tags` и `pinned`
+///   упорядочивают журнал; поиск по заметкам выполняется на клиенте.
+///   Поле `revisions` хранит до 10 предыдущих версий (`author`, `saved_at_ms`,
+///   `title`, `body`); восстановление версии — обычный новый `NOTE_UPSERT`.
+///   `board_position.anchor` (`scene_id`, `offset_x`, `offset_y` от центра сцены)
+///   привязывает заметку к сцене: она двигается вместе со сценой и скрывается
+///   вместе с её содержимым. `board_style.pin_icon` превращает заметку в метку на карте.
 /// - **`NOTE_DELETE`** *(зашифровано)* — удаление заметки.
+/// - **`NOTE_EDIT`** *(зашифровано)* — операции совместной правки текста
+///   публичной заметки (RGA: вставка после символа, удаление диапазона).
+///   Участники сессии сходятся к одному тексту независимо от порядка доставки;
+///   новому участнику отправляется полный снимок с `full: true`. Итог правки
+///   сохраняется обычным `NOTE_UPSERT`, поле `edited_by` — кто сохранил.
+/// - **`NOTE_CARET`** *(зашифровано)* — курсор участника сессии правки,
+///   привязанный к идентификатору символа; `editing: false` — выход из сессии.
 ///
 /// ### Сцены и токены
 /// - **`SCENE_CREATE`** — создание новой сцены.
 /// - **`SCENE_UPDATE`** — обновление параметров сцены (фон, размер сетки, токены).
 /// - **`SCENE_DELETE`** — удаление сцены.
 /// - **`SCENE_ACTIVATE`** — переключение активной сцены для всей комнаты.
+/// - **`SCENE_ASSIGN`** — своя активная сцена для выбранных игроков, когда
+///   группа разделилась; `scene_id: null` возвращает их к общей сцене (мастер).
 /// - **`TOKEN_MOVE`** — перемещение токена в мировых координатах.
+/// - **`GAME_MASTER_SET`** — назначение или снятие мастера комнаты.
+///
+/// ### Листы персонажей
+/// - **`CHARACTER_SHEET_UPSERT`** *(зашифровано)* — создание / обновление листа персонажа.
+/// - **`CHARACTER_SHEET_DELETE`** *(зашифровано)* — удаление листа (владелец или мастер).
+/// - **`CHARACTER_SHEET_SECRETS`** *(зашифровано для владельца и мастера)* — значения приватных полей листа.
+/// - **`SHEET_TEMPLATE_UPSERT`** *(зашифровано)* — загрузка JSON-шаблона листа (мастер).
+///
+/// ### Компендиум
+/// - **`COMPENDIUM_IMPORT`** — подключение файла компендиума (JSON или ZIP) к комнате (мастер).
+/// - **`COMPENDIUM_REMOVE`** — отключение компендиума по хешу файла (мастер).
+///
+/// ### Раздаточные материалы
+/// - **`HANDOUT_SHOW`** *(зашифровано; адресный материал — для получателей и мастера)* — показ изображения или PDF всем игрокам или выбранным (мастер).
+///   Повторная отправка с тем же `id` обновляет материал, например открывает большую часть.
+/// - **`HANDOUT_DELETE`** *(зашифровано)* — удаление материала из галереи (мастер).
 ///
 /// ### Файлы
 /// - **`FILE_ANNOUNCE`** *(зашифровано)* — объявление о доступном файле (по SHA-256 хешу).
@@ -104,6 +141,11 @@ use shared::events::{ClientEvent, Params};
 /// - **`BOARD_POINTER`** — включение / выключение режима указателя (один раз при
 ///   переключении; трейл строится получателем из потока `MOUSE_EVENT`).
 /// - **`ATTENTION_PING`** — Alt+ЛКМ: пульсирующий пинг во всемировых координатах.
+/// - **`BOARD_CAMERA`** — камера доски: центр в мировых координатах и масштаб.
+///   `Follow` — живая камера для подписчиков, `Pull` / `Lock` / `Release` —
+///   мастер переводит доски игроков к своему виду и фиксирует их.
+/// - **`BOARD_FOLLOW`** — начать / прекратить следовать за камерой участника.
+/// - **`BOARD_VIEWPORT`** — видимая область доски участника для миникарты.
 ///
 /// ### Крипто
 /// - **`CRYPTO_KEY_ANNOUNCE`** — публичный ключ X25519 клиента.
@@ -194,7 +236,38 @@ use shared::events::{ClientEvent, Params};
                         "created_at_ms": 1720000000000_u64,
                         "updated_at_ms": 1720000001000_u64,
                         "board_position": { "world_x": 320.0, "world_y": 150.0 },
-                        "board_style": { "width_px": 280.0, "height_px": 220.0, "font_size_pt": 14.0, "color": "#F8EE96" }
+                        "board_style": { "width_px": 280.0, "height_px": 220.0, "font_size_pt": 14.0, "color": "#F8EE96" },
+                        "folder": "Кампания/Бой",
+                        "tags": ["инициатива"],
+                        "pinned": true,
+                        "revisions": [{
+                            "author": "gm",
+                            "saved_at_ms": 1720000000000_u64,
+                            "title": "",
+                            "body": "## Инициатива\n1. Леголас"
+                        }]
+                    }
+                })
+            )),
+            ("Note Upsert Scene Pin" = (
+                summary = "NOTE_UPSERT — метка, привязанная к сцене (шифруется)",
+                description = "anchor задаёт смещение от центра сцены; world_x/world_y — последняя известная точка на случай удаления сцены.",
+                value = json!({
+                    "type": "NOTE_UPSERT",
+                    "data": {
+                        "id": "note-uuid-4",
+                        "author": "gm",
+                        "visibility": "Public",
+                        "title": "Ловушка",
+                        "body": "# Ловушка\nНажимная плита, Сл 15.",
+                        "created_at_ms": 1720000004000_u64,
+                        "updated_at_ms": 1720000004000_u64,
+                        "board_position": {
+                            "world_x": 460.0,
+                            "world_y": 210.0,
+                            "anchor": { "scene_id": "scene-uuid", "offset_x": 60.0, "offset_y": -40.0 }
+                        },
+                        "board_style": { "width_px": 280.0, "height_px": 220.0, "font_size_pt": 14.0, "color": "#F8EE96", "pin_icon": "💀" }
                     }
                 })
             )),
@@ -214,6 +287,54 @@ use shared::events::{ClientEvent, Params};
                     }
                 })
             )),
+            ("Note Upsert Group" = (
+                summary = "NOTE_UPSERT — заметка для группы (шифруется)",
+                description = "visibility: { Group: { name, members } } — видна автору и участникам группы; пустое name — безымянная группа.",
+                value = json!({
+                    "type": "NOTE_UPSERT",
+                    "data": {
+                        "id": "note-uuid-3",
+                        "author": "gm",
+                        "visibility": { "Group": { "name": "Отряд А", "members": ["aragorn", "legolas"] } },
+                        "title": "Засада",
+                        "body": "# Засада\nВы слышите шорох в кустах.",
+                        "created_at_ms": 1720000003000_u64,
+                        "updated_at_ms": 1720000003000_u64
+                    }
+                })
+            )),
+            ("Note Edit" = (
+                summary = "NOTE_EDIT — совместная правка заметки (шифруется)",
+                value = json!({
+                    "type": "NOTE_EDIT",
+                    "data": {
+                        "note_id": "note-uuid",
+                        "sender": "legolas",
+                        "base_updated_at_ms": 1720000001000_u64,
+                        "ops": [
+                            { "Insert": {
+                                "id": { "site": "legolas", "clock": 42 },
+                                "after": { "site": "", "clock": 14 },
+                                "text": "3. Гимли\n"
+                            } },
+                            { "Delete": { "id": { "site": "", "clock": 3 }, "len": 2 } }
+                        ]
+                    }
+                })
+            )),
+            ("Note Caret" = (
+                summary = "NOTE_CARET — курсор в совместной правке (шифруется)",
+                value = json!({
+                    "type": "NOTE_CARET",
+                    "data": {
+                        "note_id": "note-uuid",
+                        "username": "legolas",
+                        "base_updated_at_ms": 1720000001000_u64,
+                        "after": { "site": "legolas", "clock": 50 },
+                        "editing": true
+                    }
+                })
+            )),
             ("Note Delete" = (
                 summary = "NOTE_DELETE — удаление заметки (шифруется)",
                 value = json!({
@@ -246,6 +367,18 @@ use shared::events::{ClientEvent, Params};
                     "data": { "scene_id": "scene-uuid", "actor": "gm" }
                 })
             )),
+            ("Scene Assign" = (
+                summary = "SCENE_ASSIGN — перенести игроков на свою сцену",
+                description = "`scene_id: null` возвращает игроков к общей активной сцене. Только мастер.",
+                value = json!({
+                    "type": "SCENE_ASSIGN",
+                    "data": {
+                        "usernames": ["aragorn", "legolas"],
+                        "scene_id": "scene-uuid",
+                        "actor": "gm"
+                    }
+                })
+            )),
             ("Token Move" = (
                 summary = "TOKEN_MOVE — переместить токен",
                 description = "Координаты x/y — в клетках (дробные разрешены при Ctrl+drag).",
@@ -260,6 +393,138 @@ use shared::events::{ClientEvent, Params};
                     }
                 })
             )),
+            ("Game Master Set" = (
+                summary = "GAME_MASTER_SET — назначить мастера комнаты",
+                description = "`game_master: null` снимает роль. Занять свободную роль может любой, передать — только текущий мастер.",
+                value = json!({
+                    "type": "GAME_MASTER_SET",
+                    "data": { "game_master": "gm", "actor": "gm" }
+                })
+            )),
+            // ── Character sheets ──────────────────────────────────────────────
+            ("Character Sheet Upsert" = (
+                summary = "CHARACTER_SHEET_UPSERT — создать / обновить лист персонажа (шифруется)",
+                description = "Менять лист могут владелец и мастер; передать лист другому игроку может только мастер.",
+                value = json!({
+                    "type": "CHARACTER_SHEET_UPSERT",
+                    "data": {
+                        "sheet": {
+                            "id": "sheet-uuid",
+                            "name": "Арагорн",
+                            "template_id": "dnd5e",
+                            "owner": "aragorn",
+                            "token_id": "token-uuid",
+                            "values": { "str": "16", "dex": "14", "hp": "31" },
+                            "updated_at_ms": 1720000000000.0
+                        },
+                        "actor": "aragorn"
+                    }
+                })
+            )),
+            ("Character Sheet Delete" = (
+                summary = "CHARACTER_SHEET_DELETE — удалить лист персонажа (шифруется)",
+                value = json!({
+                    "type": "CHARACTER_SHEET_DELETE",
+                    "data": { "sheet_id": "sheet-uuid", "actor": "gm" }
+                })
+            )),
+            ("Character Sheet Secrets" = (
+                summary = "CHARACTER_SHEET_SECRETS — приватные поля листа (шифруется ключом владельца и мастера)",
+                description = "Приватные поля не входят в `CHARACTER_SHEET_UPSERT`: их видят только владелец листа и мастер, поэтому событие шифруется ключом для `audience`.",
+                value = json!({
+                    "type": "CHARACTER_SHEET_SECRETS",
+                    "data": {
+                        "sheet_id": "sheet-uuid",
+                        "audience": ["aragorn", "gm"],
+                        "values": { "secrets": "Наследник Исильдура" },
+                        "actor": "aragorn"
+                    }
+                })
+            )),
+            ("Sheet Template Upsert" = (
+                summary = "SHEET_TEMPLATE_UPSERT — загрузить шаблон листа (шифруется)",
+                description = "Поля `computed` и `roll` используют `formula` с арифметикой, `floor`/`ceil`/`min`/`max`/`abs` и костями `NdM`.",
+                value = json!({
+                    "type": "SHEET_TEMPLATE_UPSERT",
+                    "data": {
+                        "template": {
+                            "id": "mini",
+                            "name": "Mini",
+                            "sections": [{
+                                "title": "Abilities",
+                                "columns": 2,
+                                "fields": [
+                                    { "key": "str", "label": "STR", "kind": "number", "default": "10" },
+                                    { "key": "str_mod", "label": "STR mod", "kind": "computed", "formula": "floor((str - 10) / 2)", "signed": true },
+                                    { "key": "str_check", "label": "STR check", "kind": "roll", "formula": "1d20 + str_mod" },
+                                    { "key": "secret", "label": "Secret", "kind": "text_area", "private": true }
+                                ]
+                            }]
+                        },
+                        "actor": "gm"
+                    }
+                })
+            )),
+            // ── Compendium ────────────────────────────────────────────────────
+            ("Compendium Import" = (
+                summary = "COMPENDIUM_IMPORT — подключить компендиум к комнате",
+                description = "Сам файл передаётся через FILE_ANNOUNCE / FILE_REQUEST; формат описан в docs/compendium.md.",
+                value = json!({
+                    "type": "COMPENDIUM_IMPORT",
+                    "data": {
+                        "source": {
+                            "file": {
+                                "hash": "sha256-hex",
+                                "mime_type": "application/json",
+                                "file_name": "srd-5e.json",
+                                "size": 1048576
+                            },
+                            "title": "SRD 5.1",
+                            "monster_count": 325,
+                            "spell_count": 319
+                        },
+                        "actor": "gm"
+                    }
+                })
+            )),
+            ("Compendium Remove" = (
+                summary = "COMPENDIUM_REMOVE — отключить компендиум",
+                value = json!({
+                    "type": "COMPENDIUM_REMOVE",
+                    "data": { "hash": "sha256-hex", "actor": "gm" }
+                })
+            )),
+            // ── Handouts ──────────────────────────────────────────────────────
+            ("Handout Show" = (
+                summary = "HANDOUT_SHOW — показать раздаточный материал (шифруется)",
+                description = "Пустой `recipients` — всем игрокам, иначе событие шифруется ключом получателей и мастера. `reveal` задаёт открытую часть изображения в долях; `null` — целиком. При частичном открытии `file` — отрисованная открытая часть, а не исходное изображение.",
+                value = json!({
+                    "type": "HANDOUT_SHOW",
+                    "data": {
+                        "handout": {
+                            "id": "handout-uuid",
+                            "title": "Письмо барона",
+                            "file": {
+                                "hash": "sha256-hex",
+                                "mime_type": "image/png",
+                                "file_name": "letter.png",
+                                "size": 245760
+                            },
+                            "recipients": ["alice"],
+                            "reveal": { "x": 0.0, "y": 0.0, "width": 1.0, "height": 0.5 },
+                            "shown_at_ms": 1700000000000.0
+                        },
+                        "actor": "gm"
+                    }
+                })
+            )),
+            ("Handout Delete" = (
+                summary = "HANDOUT_DELETE — удалить раздаточный материал (шифруется)",
+                value = json!({
+                    "type": "HANDOUT_DELETE",
+                    "data": { "id": "handout-uuid", "recipients": ["alice"], "actor": "gm" }
+                })
+            )),
             // ── Files ─────────────────────────────────────────────────────────
             ("File Announce" = (
                 summary = "FILE_ANNOUNCE — объявить доступный файл (шифруется)",
@@ -373,6 +638,39 @@ use shared::events::{ClientEvent, Params};
                     }
                 })
             )),
+            ("Board Camera" = (
+                summary = "BOARD_CAMERA — поделиться камерой доски",
+                description = "`mode`: `Follow` (по умолчанию) двигает только подписчиков; `Pull`, `Lock` и `Release` принимаются только от мастера.",
+                value = json!({
+                    "type": "BOARD_CAMERA",
+                    "data": {
+                        "username": "gm",
+                        "center": { "x": 250.0, "y": 180.0 },
+                        "zoom": 1.5,
+                        "mode": "Lock"
+                    }
+                })
+            )),
+            ("Board Follow" = (
+                summary = "BOARD_FOLLOW — следовать за камерой участника",
+                value = json!({
+                    "type": "BOARD_FOLLOW",
+                    "data": { "follower": "aragorn", "target": "gm", "active": true }
+                })
+            )),
+            ("Board Viewport" = (
+                summary = "BOARD_VIEWPORT — видимая область доски",
+                description = "Центр и размер области в мировых координатах; рисуется на миникартах остальных участников.",
+                value = json!({
+                    "type": "BOARD_VIEWPORT",
+                    "data": {
+                        "username": "aragorn",
+                        "center": { "x": 250.0, "y": 180.0 },
+                        "width": 1280.0,
+                        "height": 720.0
+                    }
+                })
+            )),
             // ── Crypto ────────────────────────────────────────────────────────
             ("Crypto Key Announce" = (
                 summary = "CRYPTO_KEY_ANNOUNCE — публичный ключ X25519",
@@ -14,7 +14,7 @@ use futures::{
 };
 use redis::aio::PubSub;
 use serde_json::json;
-use shared::events::{ClientEvent, EncryptedPayloadKind, Params};
+use shared::events::{ClientEvent, EncryptedPayloadKind, Params, PresenceLeavePayload};
 use std::sync::Arc;
 use tokio::sync::mpsc;
 use tokio::task::JoinHandle;
@@ -59,6 +59,14 @@ async fn process_client_message(
         );
     }
 
+    if is_server_only_event(&event) {
+        return Some(
+            json!({ "error": "This event type is only sent by the server" })
+                .to_string()
+                .into(),
+        );
+    }
+
     match event {
         ClientEvent::Ping => Some(json!({ "type": "PONG" }).to_string().into()),
         _ => {
@@ -92,15 +100,28 @@ fn is_plaintext_legacy_event(event: &ClientEvent) -> bool {
         ClientEvent::ChatMessage(_)
             | ClientEvent::NoteUpsert(_)
             | ClientEvent::NoteDelete(_)
+            | ClientEvent::NoteEdit(_)
+            | ClientEvent::NoteCaret(_)
             | ClientEvent::FileAnnounce(_)
             | ClientEvent::FileRequest(_)
             | ClientEvent::FileChunk(_)
             | ClientEvent::FileAbort(_)
             | ClientEvent::SyncSnapshot(_)
             | ClientEvent::DirectMessage(_)
+            | ClientEvent::CharacterSheetUpsert(_)
+            | ClientEvent::CharacterSheetDelete(_)
+            | ClientEvent::CharacterSheetSecrets(_)
+            | ClientEvent::SheetTemplateUpsert(_)
+            | ClientEvent::HandoutShow(_)
+            | ClientEvent::HandoutDelete(_)
     )
 }
 
+/// Departures are announced by the server itself, so clients cannot evict each other.
+fn is_server_only_event(event: &ClientEvent) -> bool {
+    matches!(event, ClientEvent::PresenceLeave(_))
+}
+
 async fn publish_event(
     redis: &redis::Client,
     channel_name: &str,
@@ -162,6 +183,52 @@ fn activity_key(channel_name: &str) -> String {
     format!("{channel_name}:activity")
 }
 
+/// Open connections per user, shared by all server instances of the room.
+fn connections_key(channel_name: &str) -> String {
+    format!("{channel_name}:connections")
+}
+
+async fn register_connection(
+    redis: &redis::Client,
+    channel_name: &str,
+    username: &str,
+) -> redis::RedisResult<()> {
+    let mut connection = redis.get_multiplexed_async_connection().await?;
+    let connections_key = connections_key(channel_name);
+
+    redis::cmd("HINCRBY")
+        .arg(&connections_key)
+        .arg(username)
+        .arg(1)
+        .query_async::<i64>(&mut connection)
+        .await?;
+    // Counts of a server that went down are never released; let them expire
+    // together with the room.
+    redis::cmd("EXPIRE")
+        .arg(&connections_key)
+        .arg(ROOM_ACTIVITY_TTL_SECONDS)
+        .query_async::<()>(&mut connection)
+        .await?;
+
+    Ok(())
+}
+
+/// Returns how many connections the user still has open in the room.
+async fn release_connection(
+    redis: &redis::Client,
+    channel_name: &str,
+    username: &str,
+) -> redis::RedisResult<i64> {
+    let mut connection = redis.get_multiplexed_async_connection().await?;
+
+    redis::cmd("HINCRBY")
+        .arg(connections_key(channel_name))
+        .arg(username)
+        .arg(-1)
+        .query_async::<i64>(&mut connection)
+        .await
+}
+
 fn spawn_send_task(mut sender: SocketSender, mut rx: mpsc::Receiver<Message>) -> JoinHandle<()> {
     tokio::spawn(async move {
         while let Some(message) = rx.recv().await {
@@ -197,7 +264,10 @@ fn spawn_redis_listener(mut pubsub: PubSub, tx: mpsc::Sender<Message>) -> JoinHa
 
 fn classify_incoming_message(event: &ClientEvent) -> IncomingMessageKind {
     match event {
-        ClientEvent::MouseClickPayload(_) => IncomingMessageKind::Mouse,
+        // Shared cameras and viewports stream at cursor rate while boards move.
+        ClientEvent::MouseClickPayload(_)
+        | ClientEvent::BoardCamera(_)
+        | ClientEvent::BoardViewport(_) => IncomingMessageKind::Mouse,
         ClientEvent::FileChunk(_) => IncomingMessageKind::FileChunk,
         ClientEvent::CryptoPayload(payload) => match payload.kind {
             EncryptedPayloadKind::FileChunk => IncomingMessageKind::FileChunk,
@@ -256,7 +326,13 @@ fn spawn_receive_task(
     })
 }
 
-async fn handle_socket(socket: WebSocket, room_id: String, user_id: Uuid, state: Arc<AppState>) {
+async fn handle_socket(
+    socket: WebSocket,
+    room_id: String,
+    user_id: Uuid,
+    username: String,
+    state: Arc<AppState>,
+) {
     let channel_name = format!("room:{room_id}");
     let (sender, receiver) = socket.split();
     let (tx, rx) = mpsc::channel::<Message>(WEBSOCKET_CHANNEL_CAPACITY);
@@ -287,9 +363,17 @@ async fn handle_socket(socket: WebSocket, room_id: String, user_id: Uuid, state:
         return;
     }
 
+    if let Err(error) = register_connection(&state.redis, &channel_name, &username).await {
+        error!(
+            "Failed to register connection for channel {}: {}",
+            channel_name, error
+        );
+    }
+
     let mut send_task = spawn_send_task(sender, rx);
     let mut redis_task = spawn_redis_listener(pubsub, tx.clone());
-    let mut receive_task = spawn_receive_task(receiver, tx, channel_name, state);
+    let mut receive_task =
+        spawn_receive_task(receiver, tx, channel_name.clone(), Arc::clone(&state));
 
     tokio::select! {
         _ = &mut send_task => {
@@ -307,6 +391,38 @@ async fn handle_socket(socket: WebSocket, room_id: String, user_id: Uuid, state:
     }
 
     info!("User disconnected from room {}", room_id);
+    // Another tab of the same user is still in the room.
+    let still_connected = match release_connection(&state.redis, &channel_name, &username).await {
+        Ok(remaining) => remaining > 0,
+        Err(error) => {
+            error!(
+                "Failed to release connection for channel {}: {}",
+                channel_name, error
+            );
+            false
+        }
+    };
+    if !still_connected {
+        publish_presence_leave(&state.redis, &channel_name, username).await;
+    }
+}
+
+async fn publish_presence_leave(redis: &redis::Client, channel_name: &str, username: String) {
+    let event = ClientEvent::PresenceLeave(PresenceLeavePayload { user: username });
+    let payload = match serde_json::to_string(&event) {
+        Ok(payload) => payload,
+        Err(error) => {
+            error!("Failed to serialize presence leave: {}", error);
+            return;
+        }
+    };
+
+    if let Err(error) = publish_event(redis, channel_name, &payload).await {
+        error!(
+            "Failed to publish presence leave for channel {}: {}",
+            channel_name, error
+        );
+    }
 }
 
 pub async fn ws_room_handler(
@@ -325,7 +441,9 @@ pub async fn ws_room_handler(
     info!("Handling WebSocket connection for room: {}", params.room_id);
     ws.max_message_size(MAX_INBOUND_MESSAGE_SIZE_BYTES)
         .max_frame_size(MAX_INBOUND_MESSAGE_SIZE_BYTES)
-        .on_upgrade(move |socket| handle_socket(socket, params.room_id, claims.sub, state))
+        .on_upgrade(move |socket| {
+            handle_socket(socket, params.room_id, claims.sub, claims.username, state)
+        })
         .into_response()
 }
 
@@ -333,8 +451,9 @@ pub async fn ws_room_handler(
 mod tests {
     use super::*;
     use shared::events::{
-        ChatMessagePayload, CryptoPayload, FileChunkPayload, SyncSnapshotPackedStatePayload,
-        SyncSnapshotPayload,
+        BoardCameraMode, BoardCameraPayload, BoardViewportPayload, ChatMessagePayload,
+        CryptoPayload, FileChunkPayload, SyncSnapshotPackedStatePayload, SyncSnapshotPayload,
+        WorldPoint,
     };
 
     #[test]
@@ -367,6 +486,15 @@ mod tests {
         assert!(is_plaintext_legacy_event(&snapshot));
     }
 
+    #[test]
+    fn presence_leave_is_server_only() {
+        let leave = ClientEvent::PresenceLeave(PresenceLeavePayload {
+            user: "alice".to_string(),
+        });
+        assert!(is_server_only_event(&leave));
+        assert!(!is_server_only_event(&ClientEvent::Ping));
+    }
+
     #[test]
     fn encrypted_file_chunk_is_rate_limited_as_file_chunk() {
         let encrypted_chunk = ClientEvent::CryptoPayload(CryptoPayload {
@@ -394,4 +522,32 @@ mod tests {
             IncomingMessageKind::FileChunk
         );
     }
+
+    #[test]
+    fn board_camera_is_rate_limited_as_mouse() {
+        let camera = ClientEvent::BoardCamera(BoardCameraPayload {
+            username: "gm".to_string(),
+            center: WorldPoint { x: 10.0, y: 20.0 },
+            zoom: 1.5,
+            mode: BoardCameraMode::Follow,
+        });
+        assert_eq!(
+            classify_incoming_message(&camera),
+            IncomingMessageKind::Mouse
+        );
+    }
+
+    #[test]
+    fn board_viewport_is_rate_limited_as_mouse() {
+        let viewport = ClientEvent::BoardViewport(BoardViewportPayload {
+            username: "aragorn".to_string(),
+            center: WorldPoint { x: 10.0, y: 20.0 },
+            width: 1280.0,
+            height: 720.0,
+        });
+        assert_eq!(
+            classify_incoming_message(&viewport),
+            IncomingMessageKind::Mouse
+        );
+    }
 }
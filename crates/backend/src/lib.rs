@@ -10,14 +10,22 @@ pub mod ws_policy;
 pub use config::Config;
 pub use error::{AppError, AppResult};
 use shared::events::{
-    AttentionPingPayload, BoardPointerPayload, ChatMessagePayload, ClientEvent,
-    CryptoKeyAnnouncePayload, CryptoKeyWrapPayload, CryptoPayload, DirectMessagePayload,
-    EncryptedPayloadKind, FileAnnouncePayload, FileChunkPayload, FileRef, FileRequestPayload,
-    MouseClickPayload, NoteDeletePayload, NotePayload, NoteVisibility, PresenceAnnouncePayload,
-    PresenceRequestPayload, PresenceResponsePayload, Scene, SceneActivatePayload, SceneCreatePayload,
-    SceneDeletePayload, SceneGrid, SceneUpdatePayload, SyncSnapshotPayload,
-    SyncSnapshotRequestPayload, SyncVersionPayload, Token, TokenMovePayload, VotingCastPayload,
-    VotingEndPayload, VotingResultPayload, VotingStartPayload, WorldPoint,
+    AttentionPingPayload, AuraVisibility, BoardCameraMode, BoardCameraPayload, BoardFollowPayload,
+    BoardPointerPayload, BoardViewportPayload, CharacterSheet, CharacterSheetDeletePayload,
+    CharacterSheetSecretsPayload, CharacterSheetUpsertPayload, ChatMessagePayload, ClientEvent,
+    CompendiumImportPayload, CompendiumRemovePayload, CompendiumSource, CryptoKeyAnnouncePayload,
+    CryptoKeyWrapPayload, CryptoPayload, DirectMessagePayload, EncryptedPayloadKind,
+    FileAnnouncePayload, FileChunkPayload, FileRef, FileRequestPayload, GameMasterSetPayload,
+    Handout, HandoutCrop, HandoutDeletePayload, HandoutShowPayload, MouseClickPayload,
+    NoteCaretPayload, NoteCharId, NoteDeletePayload, NoteEditPayload, NoteGroup, NotePayload,
+    NoteRevision, NoteTextOp, NoteVisibility, PresenceAnnouncePayload, PresenceLeavePayload,
+    PresenceRequestPayload, PresenceResponsePayload, Scene, SceneActivatePayload,
+    SceneAssignPayload, SceneCreatePayload, SceneDeletePayload, SceneDoor, SceneGrid, SceneLevel,
+    SceneLight, ScenePoint, SceneStairs, SceneUpdatePayload, SceneWall, SheetField, SheetFieldKind,
+    SheetSection, SheetTemplate, SheetTemplateUpsertPayload, SyncSnapshotPayload,
+    SyncSnapshotRequestPayload, SyncVersionPayload, TerrainCell, TerrainKind, Token, TokenAura,
+    TokenMovePayload, VotingCastPayload, VotingEndPayload, VotingResultPayload, VotingStartPayload,
+    WorldPoint,
 };
 pub use state::AppState;
 use utoipa::{Modify, OpenApi};
@@ -69,10 +77,17 @@ impl Modify for SecurityAddon {
             PresenceRequestPayload,
             PresenceResponsePayload,
             PresenceAnnouncePayload,
+            PresenceLeavePayload,
             // Notes
             NotePayload,
             NoteDeletePayload,
+            NoteRevision,
             NoteVisibility,
+            NoteGroup,
+            NoteEditPayload,
+            NoteTextOp,
+            NoteCharId,
+            NoteCaretPayload,
             // Scenes / tokens
             Scene,
             SceneGrid,
@@ -80,9 +95,41 @@ impl Modify for SecurityAddon {
             SceneUpdatePayload,
             SceneDeletePayload,
             SceneActivatePayload,
+            SceneAssignPayload,
+            ScenePoint,
+            SceneWall,
+            SceneDoor,
+            SceneLight,
+            SceneLevel,
+            SceneStairs,
+            TerrainCell,
+            TerrainKind,
             Token,
+            TokenAura,
+            AuraVisibility,
             TokenMovePayload,
             FileRef,
+            // Room roles
+            GameMasterSetPayload,
+            // Character sheets
+            CharacterSheet,
+            CharacterSheetUpsertPayload,
+            CharacterSheetDeletePayload,
+            CharacterSheetSecretsPayload,
+            SheetTemplate,
+            SheetSection,
+            SheetField,
+            SheetFieldKind,
+            SheetTemplateUpsertPayload,
+            // Compendium
+            CompendiumSource,
+            CompendiumImportPayload,
+            CompendiumRemovePayload,
+            // Handouts
+            HandoutCrop,
+            Handout,
+            HandoutShowPayload,
+            HandoutDeletePayload,
             // Files
             FileAnnouncePayload,
             FileRequestPayload,
@@ -99,6 +146,10 @@ impl Modify for SecurityAddon {
             // Board tools
             BoardPointerPayload,
             AttentionPingPayload,
+            BoardCameraPayload,
+            BoardCameraMode,
+            BoardFollowPayload,
+            BoardViewportPayload,
             WorldPoint,
         ),
     ),
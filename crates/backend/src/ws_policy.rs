@@ -2,7 +2,7 @@ use axum::extract::ws::{CloseFrame, Utf8Bytes, close_code};
 use std::collections::VecDeque;
 use std::time::{Duration, Instant};
 
-pub(crate) const MAX_INBOUND_MESSAGE_SIZE_BYTES: usize = 1024 * 1024;
+pub(crate) const MAX_INBOUND_MESSAGE_SIZE_BYTES: usize = shared::events::MAX_WIRE_MESSAGE_BYTES;
 
 const GENERAL_MESSAGE_LIMIT: usize = 60;
 const MOUSE_MESSAGE_LIMIT: usize = 1200;
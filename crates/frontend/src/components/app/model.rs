@@ -6,6 +6,10 @@ pub enum ActiveWindow {
     Notes,
     Scenes,
     Tokens,
+    Characters,
+    Compendium,
+    Encounters,
+    Handouts,
     Settings,
     Statistics,
     Voting,
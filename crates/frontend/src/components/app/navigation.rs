@@ -1,3 +1,4 @@
+use crate::components::notes::collab::NoteEditSession;
 use crate::components::statistics::StateEvent;
 use crate::components::voting::VotingState;
 use crate::components::websocket::{
@@ -8,10 +9,11 @@ use crate::config;
 use crate::utils::{auth, token_refresh};
 use leptos::prelude::*;
 use shared::events::{
-    AttentionPingPayload, ChatMessagePayload, DirectMessagePayload, NotePayload, Scene,
-    voting::VotingResultPayload,
+    AttentionPingPayload, BoardCameraPayload, BoardViewportPayload, CharacterSheet,
+    ChatMessagePayload, CompendiumSource, DirectMessagePayload, Handout, NotePayload, Scene,
+    SheetTemplate, voting::VotingResultPayload,
 };
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 use super::AppState;
 
@@ -74,6 +76,15 @@ pub struct RoomSelectedCallbackArgs {
     pub state_events: RwSignal<Vec<StateEvent>>,
     pub scenes: RwSignal<Vec<Scene>>,
     pub active_scene_id: RwSignal<Option<String>>,
+    pub scene_assignments: RwSignal<BTreeMap<String, String>>,
+    pub game_master: RwSignal<Option<String>>,
+    pub character_sheets: RwSignal<Vec<CharacterSheet>>,
+    pub sheet_secrets: RwSignal<BTreeMap<String, BTreeMap<String, String>>>,
+    pub sheet_templates: RwSignal<Vec<SheetTemplate>>,
+    pub compendium_sources: RwSignal<Vec<CompendiumSource>>,
+    pub handouts: RwSignal<Vec<Handout>>,
+    /// Handouts addressed to chosen players, kept on this device.
+    pub private_handouts: RwSignal<Vec<Handout>>,
     pub conflict_signal: RwSignal<Option<SyncConflict>>,
     pub votings: RwSignal<HashMap<String, VotingState>>,
     pub voting_results: RwSignal<HashMap<String, VotingResultPayload>>,
@@ -85,7 +96,12 @@ pub struct RoomSelectedCallbackArgs {
     pub conflict_resolution_handle: ConflictResolutionHandle,
     pub board_pointers: RwSignal<HashSet<String>>,
     pub attention_pings: RwSignal<Vec<AttentionPingPayload>>,
+    pub board_camera: RwSignal<Option<BoardCameraPayload>>,
+    pub camera_followers: RwSignal<HashSet<String>>,
+    pub board_viewports: RwSignal<HashMap<String, BoardViewportPayload>>,
+    pub presented_handout: RwSignal<Option<String>>,
     pub direct_messages: RwSignal<Vec<DirectMessagePayload>>,
+    pub note_edit_session: RwSignal<Option<NoteEditSession>>,
 }
 
 /// Создает callback для выбора комнаты и подключения к WebSocket
@@ -107,6 +123,14 @@ pub fn create_room_selected_callback(args: RoomSelectedCallbackArgs) -> impl Fn(
         state_events,
         scenes,
         active_scene_id,
+        scene_assignments,
+        game_master,
+        character_sheets,
+        sheet_secrets,
+        sheet_templates,
+        compendium_sources,
+        handouts,
+        private_handouts,
         conflict_signal,
         votings,
         voting_results,
@@ -118,7 +142,12 @@ pub fn create_room_selected_callback(args: RoomSelectedCallbackArgs) -> impl Fn(
         conflict_resolution_handle,
         board_pointers,
         attention_pings,
+        board_camera,
+        camera_followers,
+        board_viewports,
+        presented_handout,
         direct_messages,
+        note_edit_session,
     } = args;
     let handle_clone = conflict_resolution_handle.clone();
     move |selected_room_id: String| {
@@ -144,6 +173,14 @@ pub fn create_room_selected_callback(args: RoomSelectedCallbackArgs) -> impl Fn(
             state_events,
             scenes_signal: scenes,
             active_scene_id_signal: active_scene_id,
+            scene_assignments_signal: scene_assignments,
+            game_master_signal: game_master,
+            character_sheets_signal: character_sheets,
+            sheet_secrets_signal: sheet_secrets,
+            sheet_templates_signal: sheet_templates,
+            compendium_sources_signal: compendium_sources,
+            handouts_signal: handouts,
+            private_handouts_signal: private_handouts,
             conflict_signal,
             votings,
             voting_results,
@@ -155,7 +192,12 @@ pub fn create_room_selected_callback(args: RoomSelectedCallbackArgs) -> impl Fn(
             conflict_resolution_handle: handle_clone.clone(),
             board_pointers,
             attention_pings,
+            board_camera,
+            camera_followers,
+            board_viewports,
+            presented_handout,
             direct_messages,
+            note_edit_session,
         });
     }
 }
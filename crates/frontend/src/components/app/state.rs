@@ -1,15 +1,28 @@
+use super::super::characters::{CharactersWindow, model::sheet_for_token};
 use super::super::chat::ChatWindow;
+use super::super::compendium::{
+    CompendiumWindow,
+    deploy::MonsterDeployer,
+    model::{CompendiumMonster, MonsterPlacement},
+};
 use super::super::conflict_resolver::ConflictResolver;
+use super::super::encounters::{
+    EncountersWindow,
+    model::{EncounterCreature, add_creature},
+};
+use super::super::handouts::{HandoutOverlay, HandoutsWindow};
 use super::super::language_selector::LanguageSelector;
 use super::super::login::LoginForm;
-use super::super::notes::NotesWindow;
+use super::super::notes::{NotesWindow, collab::NoteEditSession};
 use super::super::register::RegisterForm;
 use super::super::room_selector::RoomSelector;
-use super::super::scene_board::SceneBoard;
+use super::super::scene_board::{SceneBoard, model::BoardFocus};
 use super::super::scenes::ScenesWindow;
 use super::super::settings::{
-    Settings, load_inactive_scene_contents_visibility, load_workspace_hint_visibility,
-    save_inactive_scene_contents_visibility, save_workspace_hint_visibility,
+    Settings, load_board_renderer, load_inactive_scene_contents_visibility,
+    load_render_stats_visibility, load_workspace_hint_visibility, save_board_renderer,
+    save_inactive_scene_contents_visibility, save_render_stats_visibility,
+    save_workspace_hint_visibility,
 };
 use super::super::side_menu::SideMenu;
 use super::super::statistics::StatisticsWindow;
@@ -17,6 +30,7 @@ use super::super::tokens::TokensWindow;
 use super::super::websocket::{
     ConflictResolutionHandle, CursorSignals, FileTransferState, StoredNoteBucket,
     StoredTokenLibraryItem, SyncConflict, WsSender, delete_state, load_notes,
+    load_handout_sources, load_private_handouts, load_sheet_secrets,
 };
 use super::model::ActiveWindow;
 use super::navigation::create_room_selected_callback;
@@ -31,9 +45,12 @@ use leptos::prelude::*;
 use leptos::task::spawn_local;
 use leptos::wasm_bindgen::JsCast;
 use shared::events::{
-    AttentionPingPayload, ChatMessagePayload, DirectMessagePayload, NotePayload, Scene,
+    AttentionPingPayload, BoardCameraPayload, BoardViewportPayload, CharacterSheet,
+    ChatMessagePayload, CompendiumSource, DirectMessagePayload, FileRef, Handout, NotePayload,
+    Scene, SheetTemplate,
 };
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use uuid::Uuid;
 
 #[component]
 pub fn App() -> impl IntoView {
@@ -72,11 +89,26 @@ pub fn App() -> impl IntoView {
     let state_events = RwSignal::new(Vec::<StateEvent>::new());
     let scenes = RwSignal::new(Vec::<Scene>::new());
     let active_scene_id = RwSignal::new(Option::<String>::None);
+    let scene_assignments = RwSignal::new(BTreeMap::<String, String>::new());
+    let game_master = RwSignal::new(Option::<String>::None);
+    let character_sheets = RwSignal::new(Vec::<CharacterSheet>::new());
+    let sheet_secrets = RwSignal::new(BTreeMap::<String, BTreeMap<String, String>>::new());
+    let sheet_templates = RwSignal::new(Vec::<SheetTemplate>::new());
+    let selected_character_sheet_id = RwSignal::new(Option::<String>::None);
+    let compendium_sources = RwSignal::new(Vec::<CompendiumSource>::new());
+    let dragging_compendium_monster = RwSignal::new(Option::<CompendiumMonster>::None);
+    let encounter_draft = RwSignal::new(Vec::<EncounterCreature>::new());
+    let handouts = RwSignal::new(Vec::<Handout>::new());
+    let private_handouts = RwSignal::new(Vec::<Handout>::new());
+    let handout_sources = RwSignal::new(HashMap::<String, FileRef>::new());
+    let presented_handout = RwSignal::new(Option::<String>::None);
     let token_library_items = RwSignal::new(Vec::<StoredTokenLibraryItem>::new());
     let dragging_library_token_id = RwSignal::new(Option::<String>::None);
     let show_workspace_hint = RwSignal::new(load_workspace_hint_visibility().unwrap_or(true));
     let show_inactive_scene_contents =
         RwSignal::new(load_inactive_scene_contents_visibility().unwrap_or(false));
+    let board_renderer = RwSignal::new(load_board_renderer().unwrap_or_default());
+    let show_render_stats = RwSignal::new(load_render_stats_visibility().unwrap_or(false));
     let voting_results =
         RwSignal::new(HashMap::<String, shared::events::voting::VotingResultPayload>::new());
     let conflict_signal = RwSignal::new(Option::<SyncConflict>::None);
@@ -91,7 +123,12 @@ pub fn App() -> impl IntoView {
     let conflict_resolution_handle = ConflictResolutionHandle::new();
     let board_pointers = RwSignal::new(HashSet::<String>::new());
     let attention_pings = RwSignal::new(Vec::<AttentionPingPayload>::new());
+    let board_camera = RwSignal::new(Option::<BoardCameraPayload>::None);
+    let camera_followers = RwSignal::new(HashSet::<String>::new());
+    let board_viewports = RwSignal::new(HashMap::<String, BoardViewportPayload>::new());
+    let board_focus = RwSignal::new(Option::<BoardFocus>::None);
     let direct_messages = RwSignal::new(Vec::<DirectMessagePayload>::new());
+    let note_edit_session = RwSignal::new(Option::<NoteEditSession>::None);
 
     let clear_room_local_state = {
         let handle = conflict_resolution_handle.clone();
@@ -164,6 +201,14 @@ pub fn App() -> impl IntoView {
             state_events,
             scenes,
             active_scene_id,
+            scene_assignments,
+            game_master,
+            character_sheets,
+            sheet_secrets,
+            sheet_templates,
+            compendium_sources,
+            handouts,
+            private_handouts,
             conflict_signal,
             votings,
             voting_results,
@@ -175,7 +220,12 @@ pub fn App() -> impl IntoView {
             conflict_resolution_handle: conflict_resolution_handle.clone(),
             board_pointers,
             attention_pings,
+            board_camera,
+            camera_followers,
+            board_viewports,
+            presented_handout,
             direct_messages,
+            note_edit_session,
         },
     ));
 
@@ -203,6 +253,12 @@ pub fn App() -> impl IntoView {
         let _ = room_id.get();
         token_library_items.set(Vec::new());
         dragging_library_token_id.set(None);
+        dragging_compendium_monster.set(None);
+        encounter_draft.set(Vec::new());
+        presented_handout.set(None);
+        sheet_secrets.set(BTreeMap::new());
+        private_handouts.set(Vec::new());
+        handout_sources.set(HashMap::new());
         public_notes.set(Vec::new());
         private_notes.set(Vec::new());
         direct_notes.set(Vec::new());
@@ -211,6 +267,7 @@ pub fn App() -> impl IntoView {
         direct_note_recipients_request_id.set(None);
     });
 
+    let file_transfer_for_handouts = file_transfer.clone();
     Effect::new(move |_| {
         if vm.app_state.get() != AppState::Connected {
             return;
@@ -222,6 +279,7 @@ pub fn App() -> impl IntoView {
             return;
         }
 
+        let file_transfer = file_transfer_for_handouts.clone();
         spawn_local(async move {
             if let Ok(notes) =
                 load_notes(&current_room, &current_user, StoredNoteBucket::Private).await
@@ -233,6 +291,22 @@ pub fn App() -> impl IntoView {
             {
                 direct_notes.set(notes);
             }
+            if let Ok(secrets) = load_sheet_secrets(&current_room, &current_user).await {
+                sheet_secrets.set(secrets);
+            }
+            if let Ok(handouts) = load_private_handouts(&current_room, &current_user).await {
+                let game_master = game_master.get_untracked();
+                for handout in &handouts {
+                    let mut audience = handout.recipients.clone();
+                    audience.push(current_user.clone());
+                    audience.extend(game_master.clone());
+                    file_transfer.restrict_file(&handout.file.hash, &audience);
+                }
+                private_handouts.set(handouts);
+            }
+            if let Ok(sources) = load_handout_sources(&current_room).await {
+                handout_sources.set(sources);
+            }
         });
     });
 
@@ -244,6 +318,14 @@ pub fn App() -> impl IntoView {
         save_inactive_scene_contents_visibility(show_inactive_scene_contents.get());
     });
 
+    Effect::new(move |_| {
+        save_board_renderer(board_renderer.get());
+    });
+
+    Effect::new(move |_| {
+        save_render_stats_visibility(show_render_stats.get());
+    });
+
     // Keyboard shortcut handler - delegates to ViewModel
     let on_keydown = move |ev: web_sys::KeyboardEvent| {
         if vm.app_state.get() != AppState::Connected {
@@ -340,22 +422,55 @@ pub fn App() -> impl IntoView {
                                 room_id=room_id
                                 scenes=scenes
                                 active_scene_id=active_scene_id
+                                scene_assignments=scene_assignments
                                 public_notes=public_notes
                                 private_notes=private_notes
                                 direct_notes=direct_notes
                                 show_workspace_hint=show_workspace_hint
                                 show_inactive_scene_contents=show_inactive_scene_contents
+                                board_renderer=board_renderer
+                                show_render_stats=show_render_stats
                                 token_library_items=token_library_items
                                 dragging_library_token_id=dragging_library_token_id
+                                dragging_compendium_monster=dragging_compendium_monster
+                                on_compendium_monster_drop={
+                                    let deployer = MonsterDeployer {
+                                        scenes,
+                                        game_master,
+                                        file_transfer: file_transfer.clone(),
+                                        ws_sender,
+                                        username,
+                                    };
+                                    Callback::new(move |placement: MonsterPlacement| {
+                                        let deployer = deployer.clone();
+                                        spawn_local(async move {
+                                            if let Err(error) = deployer.deploy(vec![placement]).await {
+                                                leptos::logging::log!("Failed to place monster: {}", error);
+                                            }
+                                        });
+                                    })
+                                }
                                 cursors=cursors
                                 set_cursors=set_cursors
                                 file_transfer=file_transfer.clone()
                                 ws_sender=ws_sender
                                 username=username
+                                game_master=game_master
+                                on_open_character_sheet=Callback::new(move |token_id: String| {
+                                    let linked_sheet_id = character_sheets.with_untracked(|sheets| {
+                                        sheet_for_token(sheets, &token_id).map(|sheet| sheet.id.clone())
+                                    });
+                                    selected_character_sheet_id.set(linked_sheet_id);
+                                    vm.open_characters();
+                                })
                                 config=cfg.get_value()
                                 theme=theme.get_value()
                                 board_pointers=board_pointers
                                 attention_pings=attention_pings
+                                board_camera=board_camera
+                                camera_followers=camera_followers
+                                board_viewports=board_viewports
+                                board_focus=board_focus
                                 direct_messages=direct_messages
                             />
 
@@ -369,6 +484,10 @@ pub fn App() -> impl IntoView {
                                 on_notes_open=Callback::new(move |_| vm.open_notes())
                                 on_scenes_open=Callback::new(move |_| vm.open_scenes())
                                 on_tokens_open=Callback::new(move |_| vm.open_tokens())
+                                on_characters_open=Callback::new(move |_| vm.open_characters())
+                                on_compendium_open=Callback::new(move |_| vm.open_compendium())
+                                on_encounters_open=Callback::new(move |_| vm.open_encounters())
+                                on_handouts_open=Callback::new(move |_| vm.open_handouts())
                                 on_settings_open=Callback::new(move |_| vm.open_settings())
                                 on_statistics_open=Callback::new(move |_| vm.open_statistics())
                                 on_voting_open=Callback::new(move |_| vm.open_voting())
@@ -402,8 +521,13 @@ pub fn App() -> impl IntoView {
                                 direct_note_recipients=direct_note_recipients
                                 direct_note_recipients_cache_updated_at_ms=direct_note_recipients_cache_updated_at_ms
                                 direct_note_recipients_request_id=direct_note_recipients_request_id
+                                scenes=scenes
+                                active_scene_id=active_scene_id
+                                note_edit_session=note_edit_session
                                 is_active=Signal::derive(move || vm.active_window.get() == ActiveWindow::Notes)
                                 on_focus=Callback::new(move |_| vm.active_window.set(ActiveWindow::Notes))
+                                on_focus_board=Callback::new(move |focus: BoardFocus| board_focus.set(Some(focus)))
+                                file_transfer=file_transfer.clone()
                                 theme=theme.get_value()
                             />
 
@@ -411,8 +535,13 @@ pub fn App() -> impl IntoView {
                                 is_open=vm.is_settings_open
                                 show_workspace_hint=show_workspace_hint
                                 show_inactive_scene_contents=show_inactive_scene_contents
+                                board_renderer=board_renderer
+                                show_render_stats=show_render_stats
                                 on_clear_room_local_state=clear_room_local_state
                                 current_room=room_id
+                                game_master=game_master
+                                username=username
+                                ws_sender=ws_sender
                                 theme=theme.get_value()
                             />
 
@@ -420,6 +549,9 @@ pub fn App() -> impl IntoView {
                                 is_open=vm.is_scenes_open
                                 scenes=scenes
                                 active_scene_id=active_scene_id
+                                scene_assignments=scene_assignments
+                                game_master=game_master
+                                online_users=direct_note_recipients
                                 file_transfer=file_transfer.clone()
                                 ws_sender=ws_sender
                                 username=username
@@ -443,6 +575,80 @@ pub fn App() -> impl IntoView {
                                 theme=theme.get_value()
                             />
 
+                            <CharactersWindow
+                                is_open=vm.is_characters_open
+                                character_sheets=character_sheets
+                                sheet_secrets=sheet_secrets
+                                sheet_templates=sheet_templates
+                                scenes=scenes
+                                game_master=game_master
+                                selected_sheet_id=selected_character_sheet_id
+                                ws_sender=ws_sender
+                                username=username
+                                is_active=Signal::derive(move || vm.active_window.get() == ActiveWindow::Characters)
+                                on_focus=Callback::new(move |_| vm.active_window.set(ActiveWindow::Characters))
+                                theme=theme.get_value()
+                            />
+
+                            <CompendiumWindow
+                                is_open=vm.is_compendium_open
+                                compendium_sources=compendium_sources
+                                game_master=game_master
+                                file_transfer=file_transfer.clone()
+                                ws_sender=ws_sender
+                                username=username
+                                on_start_monster_drag=Callback::new(move |monster: CompendiumMonster| {
+                                    dragging_compendium_monster.set(Some(monster));
+                                })
+                                on_add_to_encounter=Callback::new(move |monster: CompendiumMonster| {
+                                    encounter_draft.update(|creatures| {
+                                        add_creature(
+                                            creatures,
+                                            EncounterCreature::from_monster(Uuid::new_v4().to_string(), monster),
+                                        )
+                                    });
+                                })
+                                is_active=Signal::derive(move || vm.active_window.get() == ActiveWindow::Compendium)
+                                on_focus=Callback::new(move |_| vm.active_window.set(ActiveWindow::Compendium))
+                                theme=theme.get_value()
+                            />
+
+                            <EncountersWindow
+                                is_open=vm.is_encounters_open
+                                room_id=room_id
+                                draft=encounter_draft
+                                scenes=scenes
+                                active_scene_id=active_scene_id
+                                character_sheets=character_sheets
+                                token_library_items=token_library_items
+                                game_master=game_master
+                                file_transfer=file_transfer.clone()
+                                ws_sender=ws_sender
+                                username=username
+                                is_active=Signal::derive(move || vm.active_window.get() == ActiveWindow::Encounters)
+                                on_focus=Callback::new(move |_| vm.active_window.set(ActiveWindow::Encounters))
+                                theme=theme.get_value()
+                            />
+
+                            <HandoutsWindow
+                                is_open=vm.is_handouts_open
+                                handouts=handouts
+                                private_handouts=private_handouts
+                                handout_sources=handout_sources
+                                game_master=game_master
+                                presented_handout=presented_handout
+                                online_users=direct_note_recipients
+                                online_users_cache_updated_at_ms=direct_note_recipients_cache_updated_at_ms
+                                online_users_request_id=direct_note_recipients_request_id
+                                file_transfer=file_transfer.clone()
+                                ws_sender=ws_sender
+                                username=username
+                                room_id=room_id
+                                is_active=Signal::derive(move || vm.active_window.get() == ActiveWindow::Handouts)
+                                on_focus=Callback::new(move |_| vm.active_window.set(ActiveWindow::Handouts))
+                                theme=theme.get_value()
+                            />
+
                             <StatisticsWindow
                                 is_open=vm.is_statistics_open
                                 events=state_events
@@ -529,6 +735,18 @@ pub fn App() -> impl IntoView {
                                 }
                                 theme=theme.get_value()
                             />
+
+                            <HandoutOverlay
+                                presented_handout=presented_handout
+                                handouts=handouts
+                                private_handouts=private_handouts
+                                handout_sources=handout_sources
+                                game_master=game_master
+                                file_transfer=file_transfer.clone()
+                                ws_sender=ws_sender
+                                username=username
+                                theme=theme.get_value()
+                            />
                         </div>
                     }.into_any(),
                 }}
@@ -15,6 +15,10 @@ pub struct AppViewModel {
     pub is_notes_open: RwSignal<bool>,
     pub is_scenes_open: RwSignal<bool>,
     pub is_tokens_open: RwSignal<bool>,
+    pub is_characters_open: RwSignal<bool>,
+    pub is_compendium_open: RwSignal<bool>,
+    pub is_encounters_open: RwSignal<bool>,
+    pub is_handouts_open: RwSignal<bool>,
     pub is_settings_open: RwSignal<bool>,
     pub is_statistics_open: RwSignal<bool>,
     pub is_voting_open: RwSignal<bool>,
@@ -38,6 +42,10 @@ impl AppViewModel {
             is_notes_open: RwSignal::new(false),
             is_scenes_open: RwSignal::new(false),
             is_tokens_open: RwSignal::new(false),
+            is_characters_open: RwSignal::new(false),
+            is_compendium_open: RwSignal::new(false),
+            is_encounters_open: RwSignal::new(false),
+            is_handouts_open: RwSignal::new(false),
             is_settings_open: RwSignal::new(false),
             is_statistics_open: RwSignal::new(false),
             is_voting_open: RwSignal::new(false),
@@ -73,6 +81,26 @@ impl AppViewModel {
         self.active_window.set(ActiveWindow::Tokens);
     }
 
+    pub fn open_characters(&self) {
+        self.is_characters_open.set(true);
+        self.active_window.set(ActiveWindow::Characters);
+    }
+
+    pub fn open_compendium(&self) {
+        self.is_compendium_open.set(true);
+        self.active_window.set(ActiveWindow::Compendium);
+    }
+
+    pub fn open_encounters(&self) {
+        self.is_encounters_open.set(true);
+        self.active_window.set(ActiveWindow::Encounters);
+    }
+
+    pub fn open_handouts(&self) {
+        self.is_handouts_open.set(true);
+        self.active_window.set(ActiveWindow::Handouts);
+    }
+
     pub fn open_settings(&self) {
         self.is_settings_open.set(true);
         self.active_window.set(ActiveWindow::Settings);
@@ -97,6 +125,10 @@ impl AppViewModel {
             ActiveWindow::Notes => self.is_notes_open.set(false),
             ActiveWindow::Scenes => self.is_scenes_open.set(false),
             ActiveWindow::Tokens => self.is_tokens_open.set(false),
+            ActiveWindow::Characters => self.is_characters_open.set(false),
+            ActiveWindow::Compendium => self.is_compendium_open.set(false),
+            ActiveWindow::Encounters => self.is_encounters_open.set(false),
+            ActiveWindow::Handouts => self.is_handouts_open.set(false),
             ActiveWindow::Settings => self.is_settings_open.set(false),
             ActiveWindow::Voting => self.is_voting_open.set(false),
             ActiveWindow::Statistics => self.is_statistics_open.set(false),
@@ -128,6 +160,22 @@ impl AppViewModel {
                 self.open_tokens();
                 true
             }
+            "KeyH" => {
+                self.open_characters();
+                true
+            }
+            "KeyK" => {
+                self.open_compendium();
+                true
+            }
+            "KeyE" => {
+                self.open_encounters();
+                true
+            }
+            "KeyO" => {
+                self.open_handouts();
+                true
+            }
             "KeyS" => {
                 self.open_settings();
                 true
@@ -224,6 +272,54 @@ mod tests {
         });
     }
 
+    #[test]
+    fn handle_hotkey_key_h_opens_characters() {
+        let owner = Owner::new();
+        owner.with(|| {
+            let vm = make_vm();
+            let handled = vm.handle_hotkey("KeyH");
+            assert!(handled);
+            assert!(vm.is_characters_open.get_untracked());
+            assert_eq!(vm.active_window.get_untracked(), ActiveWindow::Characters);
+        });
+    }
+
+    #[test]
+    fn handle_hotkey_key_k_opens_compendium() {
+        let owner = Owner::new();
+        owner.with(|| {
+            let vm = make_vm();
+            let handled = vm.handle_hotkey("KeyK");
+            assert!(handled);
+            assert!(vm.is_compendium_open.get_untracked());
+            assert_eq!(vm.active_window.get_untracked(), ActiveWindow::Compendium);
+        });
+    }
+
+    #[test]
+    fn handle_hotkey_key_e_opens_encounters() {
+        let owner = Owner::new();
+        owner.with(|| {
+            let vm = make_vm();
+            let handled = vm.handle_hotkey("KeyE");
+            assert!(handled);
+            assert!(vm.is_encounters_open.get_untracked());
+            assert_eq!(vm.active_window.get_untracked(), ActiveWindow::Encounters);
+        });
+    }
+
+    #[test]
+    fn handle_hotkey_key_o_opens_handouts() {
+        let owner = Owner::new();
+        owner.with(|| {
+            let vm = make_vm();
+            let handled = vm.handle_hotkey("KeyO");
+            assert!(handled);
+            assert!(vm.is_handouts_open.get_untracked());
+            assert_eq!(vm.active_window.get_untracked(), ActiveWindow::Handouts);
+        });
+    }
+
     #[test]
     fn handle_hotkey_key_n_opens_notes() {
         let owner = Owner::new();
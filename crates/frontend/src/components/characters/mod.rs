@@ -0,0 +1,5 @@
+pub mod model;
+mod view;
+mod view_model;
+
+pub use view::CharactersWindow;
@@ -0,0 +1,802 @@
+// Pure types and logic for character sheets: bundled templates, template
+// validation, and the formula engine behind computed fields and roll buttons.
+// No signals, no Leptos, no web_sys.
+
+use shared::events::{
+    CharacterSheet, MAX_SHEET_VALUE_CHARS, SheetField, SheetFieldKind, SheetTemplate,
+};
+use std::collections::{BTreeMap, HashSet};
+
+// --- Constants ---
+
+pub const DEFAULT_TEMPLATE_ID: &str = "dnd5e";
+pub const TEMPLATE_FILE_ACCEPT: &str = "application/json,.json";
+pub const MAX_FIELD_VALUE_LEN: usize = MAX_SHEET_VALUE_CHARS;
+const BUNDLED_TEMPLATE_JSON: [&str; 1] = [include_str!("templates/dnd5e.json")];
+/// How deep computed fields may reference other computed fields.
+const MAX_FORMULA_DEPTH: usize = 16;
+const MAX_DICE_COUNT: u32 = 100;
+const MAX_DICE_SIDES: u32 = 1000;
+/// Chat messages are capped at 500 characters by the protocol.
+pub const MAX_CHAT_MESSAGE_LEN: usize = 500;
+
+// --- Types ---
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FormulaError {
+    Syntax,
+    UnknownField(String),
+    UnknownFunction(String),
+    DiceNotAllowed,
+    DiceOutOfRange,
+    DivisionByZero,
+    TooDeep,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateError {
+    InvalidJson,
+    EmptyId,
+    InvalidKey(String),
+    DuplicateKey(String),
+    MissingFormula(String),
+    InvalidFormula(String),
+}
+
+/// Result of a roll button: the total and a human-readable breakdown such as
+/// `1d20[14] + 3`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RollOutcome {
+    pub total: f64,
+    pub breakdown: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Number(f64),
+    Field(String),
+    Dice { count: u32, sides: u32 },
+    Neg(Box<Expr>),
+    Group(Box<Expr>),
+    Binary(Box<Expr>, BinaryOp, Box<Expr>),
+    Call(String, Vec<Expr>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Tok {
+    Number(f64),
+    Ident(String),
+    Dice(u32, u32),
+    Op(char),
+    LParen,
+    RParen,
+    Comma,
+}
+
+// --- Templates ---
+
+/// Templates shipped with the client.
+pub fn bundled_templates() -> Vec<SheetTemplate> {
+    BUNDLED_TEMPLATE_JSON
+        .iter()
+        .filter_map(|raw| serde_json::from_str(raw).ok())
+        .collect()
+}
+
+/// Bundled templates followed by room templates; a room template with the
+/// same id replaces the bundled one.
+pub fn available_templates(room_templates: &[SheetTemplate]) -> Vec<SheetTemplate> {
+    let mut templates: Vec<SheetTemplate> = bundled_templates()
+        .into_iter()
+        .filter(|bundled| !room_templates.iter().any(|room| room.id == bundled.id))
+        .collect();
+    templates.extend(room_templates.iter().cloned());
+    templates
+}
+
+pub fn find_template<'a>(templates: &'a [SheetTemplate], id: &str) -> Option<&'a SheetTemplate> {
+    templates.iter().find(|template| template.id == id)
+}
+
+pub fn template_field<'a>(template: &'a SheetTemplate, key: &str) -> Option<&'a SheetField> {
+    template
+        .sections
+        .iter()
+        .flat_map(|section| section.fields.iter())
+        .find(|field| field.key == key)
+}
+
+fn is_valid_field_key(key: &str) -> bool {
+    !key.is_empty()
+        && key.len() <= 64
+        && key
+            .chars()
+            .all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit() || ch == '_')
+        && key.starts_with(|ch: char| ch.is_ascii_lowercase())
+}
+
+/// Parses an uploaded template and checks keys and formulas.
+pub fn parse_template_json(raw: &str) -> Result<SheetTemplate, TemplateError> {
+    let template: SheetTemplate =
+        serde_json::from_str(raw).map_err(|_| TemplateError::InvalidJson)?;
+    validate_template(&template)?;
+    Ok(template)
+}
+
+pub fn validate_template(template: &SheetTemplate) -> Result<(), TemplateError> {
+    if template.id.trim().is_empty() || template.name.trim().is_empty() {
+        return Err(TemplateError::EmptyId);
+    }
+
+    let mut keys = HashSet::new();
+    for field in template.sections.iter().flat_map(|s| s.fields.iter()) {
+        if !is_valid_field_key(&field.key) {
+            return Err(TemplateError::InvalidKey(field.key.clone()));
+        }
+        if !keys.insert(field.key.as_str()) {
+            return Err(TemplateError::DuplicateKey(field.key.clone()));
+        }
+        if matches!(field.kind, SheetFieldKind::Computed | SheetFieldKind::Roll) {
+            let Some(formula) = field.formula.as_deref() else {
+                return Err(TemplateError::MissingFormula(field.key.clone()));
+            };
+            if parse_formula(formula).is_err() {
+                return Err(TemplateError::InvalidFormula(field.key.clone()));
+            }
+        }
+    }
+    Ok(())
+}
+
+// --- Field values ---
+
+/// Stored value of an input field, falling back to the template default.
+pub fn raw_field_value(field: &SheetField, values: &BTreeMap<String, String>) -> String {
+    values
+        .get(&field.key)
+        .cloned()
+        .or_else(|| field.default.clone())
+        .unwrap_or_default()
+}
+
+pub fn is_checked(raw: &str) -> bool {
+    matches!(raw.trim(), "true" | "1")
+}
+
+/// Fields shown to a viewer; private fields only reach the owner and the GM.
+pub fn field_visible(field: &SheetField, shows_private: bool) -> bool {
+    !field.private || shows_private
+}
+
+/// Splits sheet values into what the room state may hold and the private
+/// field values that only travel to the owner and the GM.
+pub fn split_private_values(
+    template: Option<&SheetTemplate>,
+    values: &BTreeMap<String, String>,
+) -> (BTreeMap<String, String>, BTreeMap<String, String>) {
+    values.clone().into_iter().partition(|(key, _)| {
+        !template
+            .and_then(|template| template_field(template, key))
+            .is_some_and(|field| field.private)
+    })
+}
+
+/// Numeric value of a field as seen by formulas: text is parsed as a number
+/// (empty or non-numeric text counts as 0), checkboxes are 1 or 0 and
+/// computed fields are evaluated.
+pub fn field_number(
+    template: &SheetTemplate,
+    values: &BTreeMap<String, String>,
+    key: &str,
+) -> Result<f64, FormulaError> {
+    field_number_at_depth(template, values, key, 0, &mut BTreeMap::new())
+}
+
+/// `known` remembers the fields already evaluated during one evaluation, so
+/// fields referenced many times are computed once instead of once per path.
+fn field_number_at_depth(
+    template: &SheetTemplate,
+    values: &BTreeMap<String, String>,
+    key: &str,
+    depth: usize,
+    known: &mut BTreeMap<String, f64>,
+) -> Result<f64, FormulaError> {
+    if let Some(value) = known.get(key) {
+        return Ok(*value);
+    }
+    if depth > MAX_FORMULA_DEPTH {
+        return Err(FormulaError::TooDeep);
+    }
+    let Some(field) = template_field(template, key) else {
+        return Err(FormulaError::UnknownField(key.to_string()));
+    };
+
+    let value = match field.kind {
+        SheetFieldKind::Computed => {
+            let expr = parse_formula(field.formula.as_deref().unwrap_or_default())?;
+            let mut resolve =
+                |name: &str| field_number_at_depth(template, values, name, depth + 1, known);
+            Evaluator {
+                resolve: &mut resolve,
+                roll_die: None,
+            }
+            .evaluate(&expr)?
+            .0
+        }
+        SheetFieldKind::Checkbox => {
+            if is_checked(&raw_field_value(field, values)) {
+                1.0
+            } else {
+                0.0
+            }
+        }
+        SheetFieldKind::Roll => return Err(FormulaError::UnknownField(key.to_string())),
+        SheetFieldKind::Text | SheetFieldKind::TextArea | SheetFieldKind::Number => {
+            raw_field_value(field, values)
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .filter(|value| value.is_finite())
+                .unwrap_or(0.0)
+        }
+    };
+    known.insert(key.to_string(), value);
+    Ok(value)
+}
+
+/// Rolls the formula of a roll field with `roll_die(sides)` supplying each
+/// die result in `1..=sides`.
+pub fn roll_field(
+    template: &SheetTemplate,
+    values: &BTreeMap<String, String>,
+    field: &SheetField,
+    roll_die: &mut dyn FnMut(u32) -> u32,
+) -> Result<RollOutcome, FormulaError> {
+    let expr = parse_formula(field.formula.as_deref().unwrap_or_default())?;
+    let mut known = BTreeMap::new();
+    let mut resolve = |name: &str| field_number_at_depth(template, values, name, 0, &mut known);
+    let (total, breakdown) = Evaluator {
+        resolve: &mut resolve,
+        roll_die: Some(roll_die),
+    }
+    .evaluate(&expr)?;
+    Ok(RollOutcome { total, breakdown })
+}
+
+/// Formats a number without a trailing `.0`; `signed` adds `+` to non-negative
+/// values.
+pub fn format_number(value: f64, signed: bool) -> String {
+    let text = if value.fract() == 0.0 {
+        format!("{}", value as i64)
+    } else {
+        format!("{value:.2}")
+    };
+    if signed && value >= 0.0 {
+        format!("+{text}")
+    } else {
+        text
+    }
+}
+
+pub fn roll_chat_message(sheet_name: &str, label: &str, outcome: &RollOutcome) -> String {
+    let total = format_number(outcome.total, false);
+    let message = format!("🎲 {sheet_name} — {label}: {} = {total}", outcome.breakdown);
+    if message.chars().count() <= MAX_CHAT_MESSAGE_LEN {
+        return message;
+    }
+    let short = format!("🎲 {sheet_name} — {label}: {total}");
+    short.chars().take(MAX_CHAT_MESSAGE_LEN).collect()
+}
+
+pub fn sheet_for_token<'a>(
+    sheets: &'a [CharacterSheet],
+    token_id: &str,
+) -> Option<&'a CharacterSheet> {
+    sheets
+        .iter()
+        .find(|sheet| sheet.token_id.as_deref() == Some(token_id))
+}
+
+// --- Formula engine ---
+
+fn tokenize(input: &str) -> Result<Vec<Tok>, FormulaError> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut index = 0;
+
+    let read_digits = |index: &mut usize| {
+        let start = *index;
+        while *index < chars.len() && (chars[*index].is_ascii_digit() || chars[*index] == '.') {
+            *index += 1;
+        }
+        chars[start..*index].iter().collect::<String>()
+    };
+
+    while index < chars.len() {
+        let ch = chars[index];
+        match ch {
+            ' ' | '\t' | '\n' => index += 1,
+            '+' | '-' | '*' | '/' => {
+                tokens.push(Tok::Op(ch));
+                index += 1;
+            }
+            '(' => {
+                tokens.push(Tok::LParen);
+                index += 1;
+            }
+            ')' => {
+                tokens.push(Tok::RParen);
+                index += 1;
+            }
+            ',' => {
+                tokens.push(Tok::Comma);
+                index += 1;
+            }
+            _ if ch.is_ascii_digit() => {
+                let number = read_digits(&mut index);
+                if index + 1 < chars.len()
+                    && chars[index] == 'd'
+                    && chars[index + 1].is_ascii_digit()
+                {
+                    index += 1;
+                    let sides = read_digits(&mut index);
+                    tokens.push(Tok::Dice(
+                        number.parse().map_err(|_| FormulaError::Syntax)?,
+                        sides.parse().map_err(|_| FormulaError::Syntax)?,
+                    ));
+                } else {
+                    tokens.push(Tok::Number(
+                        number.parse().map_err(|_| FormulaError::Syntax)?,
+                    ));
+                }
+            }
+            _ if ch.is_ascii_alphabetic() || ch == '_' => {
+                let start = index;
+                while index < chars.len()
+                    && (chars[index].is_ascii_alphanumeric() || chars[index] == '_')
+                {
+                    index += 1;
+                }
+                let ident: String = chars[start..index].iter().collect();
+                match ident.strip_prefix('d') {
+                    Some(sides)
+                        if !sides.is_empty() && sides.chars().all(|c| c.is_ascii_digit()) =>
+                    {
+                        tokens.push(Tok::Dice(
+                            1,
+                            sides.parse().map_err(|_| FormulaError::Syntax)?,
+                        ));
+                    }
+                    _ => tokens.push(Tok::Ident(ident)),
+                }
+            }
+            _ => return Err(FormulaError::Syntax),
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Tok>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Tok> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Tok> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn expression(&mut self) -> Result<Expr, FormulaError> {
+        let mut left = self.term()?;
+        while let Some(Tok::Op(op @ ('+' | '-'))) = self.peek().cloned() {
+            self.position += 1;
+            let right = self.term()?;
+            let op = if op == '+' {
+                BinaryOp::Add
+            } else {
+                BinaryOp::Sub
+            };
+            left = Expr::Binary(Box::new(left), op, Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn term(&mut self) -> Result<Expr, FormulaError> {
+        let mut left = self.unary()?;
+        while let Some(Tok::Op(op @ ('*' | '/'))) = self.peek().cloned() {
+            self.position += 1;
+            let right = self.unary()?;
+            let op = if op == '*' {
+                BinaryOp::Mul
+            } else {
+                BinaryOp::Div
+            };
+            left = Expr::Binary(Box::new(left), op, Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Expr, FormulaError> {
+        if self.peek() == Some(&Tok::Op('-')) {
+            self.position += 1;
+            return Ok(Expr::Neg(Box::new(self.unary()?)));
+        }
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<Expr, FormulaError> {
+        match self.next() {
+            Some(Tok::Number(value)) => Ok(Expr::Number(value)),
+            Some(Tok::Dice(count, sides)) => Ok(Expr::Dice { count, sides }),
+            Some(Tok::LParen) => {
+                let inner = self.expression()?;
+                match self.next() {
+                    Some(Tok::RParen) => Ok(Expr::Group(Box::new(inner))),
+                    _ => Err(FormulaError::Syntax),
+                }
+            }
+            Some(Tok::Ident(name)) => {
+                if self.peek() != Some(&Tok::LParen) {
+                    return Ok(Expr::Field(name));
+                }
+                self.position += 1;
+                let mut args = Vec::new();
+                if self.peek() == Some(&Tok::RParen) {
+                    self.position += 1;
+                    return Ok(Expr::Call(name, args));
+                }
+                loop {
+                    args.push(self.expression()?);
+                    match self.next() {
+                        Some(Tok::Comma) => continue,
+                        Some(Tok::RParen) => break,
+                        _ => return Err(FormulaError::Syntax),
+                    }
+                }
+                Ok(Expr::Call(name, args))
+            }
+            _ => Err(FormulaError::Syntax),
+        }
+    }
+}
+
+fn parse_formula(formula: &str) -> Result<Expr, FormulaError> {
+    let mut parser = Parser {
+        tokens: tokenize(formula)?,
+        position: 0,
+    };
+    let expr = parser.expression()?;
+    if parser.position != parser.tokens.len() {
+        return Err(FormulaError::Syntax);
+    }
+    Ok(expr)
+}
+
+/// Walks a parsed formula, producing its value and breakdown text. Dice are
+/// only allowed when a roller is supplied.
+struct Evaluator<'a> {
+    resolve: &'a mut dyn FnMut(&str) -> Result<f64, FormulaError>,
+    roll_die: Option<&'a mut dyn FnMut(u32) -> u32>,
+}
+
+impl Evaluator<'_> {
+    fn evaluate(&mut self, expr: &Expr) -> Result<(f64, String), FormulaError> {
+        match expr {
+            Expr::Number(value) => Ok((*value, format_number(*value, false))),
+            Expr::Field(name) => {
+                let value = (self.resolve)(name)?;
+                Ok((value, format_number(value, false)))
+            }
+            Expr::Dice { count, sides } => {
+                let Some(roll_die) = self.roll_die.as_mut() else {
+                    return Err(FormulaError::DiceNotAllowed);
+                };
+                if *count == 0 || *count > MAX_DICE_COUNT || *sides == 0 || *sides > MAX_DICE_SIDES
+                {
+                    return Err(FormulaError::DiceOutOfRange);
+                }
+                let results: Vec<u32> = (0..*count)
+                    .map(|_| roll_die(*sides).clamp(1, *sides))
+                    .collect();
+                let total: u32 = results.iter().sum();
+                let listed = results
+                    .iter()
+                    .map(u32::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                Ok((f64::from(total), format!("{count}d{sides}[{listed}]")))
+            }
+            Expr::Neg(inner) => {
+                let (value, text) = self.evaluate(inner)?;
+                Ok((-value, format!("-{text}")))
+            }
+            Expr::Group(inner) => {
+                let (value, text) = self.evaluate(inner)?;
+                Ok((value, format!("({text})")))
+            }
+            Expr::Binary(left, op, right) => {
+                let (left_value, left_text) = self.evaluate(left)?;
+                let (right_value, right_text) = self.evaluate(right)?;
+                let (value, symbol) = match op {
+                    BinaryOp::Add => (left_value + right_value, '+'),
+                    BinaryOp::Sub => (left_value - right_value, '-'),
+                    BinaryOp::Mul => (left_value * right_value, '*'),
+                    BinaryOp::Div => {
+                        if right_value == 0.0 {
+                            return Err(FormulaError::DivisionByZero);
+                        }
+                        (left_value / right_value, '/')
+                    }
+                };
+                Ok((value, format!("{left_text} {symbol} {right_text}")))
+            }
+            Expr::Call(name, args) => {
+                let mut values = Vec::with_capacity(args.len());
+                let mut texts = Vec::with_capacity(args.len());
+                for arg in args {
+                    let (value, text) = self.evaluate(arg)?;
+                    values.push(value);
+                    texts.push(text);
+                }
+                let value = match (name.as_str(), values.as_slice()) {
+                    ("floor", [value]) => value.floor(),
+                    ("ceil", [value]) => value.ceil(),
+                    ("abs", [value]) => value.abs(),
+                    ("min", [first, rest @ ..]) => rest.iter().fold(*first, |acc, v| acc.min(*v)),
+                    ("max", [first, rest @ ..]) => rest.iter().fold(*first, |acc, v| acc.max(*v)),
+                    ("floor" | "ceil" | "abs" | "min" | "max", _) => {
+                        return Err(FormulaError::Syntax);
+                    }
+                    _ => return Err(FormulaError::UnknownFunction(name.clone())),
+                };
+                Ok((value, format!("{name}({})", texts.join(", "))))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::events::SheetSection;
+
+    fn field(key: &str, kind: SheetFieldKind, formula: Option<&str>) -> SheetField {
+        SheetField {
+            key: key.to_string(),
+            label: key.to_uppercase(),
+            kind,
+            formula: formula.map(str::to_string),
+            default: None,
+            signed: false,
+            private: false,
+        }
+    }
+
+    fn template(fields: Vec<SheetField>) -> SheetTemplate {
+        SheetTemplate {
+            id: "test".to_string(),
+            name: "Test".to_string(),
+            sections: vec![SheetSection {
+                title: "Main".to_string(),
+                columns: 2,
+                fields,
+            }],
+        }
+    }
+
+    fn values(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn bundled_5e_template_parses_and_validates() {
+        let templates = bundled_templates();
+        let dnd5e = find_template(&templates, DEFAULT_TEMPLATE_ID).expect("bundled 5e template");
+        assert_eq!(validate_template(dnd5e), Ok(()));
+
+        let scores = values(&[("dex", "14"), ("level", "5")]);
+        assert_eq!(field_number(dnd5e, &scores, "dex_mod"), Ok(2.0));
+        assert_eq!(field_number(dnd5e, &scores, "prof_bonus"), Ok(3.0));
+        assert_eq!(field_number(dnd5e, &scores, "str_mod"), Ok(0.0));
+    }
+
+    #[test]
+    fn room_template_replaces_bundled_template_with_same_id() {
+        let mut custom = template(Vec::new());
+        custom.id = DEFAULT_TEMPLATE_ID.to_string();
+        custom.name = "House rules".to_string();
+
+        let templates = available_templates(&[custom]);
+        assert_eq!(
+            templates
+                .iter()
+                .filter(|template| template.id == DEFAULT_TEMPLATE_ID)
+                .count(),
+            1
+        );
+        assert_eq!(
+            find_template(&templates, DEFAULT_TEMPLATE_ID).map(|t| t.name.as_str()),
+            Some("House rules")
+        );
+    }
+
+    #[test]
+    fn computed_fields_follow_precedence_functions_and_checkboxes() {
+        let sheet = template(vec![
+            field("a", SheetFieldKind::Number, None),
+            field("prof", SheetFieldKind::Checkbox, None),
+            field(
+                "total",
+                SheetFieldKind::Computed,
+                Some("-a + 2 * (3 + 1) - max(1, 4, 2) / 2 + prof * 10"),
+            ),
+            field("low", SheetFieldKind::Computed, Some("floor(-7 / 2)")),
+        ]);
+        let data = values(&[("a", "3"), ("prof", "true")]);
+
+        assert_eq!(field_number(&sheet, &data, "total"), Ok(13.0));
+        assert_eq!(field_number(&sheet, &data, "low"), Ok(-4.0));
+    }
+
+    #[test]
+    fn computed_fields_reject_dice_cycles_and_unknown_names() {
+        let sheet = template(vec![
+            field("dice", SheetFieldKind::Computed, Some("1d6")),
+            field("loop_a", SheetFieldKind::Computed, Some("loop_b + 1")),
+            field("loop_b", SheetFieldKind::Computed, Some("loop_a + 1")),
+            field("typo", SheetFieldKind::Computed, Some("strength + 1")),
+            field("zero", SheetFieldKind::Computed, Some("1 / 0")),
+        ]);
+        let data = BTreeMap::new();
+
+        assert_eq!(
+            field_number(&sheet, &data, "dice"),
+            Err(FormulaError::DiceNotAllowed)
+        );
+        assert_eq!(
+            field_number(&sheet, &data, "loop_a"),
+            Err(FormulaError::TooDeep)
+        );
+        assert_eq!(
+            field_number(&sheet, &data, "typo"),
+            Err(FormulaError::UnknownField("strength".to_string()))
+        );
+        assert_eq!(
+            field_number(&sheet, &data, "zero"),
+            Err(FormulaError::DivisionByZero)
+        );
+    }
+
+    #[test]
+    fn shared_references_are_evaluated_once() {
+        let mut fields = vec![field("f0", SheetFieldKind::Number, None)];
+        for level in 1..=MAX_FORMULA_DEPTH {
+            let key = format!("f{level}");
+            let formula = format!("f{0} + f{0}", level - 1);
+            fields.push(field(&key, SheetFieldKind::Computed, Some(&formula)));
+        }
+        let sheet = template(fields);
+        let data = values(&[("f0", "1")]);
+
+        let top = format!("f{MAX_FORMULA_DEPTH}");
+        assert_eq!(
+            field_number(&sheet, &data, &top),
+            Ok(2f64.powi(MAX_FORMULA_DEPTH as i32))
+        );
+    }
+
+    #[test]
+    fn roll_field_reports_each_die_and_total() {
+        let sheet = template(vec![
+            field("mod", SheetFieldKind::Number, None),
+            field("attack", SheetFieldKind::Roll, Some("2d6 + d4 + mod")),
+        ]);
+        let data = values(&[("mod", "3")]);
+        let mut rolls = [5, 2, 4].into_iter();
+        let mut roller = |_sides: u32| rolls.next().unwrap_or(1);
+
+        let outcome = roll_field(&sheet, &data, &sheet.sections[0].fields[1], &mut roller).unwrap();
+        assert_eq!(outcome.total, 14.0);
+        assert_eq!(outcome.breakdown, "2d6[5, 2] + 1d4[4] + 3");
+        assert_eq!(
+            roll_chat_message("Aria", "Attack", &outcome),
+            "🎲 Aria — Attack: 2d6[5, 2] + 1d4[4] + 3 = 14"
+        );
+
+        let long = RollOutcome {
+            total: 100.0,
+            breakdown: "1, ".repeat(300),
+        };
+        assert_eq!(
+            roll_chat_message("Aria", "Fireball", &long),
+            "🎲 Aria — Fireball: 100"
+        );
+    }
+
+    #[test]
+    fn template_validation_catches_bad_keys_and_formulas() {
+        let duplicate = template(vec![
+            field("hp", SheetFieldKind::Number, None),
+            field("hp", SheetFieldKind::Number, None),
+        ]);
+        assert_eq!(
+            validate_template(&duplicate),
+            Err(TemplateError::DuplicateKey("hp".to_string()))
+        );
+
+        let bad_key = template(vec![field("Max HP", SheetFieldKind::Number, None)]);
+        assert_eq!(
+            validate_template(&bad_key),
+            Err(TemplateError::InvalidKey("Max HP".to_string()))
+        );
+
+        let missing = template(vec![field("mod", SheetFieldKind::Computed, None)]);
+        assert_eq!(
+            validate_template(&missing),
+            Err(TemplateError::MissingFormula("mod".to_string()))
+        );
+
+        let broken = template(vec![field("roll", SheetFieldKind::Roll, Some("1d20 +"))]);
+        assert_eq!(
+            validate_template(&broken),
+            Err(TemplateError::InvalidFormula("roll".to_string()))
+        );
+
+        assert_eq!(
+            parse_template_json("{not json"),
+            Err(TemplateError::InvalidJson)
+        );
+    }
+
+    #[test]
+    fn private_fields_are_hidden_from_other_players() {
+        let mut secret = field("secret", SheetFieldKind::TextArea, None);
+        secret.private = true;
+        let public = field("name", SheetFieldKind::Text, None);
+
+        assert!(field_visible(&public, false));
+        assert!(!field_visible(&secret, false));
+        assert!(field_visible(&secret, true));
+    }
+
+    #[test]
+    fn private_values_are_split_from_the_room_state() {
+        let mut secret = field("secret", SheetFieldKind::TextArea, None);
+        secret.private = true;
+        let sheet = template(vec![field("name", SheetFieldKind::Text, None), secret]);
+        let all = values(&[("name", "Aria"), ("secret", "heir"), ("stale", "1")]);
+
+        let (public, private) = split_private_values(Some(&sheet), &all);
+        assert_eq!(public, values(&[("name", "Aria"), ("stale", "1")]));
+        assert_eq!(private, values(&[("secret", "heir")]));
+
+        let (public, private) = split_private_values(None, &all);
+        assert_eq!(public, all);
+        assert!(private.is_empty());
+    }
+
+    #[test]
+    fn format_number_trims_integers_and_signs_modifiers() {
+        assert_eq!(format_number(3.0, true), "+3");
+        assert_eq!(format_number(0.0, true), "+0");
+        assert_eq!(format_number(-1.0, true), "-1");
+        assert_eq!(format_number(2.5, false), "2.50");
+    }
+}
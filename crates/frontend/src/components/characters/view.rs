@@ -0,0 +1,602 @@
+use super::model::{
+    TEMPLATE_FILE_ACCEPT, available_templates, field_number, field_visible, find_template,
+    format_number, is_checked, parse_template_json, raw_field_value, roll_chat_message, roll_field,
+    split_private_values,
+};
+use super::view_model::{CharactersWindowViewModel, sheet_with_value};
+use crate::components::draggable_window::DraggableWindow;
+use crate::components::websocket::WsSender;
+use crate::config::Theme;
+use crate::i18n::i18n::{t, t_string, use_i18n};
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use leptos::wasm_bindgen::JsCast;
+use shared::events::{
+    CharacterSheet, CharacterSheetDeletePayload, CharacterSheetSecretsPayload,
+    CharacterSheetUpsertPayload, ChatMessagePayload, ClientEvent, Scene, SheetField,
+    SheetFieldKind, SheetTemplate, SheetTemplateUpsertPayload, acts_as_game_master,
+};
+use std::collections::BTreeMap;
+use uuid::Uuid;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{Event, HtmlInputElement};
+
+const CHARACTERS_BODY_FONT_SIZE: &str = "clamp(0.88rem, 0.85rem + 0.12vw, 0.96rem)";
+const CHARACTERS_META_FONT_SIZE: &str = "clamp(0.74rem, 0.71rem + 0.12vw, 0.82rem)";
+const CHARACTERS_BUTTON_FONT_SIZE: &str = "clamp(0.82rem, 0.79rem + 0.12vw, 0.92rem)";
+
+/// What the sheet form is built from; the form is rebuilt only when this
+/// changes so field edits from other players do not steal focus.
+#[derive(Clone, PartialEq)]
+struct SheetLayout {
+    template: Option<SheetTemplate>,
+    can_edit: bool,
+    shows_private: bool,
+}
+
+#[component]
+pub fn CharactersWindow(
+    #[prop(into)] is_open: RwSignal<bool>,
+    #[prop(into)] character_sheets: RwSignal<Vec<CharacterSheet>>,
+    /// Private field values this user may see, by sheet id.
+    #[prop(into)] sheet_secrets: RwSignal<BTreeMap<String, BTreeMap<String, String>>>,
+    #[prop(into)] sheet_templates: RwSignal<Vec<SheetTemplate>>,
+    #[prop(into)] scenes: RwSignal<Vec<Scene>>,
+    #[prop(into)] game_master: RwSignal<Option<String>>,
+    /// Sheet shown in the form; set from outside when a token's sheet is opened.
+    #[prop(into)] selected_sheet_id: RwSignal<Option<String>>,
+    ws_sender: ReadSignal<Option<WsSender>>,
+    username: ReadSignal<String>,
+    #[prop(into, optional)] is_active: Signal<bool>,
+    #[prop(optional)] on_focus: Option<Callback<()>>,
+    theme: Theme,
+) -> impl IntoView {
+    let i18n = use_i18n();
+    let vm = CharactersWindowViewModel::new();
+
+    let send_event = move |event: ClientEvent| {
+        if let Some(sender) = ws_sender.get_untracked() {
+            let _ = sender.try_send_event(event);
+        }
+    };
+
+    let templates = Memo::new(move |_| available_templates(&sheet_templates.get()));
+    let viewer_is_gm =
+        Memo::new(move |_| acts_as_game_master(game_master.get().as_deref(), &username.get()));
+    let selected_sheet = Memo::new(move |_| {
+        let id = selected_sheet_id.get()?;
+        let mut sheet = character_sheets
+            .get()
+            .into_iter()
+            .find(|sheet| sheet.id == id)?;
+        if let Some(secrets) = sheet_secrets.get().get(&id) {
+            sheet.values.extend(secrets.clone());
+        }
+        Some(sheet)
+    });
+    let sheet_layout = Memo::new(move |_| {
+        let sheet = selected_sheet.get()?;
+        let game_master = game_master.get();
+        let viewer = username.get();
+        Some(SheetLayout {
+            template: find_template(&templates.get(), &sheet.template_id).cloned(),
+            can_edit: sheet.can_be_edited_by(&viewer, game_master.as_deref()),
+            shows_private: sheet.shows_private_fields_to(&viewer, game_master.as_deref()),
+        })
+    });
+    let token_options = Memo::new(move |_| {
+        scenes
+            .get()
+            .iter()
+            .flat_map(|scene| {
+                scene
+                    .tokens
+                    .iter()
+                    .map(|token| (token.id.clone(), format!("{} · {}", scene.name, token.name)))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>()
+    });
+
+    // Private values are kept out of the room state and go only to the owner
+    // and the GM, encrypted for them alone.
+    let save_sheet = move |mut sheet: CharacterSheet| {
+        let actor = username.get_untracked();
+        let game_master = game_master.get_untracked();
+        let template = find_template(&templates.get_untracked(), &sheet.template_id).cloned();
+        let (values, secrets) = split_private_values(template.as_ref(), &sheet.values);
+        sheet.values = values;
+        let known_secrets = sheet_secrets
+            .get_untracked()
+            .get(&sheet.id)
+            .cloned()
+            .unwrap_or_default();
+        let secrets_payload = (sheet.shows_private_fields_to(&actor, game_master.as_deref())
+            && secrets != known_secrets)
+            .then(|| CharacterSheetSecretsPayload {
+                sheet_id: sheet.id.clone(),
+                audience: sheet.private_audience(game_master.as_deref()),
+                values: secrets,
+                actor: actor.clone(),
+            });
+
+        send_event(ClientEvent::CharacterSheetUpsert(
+            CharacterSheetUpsertPayload { sheet, actor },
+        ));
+        if let Some(payload) = secrets_payload {
+            send_event(ClientEvent::CharacterSheetSecrets(payload));
+        }
+    };
+    let on_field_change = Callback::new(move |(key, value): (String, String)| {
+        if let Some(sheet) = selected_sheet.get_untracked() {
+            save_sheet(sheet_with_value(&sheet, &key, value, js_sys::Date::now()));
+        }
+    });
+    let on_roll = Callback::new(move |field: SheetField| {
+        let Some(sheet) = selected_sheet.get_untracked() else {
+            return;
+        };
+        let Some(template) = sheet_layout.get_untracked().and_then(|layout| layout.template)
+        else {
+            return;
+        };
+        let mut roll_die = |sides: u32| rand::random_range(1..=sides);
+        match roll_field(&template, &sheet.values, &field, &mut roll_die) {
+            Ok(outcome) => {
+                vm.template_error.set(None);
+                send_event(ClientEvent::ChatMessage(ChatMessagePayload {
+                    payload: roll_chat_message(&sheet.name, &field.label, &outcome),
+                    username: username.get_untracked(),
+                    attachments: Vec::new(),
+                }));
+            }
+            Err(_) => vm
+                .template_error
+                .set(Some(t_string!(i18n, characters.error_roll).to_string())),
+        }
+    });
+
+    let create_sheet = move |_| {
+        let owner = if viewer_is_gm.get_untracked() {
+            None
+        } else {
+            Some(username.get_untracked())
+        };
+        let Some(sheet) = vm.build_sheet(Uuid::new_v4().to_string(), owner, js_sys::Date::now())
+        else {
+            return;
+        };
+        selected_sheet_id.set(Some(sheet.id.clone()));
+        save_sheet(sheet);
+        vm.reset();
+    };
+
+    let on_template_selected = move |event: Event| {
+        let Some(input) = event
+            .target()
+            .and_then(|target| target.dyn_into::<HtmlInputElement>().ok())
+        else {
+            return;
+        };
+        let Some(file) = input.files().and_then(|files| files.get(0)) else {
+            return;
+        };
+        input.set_value("");
+        let invalid_message = t_string!(i18n, characters.error_template).to_string();
+        spawn_local(async move {
+            let raw = JsFuture::from(file.text())
+                .await
+                .ok()
+                .and_then(|value| value.as_string())
+                .unwrap_or_default();
+            match parse_template_json(&raw) {
+                Ok(template) => {
+                    vm.template_error.set(None);
+                    vm.draft_template_id.set(template.id.clone());
+                    send_event(ClientEvent::SheetTemplateUpsert(
+                        SheetTemplateUpsertPayload {
+                            template,
+                            actor: username.get_untracked(),
+                        },
+                    ));
+                }
+                Err(error) => vm
+                    .template_error
+                    .set(Some(format!("{invalid_message} ({error:?})"))),
+            }
+        });
+    };
+
+    let list_input_style = format!(
+        "padding: 0.5rem; background: {}; color: {}; border: 0.0625rem solid {}; border-radius: 0.5rem; font-size: {};",
+        theme.ui_bg_secondary, theme.ui_text_primary, theme.ui_border, CHARACTERS_BODY_FONT_SIZE
+    );
+    let header_input_style = list_input_style.clone();
+    let field_theme = theme.clone();
+
+    view! {
+        <DraggableWindow
+            is_open=is_open
+            title=move || t_string!(i18n, characters.title)
+            initial_x=260
+            initial_y=90
+            initial_width=760
+            initial_height=620
+            min_width=480
+            min_height=360
+            is_active=is_active
+            on_focus=on_focus.unwrap_or_else(|| Callback::new(|_| {}))
+            theme=theme.clone()
+        >
+            <div style="display: flex; flex: 1; min-height: 0;">
+                // Left panel: sheet list + new sheet form
+                <div style=format!(
+                    "width: 34%; border-right: 0.0625rem solid {}; padding: 1rem; overflow-y: auto; display: flex; flex-direction: column; gap: 0.6rem;",
+                    theme.ui_border
+                )>
+                    <h4 style=format!("margin: 0; color: {};", theme.ui_text_primary)>
+                        {move || t!(i18n, characters.list_title)}
+                    </h4>
+                    {move || {
+                        if character_sheets.get().is_empty() {
+                            view! {
+                                <div style=format!("color: {}; font-style: italic;", theme.ui_text_muted)>
+                                    {t!(i18n, characters.empty)}
+                                </div>
+                            }.into_any()
+                        } else {
+                            ().into_any()
+                        }
+                    }}
+                    <For
+                        each=move || character_sheets.get()
+                        key=|sheet| (sheet.id.clone(), sheet.name.clone(), sheet.owner.clone())
+                        children=move |sheet| {
+                            let select_id = sheet.id.clone();
+                            let is_selected = Signal::derive({
+                                let id = sheet.id.clone();
+                                move || selected_sheet_id.get() == Some(id.clone())
+                            });
+                            let owner_label = sheet
+                                .owner
+                                .clone()
+                                .unwrap_or_else(|| t_string!(i18n, characters.owner_gm).to_string());
+                            view! {
+                                <button
+                                    on:click=move |_| selected_sheet_id.set(Some(select_id.clone()))
+                                    style=move || format!(
+                                        "text-align: left; padding: 0.6rem 0.75rem; background: {}; border: 0.0625rem solid {}; border-radius: 0.5rem; cursor: pointer; color: {};",
+                                        if is_selected.get() { theme.ui_bg_secondary } else { theme.ui_bg_primary },
+                                        if is_selected.get() { theme.ui_success } else { theme.ui_border },
+                                        theme.ui_text_primary
+                                    )
+                                >
+                                    <div style="font-weight: 700;">{sheet.name.clone()}</div>
+                                    <div style=format!("color: {}; font-size: {};", theme.ui_text_secondary, CHARACTERS_META_FONT_SIZE)>
+                                        {owner_label}
+                                    </div>
+                                </button>
+                            }
+                        }
+                    />
+
+                    <div style=format!("border-top: 0.0625rem solid {}; padding-top: 0.75rem; display: flex; flex-direction: column; gap: 0.5rem;", theme.ui_border)>
+                        <input
+                            type="text"
+                            maxlength="120"
+                            placeholder=move || t_string!(i18n, characters.name_placeholder)
+                            prop:value=move || vm.draft_name.get()
+                            on:input=move |ev| vm.draft_name.set(event_target_value(&ev))
+                            style=list_input_style.clone()
+                        />
+                        <label style=format!("color: {}; display: flex; flex-direction: column; gap: 0.3rem; font-size: {};", theme.ui_text_secondary, CHARACTERS_META_FONT_SIZE)>
+                            <span>{move || t!(i18n, characters.template_label)}</span>
+                            <select
+                                prop:value=move || vm.draft_template_id.get()
+                                on:change=move |ev| vm.draft_template_id.set(event_target_value(&ev))
+                                style=list_input_style.clone()
+                            >
+                                {move || templates.get().into_iter().map(|template| view! {
+                                    <option value=template.id.clone()>{template.name.clone()}</option>
+                                }).collect_view()}
+                            </select>
+                        </label>
+                        <button
+                            on:click=create_sheet
+                            style=format!(
+                                "padding: 0.5rem 0.75rem; background: {}; color: {}; border: none; border-radius: 0.5rem; cursor: pointer; font-size: {};",
+                                theme.ui_button_primary, theme.ui_text_primary, CHARACTERS_BUTTON_FONT_SIZE
+                            )
+                        >
+                            {move || t!(i18n, characters.create_button)}
+                        </button>
+                        {move || viewer_is_gm.get().then(|| view! {
+                            <label style=format!(
+                                "padding: 0.5rem 0.75rem; border: 0.0625rem dashed {}; border-radius: 0.5rem; cursor: pointer; color: {}; font-size: {}; text-align: center;",
+                                theme.ui_border, theme.ui_text_secondary, CHARACTERS_BUTTON_FONT_SIZE
+                            )>
+                                {t!(i18n, characters.upload_template)}
+                                <input type="file" accept=TEMPLATE_FILE_ACCEPT on:change=on_template_selected style="display: none;" />
+                            </label>
+                        })}
+                        {move || vm.template_error.get().map(|error| view! {
+                            <div style=format!("color: {}; font-size: {};", theme.ui_button_danger, CHARACTERS_META_FONT_SIZE)>
+                                {error}
+                            </div>
+                        })}
+                    </div>
+                </div>
+
+                // Right panel: selected sheet
+                <div style="flex: 1; padding: 1rem; overflow-y: auto;">
+                    {move || {
+                        let Some(layout) = sheet_layout.get() else {
+                            return view! {
+                                <div style=format!("color: {}; font-style: italic;", theme.ui_text_muted)>
+                                    {t!(i18n, characters.select_hint)}
+                                </div>
+                            }.into_any();
+                        };
+                        let Some(template) = layout.template.clone() else {
+                            return view! {
+                                <div style=format!("color: {};", theme.ui_button_danger)>
+                                    {t!(i18n, characters.missing_template)}
+                                </div>
+                            }.into_any();
+                        };
+                        let can_edit = layout.can_edit;
+                        let is_gm = viewer_is_gm.get_untracked();
+                        let header_style = header_input_style.clone();
+                        let section_theme = field_theme.clone();
+
+                        let sections = template
+                            .sections
+                            .iter()
+                            .filter_map(|section| {
+                                let fields: Vec<SheetField> = section
+                                    .fields
+                                    .iter()
+                                    .filter(|field| field_visible(field, layout.shows_private))
+                                    .cloned()
+                                    .collect();
+                                if fields.is_empty() {
+                                    return None;
+                                }
+                                let columns = section.columns.clamp(1, 6);
+                                let template = template.clone();
+                                let field_theme = section_theme.clone();
+                                Some(view! {
+                                    <section style="margin-top: 1rem;">
+                                        <h5 style=format!("margin: 0 0 0.5rem 0; color: {}; text-transform: uppercase; letter-spacing: 0.04em;", section_theme.ui_text_secondary)>
+                                            {section.title.clone()}
+                                        </h5>
+                                        <div style=format!("display: grid; grid-template-columns: repeat({columns}, minmax(0, 1fr)); gap: 0.5rem;")>
+                                            {fields.into_iter().map(|field| view! {
+                                                <SheetFieldInput
+                                                    field=field
+                                                    template=template.clone()
+                                                    sheet=selected_sheet
+                                                    can_edit=can_edit
+                                                    on_change=on_field_change
+                                                    on_roll=on_roll
+                                                    theme=field_theme.clone()
+                                                />
+                                            }).collect_view()}
+                                        </div>
+                                    </section>
+                                })
+                            })
+                            .collect_view();
+
+                        view! {
+                            <div style="display: flex; flex-direction: column; gap: 0.5rem;">
+                                <input
+                                    type="text"
+                                    maxlength="120"
+                                    disabled=!can_edit
+                                    prop:value=move || selected_sheet.get().map(|sheet| sheet.name).unwrap_or_default()
+                                    on:change=move |ev| {
+                                        let name = event_target_value(&ev).trim().to_string();
+                                        if let Some(mut sheet) = selected_sheet.get_untracked() && !name.is_empty() {
+                                            sheet.name = name;
+                                            sheet.updated_at_ms = js_sys::Date::now();
+                                            save_sheet(sheet);
+                                        }
+                                    }
+                                    style=format!("{header_style} font-weight: 700;")
+                                />
+                                <div style="display: grid; grid-template-columns: 1fr 1fr; gap: 0.5rem;">
+                                    <label style=format!("color: {}; display: flex; flex-direction: column; gap: 0.3rem; font-size: {};", theme.ui_text_secondary, CHARACTERS_META_FONT_SIZE)>
+                                        <span>{t!(i18n, characters.owner_label)}</span>
+                                        <input
+                                            type="text"
+                                            maxlength="255"
+                                            disabled=!is_gm
+                                            placeholder=move || t_string!(i18n, characters.owner_gm)
+                                            prop:value=move || selected_sheet.get().and_then(|sheet| sheet.owner).unwrap_or_default()
+                                            on:change=move |ev| {
+                                                let owner = event_target_value(&ev).trim().to_string();
+                                                if let Some(mut sheet) = selected_sheet.get_untracked() {
+                                                    sheet.owner = (!owner.is_empty()).then_some(owner);
+                                                    sheet.updated_at_ms = js_sys::Date::now();
+                                                    save_sheet(sheet);
+                                                }
+                                            }
+                                            style=header_style.clone()
+                                        />
+                                    </label>
+                                    <label style=format!("color: {}; display: flex; flex-direction: column; gap: 0.3rem; font-size: {};", theme.ui_text_secondary, CHARACTERS_META_FONT_SIZE)>
+                                        <span>{t!(i18n, characters.token_label)}</span>
+                                        <select
+                                            disabled=!can_edit
+                                            prop:value=move || selected_sheet.get().and_then(|sheet| sheet.token_id).unwrap_or_default()
+                                            on:change=move |ev| {
+                                                let token_id = event_target_value(&ev);
+                                                if let Some(mut sheet) = selected_sheet.get_untracked() {
+                                                    sheet.token_id = (!token_id.is_empty()).then_some(token_id);
+                                                    sheet.updated_at_ms = js_sys::Date::now();
+                                                    save_sheet(sheet);
+                                                }
+                                            }
+                                            style=header_style.clone()
+                                        >
+                                            <option value="">{t!(i18n, characters.token_none)}</option>
+                                            {move || token_options.get().into_iter().map(|(id, label)| view! {
+                                                <option value=id>{label}</option>
+                                            }).collect_view()}
+                                        </select>
+                                    </label>
+                                </div>
+                                {can_edit.then(|| view! {
+                                    <button
+                                        on:click=move |_| {
+                                            if let Some(sheet_id) = selected_sheet_id.get_untracked() {
+                                                send_event(ClientEvent::CharacterSheetDelete(CharacterSheetDeletePayload {
+                                                    sheet_id,
+                                                    actor: username.get_untracked(),
+                                                }));
+                                                selected_sheet_id.set(None);
+                                            }
+                                        }
+                                        style=format!(
+                                            "align-self: flex-end; padding: 0.4rem 0.7rem; background: {}; color: {}; border: none; border-radius: 0.5rem; cursor: pointer; font-size: {};",
+                                            theme.ui_button_danger, theme.ui_text_primary, CHARACTERS_BUTTON_FONT_SIZE
+                                        )
+                                    >
+                                        {t!(i18n, characters.delete_button)}
+                                    </button>
+                                })}
+                            </div>
+                            {sections}
+                        }.into_any()
+                    }}
+                </div>
+            </div>
+        </DraggableWindow>
+    }
+}
+
+/// One template field: an input, a computed value or a roll button.
+#[component]
+fn SheetFieldInput(
+    field: SheetField,
+    template: SheetTemplate,
+    sheet: Memo<Option<CharacterSheet>>,
+    can_edit: bool,
+    on_change: Callback<(String, String)>,
+    on_roll: Callback<SheetField>,
+    theme: Theme,
+) -> impl IntoView {
+    let input_style = format!(
+        "width: 100%; box-sizing: border-box; padding: 0.45rem; background: {}; color: {}; border: 0.0625rem solid {}; border-radius: 0.45rem; font-size: {};",
+        theme.ui_bg_secondary, theme.ui_text_primary, theme.ui_border, CHARACTERS_BODY_FONT_SIZE
+    );
+    let label_style = format!(
+        "color: {}; display: flex; flex-direction: column; gap: 0.25rem; font-size: {};",
+        theme.ui_text_secondary, CHARACTERS_META_FONT_SIZE
+    );
+    let raw_value = {
+        let field = field.clone();
+        Signal::derive(move || {
+            sheet
+                .get()
+                .map(|sheet| raw_field_value(&field, &sheet.values))
+                .unwrap_or_default()
+        })
+    };
+    let key = field.key.clone();
+    let change = move |value: String| on_change.run((key.clone(), value));
+
+    match field.kind {
+        SheetFieldKind::Text | SheetFieldKind::Number => {
+            let input_type = if field.kind == SheetFieldKind::Number {
+                "number"
+            } else {
+                "text"
+            };
+            view! {
+                <label style=label_style>
+                    <span>{field.label.clone()}</span>
+                    <input
+                        type=input_type
+                        disabled=!can_edit
+                        prop:value=move || raw_value.get()
+                        on:change=move |ev| change(event_target_value(&ev))
+                        style=input_style
+                    />
+                </label>
+            }
+            .into_any()
+        }
+        SheetFieldKind::TextArea => view! {
+            <label style=format!("{label_style} grid-column: 1 / -1;")>
+                <span>{field.label.clone()}</span>
+                <textarea
+                    rows="4"
+                    disabled=!can_edit
+                    prop:value=move || raw_value.get()
+                    on:change=move |ev| change(event_target_value(&ev))
+                    style=format!("{input_style} resize: vertical;")
+                />
+            </label>
+        }
+        .into_any(),
+        SheetFieldKind::Checkbox => view! {
+            <label style=format!("{label_style} flex-direction: row; align-items: center; gap: 0.4rem;")>
+                <input
+                    type="checkbox"
+                    disabled=!can_edit
+                    prop:checked=move || is_checked(&raw_value.get())
+                    on:change=move |ev| change(event_target_checked(&ev).to_string())
+                />
+                <span>{field.label.clone()}</span>
+            </label>
+        }
+        .into_any(),
+        SheetFieldKind::Computed => {
+            let key = field.key.clone();
+            let signed = field.signed;
+            let value = move || {
+                sheet
+                    .get()
+                    .and_then(|sheet| field_number(&template, &sheet.values, &key).ok())
+                    .map(|value| format_number(value, signed))
+                    .unwrap_or_else(|| "—".to_string())
+            };
+            view! {
+                <div style=label_style>
+                    <span>{field.label.clone()}</span>
+                    <div style=format!(
+                        "padding: 0.45rem; border: 0.0625rem dashed {}; border-radius: 0.45rem; color: {}; font-weight: 700; text-align: center;",
+                        theme.ui_border, theme.ui_text_primary
+                    )>
+                        {value}
+                    </div>
+                </div>
+            }
+            .into_any()
+        }
+        SheetFieldKind::Roll => {
+            let formula = field.formula.clone().unwrap_or_default();
+            let label = field.label.clone();
+            let title = formula.clone();
+            view! {
+                <button
+                    disabled=!can_edit
+                    title=title
+                    on:click=move |_| on_roll.run(field.clone())
+                    style=format!(
+                        "padding: 0.45rem 0.6rem; background: {}; color: {}; border: 0.0625rem solid {}; border-radius: 0.45rem; cursor: pointer; text-align: left; font-size: {};",
+                        theme.ui_bg_primary, theme.ui_text_primary, theme.ui_border, CHARACTERS_BUTTON_FONT_SIZE
+                    )
+                >
+                    {format!("🎲 {label}")}
+                    <span style=format!("color: {}; margin-left: 0.35rem; font-size: {};", theme.ui_text_muted, CHARACTERS_META_FONT_SIZE)>
+                        {formula}
+                    </span>
+                </button>
+            }
+            .into_any()
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use super::model::{DEFAULT_TEMPLATE_ID, MAX_FIELD_VALUE_LEN};
+use leptos::prelude::*;
+use shared::events::CharacterSheet;
+use std::collections::BTreeMap;
+
+/// Reactive state for the character sheet list + new sheet form.
+#[derive(Clone, Copy)]
+pub struct CharactersWindowViewModel {
+    pub draft_name: RwSignal<String>,
+    pub draft_template_id: RwSignal<String>,
+    pub template_error: RwSignal<Option<String>>,
+}
+
+impl CharactersWindowViewModel {
+    pub fn new() -> Self {
+        Self {
+            draft_name: RwSignal::new(String::new()),
+            draft_template_id: RwSignal::new(DEFAULT_TEMPLATE_ID.to_string()),
+            template_error: RwSignal::new(None),
+        }
+    }
+
+    /// Builds a new sheet from the draft; returns `None` for an empty name.
+    pub fn build_sheet(
+        &self,
+        id: String,
+        owner: Option<String>,
+        now_ms: f64,
+    ) -> Option<CharacterSheet> {
+        let name = self.draft_name.get_untracked().trim().to_string();
+        if name.is_empty() {
+            return None;
+        }
+        Some(CharacterSheet {
+            id,
+            name,
+            template_id: self.draft_template_id.get_untracked(),
+            owner,
+            token_id: None,
+            values: BTreeMap::new(),
+            updated_at_ms: now_ms,
+        })
+    }
+
+    pub fn reset(&self) {
+        self.draft_name.set(String::new());
+        self.template_error.set(None);
+    }
+}
+
+/// Copy of `sheet` with one field changed; over-long values are truncated.
+pub fn sheet_with_value(
+    sheet: &CharacterSheet,
+    key: &str,
+    value: String,
+    now_ms: f64,
+) -> CharacterSheet {
+    let mut updated = sheet.clone();
+    let value = value.chars().take(MAX_FIELD_VALUE_LEN).collect();
+    updated.values.insert(key.to_string(), value);
+    updated.updated_at_ms = now_ms;
+    updated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use leptos::reactive::owner::Owner;
+
+    #[test]
+    fn build_sheet_requires_name_and_uses_selected_template() {
+        let owner = Owner::new();
+        owner.with(|| {
+            let vm = CharactersWindowViewModel::new();
+            assert!(vm.build_sheet("id".to_string(), None, 0.0).is_none());
+
+            vm.draft_name.set("  Aria  ".to_string());
+            let sheet = vm
+                .build_sheet("id".to_string(), Some("alice".to_string()), 5.0)
+                .unwrap();
+            assert_eq!(sheet.name, "Aria");
+            assert_eq!(sheet.template_id, DEFAULT_TEMPLATE_ID);
+            assert_eq!(sheet.owner.as_deref(), Some("alice"));
+
+            vm.reset();
+            assert_eq!(vm.draft_name.get_untracked(), "");
+        });
+    }
+
+    #[test]
+    fn sheet_with_value_truncates_long_values() {
+        let sheet = CharacterSheet {
+            id: "id".to_string(),
+            name: "Aria".to_string(),
+            template_id: DEFAULT_TEMPLATE_ID.to_string(),
+            owner: None,
+            token_id: None,
+            values: BTreeMap::new(),
+            updated_at_ms: 0.0,
+        };
+
+        let updated = sheet_with_value(&sheet, "notes", "x".repeat(MAX_FIELD_VALUE_LEN + 10), 9.0);
+        assert_eq!(updated.values["notes"].len(), MAX_FIELD_VALUE_LEN);
+        assert_eq!(updated.updated_at_ms, 9.0);
+    }
+}
@@ -0,0 +1,118 @@
+use super::model::{MonsterPlacement, monster_sheet_values};
+use super::token_image::render_monster_token_png;
+use crate::components::characters::model::DEFAULT_TEMPLATE_ID;
+use crate::components::websocket::{FileTransferState, WsSender};
+use leptos::prelude::*;
+use shared::events::{
+    CharacterSheet, CharacterSheetUpsertPayload, ClientEvent, FileRef, Scene, SceneUpdatePayload,
+    Token, acts_as_game_master,
+};
+use std::collections::{BTreeMap, HashMap};
+use uuid::Uuid;
+
+/// Puts compendium monsters onto scenes: renders (or reuses) a token image
+/// unless the placement brings its own, adds the tokens and links a 5e sheet
+/// with the monster's statistics to each. Monsters placed by a player belong
+/// to that player, since only the GM may save unowned sheets.
+#[derive(Clone)]
+pub struct MonsterDeployer {
+    pub scenes: RwSignal<Vec<Scene>>,
+    pub game_master: RwSignal<Option<String>>,
+    pub file_transfer: FileTransferState,
+    pub ws_sender: ReadSignal<Option<WsSender>>,
+    pub username: ReadSignal<String>,
+}
+
+impl MonsterDeployer {
+    pub async fn deploy(&self, placements: Vec<MonsterPlacement>) -> Result<(), String> {
+        let username = self.username.get_untracked();
+        let ws_sender = self.ws_sender.get_untracked();
+        let owner = (!acts_as_game_master(self.game_master.get_untracked().as_deref(), &username))
+            .then(|| username.clone());
+
+        let mut images: HashMap<String, FileRef> = HashMap::new();
+        for placement in &placements {
+            let name = &placement.monster.name;
+            if placement.image.is_some() || images.contains_key(name) {
+                continue;
+            }
+            let bytes = render_monster_token_png(name)?;
+            let image = self
+                .file_transfer
+                .import_bytes(
+                    &bytes,
+                    format!("{name}.png"),
+                    "image/png",
+                    username.clone(),
+                    ws_sender.clone(),
+                )
+                .await?;
+            images.insert(name.clone(), image);
+        }
+
+        let mut tokens_by_scene: BTreeMap<String, Vec<(Token, CharacterSheet)>> = BTreeMap::new();
+        for placement in placements {
+            let cells = placement.monster.footprint_cells();
+            let image = placement
+                .image
+                .unwrap_or_else(|| images[&placement.monster.name].clone());
+            let token = Token {
+                id: Uuid::new_v4().to_string(),
+                name: placement.name.clone(),
+                image,
+                x: placement.x,
+                y: placement.y,
+                width_cells: cells,
+                height_cells: cells,
+                rotation_deg: 0.0,
+                elevation_feet: 0,
+                owner: owner.clone(),
+                auras: Vec::new(),
+                level_id: placement.level_id,
+            };
+            let sheet = CharacterSheet {
+                id: Uuid::new_v4().to_string(),
+                name: placement.name,
+                template_id: DEFAULT_TEMPLATE_ID.to_string(),
+                owner: owner.clone(),
+                token_id: Some(token.id.clone()),
+                values: monster_sheet_values(&placement.monster),
+                updated_at_ms: js_sys::Date::now(),
+            };
+            tokens_by_scene
+                .entry(placement.scene_id)
+                .or_default()
+                .push((token, sheet));
+        }
+
+        let Some(sender) = ws_sender else {
+            return Err("not connected".to_string());
+        };
+        for (scene_id, placed) in tokens_by_scene {
+            let (tokens, sheets): (Vec<Token>, Vec<CharacterSheet>) = placed.into_iter().unzip();
+            let mut updated_scene = None::<Scene>;
+            self.scenes.update(|items| {
+                if let Some(scene) = items.iter_mut().find(|scene| scene.id == scene_id) {
+                    scene.tokens.extend(tokens);
+                    updated_scene = Some(scene.clone());
+                }
+            });
+            let Some(scene) = updated_scene else {
+                continue;
+            };
+            let _ = sender.try_send_event(ClientEvent::SceneUpdate(SceneUpdatePayload {
+                scene,
+                actor: username.clone(),
+            }));
+            for sheet in sheets {
+                let _ = sender.try_send_event(ClientEvent::CharacterSheetUpsert(
+                    CharacterSheetUpsertPayload {
+                        sheet,
+                        actor: username.clone(),
+                    },
+                ));
+            }
+        }
+        Ok(())
+    }
+}
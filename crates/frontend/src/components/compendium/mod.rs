@@ -0,0 +1,7 @@
+pub mod deploy;
+pub mod model;
+mod token_image;
+mod view;
+mod view_model;
+
+pub use view::CompendiumWindow;
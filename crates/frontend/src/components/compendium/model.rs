@@ -0,0 +1,809 @@
+// Pure types and logic for the compendium browser: the lenient JSON/ZIP
+// importer (see docs/compendium.md), search filters, spell chat cards and the
+// monster → character sheet mapping. No signals, no Leptos, no web_sys.
+
+use crate::components::characters::model::{MAX_CHAT_MESSAGE_LEN, MAX_FIELD_VALUE_LEN};
+use crate::utils::zip::{looks_like_zip, read_zip};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use shared::events::FileRef;
+use std::collections::{BTreeMap, BTreeSet};
+
+// --- Constants ---
+
+pub const COMPENDIUM_FILE_ACCEPT: &str =
+    "application/json,application/zip,application/x-zip-compressed,.json,.zip";
+/// How many search results the browser lists at once.
+pub const MAX_LISTED_ENTRIES: usize = 200;
+const MAX_UNPACKED_BYTES: usize = 64 * 1024 * 1024;
+const MAX_ENTRIES_PER_KIND: usize = 5000;
+const MAX_TITLE_LEN: usize = 120;
+
+/// XP by challenge rating (DMG p. 274), indexed by whole CR 0..=30.
+const XP_BY_CHALLENGE_RATING: [u32; 31] = [
+    10, 200, 450, 700, 1100, 1800, 2300, 2900, 3900, 5000, 5900, 7200, 8400, 10000, 11500, 13000,
+    15000, 18000, 20000, 22000, 25000, 33000, 41000, 50000, 62000, 75000, 90000, 105000, 120000,
+    135000, 155000,
+];
+const TOKEN_COLORS: [&str; 8] = [
+    "#8e3b46", "#3b6e8e", "#4f7d3a", "#7a4f9a", "#a0672d", "#2f7f7a", "#6b6b2f", "#5a5a7a",
+];
+
+// --- Types ---
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CompendiumError {
+    InvalidJson,
+    InvalidZip(String),
+    Empty,
+}
+
+/// A named block of rules text: a trait, an action or a legendary action.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NamedText {
+    pub name: String,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CompendiumMonster {
+    pub name: String,
+    pub size: String,
+    pub creature_type: String,
+    pub alignment: String,
+    pub armor_class: u32,
+    pub hit_points: u32,
+    pub hit_dice: String,
+    pub speed: String,
+    /// STR, DEX, CON, INT, WIS, CHA.
+    pub abilities: [i32; 6],
+    pub challenge_rating: f32,
+    pub xp: u32,
+    pub senses: String,
+    pub languages: String,
+    pub traits: Vec<NamedText>,
+    pub actions: Vec<NamedText>,
+    pub legendary_actions: Vec<NamedText>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompendiumSpell {
+    pub name: String,
+    /// 0 for cantrips.
+    pub level: u8,
+    pub school: String,
+    pub casting_time: String,
+    pub range: String,
+    pub components: String,
+    pub duration: String,
+    pub concentration: bool,
+    pub ritual: bool,
+    pub classes: Vec<String>,
+    pub description: String,
+    pub higher_levels: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Compendium {
+    pub title: String,
+    pub monsters: Vec<CompendiumMonster>,
+    pub spells: Vec<CompendiumSpell>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MonsterFilter {
+    pub query: String,
+    pub min_cr: Option<f32>,
+    pub max_cr: Option<f32>,
+    pub creature_type: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SpellFilter {
+    pub query: String,
+    pub level: Option<u8>,
+    pub school: Option<String>,
+    pub class: Option<String>,
+}
+
+/// A monster dropped onto a scene at a snapped token position.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonsterPlacement {
+    pub monster: CompendiumMonster,
+    /// Token and sheet name, e.g. `Goblin 2` when an encounter places several.
+    pub name: String,
+    /// Token image to use instead of the generated one.
+    pub image: Option<FileRef>,
+    pub scene_id: String,
+    /// Floor of the scene the token lands on; `None` is the base floor.
+    pub level_id: Option<String>,
+    pub x: f32,
+    pub y: f32,
+}
+
+impl CompendiumMonster {
+    /// Grid footprint in cells for the monster's size category.
+    pub fn footprint_cells(&self) -> u16 {
+        match self.size.trim().to_lowercase().as_str() {
+            "large" => 2,
+            "huge" => 3,
+            "gargantuan" => 4,
+            _ => 1,
+        }
+    }
+}
+
+// --- Import ---
+
+/// Parses a compendium file: a JSON document or a ZIP archive of them.
+pub fn parse_compendium_file(bytes: &[u8], file_name: &str) -> Result<Compendium, CompendiumError> {
+    let mut compendium = Compendium::default();
+    if looks_like_zip(bytes) {
+        let entries = read_zip(bytes, MAX_UNPACKED_BYTES).map_err(CompendiumError::InvalidZip)?;
+        for entry in entries
+            .iter()
+            .filter(|entry| entry.name.to_lowercase().ends_with(".json"))
+        {
+            // Archives often carry unrelated JSON (class lists, licences) next to
+            // the monster and spell files, so unreadable entries are skipped.
+            if let Ok(value) = serde_json::from_slice::<Value>(&entry.data) {
+                merge_document(&mut compendium, &value);
+            }
+        }
+    } else {
+        let value: Value =
+            serde_json::from_slice(bytes).map_err(|_| CompendiumError::InvalidJson)?;
+        merge_document(&mut compendium, &value);
+    }
+
+    if compendium.monsters.is_empty() && compendium.spells.is_empty() {
+        return Err(CompendiumError::Empty);
+    }
+    if compendium.title.is_empty() {
+        compendium.title = file_stem(file_name);
+    }
+    compendium.title = compendium.title.chars().take(MAX_TITLE_LEN).collect();
+    compendium.monsters.truncate(MAX_ENTRIES_PER_KIND);
+    compendium.spells.truncate(MAX_ENTRIES_PER_KIND);
+    compendium.monsters.sort_by(|a, b| a.name.cmp(&b.name));
+    compendium.spells.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(compendium)
+}
+
+fn file_stem(file_name: &str) -> String {
+    let stem = file_name
+        .rsplit_once('.')
+        .map_or(file_name, |(stem, _)| stem)
+        .trim();
+    if stem.is_empty() {
+        "Compendium".to_string()
+    } else {
+        stem.to_string()
+    }
+}
+
+fn merge_document(compendium: &mut Compendium, value: &Value) {
+    match value {
+        Value::Array(items) => merge_entries(compendium, items),
+        Value::Object(object) => {
+            if compendium.title.is_empty()
+                && let Some(title) = ["title", "name"]
+                    .iter()
+                    .find_map(|key| object.get(*key).and_then(Value::as_str))
+            {
+                compendium.title = title.trim().to_string();
+            }
+            for key in ["monsters", "spells", "results"] {
+                if let Some(Value::Array(items)) = object.get(key) {
+                    merge_entries(compendium, items);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn merge_entries(compendium: &mut Compendium, items: &[Value]) {
+    for item in items {
+        let Value::Object(object) = item else {
+            continue;
+        };
+        if is_monster(object) {
+            if let Some(monster) = parse_monster(object) {
+                compendium.monsters.push(monster);
+            }
+        } else if is_spell(object)
+            && let Some(spell) = parse_spell(object)
+        {
+            compendium.spells.push(spell);
+        }
+    }
+}
+
+fn is_monster(object: &Map<String, Value>) -> bool {
+    ["challenge_rating", "cr", "hit_points", "hp"]
+        .iter()
+        .any(|key| object.contains_key(*key))
+}
+
+fn is_spell(object: &Map<String, Value>) -> bool {
+    object.contains_key("level")
+        && (object.contains_key("school") || object.contains_key("casting_time"))
+}
+
+fn parse_monster(object: &Map<String, Value>) -> Option<CompendiumMonster> {
+    let name = text_field(object, &["name"]);
+    if name.is_empty() {
+        return None;
+    }
+    let challenge_rating = field(object, &["challenge_rating", "cr"])
+        .and_then(number_of)
+        .unwrap_or(0.0)
+        .clamp(0.0, 30.0) as f32;
+    let ability = |keys: &[&str]| {
+        field(object, keys)
+            .and_then(number_of)
+            .map_or(10, |score| score.round() as i32)
+    };
+
+    Some(CompendiumMonster {
+        name,
+        size: text_field(object, &["size"]),
+        creature_type: text_field(object, &["type", "creature_type"]),
+        alignment: text_field(object, &["alignment"]),
+        armor_class: whole_field(object, &["armor_class", "ac"]),
+        hit_points: whole_field(object, &["hit_points", "hp"]),
+        hit_dice: text_field(object, &["hit_dice", "hit_points_roll"]),
+        speed: text_field(object, &["speed"]),
+        abilities: [
+            ability(&["strength", "str"]),
+            ability(&["dexterity", "dex"]),
+            ability(&["constitution", "con"]),
+            ability(&["intelligence", "int"]),
+            ability(&["wisdom", "wis"]),
+            ability(&["charisma", "cha"]),
+        ],
+        challenge_rating,
+        xp: field(object, &["xp"])
+            .and_then(number_of)
+            .map_or_else(|| xp_for_challenge_rating(challenge_rating), |xp| xp as u32),
+        senses: text_field(object, &["senses"]),
+        languages: text_field(object, &["languages"]),
+        traits: named_texts(object, &["special_abilities", "traits"]),
+        actions: named_texts(object, &["actions"]),
+        legendary_actions: named_texts(object, &["legendary_actions"]),
+    })
+}
+
+fn parse_spell(object: &Map<String, Value>) -> Option<CompendiumSpell> {
+    let name = text_field(object, &["name"]);
+    if name.is_empty() {
+        return None;
+    }
+    let mut components = text_field(object, &["components"]);
+    let material = text_field(object, &["material"]);
+    if !material.is_empty() && !components.contains('(') {
+        components = format!("{components} ({material})");
+    }
+
+    Some(CompendiumSpell {
+        name,
+        level: field(object, &["level_int", "level"])
+            .and_then(number_of)
+            .map_or(0, |level| level.clamp(0.0, 9.0) as u8),
+        school: text_field(object, &["school"]),
+        casting_time: text_field(object, &["casting_time"]),
+        range: text_field(object, &["range"]),
+        components,
+        duration: text_field(object, &["duration"]),
+        concentration: flag_field(object, &["concentration"]),
+        ritual: flag_field(object, &["ritual"]),
+        classes: list_field(object, &["classes", "dnd_class"]),
+        description: text_field(object, &["desc", "description", "text"]),
+        higher_levels: text_field(object, &["higher_level", "higher_levels"]),
+    })
+}
+
+fn field<'a>(object: &'a Map<String, Value>, keys: &[&str]) -> Option<&'a Value> {
+    keys.iter()
+        .find_map(|key| object.get(*key).filter(|value| !value.is_null()))
+}
+
+fn text_field(object: &Map<String, Value>, keys: &[&str]) -> String {
+    field(object, keys).map(text_of).unwrap_or_default()
+}
+
+fn whole_field(object: &Map<String, Value>, keys: &[&str]) -> u32 {
+    field(object, keys)
+        .and_then(number_of)
+        .map_or(0, |value| value.max(0.0) as u32)
+}
+
+fn flag_field(object: &Map<String, Value>, keys: &[&str]) -> bool {
+    match field(object, keys) {
+        Some(Value::Bool(flag)) => *flag,
+        Some(Value::String(text)) => matches!(text.trim().to_lowercase().as_str(), "yes" | "true"),
+        _ => false,
+    }
+}
+
+/// Accepts `["Wizard", ...]`, `[{"name": "Wizard"}, ...]` or `"Wizard, Sorcerer"`.
+fn list_field(object: &Map<String, Value>, keys: &[&str]) -> Vec<String> {
+    match field(object, keys) {
+        Some(Value::Array(items)) => items
+            .iter()
+            .map(text_of)
+            .filter(|item| !item.is_empty())
+            .collect(),
+        Some(Value::String(text)) => text
+            .split(',')
+            .map(|item| item.trim().to_string())
+            .filter(|item| !item.is_empty())
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn named_texts(object: &Map<String, Value>, keys: &[&str]) -> Vec<NamedText> {
+    let Some(Value::Array(items)) = field(object, keys) else {
+        return Vec::new();
+    };
+    items
+        .iter()
+        .filter_map(|item| {
+            let Value::Object(entry) = item else {
+                return None;
+            };
+            let name = text_field(entry, &["name"]);
+            let text = text_field(entry, &["desc", "description", "text"]);
+            (!name.is_empty() || !text.is_empty()).then_some(NamedText { name, text })
+        })
+        .collect()
+}
+
+/// Flattens the shapes SRD exports use for text: plain strings, paragraph
+/// arrays, `{ "name": ... }` references and `{ "walk": "30 ft." }` maps.
+fn text_of(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.trim().to_string(),
+        Value::Number(number) => number.to_string(),
+        Value::Bool(flag) => flag.to_string(),
+        Value::Array(items) => {
+            let parts: Vec<String> = items
+                .iter()
+                .map(text_of)
+                .filter(|part| !part.is_empty())
+                .collect();
+            // Paragraph arrays read as paragraphs; short codes such as
+            // `["V", "S", "M"]` and references read as a list.
+            let paragraphs = items.iter().all(Value::is_string)
+                && parts.iter().any(|part| part.chars().count() > 3);
+            let separator = if paragraphs { "\n\n" } else { ", " };
+            parts.join(separator)
+        }
+        Value::Object(object) => {
+            if let Some(name) = object.get("name").and_then(Value::as_str) {
+                return name.trim().to_string();
+            }
+            object
+                .iter()
+                .filter(|(_, value)| !value.is_null())
+                .map(|(key, value)| format!("{} {}", key.replace('_', " "), text_of(value)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        }
+        Value::Null => String::new(),
+    }
+}
+
+/// Reads numbers written as `13`, `"13 (natural armor)"`, `"1/4"` or
+/// `[{ "value": 13 }]`.
+fn number_of(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(number) => number.as_f64(),
+        Value::String(text) => parse_leading_number(text),
+        Value::Array(items) => items.first().and_then(number_of),
+        Value::Object(object) => object.get("value").and_then(number_of),
+        _ => None,
+    }
+}
+
+fn parse_leading_number(text: &str) -> Option<f64> {
+    let text = text.trim();
+    let end = text
+        .find(|ch: char| !(ch.is_ascii_digit() || ch == '.' || ch == '/'))
+        .unwrap_or(text.len());
+    let number = &text[..end];
+    match number.split_once('/') {
+        Some((numerator, denominator)) => {
+            let numerator: f64 = numerator.parse().ok()?;
+            let denominator: f64 = denominator.parse().ok()?;
+            (denominator != 0.0).then(|| numerator / denominator)
+        }
+        None => number.parse().ok(),
+    }
+}
+
+// --- Challenge rating ---
+
+/// Reads a challenge rating typed as `5`, `0.5` or `1/4`; blank means no bound.
+pub fn parse_challenge_rating(text: &str) -> Option<f32> {
+    parse_leading_number(text).map(|cr| cr.clamp(0.0, 30.0) as f32)
+}
+
+pub fn xp_for_challenge_rating(challenge_rating: f32) -> u32 {
+    match challenge_rating {
+        cr if cr <= 0.0 => XP_BY_CHALLENGE_RATING[0],
+        cr if cr <= 0.125 => 25,
+        cr if cr <= 0.25 => 50,
+        cr if cr <= 0.5 => 100,
+        cr => XP_BY_CHALLENGE_RATING[(cr.round() as usize).min(30)],
+    }
+}
+
+pub fn format_challenge_rating(challenge_rating: f32) -> String {
+    match challenge_rating {
+        cr if cr > 0.0 && cr <= 0.125 => "1/8".to_string(),
+        cr if cr > 0.125 && cr <= 0.25 => "1/4".to_string(),
+        cr if cr > 0.25 && cr < 1.0 => "1/2".to_string(),
+        cr => format!("{}", cr.round() as u32),
+    }
+}
+
+// --- Search ---
+
+fn matches_query(name: &str, query: &str) -> bool {
+    let query = query.trim().to_lowercase();
+    query.is_empty() || name.to_lowercase().contains(&query)
+}
+
+pub fn filter_monsters<'a>(
+    monsters: impl IntoIterator<Item = &'a CompendiumMonster>,
+    filter: &MonsterFilter,
+) -> Vec<&'a CompendiumMonster> {
+    monsters
+        .into_iter()
+        .filter(|monster| matches_query(&monster.name, &filter.query))
+        .filter(|monster| {
+            filter
+                .min_cr
+                .is_none_or(|min| monster.challenge_rating >= min)
+        })
+        .filter(|monster| {
+            filter
+                .max_cr
+                .is_none_or(|max| monster.challenge_rating <= max)
+        })
+        .filter(|monster| {
+            filter
+                .creature_type
+                .as_deref()
+                .is_none_or(|kind| monster.creature_type.eq_ignore_ascii_case(kind))
+        })
+        .collect()
+}
+
+pub fn filter_spells<'a>(
+    spells: impl IntoIterator<Item = &'a CompendiumSpell>,
+    filter: &SpellFilter,
+) -> Vec<&'a CompendiumSpell> {
+    spells
+        .into_iter()
+        .filter(|spell| matches_query(&spell.name, &filter.query))
+        .filter(|spell| filter.level.is_none_or(|level| spell.level == level))
+        .filter(|spell| {
+            filter
+                .school
+                .as_deref()
+                .is_none_or(|school| spell.school.eq_ignore_ascii_case(school))
+        })
+        .filter(|spell| {
+            filter.class.as_deref().is_none_or(|class| {
+                spell
+                    .classes
+                    .iter()
+                    .any(|spell_class| spell_class.eq_ignore_ascii_case(class))
+            })
+        })
+        .collect()
+}
+
+/// Distinct non-empty values, case-insensitively deduplicated and sorted.
+pub fn distinct_values<'a>(values: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    let mut seen = BTreeSet::new();
+    let mut distinct = Vec::new();
+    for value in values {
+        let value = value.trim();
+        if !value.is_empty() && seen.insert(value.to_lowercase()) {
+            distinct.push(value.to_string());
+        }
+    }
+    distinct.sort_by_key(|value| value.to_lowercase());
+    distinct
+}
+
+// --- Chat and sheets ---
+
+pub fn spell_level_label(level: u8) -> String {
+    match level {
+        0 => "Cantrip".to_string(),
+        1 => "1st level".to_string(),
+        2 => "2nd level".to_string(),
+        3 => "3rd level".to_string(),
+        level => format!("{level}th level"),
+    }
+}
+
+/// Spell card posted to chat; the description is cut to fit the message limit.
+pub fn spell_chat_card(spell: &CompendiumSpell) -> String {
+    let mut tags = Vec::new();
+    if spell.concentration {
+        tags.push("concentration");
+    }
+    if spell.ritual {
+        tags.push("ritual");
+    }
+    let tags = if tags.is_empty() {
+        String::new()
+    } else {
+        format!(" ({})", tags.join(", "))
+    };
+    let header = format!(
+        "✨ **{}** — {} {}{}\n{} · {} · {} · {}\n",
+        spell.name,
+        spell_level_label(spell.level),
+        spell.school.to_lowercase(),
+        tags,
+        spell.casting_time,
+        spell.range,
+        spell.components,
+        spell.duration,
+    );
+
+    let budget = MAX_CHAT_MESSAGE_LEN.saturating_sub(header.chars().count());
+    let description = spell.description.trim();
+    let body = if description.chars().count() <= budget {
+        description.to_string()
+    } else {
+        let mut cut: String = description.chars().take(budget.saturating_sub(1)).collect();
+        cut.push('…');
+        cut
+    };
+    let card = format!("{header}{body}");
+    card.chars().take(MAX_CHAT_MESSAGE_LEN).collect()
+}
+
+/// Values for a sheet built on the bundled 5e template, so a dropped monster
+/// arrives with its statistics filled in.
+pub fn monster_sheet_values(monster: &CompendiumMonster) -> BTreeMap<String, String> {
+    let mut values = BTreeMap::new();
+    let level = monster.challenge_rating.ceil().max(1.0) as u32;
+    let speed = parse_leading_number(&monster.speed.replace("walk ", "")).unwrap_or(30.0);
+    let [str_score, dex, con, int, wis, cha] = monster.abilities;
+    for (key, value) in [
+        ("class", monster.creature_type.clone()),
+        ("race", monster.size.clone()),
+        ("alignment", monster.alignment.clone()),
+        ("level", level.to_string()),
+        ("xp", monster.xp.to_string()),
+        ("str", str_score.to_string()),
+        ("dex", dex.to_string()),
+        ("con", con.to_string()),
+        ("int", int.to_string()),
+        ("wis", wis.to_string()),
+        ("cha", cha.to_string()),
+        ("ac", monster.armor_class.to_string()),
+        ("speed", (speed as u32).to_string()),
+        ("hp_max", monster.hit_points.to_string()),
+        ("hp", monster.hit_points.to_string()),
+        ("hit_dice", monster.hit_dice.clone()),
+    ] {
+        values.insert(key.to_string(), value);
+    }
+
+    let features: String = monster_features_text(monster)
+        .chars()
+        .take(MAX_FIELD_VALUE_LEN)
+        .collect();
+    values.insert("features".to_string(), features);
+    values
+}
+
+fn monster_features_text(monster: &CompendiumMonster) -> String {
+    let mut lines = vec![format!(
+        "CR {} ({} XP)",
+        format_challenge_rating(monster.challenge_rating),
+        monster.xp
+    )];
+    if !monster.senses.is_empty() {
+        lines.push(format!("Senses: {}", monster.senses));
+    }
+    if !monster.languages.is_empty() {
+        lines.push(format!("Languages: {}", monster.languages));
+    }
+    for (title, blocks) in [
+        ("", &monster.traits),
+        ("Actions", &monster.actions),
+        ("Legendary actions", &monster.legendary_actions),
+    ] {
+        if blocks.is_empty() {
+            continue;
+        }
+        if !title.is_empty() {
+            lines.push(format!("\n{title}"));
+        }
+        for block in blocks {
+            lines.push(format!("{}. {}", block.name, block.text));
+        }
+    }
+    lines.join("\n")
+}
+
+// --- Token art ---
+
+/// Up to two initials for the generated token image.
+pub fn token_initials(name: &str) -> String {
+    name.split_whitespace()
+        .filter_map(|word| word.chars().find(|ch| ch.is_alphanumeric()))
+        .take(2)
+        .flat_map(char::to_uppercase)
+        .collect()
+}
+
+/// Stable background colour for the generated token image.
+pub fn token_color(name: &str) -> &'static str {
+    let sum = name.bytes().fold(0usize, |acc, byte| {
+        acc.wrapping_mul(31).wrapping_add(byte as usize)
+    });
+    TOKEN_COLORS[sum % TOKEN_COLORS.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SRD_SAMPLE: &str = r#"{
+        "title": "SRD sample",
+        "monsters": [{
+            "name": "Goblin", "size": "Small", "type": "humanoid", "alignment": "neutral evil",
+            "armor_class": [{"type": "armor", "value": 15}], "hit_points": 7, "hit_dice": "2d6",
+            "speed": {"walk": "30 ft."}, "strength": 8, "dexterity": 14, "constitution": 10,
+            "intelligence": 10, "wisdom": 8, "charisma": 8, "challenge_rating": 0.25,
+            "actions": [{"name": "Scimitar", "desc": "Melee Weapon Attack: +4 to hit."}]
+        }, {
+            "name": "Adult Red Dragon", "size": "Huge", "type": "dragon",
+            "armor_class": "19 (natural armor)", "hit_points": 256, "cr": "17", "xp": 18000
+        }],
+        "spells": [{
+            "name": "Fireball", "level": 3, "school": {"name": "Evocation"},
+            "casting_time": "1 action", "range": "150 feet", "components": ["V", "S", "M"],
+            "material": "A tiny ball of bat guano and sulfur.", "duration": "Instantaneous",
+            "concentration": false, "classes": [{"name": "Sorcerer"}, {"name": "Wizard"}],
+            "desc": ["A bright streak flashes.", "Each creature takes 8d6 fire damage."]
+        }]
+    }"#;
+
+    #[test]
+    fn parses_srd_style_documents() {
+        let compendium = parse_compendium_file(SRD_SAMPLE.as_bytes(), "srd.json").unwrap();
+        assert_eq!(compendium.title, "SRD sample");
+        assert_eq!(compendium.monsters.len(), 2);
+
+        let dragon = &compendium.monsters[0];
+        assert_eq!(dragon.name, "Adult Red Dragon");
+        assert_eq!(dragon.armor_class, 19);
+        assert_eq!(dragon.challenge_rating, 17.0);
+        assert_eq!(dragon.footprint_cells(), 3);
+
+        let goblin = &compendium.monsters[1];
+        assert_eq!(goblin.armor_class, 15);
+        assert_eq!(goblin.xp, 50);
+        assert_eq!(goblin.speed, "walk 30 ft.");
+        assert_eq!(goblin.abilities, [8, 14, 10, 10, 8, 8]);
+        assert_eq!(goblin.actions[0].name, "Scimitar");
+
+        let fireball = &compendium.spells[0];
+        assert_eq!(fireball.level, 3);
+        assert_eq!(fireball.school, "Evocation");
+        assert_eq!(
+            fireball.components,
+            "V, S, M (A tiny ball of bat guano and sulfur.)"
+        );
+        assert_eq!(fireball.classes, vec!["Sorcerer", "Wizard"]);
+        assert!(fireball.description.contains("\n\n"));
+    }
+
+    #[test]
+    fn parses_flat_arrays_and_rejects_empty_files() {
+        let raw = r#"[
+            {"name": "Wolf", "challenge_rating": "1/4", "hit_points": 11},
+            {"name": "Light", "level": "Cantrip", "level_int": 0, "school": "evocation",
+             "dnd_class": "Bard, Cleric", "ritual": "no"}
+        ]"#;
+        let compendium = parse_compendium_file(raw.as_bytes(), "beasts.json").unwrap();
+        assert_eq!(compendium.title, "beasts");
+        assert_eq!(compendium.monsters[0].challenge_rating, 0.25);
+        assert_eq!(compendium.spells[0].classes, vec!["Bard", "Cleric"]);
+
+        assert_eq!(
+            parse_compendium_file(b"{\"items\": []}", "x.json"),
+            Err(CompendiumError::Empty)
+        );
+        assert_eq!(
+            parse_compendium_file(b"not json", "x.json"),
+            Err(CompendiumError::InvalidJson)
+        );
+    }
+
+    #[test]
+    fn filters_by_name_challenge_rating_and_type() {
+        let compendium = parse_compendium_file(SRD_SAMPLE.as_bytes(), "srd.json").unwrap();
+        let filter = MonsterFilter {
+            max_cr: Some(1.0),
+            ..MonsterFilter::default()
+        };
+        let found = filter_monsters(&compendium.monsters, &filter);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].name, "Goblin");
+
+        let filter = MonsterFilter {
+            query: "DRAG".to_string(),
+            creature_type: Some("Dragon".to_string()),
+            ..MonsterFilter::default()
+        };
+        assert_eq!(filter_monsters(&compendium.monsters, &filter).len(), 1);
+
+        let filter = SpellFilter {
+            class: Some("wizard".to_string()),
+            level: Some(3),
+            ..SpellFilter::default()
+        };
+        assert_eq!(filter_spells(&compendium.spells, &filter).len(), 1);
+        assert_eq!(
+            distinct_values(["Dragon", "dragon", "", "Beast"]),
+            vec!["Beast", "Dragon"]
+        );
+    }
+
+    #[test]
+    fn challenge_ratings_map_to_xp_and_labels() {
+        assert_eq!(xp_for_challenge_rating(0.0), 10);
+        assert_eq!(xp_for_challenge_rating(0.125), 25);
+        assert_eq!(xp_for_challenge_rating(0.5), 100);
+        assert_eq!(xp_for_challenge_rating(5.0), 1800);
+        assert_eq!(xp_for_challenge_rating(30.0), 155000);
+        assert_eq!(format_challenge_rating(0.25), "1/4");
+        assert_eq!(format_challenge_rating(12.0), "12");
+        assert_eq!(parse_challenge_rating(" 1/4"), Some(0.25));
+        assert_eq!(parse_challenge_rating(""), None);
+    }
+
+    #[test]
+    fn spell_card_fits_into_a_chat_message() {
+        let compendium = parse_compendium_file(SRD_SAMPLE.as_bytes(), "srd.json").unwrap();
+        let mut spell = compendium.spells[0].clone();
+        let card = spell_chat_card(&spell);
+        assert!(card.starts_with("✨ **Fireball** — 3rd level evocation"));
+        assert!(card.contains("150 feet"));
+
+        spell.description = "x".repeat(2000);
+        let card = spell_chat_card(&spell);
+        assert_eq!(card.chars().count(), MAX_CHAT_MESSAGE_LEN);
+        assert!(card.ends_with('…'));
+    }
+
+    #[test]
+    fn monster_sheet_values_fill_the_5e_template() {
+        let compendium = parse_compendium_file(SRD_SAMPLE.as_bytes(), "srd.json").unwrap();
+        let values = monster_sheet_values(&compendium.monsters[1]);
+        assert_eq!(values["ac"], "15");
+        assert_eq!(values["hp"], "7");
+        assert_eq!(values["dex"], "14");
+        assert_eq!(values["speed"], "30");
+        assert_eq!(values["level"], "1");
+        assert!(values["features"].contains("Scimitar. Melee Weapon Attack"));
+
+        assert_eq!(token_initials("Adult Red Dragon"), "AR");
+        assert_eq!(token_color("Goblin"), token_color("Goblin"));
+    }
+}
@@ -0,0 +1,57 @@
+use super::model::{token_color, token_initials};
+use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
+use leptos::wasm_bindgen::JsCast;
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement};
+
+const TOKEN_IMAGE_SIZE: u32 = 256;
+
+/// Renders a round token with the monster's initials and returns PNG bytes.
+/// The output only depends on the name, so repeated drops reuse one file hash.
+pub fn render_monster_token_png(name: &str) -> Result<Vec<u8>, String> {
+    let document = web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or_else(|| "document is unavailable".to_string())?;
+    let canvas: HtmlCanvasElement = document
+        .create_element("canvas")
+        .map_err(|error| format!("failed to create canvas: {error:?}"))?
+        .dyn_into()
+        .map_err(|_| "canvas element has an unexpected type".to_string())?;
+    canvas.set_width(TOKEN_IMAGE_SIZE);
+    canvas.set_height(TOKEN_IMAGE_SIZE);
+    let context: CanvasRenderingContext2d = canvas
+        .get_context("2d")
+        .map_err(|error| format!("failed to get canvas context: {error:?}"))?
+        .ok_or_else(|| "canvas 2d context is unavailable".to_string())?
+        .dyn_into()
+        .map_err(|_| "canvas context has an unexpected type".to_string())?;
+
+    let center = f64::from(TOKEN_IMAGE_SIZE) / 2.0;
+    context.begin_path();
+    context
+        .arc(center, center, center - 8.0, 0.0, std::f64::consts::TAU)
+        .map_err(|error| format!("failed to draw token: {error:?}"))?;
+    context.set_fill_style_str(token_color(name));
+    context.fill();
+    context.set_line_width(8.0);
+    context.set_stroke_style_str("#f4efe6");
+    context.stroke();
+
+    context.set_fill_style_str("#f4efe6");
+    context.set_font("bold 104px sans-serif");
+    context.set_text_align("center");
+    context.set_text_baseline("middle");
+    context
+        .fill_text(&token_initials(name), center, center + 4.0)
+        .map_err(|error| format!("failed to draw token initials: {error:?}"))?;
+
+    let data_url = canvas
+        .to_data_url_with_type("image/png")
+        .map_err(|error| format!("failed to encode token image: {error:?}"))?;
+    let encoded = data_url
+        .split_once(',')
+        .map(|(_, encoded)| encoded)
+        .ok_or_else(|| "token image data URL is malformed".to_string())?;
+    BASE64
+        .decode(encoded)
+        .map_err(|error| format!("failed to decode token image: {error}"))
+}
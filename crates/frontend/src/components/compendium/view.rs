@@ -0,0 +1,622 @@
+use super::model::{
+    COMPENDIUM_FILE_ACCEPT, CompendiumMonster, CompendiumSpell, MAX_LISTED_ENTRIES,
+    distinct_values, filter_monsters, filter_spells, format_challenge_rating,
+    parse_compendium_file, spell_chat_card, spell_level_label,
+};
+use super::view_model::{CompendiumTab, CompendiumWindowViewModel};
+use crate::components::draggable_window::DraggableWindow;
+use crate::components::websocket::{FileTransferState, WsSender};
+use crate::config::Theme;
+use crate::i18n::i18n::{t, t_string, use_i18n};
+use leptos::logging::log;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use leptos::wasm_bindgen::JsCast;
+use shared::events::{
+    ChatMessagePayload, ClientEvent, CompendiumImportPayload, CompendiumRemovePayload,
+    CompendiumSource, acts_as_game_master,
+};
+use std::collections::HashSet;
+use web_sys::{Event, HtmlInputElement, MouseEvent};
+
+const COMPENDIUM_BODY_FONT_SIZE: &str = "clamp(0.88rem, 0.85rem + 0.12vw, 0.96rem)";
+const COMPENDIUM_META_FONT_SIZE: &str = "clamp(0.74rem, 0.71rem + 0.12vw, 0.82rem)";
+const COMPENDIUM_BUTTON_FONT_SIZE: &str = "clamp(0.82rem, 0.79rem + 0.12vw, 0.92rem)";
+
+#[component]
+pub fn CompendiumWindow(
+    #[prop(into)] is_open: RwSignal<bool>,
+    #[prop(into)] compendium_sources: RwSignal<Vec<CompendiumSource>>,
+    #[prop(into)] game_master: RwSignal<Option<String>>,
+    file_transfer: FileTransferState,
+    ws_sender: ReadSignal<Option<WsSender>>,
+    username: ReadSignal<String>,
+    /// Starts dragging a monster towards the scene board.
+    on_start_monster_drag: Callback<CompendiumMonster>,
+    /// Adds a monster to the encounter being built.
+    #[prop(optional)]
+    on_add_to_encounter: Option<Callback<CompendiumMonster>>,
+    #[prop(into, optional)] is_active: Signal<bool>,
+    #[prop(optional)] on_focus: Option<Callback<()>>,
+    theme: Theme,
+) -> impl IntoView {
+    let i18n = use_i18n();
+    let vm = CompendiumWindowViewModel::new();
+    let loading_hashes = StoredValue::new(HashSet::<String>::new());
+
+    let send_event = move |event: ClientEvent| {
+        if let Some(sender) = ws_sender.get_untracked() {
+            let _ = sender.try_send_event(event);
+        }
+    };
+
+    let viewer_is_gm =
+        Memo::new(move |_| acts_as_game_master(game_master.get().as_deref(), &username.get()));
+
+    // Parse every room compendium that is available locally and ask peers for
+    // the rest; `file_urls` changes once a requested file has arrived.
+    let file_transfer_for_load = file_transfer.clone();
+    Effect::new(move |_| {
+        if !is_open.get() {
+            return;
+        }
+        let sources = compendium_sources.get();
+        file_transfer_for_load.file_urls.track();
+        vm.retain_sources(
+            &sources
+                .iter()
+                .map(|source| source.file.hash.clone())
+                .collect::<Vec<_>>(),
+        );
+
+        for source in sources {
+            let hash = source.file.hash.clone();
+            let already_known = vm
+                .compendia
+                .with_untracked(|compendia| compendia.contains_key(&hash))
+                || loading_hashes.with_value(|loading| loading.contains(&hash));
+            if already_known {
+                continue;
+            }
+            loading_hashes.update_value(|loading| {
+                loading.insert(hash.clone());
+            });
+            let file_transfer = file_transfer_for_load.clone();
+            spawn_local(async move {
+                let user = username.get_untracked();
+                let sender = ws_sender.get_untracked();
+                match file_transfer.load_local_bytes(&hash).await {
+                    Ok(Some(bytes)) => {
+                        file_transfer.announce_local_files(
+                            std::slice::from_ref(&source.file),
+                            user,
+                            sender,
+                        );
+                        match parse_compendium_file(&bytes, &source.file.file_name) {
+                            Ok(compendium) => vm.compendia.update(|compendia| {
+                                compendia.insert(hash.clone(), compendium);
+                            }),
+                            Err(error) => {
+                                log!("Failed to parse compendium '{}': {:?}", hash, error)
+                            }
+                        }
+                    }
+                    Ok(None) => file_transfer.request_file(source.file.clone(), user, sender),
+                    Err(error) => log!("Failed to load compendium '{}': {}", hash, error),
+                }
+                loading_hashes.update_value(|loading| {
+                    loading.remove(&hash);
+                });
+            });
+        }
+    });
+
+    let monsters = Memo::new(move |_| {
+        let filter = vm.monster_filter();
+        vm.compendia.with(|compendia| {
+            let mut found: Vec<CompendiumMonster> =
+                filter_monsters(compendia.values().flat_map(|c| c.monsters.iter()), &filter)
+                    .into_iter()
+                    .cloned()
+                    .collect();
+            found.sort_by(|a, b| a.name.cmp(&b.name));
+            found
+        })
+    });
+    let spells = Memo::new(move |_| {
+        let filter = vm.spell_filter();
+        vm.compendia.with(|compendia| {
+            let mut found: Vec<CompendiumSpell> =
+                filter_spells(compendia.values().flat_map(|c| c.spells.iter()), &filter)
+                    .into_iter()
+                    .cloned()
+                    .collect();
+            found.sort_by(|a, b| a.level.cmp(&b.level).then_with(|| a.name.cmp(&b.name)));
+            found
+        })
+    });
+    let creature_types = Memo::new(move |_| {
+        vm.compendia.with(|compendia| {
+            distinct_values(compendia.values().flat_map(|c| {
+                c.monsters
+                    .iter()
+                    .map(|monster| monster.creature_type.as_str())
+            }))
+        })
+    });
+    let spell_schools = Memo::new(move |_| {
+        vm.compendia.with(|compendia| {
+            distinct_values(
+                compendia
+                    .values()
+                    .flat_map(|c| c.spells.iter().map(|spell| spell.school.as_str())),
+            )
+        })
+    });
+    let spell_classes = Memo::new(move |_| {
+        vm.compendia.with(|compendia| {
+            distinct_values(compendia.values().flat_map(|c| {
+                c.spells
+                    .iter()
+                    .flat_map(|spell| spell.classes.iter().map(String::as_str))
+            }))
+        })
+    });
+    let selected_monster = Memo::new(move |_| {
+        let name = vm.selected_monster.get()?;
+        monsters
+            .get()
+            .into_iter()
+            .find(|monster| monster.name == name)
+    });
+
+    let post_spell = move |spell: CompendiumSpell| {
+        send_event(ClientEvent::ChatMessage(ChatMessagePayload {
+            payload: spell_chat_card(&spell),
+            username: username.get_untracked(),
+            attachments: Vec::new(),
+        }));
+    };
+
+    let file_transfer_for_import = file_transfer.clone();
+    let on_file_selected = move |event: Event| {
+        let Some(input) = event
+            .target()
+            .and_then(|target| target.dyn_into::<HtmlInputElement>().ok())
+        else {
+            return;
+        };
+        let Some(file) = input.files().and_then(|files| files.get(0)) else {
+            return;
+        };
+        input.set_value("");
+        let invalid_message = t_string!(i18n, compendium.error_import).to_string();
+        let file_transfer = file_transfer_for_import.clone();
+        vm.is_importing.set(true);
+        vm.import_error.set(None);
+        spawn_local(async move {
+            let imported = async {
+                let file_ref = file_transfer
+                    .import_browser_file(file, username.get_untracked(), ws_sender.get_untracked())
+                    .await?;
+                let bytes = file_transfer
+                    .load_local_bytes(&file_ref.hash)
+                    .await?
+                    .unwrap_or_default();
+                let compendium = parse_compendium_file(&bytes, &file_ref.file_name)
+                    .map_err(|error| format!("{error:?}"))?;
+                Ok::<_, String>((file_ref, compendium))
+            }
+            .await;
+            vm.is_importing.set(false);
+            match imported {
+                Ok((file, compendium)) => {
+                    let source = CompendiumSource {
+                        file: file.clone(),
+                        title: compendium.title.clone(),
+                        monster_count: compendium.monsters.len() as u32,
+                        spell_count: compendium.spells.len() as u32,
+                    };
+                    vm.compendia.update(|compendia| {
+                        compendia.insert(file.hash.clone(), compendium);
+                    });
+                    send_event(ClientEvent::CompendiumImport(CompendiumImportPayload {
+                        source,
+                        actor: username.get_untracked(),
+                    }));
+                }
+                Err(error) => vm
+                    .import_error
+                    .set(Some(format!("{invalid_message} ({error})"))),
+            }
+        });
+    };
+
+    let input_style = format!(
+        "padding: 0.45rem; background: {}; color: {}; border: 0.0625rem solid {}; border-radius: 0.5rem; font-size: {}; min-width: 0;",
+        theme.ui_bg_secondary, theme.ui_text_primary, theme.ui_border, COMPENDIUM_BODY_FONT_SIZE
+    );
+    let tab_button_style = {
+        let theme = theme.clone();
+        move |active: bool| {
+            format!(
+                "flex: 1; padding: 0.45rem; background: {}; color: {}; border: 0.0625rem solid {}; border-radius: 0.5rem; cursor: pointer; font-size: {};",
+                if active {
+                    theme.ui_bg_secondary
+                } else {
+                    theme.ui_bg_primary
+                },
+                theme.ui_text_primary,
+                if active {
+                    theme.ui_success
+                } else {
+                    theme.ui_border
+                },
+                COMPENDIUM_BUTTON_FONT_SIZE
+            )
+        }
+    };
+    let tab_monsters_style = tab_button_style;
+    let tab_spells_style = tab_button_style;
+    let row_style = format!(
+        "display: flex; align-items: center; gap: 0.5rem; padding: 0.45rem 0.6rem; border: 0.0625rem solid {}; border-radius: 0.5rem; color: {}; font-size: {};",
+        theme.ui_border, theme.ui_text_primary, COMPENDIUM_BODY_FONT_SIZE
+    );
+    let meta_style = format!(
+        "color: {}; font-size: {};",
+        theme.ui_text_secondary, COMPENDIUM_META_FONT_SIZE
+    );
+    let hint_style = format!(
+        "color: {}; font-size: {}; font-style: italic;",
+        theme.ui_text_muted, COMPENDIUM_META_FONT_SIZE
+    );
+    let monster_filter_style = input_style.clone();
+    let spell_filter_style = input_style.clone();
+    let monster_row_style = row_style.clone();
+    let spell_row_style = row_style.clone();
+    let monster_meta_style = meta_style.clone();
+    let spell_meta_style = meta_style.clone();
+    let detail_meta_style = meta_style.clone();
+    let monster_hint_style = hint_style.clone();
+    let spell_hint_style = hint_style.clone();
+    let detail_theme = theme.clone();
+
+    view! {
+        <DraggableWindow
+            is_open=is_open
+            title=move || t_string!(i18n, compendium.title)
+            initial_x=300
+            initial_y=110
+            initial_width=780
+            initial_height=600
+            min_width=480
+            min_height=360
+            is_active=is_active
+            on_focus=on_focus.unwrap_or_else(|| Callback::new(|_| {}))
+            theme=theme.clone()
+        >
+            <div style="display: flex; flex-direction: column; flex: 1; min-height: 0; padding: 1rem; gap: 0.75rem;">
+                // Sources
+                <div style="display: flex; flex-wrap: wrap; align-items: center; gap: 0.5rem;">
+                    <strong style=format!("color: {};", theme.ui_text_primary)>
+                        {move || t!(i18n, compendium.sources_title)}
+                    </strong>
+                    {move || {
+                        let sources = compendium_sources.get();
+                        if sources.is_empty() {
+                            return view! {
+                                <span style=hint_style.clone()>{t!(i18n, compendium.sources_empty)}</span>
+                            }.into_any();
+                        }
+                        let is_gm = viewer_is_gm.get();
+                        let loaded = vm.compendia.with(|compendia| compendia.keys().cloned().collect::<HashSet<_>>());
+                        sources.into_iter().map(|source| {
+                            let hash = source.file.hash.clone();
+                            let is_loaded = loaded.contains(&hash);
+                            view! {
+                                <span style=format!(
+                                    "display: inline-flex; align-items: center; gap: 0.4rem; padding: 0.3rem 0.6rem; border: 0.0625rem solid {}; border-radius: 1rem; color: {}; font-size: {};",
+                                    theme.ui_border, theme.ui_text_primary, COMPENDIUM_META_FONT_SIZE
+                                )>
+                                    {format!("{} · {} / {}", source.title, source.monster_count, source.spell_count)}
+                                    {(!is_loaded).then(|| view! {
+                                        <em style=format!("color: {};", theme.ui_text_muted)>{t!(i18n, compendium.loading)}</em>
+                                    })}
+                                    {is_gm.then(|| view! {
+                                        <button
+                                            title=move || t_string!(i18n, compendium.remove_button)
+                                            on:click=move |_| send_event(ClientEvent::CompendiumRemove(
+                                                CompendiumRemovePayload {
+                                                    hash: hash.clone(),
+                                                    actor: username.get_untracked(),
+                                                },
+                                            ))
+                                            style=format!("background: none; border: none; cursor: pointer; color: {};", theme.ui_button_danger)
+                                        >
+                                            "✕"
+                                        </button>
+                                    })}
+                                </span>
+                            }
+                        }).collect_view().into_any()
+                    }}
+                    {move || viewer_is_gm.get().then(|| view! {
+                        <label style=format!(
+                            "margin-left: auto; padding: 0.4rem 0.75rem; border: 0.0625rem dashed {}; border-radius: 0.5rem; cursor: pointer; color: {}; font-size: {};",
+                            theme.ui_border, theme.ui_text_secondary, COMPENDIUM_BUTTON_FONT_SIZE
+                        )>
+                            {move || if vm.is_importing.get() {
+                                t_string!(i18n, compendium.importing)
+                            } else {
+                                t_string!(i18n, compendium.import_button)
+                            }}
+                            <input type="file" accept=COMPENDIUM_FILE_ACCEPT on:change=on_file_selected.clone() style="display: none;" />
+                        </label>
+                    })}
+                </div>
+                {move || vm.import_error.get().map(|error| view! {
+                    <div style=format!("color: {}; font-size: {};", theme.ui_button_danger, COMPENDIUM_META_FONT_SIZE)>
+                        {error}
+                    </div>
+                })}
+
+                // Tabs + shared search
+                <div style="display: flex; gap: 0.5rem;">
+                    <button
+                        on:click=move |_| vm.tab.set(CompendiumTab::Monsters)
+                        style=move || tab_monsters_style(vm.tab.get() == CompendiumTab::Monsters)
+                    >
+                        {move || t!(i18n, compendium.tab_monsters)}
+                    </button>
+                    <button
+                        on:click=move |_| vm.tab.set(CompendiumTab::Spells)
+                        style=move || tab_spells_style(vm.tab.get() == CompendiumTab::Spells)
+                    >
+                        {move || t!(i18n, compendium.tab_spells)}
+                    </button>
+                </div>
+                <input
+                    type="search"
+                    placeholder=move || t_string!(i18n, compendium.search_placeholder)
+                    prop:value=move || vm.query.get()
+                    on:input=move |ev| vm.query.set(event_target_value(&ev))
+                    style=input_style.clone()
+                />
+
+                {move || match vm.tab.get() {
+                    CompendiumTab::Monsters => {
+                        let filter_style = monster_filter_style.clone();
+                        let row_style = monster_row_style.clone();
+                        let meta_style = monster_meta_style.clone();
+                        let detail_meta_style = detail_meta_style.clone();
+                        let detail_theme = detail_theme.clone();
+                        let hint_style = monster_hint_style.clone();
+                        view! {
+                            <div style="display: flex; gap: 0.5rem;">
+                                <input
+                                    type="text"
+                                    placeholder=move || t_string!(i18n, compendium.min_cr_placeholder)
+                                    prop:value=move || vm.min_cr.get()
+                                    on:input=move |ev| vm.min_cr.set(event_target_value(&ev))
+                                    style=format!("{filter_style} width: 6rem;")
+                                />
+                                <input
+                                    type="text"
+                                    placeholder=move || t_string!(i18n, compendium.max_cr_placeholder)
+                                    prop:value=move || vm.max_cr.get()
+                                    on:input=move |ev| vm.max_cr.set(event_target_value(&ev))
+                                    style=format!("{filter_style} width: 6rem;")
+                                />
+                                <select
+                                    prop:value=move || vm.creature_type.get()
+                                    on:change=move |ev| vm.creature_type.set(event_target_value(&ev))
+                                    style=format!("{filter_style} flex: 1;")
+                                >
+                                    <option value="">{move || t!(i18n, compendium.any_type)}</option>
+                                    {move || creature_types.get().into_iter().map(|kind| view! {
+                                        <option value=kind.clone()>{kind.clone()}</option>
+                                    }).collect_view()}
+                                </select>
+                            </div>
+                            {move || viewer_is_gm.get().then(|| view! {
+                                <div style=hint_style.clone()>{t!(i18n, compendium.drag_hint)}</div>
+                            })}
+                            <div style="display: flex; flex: 1; min-height: 0; gap: 0.75rem;">
+                                <div style="flex: 1; overflow-y: auto; display: flex; flex-direction: column; gap: 0.35rem;">
+                                    {move || {
+                                        let found = monsters.get();
+                                        if found.is_empty() {
+                                            return view! {
+                                                <div style=format!("color: {}; font-style: italic;", theme.ui_text_muted)>
+                                                    {t!(i18n, compendium.no_results)}
+                                                </div>
+                                            }.into_any();
+                                        }
+                                        let truncated = found.len() > MAX_LISTED_ENTRIES;
+                                        let is_gm = viewer_is_gm.get();
+                                        let rows = found.into_iter().take(MAX_LISTED_ENTRIES).map(|monster| {
+                                            let name = monster.name.clone();
+                                            let meta = format!(
+                                                "CR {} · {} {}",
+                                                format_challenge_rating(monster.challenge_rating),
+                                                monster.size,
+                                                monster.creature_type
+                                            );
+                                            let is_selected = {
+                                                let name = name.clone();
+                                                move || vm.selected_monster.get().as_deref() == Some(name.as_str())
+                                            };
+                                            view! {
+                                                <div
+                                                    on:click={
+                                                        let name = name.clone();
+                                                        move |_| vm.selected_monster.set(Some(name.clone()))
+                                                    }
+                                                    on:mousedown=move |event: MouseEvent| {
+                                                        if is_gm && event.button() == 0 {
+                                                            event.prevent_default();
+                                                            on_start_monster_drag.run(monster.clone());
+                                                        }
+                                                    }
+                                                    style=format!(
+                                                        "{row_style} cursor: {}; background: {};",
+                                                        if is_gm { "grab" } else { "pointer" },
+                                                        if is_selected() { theme.ui_bg_secondary } else { theme.ui_bg_primary }
+                                                    )
+                                                >
+                                                    <span style="flex: 1; font-weight: 600;">{name.clone()}</span>
+                                                    <span style=meta_style.clone()>{meta}</span>
+                                                </div>
+                                            }
+                                        }).collect_view();
+                                        view! {
+                                            {rows}
+                                            {truncated.then(|| view! {
+                                                <div style=format!("color: {}; font-style: italic;", theme.ui_text_muted)>
+                                                    {t!(i18n, compendium.more_results)}
+                                                </div>
+                                            })}
+                                        }.into_any()
+                                    }}
+                                </div>
+                                {move || selected_monster.get().map(|monster| {
+                                    let abilities = ["STR", "DEX", "CON", "INT", "WIS", "CHA"]
+                                        .iter()
+                                        .zip(monster.abilities)
+                                        .map(|(label, score)| format!("{label} {score}"))
+                                        .collect::<Vec<_>>()
+                                        .join(" · ");
+                                    view! {
+                                        <div style=format!(
+                                            "width: 42%; overflow-y: auto; padding: 0.75rem; border: 0.0625rem solid {}; border-radius: 0.5rem; color: {}; font-size: {}; display: flex; flex-direction: column; gap: 0.4rem;",
+                                            detail_theme.ui_border, detail_theme.ui_text_primary, COMPENDIUM_BODY_FONT_SIZE
+                                        )>
+                                            <div style="display: flex; align-items: center; gap: 0.5rem;">
+                                                <strong style="flex: 1;">{monster.name.clone()}</strong>
+                                                {on_add_to_encounter.map(|on_add| {
+                                                    let monster = monster.clone();
+                                                    view! {
+                                                        <button
+                                                            on:click=move |_| on_add.run(monster.clone())
+                                                            style=format!(
+                                                                "padding: 0.3rem 0.6rem; background: none; color: {}; border: 0.0625rem solid {}; border-radius: 0.5rem; cursor: pointer; font-size: {};",
+                                                                detail_theme.ui_text_secondary, detail_theme.ui_border, COMPENDIUM_META_FONT_SIZE
+                                                            )
+                                                        >
+                                                            {move || t!(i18n, compendium.add_to_encounter)}
+                                                        </button>
+                                                    }
+                                                })}
+                                            </div>
+                                            <span style=detail_meta_style.clone()>
+                                                {format!("{} {}, {}", monster.size, monster.creature_type, monster.alignment)}
+                                            </span>
+                                            <span>
+                                                {move || t!(i18n, compendium.armor_class)}
+                                                {format!(" {} · ", monster.armor_class)}
+                                                {move || t!(i18n, compendium.hit_points)}
+                                                {format!(" {} ({}) · ", monster.hit_points, monster.hit_dice)}
+                                                {move || t!(i18n, compendium.challenge)}
+                                                {format!(" {} ({} XP)", format_challenge_rating(monster.challenge_rating), monster.xp)}
+                                            </span>
+                                            <span>
+                                                {move || t!(i18n, compendium.speed)}
+                                                {format!(": {}", monster.speed)}
+                                            </span>
+                                            <span style=detail_meta_style.clone()>{abilities}</span>
+                                            {monster.traits.iter().map(|block| view! {
+                                                <div><em>{format!("{}. ", block.name)}</em>{block.text.clone()}</div>
+                                            }).collect_view()}
+                                            {(!monster.actions.is_empty()).then(|| view! {
+                                                <strong>{move || t!(i18n, compendium.actions)}</strong>
+                                            })}
+                                            {monster.actions.iter().map(|block| view! {
+                                                <div><em>{format!("{}. ", block.name)}</em>{block.text.clone()}</div>
+                                            }).collect_view()}
+                                        </div>
+                                    }
+                                })}
+                            </div>
+                        }.into_any()
+                    }
+                    CompendiumTab::Spells => {
+                        let filter_style = spell_filter_style.clone();
+                        let row_style = spell_row_style.clone();
+                        let meta_style = spell_meta_style.clone();
+                        view! {
+                            <div style="display: flex; gap: 0.5rem;">
+                                <select
+                                    prop:value=move || vm.spell_level.get()
+                                    on:change=move |ev| vm.spell_level.set(event_target_value(&ev))
+                                    style=format!("{filter_style} flex: 1;")
+                                >
+                                    <option value="">{move || t!(i18n, compendium.any_level)}</option>
+                                    {(0..=9u8).map(|level| view! {
+                                        <option value=level.to_string()>{spell_level_label(level)}</option>
+                                    }).collect_view()}
+                                </select>
+                                <select
+                                    prop:value=move || vm.spell_school.get()
+                                    on:change=move |ev| vm.spell_school.set(event_target_value(&ev))
+                                    style=format!("{filter_style} flex: 1;")
+                                >
+                                    <option value="">{move || t!(i18n, compendium.any_school)}</option>
+                                    {move || spell_schools.get().into_iter().map(|school| view! {
+                                        <option value=school.clone()>{school.clone()}</option>
+                                    }).collect_view()}
+                                </select>
+                                <select
+                                    prop:value=move || vm.spell_class.get()
+                                    on:change=move |ev| vm.spell_class.set(event_target_value(&ev))
+                                    style=format!("{filter_style} flex: 1;")
+                                >
+                                    <option value="">{move || t!(i18n, compendium.any_class)}</option>
+                                    {move || spell_classes.get().into_iter().map(|class| view! {
+                                        <option value=class.clone()>{class.clone()}</option>
+                                    }).collect_view()}
+                                </select>
+                            </div>
+                            <div style=spell_hint_style.clone()>{move || t!(i18n, compendium.spell_hint)}</div>
+                            <div style="flex: 1; min-height: 0; overflow-y: auto; display: flex; flex-direction: column; gap: 0.35rem;">
+                                {move || {
+                                    let found = spells.get();
+                                    if found.is_empty() {
+                                        return view! {
+                                            <div style=format!("color: {}; font-style: italic;", theme.ui_text_muted)>
+                                                {t!(i18n, compendium.no_results)}
+                                            </div>
+                                        }.into_any();
+                                    }
+                                    let truncated = found.len() > MAX_LISTED_ENTRIES;
+                                    let rows = found.into_iter().take(MAX_LISTED_ENTRIES).map(|spell| {
+                                        let meta = format!("{} · {}", spell_level_label(spell.level), spell.school);
+                                        let name = spell.name.clone();
+                                        let description = spell.description.clone();
+                                        view! {
+                                            <button
+                                                title=description
+                                                on:click=move |_| post_spell(spell.clone())
+                                                style=format!("{row_style} background: {}; cursor: pointer; text-align: left;", theme.ui_bg_primary)
+                                            >
+                                                <span style="flex: 1; font-weight: 600;">{name}</span>
+                                                <span style=meta_style.clone()>{meta}</span>
+                                            </button>
+                                        }
+                                    }).collect_view();
+                                    view! {
+                                        {rows}
+                                        {truncated.then(|| view! {
+                                            <div style=format!("color: {}; font-style: italic;", theme.ui_text_muted)>
+                                                {t!(i18n, compendium.more_results)}
+                                            </div>
+                                        })}
+                                    }.into_any()
+                                }}
+                            </div>
+                        }.into_any()
+                    }
+                }}
+            </div>
+        </DraggableWindow>
+    }
+}
@@ -0,0 +1,129 @@
+use super::model::{Compendium, MonsterFilter, SpellFilter, parse_challenge_rating};
+use leptos::prelude::*;
+use std::collections::BTreeMap;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CompendiumTab {
+    Monsters,
+    Spells,
+}
+
+/// Reactive state for the compendium browser: loaded files, tab and filters.
+#[derive(Clone, Copy)]
+pub struct CompendiumWindowViewModel {
+    /// Parsed compendia by file hash.
+    pub compendia: RwSignal<BTreeMap<String, Compendium>>,
+    pub tab: RwSignal<CompendiumTab>,
+    pub query: RwSignal<String>,
+    pub min_cr: RwSignal<String>,
+    pub max_cr: RwSignal<String>,
+    pub creature_type: RwSignal<String>,
+    pub spell_level: RwSignal<String>,
+    pub spell_school: RwSignal<String>,
+    pub spell_class: RwSignal<String>,
+    pub selected_monster: RwSignal<Option<String>>,
+    pub is_importing: RwSignal<bool>,
+    pub import_error: RwSignal<Option<String>>,
+}
+
+impl CompendiumWindowViewModel {
+    pub fn new() -> Self {
+        Self {
+            compendia: RwSignal::new(BTreeMap::new()),
+            tab: RwSignal::new(CompendiumTab::Monsters),
+            query: RwSignal::new(String::new()),
+            min_cr: RwSignal::new(String::new()),
+            max_cr: RwSignal::new(String::new()),
+            creature_type: RwSignal::new(String::new()),
+            spell_level: RwSignal::new(String::new()),
+            spell_school: RwSignal::new(String::new()),
+            spell_class: RwSignal::new(String::new()),
+            selected_monster: RwSignal::new(None),
+            is_importing: RwSignal::new(false),
+            import_error: RwSignal::new(None),
+        }
+    }
+
+    pub fn monster_filter(&self) -> MonsterFilter {
+        MonsterFilter {
+            query: self.query.get(),
+            min_cr: parse_challenge_rating(&self.min_cr.get()),
+            max_cr: parse_challenge_rating(&self.max_cr.get()),
+            creature_type: non_empty(self.creature_type.get()),
+        }
+    }
+
+    pub fn spell_filter(&self) -> SpellFilter {
+        SpellFilter {
+            query: self.query.get(),
+            level: self.spell_level.get().trim().parse().ok(),
+            school: non_empty(self.spell_school.get()),
+            class: non_empty(self.spell_class.get()),
+        }
+    }
+
+    /// Drops parsed compendia whose source was removed from the room.
+    pub fn retain_sources(&self, hashes: &[String]) {
+        let stale = self
+            .compendia
+            .with_untracked(|compendia| compendia.keys().any(|hash| !hashes.contains(hash)));
+        if stale {
+            self.compendia
+                .update(|compendia| compendia.retain(|hash, _| hashes.contains(hash)));
+        }
+    }
+}
+
+fn non_empty(value: String) -> Option<String> {
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use leptos::reactive::owner::Owner;
+
+    #[test]
+    fn filters_are_built_from_the_form() {
+        let owner = Owner::new();
+        owner.with(|| {
+            let vm = CompendiumWindowViewModel::new();
+            assert_eq!(vm.monster_filter(), MonsterFilter::default());
+
+            vm.query.set("orc".to_string());
+            vm.min_cr.set("1/2".to_string());
+            vm.creature_type.set("  ".to_string());
+            vm.spell_level.set("3".to_string());
+            vm.spell_class.set("Wizard".to_string());
+
+            let monsters = vm.monster_filter();
+            assert_eq!(monsters.min_cr, Some(0.5));
+            assert_eq!(monsters.max_cr, None);
+            assert_eq!(monsters.creature_type, None);
+
+            let spells = vm.spell_filter();
+            assert_eq!(spells.query, "orc");
+            assert_eq!(spells.level, Some(3));
+            assert_eq!(spells.class.as_deref(), Some("Wizard"));
+        });
+    }
+
+    #[test]
+    fn removed_sources_are_forgotten() {
+        let owner = Owner::new();
+        owner.with(|| {
+            let vm = CompendiumWindowViewModel::new();
+            vm.compendia.update(|compendia| {
+                compendia.insert("a".to_string(), Compendium::default());
+                compendia.insert("b".to_string(), Compendium::default());
+            });
+
+            vm.retain_sources(&["b".to_string()]);
+            assert_eq!(
+                vm.compendia.get_untracked().keys().collect::<Vec<_>>(),
+                vec!["b"]
+            );
+        });
+    }
+}
@@ -53,6 +53,53 @@ pub fn apply_resize(
     }
 }
 
+/// Gap kept between a window and the edges of the browser viewport.
+pub const VIEWPORT_MARGIN: i32 = 8;
+
+/// Pulls a window back inside the viewport, shrinking it when the viewport is
+/// smaller, e.g. after a tablet rotates to portrait.
+pub fn fit_window_to_viewport(
+    position: WindowPosition,
+    size: WindowSize,
+    viewport_width: i32,
+    viewport_height: i32,
+) -> (WindowPosition, WindowSize) {
+    let size = WindowSize {
+        width: size.width.min(viewport_width - 2 * VIEWPORT_MARGIN).max(0),
+        height: size
+            .height
+            .min(viewport_height - 2 * VIEWPORT_MARGIN)
+            .max(0),
+    };
+    let position = WindowPosition {
+        x: position
+            .x
+            .min(viewport_width - VIEWPORT_MARGIN - size.width)
+            .max(VIEWPORT_MARGIN),
+        y: position
+            .y
+            .min(viewport_height - VIEWPORT_MARGIN - size.height)
+            .max(VIEWPORT_MARGIN),
+    };
+    (position, size)
+}
+
+/// Geometry of a window shown as a full-screen sheet on compact viewports.
+pub fn compact_window_geometry(
+    viewport_width: i32,
+    viewport_height: i32,
+) -> (WindowPosition, WindowSize) {
+    fit_window_to_viewport(
+        WindowPosition { x: 0, y: 0 },
+        WindowSize {
+            width: viewport_width,
+            height: viewport_height,
+        },
+        viewport_width,
+        viewport_height,
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -115,4 +162,63 @@ mod tests {
         };
         assert_eq!(size.clamp(300, 200), size);
     }
+
+    #[test]
+    fn fit_window_keeps_a_visible_window_as_is() {
+        let position = WindowPosition { x: 100, y: 80 };
+        let size = WindowSize {
+            width: 400,
+            height: 300,
+        };
+        assert_eq!(
+            fit_window_to_viewport(position, size, 1280, 800),
+            (position, size)
+        );
+    }
+
+    #[test]
+    fn fit_window_pulls_an_offscreen_window_back() {
+        let size = WindowSize {
+            width: 400,
+            height: 300,
+        };
+        let (position, _) =
+            fit_window_to_viewport(WindowPosition { x: 1100, y: -50 }, size, 1280, 800);
+        assert_eq!(position, WindowPosition { x: 872, y: 8 });
+    }
+
+    #[test]
+    fn fit_window_shrinks_a_window_larger_than_the_viewport() {
+        let (position, size) = fit_window_to_viewport(
+            WindowPosition { x: 300, y: 100 },
+            WindowSize {
+                width: 900,
+                height: 700,
+            },
+            768,
+            1024,
+        );
+        assert_eq!(position, WindowPosition { x: 8, y: 100 });
+        assert_eq!(
+            size,
+            WindowSize {
+                width: 752,
+                height: 700
+            }
+        );
+    }
+
+    #[test]
+    fn compact_window_fills_the_viewport_inside_the_margin() {
+        assert_eq!(
+            compact_window_geometry(390, 844),
+            (
+                WindowPosition { x: 8, y: 8 },
+                WindowSize {
+                    width: 374,
+                    height: 828
+                }
+            )
+        );
+    }
 }
@@ -1,8 +1,10 @@
+use super::model::{WindowPosition, WindowSize, compact_window_geometry};
 use super::view_model::DraggableWindowViewModel;
 use crate::config::Theme;
+use crate::utils::viewport::{is_compact_viewport, use_viewport_size};
 use leptos::ev;
 use leptos::prelude::*;
-use leptos::web_sys::MouseEvent;
+use leptos::web_sys::PointerEvent;
 
 const WINDOW_TITLE_FONT_SIZE: &str = "clamp(1rem, 0.94rem + 0.22vw, 1.15rem)";
 const WINDOW_CLOSE_FONT_SIZE: &str = "clamp(1rem, 0.95rem + 0.25vw, 1.2rem)";
@@ -11,6 +13,7 @@ const WINDOW_CLOSE_FONT_SIZE: &str = "clamp(1rem, 0.95rem + 0.25vw, 1.2rem)";
 ///
 /// Drag by the header. Resize via the bottom-right corner handle.
 /// Visibility is controlled by the `is_open` signal.
+/// On compact viewports the window fills the screen and stays put.
 #[component]
 pub fn DraggableWindow(
     #[prop(into)] is_open: RwSignal<bool>,
@@ -35,12 +38,18 @@ pub fn DraggableWindow(
         min_height,
     );
 
-    let on_header_mouse_down = move |ev: MouseEvent| {
+    let viewport = use_viewport_size();
+    let is_compact = move || is_compact_viewport(viewport.get().0);
+
+    let on_header_pointer_down = move |ev: PointerEvent| {
         ev.prevent_default();
+        if is_compact() {
+            return;
+        }
         vm.start_drag(ev.client_x(), ev.client_y());
     };
 
-    let on_resize_mouse_down = move |ev: MouseEvent| {
+    let on_resize_pointer_down = move |ev: PointerEvent| {
         ev.prevent_default();
         ev.stop_propagation();
         vm.start_resize(ev.client_x(), ev.client_y());
@@ -48,28 +57,58 @@ pub fn DraggableWindow(
 
     let display = move || if is_open.get() { "flex" } else { "none" };
 
-    // Global mouse move/up listeners handle drag/resize even when cursor leaves the window
+    // Compact viewports show a full-screen sheet and keep the desktop geometry.
+    let geometry = move || {
+        let (width, height) = viewport.get();
+        if is_compact_viewport(width) {
+            compact_window_geometry(width as i32, height as i32)
+        } else {
+            (
+                WindowPosition {
+                    x: vm.pos_x.get(),
+                    y: vm.pos_y.get(),
+                },
+                WindowSize {
+                    width: vm.width.get(),
+                    height: vm.height.get(),
+                },
+            )
+        }
+    };
+
     Effect::new(move || {
-        use leptos::ev::MouseEvent as LeptosMouseEvent;
+        let (width, height) = viewport.get();
+        if !is_compact_viewport(width) {
+            vm.fit_to_viewport(width as i32, height as i32);
+        }
+    });
 
-        let handle_mousemove = window_event_listener(ev::mousemove, move |ev: LeptosMouseEvent| {
+    // Global pointer move/up listeners handle drag/resize even when the pointer leaves the window
+    Effect::new(move || {
+        let handle_pointermove = window_event_listener(ev::pointermove, move |ev: PointerEvent| {
             vm.update_drag(ev.client_x(), ev.client_y());
             vm.update_resize(ev.client_x(), ev.client_y());
         });
 
-        let handle_mouseup = window_event_listener(ev::mouseup, move |_: LeptosMouseEvent| {
+        let handle_pointerup = window_event_listener(ev::pointerup, move |_: PointerEvent| {
             vm.end_interaction();
         });
 
+        let handle_pointercancel =
+            window_event_listener(ev::pointercancel, move |_: PointerEvent| {
+                vm.end_interaction();
+            });
+
         on_cleanup(move || {
-            drop(handle_mousemove);
-            drop(handle_mouseup);
+            drop(handle_pointermove);
+            drop(handle_pointerup);
+            drop(handle_pointercancel);
         });
     });
 
     view! {
         <div
-            on:mousedown=move |_| {
+            on:pointerdown=move |_| {
                 if let Some(cb) = on_focus {
                     cb.run(());
                 }
@@ -81,16 +120,17 @@ pub fn DraggableWindow(
             }
             style=move || {
                 let opacity = if is_active.get() { "1" } else { "0.7" };
+                let (position, size) = geometry();
                 format!(
                     "position: fixed; left: {}px; top: {}px; width: {}px; height: {}px; \
                      background: {}; border: 0.0625rem solid {}; border-radius: 0.75rem; \
                      box-shadow: 0 0.5rem 2rem rgba(0,0,0,0.38); z-index: 1001; \
                      display: {}; flex-direction: column; overflow: hidden; \
                      opacity: {}; transition: opacity 0.2s;",
-                    vm.pos_x.get(),
-                    vm.pos_y.get(),
-                    vm.width.get(),
-                    vm.height.get(),
+                    position.x,
+                    position.y,
+                    size.width,
+                    size.height,
                     theme.ui_bg_primary,
                     theme.ui_border,
                     display(),
@@ -100,12 +140,14 @@ pub fn DraggableWindow(
         >
             // Header - drag handle
             <div
-                on:mousedown=on_header_mouse_down
-                style=format!(
+                on:pointerdown=on_header_pointer_down
+                style=move || format!(
                     "padding: 0.875rem 1rem; background: {}; border-bottom: 0.0625rem solid {}; \
-                     cursor: move; display: flex; justify-content: space-between; \
-                     align-items: center; user-select: none;",
-                    theme.ui_bg_secondary, theme.ui_border
+                     cursor: {}; display: flex; justify-content: space-between; \
+                     align-items: center; user-select: none; touch-action: none;",
+                    theme.ui_bg_secondary,
+                    theme.ui_border,
+                    if is_compact() { "default" } else { "move" }
                 )
             >
                 <h3 style=format!(
@@ -131,14 +173,17 @@ pub fn DraggableWindow(
             {children()}
 
             // Resize handle (bottom-right corner)
-            <div
-                on:mousedown=on_resize_mouse_down
-                style=format!(
-                    "position: absolute; bottom: 0; right: 0; width: 1.25rem; height: 1.25rem; \
-                     cursor: nwse-resize; background: linear-gradient(135deg, transparent 50%, {} 50%);",
-                    theme.ui_text_muted
-                )
-            />
+            <Show when=move || !is_compact()>
+                <div
+                    on:pointerdown=on_resize_pointer_down
+                    style=format!(
+                        "position: absolute; bottom: 0; right: 0; width: 1.25rem; height: 1.25rem; \
+                         cursor: nwse-resize; touch-action: none; \
+                         background: linear-gradient(135deg, transparent 50%, {} 50%);",
+                        theme.ui_text_muted
+                    )
+                />
+            </Show>
         </div>
     }
 }
@@ -1,4 +1,4 @@
-use super::model::{WindowPosition, WindowSize, apply_drag, apply_resize};
+use super::model::{WindowPosition, WindowSize, apply_drag, apply_resize, fit_window_to_viewport};
 use leptos::prelude::*;
 
 /// Reactive state for a draggable, resizable floating window.
@@ -116,6 +116,26 @@ impl DraggableWindowViewModel {
         self.is_dragging.set(false);
         self.is_resizing.set(false);
     }
+
+    /// Keeps the window reachable after the browser viewport shrinks.
+    pub fn fit_to_viewport(&self, viewport_width: i32, viewport_height: i32) {
+        let (position, size) = fit_window_to_viewport(
+            WindowPosition {
+                x: self.pos_x.get_untracked(),
+                y: self.pos_y.get_untracked(),
+            },
+            WindowSize {
+                width: self.width.get_untracked(),
+                height: self.height.get_untracked(),
+            },
+            viewport_width,
+            viewport_height,
+        );
+        self.pos_x.set(position.x);
+        self.pos_y.set(position.y);
+        self.width.set(size.width);
+        self.height.set(size.height);
+    }
 }
 
 #[cfg(test)]
@@ -182,4 +202,17 @@ mod tests {
             assert_eq!(vm.pos_x.get_untracked(), 100); // unchanged
         });
     }
+
+    #[test]
+    fn fit_to_viewport_moves_and_shrinks_the_window() {
+        let owner = Owner::new();
+        owner.with(|| {
+            let vm = make_vm();
+            vm.fit_to_viewport(320, 480);
+            assert_eq!(vm.pos_x.get_untracked(), 8);
+            assert_eq!(vm.pos_y.get_untracked(), 172);
+            assert_eq!(vm.width.get_untracked(), 304);
+            assert_eq!(vm.height.get_untracked(), 300);
+        });
+    }
 }
@@ -0,0 +1,539 @@
+// Pure types and logic for the encounter builder: 5e difficulty (DMG p. 82),
+// the party read from character sheets and automatic token placement.
+// No signals, no Leptos, no web_sys.
+
+use crate::components::compendium::model::{
+    CompendiumMonster, format_challenge_rating, xp_for_challenge_rating,
+};
+use serde::{Deserialize, Serialize};
+use shared::events::{CharacterSheet, Scene};
+use std::collections::HashMap;
+
+// --- Constants ---
+
+pub const MAX_CREATURE_COUNT: u32 = 50;
+/// Upper bound on tokens placed by a single deploy.
+pub const MAX_DEPLOYED_CREATURES: u32 = 100;
+pub const MAX_ENCOUNTER_NAME_LEN: usize = 120;
+const MAX_CHARACTER_LEVEL: u8 = 20;
+
+/// XP thresholds (easy, medium, hard, deadly) per character, levels 1..=20.
+const XP_THRESHOLDS_BY_LEVEL: [[u32; 4]; 20] = [
+    [25, 50, 75, 100],
+    [50, 100, 150, 200],
+    [75, 150, 225, 400],
+    [125, 250, 375, 500],
+    [250, 500, 750, 1100],
+    [300, 600, 900, 1400],
+    [350, 750, 1100, 1700],
+    [450, 900, 1400, 2100],
+    [550, 1100, 1600, 2400],
+    [600, 1200, 1900, 2800],
+    [800, 1600, 2400, 3600],
+    [1000, 2000, 3000, 4500],
+    [1100, 2200, 3400, 5100],
+    [1250, 2500, 3800, 5700],
+    [1400, 2800, 4300, 6400],
+    [1600, 3200, 4800, 7200],
+    [2000, 3900, 5900, 8800],
+    [2100, 4200, 6300, 9500],
+    [2400, 4900, 7300, 10900],
+    [2800, 5700, 8500, 12700],
+];
+/// Encounter multipliers, including the steps used for small and large parties.
+const MULTIPLIER_STEPS: [f32; 8] = [0.5, 1.0, 1.5, 2.0, 2.5, 3.0, 4.0, 5.0];
+
+// --- Types ---
+
+/// One line of an encounter: a creature kind and how many of it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EncounterCreature {
+    pub id: String,
+    pub name: String,
+    pub challenge_rating: f32,
+    pub xp: u32,
+    pub count: u32,
+    pub size_cells: u16,
+    /// Token library item whose image the deployed tokens use.
+    #[serde(default)]
+    pub library_item_id: Option<String>,
+    /// Full statistics when the creature came from the compendium.
+    #[serde(default)]
+    pub monster: Option<CompendiumMonster>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncounterDifficulty {
+    Trivial,
+    Easy,
+    Medium,
+    Hard,
+    Deadly,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DifficultyThresholds {
+    pub easy: u32,
+    pub medium: u32,
+    pub hard: u32,
+    pub deadly: u32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EncounterReport {
+    pub monster_count: u32,
+    pub base_xp: u32,
+    pub multiplier: f32,
+    pub adjusted_xp: u32,
+    pub thresholds: DifficultyThresholds,
+    /// `None` while the party is empty.
+    pub difficulty: Option<EncounterDifficulty>,
+}
+
+// --- Creatures ---
+
+impl EncounterCreature {
+    pub fn from_monster(id: String, monster: CompendiumMonster) -> Self {
+        Self {
+            id,
+            name: monster.name.clone(),
+            challenge_rating: monster.challenge_rating,
+            xp: monster.xp,
+            count: 1,
+            size_cells: monster.footprint_cells(),
+            library_item_id: None,
+            monster: Some(monster),
+        }
+    }
+
+    pub fn custom(id: String, name: String, challenge_rating: f32, size_cells: u16) -> Self {
+        Self {
+            id,
+            name,
+            challenge_rating,
+            xp: xp_for_challenge_rating(challenge_rating),
+            count: 1,
+            size_cells: size_cells.clamp(1, 4),
+            library_item_id: None,
+            monster: None,
+        }
+    }
+
+    /// Statistics for the linked sheet; custom creatures only know their CR.
+    pub fn deployed_monster(&self) -> CompendiumMonster {
+        if let Some(monster) = &self.monster {
+            return monster.clone();
+        }
+        CompendiumMonster {
+            name: self.name.clone(),
+            size: size_name(self.size_cells).to_string(),
+            creature_type: String::new(),
+            alignment: String::new(),
+            armor_class: 10,
+            hit_points: 0,
+            hit_dice: String::new(),
+            speed: "30 ft.".to_string(),
+            abilities: [10; 6],
+            challenge_rating: self.challenge_rating,
+            xp: self.xp,
+            senses: String::new(),
+            languages: String::new(),
+            traits: Vec::new(),
+            actions: Vec::new(),
+            legendary_actions: Vec::new(),
+        }
+    }
+
+    pub fn summary(&self) -> String {
+        format!(
+            "CR {} · {} XP",
+            format_challenge_rating(self.challenge_rating),
+            self.xp
+        )
+    }
+}
+
+fn size_name(size_cells: u16) -> &'static str {
+    match size_cells {
+        0 | 1 => "Medium",
+        2 => "Large",
+        3 => "Huge",
+        _ => "Gargantuan",
+    }
+}
+
+/// Adds a creature, merging it into an existing line of the same kind.
+pub fn add_creature(creatures: &mut Vec<EncounterCreature>, creature: EncounterCreature) {
+    if let Some(existing) = creatures.iter_mut().find(|existing| {
+        existing.name == creature.name
+            && existing.library_item_id == creature.library_item_id
+            && existing.challenge_rating == creature.challenge_rating
+    }) {
+        existing.count = (existing.count + creature.count).min(MAX_CREATURE_COUNT);
+    } else {
+        creatures.push(creature);
+    }
+}
+
+pub fn set_creature_count(creatures: &mut Vec<EncounterCreature>, id: &str, count: u32) {
+    if count == 0 {
+        creatures.retain(|creature| creature.id != id);
+    } else if let Some(creature) = creatures.iter_mut().find(|creature| creature.id == id) {
+        creature.count = count.min(MAX_CREATURE_COUNT);
+    }
+}
+
+/// Token names for one line: `Goblin` alone, `Goblin 1`…`Goblin N` otherwise.
+pub fn numbered_names(name: &str, count: u32) -> Vec<String> {
+    if count == 1 {
+        return vec![name.to_string()];
+    }
+    (1..=count).map(|index| format!("{name} {index}")).collect()
+}
+
+// --- Party ---
+
+/// Levels of player-owned sheets; a missing or unreadable level counts as 1.
+pub fn party_levels_from_sheets(sheets: &[CharacterSheet]) -> Vec<u8> {
+    sheets
+        .iter()
+        .filter(|sheet| sheet.owner.is_some())
+        .map(|sheet| {
+            sheet
+                .values
+                .get("level")
+                .and_then(|level| level.trim().parse::<u8>().ok())
+                .map_or(1, clamp_level)
+        })
+        .collect()
+}
+
+/// Reads levels typed as `3, 3, 4` or `3 3 4`.
+pub fn parse_party_levels(text: &str) -> Vec<u8> {
+    text.split(|ch: char| ch == ',' || ch.is_whitespace())
+        .filter_map(|part| part.trim().parse::<u8>().ok())
+        .map(clamp_level)
+        .collect()
+}
+
+pub fn format_party_levels(levels: &[u8]) -> String {
+    levels
+        .iter()
+        .map(u8::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn clamp_level(level: u8) -> u8 {
+    level.clamp(1, MAX_CHARACTER_LEVEL)
+}
+
+// --- Difficulty ---
+
+pub fn party_thresholds(party_levels: &[u8]) -> DifficultyThresholds {
+    party_levels
+        .iter()
+        .fold(DifficultyThresholds::default(), |total, level| {
+            let [easy, medium, hard, deadly] =
+                XP_THRESHOLDS_BY_LEVEL[usize::from(clamp_level(*level)) - 1];
+            DifficultyThresholds {
+                easy: total.easy + easy,
+                medium: total.medium + medium,
+                hard: total.hard + hard,
+                deadly: total.deadly + deadly,
+            }
+        })
+}
+
+/// Multiplier for the number of monsters, shifted one step up for parties of
+/// fewer than three characters and one step down for six or more.
+pub fn encounter_multiplier(monster_count: u32, party_size: usize) -> f32 {
+    let step: usize = match monster_count {
+        0 | 1 => 1,
+        2 => 2,
+        3..=6 => 3,
+        7..=10 => 4,
+        11..=14 => 5,
+        _ => 6,
+    };
+    let step = match party_size {
+        0..=2 => step + 1,
+        6.. => step - 1,
+        _ => step,
+    };
+    MULTIPLIER_STEPS[step]
+}
+
+pub fn evaluate_encounter(creatures: &[EncounterCreature], party_levels: &[u8]) -> EncounterReport {
+    let monster_count: u32 = creatures.iter().map(|creature| creature.count).sum();
+    let base_xp: u32 = creatures
+        .iter()
+        .map(|creature| creature.xp.saturating_mul(creature.count))
+        .sum();
+    let multiplier = encounter_multiplier(monster_count, party_levels.len());
+    let adjusted_xp = (base_xp as f32 * multiplier).round() as u32;
+    let thresholds = party_thresholds(party_levels);
+    let difficulty = (!party_levels.is_empty()).then_some(match adjusted_xp {
+        xp if xp >= thresholds.deadly => EncounterDifficulty::Deadly,
+        xp if xp >= thresholds.hard => EncounterDifficulty::Hard,
+        xp if xp >= thresholds.medium => EncounterDifficulty::Medium,
+        xp if xp >= thresholds.easy => EncounterDifficulty::Easy,
+        _ => EncounterDifficulty::Trivial,
+    });
+
+    EncounterReport {
+        monster_count,
+        base_xp,
+        multiplier,
+        adjusted_xp,
+        thresholds,
+        difficulty,
+    }
+}
+
+// --- Placement ---
+
+/// Finds free top-left cells for tokens of the given sizes, packing them
+/// around the centre of the scene and around the tokens already there.
+/// Larger creatures are placed first; the result follows the input order.
+/// When the grid is full a token falls back to the centre.
+pub fn auto_place(scene: &Scene, sizes: &[u16]) -> Vec<(f32, f32)> {
+    let columns = usize::from(scene.grid.columns.max(1));
+    let rows = usize::from(scene.grid.rows.max(1));
+    let mut occupied = vec![false; columns * rows];
+    for token in &scene.tokens {
+        mark_cells(
+            &mut occupied,
+            columns,
+            rows,
+            token.x.floor().max(0.0) as usize,
+            token.y.floor().max(0.0) as usize,
+            usize::from(token.width_cells.max(1)),
+            usize::from(token.height_cells.max(1)),
+        );
+    }
+
+    let mut order: Vec<usize> = (0..sizes.len()).collect();
+    order.sort_by(|left, right| sizes[*right].cmp(&sizes[*left]));
+
+    let mut candidates_by_size: HashMap<usize, Vec<(usize, usize)>> = HashMap::new();
+    let mut positions = vec![(0.0, 0.0); sizes.len()];
+    for index in order {
+        let size = usize::from(sizes[index].max(1)).min(columns).min(rows);
+        let candidates = candidates_by_size
+            .entry(size)
+            .or_insert_with(|| candidate_cells(columns, rows, size));
+        let free = candidates.iter().copied().find(|(column, row)| {
+            (0..size).all(|dy| (0..size).all(|dx| !occupied[(row + dy) * columns + column + dx]))
+        });
+        let (column, row) = free.unwrap_or(((columns - size) / 2, (rows - size) / 2));
+        mark_cells(&mut occupied, columns, rows, column, row, size, size);
+        positions[index] = (column as f32, row as f32);
+    }
+    positions
+}
+
+/// Top-left cells where a `size`×`size` token fits, nearest to the centre first.
+fn candidate_cells(columns: usize, rows: usize, size: usize) -> Vec<(usize, usize)> {
+    let center_x = columns as f32 / 2.0;
+    let center_y = rows as f32 / 2.0;
+    let half = size as f32 / 2.0;
+    let mut cells: Vec<(usize, usize)> = (0..=rows - size)
+        .flat_map(|row| (0..=columns - size).map(move |column| (column, row)))
+        .collect();
+    cells.sort_by(|left, right| {
+        let distance = |(column, row): (usize, usize)| {
+            let dx = column as f32 + half - center_x;
+            let dy = row as f32 + half - center_y;
+            dx * dx + dy * dy
+        };
+        distance(*left)
+            .total_cmp(&distance(*right))
+            .then_with(|| left.1.cmp(&right.1))
+            .then_with(|| left.0.cmp(&right.0))
+    });
+    cells
+}
+
+fn mark_cells(
+    occupied: &mut [bool],
+    columns: usize,
+    rows: usize,
+    column: usize,
+    row: usize,
+    width: usize,
+    height: usize,
+) {
+    for y in row..(row + height).min(rows) {
+        for x in column..(column + width).min(columns) {
+            occupied[y * columns + x] = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::events::{FileRef, SceneGrid, Token};
+    use std::collections::BTreeMap;
+
+    fn creature(name: &str, challenge_rating: f32, count: u32) -> EncounterCreature {
+        EncounterCreature {
+            count,
+            ..EncounterCreature::custom(name.to_string(), name.to_string(), challenge_rating, 1)
+        }
+    }
+
+    fn scene(columns: u16, rows: u16, tokens: Vec<Token>) -> Scene {
+        Scene {
+            id: "scene".to_string(),
+            name: "Scene".to_string(),
+            grid: SceneGrid {
+                columns,
+                rows,
+                cell_size_feet: 5,
+            },
+            workspace_x: 0.0,
+            workspace_y: 0.0,
+            background: None,
+            background_scale: 1.0,
+            background_offset_x: 0.0,
+            background_offset_y: 0.0,
+            background_rotation_deg: 0.0,
+            tokens,
+            terrain: Vec::new(),
+            walls: Vec::new(),
+            doors: Vec::new(),
+            lights: Vec::new(),
+            folder: None,
+            sort_index: 0,
+            levels: Vec::new(),
+            stairs: Vec::new(),
+        }
+    }
+
+    fn token(x: f32, y: f32, cells: u16) -> Token {
+        Token {
+            id: format!("{x}:{y}"),
+            name: "Hero".to_string(),
+            image: FileRef {
+                hash: "hash".to_string(),
+                mime_type: "image/png".to_string(),
+                file_name: "hero.png".to_string(),
+                size: 1,
+            },
+            x,
+            y,
+            width_cells: cells,
+            height_cells: cells,
+            rotation_deg: 0.0,
+            elevation_feet: 0,
+            owner: None,
+            auras: Vec::new(),
+            level_id: None,
+        }
+    }
+
+    #[test]
+    fn difficulty_follows_the_dmg_example() {
+        // Four 3rd-level characters against a bugbear (CR 1) and three
+        // hobgoblins (CR 1/2): 500 XP × 2 = 1000, a hard encounter.
+        let creatures = vec![creature("Bugbear", 1.0, 1), creature("Hobgoblin", 0.5, 3)];
+        let report = evaluate_encounter(&creatures, &[3, 3, 3, 3]);
+        assert_eq!(report.monster_count, 4);
+        assert_eq!(report.base_xp, 500);
+        assert_eq!(report.multiplier, 2.0);
+        assert_eq!(report.adjusted_xp, 1000);
+        assert_eq!(
+            report.thresholds,
+            DifficultyThresholds {
+                easy: 300,
+                medium: 600,
+                hard: 900,
+                deadly: 1600,
+            }
+        );
+        assert_eq!(report.difficulty, Some(EncounterDifficulty::Hard));
+        assert_eq!(evaluate_encounter(&creatures, &[]).difficulty, None);
+    }
+
+    #[test]
+    fn multiplier_shifts_with_party_size() {
+        assert_eq!(encounter_multiplier(1, 4), 1.0);
+        assert_eq!(encounter_multiplier(1, 2), 1.5);
+        assert_eq!(encounter_multiplier(1, 6), 0.5);
+        assert_eq!(encounter_multiplier(15, 1), 5.0);
+        assert_eq!(encounter_multiplier(8, 4), 2.5);
+    }
+
+    #[test]
+    fn party_is_read_from_player_sheets() {
+        let sheet = |owner: Option<&str>, level: Option<&str>| CharacterSheet {
+            id: "sheet".to_string(),
+            name: "Sheet".to_string(),
+            template_id: "dnd5e".to_string(),
+            owner: owner.map(str::to_string),
+            token_id: None,
+            values: level
+                .map(|level| BTreeMap::from([("level".to_string(), level.to_string())]))
+                .unwrap_or_default(),
+            updated_at_ms: 0.0,
+        };
+        let sheets = vec![
+            sheet(Some("alice"), Some("5")),
+            sheet(Some("bob"), None),
+            sheet(Some("carol"), Some("25")),
+            sheet(None, Some("8")),
+        ];
+        assert_eq!(party_levels_from_sheets(&sheets), vec![5, 1, 20]);
+        assert_eq!(parse_party_levels("3, 4 x 0"), vec![3, 4, 1]);
+        assert_eq!(format_party_levels(&[3, 4]), "3, 4");
+    }
+
+    #[test]
+    fn creatures_merge_and_count() {
+        let mut creatures = Vec::new();
+        add_creature(&mut creatures, creature("Goblin", 0.25, 1));
+        add_creature(&mut creatures, creature("Goblin", 0.25, 2));
+        add_creature(&mut creatures, creature("Wolf", 0.25, 1));
+        assert_eq!(creatures.len(), 2);
+        assert_eq!(creatures[0].count, 3);
+
+        set_creature_count(&mut creatures, "Goblin", 80);
+        assert_eq!(creatures[0].count, MAX_CREATURE_COUNT);
+        set_creature_count(&mut creatures, "Wolf", 0);
+        assert_eq!(creatures.len(), 1);
+
+        assert_eq!(numbered_names("Wolf", 1), vec!["Wolf"]);
+        assert_eq!(numbered_names("Wolf", 2), vec!["Wolf 1", "Wolf 2"]);
+    }
+
+    #[test]
+    fn auto_place_packs_around_the_centre_without_overlaps() {
+        let scene = scene(10, 10, vec![token(4.0, 4.0, 2)]);
+        let positions = auto_place(&scene, &[1, 2, 1]);
+        assert_eq!(positions.len(), 3);
+
+        let mut occupied = vec![(4, 4), (5, 4), (4, 5), (5, 5)];
+        for (position, size) in positions.iter().zip([1usize, 2, 1]) {
+            let (column, row) = (position.0 as usize, position.1 as usize);
+            assert!(column + size <= 10 && row + size <= 10);
+            for dy in 0..size {
+                for dx in 0..size {
+                    let cell = (column + dx, row + dy);
+                    assert!(!occupied.contains(&cell), "{cell:?} is taken twice");
+                    occupied.push(cell);
+                }
+            }
+            // Everything stays next to the centre on an empty board.
+            assert!((2..=7).contains(&column) && (2..=7).contains(&row));
+        }
+    }
+
+    #[test]
+    fn auto_place_falls_back_to_the_centre_when_full() {
+        let scene = scene(2, 2, vec![token(0.0, 0.0, 2)]);
+        assert_eq!(auto_place(&scene, &[1]), vec![(0.0, 0.0)]);
+        assert_eq!(auto_place(&scene, &[4]), vec![(0.0, 0.0)]);
+    }
+}
@@ -0,0 +1,556 @@
+use super::model::{
+    EncounterCreature, EncounterDifficulty, MAX_CREATURE_COUNT, MAX_DEPLOYED_CREATURES,
+    add_creature, auto_place, evaluate_encounter, format_party_levels, numbered_names,
+    party_levels_from_sheets, set_creature_count,
+};
+use super::view_model::EncountersWindowViewModel;
+use crate::components::compendium::deploy::MonsterDeployer;
+use crate::components::compendium::model::{MonsterPlacement, parse_challenge_rating};
+use crate::components::draggable_window::DraggableWindow;
+use crate::components::websocket::{
+    FileTransferState, StoredTokenLibraryItem, WsSender, delete_encounter, load_encounters,
+    load_token_library, save_encounter,
+};
+use crate::config::Theme;
+use crate::i18n::i18n::{t, t_string, use_i18n};
+use leptos::logging::log;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use shared::events::{CharacterSheet, Scene, acts_as_game_master};
+use uuid::Uuid;
+
+const ENCOUNTERS_BODY_FONT_SIZE: &str = "clamp(0.88rem, 0.85rem + 0.12vw, 0.96rem)";
+const ENCOUNTERS_META_FONT_SIZE: &str = "clamp(0.74rem, 0.71rem + 0.12vw, 0.82rem)";
+const ENCOUNTERS_BUTTON_FONT_SIZE: &str = "clamp(0.82rem, 0.79rem + 0.12vw, 0.92rem)";
+
+#[component]
+pub fn EncountersWindow(
+    #[prop(into)] is_open: RwSignal<bool>,
+    room_id: ReadSignal<String>,
+    /// Creatures of the encounter being edited.
+    #[prop(into)]
+    draft: RwSignal<Vec<EncounterCreature>>,
+    #[prop(into)] scenes: RwSignal<Vec<Scene>>,
+    #[prop(into)] active_scene_id: RwSignal<Option<String>>,
+    #[prop(into)] character_sheets: RwSignal<Vec<CharacterSheet>>,
+    #[prop(into)] token_library_items: RwSignal<Vec<StoredTokenLibraryItem>>,
+    #[prop(into)] game_master: RwSignal<Option<String>>,
+    file_transfer: FileTransferState,
+    ws_sender: ReadSignal<Option<WsSender>>,
+    username: ReadSignal<String>,
+    #[prop(into, optional)] is_active: Signal<bool>,
+    #[prop(optional)] on_focus: Option<Callback<()>>,
+    theme: Theme,
+) -> impl IntoView {
+    let i18n = use_i18n();
+    let vm = EncountersWindowViewModel::new();
+    let deployer = MonsterDeployer {
+        scenes,
+        game_master,
+        file_transfer: file_transfer.clone(),
+        ws_sender,
+        username,
+    };
+
+    let viewer_is_gm =
+        Memo::new(move |_| acts_as_game_master(game_master.get().as_deref(), &username.get()));
+
+    // Saved encounters and the token library are kept on this device per room.
+    let file_transfer_for_load = file_transfer.clone();
+    Effect::new(move |_| {
+        if !is_open.get() {
+            return;
+        }
+        let current_room = room_id.get();
+        if current_room.is_empty() {
+            vm.saved.set(Vec::new());
+            return;
+        }
+        let file_transfer = file_transfer_for_load.clone();
+        spawn_local(async move {
+            let encounters = load_encounters(&current_room).await;
+            let library = load_token_library(&current_room).await;
+            if room_id.get_untracked() != current_room {
+                return;
+            }
+            match encounters {
+                Ok(encounters) => vm.saved.set(encounters),
+                Err(error) => vm.error.set(Some(error)),
+            }
+            if let Ok(items) = library {
+                let files = items
+                    .iter()
+                    .map(|item| item.image.clone())
+                    .collect::<Vec<_>>();
+                file_transfer.hydrate_local_files(&files);
+                token_library_items.set(items);
+            }
+        });
+    });
+
+    Effect::new(move |_| {
+        let _ = room_id.get();
+        vm.editing_id.set(None);
+        vm.name.set(String::new());
+        vm.target_scene_id.set(String::new());
+    });
+
+    let report = Memo::new(move |_| {
+        draft.with(|creatures| evaluate_encounter(creatures, &vm.party_levels()))
+    });
+
+    let target_scene_id = move || {
+        let chosen = vm.target_scene_id.get();
+        if !chosen.is_empty() && scenes.with(|items| items.iter().any(|scene| scene.id == chosen)) {
+            return Some(chosen);
+        }
+        active_scene_id
+            .get()
+            .or_else(|| scenes.with(|items| items.first().map(|scene| scene.id.clone())))
+    };
+
+    let add_custom = move || {
+        let name = vm.custom_name.get_untracked().trim().to_string();
+        let Some(challenge_rating) = parse_challenge_rating(&vm.custom_cr.get_untracked()) else {
+            return;
+        };
+        if name.is_empty() {
+            return;
+        }
+        draft.update(|creatures| {
+            add_creature(
+                creatures,
+                EncounterCreature::custom(Uuid::new_v4().to_string(), name, challenge_rating, 1),
+            )
+        });
+        vm.custom_name.set(String::new());
+        vm.custom_cr.set(String::new());
+    };
+
+    let add_library_token = move || {
+        let item_id = vm.library_item_id.get_untracked();
+        let Some(item) = token_library_items
+            .with_untracked(|items| items.iter().find(|item| item.id == item_id).cloned())
+        else {
+            return;
+        };
+        let challenge_rating =
+            parse_challenge_rating(&vm.library_cr.get_untracked()).unwrap_or(0.0);
+        let mut creature = EncounterCreature::custom(
+            Uuid::new_v4().to_string(),
+            item.name.clone(),
+            challenge_rating,
+            item.width_cells.max(item.height_cells),
+        );
+        creature.library_item_id = Some(item.id);
+        draft.update(|creatures| add_creature(creatures, creature));
+        vm.library_cr.set(String::new());
+    };
+
+    let save = move || {
+        let room = room_id.get_untracked();
+        if room.is_empty() {
+            return;
+        }
+        let id = vm
+            .editing_id
+            .get_untracked()
+            .unwrap_or_else(|| Uuid::new_v4().to_string());
+        let fallback_name = t_string!(i18n, encounters.untitled).to_string();
+        let encounter = vm.to_stored(
+            &room,
+            id.clone(),
+            &fallback_name,
+            draft.get_untracked(),
+            js_sys::Date::now(),
+        );
+        spawn_local(async move {
+            match save_encounter(&encounter).await {
+                Ok(()) => {
+                    vm.editing_id.set(Some(id.clone()));
+                    vm.name.set(encounter.name.clone());
+                    vm.saved.update(|saved| {
+                        saved.retain(|existing| existing.id != id);
+                        saved.push(encounter);
+                        saved.sort_by(|left, right| {
+                            left.name.to_lowercase().cmp(&right.name.to_lowercase())
+                        });
+                    });
+                    vm.error.set(None);
+                }
+                Err(error) => vm.error.set(Some(error)),
+            }
+        });
+    };
+
+    let delete = move || {
+        let room = room_id.get_untracked();
+        let Some(id) = vm.editing_id.get_untracked() else {
+            return;
+        };
+        spawn_local(async move {
+            match delete_encounter(&room, &id).await {
+                Ok(()) => {
+                    vm.saved
+                        .update(|saved| saved.retain(|existing| existing.id != id));
+                    vm.start_new(draft);
+                }
+                Err(error) => vm.error.set(Some(error)),
+            }
+        });
+    };
+
+    let deploy = move || {
+        let Some(scene_id) = target_scene_id() else {
+            return;
+        };
+        let Some(scene) =
+            scenes.with_untracked(|items| items.iter().find(|scene| scene.id == scene_id).cloned())
+        else {
+            return;
+        };
+        let creatures = draft.get_untracked();
+        let total: u32 = creatures.iter().map(|creature| creature.count).sum();
+        if total == 0 {
+            return;
+        }
+        if total > MAX_DEPLOYED_CREATURES {
+            vm.error
+                .set(Some(t_string!(i18n, encounters.error_too_many).to_string()));
+            return;
+        }
+
+        let library = token_library_items.get_untracked();
+        let mut placements = Vec::new();
+        for creature in &creatures {
+            let image = creature.library_item_id.as_ref().and_then(|item_id| {
+                library
+                    .iter()
+                    .find(|item| &item.id == item_id)
+                    .map(|item| item.image.clone())
+            });
+            let monster = creature.deployed_monster();
+            for name in numbered_names(&creature.name, creature.count) {
+                placements.push(MonsterPlacement {
+                    monster: monster.clone(),
+                    name,
+                    image: image.clone(),
+                    scene_id: scene.id.clone(),
+                    level_id: None,
+                    x: 0.0,
+                    y: 0.0,
+                });
+            }
+        }
+        let sizes = creatures
+            .iter()
+            .flat_map(|creature| std::iter::repeat_n(creature.size_cells, creature.count as usize))
+            .collect::<Vec<_>>();
+        for (placement, (x, y)) in placements.iter_mut().zip(auto_place(&scene, &sizes)) {
+            placement.x = x;
+            placement.y = y;
+        }
+
+        let deployer = deployer.clone();
+        let failed_message = t_string!(i18n, encounters.error_deploy).to_string();
+        vm.is_deploying.set(true);
+        vm.error.set(None);
+        spawn_local(async move {
+            if let Err(error) = deployer.deploy(placements).await {
+                log!("Failed to deploy encounter: {}", error);
+                vm.error.set(Some(format!("{failed_message} ({error})")));
+            }
+            vm.is_deploying.set(false);
+        });
+    };
+
+    let input_style = format!(
+        "padding: 0.45rem; background: {}; color: {}; border: 0.0625rem solid {}; border-radius: 0.5rem; font-size: {}; min-width: 0;",
+        theme.ui_bg_secondary, theme.ui_text_primary, theme.ui_border, ENCOUNTERS_BODY_FONT_SIZE
+    );
+    let button_style = format!(
+        "padding: 0.45rem 0.75rem; background: {}; color: {}; border: none; border-radius: 0.5rem; cursor: pointer; font-size: {};",
+        theme.ui_button_primary, theme.ui_text_primary, ENCOUNTERS_BUTTON_FONT_SIZE
+    );
+    let secondary_button_style = format!(
+        "padding: 0.45rem 0.75rem; background: none; color: {}; border: 0.0625rem solid {}; border-radius: 0.5rem; cursor: pointer; font-size: {};",
+        theme.ui_text_secondary, theme.ui_border, ENCOUNTERS_BUTTON_FONT_SIZE
+    );
+    let section_style = format!(
+        "display: flex; flex-direction: column; gap: 0.5rem; padding-top: 0.75rem; border-top: 0.0625rem solid {};",
+        theme.ui_border
+    );
+    let heading_style = format!("margin: 0; color: {};", theme.ui_text_primary);
+    let meta_style = format!(
+        "color: {}; font-size: {};",
+        theme.ui_text_secondary, ENCOUNTERS_META_FONT_SIZE
+    );
+    let difficulty_color = move |difficulty: EncounterDifficulty| match difficulty {
+        EncounterDifficulty::Trivial => theme.ui_text_muted,
+        EncounterDifficulty::Easy | EncounterDifficulty::Medium => theme.ui_success,
+        EncounterDifficulty::Hard => theme.ui_notification,
+        EncounterDifficulty::Deadly => theme.ui_button_danger,
+    };
+
+    view! {
+        <DraggableWindow
+            is_open=is_open
+            title=move || t_string!(i18n, encounters.title)
+            initial_x=340
+            initial_y=120
+            initial_width=560
+            initial_height=640
+            min_width=420
+            min_height=380
+            is_active=is_active
+            on_focus=on_focus.unwrap_or_else(|| Callback::new(|_| {}))
+            theme=theme.clone()
+        >
+            <div style=format!(
+                "display: flex; flex-direction: column; flex: 1; min-height: 0; overflow-y: auto; padding: 1rem; gap: 0.75rem; color: {}; font-size: {};",
+                theme.ui_text_primary, ENCOUNTERS_BODY_FONT_SIZE
+            )>
+                // Saved encounters
+                <div style="display: flex; gap: 0.5rem; align-items: center;">
+                    <select
+                        prop:value=move || vm.editing_id.get().unwrap_or_default()
+                        on:change=move |ev| {
+                            let id = event_target_value(&ev);
+                            match vm.saved.with_untracked(|saved| saved.iter().find(|encounter| encounter.id == id).cloned()) {
+                                Some(encounter) => vm.load(&encounter, draft),
+                                None => vm.start_new(draft),
+                            }
+                        }
+                        style=format!("{input_style} flex: 1;")
+                    >
+                        <option value="">{move || t!(i18n, encounters.new_encounter)}</option>
+                        {move || vm.saved.get().into_iter().map(|encounter| view! {
+                            <option value=encounter.id.clone()>{encounter.name.clone()}</option>
+                        }).collect_view()}
+                    </select>
+                    <input
+                        type="text"
+                        placeholder=move || t_string!(i18n, encounters.name_placeholder)
+                        prop:value=move || vm.name.get()
+                        on:input=move |ev| vm.name.set(event_target_value(&ev))
+                        style=format!("{input_style} flex: 1;")
+                    />
+                    <button on:click=move |_| save() style=button_style.clone()>
+                        {move || t!(i18n, encounters.save_button)}
+                    </button>
+                    {move || vm.editing_id.get().is_some().then(|| view! {
+                        <button
+                            on:click=move |_| delete()
+                            style=format!("background: none; border: none; cursor: pointer; color: {};", theme.ui_button_danger)
+                            title=move || t_string!(i18n, encounters.delete_button)
+                        >
+                            "✕"
+                        </button>
+                    })}
+                </div>
+
+                // Creatures
+                <div style=section_style.clone()>
+                    <h4 style=heading_style.clone()>{move || t!(i18n, encounters.creatures_title)}</h4>
+                    {move || {
+                        let creatures = draft.get();
+                        if creatures.is_empty() {
+                            return view! {
+                                <div style=format!("color: {}; font-style: italic;", theme.ui_text_muted)>
+                                    {t!(i18n, encounters.creatures_empty)}
+                                </div>
+                            }.into_any();
+                        }
+                        creatures.into_iter().map(|creature| {
+                            let id_minus = creature.id.clone();
+                            let id_plus = creature.id.clone();
+                            let id_remove = creature.id.clone();
+                            let count = creature.count;
+                            let is_full = count >= MAX_CREATURE_COUNT;
+                            view! {
+                                <div style=format!(
+                                    "display: flex; align-items: center; gap: 0.5rem; padding: 0.4rem 0.6rem; border: 0.0625rem solid {}; border-radius: 0.5rem;",
+                                    theme.ui_border
+                                )>
+                                    <span style="flex: 1; font-weight: 600;">{creature.name.clone()}</span>
+                                    <span style=format!("color: {}; font-size: {};", theme.ui_text_secondary, ENCOUNTERS_META_FONT_SIZE)>
+                                        {creature.summary()}
+                                    </span>
+                                    <button
+                                        on:click=move |_| draft.update(|creatures| set_creature_count(creatures, &id_minus, count - 1))
+                                        style=format!("width: 1.8rem; background: none; color: {}; border: 0.0625rem solid {}; border-radius: 0.4rem; cursor: pointer;", theme.ui_text_primary, theme.ui_border)
+                                    >
+                                        "−"
+                                    </button>
+                                    <span style="min-width: 1.5rem; text-align: center;">{count}</span>
+                                    <button
+                                        disabled=is_full
+                                        on:click=move |_| draft.update(|creatures| set_creature_count(creatures, &id_plus, count + 1))
+                                        style=format!("width: 1.8rem; background: none; color: {}; border: 0.0625rem solid {}; border-radius: 0.4rem; cursor: pointer;", theme.ui_text_primary, theme.ui_border)
+                                    >
+                                        "+"
+                                    </button>
+                                    <button
+                                        on:click=move |_| draft.update(|creatures| set_creature_count(creatures, &id_remove, 0))
+                                        style=format!("background: none; border: none; cursor: pointer; color: {};", theme.ui_button_danger)
+                                    >
+                                        "✕"
+                                    </button>
+                                </div>
+                            }
+                        }).collect_view().into_any()
+                    }}
+                    <div style=meta_style.clone()>{move || t!(i18n, encounters.compendium_hint)}</div>
+                    <div style="display: flex; gap: 0.5rem;">
+                        <input
+                            type="text"
+                            placeholder=move || t_string!(i18n, encounters.custom_name_placeholder)
+                            prop:value=move || vm.custom_name.get()
+                            on:input=move |ev| vm.custom_name.set(event_target_value(&ev))
+                            style=format!("{input_style} flex: 1;")
+                        />
+                        <input
+                            type="text"
+                            placeholder=move || t_string!(i18n, encounters.cr_placeholder)
+                            prop:value=move || vm.custom_cr.get()
+                            on:input=move |ev| vm.custom_cr.set(event_target_value(&ev))
+                            style=format!("{input_style} width: 5rem;")
+                        />
+                        <button on:click=move |_| add_custom() style=secondary_button_style.clone()>
+                            {move || t!(i18n, encounters.add_button)}
+                        </button>
+                    </div>
+                    <div style="display: flex; gap: 0.5rem;">
+                        <select
+                            prop:value=move || vm.library_item_id.get()
+                            on:change=move |ev| vm.library_item_id.set(event_target_value(&ev))
+                            style=format!("{input_style} flex: 1;")
+                        >
+                            <option value="">{move || t!(i18n, encounters.library_placeholder)}</option>
+                            {move || token_library_items.get().into_iter().map(|item| view! {
+                                <option value=item.id.clone()>{item.name.clone()}</option>
+                            }).collect_view()}
+                        </select>
+                        <input
+                            type="text"
+                            placeholder=move || t_string!(i18n, encounters.cr_placeholder)
+                            prop:value=move || vm.library_cr.get()
+                            on:input=move |ev| vm.library_cr.set(event_target_value(&ev))
+                            style=format!("{input_style} width: 5rem;")
+                        />
+                        <button on:click=move |_| add_library_token() style=secondary_button_style.clone()>
+                            {move || t!(i18n, encounters.add_button)}
+                        </button>
+                    </div>
+                </div>
+
+                // Party and difficulty
+                <div style=section_style.clone()>
+                    <h4 style=heading_style.clone()>{move || t!(i18n, encounters.party_title)}</h4>
+                    <div style="display: flex; gap: 0.5rem;">
+                        <input
+                            type="text"
+                            placeholder=move || t_string!(i18n, encounters.party_placeholder)
+                            prop:value=move || vm.party_text.get()
+                            on:input=move |ev| vm.party_text.set(event_target_value(&ev))
+                            style=format!("{input_style} flex: 1;")
+                        />
+                        <button
+                            on:click=move |_| vm.party_text.set(format_party_levels(
+                                &character_sheets.with_untracked(|sheets| party_levels_from_sheets(sheets)),
+                            ))
+                            style=secondary_button_style.clone()
+                        >
+                            {move || t!(i18n, encounters.party_from_sheets)}
+                        </button>
+                    </div>
+                    {move || {
+                        let report = report.get();
+                        let thresholds = report.thresholds;
+                        view! {
+                            <div style="display: flex; flex-wrap: wrap; gap: 0.75rem; align-items: baseline;">
+                                <span>
+                                    {move || t!(i18n, encounters.total_xp)}
+                                    {format!(": {}", report.base_xp)}
+                                </span>
+                                <span>
+                                    {move || t!(i18n, encounters.adjusted_xp)}
+                                    {format!(": {} (×{})", report.adjusted_xp, report.multiplier)}
+                                </span>
+                                {match report.difficulty {
+                                    Some(difficulty) => view! {
+                                        <strong style=format!("color: {};", difficulty_color(difficulty))>
+                                            {move || match difficulty {
+                                                EncounterDifficulty::Trivial => t_string!(i18n, encounters.difficulty_trivial),
+                                                EncounterDifficulty::Easy => t_string!(i18n, encounters.difficulty_easy),
+                                                EncounterDifficulty::Medium => t_string!(i18n, encounters.difficulty_medium),
+                                                EncounterDifficulty::Hard => t_string!(i18n, encounters.difficulty_hard),
+                                                EncounterDifficulty::Deadly => t_string!(i18n, encounters.difficulty_deadly),
+                                            }}
+                                        </strong>
+                                    }.into_any(),
+                                    None => view! {
+                                        <em style=format!("color: {};", theme.ui_text_muted)>
+                                            {move || t!(i18n, encounters.difficulty_unknown)}
+                                        </em>
+                                    }.into_any(),
+                                }}
+                            </div>
+                            {report.difficulty.is_some().then(|| view! {
+                                <div style=format!("color: {}; font-size: {};", theme.ui_text_secondary, ENCOUNTERS_META_FONT_SIZE)>
+                                    {move || t!(i18n, encounters.thresholds)}
+                                    {format!(
+                                        ": {} / {} / {} / {}",
+                                        thresholds.easy, thresholds.medium, thresholds.hard, thresholds.deadly
+                                    )}
+                                </div>
+                            })}
+                        }
+                    }}
+                </div>
+
+                // Deploy
+                <div style=section_style.clone()>
+                    <div style="display: flex; gap: 0.5rem; align-items: center;">
+                        <select
+                            prop:value=move || target_scene_id().unwrap_or_default()
+                            on:change=move |ev| vm.target_scene_id.set(event_target_value(&ev))
+                            style=format!("{input_style} flex: 1;")
+                        >
+                            {move || scenes.get().into_iter().map(|scene| view! {
+                                <option value=scene.id.clone()>{scene.name.clone()}</option>
+                            }).collect_view()}
+                        </select>
+                        <button
+                            disabled=move || {
+                                !viewer_is_gm.get()
+                                    || vm.is_deploying.get()
+                                    || draft.with(Vec::is_empty)
+                                    || target_scene_id().is_none()
+                            }
+                            on:click=move |_| deploy()
+                            style=button_style.clone()
+                        >
+                            {move || if vm.is_deploying.get() {
+                                t_string!(i18n, encounters.deploying)
+                            } else {
+                                t_string!(i18n, encounters.deploy_button)
+                            }}
+                        </button>
+                    </div>
+                    {move || (!viewer_is_gm.get()).then(|| view! {
+                        <div style=format!("color: {}; font-size: {};", theme.ui_text_muted, ENCOUNTERS_META_FONT_SIZE)>
+                            {t!(i18n, encounters.deploy_gm_only)}
+                        </div>
+                    })}
+                </div>
+
+                {move || vm.error.get().map(|error| view! {
+                    <div style=format!("color: {}; font-size: {};", theme.ui_button_danger, ENCOUNTERS_META_FONT_SIZE)>
+                        {error}
+                    </div>
+                })}
+            </div>
+        </DraggableWindow>
+    }
+}
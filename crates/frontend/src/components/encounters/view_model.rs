@@ -0,0 +1,124 @@
+use super::model::{
+    EncounterCreature, MAX_ENCOUNTER_NAME_LEN, format_party_levels, parse_party_levels,
+};
+use crate::components::websocket::{StoredEncounter, encounter_key};
+use leptos::prelude::*;
+
+/// Reactive state for the encounter builder. The creature list itself lives
+/// in the app shell so the compendium can add to it.
+#[derive(Clone, Copy)]
+pub struct EncountersWindowViewModel {
+    pub saved: RwSignal<Vec<StoredEncounter>>,
+    /// Saved encounter being edited; `None` for a new one.
+    pub editing_id: RwSignal<Option<String>>,
+    pub name: RwSignal<String>,
+    pub party_text: RwSignal<String>,
+    pub custom_name: RwSignal<String>,
+    pub custom_cr: RwSignal<String>,
+    pub library_item_id: RwSignal<String>,
+    pub library_cr: RwSignal<String>,
+    pub target_scene_id: RwSignal<String>,
+    pub is_deploying: RwSignal<bool>,
+    pub error: RwSignal<Option<String>>,
+}
+
+impl EncountersWindowViewModel {
+    pub fn new() -> Self {
+        Self {
+            saved: RwSignal::new(Vec::new()),
+            editing_id: RwSignal::new(None),
+            name: RwSignal::new(String::new()),
+            party_text: RwSignal::new(String::new()),
+            custom_name: RwSignal::new(String::new()),
+            custom_cr: RwSignal::new(String::new()),
+            library_item_id: RwSignal::new(String::new()),
+            library_cr: RwSignal::new(String::new()),
+            target_scene_id: RwSignal::new(String::new()),
+            is_deploying: RwSignal::new(false),
+            error: RwSignal::new(None),
+        }
+    }
+
+    pub fn party_levels(&self) -> Vec<u8> {
+        parse_party_levels(&self.party_text.get())
+    }
+
+    pub fn load(&self, encounter: &StoredEncounter, draft: RwSignal<Vec<EncounterCreature>>) {
+        self.editing_id.set(Some(encounter.id.clone()));
+        self.name.set(encounter.name.clone());
+        if !encounter.party_levels.is_empty() {
+            self.party_text
+                .set(format_party_levels(&encounter.party_levels));
+        }
+        draft.set(encounter.creatures.clone());
+        self.error.set(None);
+    }
+
+    pub fn start_new(&self, draft: RwSignal<Vec<EncounterCreature>>) {
+        self.editing_id.set(None);
+        self.name.set(String::new());
+        draft.set(Vec::new());
+        self.error.set(None);
+    }
+
+    /// The record to save; a blank name falls back to `fallback_name`.
+    pub fn to_stored(
+        self,
+        room_name: &str,
+        id: String,
+        fallback_name: &str,
+        creatures: Vec<EncounterCreature>,
+        updated_at_ms: f64,
+    ) -> StoredEncounter {
+        let name = self.name.get_untracked();
+        let name = match name.trim() {
+            "" => fallback_name.to_string(),
+            name => name.chars().take(MAX_ENCOUNTER_NAME_LEN).collect(),
+        };
+        StoredEncounter {
+            key: encounter_key(room_name, &id),
+            room_name: room_name.to_string(),
+            id,
+            name,
+            creatures,
+            party_levels: parse_party_levels(&self.party_text.get_untracked()),
+            updated_at_ms,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use leptos::reactive::owner::Owner;
+
+    #[test]
+    fn saved_encounters_round_trip_through_the_form() {
+        let owner = Owner::new();
+        owner.with(|| {
+            let vm = EncountersWindowViewModel::new();
+            let draft = RwSignal::new(vec![EncounterCreature::custom(
+                "c1".to_string(),
+                "Bandit".to_string(),
+                0.125,
+                1,
+            )]);
+            vm.party_text.set("2, 2 3".to_string());
+
+            let stored = vm.to_stored("room", "e1".to_string(), "Encounter", draft.get(), 1.0);
+            assert_eq!(stored.key, "room:e1");
+            assert_eq!(stored.name, "Encounter");
+            assert_eq!(stored.party_levels, vec![2, 2, 3]);
+
+            vm.start_new(draft);
+            assert!(draft.get_untracked().is_empty());
+            assert_eq!(vm.editing_id.get_untracked(), None);
+
+            vm.party_text.set(String::new());
+            vm.load(&stored, draft);
+            assert_eq!(vm.editing_id.get_untracked().as_deref(), Some("e1"));
+            assert_eq!(vm.party_text.get_untracked(), "2, 2, 3");
+            assert_eq!(draft.get_untracked().len(), 1);
+        });
+    }
+}
@@ -0,0 +1,6 @@
+pub mod model;
+mod reveal_image;
+mod view;
+mod view_model;
+
+pub use view::{HandoutOverlay, HandoutsWindow};
@@ -0,0 +1,286 @@
+// Pure types and logic for handouts: which room files can be shown, which
+// handouts a user may see and how a progressive reveal grows.
+// No signals, no Leptos, no web_sys.
+
+use shared::events::{FileRef, Handout, HandoutCrop};
+
+// --- Constants ---
+
+pub const MAX_HANDOUT_TITLE_LEN: usize = 120;
+pub const MAX_HANDOUT_RECIPIENTS: usize = 64;
+/// Share of the image added on every side by one "reveal more" step.
+pub const REVEAL_STEP: f32 = 0.125;
+const EDGE_EPSILON: f32 = 1e-4;
+
+// --- File kinds ---
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HandoutKind {
+    Image,
+    Pdf,
+}
+
+impl HandoutKind {
+    pub fn of(file: &FileRef) -> Option<Self> {
+        if file.mime_type.starts_with("image/") {
+            Some(Self::Image)
+        } else if file.mime_type == "application/pdf" {
+            Some(Self::Pdf)
+        } else {
+            None
+        }
+    }
+}
+
+/// Room files that can be shown as a handout: images and PDFs.
+pub fn handout_candidates(files: Vec<FileRef>) -> Vec<FileRef> {
+    files
+        .into_iter()
+        .filter(|file| HandoutKind::of(file).is_some())
+        .collect()
+}
+
+/// Handout title suggested for a file: its name without the extension.
+pub fn title_from_file_name(file_name: &str) -> String {
+    let stem = match file_name.rsplit_once('.') {
+        Some((stem, _)) if !stem.trim().is_empty() => stem,
+        _ => file_name,
+    };
+    stem.trim().chars().take(MAX_HANDOUT_TITLE_LEN).collect()
+}
+
+// --- Visibility ---
+
+/// Handouts shown to `user`, most recently shown first.
+pub fn visible_handouts(
+    handouts: &[Handout],
+    user: &str,
+    game_master: Option<&str>,
+) -> Vec<Handout> {
+    let mut visible = handouts
+        .iter()
+        .filter(|handout| handout.is_visible_to(user, game_master))
+        .cloned()
+        .collect::<Vec<_>>();
+    visible.sort_by(|left, right| right.shown_at_ms.total_cmp(&left.shown_at_ms));
+    visible
+}
+
+// --- Progressive reveal ---
+
+/// Region the game master starts a progressive reveal with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RevealPreset {
+    Whole,
+    TopHalf,
+    BottomHalf,
+    LeftHalf,
+    RightHalf,
+    Center,
+}
+
+impl RevealPreset {
+    pub const ALL: [Self; 6] = [
+        Self::Whole,
+        Self::TopHalf,
+        Self::BottomHalf,
+        Self::LeftHalf,
+        Self::RightHalf,
+        Self::Center,
+    ];
+
+    pub fn crop(self) -> Option<HandoutCrop> {
+        let crop = |x, y, width, height| {
+            Some(HandoutCrop {
+                x,
+                y,
+                width,
+                height,
+            })
+        };
+        match self {
+            Self::Whole => None,
+            Self::TopHalf => crop(0.0, 0.0, 1.0, 0.5),
+            Self::BottomHalf => crop(0.0, 0.5, 1.0, 0.5),
+            Self::LeftHalf => crop(0.0, 0.0, 0.5, 1.0),
+            Self::RightHalf => crop(0.5, 0.0, 0.5, 1.0),
+            Self::Center => crop(0.375, 0.375, 0.25, 0.25),
+        }
+    }
+
+    /// Stable value for `<select>` options.
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Whole => "whole",
+            Self::TopHalf => "top",
+            Self::BottomHalf => "bottom",
+            Self::LeftHalf => "left",
+            Self::RightHalf => "right",
+            Self::Center => "center",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Self {
+        Self::ALL
+            .into_iter()
+            .find(|preset| preset.key() == key)
+            .unwrap_or(Self::Whole)
+    }
+}
+
+/// Widens the revealed region by `step` on every side. Returns `None` once
+/// the whole image is uncovered.
+pub fn grow_reveal(crop: HandoutCrop, step: f32) -> Option<HandoutCrop> {
+    let left = (crop.x - step).max(0.0);
+    let top = (crop.y - step).max(0.0);
+    let right = (crop.x + crop.width + step).min(1.0);
+    let bottom = (crop.y + crop.height + step).min(1.0);
+    let is_whole = left <= EDGE_EPSILON
+        && top <= EDGE_EPSILON
+        && right >= 1.0 - EDGE_EPSILON
+        && bottom >= 1.0 - EDGE_EPSILON;
+    (!is_whole).then_some(HandoutCrop {
+        x: left,
+        y: top,
+        width: right - left,
+        height: bottom - top,
+    })
+}
+
+/// Pixel rectangle `(x, y, width, height)` of the revealed region in an
+/// image of the given size; never empty.
+pub fn crop_rect(crop: HandoutCrop, image_width: u32, image_height: u32) -> (u32, u32, u32, u32) {
+    let pixels = |share: f32, size: u32| (share.clamp(0.0, 1.0) * size as f32).round() as u32;
+    let x = pixels(crop.x, image_width).min(image_width.saturating_sub(1));
+    let y = pixels(crop.y, image_height).min(image_height.saturating_sub(1));
+    let right = pixels(crop.x + crop.width, image_width).clamp(x + 1, image_width.max(x + 1));
+    let bottom = pixels(crop.y + crop.height, image_height).clamp(y + 1, image_height.max(y + 1));
+    (x, y, right - x, bottom - y)
+}
+
+/// CSS placing the rendered crop over the original image it was cut from.
+pub fn reveal_position_style(crop: HandoutCrop) -> String {
+    let percent = |share: f32| share.clamp(0.0, 1.0) * 100.0;
+    format!(
+        "left: {}%; top: {}%; width: {}%; height: {}%;",
+        percent(crop.x),
+        percent(crop.y),
+        percent(crop.width),
+        percent(crop.height)
+    )
+}
+
+/// Share of the image that is revealed, in whole percent.
+pub fn revealed_percent(reveal: Option<HandoutCrop>) -> u32 {
+    match reveal {
+        Some(crop) => (crop.width * crop.height * 100.0).round() as u32,
+        None => 100,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(name: &str, mime_type: &str) -> FileRef {
+        FileRef {
+            hash: name.to_string(),
+            mime_type: mime_type.to_string(),
+            file_name: name.to_string(),
+            size: 1,
+        }
+    }
+
+    fn handout(id: &str, recipients: &[&str], shown_at_ms: f64) -> Handout {
+        Handout {
+            id: id.to_string(),
+            title: id.to_string(),
+            file: file("map.png", "image/png"),
+            recipients: recipients.iter().map(|user| user.to_string()).collect(),
+            reveal: None,
+            shown_at_ms,
+        }
+    }
+
+    #[test]
+    fn only_images_and_pdfs_can_be_handouts() {
+        let candidates = handout_candidates(vec![
+            file("map.webp", "image/webp"),
+            file("letter.pdf", "application/pdf"),
+            file("monsters.json", "application/json"),
+        ]);
+        let names = candidates
+            .iter()
+            .map(|file| file.file_name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["map.webp", "letter.pdf"]);
+        assert_eq!(HandoutKind::of(&candidates[1]), Some(HandoutKind::Pdf));
+    }
+
+    #[test]
+    fn titles_drop_the_extension() {
+        assert_eq!(title_from_file_name("Old letter.png"), "Old letter");
+        assert_eq!(title_from_file_name(".hidden"), ".hidden");
+        assert_eq!(title_from_file_name("notes"), "notes");
+    }
+
+    #[test]
+    fn players_see_their_handouts_newest_first() {
+        let handouts = vec![
+            handout("old", &[], 1.0),
+            handout("secret", &["bob"], 2.0),
+            handout("new", &["alice"], 3.0),
+        ];
+        let ids = |user| {
+            visible_handouts(&handouts, user, Some("gm"))
+                .into_iter()
+                .map(|handout| handout.id)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids("alice"), vec!["new", "old"]);
+        assert_eq!(ids("gm"), vec!["new", "secret", "old"]);
+    }
+
+    #[test]
+    fn reveal_grows_until_the_whole_image_is_shown() {
+        let mut reveal = RevealPreset::Center.crop();
+        assert_eq!(revealed_percent(reveal), 6);
+
+        let mut steps = 0;
+        while let Some(crop) = reveal {
+            reveal = grow_reveal(crop, REVEAL_STEP);
+            steps += 1;
+        }
+        assert_eq!(steps, 3);
+        assert_eq!(revealed_percent(reveal), 100);
+
+        let top = grow_reveal(RevealPreset::TopHalf.crop().unwrap(), REVEAL_STEP).unwrap();
+        assert_eq!(crop_rect(top, 800, 600), (0, 0, 800, 375));
+    }
+
+    #[test]
+    fn crop_rect_stays_inside_the_image() {
+        let center = RevealPreset::Center.crop().unwrap();
+        assert_eq!(crop_rect(center, 800, 600), (300, 225, 200, 150));
+
+        let sliver = HandoutCrop {
+            x: 1.0,
+            y: 0.999,
+            width: 0.0,
+            height: 0.0,
+        };
+        assert_eq!(crop_rect(sliver, 10, 10), (9, 9, 1, 1));
+        assert_eq!(
+            reveal_position_style(center),
+            "left: 37.5%; top: 37.5%; width: 25%; height: 25%;"
+        );
+    }
+
+    #[test]
+    fn presets_round_trip_through_their_keys() {
+        for preset in RevealPreset::ALL {
+            assert_eq!(RevealPreset::from_key(preset.key()), preset);
+        }
+        assert_eq!(RevealPreset::from_key("unknown"), RevealPreset::Whole);
+    }
+}
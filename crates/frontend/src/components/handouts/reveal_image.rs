@@ -0,0 +1,63 @@
+use super::model::crop_rect;
+use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
+use leptos::wasm_bindgen::JsCast;
+use shared::events::HandoutCrop;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement, HtmlImageElement};
+
+/// Cuts the revealed region out of the image behind `url` and returns it as
+/// PNG bytes, so players only ever receive the part the game master opened.
+pub async fn render_reveal_png(url: &str, crop: HandoutCrop) -> Result<Vec<u8>, String> {
+    let image =
+        HtmlImageElement::new().map_err(|error| format!("failed to create image: {error:?}"))?;
+    image.set_src(url);
+    JsFuture::from(image.decode())
+        .await
+        .map_err(|error| format!("failed to decode handout image: {error:?}"))?;
+    let (natural_width, natural_height) = (image.natural_width(), image.natural_height());
+    if natural_width == 0 || natural_height == 0 {
+        return Err("handout image is empty".to_string());
+    }
+    let (x, y, width, height) = crop_rect(crop, natural_width, natural_height);
+
+    let document = web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or_else(|| "document is unavailable".to_string())?;
+    let canvas: HtmlCanvasElement = document
+        .create_element("canvas")
+        .map_err(|error| format!("failed to create canvas: {error:?}"))?
+        .dyn_into()
+        .map_err(|_| "canvas element has an unexpected type".to_string())?;
+    canvas.set_width(width);
+    canvas.set_height(height);
+    let context: CanvasRenderingContext2d = canvas
+        .get_context("2d")
+        .map_err(|error| format!("failed to get canvas context: {error:?}"))?
+        .ok_or_else(|| "canvas 2d context is unavailable".to_string())?
+        .dyn_into()
+        .map_err(|_| "canvas context has an unexpected type".to_string())?;
+    context
+        .draw_image_with_html_image_element_and_sw_and_sh_and_dx_and_dy_and_dw_and_dh(
+            &image,
+            f64::from(x),
+            f64::from(y),
+            f64::from(width),
+            f64::from(height),
+            0.0,
+            0.0,
+            f64::from(width),
+            f64::from(height),
+        )
+        .map_err(|error| format!("failed to draw handout crop: {error:?}"))?;
+
+    let data_url = canvas
+        .to_data_url_with_type("image/png")
+        .map_err(|error| format!("failed to encode handout crop: {error:?}"))?;
+    let encoded = data_url
+        .split_once(',')
+        .map(|(_, encoded)| encoded)
+        .ok_or_else(|| "handout crop data URL is malformed".to_string())?;
+    BASE64
+        .decode(encoded)
+        .map_err(|error| format!("failed to decode handout crop: {error}"))
+}
@@ -0,0 +1,720 @@
+use super::model::{
+    HandoutKind, REVEAL_STEP, RevealPreset, grow_reveal, handout_candidates, reveal_position_style,
+    revealed_percent, visible_handouts,
+};
+use super::reveal_image::render_reveal_png;
+use super::view_model::HandoutsWindowViewModel;
+use crate::components::draggable_window::DraggableWindow;
+use crate::components::notes::model::recipients_cache_is_stale;
+use crate::components::websocket::{
+    FileTransferState, StoredHandoutSource, WsSender, delete_handout_source, handout_source_key,
+    save_handout_source,
+};
+use crate::config::Theme;
+use crate::i18n::i18n::{t, t_string, use_i18n};
+use gloo_timers::future::TimeoutFuture;
+use leptos::prelude::*;
+use leptos::task::{spawn_local, spawn_local_scoped};
+use log::warn;
+use shared::events::{
+    ClientEvent, FileRef, Handout, HandoutDeletePayload, HandoutShowPayload,
+    PresenceRequestPayload, acts_as_game_master,
+};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+const HANDOUTS_BODY_FONT_SIZE: &str = "clamp(0.88rem, 0.85rem + 0.12vw, 0.96rem)";
+const HANDOUTS_META_FONT_SIZE: &str = "clamp(0.74rem, 0.71rem + 0.12vw, 0.82rem)";
+const HANDOUTS_BUTTON_FONT_SIZE: &str = "clamp(0.82rem, 0.79rem + 0.12vw, 0.92rem)";
+const RECIPIENT_REQUEST_TIMEOUT_MS: u32 = 1500;
+const OVERLAY_Z_INDEX: u32 = 3000;
+
+fn current_time_ms() -> f64 {
+    js_sys::Date::now()
+}
+
+/// Room and locally stored handouts together.
+fn all_handouts(handouts: RwSignal<Vec<Handout>>, private: RwSignal<Vec<Handout>>) -> Vec<Handout> {
+    let mut all = handouts.get();
+    all.extend(private.get());
+    all
+}
+
+/// Points `handout` at the file players receive. A partial reveal is cut out
+/// of `source` into a new image; for chosen players that image is never
+/// announced to the room.
+async fn render_handout(
+    mut handout: Handout,
+    source: FileRef,
+    file_transfer: &FileTransferState,
+    username: String,
+    ws_sender: Option<WsSender>,
+) -> Result<Handout, String> {
+    let Some(crop) = handout.reveal else {
+        handout.file = source;
+        return Ok(handout);
+    };
+    let url = file_transfer
+        .file_urls
+        .with_untracked(|urls| urls.get(&source.hash).cloned())
+        .ok_or_else(|| format!("'{}' is not loaded yet", source.file_name))?;
+    let bytes = render_reveal_png(&url, crop).await?;
+    let file_name = format!("{}.png", handout.title);
+    handout.file = match handout.audience(&username) {
+        Some(audience) => {
+            file_transfer
+                .import_restricted_bytes(&bytes, file_name, "image/png", &audience)
+                .await?
+        }
+        None => {
+            file_transfer
+                .import_bytes(&bytes, file_name, "image/png", username, ws_sender)
+                .await?
+        }
+    };
+    Ok(handout)
+}
+
+#[component]
+pub fn HandoutsWindow(
+    #[prop(into)] is_open: RwSignal<bool>,
+    #[prop(into)] handouts: RwSignal<Vec<Handout>>,
+    /// Handouts addressed to chosen players, kept on this device.
+    #[prop(into)]
+    private_handouts: RwSignal<Vec<Handout>>,
+    /// Original images of partially revealed handouts, by handout id.
+    #[prop(into)]
+    handout_sources: RwSignal<HashMap<String, FileRef>>,
+    #[prop(into)] game_master: RwSignal<Option<String>>,
+    /// Handout shown full-screen on this device.
+    #[prop(into)]
+    presented_handout: RwSignal<Option<String>>,
+    /// Online users, shared with direct notes.
+    #[prop(into)]
+    online_users: RwSignal<Vec<String>>,
+    #[prop(into)] online_users_cache_updated_at_ms: RwSignal<Option<f64>>,
+    #[prop(into)] online_users_request_id: RwSignal<Option<String>>,
+    file_transfer: FileTransferState,
+    ws_sender: ReadSignal<Option<WsSender>>,
+    username: ReadSignal<String>,
+    room_id: ReadSignal<String>,
+    #[prop(into, optional)] is_active: Signal<bool>,
+    #[prop(optional)] on_focus: Option<Callback<()>>,
+    theme: Theme,
+) -> impl IntoView {
+    let i18n = use_i18n();
+    let vm = HandoutsWindowViewModel::new();
+
+    let send_event = move |event: ClientEvent| {
+        if let Some(sender) = ws_sender.get_untracked() {
+            let _ = sender.try_send_event(event);
+        }
+    };
+    let show_handout = move |handout: Handout| {
+        send_event(ClientEvent::HandoutShow(HandoutShowPayload {
+            handout,
+            actor: username.get_untracked(),
+        }));
+    };
+    let file_transfer_for_share = file_transfer.clone();
+    // Shows a handout whose `file` is still the original image.
+    let share_handout = Callback::new(move |(handout, source): (Handout, FileRef)| {
+        let file_transfer = file_transfer_for_share.clone();
+        let username = username.get_untracked();
+        let room_name = room_id.get_untracked();
+        spawn_local_scoped(async move {
+            let rendered = render_handout(
+                handout,
+                source.clone(),
+                &file_transfer,
+                username,
+                ws_sender.get_untracked(),
+            )
+            .await;
+            let handout = match rendered {
+                Ok(handout) => handout,
+                Err(error) => {
+                    vm.error.set(Some(error));
+                    return;
+                }
+            };
+            if handout.reveal.is_some() {
+                handout_sources.update(|sources| {
+                    sources.insert(handout.id.clone(), source.clone());
+                });
+                let record = StoredHandoutSource {
+                    key: handout_source_key(&room_name, &handout.id),
+                    room_name,
+                    handout_id: handout.id.clone(),
+                    source,
+                };
+                if let Err(error) = save_handout_source(&record).await {
+                    warn!("Failed to persist handout source to IndexedDB: {}", error);
+                }
+            }
+            show_handout(handout);
+        });
+    });
+    let delete_handout = move |handout: &Handout| {
+        send_event(ClientEvent::HandoutDelete(HandoutDeletePayload {
+            id: handout.id.clone(),
+            recipients: handout.recipients.clone(),
+            actor: username.get_untracked(),
+        }));
+        handout_sources.update(|sources| {
+            sources.remove(&handout.id);
+        });
+        let room_name = room_id.get_untracked();
+        let id = handout.id.clone();
+        spawn_local(async move {
+            if let Err(error) = delete_handout_source(&room_name, &id).await {
+                warn!("Failed to delete handout source from IndexedDB: {}", error);
+            }
+        });
+    };
+
+    let viewer_is_gm =
+        Memo::new(move |_| acts_as_game_master(game_master.get().as_deref(), &username.get()));
+    let gallery = Memo::new(move |_| {
+        visible_handouts(
+            &all_handouts(handouts, private_handouts),
+            &username.get(),
+            game_master.get().as_deref(),
+        )
+    });
+    let file_transfer_for_candidates = file_transfer.clone();
+    let candidates =
+        Memo::new(move |_| handout_candidates(file_transfer_for_candidates.known_files()));
+    let players = Memo::new(move |_| {
+        let me = username.get();
+        let gm = game_master.get();
+        online_users
+            .get()
+            .into_iter()
+            .filter(|user| *user != me && Some(user) != gm.as_ref())
+            .collect::<Vec<_>>()
+    });
+
+    // Handouts shown before this device joined are fetched from peers.
+    let file_transfer_for_load = file_transfer.clone();
+    Effect::new(move |_| {
+        if !is_open.get() {
+            return;
+        }
+        let file_urls = file_transfer_for_load.file_urls.get();
+        for handout in gallery.get() {
+            if !file_urls.contains_key(&handout.file.hash) {
+                file_transfer_for_load.request_file(
+                    handout.file,
+                    username.get_untracked(),
+                    ws_sender.get_untracked(),
+                );
+            }
+        }
+    });
+
+    let refresh_players = move || {
+        let Some(sender) = ws_sender.get_untracked() else {
+            return;
+        };
+        let current_user = username.get_untracked();
+        let requested_at_ms = current_time_ms();
+        let request_id = format!(
+            "handout_recipients:{}:{}",
+            current_user, requested_at_ms as u64
+        );
+
+        online_users_request_id.set(Some(request_id.clone()));
+        vm.is_loading_recipients.set(true);
+        if sender
+            .try_send_event(ClientEvent::PresenceRequest(PresenceRequestPayload {
+                request_id: request_id.clone(),
+                requester: current_user,
+            }))
+            .is_err()
+        {
+            online_users_request_id.set(None);
+            vm.is_loading_recipients.set(false);
+            return;
+        }
+        online_users.set(Vec::new());
+        online_users_cache_updated_at_ms.set(Some(requested_at_ms));
+
+        spawn_local_scoped(async move {
+            TimeoutFuture::new(RECIPIENT_REQUEST_TIMEOUT_MS).await;
+            if online_users_request_id.get_untracked().as_deref() == Some(request_id.as_str()) {
+                vm.is_loading_recipients.set(false);
+            }
+        });
+    };
+
+    Effect::new(move |_| {
+        if !is_open.get() || !viewer_is_gm.get() {
+            return;
+        }
+        if recipients_cache_is_stale(online_users_cache_updated_at_ms.get(), current_time_ms()) {
+            refresh_players();
+        }
+    });
+
+    let on_show = move || {
+        let hash = vm.selected_hash.get_untracked();
+        let Some(file) =
+            candidates.with_untracked(|files| files.iter().find(|file| file.hash == hash).cloned())
+        else {
+            vm.error
+                .set(Some(t_string!(i18n, handouts.error_no_file).to_string()));
+            return;
+        };
+        share_handout.run((
+            vm.build(Uuid::new_v4().to_string(), file.clone(), current_time_ms()),
+            file,
+        ));
+        vm.selected_hash.set(String::new());
+        vm.title.set(String::new());
+        vm.error.set(None);
+    };
+
+    let input_style = format!(
+        "padding: 0.45rem; background: {}; color: {}; border: 0.0625rem solid {}; border-radius: 0.5rem; font-size: {}; min-width: 0;",
+        theme.ui_bg_secondary, theme.ui_text_primary, theme.ui_border, HANDOUTS_BODY_FONT_SIZE
+    );
+    let button_style = format!(
+        "padding: 0.45rem 0.75rem; background: {}; color: {}; border: none; border-radius: 0.5rem; cursor: pointer; font-size: {};",
+        theme.ui_button_primary, theme.ui_text_primary, HANDOUTS_BUTTON_FONT_SIZE
+    );
+    let secondary_button_style = format!(
+        "padding: 0.35rem 0.6rem; background: none; color: {}; border: 0.0625rem solid {}; border-radius: 0.5rem; cursor: pointer; font-size: {};",
+        theme.ui_text_secondary, theme.ui_border, HANDOUTS_BUTTON_FONT_SIZE
+    );
+    let section_style = format!(
+        "display: flex; flex-direction: column; gap: 0.5rem; padding-top: 0.75rem; border-top: 0.0625rem solid {};",
+        theme.ui_border
+    );
+    let heading_style = format!("margin: 0; color: {};", theme.ui_text_primary);
+    let meta_style = format!(
+        "color: {}; font-size: {};",
+        theme.ui_text_secondary, HANDOUTS_META_FONT_SIZE
+    );
+    let hint_style = format!(
+        "color: {}; font-size: {}; font-style: italic;",
+        theme.ui_text_muted, HANDOUTS_META_FONT_SIZE
+    );
+    let composer_heading_style = heading_style.clone();
+    let composer_meta_style = meta_style.clone();
+    let composer_hint_style = hint_style.clone();
+    let gallery_button_style = secondary_button_style.clone();
+
+    view! {
+        <DraggableWindow
+            is_open=is_open
+            title=move || t_string!(i18n, handouts.title)
+            initial_x=360
+            initial_y=130
+            initial_width=560
+            initial_height=600
+            min_width=400
+            min_height=340
+            is_active=is_active
+            on_focus=on_focus.unwrap_or_else(|| Callback::new(|_| {}))
+            theme=theme.clone()
+        >
+            <div style=format!(
+                "display: flex; flex-direction: column; flex: 1; min-height: 0; overflow-y: auto; padding: 1rem; gap: 0.75rem; color: {}; font-size: {};",
+                theme.ui_text_primary, HANDOUTS_BODY_FONT_SIZE
+            )>
+                // Composer (game master only)
+                {move || viewer_is_gm.get().then(|| {
+                    let input_style = input_style.clone();
+                    let button_style = button_style.clone();
+                    let secondary_button_style = secondary_button_style.clone();
+                    let heading_style = composer_heading_style.clone();
+                    let meta_style = composer_meta_style.clone();
+                    let hint_style = composer_hint_style.clone();
+                    let reveal_label_style = meta_style.clone();
+                    let reveal_select_style = format!("{} flex: 1;", input_style);
+                    let is_image = move || {
+                        let hash = vm.selected_hash.get();
+                        candidates.with(|files| {
+                            files
+                                .iter()
+                                .find(|file| file.hash == hash)
+                                .and_then(HandoutKind::of)
+                                == Some(HandoutKind::Image)
+                        })
+                    };
+                    view! {
+                        <div style="display: flex; flex-direction: column; gap: 0.5rem;">
+                            <h4 style=heading_style>{t!(i18n, handouts.compose_title)}</h4>
+                            <select
+                                prop:value=move || vm.selected_hash.get()
+                                on:change=move |ev| {
+                                    let hash = event_target_value(&ev);
+                                    match candidates.with_untracked(|files| files.iter().find(|file| file.hash == hash).cloned()) {
+                                        Some(file) => vm.select_file(&file),
+                                        None => vm.selected_hash.set(String::new()),
+                                    }
+                                }
+                                style=input_style.clone()
+                            >
+                                <option value="">{move || t!(i18n, handouts.file_placeholder)}</option>
+                                {move || candidates.get().into_iter().map(|file| view! {
+                                    <option value=file.hash.clone()>{file.file_name.clone()}</option>
+                                }).collect_view()}
+                            </select>
+                            {move || candidates.with(Vec::is_empty).then(|| view! {
+                                <span style=hint_style.clone()>{t!(i18n, handouts.files_empty)}</span>
+                            })}
+                            <input
+                                type="text"
+                                maxlength="120"
+                                placeholder=move || t_string!(i18n, handouts.title_placeholder)
+                                prop:value=move || vm.title.get()
+                                on:input=move |ev| vm.title.set(event_target_value(&ev))
+                                style=input_style.clone()
+                            />
+                            <Show when=is_image>
+                                <label style="display: flex; align-items: center; gap: 0.5rem;">
+                                    <span style=reveal_label_style.clone()>{t!(i18n, handouts.reveal_label)}</span>
+                                    <select
+                                        prop:value=move || vm.preset.get().key()
+                                        on:change=move |ev| vm.preset.set(RevealPreset::from_key(&event_target_value(&ev)))
+                                        style=reveal_select_style.clone()
+                                    >
+                                        {RevealPreset::ALL.into_iter().map(|preset| view! {
+                                            <option value=preset.key()>
+                                                {move || match preset {
+                                                    RevealPreset::Whole => t_string!(i18n, handouts.reveal_whole),
+                                                    RevealPreset::TopHalf => t_string!(i18n, handouts.reveal_top),
+                                                    RevealPreset::BottomHalf => t_string!(i18n, handouts.reveal_bottom),
+                                                    RevealPreset::LeftHalf => t_string!(i18n, handouts.reveal_left),
+                                                    RevealPreset::RightHalf => t_string!(i18n, handouts.reveal_right),
+                                                    RevealPreset::Center => t_string!(i18n, handouts.reveal_center),
+                                                }}
+                                            </option>
+                                        }).collect_view()}
+                                    </select>
+                                </label>
+                            </Show>
+                            <div style="display: flex; flex-wrap: wrap; align-items: center; gap: 0.4rem;">
+                                <span style=meta_style.clone()>{t!(i18n, handouts.recipients_label)}</span>
+                                <label style="display: inline-flex; align-items: center; gap: 0.25rem;">
+                                    <input
+                                        type="radio"
+                                        prop:checked=move || vm.recipients.with(Vec::is_empty)
+                                        on:change=move |_| vm.recipients.set(Vec::new())
+                                    />
+                                    {t!(i18n, handouts.everyone)}
+                                </label>
+                                {move || players.get().into_iter().map(|user| {
+                                    let checked_user = user.clone();
+                                    let toggled_user = user.clone();
+                                    view! {
+                                        <label style="display: inline-flex; align-items: center; gap: 0.25rem;">
+                                            <input
+                                                type="checkbox"
+                                                prop:checked=move || vm.recipients.with(|recipients| recipients.contains(&checked_user))
+                                                on:change=move |_| vm.toggle_recipient(&toggled_user)
+                                            />
+                                            {user}
+                                        </label>
+                                    }
+                                }).collect_view()}
+                                <button
+                                    disabled=move || vm.is_loading_recipients.get()
+                                    on:click=move |_| refresh_players()
+                                    style=secondary_button_style.clone()
+                                >
+                                    {move || if vm.is_loading_recipients.get() {
+                                        t_string!(i18n, handouts.recipients_loading)
+                                    } else {
+                                        t_string!(i18n, handouts.recipients_refresh)
+                                    }}
+                                </button>
+                            </div>
+                            <button
+                                disabled=move || vm.selected_hash.with(String::is_empty)
+                                on:click=move |_| on_show()
+                                style=button_style.clone()
+                            >
+                                {move || t!(i18n, handouts.show_button)}
+                            </button>
+                            {move || vm.error.get().map(|error| view! {
+                                <div style=format!("color: {}; font-size: {};", theme.ui_button_danger, HANDOUTS_META_FONT_SIZE)>
+                                    {error}
+                                </div>
+                            })}
+                        </div>
+                    }
+                })}
+
+                // Gallery
+                <div style=section_style>
+                    <h4 style=heading_style>{move || t!(i18n, handouts.gallery_title)}</h4>
+                    {move || {
+                        let items = gallery.get();
+                        if items.is_empty() {
+                            return view! {
+                                <span style=hint_style.clone()>{t!(i18n, handouts.gallery_empty)}</span>
+                            }.into_any();
+                        }
+                        let is_gm = viewer_is_gm.get();
+                        let file_urls = file_transfer.file_urls.get();
+                        let sources = handout_sources.get();
+                        items.into_iter().map(|handout| {
+                            let url = file_urls.get(&handout.file.hash).cloned();
+                            let is_image = HandoutKind::of(&handout.file) == Some(HandoutKind::Image);
+                            let recipients = if handout.recipients.is_empty() {
+                                t_string!(i18n, handouts.everyone).to_string()
+                            } else {
+                                handout.recipients.join(", ")
+                            };
+                            let open_id = handout.id.clone();
+                            let gm_controls = is_gm.then(|| {
+                                // Growing a reveal re-renders it from the original image.
+                                let source = handout
+                                    .reveal
+                                    .and(sources.get(&handout.id).cloned());
+                                let reveal_more = handout.reveal.zip(source.clone()).map(|(crop, source)| (
+                                    Handout {
+                                        reveal: grow_reveal(crop, REVEAL_STEP),
+                                        ..handout.clone()
+                                    },
+                                    source,
+                                ));
+                                let reveal_all = source.map(|source| (
+                                    Handout {
+                                        reveal: None,
+                                        ..handout.clone()
+                                    },
+                                    source,
+                                ));
+                                let show_again = handout.clone();
+                                let deleted = handout.clone();
+                                let button_style = gallery_button_style.clone();
+                                view! {
+                                    {reveal_more.map(|(handout, source)| {
+                                        let button_style = button_style.clone();
+                                        view! {
+                                            <button
+                                                on:click=move |_| share_handout.run((
+                                                    Handout { shown_at_ms: current_time_ms(), ..handout.clone() },
+                                                    source.clone(),
+                                                ))
+                                                style=button_style
+                                            >
+                                                {t!(i18n, handouts.reveal_more_button)}
+                                            </button>
+                                        }
+                                    })}
+                                    {reveal_all.map(|(handout, source)| {
+                                        let button_style = button_style.clone();
+                                        view! {
+                                            <button
+                                                on:click=move |_| share_handout.run((
+                                                    Handout { shown_at_ms: current_time_ms(), ..handout.clone() },
+                                                    source.clone(),
+                                                ))
+                                                style=button_style
+                                            >
+                                                {t!(i18n, handouts.reveal_all_button)}
+                                            </button>
+                                        }
+                                    })}
+                                    <button
+                                        on:click=move |_| show_handout(Handout { shown_at_ms: current_time_ms(), ..show_again.clone() })
+                                        style=button_style.clone()
+                                    >
+                                        {t!(i18n, handouts.show_again_button)}
+                                    </button>
+                                    <button
+                                        title=move || t_string!(i18n, handouts.remove_button)
+                                        on:click=move |_| delete_handout(&deleted)
+                                        style=format!("background: none; border: none; cursor: pointer; color: {};", theme.ui_button_danger)
+                                    >
+                                        "✕"
+                                    </button>
+                                }
+                            });
+                            view! {
+                                <div style=format!(
+                                    "display: flex; align-items: center; gap: 0.6rem; padding: 0.45rem 0.6rem; border: 0.0625rem solid {}; border-radius: 0.5rem;",
+                                    theme.ui_border
+                                )>
+                                    <button
+                                        on:click=move |_| presented_handout.set(Some(open_id.clone()))
+                                        style=format!(
+                                            "flex-shrink: 0; width: 3.5rem; height: 3.5rem; padding: 0; overflow: hidden; background: {}; border: 0.0625rem solid {}; border-radius: 0.4rem; cursor: pointer; font-size: 1.5rem;",
+                                            theme.ui_bg_secondary, theme.ui_border
+                                        )
+                                    >
+                                        {match (is_image, url) {
+                                            (true, Some(url)) => view! {
+                                                <img
+                                                    src=url
+                                                    alt=""
+                                                    style="width: 100%; height: 100%; object-fit: cover;"
+                                                />
+                                            }.into_any(),
+                                            (true, None) => "🖼️".into_any(),
+                                            (false, _) => "📄".into_any(),
+                                        }}
+                                    </button>
+                                    <div style="display: flex; flex-direction: column; gap: 0.15rem; flex: 1; min-width: 0;">
+                                        <strong style="overflow: hidden; text-overflow: ellipsis; white-space: nowrap;">
+                                            {handout.title.clone()}
+                                        </strong>
+                                        <span style=meta_style.clone()>
+                                            {format!("{} · {}", handout.file.file_name, recipients)}
+                                            {(is_image && handout.reveal.is_some()).then(|| format!(
+                                                " · {} {}%",
+                                                t_string!(i18n, handouts.revealed),
+                                                revealed_percent(handout.reveal)
+                                            ))}
+                                        </span>
+                                    </div>
+                                    <div style="display: flex; flex-wrap: wrap; justify-content: flex-end; gap: 0.3rem;">
+                                        {gm_controls}
+                                    </div>
+                                </div>
+                            }
+                        }).collect_view().into_any()
+                    }}
+                </div>
+            </div>
+        </DraggableWindow>
+    }
+}
+
+/// Full-screen view of the handout the game master has just shown, or one
+/// reopened from the gallery.
+#[component]
+pub fn HandoutOverlay(
+    #[prop(into)] presented_handout: RwSignal<Option<String>>,
+    #[prop(into)] handouts: RwSignal<Vec<Handout>>,
+    #[prop(into)] private_handouts: RwSignal<Vec<Handout>>,
+    #[prop(into)] handout_sources: RwSignal<HashMap<String, FileRef>>,
+    #[prop(into)] game_master: RwSignal<Option<String>>,
+    file_transfer: FileTransferState,
+    ws_sender: ReadSignal<Option<WsSender>>,
+    username: ReadSignal<String>,
+    theme: Theme,
+) -> impl IntoView {
+    let i18n = use_i18n();
+
+    let handout = Memo::new(move |_| {
+        let id = presented_handout.get()?;
+        let user = username.get();
+        let game_master = game_master.get();
+        all_handouts(handouts, private_handouts)
+            .into_iter()
+            .find(|handout| handout.id == id)
+            .filter(|handout| handout.is_visible_to(&user, game_master.as_deref()))
+    });
+    let viewer_is_gm =
+        Memo::new(move |_| acts_as_game_master(game_master.get().as_deref(), &username.get()));
+
+    let file_transfer_for_request = file_transfer.clone();
+    Effect::new(move |_| {
+        let Some(handout) = handout.get() else {
+            return;
+        };
+        if !file_transfer_for_request
+            .file_urls
+            .with(|urls| urls.contains_key(&handout.file.hash))
+        {
+            file_transfer_for_request.request_file(
+                handout.file,
+                username.get_untracked(),
+                ws_sender.get_untracked(),
+            );
+        }
+    });
+
+    let close = move || presented_handout.set(None);
+
+    move || {
+        let handout = handout.get()?;
+        let (url, source_url) = file_transfer.file_urls.with(|urls| {
+            let source_url = handout_sources.with(|sources| {
+                sources
+                    .get(&handout.id)
+                    .and_then(|source| urls.get(&source.hash).cloned())
+            });
+            (urls.get(&handout.file.hash).cloned(), source_url)
+        });
+        let gm_reveal = handout
+            .reveal
+            .zip(source_url)
+            .filter(|_| viewer_is_gm.get());
+        let content = match (HandoutKind::of(&handout.file), url, gm_reveal) {
+            (Some(HandoutKind::Pdf), Some(url), _) => view! {
+                <iframe
+                    src=url
+                    title=handout.title.clone()
+                    style="width: 90vw; height: 82vh; border: none; border-radius: 0.5rem; background: #fff;"
+                />
+            }
+            .into_any(),
+            // The game master sees the hidden part dimmed to judge the next step.
+            (_, Some(url), Some((crop, source_url))) => view! {
+                <div style="position: relative; line-height: 0;">
+                    <img
+                        src=source_url
+                        alt=""
+                        style="display: block; max-width: 92vw; max-height: 82vh; opacity: 0.3;"
+                    />
+                    <img
+                        src=url
+                        alt=handout.title.clone()
+                        style=format!("position: absolute; {}", reveal_position_style(crop))
+                    />
+                </div>
+            }
+            .into_any(),
+            (_, Some(url), None) => view! {
+                <img
+                    src=url
+                    alt=handout.title.clone()
+                    style="display: block; max-width: 92vw; max-height: 82vh;"
+                />
+            }
+            .into_any(),
+            (_, None, _) => view! {
+                <span style=format!("color: {}; font-style: italic;", theme.ui_text_muted)>
+                    {t!(i18n, handouts.loading)}
+                </span>
+            }
+            .into_any(),
+        };
+
+        Some(view! {
+            <div
+                on:click=move |_| close()
+                style=format!(
+                    "position: fixed; inset: 0; z-index: {}; display: flex; flex-direction: column; \
+                     align-items: center; justify-content: center; gap: 0.75rem; \
+                     background: rgba(0, 0, 0, 0.82);",
+                    OVERLAY_Z_INDEX
+                )
+            >
+                <div style=format!(
+                    "display: flex; align-items: center; gap: 1rem; color: {}; font-size: 1.1rem;",
+                    theme.ui_text_primary
+                )>
+                    <strong>{handout.title.clone()}</strong>
+                    <button
+                        on:click=move |_| close()
+                        style=format!(
+                            "padding: 0.35rem 0.75rem; background: {}; color: {}; border: none; border-radius: 0.5rem; cursor: pointer;",
+                            theme.ui_button_primary, theme.ui_text_primary
+                        )
+                    >
+                        {t!(i18n, handouts.close_button)}
+                    </button>
+                </div>
+                <div on:click=|ev| ev.stop_propagation()>{content}</div>
+            </div>
+        })
+    }
+}
@@ -0,0 +1,112 @@
+use super::model::{
+    HandoutKind, MAX_HANDOUT_RECIPIENTS, MAX_HANDOUT_TITLE_LEN, RevealPreset, title_from_file_name,
+};
+use leptos::prelude::*;
+use shared::events::{FileRef, Handout};
+
+/// Reactive state for the game master's handout composer.
+#[derive(Clone, Copy)]
+pub struct HandoutsWindowViewModel {
+    pub selected_hash: RwSignal<String>,
+    pub title: RwSignal<String>,
+    /// Chosen players; empty means everyone in the room.
+    pub recipients: RwSignal<Vec<String>>,
+    pub preset: RwSignal<RevealPreset>,
+    pub is_loading_recipients: RwSignal<bool>,
+    pub error: RwSignal<Option<String>>,
+}
+
+impl HandoutsWindowViewModel {
+    pub fn new() -> Self {
+        Self {
+            selected_hash: RwSignal::new(String::new()),
+            title: RwSignal::new(String::new()),
+            recipients: RwSignal::new(Vec::new()),
+            preset: RwSignal::new(RevealPreset::Whole),
+            is_loading_recipients: RwSignal::new(false),
+            error: RwSignal::new(None),
+        }
+    }
+
+    /// Picks a file and suggests its name as the title.
+    pub fn select_file(&self, file: &FileRef) {
+        self.selected_hash.set(file.hash.clone());
+        self.title.set(title_from_file_name(&file.file_name));
+        self.error.set(None);
+    }
+
+    pub fn toggle_recipient(&self, user: &str) {
+        self.recipients.update(|recipients| {
+            match recipients.iter().position(|recipient| recipient == user) {
+                Some(index) => {
+                    recipients.remove(index);
+                }
+                None if recipients.len() < MAX_HANDOUT_RECIPIENTS => {
+                    recipients.push(user.to_string());
+                    recipients.sort();
+                }
+                None => {}
+            }
+        });
+    }
+
+    /// The handout to show; PDFs are always shown whole.
+    pub fn build(&self, id: String, file: FileRef, shown_at_ms: f64) -> Handout {
+        let title = self.title.get_untracked();
+        let title = match title.trim() {
+            "" => title_from_file_name(&file.file_name),
+            title => title.chars().take(MAX_HANDOUT_TITLE_LEN).collect(),
+        };
+        let reveal = match HandoutKind::of(&file) {
+            Some(HandoutKind::Image) => self.preset.get_untracked().crop(),
+            _ => None,
+        };
+        Handout {
+            id,
+            title,
+            file,
+            recipients: self.recipients.get_untracked(),
+            reveal,
+            shown_at_ms,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use leptos::reactive::owner::Owner;
+
+    fn file(name: &str, mime_type: &str) -> FileRef {
+        FileRef {
+            hash: name.to_string(),
+            mime_type: mime_type.to_string(),
+            file_name: name.to_string(),
+            size: 1,
+        }
+    }
+
+    #[test]
+    fn composer_builds_the_handout_to_show() {
+        let owner = Owner::new();
+        owner.with(|| {
+            let vm = HandoutsWindowViewModel::new();
+            let map = file("Crypt map.png", "image/png");
+            vm.select_file(&map);
+            vm.preset.set(RevealPreset::TopHalf);
+            vm.toggle_recipient("bob");
+            vm.toggle_recipient("alice");
+            vm.toggle_recipient("bob");
+
+            let handout = vm.build("h1".to_string(), map, 5.0);
+            assert_eq!(handout.title, "Crypt map");
+            assert_eq!(handout.recipients, vec!["alice".to_string()]);
+            assert_eq!(handout.reveal, RevealPreset::TopHalf.crop());
+
+            vm.title.set("  ".to_string());
+            let letter = vm.build("h2".to_string(), file("letter.pdf", "application/pdf"), 6.0);
+            assert_eq!(letter.title, "letter");
+            assert_eq!(letter.reveal, None);
+        });
+    }
+}
@@ -1,8 +1,12 @@
 pub mod app;
+pub mod characters;
+pub mod compendium;
 pub mod chat;
 pub mod conflict_resolver;
 pub mod cursor;
 pub mod draggable_window;
+pub mod encounters;
+pub mod handouts;
 pub mod language_selector;
 pub mod login;
 pub mod notes;
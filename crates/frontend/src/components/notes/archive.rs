@@ -0,0 +1,672 @@
+// Pure journal archive: notes as Markdown files with YAML front matter plus
+// the room files they embed, packed for Obsidian and other Markdown editors.
+// No signals, no Leptos, no web_sys.
+
+use super::embeds::is_file_hash;
+use super::model::note_title_from_markdown;
+use super::search::{normalize_folder_path, normalize_tags};
+use crate::utils::zip::ZipEntry;
+use shared::events::{
+    FileRef, NoteBoardPosition, NoteBoardStyle, NoteGroup, NotePayload, NoteVisibility,
+};
+use std::collections::{HashMap, HashSet};
+
+// --- Constants ---
+
+/// Folder inside the archive holding embedded room files.
+pub const JOURNAL_FILES_DIR: &str = "files";
+/// Unpacked size an imported archive may reach.
+pub const MAX_JOURNAL_ARCHIVE_BYTES: usize = 64 * 1024 * 1024;
+/// Matches the note body limit validated on the wire.
+pub const MAX_NOTE_BODY_CHARS: usize = 20_000;
+const MAX_NOTE_TITLE_CHARS: usize = 120;
+const MAX_FILE_STEM_CHARS: usize = 80;
+const FRONT_MATTER_FENCE: &str = "---";
+
+// --- Export ---
+
+/// Archive path of an embedded room file: `files/<sha256>.<ext>`.
+pub fn archive_file_path(file: &FileRef) -> String {
+    let extension = file
+        .file_name
+        .rsplit_once('.')
+        .map(|(_, extension)| extension.to_ascii_lowercase())
+        .filter(|extension| {
+            !extension.is_empty()
+                && extension.len() <= 5
+                && extension.chars().all(|c| c.is_ascii_alphanumeric())
+        })
+        .or_else(|| extension_for_mime_type(&file.mime_type).map(str::to_string));
+    match extension {
+        Some(extension) => format!("{JOURNAL_FILES_DIR}/{}.{extension}", file.hash),
+        None => format!("{JOURNAL_FILES_DIR}/{}", file.hash),
+    }
+}
+
+/// Zip entries for `notes`: one Markdown file per note under its folder, and
+/// every embedded file available in `files` (keyed by hash, with its bytes).
+pub fn journal_archive(
+    notes: &[NotePayload],
+    files: &HashMap<String, (FileRef, Vec<u8>)>,
+) -> Vec<ZipEntry> {
+    let file_paths = files
+        .iter()
+        .map(|(hash, (file, _))| (hash.clone(), archive_file_path(file)))
+        .collect::<HashMap<_, _>>();
+    let mut used_paths = HashSet::new();
+    let mut entries = Vec::new();
+    for note in notes {
+        let path = unique_note_path(note, &mut used_paths);
+        let depth = path.matches('/').count();
+        let body = rewrite_link_targets(&note.body, |target| {
+            let hash = target
+                .strip_prefix("file:")
+                .filter(|hash| is_file_hash(hash))?;
+            let file_path = file_paths.get(hash)?;
+            Some(format!("{}{file_path}", "../".repeat(depth)))
+        });
+        entries.push(ZipEntry {
+            name: path,
+            data: note_document(note, &body).into_bytes(),
+        });
+    }
+    let mut embedded = files.values().collect::<Vec<_>>();
+    embedded.sort_by(|left, right| left.0.hash.cmp(&right.0.hash));
+    for (file, bytes) in embedded {
+        entries.push(ZipEntry {
+            name: archive_file_path(file),
+            data: bytes.clone(),
+        });
+    }
+    entries
+}
+
+/// A note as Markdown with YAML front matter. Values are written as JSON,
+/// which YAML reads as quoted strings, flow lists and flow mappings.
+pub fn note_document(note: &NotePayload, body: &str) -> String {
+    let mut lines = vec![FRONT_MATTER_FENCE.to_string()];
+    let mut field = |key: &str, value: String| lines.push(format!("{key}: {value}"));
+    field("id", json_string(&note.id));
+    field("title", json_string(&note.title));
+    field("author", json_string(&note.author));
+    match &note.visibility {
+        NoteVisibility::Public => field("visibility", json_string("public")),
+        NoteVisibility::Private => field("visibility", json_string("private")),
+        NoteVisibility::Direct(recipient) => {
+            field("visibility", json_string("direct"));
+            field("recipient", json_string(recipient));
+        }
+        NoteVisibility::Group(group) => {
+            field("visibility", json_string("group"));
+            if !group.name.is_empty() {
+                field("group", json_string(&group.name));
+            }
+            field("members", json_list(&group.members));
+        }
+    }
+    if !note.folder.is_empty() {
+        field("folder", json_string(&note.folder));
+    }
+    if !note.tags.is_empty() {
+        field("tags", json_list(&note.tags));
+    }
+    if note.pinned {
+        field("pinned", "true".to_string());
+    }
+    field("created_at_ms", format!("{:.0}", note.created_at_ms));
+    field("updated_at_ms", format!("{:.0}", note.updated_at_ms));
+    if let Some(position) = &note.board_position {
+        field(
+            "board_position",
+            serde_json::to_string(position).unwrap_or_default(),
+        );
+        field(
+            "board_style",
+            serde_json::to_string(&note.board_style).unwrap_or_default(),
+        );
+    }
+    lines.push(FRONT_MATTER_FENCE.to_string());
+    lines.push(String::new());
+    lines.push(body.to_string());
+    let mut document = lines.join("\n");
+    if !document.ends_with('\n') {
+        document.push('\n');
+    }
+    document
+}
+
+fn unique_note_path(note: &NotePayload, used_paths: &mut HashSet<String>) -> String {
+    let folder = note
+        .folder
+        .split('/')
+        .map(file_name_segment)
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>()
+        .join("/");
+    let title = if note.title.trim().is_empty() {
+        note_title_from_markdown(&note.body)
+    } else {
+        note.title.clone()
+    };
+    let mut stem = file_name_segment(&title);
+    if stem.is_empty() {
+        stem = file_name_segment(&note.id);
+    }
+    let prefix = if folder.is_empty() {
+        String::new()
+    } else {
+        format!("{folder}/")
+    };
+    let mut path = format!("{prefix}{stem}.md");
+    let mut copy = 2;
+    while !used_paths.insert(path.to_lowercase()) {
+        path = format!("{prefix}{stem} ({copy}).md");
+        copy += 1;
+    }
+    path
+}
+
+/// A file or folder name safe on every desktop file system.
+fn file_name_segment(name: &str) -> String {
+    let cleaned = name
+        .chars()
+        .map(|c| {
+            if c.is_control() || matches!(c, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|') {
+                ' '
+            } else {
+                c
+            }
+        })
+        .collect::<String>();
+    cleaned
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .trim_matches('.')
+        .chars()
+        .take(MAX_FILE_STEM_CHARS)
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+fn json_string(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_default()
+}
+
+fn json_list(values: &[String]) -> String {
+    serde_json::to_string(values).unwrap_or_default()
+}
+
+// --- Import ---
+
+/// A note read from an archive, with the archive path it came from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedNote {
+    pub path: String,
+    pub note: NotePayload,
+}
+
+/// Reads every Markdown file of an archive as a note owned by `author`.
+/// Notes without front matter are private; files with an empty or oversized
+/// body are counted in the second value and skipped.
+pub fn parse_journal_archive(
+    entries: &[ZipEntry],
+    author: &str,
+    now_ms: f64,
+    mut new_id: impl FnMut() -> String,
+) -> (Vec<ImportedNote>, usize) {
+    let mut notes = Vec::new();
+    let mut skipped = 0;
+    for entry in entries {
+        let is_markdown = entry.name.to_lowercase().ends_with(".md");
+        let is_hidden = entry
+            .name
+            .split('/')
+            .any(|segment| segment.starts_with('.') || segment == "__MACOSX");
+        if !is_markdown || is_hidden {
+            continue;
+        }
+        let text = String::from_utf8_lossy(&entry.data);
+        match parse_note_document(&entry.name, &text, author, now_ms, &mut new_id) {
+            Some(note) => notes.push(ImportedNote {
+                path: entry.name.clone(),
+                note,
+            }),
+            None => skipped += 1,
+        }
+    }
+    (notes, skipped)
+}
+
+/// Parses one Markdown note; `None` when its body is empty or too long.
+pub fn parse_note_document(
+    path: &str,
+    text: &str,
+    author: &str,
+    now_ms: f64,
+    new_id: &mut impl FnMut() -> String,
+) -> Option<NotePayload> {
+    let (fields, body) = split_front_matter(text);
+    let body = body.trim().to_string();
+    if body.is_empty() || body.chars().count() > MAX_NOTE_BODY_CHARS {
+        return None;
+    }
+    let scalar = |key: &str| {
+        fields
+            .get(key)
+            .and_then(|value| value.first())
+            .map(String::as_str)
+    };
+
+    let visibility = match scalar("visibility").map(str::to_lowercase).as_deref() {
+        Some("public") => NoteVisibility::Public,
+        Some("direct") => match scalar("recipient").filter(|recipient| !recipient.is_empty()) {
+            Some(recipient) => NoteVisibility::Direct(recipient.to_string()),
+            None => NoteVisibility::Private,
+        },
+        Some("group") => {
+            let members = fields.get("members").cloned().unwrap_or_default();
+            let group = NoteGroup::new(scalar("group").unwrap_or_default(), members);
+            if group.members.is_empty() {
+                NoteVisibility::Private
+            } else {
+                NoteVisibility::Group(group)
+            }
+        }
+        _ => NoteVisibility::Private,
+    };
+
+    let (folder_from_path, stem) = match path.rsplit_once('/') {
+        Some((folder, file_name)) => (folder, file_name),
+        None => ("", path),
+    };
+    let stem = stem.rsplit_once('.').map_or(stem, |(stem, _)| stem);
+    let mut title = scalar("title").unwrap_or_default().trim().to_string();
+    if title.is_empty() {
+        title = note_title_from_markdown(&body);
+    }
+    if title.is_empty() {
+        title = stem.to_string();
+    }
+
+    let board_position = scalar("board_position")
+        .and_then(|raw| serde_json::from_str::<NoteBoardPosition>(raw).ok());
+    let board_style = scalar("board_style")
+        .and_then(|raw| serde_json::from_str::<NoteBoardStyle>(raw).ok())
+        .unwrap_or_default();
+    let timestamp = |key: &str| scalar(key).and_then(|raw| raw.parse::<f64>().ok());
+
+    Some(NotePayload {
+        id: scalar("id")
+            .filter(|id| (1..=255).contains(&id.len()))
+            .map(str::to_string)
+            .unwrap_or_else(new_id),
+        author: author.to_string(),
+        visibility,
+        title: title.chars().take(MAX_NOTE_TITLE_CHARS).collect(),
+        body,
+        created_at_ms: timestamp("created_at_ms").unwrap_or(now_ms),
+        updated_at_ms: now_ms,
+        board_position,
+        board_style,
+        folder: normalize_folder_path(scalar("folder").unwrap_or(folder_from_path)),
+        tags: normalize_tags(&fields.get("tags").cloned().unwrap_or_default().join(",")),
+        pinned: scalar("pinned") == Some("true"),
+        edited_by: String::new(),
+        revisions: Vec::new(),
+    })
+}
+
+/// Archive paths of the local files a note links to, resolved against the
+/// note's own folder.
+pub fn linked_archive_paths(note_path: &str, markdown: &str) -> Vec<String> {
+    let mut paths = Vec::new();
+    rewrite_link_targets(markdown, |target| {
+        if let Some(path) = resolve_archive_path(note_path, target)
+            && !paths.contains(&path)
+        {
+            paths.push(path);
+        }
+        None
+    });
+    paths
+}
+
+/// Points links at imported archive files back to `file:<sha256>` embeds.
+pub fn link_imported_files(
+    note_path: &str,
+    markdown: &str,
+    hashes_by_path: &HashMap<String, String>,
+) -> String {
+    rewrite_link_targets(markdown, |target| {
+        let path = resolve_archive_path(note_path, target)?;
+        hashes_by_path.get(&path).map(|hash| format!("file:{hash}"))
+    })
+}
+
+/// MIME type of an archive file the room can store, by extension.
+pub fn mime_type_for_path(path: &str) -> Option<&'static str> {
+    let (_, extension) = path.rsplit_once('.')?;
+    match extension.to_ascii_lowercase().as_str() {
+        "png" => Some("image/png"),
+        "jpg" | "jpeg" => Some("image/jpeg"),
+        "gif" => Some("image/gif"),
+        "webp" => Some("image/webp"),
+        "pdf" => Some("application/pdf"),
+        _ => None,
+    }
+}
+
+fn extension_for_mime_type(mime_type: &str) -> Option<&'static str> {
+    match mime_type {
+        "image/png" => Some("png"),
+        "image/jpeg" => Some("jpg"),
+        "image/gif" => Some("gif"),
+        "image/webp" => Some("webp"),
+        "application/pdf" => Some("pdf"),
+        _ => None,
+    }
+}
+
+/// Front matter fields (scalars as one-item lists) and the text after it.
+fn split_front_matter(text: &str) -> (HashMap<String, Vec<String>>, &str) {
+    let mut fields = HashMap::new();
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let Some(rest) = text.strip_prefix(FRONT_MATTER_FENCE).and_then(|rest| {
+        rest.strip_prefix('\n')
+            .or_else(|| rest.strip_prefix("\r\n"))
+    }) else {
+        return (fields, text);
+    };
+    let mut offset = 0;
+    let mut list_key = None::<String>;
+    for line in rest.split_inclusive('\n') {
+        offset += line.len();
+        let line = line.trim_end_matches(['\n', '\r']);
+        if line.trim_end() == FRONT_MATTER_FENCE {
+            return (fields, &rest[offset..]);
+        }
+        if let Some(item) = line.trim_start().strip_prefix("- ")
+            && let Some(key) = &list_key
+        {
+            fields
+                .entry(key.clone())
+                .or_insert_with(Vec::new)
+                .push(yaml_scalar(item));
+            continue;
+        }
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let key = key.trim().to_string();
+        let value = value.trim();
+        if value.is_empty() {
+            list_key = Some(key);
+            continue;
+        }
+        list_key = None;
+        let values = if value.starts_with('[') {
+            yaml_list(value)
+        } else {
+            vec![yaml_scalar(value)]
+        };
+        fields.insert(key, values);
+    }
+    // No closing fence: the whole file is the body.
+    (HashMap::new(), text)
+}
+
+fn yaml_scalar(raw: &str) -> String {
+    let raw = raw.trim();
+    if raw.starts_with('"') {
+        if let Ok(value) = serde_json::from_str::<String>(raw) {
+            return value;
+        }
+    } else if let Some(inner) = raw
+        .strip_prefix('\'')
+        .and_then(|inner| inner.strip_suffix('\''))
+    {
+        return inner.replace("''", "'");
+    }
+    raw.to_string()
+}
+
+fn yaml_list(raw: &str) -> Vec<String> {
+    if let Ok(values) = serde_json::from_str::<Vec<String>>(raw) {
+        return values;
+    }
+    raw.trim_start_matches('[')
+        .trim_end_matches(']')
+        .split(',')
+        .map(yaml_scalar)
+        .filter(|value| !value.is_empty())
+        .collect()
+}
+
+/// Archive path a relative link from `note_path` points at; `None` for
+/// links with a scheme, anchors and paths that climb out of the archive.
+fn resolve_archive_path(note_path: &str, target: &str) -> Option<String> {
+    if target.is_empty() || target.starts_with('#') || target.contains(':') {
+        return None;
+    }
+    let target = percent_decode(target.split(['#', '?']).next()?);
+    let mut segments = match (target.strip_prefix('/'), note_path.rsplit_once('/')) {
+        (Some(_), _) | (None, None) => Vec::new(),
+        (None, Some((folder, _))) => folder.split('/').collect::<Vec<_>>(),
+    };
+    for segment in target.trim_start_matches('/').split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments.pop()?;
+            }
+            segment => segments.push(segment),
+        }
+    }
+    Some(segments.join("/"))
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let hex = bytes
+            .get(index + 1..index + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[index], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                index += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                index += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Rewrites the destinations of inline Markdown links and images. `rewrite`
+/// gets each destination (without angle brackets) and returns a replacement,
+/// or `None` to keep it.
+fn rewrite_link_targets(markdown: &str, mut rewrite: impl FnMut(&str) -> Option<String>) -> String {
+    let mut result = String::with_capacity(markdown.len());
+    let mut rest = markdown;
+    while let Some(start) = rest.find("](") {
+        let (before, after) = rest.split_at(start + 2);
+        result.push_str(before);
+        let (target, closing, remainder) = if let Some(inner) = after.strip_prefix('<') {
+            match inner.find('>') {
+                Some(end) => (&inner[..end], ">", &inner[end + 1..]),
+                None => ("", "", after),
+            }
+        } else {
+            let end = after
+                .find(|c: char| c == ')' || c.is_whitespace())
+                .unwrap_or(after.len());
+            (&after[..end], "", &after[end..])
+        };
+        match (target.is_empty(), rewrite(target)) {
+            (false, Some(replacement)) if replacement.contains(' ') => {
+                result.push_str(&format!("<{replacement}>"));
+            }
+            (false, Some(replacement)) => result.push_str(&replacement),
+            (false, None) if !closing.is_empty() => result.push_str(&format!("<{target}>")),
+            (false, None) => result.push_str(target),
+            (true, _) => {}
+        }
+        rest = remainder;
+    }
+    result.push_str(rest);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HASH: &str = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+
+    fn note(id: &str, title: &str, folder: &str, body: &str) -> NotePayload {
+        NotePayload {
+            id: id.to_string(),
+            author: "gm".to_string(),
+            visibility: NoteVisibility::Public,
+            title: title.to_string(),
+            body: body.to_string(),
+            created_at_ms: 1_720_000_000_000.0,
+            updated_at_ms: 1_720_000_001_000.0,
+            board_position: None,
+            board_style: NoteBoardStyle::default(),
+            folder: folder.to_string(),
+            tags: vec!["lore".to_string()],
+            pinned: false,
+            edited_by: String::new(),
+            revisions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn exported_notes_link_their_files_relatively() {
+        let map = FileRef {
+            hash: HASH.to_string(),
+            mime_type: "image/png".to_string(),
+            file_name: "Map.PNG".to_string(),
+            size: 3,
+        };
+        let files = HashMap::from([(HASH.to_string(), (map, vec![1, 2, 3]))]);
+        let notes = [
+            note(
+                "n1",
+                "Moria: Gates",
+                "World/Places",
+                &format!("![Gates](file:{HASH})"),
+            ),
+            note("n2", "Moria: Gates", "World/Places", "Second"),
+        ];
+
+        let entries = journal_archive(&notes, &files);
+        let names = entries
+            .iter()
+            .map(|entry| entry.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec![
+                "World/Places/Moria Gates.md",
+                "World/Places/Moria Gates (2).md",
+                &format!("files/{HASH}.png"),
+            ]
+        );
+        let document = String::from_utf8(entries[0].data.clone()).unwrap();
+        assert!(document.starts_with("---\nid: \"n1\"\ntitle: \"Moria: Gates\"\n"));
+        assert!(document.contains("tags: [\"lore\"]\n"));
+        assert!(document.ends_with(&format!("---\n\n![Gates](../../files/{HASH}.png)\n")));
+    }
+
+    #[test]
+    fn archives_round_trip_into_another_room() {
+        let mut exported = note("n1", "Bree", "World", "# Bree\nA town.");
+        exported.visibility = NoteVisibility::Group(NoteGroup::new("Party", ["frodo".to_string()]));
+        exported.pinned = true;
+        exported.board_position = Some(NoteBoardPosition {
+            world_x: 10.0,
+            world_y: 20.0,
+            anchor: None,
+        });
+        let entries = journal_archive(std::slice::from_ref(&exported), &HashMap::new());
+
+        let (notes, skipped) = parse_journal_archive(&entries, "sam", 5.0, || "new".to_string());
+        assert_eq!(skipped, 0);
+        let imported = &notes[0].note;
+        assert_eq!(imported.id, "n1");
+        assert_eq!(imported.author, "sam");
+        assert_eq!(imported.visibility, exported.visibility);
+        assert_eq!(imported.title, "Bree");
+        assert_eq!(imported.body, exported.body);
+        assert_eq!(imported.folder, "World");
+        assert_eq!(imported.tags, exported.tags);
+        assert!(imported.pinned);
+        assert_eq!(imported.created_at_ms, exported.created_at_ms);
+        assert_eq!(imported.updated_at_ms, 5.0);
+        assert_eq!(imported.board_position, exported.board_position);
+    }
+
+    #[test]
+    fn hand_written_markdown_imports_as_private_notes() {
+        let text = "---\ntitle: 'Sam''s notes'\ntags:\n  - Hobbits\n  - '#Shire'\n---\nSecond breakfast.\n";
+        let entries = [
+            ZipEntry {
+                name: "Shire/sam.md".to_string(),
+                data: text.as_bytes().to_vec(),
+            },
+            ZipEntry {
+                name: "plain.md".to_string(),
+                data: b"# Plain\nNo front matter".to_vec(),
+            },
+            ZipEntry {
+                name: "empty.md".to_string(),
+                data: b"---\ntitle: x\n---\n".to_vec(),
+            },
+            ZipEntry {
+                name: ".obsidian/workspace.md".to_string(),
+                data: b"ignored".to_vec(),
+            },
+        ];
+        let mut next = 0;
+        let (notes, skipped) = parse_journal_archive(&entries, "sam", 1.0, || {
+            next += 1;
+            format!("id-{next}")
+        });
+        assert_eq!(skipped, 1);
+        assert_eq!(notes.len(), 2);
+        assert_eq!(notes[0].note.title, "Sam's notes");
+        assert_eq!(notes[0].note.tags, vec!["hobbits", "shire"]);
+        assert_eq!(notes[0].note.folder, "Shire");
+        assert_eq!(notes[0].note.visibility, NoteVisibility::Private);
+        assert_eq!(notes[0].note.id, "id-1");
+        assert_eq!(notes[1].note.title, "Plain");
+    }
+
+    #[test]
+    fn imported_file_links_become_embeds() {
+        let markdown = "![Map](../files/map%20v2.png) [Rules](<../files/rules book.pdf>) [Web](https://x.org/a.png) [Up](../../../escape.png)";
+        assert_eq!(
+            linked_archive_paths("World/bree.md", markdown),
+            vec!["files/map v2.png", "files/rules book.pdf"]
+        );
+        let hashes = HashMap::from([("files/map v2.png".to_string(), HASH.to_string())]);
+        assert_eq!(
+            link_imported_files("World/bree.md", markdown, &hashes),
+            format!(
+                "![Map](file:{HASH}) [Rules](<../files/rules book.pdf>) [Web](https://x.org/a.png) [Up](../../../escape.png)"
+            )
+        );
+        assert_eq!(mime_type_for_path("files/map v2.png"), Some("image/png"));
+        assert_eq!(mime_type_for_path("files/notes.txt"), None);
+    }
+}
@@ -0,0 +1,727 @@
+// Pure collaborative editing of note bodies: an RGA sequence CRDT and the
+// per-note edit session built on top of it.
+// No signals, no Leptos, no web_sys.
+
+use shared::events::{NoteCaretPayload, NoteCharId, NoteEditPayload, NoteTextOp};
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+
+/// Site of the characters seeded from the saved note body. Everyone who
+/// starts from the same note version derives the same seed ids.
+const SEED_SITE: &str = "";
+
+/// Matches the `ops` length limit validated on the wire.
+pub const MAX_NOTE_EDIT_OPS: usize = 4096;
+/// Parked operations kept while their anchors are missing; the oldest are
+/// dropped first, since a peer that never sends the anchor cannot be waited on.
+const MAX_PENDING_OPS: usize = MAX_NOTE_EDIT_OPS;
+/// Longest run of characters one operation inserts or deletes; longer runs
+/// are split when sent and rejected when received.
+pub const MAX_NOTE_OP_CHARS: usize = 20_000;
+/// Deletions kept while their characters are missing; later ones are dropped
+/// for the same reason as parked operations.
+const MAX_EARLY_DELETES: usize = MAX_NOTE_OP_CHARS;
+
+/// RGA order: later clocks win, ties broken by site.
+fn id_order(left: &NoteCharId, right: &NoteCharId) -> Ordering {
+    left.clock
+        .cmp(&right.clock)
+        .then_with(|| left.site.cmp(&right.site))
+}
+
+fn char_id(site: &str, clock: u64) -> NoteCharId {
+    NoteCharId {
+        site: site.to_string(),
+        clock,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct CharSlot {
+    id: NoteCharId,
+    value: char,
+    deleted: bool,
+}
+
+/// Replicated text: every character ever inserted, deletions kept as
+/// tombstones so concurrent operations can still find their anchors.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextDoc {
+    site: String,
+    clock: u64,
+    slots: Vec<CharSlot>,
+    /// Remote operations whose anchor has not arrived yet.
+    pending: Vec<NoteTextOp>,
+    /// Deletions that arrived before the characters they delete.
+    early_deletes: HashSet<NoteCharId>,
+}
+
+impl TextDoc {
+    pub fn new(site: &str) -> Self {
+        Self {
+            site: site.to_string(),
+            clock: 0,
+            slots: Vec::new(),
+            pending: Vec::new(),
+            early_deletes: HashSet::new(),
+        }
+    }
+
+    /// Document holding `text` under deterministic seed ids.
+    pub fn seeded(site: &str, text: &str) -> Self {
+        let mut doc = Self::new(site);
+        doc.slots = text
+            .chars()
+            .enumerate()
+            .map(|(index, value)| CharSlot {
+                id: char_id(SEED_SITE, index as u64 + 1),
+                value,
+                deleted: false,
+            })
+            .collect();
+        doc.clock = doc.slots.len() as u64;
+        doc
+    }
+
+    pub fn text(&self) -> String {
+        self.slots
+            .iter()
+            .filter(|slot| !slot.deleted)
+            .map(|slot| slot.value)
+            .collect()
+    }
+
+    fn position(&self, id: &NoteCharId) -> Option<usize> {
+        self.slots.iter().position(|slot| &slot.id == id)
+    }
+
+    /// Slot index of the `index`-th visible character.
+    fn visible_slot(&self, index: usize) -> Option<usize> {
+        self.slots
+            .iter()
+            .enumerate()
+            .filter(|(_, slot)| !slot.deleted)
+            .nth(index)
+            .map(|(slot_index, _)| slot_index)
+    }
+
+    fn integrate(&mut self, after: Option<usize>, id: NoteCharId, value: char) -> usize {
+        let mut index = after.map_or(0, |after| after + 1);
+        while index < self.slots.len() && id_order(&self.slots[index].id, &id) == Ordering::Greater
+        {
+            index += 1;
+        }
+        self.clock = self.clock.max(id.clock);
+        let deleted = self.early_deletes.remove(&id);
+        self.slots.insert(index, CharSlot { id, value, deleted });
+        index
+    }
+
+    /// Applies one operation; `false` when its anchor is still missing.
+    /// Operations whose clocks overflow are dropped.
+    fn apply_op(&mut self, op: &NoteTextOp) -> bool {
+        match op {
+            NoteTextOp::Insert { id, after, text } => {
+                let mut previous = match after {
+                    Some(after) => match self.position(after) {
+                        Some(index) => Some(index),
+                        None => return false,
+                    },
+                    None => None,
+                };
+                // Snapshot runs may overlap characters already received.
+                let Some(end) = id.clock.checked_add(text.chars().count() as u64) else {
+                    return true;
+                };
+                let run = id.clock..end;
+                let known = self
+                    .slots
+                    .iter()
+                    .filter(|slot| slot.id.site == id.site && run.contains(&slot.id.clock))
+                    .map(|slot| slot.id.clock)
+                    .collect::<HashSet<_>>();
+                for (offset, value) in text.chars().enumerate() {
+                    let id = char_id(&id.site, id.clock + offset as u64);
+                    previous = if known.contains(&id.clock) {
+                        self.position(&id)
+                    } else {
+                        Some(self.integrate(previous, id, value))
+                    };
+                }
+                true
+            }
+            NoteTextOp::Delete { id, len } => {
+                let Some(end) = id.clock.checked_add(u64::from(*len)) else {
+                    return true;
+                };
+                let range = id.clock..end;
+                let mut found = HashSet::new();
+                for slot in &mut self.slots {
+                    if slot.id.site == id.site && range.contains(&slot.id.clock) {
+                        slot.deleted = true;
+                        found.insert(slot.id.clock);
+                    }
+                }
+                for clock in range.filter(|clock| !found.contains(clock)) {
+                    if self.early_deletes.len() >= MAX_EARLY_DELETES {
+                        break;
+                    }
+                    self.early_deletes.insert(char_id(&id.site, clock));
+                }
+                self.clock = self.clock.max(end);
+                true
+            }
+        }
+    }
+
+    /// Applies remote operations in any order, parking the ones whose anchor
+    /// has not arrived yet until it does.
+    pub fn apply(&mut self, ops: Vec<NoteTextOp>) {
+        self.pending.extend(ops);
+        loop {
+            let pending = std::mem::take(&mut self.pending);
+            let before = pending.len();
+            for op in pending {
+                if !self.apply_op(&op) {
+                    self.pending.push(op);
+                }
+            }
+            if self.pending.is_empty() || self.pending.len() == before {
+                break;
+            }
+        }
+        let excess = self.pending.len().saturating_sub(MAX_PENDING_OPS);
+        self.pending.drain(..excess);
+    }
+
+    /// Inserts `text` before the `index`-th visible character, in runs of at
+    /// most `MAX_NOTE_OP_CHARS` characters.
+    pub fn local_insert(&mut self, index: usize, text: &str) -> Vec<NoteTextOp> {
+        let mut after = index
+            .checked_sub(1)
+            .and_then(|previous| self.visible_slot(previous))
+            .map(|slot| self.slots[slot].id.clone());
+        let chars = text.chars().collect::<Vec<_>>();
+        let mut ops = Vec::new();
+        for run in chars.chunks(MAX_NOTE_OP_CHARS) {
+            let Some(clock) = self.clock.checked_add(1) else {
+                break;
+            };
+            let op = NoteTextOp::Insert {
+                id: char_id(&self.site, clock),
+                after,
+                text: run.iter().collect(),
+            };
+            self.apply_op(&op);
+            after = Some(char_id(&self.site, self.clock));
+            ops.push(op);
+        }
+        ops
+    }
+
+    /// Deletes `len` visible characters starting at `index`.
+    pub fn local_delete(&mut self, index: usize, len: usize) -> Vec<NoteTextOp> {
+        let targets = self
+            .slots
+            .iter()
+            .enumerate()
+            .filter(|(_, slot)| !slot.deleted)
+            .skip(index)
+            .take(len)
+            .map(|(slot_index, _)| slot_index)
+            .collect::<Vec<_>>();
+        let mut ops = Vec::new();
+        for slot_index in targets {
+            let slot = &mut self.slots[slot_index];
+            slot.deleted = true;
+            push_delete(&mut ops, &slot.id);
+        }
+        ops
+    }
+
+    /// Operations that rebuild this document from scratch, tombstones
+    /// included, so later concurrent operations land identically.
+    pub fn snapshot_ops(&self) -> Vec<NoteTextOp> {
+        let mut ops = Vec::new();
+        let mut previous: Option<&NoteCharId> = None;
+        let mut run_len = 0;
+        for slot in &self.slots {
+            match ops.last_mut() {
+                Some(NoteTextOp::Insert { text, .. })
+                    if run_len < MAX_NOTE_OP_CHARS
+                        && previous.is_some_and(|previous| {
+                            previous.site == slot.id.site
+                                && previous.clock.checked_add(1) == Some(slot.id.clock)
+                        }) =>
+                {
+                    text.push(slot.value);
+                    run_len += 1;
+                }
+                _ => {
+                    ops.push(NoteTextOp::Insert {
+                        id: slot.id.clone(),
+                        after: previous.cloned(),
+                        text: slot.value.to_string(),
+                    });
+                    run_len = 1;
+                }
+            }
+            previous = Some(&slot.id);
+        }
+        let mut deletes = Vec::new();
+        for slot in self.slots.iter().filter(|slot| slot.deleted) {
+            push_delete(&mut deletes, &slot.id);
+        }
+        ops.extend(deletes);
+        ops
+    }
+
+    /// Id of the character just before visible position `index`.
+    pub fn anchor_at(&self, index: usize) -> Option<NoteCharId> {
+        index
+            .checked_sub(1)
+            .and_then(|previous| self.visible_slot(previous))
+            .map(|slot| self.slots[slot].id.clone())
+    }
+
+    /// Visible position right after `anchor`; `None` while the anchor is unknown.
+    pub fn index_after(&self, anchor: Option<&NoteCharId>) -> Option<usize> {
+        let Some(anchor) = anchor else {
+            return Some(0);
+        };
+        let slot_index = self.position(anchor)?;
+        Some(
+            self.slots[..=slot_index]
+                .iter()
+                .filter(|slot| !slot.deleted)
+                .count(),
+        )
+    }
+}
+
+/// Appends a deletion of `id`, extending the last run when the clocks line up.
+fn push_delete(ops: &mut Vec<NoteTextOp>, target: &NoteCharId) {
+    if let Some(NoteTextOp::Delete { id, len }) = ops.last_mut()
+        && id.site == target.site
+        && (*len as usize) < MAX_NOTE_OP_CHARS
+        && id.clock.checked_add(u64::from(*len)) == Some(target.clock)
+    {
+        *len += 1;
+        return;
+    }
+    ops.push(NoteTextOp::Delete {
+        id: target.clone(),
+        len: 1,
+    });
+}
+
+/// Whether a received operation stays within the runs this editor sends.
+pub fn op_in_bounds(op: &NoteTextOp) -> bool {
+    match op {
+        NoteTextOp::Insert { text, .. } => (1..=MAX_NOTE_OP_CHARS).contains(&text.chars().count()),
+        NoteTextOp::Delete { len, .. } => (1..=MAX_NOTE_OP_CHARS).contains(&(*len as usize)),
+    }
+}
+
+/// Smallest single replacement turning `old` into `new`:
+/// `(start, removed_chars, inserted_text)` in character positions.
+pub fn text_change(old: &str, new: &str) -> Option<(usize, usize, String)> {
+    if old == new {
+        return None;
+    }
+    let old_chars = old.chars().collect::<Vec<_>>();
+    let new_chars = new.chars().collect::<Vec<_>>();
+    let prefix = old_chars
+        .iter()
+        .zip(&new_chars)
+        .take_while(|(left, right)| left == right)
+        .count();
+    let suffix = old_chars[prefix..]
+        .iter()
+        .rev()
+        .zip(new_chars[prefix..].iter().rev())
+        .take_while(|(left, right)| left == right)
+        .count();
+    Some((
+        prefix,
+        old_chars.len() - prefix - suffix,
+        new_chars[prefix..new_chars.len() - suffix].iter().collect(),
+    ))
+}
+
+/// Where a caret at `index` ends up after `removed` characters at `start`
+/// were replaced by `inserted` characters.
+pub fn shift_index(index: usize, start: usize, removed: usize, inserted: usize) -> usize {
+    if index <= start {
+        index
+    } else if index >= start + removed {
+        index - removed + inserted
+    } else {
+        start + inserted
+    }
+}
+
+/// Character position for a UTF-16 offset as reported by text inputs.
+pub fn char_index_from_utf16(text: &str, utf16_offset: usize) -> usize {
+    let mut units = 0;
+    for (index, value) in text.chars().enumerate() {
+        if units >= utf16_offset {
+            return index;
+        }
+        units += value.len_utf16();
+    }
+    text.chars().count()
+}
+
+/// UTF-16 offset of character position `char_index`.
+pub fn utf16_offset(text: &str, char_index: usize) -> usize {
+    text.chars().take(char_index).map(char::len_utf16).sum()
+}
+
+/// A slice of the editor mirror: plain text or another participant's caret.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MirrorPiece {
+    Text(String),
+    Caret(String),
+}
+
+/// Splits `text` at each caret position for the overlay drawn under the editor.
+pub fn mirror_pieces(text: &str, carets: &[(String, usize)]) -> Vec<MirrorPiece> {
+    let mut carets = carets.to_vec();
+    carets.sort_by(|left, right| left.1.cmp(&right.1).then_with(|| left.0.cmp(&right.0)));
+    let chars = text.chars().collect::<Vec<_>>();
+    let mut pieces = Vec::new();
+    let mut cursor = 0;
+    for (username, index) in carets {
+        let index = index.min(chars.len());
+        if index > cursor {
+            pieces.push(MirrorPiece::Text(chars[cursor..index].iter().collect()));
+            cursor = index;
+        }
+        pieces.push(MirrorPiece::Caret(username));
+    }
+    if cursor < chars.len() {
+        pieces.push(MirrorPiece::Text(chars[cursor..].iter().collect()));
+    }
+    pieces
+}
+
+/// Live editing of one public note shared with everyone editing it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NoteEditSession {
+    pub note_id: String,
+    /// `updated_at_ms` of the note version the session started from.
+    pub base_updated_at_ms: f64,
+    pub doc: TextDoc,
+    /// Other participants' carets, anchored to character ids.
+    pub carets: HashMap<String, Option<NoteCharId>>,
+    /// Bumped whenever remote operations change the text.
+    pub remote_revision: u64,
+    local_edits: bool,
+}
+
+impl NoteEditSession {
+    pub fn start(note_id: &str, base_updated_at_ms: f64, site: &str, body: &str) -> Self {
+        Self {
+            note_id: note_id.to_string(),
+            base_updated_at_ms,
+            doc: TextDoc::seeded(site, body),
+            carets: HashMap::new(),
+            remote_revision: 0,
+            local_edits: false,
+        }
+    }
+
+    /// Records the editor now showing `new_text`; returns the operations to
+    /// broadcast.
+    pub fn edit(&mut self, new_text: &str) -> Vec<NoteTextOp> {
+        let Some((start, removed, inserted)) = text_change(&self.doc.text(), new_text) else {
+            return Vec::new();
+        };
+        self.local_edits = true;
+        let mut ops = self.doc.local_delete(start, removed);
+        ops.extend(self.doc.local_insert(start, &inserted));
+        ops
+    }
+
+    /// Merges a remote batch. A full snapshot from an older session replaces
+    /// this one while nothing has been typed locally, so late joiners fall in
+    /// with whoever started first. Returns whether the text changed.
+    pub fn receive_edit(&mut self, payload: NoteEditPayload) -> bool {
+        if payload.note_id != self.note_id {
+            return false;
+        }
+        if payload.base_updated_at_ms != self.base_updated_at_ms {
+            let adopt = payload.full
+                && payload.base_updated_at_ms < self.base_updated_at_ms
+                && !self.local_edits;
+            if !adopt {
+                return false;
+            }
+            self.base_updated_at_ms = payload.base_updated_at_ms;
+            self.doc = TextDoc::new(&self.doc.site);
+        }
+        let before = self.doc.text();
+        self.doc.apply(payload.ops);
+        let changed = self.doc.text() != before;
+        if changed {
+            self.remote_revision += 1;
+        }
+        changed
+    }
+
+    /// Tracks a remote caret; returns `true` when its owner just joined and
+    /// needs a snapshot of the document.
+    pub fn receive_caret(&mut self, payload: NoteCaretPayload) -> bool {
+        if payload.note_id != self.note_id {
+            return false;
+        }
+        if !payload.editing {
+            self.carets.remove(&payload.username);
+            return false;
+        }
+        self.carets
+            .insert(payload.username, payload.after)
+            .is_none()
+    }
+
+    /// `ops` split into wire-sized payloads.
+    pub fn edit_payloads(
+        &self,
+        sender: &str,
+        ops: Vec<NoteTextOp>,
+        full: bool,
+    ) -> Vec<NoteEditPayload> {
+        ops.chunks(MAX_NOTE_EDIT_OPS)
+            .map(|chunk| NoteEditPayload {
+                note_id: self.note_id.clone(),
+                sender: sender.to_string(),
+                base_updated_at_ms: self.base_updated_at_ms,
+                full,
+                ops: chunk.to_vec(),
+            })
+            .collect()
+    }
+
+    /// The whole document for a participant who just joined.
+    pub fn snapshot_payloads(&self, sender: &str) -> Vec<NoteEditPayload> {
+        self.edit_payloads(sender, self.doc.snapshot_ops(), true)
+    }
+
+    pub fn caret_payload(&self, username: &str, index: usize, editing: bool) -> NoteCaretPayload {
+        NoteCaretPayload {
+            note_id: self.note_id.clone(),
+            username: username.to_string(),
+            base_updated_at_ms: self.base_updated_at_ms,
+            after: self.doc.anchor_at(index),
+            editing,
+        }
+    }
+
+    /// Remote carets resolved to visible positions, sorted by name.
+    pub fn caret_positions(&self) -> Vec<(String, usize)> {
+        let mut positions = self
+            .carets
+            .iter()
+            .filter_map(|(username, anchor)| {
+                self.doc
+                    .index_after(anchor.as_ref())
+                    .map(|index| (username.clone(), index))
+            })
+            .collect::<Vec<_>>();
+        positions.sort();
+        positions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sync(from: &NoteEditSession, ops: Vec<NoteTextOp>, to: &mut NoteEditSession) {
+        for payload in from.edit_payloads("peer", ops, false) {
+            to.receive_edit(payload);
+        }
+    }
+
+    #[test]
+    fn concurrent_edits_converge() {
+        let mut alice = NoteEditSession::start("note-1", 5.0, "alice", "Hello world");
+        let mut bob = NoteEditSession::start("note-1", 5.0, "bob", "Hello world");
+
+        let alice_ops = alice.edit("Hello brave world");
+        let bob_ops = bob.edit("Hello world!");
+        let bob_more = bob.edit("Hi world!");
+
+        sync(&bob, bob_more.clone(), &mut alice);
+        sync(&bob, bob_ops.clone(), &mut alice);
+        sync(&alice, alice_ops, &mut bob);
+
+        assert_eq!(alice.doc.text(), bob.doc.text());
+        assert_eq!(alice.doc.text(), "Hi brave world!");
+    }
+
+    #[test]
+    fn same_position_inserts_order_by_site() {
+        let mut alice = NoteEditSession::start("note-1", 5.0, "alice", "ab");
+        let mut bob = NoteEditSession::start("note-1", 5.0, "bob", "ab");
+        let alice_ops = alice.edit("aXb");
+        let bob_ops = bob.edit("aYb");
+        sync(&alice, alice_ops, &mut bob);
+        sync(&bob, bob_ops, &mut alice);
+        assert_eq!(alice.doc.text(), bob.doc.text());
+        assert_eq!(alice.doc.text().chars().count(), 4);
+    }
+
+    #[test]
+    fn deletes_before_inserts_are_parked() {
+        let mut alice = NoteEditSession::start("note-1", 5.0, "alice", "");
+        let mut bob = NoteEditSession::start("note-1", 5.0, "bob", "");
+        let typed = alice.edit("abc");
+        let erased = alice.edit("ac");
+        sync(&alice, erased, &mut bob);
+        sync(&alice, typed, &mut bob);
+        assert_eq!(bob.doc.text(), "ac");
+    }
+
+    #[test]
+    fn orphaned_operations_are_capped() {
+        let mut doc = TextDoc::new("bob");
+        let orphans = (0..MAX_PENDING_OPS as u64 + 10)
+            .map(|clock| NoteTextOp::Insert {
+                id: char_id("alice", clock + 100),
+                after: Some(char_id("ghost", clock)),
+                text: "x".to_string(),
+            })
+            .collect();
+        doc.apply(orphans);
+        assert_eq!(doc.pending.len(), MAX_PENDING_OPS);
+        assert_eq!(doc.text(), "");
+    }
+
+    #[test]
+    fn overflowing_and_oversized_operations_are_dropped() {
+        let mut doc = TextDoc::seeded("bob", "ab");
+        doc.apply(vec![
+            NoteTextOp::Insert {
+                id: char_id("mallory", u64::MAX),
+                after: None,
+                text: "xy".to_string(),
+            },
+            NoteTextOp::Delete {
+                id: char_id("mallory", u64::MAX - 1),
+                len: 5,
+            },
+        ]);
+        assert_eq!(doc.text(), "ab");
+        assert!(doc.early_deletes.is_empty());
+
+        doc.apply(vec![NoteTextOp::Delete {
+            id: char_id("ghost", 1),
+            len: u32::MAX,
+        }]);
+        assert_eq!(doc.early_deletes.len(), MAX_EARLY_DELETES);
+
+        assert!(!op_in_bounds(&NoteTextOp::Delete {
+            id: char_id("ghost", 1),
+            len: u32::MAX,
+        }));
+        assert!(!op_in_bounds(&NoteTextOp::Insert {
+            id: char_id("ghost", 1),
+            after: None,
+            text: "x".repeat(MAX_NOTE_OP_CHARS + 1),
+        }));
+    }
+
+    #[test]
+    fn long_runs_are_split_on_send() {
+        let mut alice = NoteEditSession::start("note-1", 5.0, "alice", "");
+        let mut bob = NoteEditSession::start("note-1", 5.0, "bob", "");
+        let text = "x".repeat(MAX_NOTE_OP_CHARS + 5);
+        let ops = alice.edit(&text);
+        assert_eq!(ops.len(), 2);
+        assert!(ops.iter().all(op_in_bounds));
+        sync(&alice, ops, &mut bob);
+        assert_eq!(bob.doc.text(), text);
+
+        let erased = alice.edit("");
+        assert!(erased.iter().all(op_in_bounds));
+        assert!(alice.doc.snapshot_ops().iter().all(op_in_bounds));
+        sync(&alice, erased, &mut bob);
+        assert_eq!(bob.doc.text(), "");
+    }
+
+    #[test]
+    fn snapshots_let_late_joiners_adopt_the_session() {
+        let mut alice = NoteEditSession::start("note-1", 5.0, "alice", "Plan");
+        alice.edit("Plan\n- scout");
+        alice.edit("Plan\n- camp");
+
+        let mut carol = NoteEditSession::start("note-1", 9.0, "carol", "Plan\n- scout");
+        for payload in alice.snapshot_payloads("alice") {
+            assert!(carol.receive_edit(payload));
+        }
+        assert_eq!(carol.base_updated_at_ms, 5.0);
+        assert_eq!(carol.doc.text(), "Plan\n- camp");
+        assert_eq!(carol.doc.snapshot_ops(), alice.doc.snapshot_ops());
+
+        let ops = carol.edit("Plan\n- camp\n- rest");
+        sync(&carol, ops, &mut alice);
+        assert_eq!(alice.doc.text(), "Plan\n- camp\n- rest");
+
+        let mut stale = NoteEditSession::start("note-1", 1.0, "dave", "Old");
+        stale.edit("Older");
+        for payload in alice.snapshot_payloads("alice") {
+            assert!(!stale.receive_edit(payload));
+        }
+        assert_eq!(stale.doc.text(), "Older");
+    }
+
+    #[test]
+    fn snapshots_merge_into_same_base_sessions() {
+        let mut alice = NoteEditSession::start("note-1", 5.0, "alice", "");
+        let mut bob = NoteEditSession::start("note-1", 5.0, "bob", "");
+        let first = alice.edit("a");
+        alice.edit("ab");
+        sync(&alice, first, &mut bob);
+        for payload in alice.snapshot_payloads("alice") {
+            bob.receive_edit(payload);
+        }
+        assert_eq!(bob.doc.text(), "ab");
+    }
+
+    #[test]
+    fn carets_follow_their_character() {
+        let mut alice = NoteEditSession::start("note-1", 5.0, "alice", "abc");
+        let mut bob = NoteEditSession::start("note-1", 5.0, "bob", "abc");
+        assert!(alice.receive_caret(bob.caret_payload("bob", 2, true)));
+        assert!(!alice.receive_caret(bob.caret_payload("bob", 2, true)));
+
+        let ops = bob.edit("XYabc");
+        sync(&bob, ops, &mut alice);
+        assert_eq!(alice.caret_positions(), vec![("bob".to_string(), 4)]);
+
+        alice.receive_caret(bob.caret_payload("bob", 0, false));
+        assert!(alice.caret_positions().is_empty());
+    }
+
+    #[test]
+    fn text_helpers_handle_multibyte_text() {
+        assert_eq!(text_change("кот", "кит"), Some((1, 1, "и".to_string())));
+        assert_eq!(text_change("same", "same"), None);
+        assert_eq!(shift_index(2, 4, 1, 3), 2);
+        assert_eq!(shift_index(6, 4, 1, 3), 8);
+        assert_eq!(shift_index(5, 4, 3, 0), 4);
+        assert_eq!(char_index_from_utf16("a😀b", 3), 2);
+        assert_eq!(utf16_offset("a😀b", 2), 3);
+        assert_eq!(
+            mirror_pieces("abcd", &[("bob".into(), 2), ("amy".into(), 9)]),
+            vec![
+                MirrorPiece::Text("ab".into()),
+                MirrorPiece::Caret("bob".into()),
+                MirrorPiece::Text("cd".into()),
+                MirrorPiece::Caret("amy".into()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,145 @@
+// Pure note file embeds: `![caption](file:<sha256>)` images and `[name](file:<sha256>)`
+// links to room files kept in IndexedDB by hash.
+// No signals, no Leptos, no web_sys.
+
+use pulldown_cmark::{Event, Parser, Tag};
+use std::collections::HashMap;
+
+pub const FILE_EMBED_SCHEME: &str = "file";
+
+const FILE_EMBED_PREFIX: &str = "file:";
+
+/// SHA-256 hashes are stored as 64 lowercase hex digits.
+pub fn is_file_hash(value: &str) -> bool {
+    value.len() == 64
+        && value
+            .bytes()
+            .all(|byte| byte.is_ascii_digit() || (b'a'..=b'f').contains(&byte))
+}
+
+/// Hashes of the files `markdown` embeds or links to, in order of first use.
+pub fn embedded_file_hashes(markdown: &str) -> Vec<String> {
+    let mut hashes = Vec::new();
+    for event in Parser::new(markdown) {
+        let (Event::Start(Tag::Image { dest_url, .. }) | Event::Start(Tag::Link { dest_url, .. })) =
+            event
+        else {
+            continue;
+        };
+        let Some(hash) = dest_url.strip_prefix(FILE_EMBED_PREFIX) else {
+            continue;
+        };
+        if is_file_hash(hash) && !hashes.iter().any(|known| known == hash) {
+            hashes.push(hash.to_string());
+        }
+    }
+    hashes
+}
+
+/// Markdown referencing the file stored under `hash`: images are shown
+/// inline, anything else becomes a link.
+pub fn file_embed_markdown(file_name: &str, hash: &str, is_image: bool) -> String {
+    let mut caption = String::with_capacity(file_name.len());
+    for character in file_name.chars() {
+        if matches!(character, '[' | ']' | '\\') {
+            caption.push('\\');
+        }
+        caption.push(character);
+    }
+    let marker = if is_image { "!" } else { "" };
+    format!("{marker}[{caption}]({FILE_EMBED_PREFIX}{hash})")
+}
+
+/// Points the `file:` references left in sanitized note HTML at local blob
+/// URLs. Files that have not arrived yet lose their address, so an image shows
+/// its caption until the next render.
+pub fn resolve_file_embeds(html: &str, file_urls: &HashMap<String, String>) -> String {
+    let mut resolved = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some((start, attribute)) = next_file_attribute(rest) {
+        resolved.push_str(&rest[..start]);
+        let value_start = start + attribute.len() + 2 + FILE_EMBED_PREFIX.len();
+        let Some(value_len) = rest[value_start..].find('"') else {
+            rest = &rest[start..];
+            break;
+        };
+        let hash = &rest[value_start..value_start + value_len];
+        let url = is_file_hash(hash).then(|| file_urls.get(hash)).flatten();
+        match (attribute, url) {
+            ("src", Some(url)) => {
+                resolved.push_str(&format!("src=\"{}\"", escape_attribute(url)));
+            }
+            ("href", Some(url)) => {
+                resolved.push_str(&format!(
+                    "href=\"{}\" target=\"_blank\"",
+                    escape_attribute(url)
+                ));
+            }
+            _ => resolved.push_str(&format!("data-pending-file=\"{}\"", escape_attribute(hash))),
+        }
+        rest = &rest[value_start + value_len + 1..];
+    }
+    resolved.push_str(rest);
+    resolved
+}
+
+fn next_file_attribute(html: &str) -> Option<(usize, &'static str)> {
+    ["src", "href"]
+        .into_iter()
+        .filter_map(|attribute| {
+            html.find(&format!("{attribute}=\"{FILE_EMBED_PREFIX}"))
+                .map(|index| (index, attribute))
+        })
+        .min_by_key(|(index, _)| *index)
+}
+
+fn escape_attribute(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HASH: &str = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+
+    #[test]
+    fn embeds_are_collected_once() {
+        let markdown = format!(
+            "![Map](file:{HASH})\n[map.png](file:{HASH})\n![web](https://example.com/x.png)\n![bad](file:xyz)"
+        );
+        assert_eq!(embedded_file_hashes(&markdown), vec![HASH.to_string()]);
+        assert!(!is_file_hash(&HASH.to_uppercase()));
+    }
+
+    #[test]
+    fn embed_markdown_escapes_captions() {
+        assert_eq!(
+            file_embed_markdown("map [v2].png", HASH, true),
+            format!("![map \\[v2\\].png](file:{HASH})")
+        );
+        let link = file_embed_markdown("rules.pdf", HASH, false);
+        assert_eq!(embedded_file_hashes(&link), vec![HASH.to_string()]);
+        assert!(!link.starts_with('!'));
+    }
+
+    #[test]
+    fn resolved_embeds_point_at_blob_urls() {
+        let html = format!(
+            "<p><img src=\"file:{HASH}\" alt=\"Map\"> <a href=\"file:{HASH}\" rel=\"noopener noreferrer\">map</a> <img src=\"file:nope\" alt=\"x\"></p>"
+        );
+        let urls = HashMap::from([(HASH.to_string(), "blob:http://vtt/1\"".to_string())]);
+        assert_eq!(
+            resolve_file_embeds(&html, &urls),
+            "<p><img src=\"blob:http://vtt/1&quot;\" alt=\"Map\"> <a href=\"blob:http://vtt/1&quot;\" target=\"_blank\" rel=\"noopener noreferrer\">map</a> <img data-pending-file=\"nope\" alt=\"x\"></p>"
+        );
+        assert_eq!(
+            resolve_file_embeds(&format!("<img src=\"file:{HASH}\">"), &HashMap::new()),
+            format!("<img data-pending-file=\"{HASH}\">")
+        );
+    }
+}
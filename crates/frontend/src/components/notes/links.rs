@@ -0,0 +1,637 @@
+// Wiki links between notes: `[[Note title]]` links, `@token` and `#scene`
+// references, backlinks and the journal graph.
+// No signals, no Leptos, no web_sys.
+
+use shared::events::{NotePayload, Scene};
+use std::collections::{HashMap, HashSet};
+use std::f64::consts::TAU;
+
+// --- Link targets ---
+
+/// Link schemes the note renderer lets through; every other link keeps the
+/// sanitizer's default scheme list.
+pub const WIKI_LINK_SCHEMES: [&str; 4] = ["note", "note-new", "token", "scene"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WikiTarget {
+    /// An existing note, by id.
+    Note(String),
+    /// A `[[link]]` to a note that does not exist yet, by title.
+    NewNote(String),
+    Token {
+        scene_id: String,
+        token_id: String,
+    },
+    Scene(String),
+}
+
+impl WikiTarget {
+    pub fn href(&self) -> String {
+        match self {
+            Self::Note(id) => format!("note:{}", encode_component(id)),
+            Self::NewNote(title) => format!("note-new:{}", encode_component(title)),
+            Self::Token { scene_id, token_id } => format!(
+                "token:{}/{}",
+                encode_component(scene_id),
+                encode_component(token_id)
+            ),
+            Self::Scene(id) => format!("scene:{}", encode_component(id)),
+        }
+    }
+
+    pub fn from_href(href: &str) -> Option<Self> {
+        let (scheme, value) = href.split_once(':')?;
+        let target = match scheme {
+            "note" => Self::Note(decode_component(value)?),
+            "note-new" => Self::NewNote(decode_component(value)?),
+            "token" => {
+                let (scene_id, token_id) = value.split_once('/')?;
+                Self::Token {
+                    scene_id: decode_component(scene_id)?,
+                    token_id: decode_component(token_id)?,
+                }
+            }
+            "scene" => Self::Scene(decode_component(value)?),
+            _ => return None,
+        };
+        Some(target)
+    }
+}
+
+fn encode_component(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            encoded.push(char::from(byte));
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
+
+fn decode_component(value: &str) -> Option<String> {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        if bytes[index] == b'%' {
+            let hex = value.get(index + 1..index + 3)?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            index += 3;
+        } else {
+            decoded.push(bytes[index]);
+            index += 1;
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
+
+// --- Name resolution ---
+
+/// Case-insensitive lookup of note titles, token names and scene names.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LinkIndex {
+    notes: HashMap<String, String>,
+    tokens: HashMap<String, (String, String)>,
+    scenes: HashMap<String, String>,
+}
+
+impl LinkIndex {
+    /// When names repeat, the most recently updated note and the first
+    /// token on the active scene win.
+    pub fn new(notes: &[NotePayload], scenes: &[Scene], active_scene_id: Option<&str>) -> Self {
+        let mut index = Self::default();
+
+        let mut by_recency = notes.iter().collect::<Vec<_>>();
+        by_recency.sort_by(|left, right| right.updated_at_ms.total_cmp(&left.updated_at_ms));
+        for note in by_recency {
+            let key = name_key(&note.title);
+            if !key.is_empty() {
+                index.notes.entry(key).or_insert_with(|| note.id.clone());
+            }
+        }
+
+        let mut ordered_scenes = scenes.iter().collect::<Vec<_>>();
+        ordered_scenes.sort_by_key(|scene| Some(scene.id.as_str()) != active_scene_id);
+        for scene in ordered_scenes {
+            index
+                .scenes
+                .entry(name_key(&scene.name))
+                .or_insert_with(|| scene.id.clone());
+            for token in &scene.tokens {
+                index
+                    .tokens
+                    .entry(name_key(&token.name))
+                    .or_insert_with(|| (scene.id.clone(), token.id.clone()));
+            }
+        }
+        index
+    }
+
+    pub fn note(&self, title: &str) -> Option<&str> {
+        self.notes.get(&name_key(title)).map(String::as_str)
+    }
+
+    fn token(&self, name: &str) -> Option<WikiTarget> {
+        self.tokens
+            .get(&name_key(name))
+            .map(|(scene_id, token_id)| WikiTarget::Token {
+                scene_id: scene_id.clone(),
+                token_id: token_id.clone(),
+            })
+    }
+
+    fn scene(&self, name: &str) -> Option<WikiTarget> {
+        self.scenes
+            .get(&name_key(name))
+            .map(|id| WikiTarget::Scene(id.clone()))
+    }
+}
+
+fn name_key(name: &str) -> String {
+    name.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+// --- Markdown rewriting ---
+
+/// A reference found in note text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Reference {
+    Wiki { title: String, label: String },
+    Token(String),
+    Scene(String),
+}
+
+/// Rewrites `[[Title]]`, `[[Title|label]]`, `@token`, `@[Token name]`,
+/// `#scene` and `#[Scene name]` into Markdown links. Unknown tokens and
+/// scenes stay plain text; code spans and fenced blocks are left alone.
+pub fn link_markdown(markdown: &str, index: &LinkIndex) -> String {
+    map_references(markdown, |reference, source| match reference {
+        Reference::Wiki { title, label } => {
+            let target = match index.note(&title) {
+                Some(id) => WikiTarget::Note(id.to_string()),
+                None => WikiTarget::NewNote(title),
+            };
+            format!("[{}]({})", escape_label(&label), target.href())
+        }
+        Reference::Token(name) => match index.token(&name) {
+            Some(target) => format!("[@{}]({})", escape_label(&name), target.href()),
+            None => source.to_string(),
+        },
+        Reference::Scene(name) => match index.scene(&name) {
+            Some(target) => format!("[#{}]({})", escape_label(&name), target.href()),
+            None => source.to_string(),
+        },
+    })
+}
+
+/// Titles of the notes a text links to with `[[...]]`, in order, without repeats.
+pub fn wiki_link_titles(markdown: &str) -> Vec<String> {
+    let mut titles = Vec::new();
+    let mut seen = HashSet::new();
+    map_references(markdown, |reference, source| {
+        if let Reference::Wiki { title, .. } = reference
+            && seen.insert(name_key(&title))
+        {
+            titles.push(title);
+        }
+        source.to_string()
+    });
+    titles
+}
+
+fn escape_label(label: &str) -> String {
+    let mut escaped = String::with_capacity(label.len());
+    for character in label.chars() {
+        if matches!(character, '\\' | '[' | ']' | '*' | '_' | '`' | '<') {
+            escaped.push('\\');
+        }
+        escaped.push(character);
+    }
+    escaped
+}
+
+fn map_references(markdown: &str, mut replace: impl FnMut(Reference, &str) -> String) -> String {
+    let mut output = String::with_capacity(markdown.len());
+    let mut fence: Option<&str> = None;
+
+    for line in markdown.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        let marker = ["```", "~~~"]
+            .into_iter()
+            .find(|marker| trimmed.starts_with(marker));
+        match (fence, marker) {
+            (Some(open), Some(marker)) if open == marker => fence = None,
+            (None, Some(marker)) => fence = Some(marker),
+            _ => {}
+        }
+        if fence.is_some() || marker.is_some() {
+            output.push_str(line);
+            continue;
+        }
+        map_line_references(line, &mut output, &mut replace);
+    }
+    output
+}
+
+fn map_line_references(
+    line: &str,
+    output: &mut String,
+    replace: &mut impl FnMut(Reference, &str) -> String,
+) {
+    let mut rest = line;
+    let mut previous: Option<char> = None;
+
+    while let Some(character) = rest.chars().next() {
+        if character == '`' {
+            let run = rest.len() - rest.trim_start_matches('`').len();
+            let fence = &rest[..run];
+            let after = &rest[run..];
+            let code_len = after.find(fence).map_or(run, |end| run + end + run);
+            output.push_str(&rest[..code_len]);
+            rest = &rest[code_len..];
+            previous = Some('`');
+            continue;
+        }
+
+        let at_word_start = previous.is_none_or(|previous| {
+            previous.is_whitespace() || matches!(previous, '(' | '[' | '"' | '\'')
+        });
+        let parsed = if rest.starts_with("[[") {
+            parse_wiki_link(rest)
+        } else if at_word_start && (character == '@' || character == '#') {
+            parse_named_reference(rest, character)
+        } else {
+            None
+        };
+
+        match parsed {
+            Some((reference, consumed)) => {
+                output.push_str(&replace(reference, &rest[..consumed]));
+                previous = rest[..consumed].chars().last();
+                rest = &rest[consumed..];
+            }
+            None => {
+                output.push(character);
+                previous = Some(character);
+                rest = &rest[character.len_utf8()..];
+            }
+        }
+    }
+}
+
+fn parse_wiki_link(text: &str) -> Option<(Reference, usize)> {
+    let inner_end = text[2..].find("]]")?;
+    let inner = &text[2..2 + inner_end];
+    if inner.contains('[') || inner.contains('\n') {
+        return None;
+    }
+    let (title, label) = match inner.split_once('|') {
+        Some((title, label)) => (title.trim(), label.trim()),
+        None => (inner.trim(), inner.trim()),
+    };
+    if title.is_empty() {
+        return None;
+    }
+    let label = if label.is_empty() { title } else { label };
+    Some((
+        Reference::Wiki {
+            title: title.to_string(),
+            label: label.to_string(),
+        },
+        inner_end + 4,
+    ))
+}
+
+fn parse_named_reference(text: &str, sigil: char) -> Option<(Reference, usize)> {
+    let after = &text[sigil.len_utf8()..];
+    let (name, consumed) = if let Some(bracketed) = after.strip_prefix('[') {
+        let end = bracketed.find(']')?;
+        let name = bracketed[..end].trim();
+        (name, sigil.len_utf8() + end + 2)
+    } else {
+        let end = after
+            .char_indices()
+            .find(|(_, character)| !(character.is_alphanumeric() || matches!(character, '_' | '-')))
+            .map_or(after.len(), |(index, _)| index);
+        (&after[..end], sigil.len_utf8() + end)
+    };
+    if name.is_empty() || name.contains('\n') {
+        return None;
+    }
+    let reference = match sigil {
+        '@' => Reference::Token(name.to_string()),
+        _ => Reference::Scene(name.to_string()),
+    };
+    Some((reference, consumed))
+}
+
+// --- Backlinks and the journal graph ---
+
+/// Notes from every bucket, once each; the most recently updated copy wins.
+pub fn journal_notes(buckets: &[&[NotePayload]]) -> Vec<NotePayload> {
+    let mut by_id = HashMap::<&str, &NotePayload>::new();
+    for note in buckets.iter().flat_map(|bucket| bucket.iter()) {
+        by_id
+            .entry(note.id.as_str())
+            .and_modify(|existing| {
+                if note.updated_at_ms > existing.updated_at_ms {
+                    *existing = note;
+                }
+            })
+            .or_insert(note);
+    }
+    let mut notes = by_id.into_values().cloned().collect::<Vec<_>>();
+    notes.sort_by(|left, right| {
+        name_key(&left.title)
+            .cmp(&name_key(&right.title))
+            .then_with(|| left.id.cmp(&right.id))
+    });
+    notes
+}
+
+/// Note id -> ids of the notes it links to with `[[...]]`.
+pub fn outgoing_links(notes: &[NotePayload]) -> HashMap<String, Vec<String>> {
+    let index = LinkIndex::new(notes, &[], None);
+    notes
+        .iter()
+        .map(|note| {
+            let mut targets = Vec::new();
+            for title in wiki_link_titles(&note.body) {
+                if let Some(id) = index.note(&title)
+                    && id != note.id
+                    && !targets.iter().any(|target| target == id)
+                {
+                    targets.push(id.to_string());
+                }
+            }
+            (note.id.clone(), targets)
+        })
+        .collect()
+}
+
+/// Note id -> ids of the notes linking to it, ordered like `notes`.
+pub fn backlinks(notes: &[NotePayload]) -> HashMap<String, Vec<String>> {
+    let outgoing = outgoing_links(notes);
+    let mut incoming = HashMap::<String, Vec<String>>::new();
+    for note in notes {
+        for target in outgoing.get(&note.id).into_iter().flatten() {
+            incoming
+                .entry(target.clone())
+                .or_default()
+                .push(note.id.clone());
+        }
+    }
+    incoming
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphNode {
+    pub id: String,
+    pub title: String,
+    /// Position in the unit square.
+    pub x: f64,
+    pub y: f64,
+    pub link_count: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct JournalGraph {
+    pub nodes: Vec<GraphNode>,
+    /// Pairs of indices into `nodes`.
+    pub edges: Vec<(usize, usize)>,
+}
+
+/// Notes on a circle, linked notes first so their edges stay short.
+pub fn journal_graph(notes: &[NotePayload]) -> JournalGraph {
+    let outgoing = outgoing_links(notes);
+    let mut link_counts = HashMap::<&str, usize>::new();
+    for (source, targets) in &outgoing {
+        for target in targets {
+            *link_counts.entry(source.as_str()).or_default() += 1;
+            *link_counts.entry(target.as_str()).or_default() += 1;
+        }
+    }
+
+    let mut ordered = notes.iter().collect::<Vec<_>>();
+    ordered.sort_by_key(|note| {
+        std::cmp::Reverse(link_counts.get(note.id.as_str()).copied().unwrap_or(0))
+    });
+    let count = ordered.len();
+    let nodes = ordered
+        .iter()
+        .enumerate()
+        .map(|(position, note)| {
+            let (x, y) = if count == 1 {
+                (0.5, 0.5)
+            } else {
+                let angle = TAU * position as f64 / count as f64 - TAU / 4.0;
+                (0.5 + 0.42 * angle.cos(), 0.5 + 0.42 * angle.sin())
+            };
+            GraphNode {
+                id: note.id.clone(),
+                title: note.title.clone(),
+                x,
+                y,
+                link_count: link_counts.get(note.id.as_str()).copied().unwrap_or(0),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let positions = nodes
+        .iter()
+        .enumerate()
+        .map(|(position, node)| (node.id.as_str(), position))
+        .collect::<HashMap<_, _>>();
+    let mut edges = Vec::new();
+    for node in &nodes {
+        for target in outgoing.get(&node.id).into_iter().flatten() {
+            if let (Some(&from), Some(&to)) = (
+                positions.get(node.id.as_str()),
+                positions.get(target.as_str()),
+            ) {
+                edges.push((from, to));
+            }
+        }
+    }
+    JournalGraph { nodes, edges }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::events::{FileRef, NoteBoardStyle, NoteVisibility, SceneGrid, Token};
+
+    fn note(id: &str, title: &str, body: &str, updated_at_ms: f64) -> NotePayload {
+        NotePayload {
+            id: id.to_string(),
+            author: "gm".to_string(),
+            visibility: NoteVisibility::Public,
+            title: title.to_string(),
+            body: body.to_string(),
+            created_at_ms: 0.0,
+            updated_at_ms,
+            board_position: None,
+            board_style: NoteBoardStyle::default(),
+            folder: String::new(),
+            tags: Vec::new(),
+            pinned: false,
+            edited_by: String::new(),
+            revisions: Vec::new(),
+        }
+    }
+
+    fn scene(id: &str, name: &str, tokens: &[(&str, &str)]) -> Scene {
+        Scene {
+            id: id.to_string(),
+            name: name.to_string(),
+            grid: SceneGrid {
+                columns: 10,
+                rows: 10,
+                cell_size_feet: 5,
+            },
+            workspace_x: 0.0,
+            workspace_y: 0.0,
+            background: None,
+            background_scale: 1.0,
+            background_offset_x: 0.0,
+            background_offset_y: 0.0,
+            background_rotation_deg: 0.0,
+            tokens: tokens
+                .iter()
+                .map(|(id, name)| Token {
+                    id: id.to_string(),
+                    name: name.to_string(),
+                    image: FileRef {
+                        hash: "img".to_string(),
+                        mime_type: "image/png".to_string(),
+                        file_name: "img.png".to_string(),
+                        size: 1,
+                    },
+                    x: 0.0,
+                    y: 0.0,
+                    width_cells: 1,
+                    height_cells: 1,
+                    rotation_deg: 0.0,
+                    elevation_feet: 0,
+                    owner: None,
+                    auras: Vec::new(),
+                    level_id: None,
+                })
+                .collect(),
+            terrain: Vec::new(),
+            walls: Vec::new(),
+            doors: Vec::new(),
+            lights: Vec::new(),
+            folder: None,
+            sort_index: 0,
+            levels: Vec::new(),
+            stairs: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn hrefs_round_trip() {
+        let targets = [
+            WikiTarget::Note("n-1".to_string()),
+            WikiTarget::NewNote("Башня магов / 2".to_string()),
+            WikiTarget::Token {
+                scene_id: "s 1".to_string(),
+                token_id: "t/1".to_string(),
+            },
+            WikiTarget::Scene("s-1".to_string()),
+        ];
+        for target in targets {
+            assert_eq!(WikiTarget::from_href(&target.href()), Some(target));
+        }
+        assert_eq!(WikiTarget::from_href("https://example.com"), None);
+        assert_eq!(WikiTarget::from_href("note-new:%E"), None);
+    }
+
+    #[test]
+    fn wiki_links_resolve_case_insensitively_to_the_newest_note() {
+        let notes = [
+            note("old", "Black Tower", "", 1.0),
+            note("new", "black  tower", "", 2.0),
+        ];
+        let index = LinkIndex::new(&notes, &[], None);
+        assert_eq!(
+            link_markdown("See [[Black Tower|the tower]].", &index),
+            "See [the tower](note:new)."
+        );
+        assert_eq!(
+            link_markdown("[[Lost Mine]]", &index),
+            "[Lost Mine](note-new:Lost%20Mine)"
+        );
+    }
+
+    #[test]
+    fn token_and_scene_references_become_links_only_when_known() {
+        let scenes = [
+            scene("s1", "Crypt", &[("t1", "Goblin")]),
+            scene("s2", "Old Road", &[("t2", "Goblin")]),
+        ];
+        let index = LinkIndex::new(&[], &scenes, Some("s2"));
+        assert_eq!(
+            link_markdown(
+                "@Goblin waits in #Crypt, not #[old road]. Mail me@home #tag",
+                &index
+            ),
+            "[@Goblin](token:s2/t2) waits in [#Crypt](scene:s1), not [#old road](scene:s2). Mail me@home #tag"
+        );
+        assert_eq!(link_markdown("# Crypt\n", &index), "# Crypt\n");
+    }
+
+    #[test]
+    fn code_is_left_untouched() {
+        let index = LinkIndex::new(&[note("n", "Spell", "", 1.0)], &[], None);
+        let markdown = "`[[Spell]]` and\n```\n[[Spell]]\n```\n[[Spell]]";
+        assert_eq!(
+            link_markdown(markdown, &index),
+            "`[[Spell]]` and\n```\n[[Spell]]\n```\n[Spell](note:n)"
+        );
+        assert_eq!(wiki_link_titles(markdown), vec!["Spell".to_string()]);
+    }
+
+    #[test]
+    fn backlinks_and_graph_follow_wiki_links() {
+        let notes = journal_notes(&[
+            &[
+                note(
+                    "a",
+                    "Alpha",
+                    "# Alpha\n[[Beta]] and [[beta]] and [[Alpha]]",
+                    1.0,
+                ),
+                note("b", "Beta", "# Beta\n[[Gamma]]", 1.0),
+            ],
+            &[
+                note("c", "Gamma", "# Gamma\n[[Alpha]]", 1.0),
+                note("a", "Alpha", "stale copy", 0.5),
+            ],
+        ]);
+        assert_eq!(notes.len(), 3);
+
+        let outgoing = outgoing_links(&notes);
+        assert_eq!(outgoing["a"], vec!["b".to_string()]);
+
+        let incoming = backlinks(&notes);
+        assert_eq!(incoming["a"], vec!["c".to_string()]);
+        assert_eq!(incoming["b"], vec!["a".to_string()]);
+
+        let graph = journal_graph(&notes);
+        assert_eq!(graph.nodes.len(), 3);
+        assert_eq!(graph.edges.len(), 3);
+        assert!(graph.nodes.iter().all(|node| node.link_count == 2));
+        assert!(
+            graph
+                .nodes
+                .iter()
+                .all(|node| (0.0..=1.0).contains(&node.x) && (0.0..=1.0).contains(&node.y))
+        );
+    }
+}
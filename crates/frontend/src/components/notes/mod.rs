@@ -1,4 +1,10 @@
+pub mod archive;
+pub mod collab;
+pub mod embeds;
+pub mod links;
 pub mod model;
+pub mod revisions;
+pub mod search;
 mod view;
 pub mod view_model;
 
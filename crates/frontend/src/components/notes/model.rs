@@ -1,12 +1,25 @@
 use ammonia::Builder;
 use pulldown_cmark::{Options, Parser, html};
-use shared::events::{NotePayload, NoteVisibility};
+use shared::events::{NoteGroup, NotePayload, NoteVisibility};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+use super::embeds::FILE_EMBED_SCHEME;
+use super::links::{LinkIndex, WIKI_LINK_SCHEMES, link_markdown};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum NotesTab {
     Public,
     Private,
     Direct,
+    /// Notes shared with one group; every group gets its own tab.
+    Group(NoteGroup),
+}
+
+/// How the notes window presents the journal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JournalView {
+    Notes,
+    Index,
+    Graph,
 }
 
 pub const BOARD_NOTE_DRAG_MIME: &str = "application/x-dnd-note";
@@ -24,19 +37,44 @@ pub fn tab_for_visibility(visibility: &NoteVisibility) -> NotesTab {
         NoteVisibility::Public => NotesTab::Public,
         NoteVisibility::Private => NotesTab::Private,
         NoteVisibility::Direct(_) => NotesTab::Direct,
+        NoteVisibility::Group(group) => NotesTab::Group(group.clone()),
     }
 }
 
+/// Groups with at least one note, ordered by label.
+pub fn note_groups(notes: &[NotePayload]) -> Vec<NoteGroup> {
+    let mut groups = notes
+        .iter()
+        .filter_map(|note| match &note.visibility {
+            NoteVisibility::Group(group) => Some(group.clone()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    groups.sort_by(|left, right| {
+        left.label()
+            .cmp(&right.label())
+            .then_with(|| left.members.cmp(&right.members))
+    });
+    groups.dedup();
+    groups
+}
+
 pub fn can_edit_note(note: &NotePayload, current_username: &str) -> bool {
     note.author == current_username
 }
 
+/// Public notes are co-edited by everyone in the room; the other note
+/// actions stay with the author.
+pub fn can_edit_note_body(note: &NotePayload, current_username: &str) -> bool {
+    can_edit_note(note, current_username) || note.visibility == NoteVisibility::Public
+}
+
 pub fn can_delete_note(note: &NotePayload, current_username: &str) -> bool {
     match &note.visibility {
         NoteVisibility::Public | NoteVisibility::Private => note.author == current_username,
-        NoteVisibility::Direct(recipient) => {
-            note.author == current_username || recipient == current_username
-        }
+        NoteVisibility::Direct(_) | NoteVisibility::Group(_) => note
+            .visibility
+            .is_visible_to(&note.author, current_username),
     }
 }
 
@@ -48,7 +86,16 @@ pub fn render_note_html(markdown: &str) -> String {
     let parser = Parser::new_ext(markdown, options);
     let mut html_output = String::new();
     html::push_html(&mut html_output, parser);
-    Builder::default().clean(&html_output).to_string()
+    let mut sanitizer = Builder::default();
+    sanitizer.add_url_schemes(WIKI_LINK_SCHEMES);
+    sanitizer.add_url_schemes([FILE_EMBED_SCHEME]);
+    sanitizer.clean(&html_output).to_string()
+}
+
+/// Renders a note with its `[[wiki links]]`, `@token` and `#scene` references
+/// turned into links.
+pub fn render_linked_note_html(markdown: &str, index: &LinkIndex) -> String {
+    render_note_html(&link_markdown(markdown, index))
 }
 
 pub fn note_heading_and_body(markdown: &str) -> (String, String) {
@@ -84,12 +131,18 @@ pub fn note_title_from_markdown(markdown: &str) -> String {
     title.chars().take(120).collect()
 }
 
+/// Pinned notes first, then the most recently updated.
 pub fn sort_notes(notes: &mut [NotePayload]) {
     notes.sort_by(|left, right| {
         right
-            .updated_at_ms
-            .partial_cmp(&left.updated_at_ms)
-            .unwrap_or(std::cmp::Ordering::Equal)
+            .pinned
+            .cmp(&left.pinned)
+            .then_with(|| {
+                right
+                    .updated_at_ms
+                    .partial_cmp(&left.updated_at_ms)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
             .then_with(|| left.id.cmp(&right.id))
     });
 }
@@ -111,8 +164,14 @@ mod tests {
             board_position: Some(NoteBoardPosition {
                 world_x: 10.0,
                 world_y: 20.0,
+                anchor: None,
             }),
             board_style: NoteBoardStyle::default(),
+            folder: String::new(),
+            tags: Vec::new(),
+            pinned: false,
+            edited_by: String::new(),
+            revisions: Vec::new(),
         }
     }
 
@@ -122,6 +181,15 @@ mod tests {
         assert!(!can_edit_note(&note("gm"), "player"));
     }
 
+    #[test]
+    fn anyone_can_edit_public_note_bodies() {
+        assert!(can_edit_note_body(&note("gm"), "player"));
+        let mut private_note = note("gm");
+        private_note.visibility = NoteVisibility::Private;
+        assert!(!can_edit_note_body(&private_note, "player"));
+        assert!(can_edit_note_body(&private_note, "gm"));
+    }
+
     #[test]
     fn direct_note_can_be_deleted_by_author_or_recipient() {
         let mut direct_note = note("gm");
@@ -132,6 +200,17 @@ mod tests {
         assert!(!can_delete_note(&direct_note, "spectator"));
     }
 
+    #[test]
+    fn group_note_can_be_deleted_by_any_member() {
+        let mut group_note = note("gm");
+        group_note.visibility =
+            NoteVisibility::Group(NoteGroup::new("Party A", ["alice".into(), "bob".into()]));
+
+        assert!(can_delete_note(&group_note, "gm"));
+        assert!(can_delete_note(&group_note, "bob"));
+        assert!(!can_delete_note(&group_note, "carol"));
+    }
+
     #[test]
     fn markdown_is_sanitized() {
         let html = render_note_html("hello<script>alert(1)</script>");
@@ -140,6 +219,31 @@ mod tests {
         assert!(!html.contains("alert(1)</script>"));
     }
 
+    #[test]
+    fn wiki_links_survive_sanitizing() {
+        let mut target = note("gm");
+        target.id = "tower".to_string();
+        target.title = "Black Tower".to_string();
+        let index = LinkIndex::new(&[target], &[], None);
+        let html = render_linked_note_html(
+            "See [[Black Tower]] and [[Lost Mine]], not [x](javascript:alert(1))",
+            &index,
+        );
+        assert!(html.contains("href=\"note:tower\""));
+        assert!(html.contains("href=\"note-new:Lost%20Mine\""));
+        assert!(!html.contains("javascript:"));
+    }
+
+    #[test]
+    fn file_embeds_survive_sanitizing() {
+        let hash = "a".repeat(64);
+        let html = render_note_html(&format!(
+            "![Map](file:{hash} \"title\") <img src=x onerror=alert(1)>"
+        ));
+        assert!(html.contains(&format!("src=\"file:{hash}\"")));
+        assert!(!html.contains("onerror"));
+    }
+
     #[test]
     fn heading_and_body_are_split_from_first_non_empty_line() {
         let (title, body) = note_heading_and_body("\n# Title\nBody\nNext");
@@ -147,6 +251,20 @@ mod tests {
         assert_eq!(body, "Body\nNext");
     }
 
+    #[test]
+    fn pinned_notes_sort_first() {
+        let mut old_pinned = note("gm");
+        old_pinned.id = "pinned".to_string();
+        old_pinned.updated_at_ms = 1.0;
+        old_pinned.pinned = true;
+        let mut recent = note("gm");
+        recent.id = "recent".to_string();
+        recent.updated_at_ms = 5.0;
+        let mut notes = vec![recent, old_pinned];
+        sort_notes(&mut notes);
+        assert_eq!(notes[0].id, "pinned");
+    }
+
     #[test]
     fn tab_selection_matches_visibility() {
         assert_eq!(
@@ -161,6 +279,28 @@ mod tests {
             tab_for_visibility(&NoteVisibility::Direct("gm".into())),
             NotesTab::Direct
         );
+        let group = NoteGroup::new("", ["alice".into(), "gm".into()]);
+        assert_eq!(
+            tab_for_visibility(&NoteVisibility::Group(group.clone())),
+            NotesTab::Group(group)
+        );
+    }
+
+    #[test]
+    fn each_group_is_listed_once() {
+        let party = NoteGroup::new("Party A", ["alice".into(), "gm".into()]);
+        let pair = NoteGroup::new("", ["bob".into(), "gm".into()]);
+        let notes = [&party, &pair, &party]
+            .into_iter()
+            .map(|group| {
+                let mut group_note = note("gm");
+                group_note.visibility = NoteVisibility::Group(group.clone());
+                group_note
+            })
+            .chain([note("gm")])
+            .collect::<Vec<_>>();
+
+        assert_eq!(note_groups(&notes), vec![party, pair]);
     }
 
     #[test]
@@ -0,0 +1,229 @@
+// Pure note revision history: bounded revision lists, restore and line diffs.
+// No signals, no Leptos, no web_sys.
+
+use shared::events::{NotePayload, NoteRevision};
+
+/// Matches the `revisions` length limit validated on the wire.
+pub const MAX_NOTE_REVISIONS: usize = 10;
+
+/// Carries `previous.revisions` over to `updated` and appends `previous`
+/// itself when its text differs, dropping the oldest entries past the limit.
+pub fn push_revision(updated: &mut NotePayload, previous: &NotePayload) {
+    let mut revisions = previous.revisions.clone();
+    if previous.body != updated.body || previous.title != updated.title {
+        let author = if previous.edited_by.is_empty() {
+            &previous.author
+        } else {
+            &previous.edited_by
+        };
+        revisions.push(NoteRevision {
+            author: author.clone(),
+            saved_at_ms: previous.updated_at_ms,
+            title: previous.title.clone(),
+            body: previous.body.clone(),
+        });
+    }
+    let overflow = revisions.len().saturating_sub(MAX_NOTE_REVISIONS);
+    revisions.drain(..overflow);
+    updated.revisions = revisions;
+}
+
+/// Builds the upsert in which `editor` brings back revision `index`; the
+/// current text becomes the newest revision so a restore can itself be undone.
+pub fn restore_revision(
+    note: &NotePayload,
+    index: usize,
+    editor: &str,
+    now_ms: f64,
+) -> Option<NotePayload> {
+    let revision = note.revisions.get(index)?;
+    let mut restored = note.clone();
+    restored.title = revision.title.clone();
+    restored.body = revision.body.clone();
+    restored.edited_by = editor.to_string();
+    restored.updated_at_ms = now_ms;
+    push_revision(&mut restored, note);
+    Some(restored)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffKind {
+    Same,
+    Added,
+    Removed,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffLine {
+    pub kind: DiffKind,
+    pub text: String,
+}
+
+impl DiffLine {
+    fn new(kind: DiffKind, text: &str) -> Self {
+        Self {
+            kind,
+            text: text.to_string(),
+        }
+    }
+}
+
+/// Line diff from `old` to `new`, removals listed before additions in each
+/// changed run.
+pub fn diff_lines(old: &str, new: &str) -> Vec<DiffLine> {
+    let old_lines = old.lines().collect::<Vec<_>>();
+    let new_lines = new.lines().collect::<Vec<_>>();
+
+    let prefix = old_lines
+        .iter()
+        .zip(&new_lines)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = old_lines[prefix..]
+        .iter()
+        .rev()
+        .zip(new_lines[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_mid = &old_lines[prefix..old_lines.len() - suffix];
+    let new_mid = &new_lines[prefix..new_lines.len() - suffix];
+
+    let mut diff = old_lines[..prefix]
+        .iter()
+        .map(|line| DiffLine::new(DiffKind::Same, line))
+        .collect::<Vec<_>>();
+
+    // lcs[i][j] is the common subsequence length of old_mid[i..] and new_mid[j..].
+    let width = new_mid.len() + 1;
+    let mut lcs = vec![0usize; (old_mid.len() + 1) * width];
+    for i in (0..old_mid.len()).rev() {
+        for j in (0..new_mid.len()).rev() {
+            lcs[i * width + j] = if old_mid[i] == new_mid[j] {
+                lcs[(i + 1) * width + j + 1] + 1
+            } else {
+                lcs[(i + 1) * width + j].max(lcs[i * width + j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    while i < old_mid.len() || j < new_mid.len() {
+        if i < old_mid.len() && j < new_mid.len() && old_mid[i] == new_mid[j] {
+            diff.push(DiffLine::new(DiffKind::Same, old_mid[i]));
+            i += 1;
+            j += 1;
+        } else if i < old_mid.len()
+            && (j == new_mid.len() || lcs[(i + 1) * width + j] >= lcs[i * width + j + 1])
+        {
+            diff.push(DiffLine::new(DiffKind::Removed, old_mid[i]));
+            i += 1;
+        } else {
+            diff.push(DiffLine::new(DiffKind::Added, new_mid[j]));
+            j += 1;
+        }
+    }
+
+    diff.extend(
+        old_lines[old_lines.len() - suffix..]
+            .iter()
+            .map(|line| DiffLine::new(DiffKind::Same, line)),
+    );
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::events::{NoteBoardStyle, NoteVisibility};
+
+    fn note(body: &str, updated_at_ms: f64) -> NotePayload {
+        NotePayload {
+            id: "note-1".into(),
+            author: "gm".into(),
+            visibility: NoteVisibility::Public,
+            title: body.lines().next().unwrap_or_default().into(),
+            body: body.into(),
+            created_at_ms: 1.0,
+            updated_at_ms,
+            board_position: None,
+            board_style: NoteBoardStyle::default(),
+            folder: String::new(),
+            tags: Vec::new(),
+            pinned: false,
+            edited_by: String::new(),
+            revisions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn edits_push_bounded_revisions() {
+        let mut current = note("v0", 0.0);
+        for version in 1..=MAX_NOTE_REVISIONS + 2 {
+            let mut updated = note(&format!("v{version}"), version as f64);
+            push_revision(&mut updated, &current);
+            current = updated;
+        }
+
+        assert_eq!(current.revisions.len(), MAX_NOTE_REVISIONS);
+        assert_eq!(current.revisions[0].body, "v2");
+        assert_eq!(current.revisions[0].saved_at_ms, 2.0);
+        assert_eq!(
+            current
+                .revisions
+                .last()
+                .map(|revision| revision.body.as_str()),
+            Some("v11")
+        );
+
+        let mut pinned = current.clone();
+        pinned.pinned = true;
+        push_revision(&mut pinned, &current);
+        assert_eq!(pinned.revisions, current.revisions);
+    }
+
+    #[test]
+    fn restore_keeps_current_text_as_revision() {
+        let mut current = note("# Tower\nnew", 2.0);
+        current.edited_by = "player".into();
+        current.revisions.push(NoteRevision {
+            author: "gm".into(),
+            saved_at_ms: 1.0,
+            title: "Tower".into(),
+            body: "# Tower\nold".into(),
+        });
+
+        let restored = restore_revision(&current, 0, "gm", 5.0).unwrap();
+        assert_eq!(restored.body, "# Tower\nold");
+        assert_eq!(restored.title, "Tower");
+        assert_eq!(restored.updated_at_ms, 5.0);
+        assert_eq!(restored.revisions.len(), 2);
+        assert_eq!(restored.revisions[1].body, "# Tower\nnew");
+        assert_eq!(restored.revisions[1].author, "player");
+        assert_eq!(restored.edited_by, "gm");
+        assert!(restore_revision(&current, 3, "gm", 5.0).is_none());
+    }
+
+    #[test]
+    fn diff_marks_changed_lines() {
+        let diff = diff_lines("a\nb\nc\nd", "a\nc\nx\nd");
+        let marked = diff
+            .iter()
+            .map(|line| match line.kind {
+                DiffKind::Same => format!(" {}", line.text),
+                DiffKind::Added => format!("+{}", line.text),
+                DiffKind::Removed => format!("-{}", line.text),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(marked, vec![" a", "-b", " c", "+x", " d"]);
+
+        assert!(
+            diff_lines("same", "same")
+                .iter()
+                .all(|line| line.kind == DiffKind::Same)
+        );
+        assert_eq!(
+            diff_lines("", "new"),
+            vec![DiffLine::new(DiffKind::Added, "new")]
+        );
+    }
+}
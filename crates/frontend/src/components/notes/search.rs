@@ -0,0 +1,475 @@
+// Pure logic for organizing notes: nested folders, tags, and full-text search
+// over titles and bodies with highlighted matches.
+// No signals, no Leptos, no web_sys.
+
+use shared::events::NotePayload;
+use std::collections::BTreeMap;
+
+// --- Constants ---
+
+pub const MAX_NOTE_TAGS: usize = 16;
+pub const MAX_NOTE_TAG_LEN: usize = 40;
+pub const MAX_FOLDER_PATH_LEN: usize = 255;
+pub const MAX_FOLDER_DEPTH: usize = 8;
+/// Characters of body text kept around the first match in a search snippet.
+const SNIPPET_CONTEXT_CHARS: usize = 60;
+
+// --- Folders ---
+
+/// Trims every segment of a `/`-separated folder path and drops empty ones.
+pub fn normalize_folder_path(input: &str) -> String {
+    let path = input
+        .split('/')
+        .map(|segment| segment.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|segment| !segment.is_empty())
+        .take(MAX_FOLDER_DEPTH)
+        .collect::<Vec<_>>()
+        .join("/");
+    path.chars().take(MAX_FOLDER_PATH_LEN).collect()
+}
+
+/// Whether `path` is `folder` or one of its subfolders; the root holds everything.
+pub fn folder_contains(folder: &str, path: &str) -> bool {
+    folder.is_empty()
+        || path == folder
+        || path
+            .strip_prefix(folder)
+            .is_some_and(|rest| rest.starts_with('/'))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FolderNode {
+    pub path: String,
+    pub name: String,
+    pub depth: usize,
+    /// Notes in this folder and all of its subfolders.
+    pub note_count: usize,
+}
+
+/// Every folder used by `notes`, parents before children, siblings by name.
+pub fn folder_tree(notes: &[NotePayload]) -> Vec<FolderNode> {
+    let mut counts = BTreeMap::<Vec<String>, usize>::new();
+    for note in notes {
+        let segments = note
+            .folder
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .collect::<Vec<_>>();
+        for depth in 1..=segments.len() {
+            let key = segments[..depth]
+                .iter()
+                .map(|segment| segment.to_string())
+                .collect();
+            *counts.entry(key).or_default() += 1;
+        }
+    }
+    counts
+        .into_iter()
+        .map(|(segments, note_count)| FolderNode {
+            path: segments.join("/"),
+            name: segments.last().cloned().unwrap_or_default(),
+            depth: segments.len() - 1,
+            note_count,
+        })
+        .collect()
+}
+
+// --- Tags ---
+
+fn normalize_tag(tag: &str) -> String {
+    tag.trim()
+        .trim_start_matches('#')
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("-")
+        .to_lowercase()
+        .chars()
+        .take(MAX_NOTE_TAG_LEN)
+        .collect()
+}
+
+/// Parses a comma-separated tag list: lowercase, no `#`, no repeats.
+pub fn normalize_tags(input: &str) -> Vec<String> {
+    let mut tags = Vec::new();
+    for tag in input.split(',').map(normalize_tag) {
+        if !tag.is_empty() && !tags.contains(&tag) && tags.len() < MAX_NOTE_TAGS {
+            tags.push(tag);
+        }
+    }
+    tags
+}
+
+pub fn format_tags(tags: &[String]) -> String {
+    tags.join(", ")
+}
+
+/// Tags used by `notes` with the number of notes carrying each, by name.
+pub fn tag_counts(notes: &[NotePayload]) -> Vec<(String, usize)> {
+    let mut counts = BTreeMap::<&str, usize>::new();
+    for note in notes {
+        for tag in &note.tags {
+            *counts.entry(tag.as_str()).or_default() += 1;
+        }
+    }
+    counts
+        .into_iter()
+        .map(|(tag, count)| (tag.to_string(), count))
+        .collect()
+}
+
+// --- Filtering and search ---
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NoteFilter {
+    /// Free text; `#tag` words also filter by tag.
+    pub query: String,
+    /// Folder path; empty means every folder.
+    pub folder: String,
+    pub tag: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchQuery {
+    /// Lowercase words that must all appear in the title, body or tags.
+    pub terms: Vec<String>,
+    pub tags: Vec<String>,
+}
+
+impl SearchQuery {
+    pub fn parse(query: &str) -> Self {
+        let mut parsed = Self::default();
+        for word in query.split_whitespace() {
+            if word.starts_with('#') {
+                let tag = normalize_tag(word);
+                if !tag.is_empty() && !parsed.tags.contains(&tag) {
+                    parsed.tags.push(tag);
+                }
+            } else {
+                let term = word.to_lowercase();
+                if !parsed.terms.contains(&term) {
+                    parsed.terms.push(term);
+                }
+            }
+        }
+        parsed
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty() && self.tags.is_empty()
+    }
+}
+
+fn in_scope(note: &NotePayload, filter: &NoteFilter) -> bool {
+    folder_contains(&filter.folder, &note.folder)
+        && filter
+            .tag
+            .as_ref()
+            .is_none_or(|tag| note.tags.contains(tag))
+}
+
+/// Whether `note` passes the folder, tag and query filters.
+pub fn matches_filter(note: &NotePayload, filter: &NoteFilter) -> bool {
+    in_scope(note, filter) && match_score(note, &SearchQuery::parse(&filter.query)).is_some()
+}
+
+/// Relevance of `note` for `query`, or `None` when a term or tag is missing.
+/// Title matches weigh more than body matches.
+fn match_score(note: &NotePayload, query: &SearchQuery) -> Option<usize> {
+    if query.tags.iter().any(|tag| !note.tags.contains(tag)) {
+        return None;
+    }
+    let title = note.title.to_lowercase();
+    let body = note.body.to_lowercase();
+    let mut score = 0;
+    for term in &query.terms {
+        let title_hits = title.matches(term.as_str()).count();
+        let body_hits = body.matches(term.as_str()).count();
+        let tag_hits = note.tags.iter().filter(|tag| tag.contains(term)).count();
+        if title_hits + body_hits + tag_hits == 0 {
+            return None;
+        }
+        score += title_hits * 4 + tag_hits * 2 + body_hits;
+    }
+    Some(score)
+}
+
+/// Notes passing `filter`: pinned first, then by relevance, then newest.
+pub fn search_notes(notes: &[NotePayload], filter: &NoteFilter) -> Vec<NotePayload> {
+    let query = SearchQuery::parse(&filter.query);
+    let mut hits = notes
+        .iter()
+        .filter(|note| in_scope(note, filter))
+        .filter_map(|note| match_score(note, &query).map(|score| (score, note)))
+        .collect::<Vec<_>>();
+    hits.sort_by(|(left_score, left), (right_score, right)| {
+        right
+            .pinned
+            .cmp(&left.pinned)
+            .then_with(|| right_score.cmp(left_score))
+            .then_with(|| right.updated_at_ms.total_cmp(&left.updated_at_ms))
+            .then_with(|| left.id.cmp(&right.id))
+    });
+    hits.into_iter().map(|(_, note)| note.clone()).collect()
+}
+
+// --- Highlighting ---
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextSegment {
+    pub text: String,
+    pub is_match: bool,
+}
+
+/// Byte ranges of `text` matching any of `terms`, case-insensitively, merged.
+fn match_ranges(text: &str, terms: &[String]) -> Vec<(usize, usize)> {
+    // Lowercasing may change byte lengths, so map lowered offsets back to
+    // the original character boundaries.
+    let mut lowered = String::with_capacity(text.len());
+    let mut boundaries = Vec::with_capacity(text.len() + 1);
+    for (original, character) in text.char_indices() {
+        boundaries.push((lowered.len(), original));
+        lowered.extend(character.to_lowercase());
+    }
+    boundaries.push((lowered.len(), text.len()));
+    let original_start = |lowered_at: usize| {
+        boundaries
+            .iter()
+            .rev()
+            .find(|(boundary, _)| *boundary <= lowered_at)
+            .map_or(0, |(_, original)| *original)
+    };
+    let original_end = |lowered_at: usize| {
+        boundaries
+            .iter()
+            .find(|(boundary, _)| *boundary >= lowered_at)
+            .map_or(text.len(), |(_, original)| *original)
+    };
+
+    let mut ranges = terms
+        .iter()
+        .filter(|term| !term.is_empty())
+        .flat_map(|term| {
+            lowered
+                .match_indices(term.as_str())
+                .map(|(start, found)| (original_start(start), original_end(start + found.len())))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    ranges.sort_unstable();
+    let mut merged: Vec<(usize, usize)> = Vec::with_capacity(ranges.len());
+    for (start, end) in ranges {
+        match merged.last_mut() {
+            Some((_, last_end)) if start <= *last_end => *last_end = (*last_end).max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+fn segments_for(text: &str, ranges: &[(usize, usize)]) -> Vec<TextSegment> {
+    let mut segments = Vec::new();
+    let mut cursor = 0;
+    for &(start, end) in ranges {
+        if start > cursor {
+            segments.push(TextSegment {
+                text: text[cursor..start].to_string(),
+                is_match: false,
+            });
+        }
+        segments.push(TextSegment {
+            text: text[start..end].to_string(),
+            is_match: true,
+        });
+        cursor = end;
+    }
+    if cursor < text.len() {
+        segments.push(TextSegment {
+            text: text[cursor..].to_string(),
+            is_match: false,
+        });
+    }
+    segments
+}
+
+/// Splits `text` into plain and matching segments.
+pub fn highlight(text: &str, terms: &[String]) -> Vec<TextSegment> {
+    segments_for(text, &match_ranges(text, terms))
+}
+
+/// A single-line excerpt of `body` around its first match, highlighted.
+/// Empty when no term occurs in the body.
+pub fn match_snippet(body: &str, terms: &[String]) -> Vec<TextSegment> {
+    let flat = body.split_whitespace().collect::<Vec<_>>().join(" ");
+    let Some(&(first_start, _)) = match_ranges(&flat, terms).first() else {
+        return Vec::new();
+    };
+    let start = flat[..first_start]
+        .char_indices()
+        .rev()
+        .nth(SNIPPET_CONTEXT_CHARS - 1)
+        .map_or(0, |(index, _)| index);
+    let end = flat[first_start..]
+        .char_indices()
+        .nth(SNIPPET_CONTEXT_CHARS * 2)
+        .map_or(flat.len(), |(index, _)| first_start + index);
+    let excerpt = format!(
+        "{}{}{}",
+        if start > 0 { "…" } else { "" },
+        &flat[start..end],
+        if end < flat.len() { "…" } else { "" }
+    );
+    highlight(&excerpt, terms)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::events::{NoteBoardStyle, NoteVisibility};
+
+    fn note(id: &str, title: &str, body: &str, folder: &str, tags: &[&str]) -> NotePayload {
+        NotePayload {
+            id: id.to_string(),
+            author: "gm".to_string(),
+            visibility: NoteVisibility::Public,
+            title: title.to_string(),
+            body: body.to_string(),
+            created_at_ms: 0.0,
+            updated_at_ms: 0.0,
+            board_position: None,
+            board_style: NoteBoardStyle::default(),
+            folder: folder.to_string(),
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            pinned: false,
+            edited_by: String::new(),
+            revisions: Vec::new(),
+        }
+    }
+
+    fn ids(notes: &[NotePayload]) -> Vec<&str> {
+        notes.iter().map(|note| note.id.as_str()).collect()
+    }
+
+    #[test]
+    fn folders_are_normalized_and_nested() {
+        assert_eq!(
+            normalize_folder_path(" /Campaign//  NPCs /"),
+            "Campaign/NPCs"
+        );
+        assert!(folder_contains("Campaign", "Campaign/NPCs"));
+        assert!(!folder_contains("Camp", "Campaign"));
+        assert!(folder_contains("", "anything"));
+
+        let notes = [
+            note("a", "", "x", "Campaign/NPCs", &[]),
+            note("b", "", "x", "Campaign", &[]),
+            note("c", "", "x", "", &[]),
+            note("d", "", "x", "Atlas", &[]),
+        ];
+        let tree = folder_tree(&notes)
+            .into_iter()
+            .map(|node| (node.path, node.depth, node.note_count))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            tree,
+            vec![
+                ("Atlas".to_string(), 0, 1),
+                ("Campaign".to_string(), 0, 2),
+                ("Campaign/NPCs".to_string(), 1, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn tags_are_normalized_and_counted() {
+        assert_eq!(
+            normalize_tags("#Villain, big bad ,villain,, "),
+            vec!["villain".to_string(), "big-bad".to_string()]
+        );
+        let notes = [
+            note("a", "", "x", "", &["villain"]),
+            note("b", "", "x", "", &["villain", "loot"]),
+        ];
+        assert_eq!(
+            tag_counts(&notes),
+            vec![("loot".to_string(), 1), ("villain".to_string(), 2)]
+        );
+    }
+
+    #[test]
+    fn search_needs_every_term_and_ranks_titles_and_pins_first() {
+        let mut pinned = note("pinned", "Misc", "the dragon sleeps", "", &[]);
+        pinned.pinned = true;
+        let notes = [
+            note("body", "Rumors", "A red dragon hoards gold", "", &[]),
+            note("title", "Red Dragon", "lair notes", "Bestiary", &["boss"]),
+            note("other", "Shop", "potions", "", &[]),
+            pinned,
+        ];
+        let filter = |query: &str, folder: &str, tag: Option<&str>| NoteFilter {
+            query: query.to_string(),
+            folder: folder.to_string(),
+            tag: tag.map(str::to_string),
+        };
+
+        assert_eq!(
+            ids(&search_notes(&notes, &filter("DRAGON", "", None))),
+            vec!["pinned", "title", "body"]
+        );
+        assert_eq!(
+            ids(&search_notes(&notes, &filter("red dragon", "", None))),
+            vec!["title", "body"]
+        );
+        assert_eq!(
+            ids(&search_notes(&notes, &filter("dragon #boss", "", None))),
+            vec!["title"]
+        );
+        assert_eq!(
+            ids(&search_notes(&notes, &filter("", "Bestiary", None))),
+            vec!["title"]
+        );
+        assert!(matches_filter(&notes[1], &filter("", "", Some("boss"))));
+        assert!(!matches_filter(&notes[0], &filter("", "", Some("boss"))));
+    }
+
+    #[test]
+    fn highlights_are_case_insensitive_and_merged() {
+        let terms = SearchQuery::parse("drag DRAGON").terms;
+        let segments = highlight("A Dragon!", &terms);
+        assert_eq!(
+            segments,
+            vec![
+                TextSegment {
+                    text: "A ".to_string(),
+                    is_match: false
+                },
+                TextSegment {
+                    text: "Dragon".to_string(),
+                    is_match: true
+                },
+                TextSegment {
+                    text: "!".to_string(),
+                    is_match: false
+                },
+            ]
+        );
+
+        let cyrillic = highlight("Старый ДРАКОН", &SearchQuery::parse("дракон").terms);
+        assert_eq!(cyrillic[1].text, "ДРАКОН");
+        assert!(cyrillic[1].is_match);
+    }
+
+    #[test]
+    fn snippets_cut_around_the_first_match() {
+        let body = format!("{} treasure {}", "word ".repeat(40), "tail ".repeat(40));
+        let terms = SearchQuery::parse("treasure").terms;
+        let snippet = match_snippet(&body, &terms);
+        let text = snippet
+            .iter()
+            .map(|segment| segment.text.as_str())
+            .collect::<String>();
+        assert!(text.starts_with('…') && text.ends_with('…'));
+        assert!(text.chars().count() <= SNIPPET_CONTEXT_CHARS * 3 + 2);
+        assert_eq!(snippet.iter().filter(|segment| segment.is_match).count(), 1);
+        assert!(match_snippet("nothing here", &terms).is_empty());
+    }
+}
@@ -1,30 +1,88 @@
+use super::archive::{
+    MAX_JOURNAL_ARCHIVE_BYTES, MAX_NOTE_BODY_CHARS, journal_archive, link_imported_files,
+    linked_archive_paths, mime_type_for_path, parse_journal_archive,
+};
+use super::collab::{
+    MirrorPiece, NoteEditSession, char_index_from_utf16, mirror_pieces, shift_index, text_change,
+    utf16_offset,
+};
+use super::embeds::{embedded_file_hashes, file_embed_markdown, resolve_file_embeds};
+use super::links::{
+    LinkIndex, WikiTarget, backlinks, journal_graph, journal_notes, outgoing_links,
+};
 use super::model::{
-    BOARD_NOTE_DRAG_MIME, NotesTab, can_delete_note, can_edit_note, note_heading_and_body,
-    recipients_cache_is_stale, render_note_html, sort_notes,
+    BOARD_NOTE_DRAG_MIME, JournalView, NotesTab, can_delete_note, can_edit_note,
+    can_edit_note_body, note_groups, note_heading_and_body, recipients_cache_is_stale,
+    render_linked_note_html, sort_notes, tab_for_visibility,
+};
+use super::revisions::{DiffKind, diff_lines, push_revision, restore_revision};
+use super::search::{
+    SearchQuery, TextSegment, folder_tree, highlight, match_snippet, matches_filter, search_notes,
+    tag_counts,
 };
 use super::view_model::NotesViewModel;
 use crate::components::draggable_window::DraggableWindow;
+use crate::components::scene_board::model::BoardFocus;
 use crate::components::tab_bar::{TabBar, TabItem};
-use crate::components::websocket::{StoredNoteBucket, WsSender, delete_note, save_note};
+use crate::components::websocket::{
+    FileTransferState, StoredNoteBucket, WsSender, blob_to_bytes, bytes_to_blob, delete_note,
+    save_note,
+};
 use crate::config::Theme;
 use crate::i18n::i18n::{t, t_string, use_i18n};
+use crate::utils::zip::{read_zip, write_zip};
 use gloo_timers::future::TimeoutFuture;
+use leptos::html;
 use leptos::prelude::*;
 use leptos::task::spawn_local;
 use leptos::task::spawn_local_scoped;
+use leptos::wasm_bindgen::{JsCast, JsValue};
 use shared::events::PresenceRequestPayload;
-use shared::events::{ClientEvent, NoteDeletePayload, NotePayload, NoteVisibility};
-use web_sys::DragEvent;
+use shared::events::{
+    ClientEvent, NoteCaretPayload, NoteDeletePayload, NotePayload, NoteVisibility, Scene,
+};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+use web_sys::{
+    ClipboardEvent, DragEvent, Element, Event, FileList, HtmlElement, HtmlInputElement, MouseEvent,
+};
 
 const NOTE_DRAG_LABEL_FONT_SIZE: &str = "0.74rem";
 const NOTE_META_FONT_SIZE: &str = "0.78rem";
 const NOTE_BODY_FONT_SIZE: &str = "0.92rem";
+/// Shared by the composer and the caret overlay drawn over it so both wrap alike.
+const NOTE_EDITOR_TEXT_STYLE: &str =
+    "padding: 0.8rem; font-family: inherit; font-size: 0.92rem; line-height: 1.45;";
 const RECIPIENT_REQUEST_TIMEOUT_MS: u32 = 1500;
+const JOURNAL_GRAPH_SIZE: f64 = 100.0;
+const JOURNAL_ARCHIVE_ACCEPT: &str = ".zip,application/zip";
+/// Object URLs of exported archives stay alive until the download has started.
+const DOWNLOAD_URL_LIFETIME_MS: u32 = 10_000;
 
 fn current_time_ms() -> f64 {
     js_sys::Date::now()
 }
 
+/// Saves `bytes` through a temporary download link.
+fn download_bytes(bytes: &[u8], mime_type: &str, file_name: &str) -> Result<(), String> {
+    let blob = bytes_to_blob(bytes, mime_type)?;
+    let url = web_sys::Url::create_object_url_with_blob(&blob)
+        .map_err(|error| format!("Failed to create download URL: {error:?}"))?;
+    let link = document()
+        .create_element("a")
+        .ok()
+        .and_then(|element| element.dyn_into::<HtmlElement>().ok())
+        .ok_or_else(|| "Failed to create download link".to_string())?;
+    let _ = link.set_attribute("href", &url);
+    let _ = link.set_attribute("download", file_name);
+    link.click();
+    spawn_local(async move {
+        TimeoutFuture::new(DOWNLOAD_URL_LIFETIME_MS).await;
+        let _ = web_sys::Url::revoke_object_url(&url);
+    });
+    Ok(())
+}
+
 fn upsert_note(notes: &mut Vec<NotePayload>, note: NotePayload) {
     match notes.iter_mut().find(|existing| existing.id == note.id) {
         Some(existing) => *existing = note,
@@ -46,7 +104,10 @@ fn notes_for_tab(
     match tab {
         NotesTab::Public => public_notes,
         NotesTab::Private => private_notes,
-        NotesTab::Direct => direct_notes,
+        NotesTab::Direct | NotesTab::Group(_) => direct_notes
+            .into_iter()
+            .filter(|note| tab_for_visibility(&note.visibility) == tab)
+            .collect(),
     }
 }
 
@@ -58,26 +119,52 @@ fn note_scope_label(note: &NotePayload, current_username: &str) -> String {
             format!("@{} -> @{}", note.author, recipient)
         }
         NoteVisibility::Direct(_) => format!("@{} -> you", note.author),
+        NoteVisibility::Group(group) => format!("@{} -> {}", note.author, group.label()),
     }
 }
 
+fn highlighted_text(segments: Vec<TextSegment>) -> impl IntoView {
+    segments
+        .into_iter()
+        .map(|segment| {
+            if segment.is_match {
+                view! {
+                    <mark style="background: rgba(250, 204, 21, 0.45); color: inherit; border-radius: 0.2rem; padding: 0 0.1rem;">
+                        {segment.text}
+                    </mark>
+                }
+                .into_any()
+            } else {
+                view! { <span>{segment.text}</span> }.into_any()
+            }
+        })
+        .collect_view()
+}
+
+fn note_element_id(note_id: &str) -> String {
+    format!("note-{note_id}")
+}
+
+/// Wiki link under a click inside rendered note HTML, if any.
+fn wiki_target_from_click(event: &MouseEvent) -> Option<WikiTarget> {
+    let element = event.target()?.dyn_into::<Element>().ok()?;
+    let link = element.closest("a").ok()??;
+    WikiTarget::from_href(&link.get_attribute("href")?)
+}
+
 fn recipient_options(
-    current_recipient: &str,
+    selected_recipients: &[String],
     recipients: &[String],
     current_username: &str,
 ) -> Vec<String> {
     let mut options = recipients
         .iter()
+        .chain(selected_recipients)
         .filter(|username| username.as_str() != current_username)
         .cloned()
         .collect::<Vec<_>>();
     options.sort();
     options.dedup();
-
-    if !current_recipient.is_empty() && !options.iter().any(|option| option == current_recipient) {
-        options.insert(0, current_recipient.to_string());
-    }
-
     options
 }
 
@@ -93,19 +180,97 @@ pub fn NotesWindow(
     #[prop(into)] direct_note_recipients: RwSignal<Vec<String>>,
     #[prop(into)] direct_note_recipients_cache_updated_at_ms: RwSignal<Option<f64>>,
     #[prop(into)] direct_note_recipients_request_id: RwSignal<Option<String>>,
+    #[prop(into)] scenes: Signal<Vec<Scene>>,
+    #[prop(into)] active_scene_id: Signal<Option<String>>,
+    /// Live co-editing session for the public note open in the composer.
+    #[prop(into)]
+    note_edit_session: RwSignal<Option<NoteEditSession>>,
     #[prop(into, optional)] is_active: Signal<bool>,
     #[prop(optional)] on_focus: Option<Callback<()>>,
+    /// Called when an `@token` or `#scene` reference is clicked.
+    #[prop(optional)]
+    on_focus_board: Option<Callback<BoardFocus>>,
+    file_transfer: FileTransferState,
     theme: Theme,
 ) -> impl IntoView {
     let i18n = use_i18n();
     let vm = NotesViewModel::new();
+    let file_urls = file_transfer.file_urls;
+    let file_transfer = StoredValue::new(file_transfer);
 
-    let tabs = move || {
+    let journal = Memo::new(move |_| {
+        journal_notes(&[
+            public_notes.get().as_slice(),
+            private_notes.get().as_slice(),
+            direct_notes.get().as_slice(),
+        ])
+    });
+    let link_index = Memo::new(move |_| {
+        LinkIndex::new(
+            &journal.get(),
+            &scenes.get(),
+            active_scene_id.get().as_deref(),
+        )
+    });
+    let incoming_links = Memo::new(move |_| backlinks(&journal.get()));
+
+    let journal_views = move || {
         vec![
+            TabItem::new(JournalView::Notes, t_string!(i18n, notes.view_notes)),
+            TabItem::new(JournalView::Index, t_string!(i18n, notes.view_index)),
+            TabItem::new(JournalView::Graph, t_string!(i18n, notes.view_graph)),
+        ]
+    };
+
+    let follow_link = move |target: WikiTarget| match target {
+        WikiTarget::Note(note_id) => {
+            let Some(note) = journal
+                .get_untracked()
+                .into_iter()
+                .find(|note| note.id == note_id)
+            else {
+                return;
+            };
+            vm.open_note(&note);
+            spawn_local_scoped(async move {
+                TimeoutFuture::new(0).await;
+                if let Some(element) = document().get_element_by_id(&note_element_id(&note_id)) {
+                    element.scroll_into_view();
+                }
+            });
+        }
+        WikiTarget::NewNote(title) => vm.start_linked_note(&title),
+        WikiTarget::Token { scene_id, token_id } => {
+            if let Some(on_focus_board) = on_focus_board {
+                on_focus_board.run(BoardFocus::Token { scene_id, token_id });
+            }
+        }
+        WikiTarget::Scene(scene_id) => {
+            if let Some(on_focus_board) = on_focus_board {
+                on_focus_board.run(BoardFocus::Scene(scene_id));
+            }
+        }
+    };
+    let follow_link = StoredValue::new(follow_link);
+
+    let tabs = move || {
+        let mut groups = note_groups(&direct_notes.get());
+        // A group whose last note was just deleted keeps its tab while it is open.
+        if let NotesTab::Group(active_group) = vm.active_tab.get()
+            && !groups.contains(&active_group)
+        {
+            groups.push(active_group);
+        }
+        let mut tabs = vec![
             TabItem::new(NotesTab::Public, t_string!(i18n, notes.tab_public)),
             TabItem::new(NotesTab::Private, t_string!(i18n, notes.tab_private)),
             TabItem::new(NotesTab::Direct, t_string!(i18n, notes.tab_direct)),
-        ]
+        ];
+        tabs.extend(groups.into_iter().map(|group| {
+            let label = format!("👥 {}", group.label());
+            TabItem::new(NotesTab::Group(group), label)
+        }));
+        tabs
     };
 
     let refresh_recipients = {
@@ -170,17 +335,6 @@ pub fn NotesWindow(
             return;
         }
 
-        let options = recipient_options(
-            &vm.recipient.get_untracked(),
-            &recipients,
-            &username.get_untracked(),
-        );
-        if vm.recipient.get_untracked().is_empty()
-            && let Some(first) = options.first()
-        {
-            vm.recipient.set(first.clone());
-        }
-
         if !vm.is_loading_recipients.get_untracked() {
             return;
         }
@@ -209,16 +363,22 @@ pub fn NotesWindow(
             .as_ref()
             .map(|note| note.board_style.clone())
             .unwrap_or_default();
-        let note = match vm.build_note(&current_user, board_position, board_style) {
+        let mut note = match vm.build_note(&current_user, board_position, board_style) {
             Ok(note) => note,
             Err(error) => {
                 vm.error_message.set(Some(error));
                 return;
             }
         };
+        if let Some(previous) = existing_note.as_ref() {
+            // Collaborators save public notes under the owner's name.
+            note.author = previous.author.clone();
+            push_revision(&mut note, previous);
+        }
 
+        let note_tab = tab_for_visibility(&note.visibility);
         match &note.visibility {
-            NoteVisibility::Public | NoteVisibility::Direct(_) => {
+            NoteVisibility::Public | NoteVisibility::Direct(_) | NoteVisibility::Group(_) => {
                 let Some(sender) = ws_sender.get_untracked() else {
                     vm.error_message
                         .set(Some("WebSocket connection is not available".to_string()));
@@ -244,6 +404,8 @@ pub fn NotesWindow(
         }
 
         vm.reset_form();
+        // A note for a new group opens that group's tab.
+        vm.active_tab.set(note_tab);
     };
 
     let delete_note_action = move |note: NotePayload| {
@@ -263,7 +425,7 @@ pub fn NotesWindow(
                         .set(Some("WebSocket connection is not available".to_string()));
                 }
             }
-            NoteVisibility::Direct(_) => {
+            NoteVisibility::Direct(_) | NoteVisibility::Group(_) => {
                 if note.author == current_user {
                     if let Some(sender) = ws_sender.get_untracked() {
                         let _ = sender.try_send_event(ClientEvent::NoteDelete(NoteDeletePayload {
@@ -310,14 +472,11 @@ pub fn NotesWindow(
     };
     let delete_note_action = StoredValue::new(delete_note_action);
 
-    let unpin_note_action = move |note: NotePayload| {
-        let mut updated = note.clone();
-        updated.board_position = None;
-        updated.updated_at_ms = js_sys::Date::now();
+    let resave_note = move |updated: NotePayload| {
         let current_room = room_id.get_untracked();
         let current_user = username.get_untracked();
         match updated.visibility.clone() {
-            NoteVisibility::Public | NoteVisibility::Direct(_) => {
+            NoteVisibility::Public | NoteVisibility::Direct(_) | NoteVisibility::Group(_) => {
                 if let Some(sender) = ws_sender.get_untracked() {
                     let _ = sender.try_send_event(ClientEvent::NoteUpsert(updated));
                 } else {
@@ -339,8 +498,380 @@ pub fn NotesWindow(
             }
         }
     };
+
+    let unpin_note_action = move |note: NotePayload| {
+        let mut updated = note;
+        updated.board_position = None;
+        updated.updated_at_ms = js_sys::Date::now();
+        resave_note(updated);
+    };
     let unpin_note_action = StoredValue::new(unpin_note_action);
 
+    let toggle_pin_action = move |note: NotePayload| {
+        let mut updated = note;
+        updated.pinned = !updated.pinned;
+        updated.updated_at_ms = js_sys::Date::now();
+        resave_note(updated);
+    };
+    let toggle_pin_action = StoredValue::new(toggle_pin_action);
+
+    let restore_revision_action = move |note: NotePayload, index: usize| {
+        let Some(restored) =
+            restore_revision(&note, index, &username.get_untracked(), js_sys::Date::now())
+        else {
+            return;
+        };
+        vm.history_note_id.set(None);
+        vm.history_revision.set(None);
+        resave_note(restored);
+    };
+    let restore_revision_action = StoredValue::new(restore_revision_action);
+
+    // Journal archives: every visible note as Markdown plus the files it embeds.
+    let export_journal = move || {
+        let exported_message = t_string!(i18n, notes.journal_exported).to_string();
+        let notes = journal.get_untracked();
+        let file_name = format!("journal-{}.zip", room_id.get_untracked());
+        let file_transfer = file_transfer.get_value();
+        vm.is_archiving.set(true);
+        vm.archive_status.set(None);
+        spawn_local(async move {
+            let exported = async {
+                let mut files = HashMap::new();
+                for hash in notes
+                    .iter()
+                    .flat_map(|note| embedded_file_hashes(&note.body))
+                {
+                    if files.contains_key(&hash) {
+                        continue;
+                    }
+                    let Some(file) = file_transfer.file_ref(&hash) else {
+                        continue;
+                    };
+                    if let Some(bytes) = file_transfer.load_local_bytes(&hash).await? {
+                        files.insert(hash, (file, bytes));
+                    }
+                }
+                let archive = write_zip(&journal_archive(&notes, &files))?;
+                download_bytes(&archive, "application/zip", &file_name)
+            }
+            .await;
+            vm.is_archiving.set(false);
+            vm.archive_status.set(Some(match exported {
+                Ok(()) => exported_message,
+                Err(error) => error,
+            }));
+        });
+    };
+
+    let import_journal = move |event: Event| {
+        let Some(input) = event
+            .target()
+            .and_then(|target| target.dyn_into::<HtmlInputElement>().ok())
+        else {
+            return;
+        };
+        let Some(file) = input.files().and_then(|files| files.get(0)) else {
+            return;
+        };
+        input.set_value("");
+        let current_user = username.get_untracked();
+        let existing_notes = journal.get_untracked();
+        let scene_ids = scenes
+            .get_untracked()
+            .into_iter()
+            .map(|scene| scene.id)
+            .collect::<HashSet<_>>();
+        let imported_label = t_string!(i18n, notes.journal_imported).to_string();
+        let skipped_label = t_string!(i18n, notes.journal_skipped).to_string();
+        let file_transfer = file_transfer.get_value();
+        vm.is_archiving.set(true);
+        vm.archive_status.set(None);
+        spawn_local(async move {
+            let parsed = async {
+                let bytes = blob_to_bytes(&file).await?;
+                let entries = read_zip(&bytes, MAX_JOURNAL_ARCHIVE_BYTES)?;
+                let (notes, skipped) =
+                    parse_journal_archive(&entries, &current_user, current_time_ms(), || {
+                        Uuid::new_v4().to_string()
+                    });
+                // Linked images and PDFs become room files the notes embed.
+                let mut hashes_by_path = HashMap::new();
+                for path in notes
+                    .iter()
+                    .flat_map(|imported| linked_archive_paths(&imported.path, &imported.note.body))
+                {
+                    if hashes_by_path.contains_key(&path) {
+                        continue;
+                    }
+                    let Some(mime_type) = mime_type_for_path(&path) else {
+                        continue;
+                    };
+                    let Some(entry) = entries.iter().find(|entry| entry.name == path) else {
+                        continue;
+                    };
+                    let file_name = path.rsplit('/').next().unwrap_or(&path).to_string();
+                    let file_ref = file_transfer
+                        .import_bytes(
+                            &entry.data,
+                            file_name,
+                            mime_type,
+                            current_user.clone(),
+                            ws_sender.get_untracked(),
+                        )
+                        .await?;
+                    hashes_by_path.insert(path, file_ref.hash);
+                }
+                Ok::<_, String>((notes, skipped, hashes_by_path))
+            }
+            .await;
+            vm.is_archiving.set(false);
+            let (notes, mut skipped, hashes_by_path) = match parsed {
+                Ok(parsed) => parsed,
+                Err(error) => {
+                    vm.archive_status.set(Some(error));
+                    return;
+                }
+            };
+            let mut imported = 0;
+            for imported_note in notes {
+                let mut note = imported_note.note;
+                note.body = link_imported_files(&imported_note.path, &note.body, &hashes_by_path);
+                if note.body.chars().count() > MAX_NOTE_BODY_CHARS {
+                    skipped += 1;
+                    continue;
+                }
+                // Someone else's note with the same id stays theirs.
+                if existing_notes
+                    .iter()
+                    .any(|existing| existing.id == note.id && existing.author != note.author)
+                {
+                    note.id = Uuid::new_v4().to_string();
+                }
+                if let Some(position) = note.board_position.as_mut()
+                    && position
+                        .anchor
+                        .as_ref()
+                        .is_some_and(|anchor| !scene_ids.contains(&anchor.scene_id))
+                {
+                    position.anchor = None;
+                }
+                resave_note(note);
+                imported += 1;
+            }
+            vm.archive_status.set(Some(format!(
+                "{imported_label} {imported}, {skipped_label} {skipped}"
+            )));
+        });
+    };
+
+    // Co-editing: public notes open in the composer share one RGA document with
+    // everyone else editing them; see `collab.rs`.
+    let edit_site = StoredValue::new(Uuid::new_v4().simple().to_string());
+    let body_ref = NodeRef::<html::Textarea>::new();
+    let mirror_ref = NodeRef::<html::Div>::new();
+    let last_caret = StoredValue::new(Option::<NoteCaretPayload>::None);
+
+    let send_note_event = move |event: ClientEvent| {
+        if let Some(sender) = ws_sender.get_untracked() {
+            let _ = sender.try_send_event(event);
+        }
+    };
+
+    let local_caret_index = move || {
+        body_ref
+            .get_untracked()
+            .and_then(|textarea| textarea.selection_start().ok().flatten())
+            .map(|offset| char_index_from_utf16(&vm.body.get_untracked(), offset as usize))
+            .unwrap_or(0)
+    };
+
+    let broadcast_caret = move |editing: bool| {
+        let Some(payload) = note_edit_session.with_untracked(|session| {
+            session.as_ref().map(|session| {
+                session.caret_payload(&username.get_untracked(), local_caret_index(), editing)
+            })
+        }) else {
+            return;
+        };
+        if last_caret.with_value(|last| last.as_ref() == Some(&payload)) {
+            return;
+        }
+        last_caret.set_value(Some(payload.clone()));
+        send_note_event(ClientEvent::NoteCaret(payload));
+    };
+
+    Effect::new(move |_| {
+        let target_id = vm
+            .editing_note_id
+            .get()
+            .filter(|_| vm.active_tab.get() == NotesTab::Public);
+        let current_id = note_edit_session
+            .with_untracked(|session| session.as_ref().map(|session| session.note_id.clone()));
+        if current_id == target_id {
+            return;
+        }
+        if current_id.is_some() {
+            broadcast_caret(false);
+        }
+        let session = target_id
+            .and_then(|note_id| {
+                public_notes
+                    .get_untracked()
+                    .into_iter()
+                    .find(|note| note.id == note_id)
+            })
+            .map(|note| {
+                NoteEditSession::start(
+                    &note.id,
+                    note.updated_at_ms,
+                    &edit_site.get_value(),
+                    &note.body,
+                )
+            });
+        let joined = session.is_some();
+        note_edit_session.set(session);
+        last_caret.set_value(None);
+        if joined {
+            broadcast_caret(true);
+        }
+    });
+
+    let on_body_input = move |value: String| {
+        vm.body.set(value.clone());
+        if note_edit_session.with_untracked(Option::is_none) {
+            return;
+        }
+        let current_user = username.get_untracked();
+        let mut payloads = Vec::new();
+        note_edit_session.update(|session| {
+            if let Some(session) = session {
+                let ops = session.edit(&value);
+                payloads = session.edit_payloads(&current_user, ops, false);
+            }
+        });
+        for payload in payloads {
+            send_note_event(ClientEvent::NoteEdit(payload));
+        }
+        broadcast_caret(true);
+    };
+
+    // Embedded files are fetched from peers as soon as a note references them.
+    Effect::new(move |_| {
+        let hashes = journal.with(|notes| {
+            notes
+                .iter()
+                .flat_map(|note| embedded_file_hashes(&note.body))
+                .collect::<Vec<_>>()
+        });
+        let missing = file_urls.with(|urls| {
+            hashes
+                .into_iter()
+                .filter(|hash| !urls.contains_key(hash))
+                .collect::<Vec<_>>()
+        });
+        for hash in missing {
+            file_transfer.with_value(|file_transfer| {
+                file_transfer.request_file_by_hash(
+                    &hash,
+                    username.get_untracked(),
+                    ws_sender.get_untracked(),
+                )
+            });
+        }
+    });
+
+    // Dropped or pasted files are shared with the room and linked at the caret.
+    let embed_files = move |files: FileList| {
+        for index in 0..files.length() {
+            let Some(file) = files.get(index) else {
+                continue;
+            };
+            let file_transfer = file_transfer.get_value();
+            spawn_local(async move {
+                match file_transfer
+                    .import_browser_file(file, username.get_untracked(), ws_sender.get_untracked())
+                    .await
+                {
+                    Ok(file_ref) => {
+                        let embed = file_embed_markdown(
+                            &file_ref.file_name,
+                            &file_ref.hash,
+                            file_ref.mime_type.starts_with("image/"),
+                        );
+                        let body = vm.body.get_untracked();
+                        let caret = local_caret_index().min(body.chars().count());
+                        let split = body
+                            .char_indices()
+                            .nth(caret)
+                            .map(|(offset, _)| offset)
+                            .unwrap_or(body.len());
+                        let (before, after) = body.split_at(split);
+                        let separator = if before.is_empty() || before.ends_with('\n') {
+                            ""
+                        } else {
+                            "\n"
+                        };
+                        on_body_input(format!("{before}{separator}{embed}\n{after}"));
+                    }
+                    Err(error) => vm.error_message.set(Some(error)),
+                }
+            });
+        }
+    };
+
+    // Remote edits rewrite the composer while keeping the local caret in place.
+    Effect::new(move |_| {
+        let Some(text) =
+            note_edit_session.with(|session| session.as_ref().map(|session| session.doc.text()))
+        else {
+            return;
+        };
+        let previous = vm.body.get_untracked();
+        let Some((start, removed, inserted)) = text_change(&previous, &text) else {
+            return;
+        };
+        let caret = shift_index(
+            local_caret_index(),
+            start,
+            removed,
+            inserted.chars().count(),
+        );
+        vm.body.set(text.clone());
+        let Some(textarea) = body_ref.get_untracked() else {
+            return;
+        };
+        let textarea_element: &Element = textarea.as_ref();
+        if document().active_element().as_ref() == Some(textarea_element) {
+            textarea.set_value(&text);
+            let offset = utf16_offset(&text, caret) as u32;
+            let _ = textarea.set_selection_range(offset, offset);
+        }
+    });
+
+    // Without a search query the list shows the active tab; a query searches every bucket.
+    let visible_notes = move || {
+        let filter = vm.filter();
+        if SearchQuery::parse(&filter.query).is_empty() {
+            let mut notes = notes_for_tab(
+                vm.active_tab.get(),
+                public_notes.get(),
+                private_notes.get(),
+                direct_notes.get(),
+            );
+            notes.retain(|note| matches_filter(note, &filter));
+            sort_notes(&mut notes);
+            notes
+        } else {
+            search_notes(&journal.get(), &filter)
+        }
+    };
+    let filters_active = move || {
+        !vm.search_query.get().trim().is_empty()
+            || !vm.folder_filter.get().is_empty()
+            || vm.tag_filter.get().is_some()
+    };
+
     view! {
         <DraggableWindow
             is_open=is_open
@@ -357,57 +888,113 @@ pub fn NotesWindow(
         >
             <div style="display: flex; flex-direction: column; height: 100%;">
                 <TabBar
-                    tabs=tabs
-                    active_tab=vm.active_tab
+                    tabs=journal_views
+                    active_tab=vm.journal_view
                     theme=theme.clone()
                 />
+                <div style=format!(
+                    "display: flex; flex-wrap: wrap; gap: 0.5rem; align-items: center; padding: 0.45rem 1rem; font-size: {}; color: {};",
+                    NOTE_META_FONT_SIZE, theme.ui_text_muted
+                )>
+                    <button
+                        on:click=move |_| export_journal()
+                        disabled=move || vm.is_archiving.get()
+                        style=format!(
+                            "padding: 0.35rem 0.7rem; background: {}; color: {}; border: 0.0625rem solid {}; border-radius: 0.45rem; cursor: pointer;",
+                            theme.ui_bg_secondary, theme.ui_text_primary, theme.ui_border
+                        )
+                    >
+                        {move || t!(i18n, notes.export_journal_button)}
+                    </button>
+                    <label style=format!(
+                        "padding: 0.35rem 0.7rem; background: {}; color: {}; border: 0.0625rem solid {}; border-radius: 0.45rem; cursor: pointer;",
+                        theme.ui_bg_secondary, theme.ui_text_primary, theme.ui_border
+                    )>
+                        {move || t!(i18n, notes.import_journal_button)}
+                        <input
+                            type="file"
+                            accept=JOURNAL_ARCHIVE_ACCEPT
+                            on:change=import_journal
+                            disabled=move || vm.is_archiving.get()
+                            style="display: none;"
+                        />
+                    </label>
+                    {move || if vm.is_archiving.get() {
+                        Some("…".to_string())
+                    } else {
+                        vm.archive_status.get()
+                    }}
+                </div>
+                <div style=move || if vm.journal_view.get() == JournalView::Notes { "display: block;" } else { "display: none;" }>
+                    <TabBar
+                        tabs=tabs
+                        active_tab=vm.active_tab
+                        theme=theme.clone()
+                    />
+                </div>
 
-                <div style="display: flex; flex-direction: column; gap: 0.9rem; padding: 0.5rem 1rem 0.75rem 1rem; border-bottom: 0.0625rem solid rgba(255,255,255,0.08);">
+                <div style=move || format!(
+                    "display: {}; flex-direction: column; gap: 0.9rem; padding: 0.5rem 1rem 0.75rem 1rem; border-bottom: 0.0625rem solid rgba(255,255,255,0.08);",
+                    if vm.journal_view.get() == JournalView::Notes { "flex" } else { "none" }
+                )>
                     {move || if vm.active_tab.get() == NotesTab::Direct {
                         view! {
                             <div style="display: flex; flex-direction: column; gap: 0.45rem;">
-                                <div style="display: flex; gap: 0.6rem; align-items: center;">
-                                    <select
-                                        prop:value=move || vm.recipient.get()
-                                        on:change=move |ev| vm.recipient.set(event_target_value(&ev))
-                                        style=format!(
-                                            "flex: 1; min-width: 0; padding: 0.7rem 0.8rem; background: {}; color: {}; border: 0.0625rem solid {}; border-radius: 0.5rem; box-sizing: border-box;",
-                                            theme.ui_bg_secondary, theme.ui_text_primary, theme.ui_border
-                                        )
-                                    >
-                                        <option value="">{move || t!(i18n, notes.recipient_placeholder)}</option>
-                                        <For
-                                            each=move || recipient_options(
-                                                &vm.recipient.get(),
-                                                &direct_note_recipients.get(),
-                                                &username.get(),
-                                            )
-                                            key=|recipient| recipient.clone()
-                                            children=move |recipient| {
-                                                let label = recipient.clone();
-                                                view! {
-                                                    <option value=recipient>{label}</option>
-                                                }
-                                            }
-                                        />
-                                    </select>
+                                <div style="display: flex; flex-wrap: wrap; gap: 0.4rem 0.75rem; align-items: center;">
+                                    <span style=format!("color: {}; font-size: 0.78rem;", theme.ui_text_secondary)>
+                                        {t!(i18n, notes.recipient_placeholder)}
+                                    </span>
+                                    {move || recipient_options(
+                                        &vm.recipients.get(),
+                                        &direct_note_recipients.get(),
+                                        &username.get(),
+                                    )
+                                    .into_iter()
+                                    .map(|recipient| {
+                                        let checked_recipient = recipient.clone();
+                                        let toggled_recipient = recipient.clone();
+                                        view! {
+                                            <label style="display: inline-flex; align-items: center; gap: 0.25rem;">
+                                                <input
+                                                    type="checkbox"
+                                                    prop:checked=move || vm.recipients.with(|recipients| recipients.contains(&checked_recipient))
+                                                    on:change=move |_| vm.toggle_recipient(&toggled_recipient)
+                                                />
+                                                {recipient}
+                                            </label>
+                                        }
+                                    })
+                                    .collect_view()}
                                     <button
                                         on:click=move |_| refresh_recipients.with_value(|refresh| refresh())
                                         style=format!(
-                                            "padding: 0.7rem 0.9rem; background: {}; color: {}; border: none; border-radius: 0.5rem; cursor: pointer; white-space: nowrap;",
+                                            "margin-left: auto; padding: 0.45rem 0.7rem; background: {}; color: {}; border: none; border-radius: 0.45rem; cursor: pointer; white-space: nowrap;",
                                             theme.ui_bg_secondary, theme.ui_text_primary
                                         )
                                     >
                                         {t!(i18n, notes.refresh_recipients_button)}
                                     </button>
                                 </div>
+                                {move || (vm.recipients.with(Vec::len) > 1).then(|| view! {
+                                    <input
+                                        type="text"
+                                        maxlength="60"
+                                        prop:value=move || vm.group_name.get()
+                                        on:input=move |ev| vm.group_name.set(event_target_value(&ev))
+                                        placeholder=move || t_string!(i18n, notes.group_name_placeholder)
+                                        style=format!(
+                                            "padding: 0.55rem 0.8rem; background: {}; color: {}; border: 0.0625rem solid {}; border-radius: 0.5rem; box-sizing: border-box;",
+                                            theme.ui_bg_secondary, theme.ui_text_primary, theme.ui_border
+                                        )
+                                    />
+                                })}
 
                                 <div style=format!("display: flex; justify-content: space-between; gap: 0.75rem; color: {}; font-size: 0.78rem;", theme.ui_text_secondary)>
                                     <span>
                                         {move || if vm.is_loading_recipients.get() {
                                             t_string!(i18n, notes.recipients_loading).to_string()
                                         } else if recipient_options(
-                                            &vm.recipient.get(),
+                                            &vm.recipients.get(),
                                             &direct_note_recipients.get(),
                                             &username.get(),
                                         ).is_empty() {
@@ -416,7 +1003,7 @@ pub fn NotesWindow(
                                             format!(
                                                 "{} {}",
                                                 recipient_options(
-                                                    &vm.recipient.get(),
+                                                    &vm.recipients.get(),
                                                     &direct_note_recipients.get(),
                                                     &username.get(),
                                                 )
@@ -445,15 +1032,140 @@ pub fn NotesWindow(
                         ().into_any()
                     }}
 
-                    <textarea
-                        prop:value=move || vm.body.get()
-                        on:input=move |ev| vm.body.set(event_target_value(&ev))
-                        placeholder=move || t_string!(i18n, notes.body_placeholder)
-                        style=format!(
-                            "width: 100%; min-height: 8rem; resize: vertical; padding: 0.8rem; background: {}; color: {}; border: 0.0625rem solid {}; border-radius: 0.5rem; box-sizing: border-box; font-family: inherit;",
-                            theme.ui_bg_secondary, theme.ui_text_primary, theme.ui_border
-                        )
-                    ></textarea>
+                    <div style="position: relative;">
+                        <textarea
+                            node_ref=body_ref
+                            prop:value=move || vm.body.get()
+                            on:input=move |ev| on_body_input(event_target_value(&ev))
+                            on:dragover=move |ev: DragEvent| {
+                                let has_files = ev
+                                    .data_transfer()
+                                    .is_some_and(|data| data.types().includes(&JsValue::from_str("Files"), 0));
+                                if has_files {
+                                    ev.prevent_default();
+                                }
+                            }
+                            on:drop=move |ev: DragEvent| {
+                                if let Some(files) = ev.data_transfer().and_then(|data| data.files()).filter(|files| files.length() > 0) {
+                                    ev.prevent_default();
+                                    embed_files(files);
+                                }
+                            }
+                            on:paste=move |ev: ClipboardEvent| {
+                                if let Some(files) = ev.clipboard_data().and_then(|data| data.files()).filter(|files| files.length() > 0) {
+                                    ev.prevent_default();
+                                    embed_files(files);
+                                }
+                            }
+                            on:keyup=move |_| broadcast_caret(true)
+                            on:click=move |_| broadcast_caret(true)
+                            on:scroll=move |_| {
+                                if let (Some(textarea), Some(mirror)) = (body_ref.get_untracked(), mirror_ref.get_untracked()) {
+                                    mirror.set_scroll_top(textarea.scroll_top());
+                                }
+                            }
+                            placeholder=move || t_string!(i18n, notes.body_placeholder)
+                            style=format!(
+                                "display: block; width: 100%; min-height: 8rem; resize: vertical; background: {}; color: {}; border: 0.0625rem solid {}; border-radius: 0.5rem; box-sizing: border-box; {}",
+                                theme.ui_bg_secondary, theme.ui_text_primary, theme.ui_border, NOTE_EDITOR_TEXT_STYLE
+                            )
+                        ></textarea>
+                        {move || {
+                            let carets = note_edit_session.with(|session| {
+                                session.as_ref().map(NoteEditSession::caret_positions).unwrap_or_default()
+                            });
+                            if carets.is_empty() {
+                                return ().into_any();
+                            }
+                            let caret_color = theme.other_cursor_color;
+                            view! {
+                                <div
+                                    node_ref=mirror_ref
+                                    aria-hidden="true"
+                                    style=format!(
+                                        "position: absolute; inset: 0; overflow: hidden; pointer-events: none; white-space: pre-wrap; overflow-wrap: break-word; color: transparent; border: 0.0625rem solid transparent; box-sizing: border-box; {}",
+                                        NOTE_EDITOR_TEXT_STYLE
+                                    )
+                                >
+                                    {mirror_pieces(&vm.body.get(), &carets)
+                                        .into_iter()
+                                        .map(|piece| match piece {
+                                            MirrorPiece::Text(text) => view! { <span>{text}</span> }.into_any(),
+                                            MirrorPiece::Caret(name) => view! {
+                                                <span style=format!("position: relative; border-left: 0.125rem solid {}; margin: 0 -0.0625rem;", caret_color)>
+                                                    <span style=format!(
+                                                        "position: absolute; left: -0.125rem; bottom: 100%; padding: 0 0.25rem; background: {}; color: #fff; font-size: 0.65rem; line-height: 1.3; border-radius: 0.2rem; white-space: nowrap;",
+                                                        caret_color
+                                                    )>
+                                                        {name}
+                                                    </span>
+                                                </span>
+                                            }.into_any(),
+                                        })
+                                        .collect_view()}
+                                </div>
+                            }.into_any()
+                        }}
+                    </div>
+                    {move || {
+                        let editors = note_edit_session.with(|session| {
+                            session
+                                .as_ref()
+                                .map(|session| {
+                                    let mut names = session.carets.keys().cloned().collect::<Vec<_>>();
+                                    names.sort();
+                                    names
+                                })
+                                .unwrap_or_default()
+                        });
+                        (!editors.is_empty()).then(|| view! {
+                            <div style=format!("color: {}; font-size: {};", theme.ui_text_secondary, NOTE_META_FONT_SIZE)>
+                                {format!("✎ {} {}", t_string!(i18n, notes.co_editing_with), editors.join(", "))}
+                            </div>
+                        })
+                    }}
+
+                    <div style="display: flex; gap: 0.6rem; align-items: center;">
+                        <input
+                            type="text"
+                            list="notes-folder-options"
+                            prop:value=move || vm.folder.get()
+                            on:input=move |ev| vm.folder.set(event_target_value(&ev))
+                            placeholder=move || t_string!(i18n, notes.folder_placeholder)
+                            style=format!(
+                                "flex: 1; min-width: 0; padding: 0.55rem 0.7rem; background: {}; color: {}; border: 0.0625rem solid {}; border-radius: 0.5rem; box-sizing: border-box;",
+                                theme.ui_bg_secondary, theme.ui_text_primary, theme.ui_border
+                            )
+                        />
+                        <datalist id="notes-folder-options">
+                            {move || folder_tree(&journal.get())
+                                .into_iter()
+                                .map(|folder| view! { <option value=folder.path></option> })
+                                .collect_view()}
+                        </datalist>
+                        <input
+                            type="text"
+                            prop:value=move || vm.tags.get()
+                            on:input=move |ev| vm.tags.set(event_target_value(&ev))
+                            placeholder=move || t_string!(i18n, notes.tags_placeholder)
+                            style=format!(
+                                "flex: 1; min-width: 0; padding: 0.55rem 0.7rem; background: {}; color: {}; border: 0.0625rem solid {}; border-radius: 0.5rem; box-sizing: border-box;",
+                                theme.ui_bg_secondary, theme.ui_text_primary, theme.ui_border
+                            )
+                        />
+                        <label style=format!("display: flex; align-items: center; gap: 0.3rem; color: {}; font-size: {}; white-space: nowrap; cursor: pointer;", theme.ui_text_secondary, NOTE_META_FONT_SIZE)>
+                            <input
+                                type="checkbox"
+                                prop:checked=move || vm.pinned.get()
+                                on:change=move |ev| vm.pinned.set(event_target_checked(&ev))
+                            />
+                            {t!(i18n, notes.pinned_label)}
+                        </label>
+                    </div>
+
+                    <div style=format!("color: {}; font-size: {};", theme.ui_text_muted, NOTE_META_FONT_SIZE)>
+                        {t!(i18n, notes.link_hint)}
+                    </div>
 
                     {move || vm.error_message.get().map(|error| view! {
                         <div style=format!("color: {}; font-size: 0.84rem;", theme.ui_button_danger)>{error}</div>
@@ -489,65 +1201,204 @@ pub fn NotesWindow(
                     </div>
                 </div>
 
-                <div style="flex: 1; overflow-y: auto; padding: 0.9rem 1rem 3rem 1rem; display: flex; flex-direction: column; gap: 0.9rem;">
+                <div style=move || format!(
+                    "display: {}; gap: 0.5rem; align-items: center; padding: 0.6rem 1rem; border-bottom: 0.0625rem solid rgba(255,255,255,0.08);",
+                    if vm.journal_view.get() == JournalView::Notes { "flex" } else { "none" }
+                )>
+                    <input
+                        type="search"
+                        prop:value=move || vm.search_query.get()
+                        on:input=move |ev| vm.search_query.set(event_target_value(&ev))
+                        placeholder=move || t_string!(i18n, notes.search_placeholder)
+                        style=format!(
+                            "flex: 2; min-width: 0; padding: 0.5rem 0.7rem; background: {}; color: {}; border: 0.0625rem solid {}; border-radius: 0.5rem; box-sizing: border-box;",
+                            theme.ui_bg_secondary, theme.ui_text_primary, theme.ui_border
+                        )
+                    />
+                    <select
+                        prop:value=move || vm.folder_filter.get()
+                        on:change=move |ev| vm.folder_filter.set(event_target_value(&ev))
+                        style=format!(
+                            "flex: 1; min-width: 0; padding: 0.5rem; background: {}; color: {}; border: 0.0625rem solid {}; border-radius: 0.5rem;",
+                            theme.ui_bg_secondary, theme.ui_text_primary, theme.ui_border
+                        )
+                    >
+                        <option value="">{move || t!(i18n, notes.all_folders)}</option>
+                        {move || folder_tree(&journal.get())
+                            .into_iter()
+                            .map(|folder| {
+                                let label = format!(
+                                    "{}{} ({})",
+                                    "\u{a0}\u{a0}".repeat(folder.depth),
+                                    folder.name,
+                                    folder.note_count
+                                );
+                                view! { <option value=folder.path>{label}</option> }
+                            })
+                            .collect_view()}
+                    </select>
+                    <select
+                        prop:value=move || vm.tag_filter.get().unwrap_or_default()
+                        on:change=move |ev| {
+                            let tag = event_target_value(&ev);
+                            vm.tag_filter.set((!tag.is_empty()).then_some(tag));
+                        }
+                        style=format!(
+                            "flex: 1; min-width: 0; padding: 0.5rem; background: {}; color: {}; border: 0.0625rem solid {}; border-radius: 0.5rem;",
+                            theme.ui_bg_secondary, theme.ui_text_primary, theme.ui_border
+                        )
+                    >
+                        <option value="">{move || t!(i18n, notes.all_tags)}</option>
+                        {move || tag_counts(&journal.get())
+                            .into_iter()
+                            .map(|(tag, count)| {
+                                let label = format!("#{tag} ({count})");
+                                view! { <option value=tag>{label}</option> }
+                            })
+                            .collect_view()}
+                    </select>
+                    <Show when=filters_active>
+                        <button
+                            on:click=move |_| vm.clear_filters()
+                            title=move || t_string!(i18n, notes.clear_filters)
+                            style=format!(
+                                "padding: 0.5rem 0.65rem; background: {}; color: {}; border: none; border-radius: 0.5rem; cursor: pointer;",
+                                theme.ui_bg_secondary, theme.ui_text_primary
+                            )
+                        >
+                            "✕"
+                        </button>
+                    </Show>
+                </div>
+
+                <div style=move || format!(
+                    "flex: 1; overflow-y: auto; padding: 0.9rem 1rem 3rem 1rem; display: {}; flex-direction: column; gap: 0.9rem;",
+                    if vm.journal_view.get() == JournalView::Notes { "flex" } else { "none" }
+                )>
                     {move || {
-                        let tab_notes = notes_for_tab(
-                            vm.active_tab.get(),
-                            public_notes.get(),
-                            private_notes.get(),
-                            direct_notes.get(),
-                        );
+                        let listed_notes = visible_notes();
                         let current_username = username.get();
-                        if tab_notes.is_empty() {
+                        if listed_notes.is_empty() {
                             return view! {
                                 <div style=format!(
                                     "padding: 2rem 1rem; text-align: center; color: {}; font-style: italic;",
                                     theme.ui_text_muted
                                 )>
-                                    {t!(i18n, notes.empty_state)}
+                                    {if filters_active() {
+                                        t_string!(i18n, notes.no_matches).to_string()
+                                    } else {
+                                        t_string!(i18n, notes.empty_state).to_string()
+                                    }}
                                 </div>
                             }.into_any();
                         }
 
                         view! {
                             <For
-                                each=move || notes_for_tab(
-                                    vm.active_tab.get(),
-                                    public_notes.get(),
-                                    private_notes.get(),
-                                    direct_notes.get(),
-                                )
-                                key=|note| format!("{:?}:{}", note.visibility, note.id)
+                                each=visible_notes
+                                key=|note| format!("{:?}:{}:{}", note.visibility, note.id, note.updated_at_ms)
                                 children=move |note| {
                                     let current_username = current_username.clone();
                                     let can_edit = can_edit_note(&note, &current_username);
+                                    let can_edit_body = can_edit_note_body(&note, &current_username);
                                     let can_delete = can_delete_note(&note, &current_username);
                                     let drag_note = note.clone();
                                     let (display_title, display_body) = note_heading_and_body(&note.body);
-                                    let rendered_html = render_note_html(&display_body);
+                                    let has_body = !display_body.is_empty();
+                                    let rendered_html = move || {
+                                        let html = link_index.with(|index| render_linked_note_html(&display_body, index));
+                                        file_urls.with(|urls| resolve_file_embeds(&html, urls))
+                                    };
+                                    let element_id = note_element_id(&note.id);
+                                    let focus_id = note.id.clone();
+                                    let backlink_target_id = note.id.clone();
+                                    let article_bg = theme.ui_bg_primary;
+                                    let article_border = theme.ui_border;
+                                    let article_focus_border = theme.ui_button_primary;
+                                    let backlink_style = format!(
+                                        "display: flex; flex-wrap: wrap; align-items: center; gap: 0.4rem; margin-top: 0.75rem; color: {}; font-size: {};",
+                                        theme.ui_text_secondary, NOTE_META_FONT_SIZE
+                                    );
+                                    let backlink_button_style = format!(
+                                        "padding: 0.2rem 0.55rem; background: {}; color: {}; border: none; border-radius: 999px; cursor: pointer; font-size: {};",
+                                        theme.ui_bg_secondary, theme.ui_text_primary, NOTE_META_FONT_SIZE
+                                    );
                                     let note_for_edit = note.clone();
                                     let note_for_delete = note.clone();
                                     let note_for_unpin = note.clone();
+                                    let note_for_pin = note.clone();
+                                    let note_for_history = note.clone();
+                                    let note_for_history_toggle = note.clone();
+                                    let revision_count = note.revisions.len();
                                     let meta = note_scope_label(&note, &current_username);
+                                    let title_text = if display_title.trim().is_empty() {
+                                        t_string!(i18n, notes.untitled_note).to_string()
+                                    } else {
+                                        display_title.clone()
+                                    };
+                                    let snippet_source = note.body.clone();
+                                    let folder = note.folder.clone();
+                                    let is_pinned = note.pinned;
+                                    let tags = note.tags.clone();
+                                    let tag_style = format!(
+                                        "padding: 0.1rem 0.45rem; background: {}; color: {}; border: none; border-radius: 999px; cursor: pointer; font-size: {};",
+                                        theme.ui_bg_secondary, theme.ui_text_secondary, NOTE_META_FONT_SIZE
+                                    );
                                     view! {
                                         <article
-                                            style=format!(
+                                            id=element_id
+                                            style=move || format!(
                                                 "padding: 0.95rem; background: linear-gradient(180deg, rgba(255,255,255,0.05), rgba(0,0,0,0.08)), {}; border: 0.0625rem solid {}; border-radius: 0.8rem; box-shadow: 0 0.9rem 2rem rgba(0,0,0,0.16);",
-                                                theme.ui_bg_primary, theme.ui_border
+                                                article_bg,
+                                                if vm.focused_note_id.get().as_deref() == Some(focus_id.as_str()) {
+                                                    article_focus_border
+                                                } else {
+                                                    article_border
+                                                }
                                             )
                                         >
                                             <div style="display: flex; justify-content: space-between; align-items: flex-start; gap: 0.75rem; margin-bottom: 0.65rem;">
                                                 <div style="min-width: 0;">
                                                     <div style=format!("color: {}; font-weight: 700; font-size: 1rem; word-break: break-word;", theme.ui_text_primary)>
-                                                        {if display_title.trim().is_empty() {
-                                                            t_string!(i18n, notes.untitled_note).to_string()
-                                                        } else {
-                                                            display_title.clone()
-                                                        }}
+                                                        {is_pinned.then_some("📌 ")}
+                                                        {move || highlighted_text(highlight(&title_text, &SearchQuery::parse(&vm.search_query.get()).terms))}
                                                     </div>
-                                                    <div style=format!("color: {}; font-size: {}; margin-top: 0.2rem;", theme.ui_text_secondary, NOTE_META_FONT_SIZE)>
-                                                        {meta}
+                                                    <div style=format!("display: flex; flex-wrap: wrap; align-items: center; gap: 0.35rem; color: {}; font-size: {}; margin-top: 0.2rem;", theme.ui_text_secondary, NOTE_META_FONT_SIZE)>
+                                                        <span>{meta}</span>
+                                                        {(!folder.is_empty()).then(|| {
+                                                            let folder_path = folder.clone();
+                                                            view! {
+                                                                <button
+                                                                    on:click=move |_| vm.folder_filter.set(folder_path.clone())
+                                                                    style=tag_style.clone()
+                                                                >
+                                                                    {format!("📁 {folder}")}
+                                                                </button>
+                                                            }
+                                                        })}
+                                                        {tags
+                                                            .into_iter()
+                                                            .map(|tag| {
+                                                                let label = format!("#{tag}");
+                                                                view! {
+                                                                    <button
+                                                                        on:click=move |_| vm.tag_filter.set(Some(tag.clone()))
+                                                                        style=tag_style.clone()
+                                                                    >
+                                                                        {label}
+                                                                    </button>
+                                                                }
+                                                            })
+                                                            .collect_view()}
                                                     </div>
+                                                    {move || {
+                                                        let snippet = match_snippet(&snippet_source, &SearchQuery::parse(&vm.search_query.get()).terms);
+                                                        (!snippet.is_empty()).then(|| view! {
+                                                            <div style="margin-top: 0.4rem; font-size: 0.82rem; font-style: italic; opacity: 0.85;">
+                                                                {highlighted_text(snippet)}
+                                                            </div>
+                                                        })
+                                                    }}
                                                 </div>
                                                 <div style="display: flex; align-items: center; gap: 0.4rem; flex-shrink: 0;">
                                                     {if can_edit {
@@ -577,12 +1428,19 @@ pub fn NotesWindow(
                                                 </div>
                                             </div>
 
-                                            {if display_body.is_empty() {
+                                            {if !has_body {
                                                 ().into_any()
                                             } else {
                                                 view! {
                                                     <div
+                                                        class="note-markdown"
                                                         inner_html=rendered_html
+                                                        on:click=move |ev: MouseEvent| {
+                                                            if let Some(target) = wiki_target_from_click(&ev) {
+                                                                ev.prevent_default();
+                                                                follow_link.with_value(|follow| follow(target));
+                                                            }
+                                                        }
                                                         style=format!(
                                                             "color: {}; font-size: {}; line-height: 1.5; word-break: break-word;",
                                                             theme.ui_text_primary, NOTE_BODY_FONT_SIZE
@@ -591,6 +1449,125 @@ pub fn NotesWindow(
                                                 }.into_any()
                                             }}
 
+                                            {move || {
+                                                let sources = incoming_links
+                                                    .get()
+                                                    .get(&backlink_target_id)
+                                                    .cloned()
+                                                    .unwrap_or_default();
+                                                if sources.is_empty() {
+                                                    return ().into_any();
+                                                }
+                                                let all_notes = journal.get();
+                                                let backlink_button_style = backlink_button_style.clone();
+                                                view! {
+                                                    <div style=backlink_style.clone()>
+                                                        <span>{t!(i18n, notes.linked_from)}</span>
+                                                        {sources
+                                                            .into_iter()
+                                                            .filter_map(|source_id| all_notes.iter().find(|note| note.id == source_id).cloned())
+                                                            .map(|source| {
+                                                                let label = if source.title.trim().is_empty() {
+                                                                    t_string!(i18n, notes.untitled_note).to_string()
+                                                                } else {
+                                                                    source.title.clone()
+                                                                };
+                                                                view! {
+                                                                    <button
+                                                                        on:click=move |_| follow_link.with_value(|follow| follow(WikiTarget::Note(source.id.clone())))
+                                                                        style=backlink_button_style.clone()
+                                                                    >
+                                                                        {label}
+                                                                    </button>
+                                                                }
+                                                            })
+                                                            .collect_view()}
+                                                    </div>
+                                                }.into_any()
+                                            }}
+
+                                            {move || {
+                                                if vm.history_note_id.get().as_deref() != Some(note_for_history.id.as_str()) {
+                                                    return ().into_any();
+                                                }
+                                                let selected = vm.history_revision.get();
+                                                let note = note_for_history.clone();
+                                                let diff = selected
+                                                    .and_then(|index| note.revisions.get(index))
+                                                    .map(|revision| diff_lines(&revision.body, &note.body))
+                                                    .unwrap_or_default();
+                                                view! {
+                                                    <div style=format!(
+                                                        "display: flex; flex-direction: column; gap: 0.5rem; margin-top: 0.75rem; padding: 0.65rem; background: {}; border: 0.0625rem solid {}; border-radius: 0.6rem; font-size: {};",
+                                                        theme.ui_bg_secondary, theme.ui_border, NOTE_META_FONT_SIZE
+                                                    )>
+                                                        <div style="display: flex; flex-wrap: wrap; gap: 0.35rem;">
+                                                            {note
+                                                                .revisions
+                                                                .iter()
+                                                                .enumerate()
+                                                                .rev()
+                                                                .map(|(index, revision)| {
+                                                                    let saved_at = js_sys::Date::new(&JsValue::from_f64(revision.saved_at_ms))
+                                                                        .to_locale_string("default", &JsValue::UNDEFINED)
+                                                                        .as_string()
+                                                                        .unwrap_or_default();
+                                                                    view! {
+                                                                        <button
+                                                                            on:click=move |_| vm.history_revision.set(Some(index))
+                                                                            style=format!(
+                                                                                "padding: 0.25rem 0.55rem; background: {}; color: {}; border: none; border-radius: 0.4rem; cursor: pointer; font-size: {};",
+                                                                                if selected == Some(index) { theme.ui_button_primary } else { theme.ui_bg_primary },
+                                                                                theme.ui_text_primary,
+                                                                                NOTE_META_FONT_SIZE
+                                                                            )
+                                                                        >
+                                                                            {format!("{saved_at} · {}", revision.author)}
+                                                                        </button>
+                                                                    }
+                                                                })
+                                                                .collect_view()}
+                                                        </div>
+                                                        {selected.map(|index| view! {
+                                                            <div style=format!("color: {};", theme.ui_text_secondary)>
+                                                                {t!(i18n, notes.diff_hint)}
+                                                            </div>
+                                                            <pre style="margin: 0; max-height: 16rem; overflow: auto; white-space: pre-wrap; word-break: break-word; font-size: 0.8rem; line-height: 1.45;">
+                                                                {diff
+                                                                    .into_iter()
+                                                                    .map(|line| {
+                                                                        let (marker, style) = match line.kind {
+                                                                            DiffKind::Same => (" ", String::new()),
+                                                                            DiffKind::Added => ("+", format!("color: {};", theme.ui_success)),
+                                                                            DiffKind::Removed => ("-", format!("color: {}; text-decoration: line-through;", theme.ui_button_danger)),
+                                                                        };
+                                                                        view! {
+                                                                            <div style=style>{format!("{marker} {}", line.text)}</div>
+                                                                        }
+                                                                    })
+                                                                    .collect_view()}
+                                                            </pre>
+                                                            {can_edit_body.then(|| {
+                                                                let note = note.clone();
+                                                                view! {
+                                                                    <div style="display: flex; justify-content: flex-end;">
+                                                                        <button
+                                                                            on:click=move |_| restore_revision_action.with_value(|action| action(note.clone(), index))
+                                                                            style=format!(
+                                                                                "padding: 0.45rem 0.7rem; background: {}; color: {}; border: none; border-radius: 0.45rem; cursor: pointer;",
+                                                                                theme.ui_button_primary, theme.ui_text_primary
+                                                                            )
+                                                                        >
+                                                                            {t!(i18n, notes.restore_button)}
+                                                                        </button>
+                                                                    </div>
+                                                                }
+                                                            })}
+                                                        })}
+                                                    </div>
+                                                }.into_any()
+                                            }}
+
                                             <div style=format!("display: flex; gap: 0.55rem; justify-content: flex-end; margin-top: 0.85rem; color: {}; font-size: {};", theme.ui_text_secondary, NOTE_META_FONT_SIZE)>
                                                 {move || if note.board_position.is_some() {
                                                     view! {
@@ -600,18 +1577,45 @@ pub fn NotesWindow(
                                                     ().into_any()
                                                 }}
 
-                                                {if can_edit || can_delete {
+                                                {(revision_count > 0).then(|| view! {
+                                                    <button
+                                                        on:click=move |_| vm.toggle_history(&note_for_history_toggle)
+                                                        style=format!(
+                                                            "padding: 0.45rem 0.7rem; background: {}; color: {}; border: none; border-radius: 0.45rem; cursor: pointer;",
+                                                            theme.ui_bg_secondary, theme.ui_text_primary
+                                                        )
+                                                    >
+                                                        {format!("{} ({revision_count})", t_string!(i18n, notes.history_button))}
+                                                    </button>
+                                                })}
+
+                                                {if can_edit_body || can_delete {
                                                     view! {
+                                                        {can_edit_body.then(|| view! {
+                                                            <button
+                                                                on:click=move |_| vm.start_edit(&note_for_edit)
+                                                                style=format!(
+                                                                    "padding: 0.45rem 0.7rem; background: {}; color: {}; border: none; border-radius: 0.45rem; cursor: pointer;",
+                                                                    theme.ui_bg_secondary, theme.ui_text_primary
+                                                                )
+                                                            >
+                                                                {t!(i18n, notes.edit_button)}
+                                                            </button>
+                                                        })}
                                                         {if can_edit {
                                                             view! {
                                                                 <button
-                                                                    on:click=move |_| vm.start_edit(&note_for_edit)
+                                                                    on:click=move |_| toggle_pin_action.with_value(|action| action(note_for_pin.clone()))
                                                                     style=format!(
                                                                         "padding: 0.45rem 0.7rem; background: {}; color: {}; border: none; border-radius: 0.45rem; cursor: pointer;",
                                                                         theme.ui_bg_secondary, theme.ui_text_primary
                                                                     )
                                                                 >
-                                                                    {t!(i18n, notes.edit_button)}
+                                                                    {if is_pinned {
+                                                                        t_string!(i18n, notes.unpin_button).to_string()
+                                                                    } else {
+                                                                        t_string!(i18n, notes.pin_button).to_string()
+                                                                    }}
                                                                 </button>
                                                             }.into_any()
                                                         } else {
@@ -659,6 +1663,153 @@ pub fn NotesWindow(
                         }.into_any()
                     }}
                 </div>
+
+                {move || match vm.journal_view.get() {
+                    JournalView::Notes => ().into_any(),
+                    JournalView::Index => {
+                        let all_notes = journal.get();
+                        if all_notes.is_empty() {
+                            return view! {
+                                <div style=format!(
+                                    "padding: 2rem 1rem; text-align: center; color: {}; font-style: italic;",
+                                    theme.ui_text_muted
+                                )>
+                                    {t!(i18n, notes.journal_empty)}
+                                </div>
+                            }.into_any();
+                        }
+                        let outgoing = outgoing_links(&all_notes);
+                        let incoming = incoming_links.get();
+                        let current_username = username.get();
+                        let row_style = format!(
+                            "display: flex; justify-content: space-between; align-items: baseline; gap: 0.75rem; padding: 0.55rem 0.2rem; border-bottom: 0.0625rem solid {};",
+                            theme.ui_border
+                        );
+                        let title_style = format!(
+                            "padding: 0; background: none; border: none; color: {}; font-weight: 600; text-align: left; cursor: pointer; word-break: break-word;",
+                            theme.ui_text_primary
+                        );
+                        let meta_style = format!(
+                            "color: {}; font-size: {}; white-space: nowrap;",
+                            theme.ui_text_secondary, NOTE_META_FONT_SIZE
+                        );
+                        view! {
+                            <div style="flex: 1; overflow-y: auto; padding: 0.5rem 1rem 2rem 1rem;">
+                                {all_notes
+                                    .into_iter()
+                                    .map(|note| {
+                                        let out_count = outgoing.get(&note.id).map_or(0, Vec::len);
+                                        let in_count = incoming.get(&note.id).map_or(0, Vec::len);
+                                        let label = if note.title.trim().is_empty() {
+                                            t_string!(i18n, notes.untitled_note).to_string()
+                                        } else {
+                                            note.title.clone()
+                                        };
+                                        let scope = note_scope_label(&note, &current_username);
+                                        view! {
+                                            <div style=row_style.clone()>
+                                                <div style="min-width: 0;">
+                                                    <button
+                                                        on:click=move |_| follow_link.with_value(|follow| follow(WikiTarget::Note(note.id.clone())))
+                                                        style=title_style.clone()
+                                                    >
+                                                        {label}
+                                                    </button>
+                                                    <div style=meta_style.clone()>{scope}</div>
+                                                </div>
+                                                <span style=meta_style.clone()>
+                                                    {format!(
+                                                        "{} {} · {} {}",
+                                                        out_count,
+                                                        t_string!(i18n, notes.links_out),
+                                                        in_count,
+                                                        t_string!(i18n, notes.links_in)
+                                                    )}
+                                                </span>
+                                            </div>
+                                        }
+                                    })
+                                    .collect_view()}
+                            </div>
+                        }.into_any()
+                    }
+                    JournalView::Graph => {
+                        let graph = journal_graph(&journal.get());
+                        if graph.nodes.is_empty() {
+                            return view! {
+                                <div style=format!(
+                                    "padding: 2rem 1rem; text-align: center; color: {}; font-style: italic;",
+                                    theme.ui_text_muted
+                                )>
+                                    {t!(i18n, notes.journal_empty)}
+                                </div>
+                            }.into_any();
+                        }
+                        let point = |share: f64| format!("{:.4}", share * JOURNAL_GRAPH_SIZE);
+                        view! {
+                            <div style="flex: 1; min-height: 0; padding: 0.5rem 1rem 1rem 1rem;">
+                                <svg
+                                    viewBox=format!("-10 -6 {:.4} {:.4}", JOURNAL_GRAPH_SIZE + 20.0, JOURNAL_GRAPH_SIZE + 12.0)
+                                    preserveAspectRatio="xMidYMid meet"
+                                    style="width: 100%; height: 100%;"
+                                >
+                                    {graph
+                                        .edges
+                                        .iter()
+                                        .map(|&(from, to)| {
+                                            let (from, to) = (&graph.nodes[from], &graph.nodes[to]);
+                                            view! {
+                                                <line
+                                                    x1=point(from.x)
+                                                    y1=point(from.y)
+                                                    x2=point(to.x)
+                                                    y2=point(to.y)
+                                                    stroke=theme.ui_border
+                                                    stroke-width="0.35"
+                                                />
+                                            }
+                                        })
+                                        .collect_view()}
+                                    {graph
+                                        .nodes
+                                        .iter()
+                                        .map(|node| {
+                                            let note_id = node.id.clone();
+                                            let label = if node.title.trim().is_empty() {
+                                                t_string!(i18n, notes.untitled_note).to_string()
+                                            } else {
+                                                node.title.chars().take(24).collect::<String>()
+                                            };
+                                            let radius = 1.6 + (node.link_count as f64).sqrt() * 0.6;
+                                            view! {
+                                                <g
+                                                    on:click=move |_| follow_link.with_value(|follow| follow(WikiTarget::Note(note_id.clone())))
+                                                    style="cursor: pointer;"
+                                                >
+                                                    <circle
+                                                        cx=point(node.x)
+                                                        cy=point(node.y)
+                                                        r=format!("{radius:.4}")
+                                                        fill=theme.ui_button_primary
+                                                    />
+                                                    <text
+                                                        x=point(node.x)
+                                                        y=format!("{:.4}", node.y * JOURNAL_GRAPH_SIZE + radius + 3.2)
+                                                        text-anchor="middle"
+                                                        font-size="3"
+                                                        fill=theme.ui_text_primary
+                                                    >
+                                                        {label}
+                                                    </text>
+                                                </g>
+                                            }
+                                        })
+                                        .collect_view()}
+                                </svg>
+                            </div>
+                        }.into_any()
+                    }
+                }}
             </div>
         </DraggableWindow>
     }
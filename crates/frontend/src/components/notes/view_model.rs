@@ -1,6 +1,10 @@
-use super::model::{NotesTab, note_title_from_markdown, tab_for_visibility};
+use super::model::{JournalView, NotesTab, note_title_from_markdown, tab_for_visibility};
+use super::search::{NoteFilter, format_tags, normalize_folder_path, normalize_tags};
 use leptos::prelude::*;
-use shared::events::{NoteBoardPosition, NoteBoardStyle, NotePayload, NoteVisibility};
+use shared::events::{
+    MAX_NOTE_GROUP_MEMBERS, NoteBoardPosition, NoteBoardStyle, NoteGroup, NotePayload,
+    NoteVisibility,
+};
 use uuid::Uuid;
 
 fn current_time_ms() -> f64 {
@@ -24,11 +28,33 @@ fn current_time_ms() -> f64 {
 pub struct NotesViewModel {
     pub active_tab: RwSignal<NotesTab>,
     pub body: RwSignal<String>,
-    pub recipient: RwSignal<String>,
+    /// Recipients picked on the direct tab; more than one makes a group note.
+    pub recipients: RwSignal<Vec<String>>,
+    /// Optional name for a group note written on the direct tab.
+    pub group_name: RwSignal<String>,
+    pub folder: RwSignal<String>,
+    /// Comma-separated tags as typed in the composer.
+    pub tags: RwSignal<String>,
+    pub pinned: RwSignal<bool>,
     pub editing_note_id: RwSignal<Option<String>>,
     editing_created_at_ms: RwSignal<Option<f64>>,
     pub error_message: RwSignal<Option<String>>,
     pub is_loading_recipients: RwSignal<bool>,
+    pub journal_view: RwSignal<JournalView>,
+    /// Note opened through a wiki link; highlighted in the list.
+    pub focused_note_id: RwSignal<Option<String>>,
+    pub search_query: RwSignal<String>,
+    /// Folder shown in the list; empty means every folder.
+    pub folder_filter: RwSignal<String>,
+    pub tag_filter: RwSignal<Option<String>>,
+    /// Note whose revision history is expanded.
+    pub history_note_id: RwSignal<Option<String>>,
+    /// Revision compared against the current text in the history panel.
+    pub history_revision: RwSignal<Option<usize>>,
+    /// Set while the journal archive is being exported or imported.
+    pub is_archiving: RwSignal<bool>,
+    /// Outcome of the last journal export or import.
+    pub archive_status: RwSignal<Option<String>>,
 }
 
 impl NotesViewModel {
@@ -36,17 +62,34 @@ impl NotesViewModel {
         Self {
             active_tab: RwSignal::new(NotesTab::Public),
             body: RwSignal::new(String::new()),
-            recipient: RwSignal::new(String::new()),
+            recipients: RwSignal::new(Vec::new()),
+            group_name: RwSignal::new(String::new()),
+            folder: RwSignal::new(String::new()),
+            tags: RwSignal::new(String::new()),
+            pinned: RwSignal::new(false),
             editing_note_id: RwSignal::new(None),
             editing_created_at_ms: RwSignal::new(None),
             error_message: RwSignal::new(None),
             is_loading_recipients: RwSignal::new(false),
+            journal_view: RwSignal::new(JournalView::Notes),
+            focused_note_id: RwSignal::new(None),
+            search_query: RwSignal::new(String::new()),
+            folder_filter: RwSignal::new(String::new()),
+            tag_filter: RwSignal::new(None),
+            history_note_id: RwSignal::new(None),
+            history_revision: RwSignal::new(None),
+            is_archiving: RwSignal::new(false),
+            archive_status: RwSignal::new(None),
         }
     }
 
     pub fn reset_form(&self) {
         self.body.set(String::new());
-        self.recipient.set(String::new());
+        self.recipients.set(Vec::new());
+        self.group_name.set(String::new());
+        self.folder.set(self.folder_filter.get_untracked());
+        self.tags.set(String::new());
+        self.pinned.set(false);
         self.editing_note_id.set(None);
         self.editing_created_at_ms.set(None);
         self.error_message.set(None);
@@ -56,15 +99,78 @@ impl NotesViewModel {
     pub fn start_edit(&self, note: &NotePayload) {
         self.active_tab.set(tab_for_visibility(&note.visibility));
         self.body.set(note.body.clone());
-        self.recipient.set(match &note.visibility {
-            NoteVisibility::Direct(recipient) => recipient.clone(),
-            _ => String::new(),
+        self.recipients.set(match &note.visibility {
+            NoteVisibility::Direct(recipient) => vec![recipient.clone()],
+            _ => Vec::new(),
         });
+        self.group_name.set(String::new());
+        self.folder.set(note.folder.clone());
+        self.tags.set(format_tags(&note.tags));
+        self.pinned.set(note.pinned);
         self.editing_note_id.set(Some(note.id.clone()));
         self.editing_created_at_ms.set(Some(note.created_at_ms));
         self.error_message.set(None);
     }
 
+    /// The author always joins their own groups, so one slot stays free.
+    pub fn toggle_recipient(&self, user: &str) {
+        self.recipients.update(|recipients| {
+            match recipients.iter().position(|recipient| recipient == user) {
+                Some(index) => {
+                    recipients.remove(index);
+                }
+                None if recipients.len() < MAX_NOTE_GROUP_MEMBERS - 1 => {
+                    recipients.push(user.to_string());
+                    recipients.sort();
+                }
+                None => {}
+            }
+        });
+    }
+
+    /// Shows the tab holding `note` and highlights it.
+    pub fn open_note(&self, note: &NotePayload) {
+        self.active_tab.set(tab_for_visibility(&note.visibility));
+        self.journal_view.set(JournalView::Notes);
+        self.focused_note_id.set(Some(note.id.clone()));
+    }
+
+    /// Starts a new note for a `[[link]]` whose target does not exist yet.
+    pub fn start_linked_note(&self, title: &str) {
+        self.reset_form();
+        self.body.set(format!("# {title}\n\n"));
+        self.journal_view.set(JournalView::Notes);
+        self.focused_note_id.set(None);
+    }
+
+    /// Expands the history of `note`, preselecting its newest revision, or
+    /// collapses it when already open.
+    pub fn toggle_history(&self, note: &NotePayload) {
+        if self.history_note_id.get_untracked().as_deref() == Some(note.id.as_str()) {
+            self.history_note_id.set(None);
+            self.history_revision.set(None);
+            return;
+        }
+        self.history_note_id.set(Some(note.id.clone()));
+        self.history_revision
+            .set(note.revisions.len().checked_sub(1));
+    }
+
+    /// Current list filter; tracks the filter signals.
+    pub fn filter(&self) -> NoteFilter {
+        NoteFilter {
+            query: self.search_query.get(),
+            folder: self.folder_filter.get(),
+            tag: self.tag_filter.get(),
+        }
+    }
+
+    pub fn clear_filters(&self) {
+        self.search_query.set(String::new());
+        self.folder_filter.set(String::new());
+        self.tag_filter.set(None);
+    }
+
     pub fn build_note(
         &self,
         author: &str,
@@ -80,12 +186,20 @@ impl NotesViewModel {
             NotesTab::Public => NoteVisibility::Public,
             NotesTab::Private => NoteVisibility::Private,
             NotesTab::Direct => {
-                let recipient = self.recipient.get_untracked().trim().to_string();
-                if recipient.is_empty() {
-                    return Err("Recipient username is required".to_string());
+                let mut recipients = self.recipients.get_untracked();
+                let group_name = self.group_name.get_untracked();
+                match recipients.len() {
+                    0 => return Err("Recipient username is required".to_string()),
+                    1 if group_name.trim().is_empty() => {
+                        NoteVisibility::Direct(recipients.remove(0))
+                    }
+                    _ => {
+                        recipients.push(author.to_string());
+                        NoteVisibility::Group(NoteGroup::new(&group_name, recipients))
+                    }
                 }
-                NoteVisibility::Direct(recipient)
             }
+            NotesTab::Group(group) => NoteVisibility::Group(group),
         };
 
         let now = current_time_ms();
@@ -102,6 +216,11 @@ impl NotesViewModel {
             updated_at_ms: now,
             board_position,
             board_style,
+            folder: normalize_folder_path(&self.folder.get_untracked()),
+            tags: normalize_tags(&self.tags.get_untracked()),
+            pinned: self.pinned.get_untracked(),
+            edited_by: author.to_string(),
+            revisions: Vec::new(),
         })
     }
 }
@@ -110,6 +229,7 @@ impl NotesViewModel {
 mod tests {
     use super::*;
     use leptos::reactive::owner::Owner;
+    use shared::events::NoteRevision;
 
     #[test]
     fn direct_note_requires_recipient() {
@@ -125,6 +245,45 @@ mod tests {
         });
     }
 
+    #[test]
+    fn several_recipients_make_a_group_note() {
+        let owner = Owner::new();
+        owner.with(|| {
+            let vm = NotesViewModel::new();
+            vm.active_tab.set(NotesTab::Direct);
+            vm.body.set("hello".into());
+            vm.toggle_recipient("bob");
+            let built = vm
+                .build_note("gm", None, NoteBoardStyle::default())
+                .unwrap();
+            assert_eq!(built.visibility, NoteVisibility::Direct("bob".into()));
+
+            vm.toggle_recipient("alice");
+            vm.group_name.set("Party A".into());
+            let built = vm
+                .build_note("gm", None, NoteBoardStyle::default())
+                .unwrap();
+            let NoteVisibility::Group(group) = built.visibility else {
+                panic!("expected a group note");
+            };
+            assert_eq!(group.label(), "Party A");
+            assert_eq!(group.members, vec!["alice", "bob", "gm"]);
+
+            vm.start_edit(&NotePayload {
+                visibility: NoteVisibility::Group(group.clone()),
+                ..built
+            });
+            assert_eq!(
+                vm.active_tab.get_untracked(),
+                NotesTab::Group(group.clone())
+            );
+            let rebuilt = vm
+                .build_note("gm", None, NoteBoardStyle::default())
+                .unwrap();
+            assert_eq!(rebuilt.visibility, NoteVisibility::Group(group));
+        });
+    }
+
     #[test]
     fn edit_mode_preserves_note_id() {
         let owner = Owner::new();
@@ -140,6 +299,11 @@ mod tests {
                 updated_at_ms: 11.0,
                 board_position: None,
                 board_style: NoteBoardStyle::default(),
+                folder: String::new(),
+                tags: Vec::new(),
+                pinned: false,
+                edited_by: String::new(),
+                revisions: Vec::new(),
             };
             vm.start_edit(&note);
             let built = vm
@@ -149,4 +313,118 @@ mod tests {
             assert_eq!(built.created_at_ms, 10.0);
         });
     }
+
+    #[test]
+    fn following_links_opens_or_drafts_notes() {
+        let owner = Owner::new();
+        owner.with(|| {
+            let vm = NotesViewModel::new();
+            vm.journal_view.set(JournalView::Graph);
+            let note = NotePayload {
+                id: "note-1".into(),
+                author: "gm".into(),
+                visibility: NoteVisibility::Private,
+                title: "Tower".into(),
+                body: "# Tower".into(),
+                created_at_ms: 10.0,
+                updated_at_ms: 11.0,
+                board_position: None,
+                board_style: NoteBoardStyle::default(),
+                folder: String::new(),
+                tags: Vec::new(),
+                pinned: false,
+                edited_by: String::new(),
+                revisions: Vec::new(),
+            };
+            vm.open_note(&note);
+            assert_eq!(vm.active_tab.get_untracked(), NotesTab::Private);
+            assert_eq!(vm.journal_view.get_untracked(), JournalView::Notes);
+            assert_eq!(
+                vm.focused_note_id.get_untracked().as_deref(),
+                Some("note-1")
+            );
+
+            vm.start_edit(&note);
+            vm.start_linked_note("Lost Mine");
+            assert_eq!(vm.editing_note_id.get_untracked(), None);
+            assert_eq!(vm.body.get_untracked(), "# Lost Mine\n\n");
+            let built = vm
+                .build_note("gm", None, NoteBoardStyle::default())
+                .unwrap();
+            assert_eq!(built.title, "Lost Mine");
+        });
+    }
+
+    #[test]
+    fn composer_keeps_folder_tags_and_pin() {
+        let owner = Owner::new();
+        owner.with(|| {
+            let vm = NotesViewModel::new();
+            vm.folder_filter.set("Campaign".into());
+            vm.reset_form();
+            assert_eq!(vm.folder.get_untracked(), "Campaign");
+
+            vm.body.set("# Ambush".into());
+            vm.folder.set("Campaign / Act 1/".into());
+            vm.tags.set("#Combat, combat, goblins".into());
+            vm.pinned.set(true);
+            let built = vm
+                .build_note("gm", None, NoteBoardStyle::default())
+                .unwrap();
+            assert_eq!(built.folder, "Campaign/Act 1");
+            assert_eq!(
+                built.tags,
+                vec!["combat".to_string(), "goblins".to_string()]
+            );
+            assert!(built.pinned);
+
+            vm.reset_form();
+            vm.start_edit(&built);
+            assert_eq!(vm.tags.get_untracked(), "combat, goblins");
+            assert!(vm.pinned.get_untracked());
+        });
+    }
+
+    #[test]
+    fn history_toggles_and_selects_newest_revision() {
+        let owner = Owner::new();
+        owner.with(|| {
+            let vm = NotesViewModel::new();
+            let mut note = NotePayload {
+                id: "note-1".into(),
+                author: "gm".into(),
+                visibility: NoteVisibility::Public,
+                title: "Body".into(),
+                body: "Body".into(),
+                created_at_ms: 10.0,
+                updated_at_ms: 11.0,
+                board_position: None,
+                board_style: NoteBoardStyle::default(),
+                folder: String::new(),
+                tags: Vec::new(),
+                pinned: false,
+                edited_by: String::new(),
+                revisions: Vec::new(),
+            };
+            vm.toggle_history(&note);
+            assert_eq!(
+                vm.history_note_id.get_untracked().as_deref(),
+                Some("note-1")
+            );
+            assert_eq!(vm.history_revision.get_untracked(), None);
+
+            vm.toggle_history(&note);
+            assert_eq!(vm.history_note_id.get_untracked(), None);
+
+            note.revisions.push(NoteRevision {
+                author: "gm".into(),
+                saved_at_ms: 5.0,
+                title: "Old".into(),
+                body: "Old".into(),
+            });
+            note.revisions.push(note.revisions[0].clone());
+            vm.toggle_history(&note);
+            assert_eq!(vm.history_revision.get_untracked(), Some(1));
+        });
+    }
 }
@@ -0,0 +1,148 @@
+use super::model::{
+    AURA_DEFAULT_COLOR, aura_radius_px, aura_visible_to, hover_range_radii_feet, token_rect,
+};
+use leptos::prelude::*;
+use shared::events::{Token, is_hex_color};
+
+/// Aura circles around tokens plus dashed range rings for the hovered token,
+/// drawn in board-local pixels underneath the token layer.
+#[component]
+pub fn SceneAuraLayer(
+    tokens: Vec<Token>,
+    cell_size: f64,
+    cell_size_feet: u16,
+    board_width: f64,
+    board_height: f64,
+    viewer: String,
+    game_master: Option<String>,
+    hovered_token_id: Option<String>,
+    ring_color: &'static str,
+) -> impl IntoView {
+    let label_font = (cell_size * 0.22).clamp(9.0, 14.0);
+
+    let circles = tokens
+        .iter()
+        .flat_map(|token| {
+            let (left, top, width, height) = token_rect(
+                0.0,
+                0.0,
+                cell_size,
+                token.x,
+                token.y,
+                token.width_cells,
+                token.height_cells,
+            );
+            let (center_x, center_y) = (left + width / 2.0, top + height / 2.0);
+            token
+                .auras
+                .iter()
+                .filter(|aura| {
+                    aura_visible_to(
+                        aura.visibility,
+                        token.owner.as_deref(),
+                        game_master.as_deref(),
+                        &viewer,
+                    )
+                })
+                .map(move |aura| {
+                    let radius = aura_radius_px(
+                        aura.radius_feet,
+                        cell_size_feet,
+                        cell_size,
+                        token.width_cells,
+                        token.height_cells,
+                    );
+                    // Rooms saved before colors were validated may hold anything.
+                    let color = if is_hex_color(&aura.color) {
+                        aura.color.clone()
+                    } else {
+                        AURA_DEFAULT_COLOR.to_string()
+                    };
+                    view! {
+                        <circle
+                            cx=format!("{center_x:.4}")
+                            cy=format!("{center_y:.4}")
+                            r=format!("{radius:.4}")
+                            fill=color.clone()
+                            fill-opacity=format!("{:.3}", aura.opacity.clamp(0.0, 1.0))
+                            stroke=color
+                            stroke-opacity="0.85"
+                            stroke-width="2"
+                        />
+                    }
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect_view();
+
+    let hover_rings = hovered_token_id
+        .and_then(|token_id| tokens.iter().find(|token| token.id == token_id))
+        .map(|token| {
+            let (left, top, width, height) = token_rect(
+                0.0,
+                0.0,
+                cell_size,
+                token.x,
+                token.y,
+                token.width_cells,
+                token.height_cells,
+            );
+            let (center_x, center_y) = (left + width / 2.0, top + height / 2.0);
+            let visible_radii = token
+                .auras
+                .iter()
+                .filter(|aura| {
+                    aura_visible_to(
+                        aura.visibility,
+                        token.owner.as_deref(),
+                        game_master.as_deref(),
+                        &viewer,
+                    )
+                })
+                .map(|aura| aura.radius_feet);
+            hover_range_radii_feet(visible_radii, cell_size_feet)
+                .into_iter()
+                .map(|radius_feet| {
+                    let radius = aura_radius_px(
+                        radius_feet,
+                        cell_size_feet,
+                        cell_size,
+                        token.width_cells,
+                        token.height_cells,
+                    );
+                    view! {
+                        <circle
+                            cx=format!("{center_x:.4}")
+                            cy=format!("{center_y:.4}")
+                            r=format!("{radius:.4}")
+                            fill="none"
+                            stroke=ring_color
+                            stroke-width="2"
+                            stroke-dasharray="8 6"
+                        />
+                        <text
+                            x=format!("{center_x:.4}")
+                            y=format!("{:.4}", center_y - radius - 4.0)
+                            text-anchor="middle"
+                            fill=ring_color
+                            font-size=format!("{label_font:.2}")
+                            font-weight="700"
+                        >
+                            {format!("{radius_feet} ft")}
+                        </text>
+                    }
+                })
+                .collect_view()
+        });
+
+    view! {
+        <svg
+            viewBox=format!("0 0 {:.4} {:.4}", board_width, board_height)
+            preserveAspectRatio="none"
+            style="position: absolute; inset: 0; width: 100%; height: 100%; pointer-events: none; overflow: visible;"
+        >
+            {circles}
+            {hover_rings}
+        </svg>
+    }
+}
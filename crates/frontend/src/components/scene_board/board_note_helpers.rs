@@ -4,11 +4,13 @@
 use super::interaction_state::{
     BoardNoteEditorDraft, BoardNoteSelection, BOARD_NOTE_TOOLBAR_HEIGHT_PX,
 };
+use super::model::board_note_visible;
 use crate::components::notes::model::note_title_from_markdown;
+use crate::components::notes::revisions::push_revision;
 use crate::components::websocket::{StoredNoteBucket, WsSender, delete_note, save_note};
 use leptos::prelude::*;
 use leptos::task::spawn_local;
-use shared::events::{ClientEvent, NoteDeletePayload, NotePayload, NoteVisibility};
+use shared::events::{ClientEvent, NoteDeletePayload, NotePayload, NoteVisibility, Scene};
 
 pub fn current_time_ms() -> f64 {
     js_sys::Date::now()
@@ -50,6 +52,23 @@ pub fn collect_board_notes(
         .collect()
 }
 
+/// Board notes left on screen once notes anchored to hidden scenes are dropped.
+pub fn visible_board_notes(
+    public_notes: &[NotePayload],
+    private_notes: &[NotePayload],
+    direct_notes: &[NotePayload],
+    scenes: &[Scene],
+    active_scene_id: Option<&str>,
+    show_inactive_scene_contents: bool,
+) -> Vec<NotePayload> {
+    collect_board_notes(public_notes, private_notes, direct_notes)
+        .into_iter()
+        .filter(|note| {
+            board_note_visible(note, scenes, active_scene_id, show_inactive_scene_contents)
+        })
+        .collect()
+}
+
 pub fn find_matching_note(
     note: &NotePayload,
     public_notes: &[NotePayload],
@@ -88,7 +107,9 @@ pub fn apply_local_note_upsert(
     match note.visibility.clone() {
         NoteVisibility::Public => public_notes.update(|notes| upsert_note(notes, note.clone())),
         NoteVisibility::Private => private_notes.update(|notes| upsert_note(notes, note.clone())),
-        NoteVisibility::Direct(_) => direct_notes.update(|notes| upsert_note(notes, note.clone())),
+        NoteVisibility::Direct(_) | NoteVisibility::Group(_) => {
+            direct_notes.update(|notes| upsert_note(notes, note.clone()))
+        }
     }
 }
 
@@ -99,7 +120,7 @@ pub fn persist_note_upsert(
     note: NotePayload,
 ) {
     match note.visibility.clone() {
-        NoteVisibility::Public | NoteVisibility::Direct(_) => {
+        NoteVisibility::Public | NoteVisibility::Direct(_) | NoteVisibility::Group(_) => {
             send_ws_event(ws_sender, ClientEvent::NoteUpsert(note));
         }
         NoteVisibility::Private => {
@@ -128,7 +149,9 @@ pub fn apply_local_note_delete(
     match visibility {
         NoteVisibility::Public => public_notes.update(|notes| remove_note(notes, note_id)),
         NoteVisibility::Private => private_notes.update(|notes| remove_note(notes, note_id)),
-        NoteVisibility::Direct(_) => direct_notes.update(|notes| remove_note(notes, note_id)),
+        NoteVisibility::Direct(_) | NoteVisibility::Group(_) => {
+            direct_notes.update(|notes| remove_note(notes, note_id))
+        }
     }
 }
 
@@ -163,7 +186,7 @@ pub fn persist_note_delete(
                 .await;
             });
         }
-        NoteVisibility::Direct(_) => {
+        NoteVisibility::Direct(_) | NoteVisibility::Group(_) => {
             let current_user = username.get_untracked();
             if note.author == current_user {
                 send_ws_event(
@@ -212,6 +235,7 @@ pub fn board_note_meta(note: &NotePayload, current_username: &str) -> String {
             format!("@{} -> @{}", note.author, recipient)
         }
         NoteVisibility::Direct(_) => format!("@{} -> you", note.author),
+        NoteVisibility::Group(group) => format!("@{} -> {}", note.author, group.label()),
     }
 }
 
@@ -241,9 +265,12 @@ pub fn commit_board_note_draft(
         return false;
     };
 
+    let previous_note = updated_note.clone();
     updated_note.title = note_title_from_markdown(&body);
     updated_note.body = body;
+    updated_note.edited_by = username.get_untracked();
     updated_note.updated_at_ms = current_time_ms();
+    push_revision(&mut updated_note, &previous_note);
     apply_local_note_upsert(
         public_notes,
         private_notes,
@@ -1,11 +1,18 @@
+use super::canvas::{BoardRenderer, RenderStats, RenderSummary};
 use super::model::BoardTool;
+use super::scene_geometry::stop_pointer_propagation;
 use crate::config::Theme;
+use crate::i18n::i18n::{t_string, use_i18n};
 use leptos::ev::MouseEvent;
 use leptos::prelude::*;
+use shared::events::{SceneLevel, TerrainKind, is_hex_color};
+use std::collections::BTreeMap;
+use web_sys::Event;
 
 /// Icon labels for each tool button.
 const RULER_ICON: &str = "📏";
 const POINTER_ICON: &str = "🖱";
+const TERRAIN_ICON: &str = "🧱";
 
 #[component]
 pub fn BoardToolbar(active_tool: RwSignal<BoardTool>, theme: Theme) -> impl IntoView {
@@ -43,6 +50,7 @@ pub fn BoardToolbar(active_tool: RwSignal<BoardTool>, theme: Theme) -> impl Into
     view! {
         <div
             on:mousedown=stop.clone()
+            on:pointerdown=stop_pointer_propagation
             on:click=stop
             style=format!(
                 "position: absolute; right: 1rem; bottom: 5rem; display: flex; \
@@ -74,6 +82,361 @@ pub fn BoardToolbar(active_tool: RwSignal<BoardTool>, theme: Theme) -> impl Into
             >
                 {POINTER_ICON}
             </button>
+
+            // Terrain brush
+            <button
+                title="Terrain (difficult cells cost double)"
+                on:mousedown=move |ev: MouseEvent| {
+                    ev.stop_propagation();
+                    ev.prevent_default();
+                    toggle(BoardTool::Terrain);
+                }
+                style=move || btn_style(BoardTool::Terrain)
+            >
+                {TERRAIN_ICON}
+            </button>
+        </div>
+    }
+}
+
+/// Brush palette shown next to the toolbar while the terrain tool is active.
+#[component]
+pub fn TerrainPalette(
+    brush: RwSignal<Option<TerrainKind>>,
+    custom_color: RwSignal<String>,
+    custom_label: RwSignal<String>,
+    theme: Theme,
+) -> impl IntoView {
+    let i18n = use_i18n();
+    let stop = move |ev: MouseEvent| ev.stop_propagation();
+
+    let option_style = move |option: Option<TerrainKind>| {
+        let bg = if brush.get() == option {
+            theme.ui_button_primary
+        } else {
+            theme.ui_bg_secondary
+        };
+        format!(
+            "padding: 0.3rem 0.55rem; border: 1px solid {}; border-radius: 0.45rem; \
+             background: {}; color: {}; cursor: pointer; font-size: 0.78rem; text-align: left;",
+            theme.ui_border, bg, theme.ui_text_primary,
+        )
+    };
+
+    let option_label = move |option: Option<TerrainKind>| match option {
+        Some(TerrainKind::Difficult) => t_string!(i18n, scene_board.terrain_difficult),
+        Some(TerrainKind::Hazard) => t_string!(i18n, scene_board.terrain_hazard),
+        Some(TerrainKind::Water) => t_string!(i18n, scene_board.terrain_water),
+        Some(TerrainKind::Custom) => t_string!(i18n, scene_board.terrain_custom),
+        None => t_string!(i18n, scene_board.terrain_erase),
+    };
+
+    let options = [
+        Some(TerrainKind::Difficult),
+        Some(TerrainKind::Hazard),
+        Some(TerrainKind::Water),
+        Some(TerrainKind::Custom),
+        None,
+    ];
+
+    view! {
+        <div
+            on:mousedown=stop
+            on:pointerdown=stop_pointer_propagation
+            on:click=stop
+            style=format!(
+                "position: absolute; right: 4rem; bottom: 5rem; display: flex; flex-direction: column; \
+                 gap: 0.35rem; padding: 0.55rem; background: rgba(0,0,0,0.48); border: 1px solid {}; \
+                 border-radius: 0.65rem; backdrop-filter: blur(8px); z-index: 6; min-width: 9rem;",
+                theme.ui_border
+            )
+        >
+            {options.into_iter().map(|option| view! {
+                <button
+                    on:mousedown=move |ev: MouseEvent| {
+                        ev.prevent_default();
+                        brush.set(option);
+                    }
+                    style=move || option_style(option)
+                >
+                    {move || option_label(option)}
+                </button>
+            }).collect_view()}
+            <Show when=move || brush.get() == Some(TerrainKind::Custom)>
+                <div style="display: flex; gap: 0.35rem; align-items: center;">
+                    <input
+                        type="color"
+                        prop:value=move || custom_color.get()
+                        on:input=move |ev: Event| {
+                            let value = event_target_value(&ev);
+                            if is_hex_color(&value) {
+                                custom_color.set(value);
+                            }
+                        }
+                        style="width: 2rem; height: 1.7rem; padding: 0; border: none; background: none; cursor: pointer;"
+                    />
+                    <input
+                        type="text"
+                        maxlength="64"
+                        placeholder=move || t_string!(i18n, scene_board.terrain_label_placeholder)
+                        prop:value=move || custom_label.get()
+                        on:input=move |ev: Event| custom_label.set(event_target_value(&ev))
+                        style=format!(
+                            "width: 6rem; padding: 0.25rem 0.4rem; border: 1px solid {}; border-radius: 0.4rem; \
+                             background: {}; color: {}; font-size: 0.76rem;",
+                            theme.ui_border, theme.ui_bg_primary, theme.ui_text_primary
+                        )
+                    />
+                </div>
+            </Show>
+        </div>
+    }
+}
+
+/// Shared-camera controls: the follow picker for everyone, plus pulling and
+/// locking players' views for the game master.
+#[component]
+pub fn CameraControls(
+    /// Users whose camera can be followed.
+    users: Signal<Vec<String>>,
+    followed_user: RwSignal<Option<String>>,
+    is_game_master: Signal<bool>,
+    /// Whether the game master currently holds players' views.
+    locking: RwSignal<bool>,
+    /// Whether the local board is held still by the game master.
+    locked: Signal<bool>,
+    on_pull: Callback<()>,
+    theme: Theme,
+) -> impl IntoView {
+    let i18n = use_i18n();
+    let stop = move |ev: MouseEvent| ev.stop_propagation();
+
+    let button_style = move |is_active: bool| {
+        let bg = if is_active {
+            theme.ui_button_primary
+        } else {
+            theme.ui_bg_secondary
+        };
+        format!(
+            "padding: 0.25rem 0.55rem; border: 1px solid {}; border-radius: 0.45rem; \
+             background: {}; color: {}; cursor: pointer; font-size: 0.76rem; white-space: nowrap;",
+            theme.ui_border, bg, theme.ui_text_primary,
+        )
+    };
+
+    view! {
+        <div
+            on:mousedown=stop
+            on:pointerdown=stop_pointer_propagation
+            on:click=stop
+            on:wheel=move |ev: web_sys::WheelEvent| ev.stop_propagation()
+            style=format!(
+                "position: absolute; left: 1rem; bottom: 1rem; display: flex; align-items: center; \
+                 gap: 0.35rem; padding: 0.35rem; background: rgba(0,0,0,0.48); border: 1px solid {}; \
+                 border-radius: 0.65rem; backdrop-filter: blur(8px); z-index: 6;",
+                theme.ui_border
+            )
+        >
+            <Show when=move || locked.get()>
+                <span style=format!("color: {}; font-size: 0.76rem;", theme.ui_text_secondary)>
+                    {move || t_string!(i18n, scene_board.camera_locked)}
+                </span>
+            </Show>
+            <select
+                title=move || t_string!(i18n, scene_board.camera_follow_title)
+                prop:value=move || followed_user.get().unwrap_or_default()
+                on:change=move |ev: Event| {
+                    let value = event_target_value(&ev);
+                    followed_user.set((!value.is_empty()).then_some(value));
+                }
+                style=format!(
+                    "padding: 0.25rem 0.4rem; border: 1px solid {}; border-radius: 0.45rem; \
+                     background: {}; color: {}; font-size: 0.76rem;",
+                    theme.ui_border, theme.ui_bg_primary, theme.ui_text_primary
+                )
+            >
+                <option value="">{move || t_string!(i18n, scene_board.camera_follow_none)}</option>
+                {move || users.get().into_iter().map(|user| view! {
+                    <option value=user.clone()>{user.clone()}</option>
+                }).collect_view()}
+            </select>
+            <Show when=move || is_game_master.get()>
+                <button
+                    title=move || t_string!(i18n, scene_board.camera_pull_title)
+                    on:mousedown=move |ev: MouseEvent| {
+                        ev.prevent_default();
+                        on_pull.run(());
+                    }
+                    style=move || button_style(false)
+                >
+                    {move || t_string!(i18n, scene_board.camera_pull)}
+                </button>
+                <button
+                    title=move || t_string!(i18n, scene_board.camera_lock_title)
+                    on:mousedown=move |ev: MouseEvent| {
+                        ev.prevent_default();
+                        locking.update(|locking| *locking = !*locking);
+                    }
+                    style=move || button_style(locking.get())
+                >
+                    {move || if locking.get() {
+                        t_string!(i18n, scene_board.camera_release)
+                    } else {
+                        t_string!(i18n, scene_board.camera_lock)
+                    }}
+                </button>
+            </Show>
+        </div>
+    }
+}
+
+/// Frame times of the board renderer, plus a scripted pan and zoom benchmark
+/// whose results are kept per renderer so the two can be compared.
+#[component]
+pub fn RenderStatsPanel(
+    renderer: Signal<BoardRenderer>,
+    stats: RwSignal<RenderStats>,
+    /// Benchmark summaries by renderer, kept across renderer switches.
+    results: RwSignal<BTreeMap<BoardRenderer, RenderSummary>>,
+    running: Signal<bool>,
+    /// The benchmark moves the camera, which the game master's lock holds still.
+    locked: Signal<bool>,
+    on_benchmark: Callback<()>,
+    theme: Theme,
+) -> impl IntoView {
+    let i18n = use_i18n();
+    let stop = move |ev: MouseEvent| ev.stop_propagation();
+    let renderer_name = move |renderer: BoardRenderer| match renderer {
+        BoardRenderer::Dom => t_string!(i18n, settings.board_renderer_dom),
+        BoardRenderer::Canvas => t_string!(i18n, settings.board_renderer_canvas),
+    };
+    let summary_text = move |summary: RenderSummary| {
+        format!(
+            "{} {:.1} ms · p95 {:.1} ms · {} {}",
+            t_string!(i18n, scene_board.render_stats_average),
+            summary.average_ms,
+            summary.p95_ms,
+            summary.frames,
+            t_string!(i18n, scene_board.render_stats_frames)
+        )
+    };
+    let line_style = format!(
+        "color: {}; font-size: 0.74rem; white-space: nowrap;",
+        theme.ui_text_secondary
+    );
+    let result_style = line_style.clone();
+
+    view! {
+        <div
+            on:mousedown=stop
+            on:pointerdown=stop_pointer_propagation
+            on:click=stop
+            on:wheel=move |ev: web_sys::WheelEvent| ev.stop_propagation()
+            style=format!(
+                "position: absolute; left: 1rem; top: 1rem; display: flex; flex-direction: column; \
+                 gap: 0.3rem; padding: 0.45rem 0.6rem; background: rgba(0,0,0,0.48); border: 1px solid {}; \
+                 border-radius: 0.65rem; backdrop-filter: blur(8px); z-index: 6; font-variant-numeric: tabular-nums;",
+                theme.ui_border
+            )
+        >
+            <div style=format!("color: {}; font-size: 0.8rem; font-weight: 700;", theme.ui_text_primary)>
+                {move || format!(
+                    "{} · {}",
+                    t_string!(i18n, scene_board.render_stats_title),
+                    renderer_name(renderer.get())
+                )}
+            </div>
+            <div style=line_style>
+                {move || stats.with(RenderStats::summary).map_or_else(
+                    || t_string!(i18n, scene_board.render_stats_empty).to_string(),
+                    summary_text,
+                )}
+            </div>
+            {move || results.get().into_iter().map(|(renderer, summary)| view! {
+                <div style=result_style.clone()>
+                    {format!(
+                        "{} {}: {}",
+                        t_string!(i18n, scene_board.render_stats_benchmark),
+                        renderer_name(renderer),
+                        summary_text(summary)
+                    )}
+                </div>
+            }).collect_view()}
+            <button
+                title=move || t_string!(i18n, scene_board.render_stats_benchmark_title)
+                disabled=move || running.get() || locked.get()
+                on:click=move |_| on_benchmark.run(())
+                style=format!(
+                    "padding: 0.25rem 0.55rem; border: 1px solid {}; border-radius: 0.45rem; \
+                     background: {}; color: {}; cursor: pointer; font-size: 0.76rem;",
+                    theme.ui_border, theme.ui_bg_secondary, theme.ui_text_primary
+                )
+            >
+                {move || if running.get() {
+                    t_string!(i18n, scene_board.render_stats_benchmark_running)
+                } else {
+                    t_string!(i18n, scene_board.render_stats_benchmark)
+                }}
+            </button>
+        </div>
+    }
+}
+
+/// Game master's floor picker for a multi-floor scene, drawn above its header.
+#[component]
+pub fn FloorSwitcher(
+    levels: Vec<SceneLevel>,
+    /// Floor on display; `None` is the base floor.
+    selected: Option<String>,
+    /// Top-left of the scene header in screen coordinates.
+    screen_x: f64,
+    screen_y: f64,
+    on_select: Callback<Option<String>>,
+    theme: Theme,
+) -> impl IntoView {
+    let i18n = use_i18n();
+    let stop = move |ev: MouseEvent| ev.stop_propagation();
+
+    let floors = std::iter::once((None, t_string!(i18n, scene_board.base_floor).to_string()))
+        .chain(levels.into_iter().map(|level| (Some(level.id), level.name)))
+        .collect::<Vec<_>>();
+
+    view! {
+        <div
+            on:mousedown=stop
+            on:pointerdown=stop_pointer_propagation
+            on:click=stop
+            title=move || t_string!(i18n, scene_board.floor_switch_title)
+            style=format!(
+                "position: absolute; left: {:.2}px; top: {:.2}px; transform: translateY(calc(-100% - 0.4rem)); \
+                 display: flex; gap: 0.3rem; padding: 0.3rem; background: rgba(0,0,0,0.48); \
+                 border: 1px solid {}; border-radius: 0.6rem; backdrop-filter: blur(8px); z-index: 6; \
+                 pointer-events: auto;",
+                screen_x, screen_y, theme.ui_border
+            )
+        >
+            {floors.into_iter().map(|(level_id, name)| {
+                let bg = if level_id == selected {
+                    theme.ui_button_primary
+                } else {
+                    theme.ui_bg_secondary
+                };
+                view! {
+                    <button
+                        on:mousedown=move |ev: MouseEvent| {
+                            ev.prevent_default();
+                            on_select.run(level_id.clone());
+                        }
+                        style=format!(
+                            "padding: 0.2rem 0.5rem; border: 1px solid {}; border-radius: 0.4rem; \
+                             background: {}; color: {}; cursor: pointer; font-size: 0.74rem; white-space: nowrap;",
+                            theme.ui_border, bg, theme.ui_text_primary,
+                        )
+                    >
+                        {name}
+                    </button>
+                }
+            }).collect_view()}
         </div>
     }
 }
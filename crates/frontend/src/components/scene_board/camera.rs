@@ -0,0 +1,257 @@
+// Pure shared-camera helpers: converting cameras to and from broadcast views and
+// easing a board towards a pulled or followed view.
+// No signals, no Leptos, no web_sys.
+
+use super::model::{camera_centered_on, clamp_zoom};
+use shared::events::{BoardCameraMode, BoardCameraPayload, WorldPoint, acts_as_game_master};
+
+// --- Constants ---
+
+/// Minimum gap between two live camera broadcasts while someone follows.
+pub const CAMERA_SHARE_INTERVAL_MS: f64 = 150.0;
+/// How long a pulled board takes to glide to the game master's view.
+pub const CAMERA_PULL_DURATION_MS: f64 = 600.0;
+/// Followed cameras update often, so boards catch up quicker.
+pub const CAMERA_FOLLOW_DURATION_MS: f64 = 180.0;
+
+// --- Types ---
+
+/// A board view independent of viewport size: world centre and zoom.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CameraView {
+    pub center_x: f64,
+    pub center_y: f64,
+    pub zoom: f64,
+}
+
+/// Glide of the local camera from one view to another.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CameraGlide {
+    pub from: CameraView,
+    pub to: CameraView,
+    pub started_at_ms: f64,
+    pub duration_ms: f64,
+}
+
+// --- Pure functions ---
+
+/// View of a camera offset (`camera_x`, `camera_y`) at `zoom`.
+pub fn camera_view(camera_x: f64, camera_y: f64, zoom: f64) -> CameraView {
+    CameraView {
+        center_x: -camera_x / zoom,
+        center_y: -camera_y / zoom,
+        zoom,
+    }
+}
+
+/// Camera offset and zoom that show `view`.
+pub fn camera_transform(view: CameraView) -> (f64, f64, f64) {
+    let zoom = clamp_zoom(view.zoom);
+    let (camera_x, camera_y) = camera_centered_on(view.center_x, view.center_y, zoom);
+    (camera_x, camera_y, zoom)
+}
+
+pub fn camera_payload(
+    username: &str,
+    view: CameraView,
+    mode: BoardCameraMode,
+) -> BoardCameraPayload {
+    BoardCameraPayload {
+        username: username.to_string(),
+        center: WorldPoint {
+            x: view.center_x,
+            y: view.center_y,
+        },
+        zoom: view.zoom,
+        mode,
+    }
+}
+
+pub fn payload_view(payload: &BoardCameraPayload) -> CameraView {
+    CameraView {
+        center_x: payload.center.x,
+        center_y: payload.center.y,
+        zoom: clamp_zoom(payload.zoom),
+    }
+}
+
+/// Whether a received camera should move the local board. Pulls and locks are
+/// honoured from the game master only and never move the game master; live
+/// cameras move only the followers of their sender.
+pub fn camera_moves_board(
+    payload: &BoardCameraPayload,
+    followed_user: Option<&str>,
+    game_master: Option<&str>,
+    username: &str,
+) -> bool {
+    if payload.username == username {
+        return false;
+    }
+    match payload.mode {
+        BoardCameraMode::Follow => followed_user == Some(payload.username.as_str()),
+        BoardCameraMode::Pull | BoardCameraMode::Lock => {
+            game_master.is_some_and(|game_master| game_master == payload.username)
+                && !acts_as_game_master(game_master, username)
+        }
+        BoardCameraMode::Release => false,
+    }
+}
+
+/// Lock holder after a received camera: `Lock` from the game master sets it,
+/// `Release` from them clears it, anything else keeps `current`.
+pub fn camera_lock_after(
+    current: Option<String>,
+    payload: &BoardCameraPayload,
+    game_master: Option<&str>,
+) -> Option<String> {
+    if game_master != Some(payload.username.as_str()) {
+        return current;
+    }
+    match payload.mode {
+        BoardCameraMode::Lock => Some(payload.username.clone()),
+        BoardCameraMode::Release => None,
+        BoardCameraMode::Follow | BoardCameraMode::Pull => current,
+    }
+}
+
+/// Whether the local user may pan and zoom: everyone but the game master is
+/// held still while a lock is on.
+pub fn camera_input_locked(
+    lock_holder: Option<&str>,
+    game_master: Option<&str>,
+    username: &str,
+) -> bool {
+    lock_holder.is_some() && !acts_as_game_master(game_master, username)
+}
+
+/// View of a glide at `now_ms`, and whether the glide has finished. Eases out
+/// so boards slow down as they arrive.
+pub fn glide_view(glide: &CameraGlide, now_ms: f64) -> (CameraView, bool) {
+    let progress = if glide.duration_ms <= 0.0 {
+        1.0
+    } else {
+        ((now_ms - glide.started_at_ms) / glide.duration_ms).clamp(0.0, 1.0)
+    };
+    let eased = 1.0 - (1.0 - progress).powi(3);
+    let lerp = |from: f64, to: f64| from + (to - from) * eased;
+    let view = CameraView {
+        center_x: lerp(glide.from.center_x, glide.to.center_x),
+        center_y: lerp(glide.from.center_y, glide.to.center_y),
+        zoom: lerp(glide.from.zoom, glide.to.zoom),
+    };
+    (view, progress >= 1.0)
+}
+
+/// Whether a live camera broadcast is due.
+pub fn camera_share_due(last_shared_at_ms: Option<f64>, now_ms: f64) -> bool {
+    last_shared_at_ms.is_none_or(|last| now_ms - last >= CAMERA_SHARE_INTERVAL_MS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn payload(username: &str, mode: BoardCameraMode) -> BoardCameraPayload {
+        camera_payload(
+            username,
+            CameraView {
+                center_x: 100.0,
+                center_y: -40.0,
+                zoom: 2.0,
+            },
+            mode,
+        )
+    }
+
+    #[test]
+    fn camera_view_round_trips_through_transform() {
+        let view = camera_view(-200.0, 80.0, 2.0);
+        assert_eq!(
+            view,
+            CameraView {
+                center_x: 100.0,
+                center_y: -40.0,
+                zoom: 2.0,
+            }
+        );
+        assert_eq!(camera_transform(view), (-200.0, 80.0, 2.0));
+        assert_eq!(payload_view(&payload("gm", BoardCameraMode::Pull)), view);
+    }
+
+    #[test]
+    fn pulls_come_from_the_game_master_and_skip_them() {
+        let gm = Some("gm");
+        let pull = payload("gm", BoardCameraMode::Pull);
+        assert!(camera_moves_board(&pull, None, gm, "alice"));
+        assert!(!camera_moves_board(&pull, None, gm, "gm"));
+        assert!(!camera_moves_board(
+            &payload("bob", BoardCameraMode::Lock),
+            None,
+            gm,
+            "alice"
+        ));
+        assert!(!camera_moves_board(
+            &payload("gm", BoardCameraMode::Release),
+            None,
+            gm,
+            "alice"
+        ));
+
+        let live = payload("bob", BoardCameraMode::Follow);
+        assert!(camera_moves_board(&live, Some("bob"), gm, "alice"));
+        assert!(!camera_moves_board(&live, Some("gm"), gm, "alice"));
+        assert!(!camera_moves_board(&live, Some("bob"), gm, "bob"));
+    }
+
+    #[test]
+    fn locks_hold_players_until_released() {
+        let gm = Some("gm");
+        let locked = camera_lock_after(None, &payload("gm", BoardCameraMode::Lock), gm);
+        assert_eq!(locked.as_deref(), Some("gm"));
+        assert!(camera_input_locked(locked.as_deref(), gm, "alice"));
+        assert!(!camera_input_locked(locked.as_deref(), gm, "gm"));
+
+        let forged = camera_lock_after(None, &payload("bob", BoardCameraMode::Lock), gm);
+        assert_eq!(forged, None);
+
+        let still_locked =
+            camera_lock_after(locked.clone(), &payload("gm", BoardCameraMode::Pull), gm);
+        assert_eq!(still_locked, locked);
+        assert_eq!(
+            camera_lock_after(locked, &payload("gm", BoardCameraMode::Release), gm),
+            None
+        );
+    }
+
+    #[test]
+    fn glide_eases_to_the_target_and_finishes() {
+        let glide = CameraGlide {
+            from: CameraView {
+                center_x: 0.0,
+                center_y: 0.0,
+                zoom: 1.0,
+            },
+            to: CameraView {
+                center_x: 100.0,
+                center_y: 50.0,
+                zoom: 2.0,
+            },
+            started_at_ms: 1_000.0,
+            duration_ms: 500.0,
+        };
+        let (start, done) = glide_view(&glide, 1_000.0);
+        assert_eq!(start, glide.from);
+        assert!(!done);
+
+        let (halfway, _) = glide_view(&glide, 1_250.0);
+        assert!(halfway.center_x > 50.0 && halfway.center_x < 100.0);
+
+        let (end, done) = glide_view(&glide, 2_000.0);
+        assert_eq!(end, glide.to);
+        assert!(done);
+
+        assert!(camera_share_due(None, 0.0));
+        assert!(!camera_share_due(Some(1_000.0), 1_100.0));
+        assert!(camera_share_due(Some(1_000.0), 1_150.0));
+    }
+}
@@ -0,0 +1,727 @@
+// Pure canvas renderer model: the static board layers (board backgrounds,
+// terrain, grids and tokens) as keyed shape lists, the canvas regions a change
+// between two frames touches, and frame timings for comparing renderers.
+// No signals, no Leptos, no web_sys.
+
+use super::export::{ExportShape, GridLines, scene_base_shapes, token_shapes};
+use super::minimap::WorldRect;
+use super::model::{
+    TOKEN_ROTATION_HANDLE_RADIUS_PX, clamp_zoom, elevation_badge, grid_line_width_screen,
+    rotation_handle_center, scene_shows_contents, token_rect, workspace_board_metrics,
+};
+use shared::events::{Scene, Token};
+use std::collections::{HashMap, VecDeque};
+
+// --- Constants ---
+
+/// Matches the 1rem corner radius of DOM board tiles.
+pub const CANVAS_BOARD_RADIUS_PX: f64 = 16.0;
+/// Opacity of boards whose contents are hidden, as on DOM tiles.
+const HIDDEN_BOARD_OPACITY: f64 = 0.78;
+/// Minor grid lines are left out once cells get this small on screen.
+const MIN_MINOR_GRID_CELL_PX: f64 = 8.0;
+const TOKEN_BORDER_WIDTH_PX: f64 = 2.0;
+/// Device pixels added around dirty regions for strokes and anti-aliasing.
+const DIRTY_PADDING_PX: f64 = 3.0;
+/// Past this share of the canvas one full redraw beats many clipped ones.
+const FULL_REDRAW_AREA_SHARE: f64 = 0.5;
+/// Frames kept for the render statistics.
+pub const RENDER_STATS_WINDOW: usize = 120;
+/// Frames the scripted benchmark pans and zooms through.
+pub const BENCHMARK_FRAMES: u32 = 120;
+/// Radius of the benchmark's circular pan, in screen pixels.
+const BENCHMARK_PAN_RADIUS_PX: f64 = 240.0;
+
+// --- Types ---
+
+/// How the board draws its static layers; chosen in the settings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub enum BoardRenderer {
+    /// Every token, grid line and image is its own DOM element.
+    #[default]
+    Dom,
+    /// Backgrounds, grids and tokens are painted on one canvas.
+    Canvas,
+}
+
+impl BoardRenderer {
+    pub const ALL: [BoardRenderer; 2] = [BoardRenderer::Dom, BoardRenderer::Canvas];
+
+    pub fn storage_value(self) -> &'static str {
+        match self {
+            BoardRenderer::Dom => "dom",
+            BoardRenderer::Canvas => "canvas",
+        }
+    }
+
+    pub fn from_storage_value(value: &str) -> Option<Self> {
+        BoardRenderer::ALL
+            .into_iter()
+            .find(|renderer| renderer.storage_value() == value)
+    }
+}
+
+/// Camera and canvas size a frame is drawn for.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CanvasView {
+    pub camera_x: f64,
+    pub camera_y: f64,
+    pub zoom: f64,
+    pub viewport_width: f64,
+    pub viewport_height: f64,
+    /// Device pixels per CSS pixel.
+    pub pixel_ratio: f64,
+}
+
+impl CanvasView {
+    pub fn canvas_width(&self) -> u32 {
+        (self.viewport_width * self.pixel_ratio).round().max(1.0) as u32
+    }
+
+    pub fn canvas_height(&self) -> u32 {
+        (self.viewport_height * self.pixel_ratio).round().max(1.0) as u32
+    }
+
+    /// Canvas transform `(scale, offset_x, offset_y)` mapping world
+    /// coordinates to device pixels.
+    pub fn world_transform(&self) -> (f64, f64, f64) {
+        (
+            self.zoom * self.pixel_ratio,
+            (self.viewport_width / 2.0 + self.camera_x) * self.pixel_ratio,
+            (self.viewport_height / 2.0 + self.camera_y) * self.pixel_ratio,
+        )
+    }
+
+    fn canvas_rect(&self, rect: &WorldRect) -> CanvasRect {
+        let (scale, offset_x, offset_y) = self.world_transform();
+        CanvasRect {
+            x: rect.left * scale + offset_x,
+            y: rect.top * scale + offset_y,
+            width: rect.width() * scale,
+            height: rect.height() * scale,
+        }
+    }
+}
+
+/// Independently redrawable piece of a board: its base layer or one token.
+/// Shapes are in board pixels; `bounds` is everything they may touch.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CanvasItem {
+    pub key: String,
+    pub bounds: WorldRect,
+    pub shapes: Vec<ExportShape>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CanvasBoard {
+    /// Board rectangle in world coordinates; shapes are clipped to it.
+    pub rect: WorldRect,
+    pub fill: String,
+    /// Hidden contents: the background is blurred and tokens are left out.
+    pub blurred: bool,
+    pub opacity: f64,
+    pub base: CanvasItem,
+    pub tokens: Vec<CanvasItem>,
+}
+
+/// Everything the canvas shows, boards in drawing order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CanvasFrame {
+    pub view: CanvasView,
+    pub boards: Vec<CanvasBoard>,
+}
+
+/// Board state the canvas depends on besides the scenes themselves.
+#[derive(Debug, Clone, Copy)]
+pub struct CanvasBoardState<'a> {
+    pub active_scene_id: Option<&'a str>,
+    pub dragging_scene_id: Option<&'a str>,
+    pub show_inactive_contents: bool,
+    pub dragging_token_id: Option<&'a str>,
+    pub focused_token_id: Option<&'a str>,
+    pub board_fill: &'a str,
+    pub border: &'a str,
+    pub highlight: &'a str,
+}
+
+/// Canvas region in device pixels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CanvasRect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl CanvasRect {
+    fn right(&self) -> f64 {
+        self.x + self.width
+    }
+
+    fn bottom(&self) -> f64 {
+        self.y + self.height
+    }
+
+    fn overlaps(&self, other: &CanvasRect) -> bool {
+        self.x <= other.right()
+            && other.x <= self.right()
+            && self.y <= other.bottom()
+            && other.y <= self.bottom()
+    }
+
+    fn union(&self, other: &CanvasRect) -> CanvasRect {
+        let (x, y) = (self.x.min(other.x), self.y.min(other.y));
+        CanvasRect {
+            x,
+            y,
+            width: self.right().max(other.right()) - x,
+            height: self.bottom().max(other.bottom()) - y,
+        }
+    }
+}
+
+/// What has to be repainted to go from one frame to the next.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CanvasRedraw {
+    Nothing,
+    Full,
+    Regions(Vec<CanvasRect>),
+}
+
+/// Recent frame times of the board, for the renderer statistics.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RenderStats {
+    samples_ms: VecDeque<f64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RenderSummary {
+    pub frames: usize,
+    pub average_ms: f64,
+    pub p95_ms: f64,
+}
+
+impl RenderStats {
+    pub fn record(&mut self, frame_ms: f64) {
+        self.samples_ms.push_back(frame_ms.max(0.0));
+        while self.samples_ms.len() > RENDER_STATS_WINDOW {
+            self.samples_ms.pop_front();
+        }
+    }
+
+    pub fn summary(&self) -> Option<RenderSummary> {
+        if self.samples_ms.is_empty() {
+            return None;
+        }
+        let mut sorted = self.samples_ms.iter().copied().collect::<Vec<_>>();
+        sorted.sort_by(f64::total_cmp);
+        let p95_index = ((sorted.len() as f64 * 0.95).ceil() as usize).clamp(1, sorted.len()) - 1;
+        Some(RenderSummary {
+            frames: sorted.len(),
+            average_ms: sorted.iter().sum::<f64>() / sorted.len() as f64,
+            p95_ms: sorted[p95_index],
+        })
+    }
+}
+
+// --- Pure functions ---
+
+/// The frame for `scenes` (as drawn on the viewed floors), in the DOM
+/// renderer's stacking order: the active board above the rest and a board
+/// being dragged on top.
+pub fn canvas_frame(view: CanvasView, scenes: &[Scene], state: &CanvasBoardState) -> CanvasFrame {
+    let mut ordered = scenes.iter().collect::<Vec<_>>();
+    ordered.sort_by_key(|scene| {
+        (
+            state.dragging_scene_id == Some(scene.id.as_str()),
+            state.active_scene_id == Some(scene.id.as_str()),
+        )
+    });
+    CanvasFrame {
+        view,
+        boards: ordered
+            .into_iter()
+            .map(|scene| canvas_board(scene, view.zoom, state))
+            .collect(),
+    }
+}
+
+fn canvas_board(scene: &Scene, zoom: f64, state: &CanvasBoardState) -> CanvasBoard {
+    let (cell_size, width, height) = workspace_board_metrics(scene.grid.columns, scene.grid.rows);
+    let left = f64::from(scene.workspace_x) - width / 2.0;
+    let top = f64::from(scene.workspace_y) - height / 2.0;
+    let rect = WorldRect {
+        left,
+        top,
+        right: left + width,
+        bottom: top + height,
+    };
+    let shows_contents = scene_shows_contents(
+        &scene.id,
+        state.active_scene_id,
+        state.show_inactive_contents,
+    );
+    let base_shapes = if shows_contents {
+        let screen_cell = (cell_size * zoom).max(1.0);
+        scene_base_shapes(
+            scene,
+            Some(GridLines {
+                line_width: grid_line_width_screen(screen_cell) / zoom.max(f64::EPSILON),
+                minor: screen_cell >= MIN_MINOR_GRID_CELL_PX,
+            }),
+        )
+    } else {
+        // Hidden boards keep only their (blurred) background image.
+        scene_base_shapes(scene, None)
+            .into_iter()
+            .filter(|shape| matches!(shape, ExportShape::Image { .. }))
+            .collect()
+    };
+    let mut tokens = if shows_contents {
+        scene.tokens.iter().collect::<Vec<_>>()
+    } else {
+        Vec::new()
+    };
+    // The dragged token is drawn last, above its neighbours.
+    tokens.sort_by_key(|token| state.dragging_token_id == Some(token.id.as_str()));
+
+    CanvasBoard {
+        rect,
+        fill: state.board_fill.to_string(),
+        blurred: !shows_contents,
+        opacity: if shows_contents {
+            1.0
+        } else {
+            HIDDEN_BOARD_OPACITY
+        },
+        base: CanvasItem {
+            key: scene.id.clone(),
+            bounds: rect,
+            shapes: base_shapes,
+        },
+        tokens: tokens
+            .into_iter()
+            .map(|token| canvas_token(scene, token, cell_size, left, top, state))
+            .collect(),
+    }
+}
+
+fn canvas_token(
+    scene: &Scene,
+    token: &Token,
+    cell_size: f64,
+    board_left: f64,
+    board_top: f64,
+    state: &CanvasBoardState,
+) -> CanvasItem {
+    let (left, top, width, height) = token_rect(
+        0.0,
+        0.0,
+        cell_size,
+        token.x,
+        token.y,
+        token.width_cells,
+        token.height_cells,
+    );
+    let is_focused = state.focused_token_id == Some(token.id.as_str());
+    let is_highlighted = is_focused || state.dragging_token_id == Some(token.id.as_str());
+    let mut shapes = token_shapes(token, cell_size);
+    shapes.push(ExportShape::Outline {
+        x: left,
+        y: top,
+        width,
+        height,
+        stroke: if is_highlighted {
+            state.highlight
+        } else {
+            state.border
+        }
+        .to_string(),
+        line_width: TOKEN_BORDER_WIDTH_PX,
+        corner_radius: (cell_size * 0.18).clamp(8.0, 16.0),
+    });
+    if let Some(badge) = elevation_badge(token.elevation_feet) {
+        let font_px = (cell_size * 0.18).clamp(11.0, 15.0) * 0.85;
+        let badge_width = font_px * 0.62 * badge.chars().count() as f64 + 8.0;
+        shapes.push(ExportShape::Rect {
+            x: left + width - badge_width - 4.0,
+            y: top + 4.0,
+            width: badge_width,
+            height: font_px * 1.5,
+            fill: "rgba(0,0,0,0.62)".to_string(),
+            opacity: 1.0,
+            corner_radius: 5.0,
+        });
+        shapes.push(ExportShape::Text {
+            x: left + width - badge_width / 2.0 - 4.0,
+            y: top + 4.0 + font_px * 0.75,
+            text: badge,
+            font_px,
+            color: "#f4efe6".to_string(),
+            bold: true,
+            centered: true,
+            max_width: badge_width,
+        });
+    }
+
+    // Rotated images reach past the token box up to its half diagonal.
+    let (center_x, center_y) = (left + width / 2.0, top + height / 2.0);
+    let reach = width.hypot(height) / 2.0;
+    let mut bounds = WorldRect {
+        left: board_left + center_x - reach,
+        top: board_top + center_y - reach,
+        right: board_left + center_x + reach,
+        bottom: board_top + center_y + reach,
+    };
+    if is_focused {
+        let (handle_x, handle_y) =
+            rotation_handle_center(left, top, width, height, token.rotation_deg);
+        shapes.push(ExportShape::Circle {
+            x: handle_x,
+            y: handle_y,
+            radius: TOKEN_ROTATION_HANDLE_RADIUS_PX,
+            fill: state.highlight.to_string(),
+            opacity: 1.0,
+        });
+        let handle_reach = TOKEN_ROTATION_HANDLE_RADIUS_PX + TOKEN_BORDER_WIDTH_PX;
+        bounds = bounds.union(&WorldRect {
+            left: board_left + handle_x - handle_reach,
+            top: board_top + handle_y - handle_reach,
+            right: board_left + handle_x + handle_reach,
+            bottom: board_top + handle_y + handle_reach,
+        });
+    }
+    CanvasItem {
+        key: format!("{}/{}", scene.id, token.id),
+        bounds,
+        shapes,
+    }
+}
+
+/// Regions to repaint between two frames. Camera, canvas size or board order
+/// changes repaint everything; otherwise only the old and new bounds of the
+/// items that changed, merged where they overlap.
+pub fn canvas_redraw(previous: Option<&CanvasFrame>, next: &CanvasFrame) -> CanvasRedraw {
+    let Some(previous) = previous else {
+        return CanvasRedraw::Full;
+    };
+    let board_keys = |frame: &CanvasFrame| {
+        frame
+            .boards
+            .iter()
+            .map(|board| board.base.key.clone())
+            .collect::<Vec<_>>()
+    };
+    if previous.view != next.view || board_keys(previous) != board_keys(next) {
+        return CanvasRedraw::Full;
+    }
+
+    let mut dirty = Vec::new();
+    for (old, new) in previous.boards.iter().zip(&next.boards) {
+        let board_changed = old.rect != new.rect
+            || old.fill != new.fill
+            || old.blurred != new.blurred
+            || old.opacity != new.opacity
+            || old.base != new.base
+            || old
+                .tokens
+                .iter()
+                .map(|token| &token.key)
+                .ne(new.tokens.iter().map(|token| &token.key));
+        if board_changed {
+            dirty.push(old.rect);
+            dirty.push(new.rect);
+            continue;
+        }
+        let old_tokens = old
+            .tokens
+            .iter()
+            .map(|token| (token.key.as_str(), token))
+            .collect::<HashMap<_, _>>();
+        for token in &new.tokens {
+            match old_tokens.get(token.key.as_str()) {
+                Some(old_token) if *old_token == token => {}
+                Some(old_token) => {
+                    dirty.push(old_token.bounds);
+                    dirty.push(token.bounds);
+                }
+                None => dirty.push(token.bounds),
+            }
+        }
+    }
+
+    let canvas = CanvasRect {
+        x: 0.0,
+        y: 0.0,
+        width: f64::from(next.view.canvas_width()),
+        height: f64::from(next.view.canvas_height()),
+    };
+    let regions = merge_canvas_rects(
+        dirty
+            .iter()
+            .map(|rect| pad_canvas_rect(next.view.canvas_rect(rect)))
+            .filter_map(|rect| clip_canvas_rect(&rect, &canvas))
+            .collect(),
+    );
+    if regions.is_empty() {
+        return CanvasRedraw::Nothing;
+    }
+    let area = regions
+        .iter()
+        .map(|rect| rect.width * rect.height)
+        .sum::<f64>();
+    if area > canvas.width * canvas.height * FULL_REDRAW_AREA_SHARE {
+        CanvasRedraw::Full
+    } else {
+        CanvasRedraw::Regions(regions)
+    }
+}
+
+/// Whether a region needs an item with these world bounds repainted.
+pub fn canvas_item_in_region(view: &CanvasView, bounds: &WorldRect, region: &CanvasRect) -> bool {
+    view.canvas_rect(bounds).overlaps(region)
+}
+
+fn pad_canvas_rect(rect: CanvasRect) -> CanvasRect {
+    CanvasRect {
+        x: (rect.x - DIRTY_PADDING_PX).floor(),
+        y: (rect.y - DIRTY_PADDING_PX).floor(),
+        width: (rect.width + DIRTY_PADDING_PX * 2.0).ceil() + 1.0,
+        height: (rect.height + DIRTY_PADDING_PX * 2.0).ceil() + 1.0,
+    }
+}
+
+fn clip_canvas_rect(rect: &CanvasRect, canvas: &CanvasRect) -> Option<CanvasRect> {
+    let (x, y) = (rect.x.max(canvas.x), rect.y.max(canvas.y));
+    let (right, bottom) = (
+        rect.right().min(canvas.right()),
+        rect.bottom().min(canvas.bottom()),
+    );
+    (right > x && bottom > y).then_some(CanvasRect {
+        x,
+        y,
+        width: right - x,
+        height: bottom - y,
+    })
+}
+
+/// Unions overlapping rectangles until none overlap.
+pub fn merge_canvas_rects(mut rects: Vec<CanvasRect>) -> Vec<CanvasRect> {
+    let mut merged = true;
+    while merged {
+        merged = false;
+        'outer: for first in 0..rects.len() {
+            for second in first + 1..rects.len() {
+                if rects[first].overlaps(&rects[second]) {
+                    let other = rects.swap_remove(second);
+                    rects[first] = rects[first].union(&other);
+                    merged = true;
+                    break 'outer;
+                }
+            }
+        }
+    }
+    rects
+}
+
+/// Camera for one benchmark frame: a circular pan around the starting view
+/// with a gentle zoom swing, so every frame repaints the whole board.
+pub fn benchmark_camera(start: (f64, f64, f64), frame: u32) -> (f64, f64, f64) {
+    let (camera_x, camera_y, zoom) = start;
+    let angle = f64::from(frame) / f64::from(BENCHMARK_FRAMES) * std::f64::consts::TAU;
+    (
+        camera_x + BENCHMARK_PAN_RADIUS_PX * (angle.cos() - 1.0),
+        camera_y + BENCHMARK_PAN_RADIUS_PX * angle.sin(),
+        clamp_zoom(zoom * (1.0 + 0.15 * (angle * 2.0).sin())),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::events::{FileRef, SceneGrid};
+
+    fn token(id: &str, x: f32) -> Token {
+        Token {
+            id: id.to_string(),
+            name: id.to_string(),
+            image: FileRef {
+                hash: "hash".to_string(),
+                mime_type: "image/png".to_string(),
+                file_name: "token.png".to_string(),
+                size: 1,
+            },
+            x,
+            y: 1.0,
+            width_cells: 1,
+            height_cells: 1,
+            rotation_deg: 0.0,
+            elevation_feet: 0,
+            owner: None,
+            auras: Vec::new(),
+            level_id: None,
+        }
+    }
+
+    fn scene(id: &str, tokens: Vec<Token>) -> Scene {
+        Scene {
+            id: id.to_string(),
+            name: id.to_string(),
+            grid: SceneGrid {
+                columns: 20,
+                rows: 20,
+                cell_size_feet: 5,
+            },
+            workspace_x: 0.0,
+            workspace_y: 0.0,
+            background: None,
+            background_scale: 1.0,
+            background_offset_x: 0.0,
+            background_offset_y: 0.0,
+            background_rotation_deg: 0.0,
+            tokens,
+            terrain: Vec::new(),
+            walls: Vec::new(),
+            doors: Vec::new(),
+            lights: Vec::new(),
+            folder: None,
+            sort_index: 0,
+            levels: Vec::new(),
+            stairs: Vec::new(),
+        }
+    }
+
+    fn view(camera_x: f64) -> CanvasView {
+        CanvasView {
+            camera_x,
+            camera_y: 0.0,
+            zoom: 1.0,
+            viewport_width: 1_600.0,
+            viewport_height: 1_000.0,
+            pixel_ratio: 1.0,
+        }
+    }
+
+    fn state(active: &str) -> CanvasBoardState<'_> {
+        CanvasBoardState {
+            active_scene_id: Some(active),
+            dragging_scene_id: None,
+            show_inactive_contents: false,
+            dragging_token_id: None,
+            focused_token_id: None,
+            board_fill: "#2a2a2a",
+            border: "#444",
+            highlight: "#2563eb",
+        }
+    }
+
+    #[test]
+    fn renderer_choice_round_trips_through_storage() {
+        for renderer in BoardRenderer::ALL {
+            assert_eq!(
+                BoardRenderer::from_storage_value(renderer.storage_value()),
+                Some(renderer)
+            );
+        }
+        assert_eq!(BoardRenderer::from_storage_value("webgl"), None);
+    }
+
+    #[test]
+    fn frames_follow_the_dom_stacking_and_hide_inactive_contents() {
+        let scenes = vec![
+            scene("active", vec![token("hero", 2.0)]),
+            scene("other", vec![token("goblin", 3.0)]),
+        ];
+        let frame = canvas_frame(view(0.0), &scenes, &state("active"));
+        let keys = frame
+            .boards
+            .iter()
+            .map(|board| board.base.key.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(keys, vec!["other", "active"]);
+        assert!(frame.boards[0].blurred && frame.boards[0].tokens.is_empty());
+        assert_eq!(frame.boards[1].tokens.len(), 1);
+    }
+
+    #[test]
+    fn moving_a_token_only_repaints_its_old_and_new_place() {
+        let before = canvas_frame(
+            view(0.0),
+            &[scene(
+                "active",
+                vec![token("hero", 2.0), token("goblin", 12.0)],
+            )],
+            &state("active"),
+        );
+        assert_eq!(canvas_redraw(None, &before), CanvasRedraw::Full);
+        assert_eq!(canvas_redraw(Some(&before), &before), CanvasRedraw::Nothing);
+
+        let after = canvas_frame(
+            view(0.0),
+            &[scene(
+                "active",
+                vec![token("hero", 3.0), token("goblin", 12.0)],
+            )],
+            &state("active"),
+        );
+        let CanvasRedraw::Regions(regions) = canvas_redraw(Some(&before), &after) else {
+            panic!("a token move should repaint regions");
+        };
+        // Old and new places overlap, so they merge into one region.
+        assert_eq!(regions.len(), 1);
+        let goblin = &after.boards[0].tokens[1];
+        assert!(!canvas_item_in_region(
+            &after.view,
+            &goblin.bounds,
+            &regions[0]
+        ));
+        let hero = &after.boards[0].tokens[0];
+        assert!(canvas_item_in_region(
+            &after.view,
+            &hero.bounds,
+            &regions[0]
+        ));
+
+        let panned = canvas_frame(
+            view(10.0),
+            &[scene(
+                "active",
+                vec![token("hero", 3.0), token("goblin", 12.0)],
+            )],
+            &state("active"),
+        );
+        assert_eq!(canvas_redraw(Some(&after), &panned), CanvasRedraw::Full);
+    }
+
+    #[test]
+    fn overlapping_regions_merge() {
+        let rect = |x: f64, y: f64| CanvasRect {
+            x,
+            y,
+            width: 10.0,
+            height: 10.0,
+        };
+        let merged = merge_canvas_rects(vec![rect(0.0, 0.0), rect(50.0, 50.0), rect(5.0, 5.0)]);
+        assert_eq!(merged.len(), 2);
+        assert!(merged.contains(&CanvasRect {
+            x: 0.0,
+            y: 0.0,
+            width: 15.0,
+            height: 15.0,
+        }));
+    }
+
+    #[test]
+    fn render_stats_keep_a_rolling_window() {
+        let mut stats = RenderStats::default();
+        assert_eq!(stats.summary(), None);
+        for frame in 0..(RENDER_STATS_WINDOW + 20) {
+            stats.record(if frame % 10 == 0 { 20.0 } else { 5.0 });
+        }
+        let summary = stats.summary().unwrap();
+        assert_eq!(summary.frames, RENDER_STATS_WINDOW);
+        assert_eq!(summary.p95_ms, 20.0);
+        assert!((summary.average_ms - 6.5).abs() < 1e-9);
+        assert_eq!(benchmark_camera((0.0, 0.0, 1.0), 0), (0.0, 0.0, 1.0));
+    }
+}
@@ -0,0 +1,433 @@
+// Canvas renderer: one viewport-sized canvas painting `CanvasFrame`s, redrawing
+// only the regions `canvas_redraw` reports. The canvas outlives the board's
+// re-renders and is moved into each new host element, keeping its pixels.
+// `BoardCanvasState` wires it, the frame timings and the benchmark to the board.
+
+use super::canvas::{
+    BENCHMARK_FRAMES, BoardRenderer, CANVAS_BOARD_RADIUS_PX, CanvasBoard, CanvasBoardState,
+    CanvasFrame, CanvasRect, CanvasRedraw, CanvasView, RenderStats, RenderSummary,
+    benchmark_camera, canvas_frame, canvas_item_in_region, canvas_redraw,
+};
+use super::export::ExportShape;
+use super::export_panel::{draw_shape, load_image, rounded_rect_path};
+use super::view_model::SceneBoardViewModel;
+use crate::config::Theme;
+use leptos::html;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use leptos::wasm_bindgen::{JsCast, JsValue};
+use shared::events::Scene;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::time::Duration;
+use web_sys::{CanvasRenderingContext2d, Element, HtmlCanvasElement, HtmlImageElement};
+
+/// Blur of boards with hidden contents, in board pixels, as on DOM tiles.
+const HIDDEN_BOARD_BLUR_PX: f64 = 6.0;
+
+/// Renderer state owned by the board: the host the canvas moves into (the
+/// board re-creates it on every render), frame timings and benchmark results.
+#[derive(Clone, Copy)]
+pub struct BoardCanvasState {
+    pub renderer: RwSignal<BoardRenderer>,
+    pub host: NodeRef<html::Div>,
+    pub stats: RwSignal<RenderStats>,
+    pub benchmark_running: RwSignal<bool>,
+    pub benchmark_results: RwSignal<BTreeMap<BoardRenderer, RenderSummary>>,
+    show_stats: RwSignal<bool>,
+    images_loaded: RwSignal<u32>,
+    probe_pending: StoredValue<bool>,
+}
+
+impl BoardCanvasState {
+    pub fn new(renderer: RwSignal<BoardRenderer>, show_stats: RwSignal<bool>) -> Self {
+        Self {
+            renderer,
+            host: NodeRef::new(),
+            stats: RwSignal::new(RenderStats::default()),
+            benchmark_running: RwSignal::new(false),
+            benchmark_results: RwSignal::new(BTreeMap::new()),
+            show_stats,
+            images_loaded: RwSignal::new(0),
+            probe_pending: StoredValue::new(false),
+        }
+    }
+
+    pub fn is_canvas(self) -> bool {
+        self.renderer.get() == BoardRenderer::Canvas
+    }
+
+    /// Whether the statistics panel is on screen.
+    pub fn shows_stats(self) -> bool {
+        self.show_stats.get() || self.benchmark_running.get()
+    }
+
+    /// Frame time: from a board update until the browser has painted it. Only
+    /// sampled while the statistics are shown or a benchmark runs.
+    pub fn start_probe(self) {
+        let measuring = self.show_stats.get_untracked() || self.benchmark_running.get_untracked();
+        if !measuring || self.probe_pending.get_value() {
+            return;
+        }
+        self.probe_pending.set_value(true);
+        let started_at_ms = performance_now();
+        after_next_paint(move || {
+            self.probe_pending.set_value(false);
+            self.stats
+                .update(|stats| stats.record(performance_now() - started_at_ms));
+        });
+    }
+
+    /// Scripted pan and zoom; the summary is kept per renderer for comparison
+    /// and the camera returns to where it was.
+    pub fn run_benchmark(self, vm: SceneBoardViewModel) {
+        if self.benchmark_running.get_untracked() {
+            return;
+        }
+        let start = (
+            vm.camera_x.get_untracked(),
+            vm.camera_y.get_untracked(),
+            vm.zoom.get_untracked(),
+        );
+        let renderer = self.renderer.get_untracked();
+        self.stats.set(RenderStats::default());
+        self.benchmark_running.set(true);
+        spawn_local(async move {
+            for frame in 1..=BENCHMARK_FRAMES {
+                let (camera_x, camera_y, zoom) = benchmark_camera(start, frame);
+                vm.set_view_transform(camera_x, camera_y, zoom);
+                // Let the board render and start its probe before waiting,
+                // so the probe's paint callback runs ahead of ours.
+                gloo_timers::future::TimeoutFuture::new(0).await;
+                next_paint().await;
+            }
+            if let Some(summary) = self.stats.with_untracked(RenderStats::summary) {
+                self.benchmark_results.update(|results| {
+                    results.insert(renderer, summary);
+                });
+            }
+            vm.set_view_transform(start.0, start.1, start.2);
+            self.benchmark_running.set(false);
+        });
+    }
+
+    /// Paints the static layers whenever the board changes; the DOM keeps
+    /// handles, borders, auras, notes and every overlay.
+    pub fn paint_board(
+        self,
+        vm: SceneBoardViewModel,
+        scenes: Signal<Vec<Scene>>,
+        active_scene_id: Memo<Option<String>>,
+        show_inactive_contents: RwSignal<bool>,
+        file_urls: RwSignal<HashMap<String, String>>,
+        theme: &Theme,
+    ) {
+        let (board_fill, border, highlight) = (
+            theme.ui_bg_primary,
+            theme.ui_border,
+            theme.ui_button_primary,
+        );
+        Effect::new(move |_| {
+            if !self.is_canvas() {
+                release_board_canvas();
+                return;
+            }
+            self.images_loaded.track();
+            self.start_probe();
+            let active_id = active_scene_id.get();
+            let dragging_scene_id = vm.dragging_scene_id.get();
+            let dragging_token_id = vm.dragging_token_id.get();
+            let focused_token_id = vm.focused_token_id.get();
+            let state = CanvasBoardState {
+                active_scene_id: active_id.as_deref(),
+                dragging_scene_id: dragging_scene_id.as_deref(),
+                show_inactive_contents: show_inactive_contents.get(),
+                dragging_token_id: dragging_token_id.as_deref(),
+                focused_token_id: focused_token_id.as_deref(),
+                board_fill,
+                border,
+                highlight,
+            };
+            let view = CanvasView {
+                camera_x: vm.camera_x.get(),
+                camera_y: vm.camera_y.get(),
+                zoom: vm.zoom.get(),
+                viewport_width: vm.viewport_width.get(),
+                viewport_height: vm.viewport_height.get(),
+                pixel_ratio: window().device_pixel_ratio(),
+            };
+            let frame = scenes.with(|items| canvas_frame(view, items, &state));
+            let images_loaded = self.images_loaded;
+            file_urls.with(|urls| {
+                paint_board_canvas(frame, urls, move || {
+                    images_loaded.update(|loaded| *loaded += 1);
+                });
+            });
+        });
+        Effect::new(move |_| {
+            if let Some(host) = self.host.get() {
+                attach_board_canvas(&host);
+            }
+        });
+        on_cleanup(release_board_canvas);
+    }
+}
+
+struct BoardCanvas {
+    canvas: HtmlCanvasElement,
+    context: CanvasRenderingContext2d,
+    images: HashMap<String, HtmlImageElement>,
+    /// Image hashes already loading or loaded, so failures are not retried.
+    requested_images: HashSet<String>,
+    painted: Option<CanvasFrame>,
+    painted_images: usize,
+}
+
+thread_local! {
+    // Canvas elements are not `Send`, so the canvas lives outside the reactive
+    // graph; there is a single board per page.
+    static BOARD_CANVAS: RefCell<Option<BoardCanvas>> = const { RefCell::new(None) };
+}
+
+/// Moves the board canvas into `host`, creating it on first use.
+fn attach_board_canvas(host: &Element) {
+    let result = with_board_canvas(|board| {
+        host.append_child(&board.canvas)
+            .map(|_| ())
+            .map_err(|error| format!("failed to attach board canvas: {error:?}"))
+    });
+    if let Err(error) = result.and_then(|result| result) {
+        log::warn!("{error}");
+    }
+}
+
+/// Drops the canvas and its images, e.g. when switching back to the DOM renderer.
+fn release_board_canvas() {
+    BOARD_CANVAS.with(|slot| {
+        if let Some(board) = slot.borrow_mut().take() {
+            board.canvas.remove();
+        }
+    });
+}
+
+/// Paints `frame`, repainting only what changed since the last one. Images
+/// still loading are left out; `on_image_loaded` runs once each arrives so
+/// the caller can paint again.
+fn paint_board_canvas(
+    frame: CanvasFrame,
+    file_urls: &HashMap<String, String>,
+    on_image_loaded: impl Fn() + Clone + 'static,
+) {
+    let result = with_board_canvas(|board| {
+        board.request_images(&frame, file_urls, on_image_loaded);
+        board
+            .paint(frame)
+            .map_err(|error| format!("failed to paint board canvas: {error:?}"))
+    });
+    if let Err(error) = result.and_then(|result| result) {
+        log::warn!("{error}");
+    }
+}
+
+fn with_board_canvas<R>(paint: impl FnOnce(&mut BoardCanvas) -> R) -> Result<R, String> {
+    BOARD_CANVAS.with(|slot| {
+        let mut slot = slot.borrow_mut();
+        if slot.is_none() {
+            *slot = Some(create_board_canvas()?);
+        }
+        Ok(paint(slot.as_mut().expect("board canvas was just created")))
+    })
+}
+
+fn create_board_canvas() -> Result<BoardCanvas, String> {
+    let canvas: HtmlCanvasElement = document()
+        .create_element("canvas")
+        .map_err(|error| format!("failed to create board canvas: {error:?}"))?
+        .dyn_into()
+        .map_err(|_| "board canvas has an unexpected type".to_string())?;
+    let _ = canvas.set_attribute(
+        "style",
+        "position: absolute; inset: 0; width: 100%; height: 100%; display: block;",
+    );
+    let context: CanvasRenderingContext2d = canvas
+        .get_context("2d")
+        .map_err(|error| format!("failed to get board canvas context: {error:?}"))?
+        .ok_or_else(|| "board canvas 2d context is unavailable".to_string())?
+        .dyn_into()
+        .map_err(|_| "board canvas context has an unexpected type".to_string())?;
+    Ok(BoardCanvas {
+        canvas,
+        context,
+        images: HashMap::new(),
+        requested_images: HashSet::new(),
+        painted: None,
+        painted_images: 0,
+    })
+}
+
+impl BoardCanvas {
+    fn request_images(
+        &mut self,
+        frame: &CanvasFrame,
+        file_urls: &HashMap<String, String>,
+        on_image_loaded: impl Fn() + Clone + 'static,
+    ) {
+        let hashes = frame
+            .boards
+            .iter()
+            .flat_map(|board| std::iter::once(&board.base).chain(&board.tokens))
+            .flat_map(|item| &item.shapes)
+            .filter_map(|shape| match shape {
+                ExportShape::Image { hash, .. } => Some(hash),
+                _ => None,
+            });
+        for hash in hashes {
+            if self.requested_images.contains(hash) {
+                continue;
+            }
+            // Files still downloading are requested again on a later paint.
+            let Some(url) = file_urls.get(hash).cloned() else {
+                continue;
+            };
+            self.requested_images.insert(hash.clone());
+            let hash = hash.clone();
+            let on_image_loaded = on_image_loaded.clone();
+            spawn_local(async move {
+                match load_image(&url).await {
+                    Ok(image) => {
+                        BOARD_CANVAS.with(|slot| {
+                            if let Some(board) = slot.borrow_mut().as_mut() {
+                                board.images.insert(hash, image);
+                            }
+                        });
+                        on_image_loaded();
+                    }
+                    Err(error) => log::warn!("board canvas image {hash}: {error}"),
+                }
+            });
+        }
+    }
+
+    fn paint(&mut self, frame: CanvasFrame) -> Result<(), JsValue> {
+        let (width, height) = (frame.view.canvas_width(), frame.view.canvas_height());
+        let resized = self.canvas.width() != width || self.canvas.height() != height;
+        if resized {
+            self.canvas.set_width(width);
+            self.canvas.set_height(height);
+        }
+        let redraw = if resized || self.painted_images != self.images.len() {
+            CanvasRedraw::Full
+        } else {
+            canvas_redraw(self.painted.as_ref(), &frame)
+        };
+        let regions = match redraw {
+            CanvasRedraw::Nothing => Vec::new(),
+            CanvasRedraw::Full => vec![CanvasRect {
+                x: 0.0,
+                y: 0.0,
+                width: f64::from(width),
+                height: f64::from(height),
+            }],
+            CanvasRedraw::Regions(regions) => regions,
+        };
+        for region in &regions {
+            self.paint_region(&frame, region)?;
+        }
+        self.painted = Some(frame);
+        self.painted_images = self.images.len();
+        Ok(())
+    }
+
+    fn paint_region(&self, frame: &CanvasFrame, region: &CanvasRect) -> Result<(), JsValue> {
+        let context = &self.context;
+        context.save();
+        context.set_transform(1.0, 0.0, 0.0, 1.0, 0.0, 0.0)?;
+        context.clear_rect(region.x, region.y, region.width, region.height);
+        context.begin_path();
+        context.rect(region.x, region.y, region.width, region.height);
+        context.clip();
+        let result = frame
+            .boards
+            .iter()
+            .filter(|board| canvas_item_in_region(&frame.view, &board.rect, region))
+            .try_for_each(|board| self.paint_board(frame, board, region));
+        context.restore();
+        result
+    }
+
+    fn paint_board(
+        &self,
+        frame: &CanvasFrame,
+        board: &CanvasBoard,
+        region: &CanvasRect,
+    ) -> Result<(), JsValue> {
+        let context = &self.context;
+        let (scale, offset_x, offset_y) = frame.view.world_transform();
+        context.save();
+        context.set_transform(
+            scale,
+            0.0,
+            0.0,
+            scale,
+            offset_x + board.rect.left * scale,
+            offset_y + board.rect.top * scale,
+        )?;
+        context.set_global_alpha(board.opacity);
+        rounded_rect_path(
+            context,
+            0.0,
+            0.0,
+            board.rect.width(),
+            board.rect.height(),
+            CANVAS_BOARD_RADIUS_PX,
+        )?;
+        context.set_fill_style_str(&board.fill);
+        context.fill();
+        context.clip();
+        if board.blurred {
+            // Canvas filters work in device pixels, unlike the scaled DOM tile.
+            context.set_filter(&format!(
+                "blur({:.2}px) saturate(0.72) brightness(0.7)",
+                HIDDEN_BOARD_BLUR_PX * scale
+            ));
+        }
+        let result = board
+            .base
+            .shapes
+            .iter()
+            .try_for_each(|shape| draw_shape(context, shape, &self.images))
+            .and_then(|()| {
+                board
+                    .tokens
+                    .iter()
+                    .filter(|token| canvas_item_in_region(&frame.view, &token.bounds, region))
+                    .flat_map(|token| &token.shapes)
+                    .try_for_each(|shape| draw_shape(context, shape, &self.images))
+            });
+        context.restore();
+        result
+    }
+}
+
+// --- Render timing ---
+
+/// Milliseconds on the page's high-resolution clock.
+fn performance_now() -> f64 {
+    window()
+        .performance()
+        .map_or_else(js_sys::Date::now, |performance| performance.now())
+}
+
+/// Runs `callback` once the browser has painted the next frame: from a task
+/// queued by the next animation frame, behind that frame's rendering.
+fn after_next_paint(callback: impl FnOnce() + 'static) {
+    request_animation_frame(move || set_timeout(callback, Duration::ZERO));
+}
+
+async fn next_paint() {
+    let (sender, receiver) = futures::channel::oneshot::channel();
+    after_next_paint(move || {
+        let _ = sender.send(());
+    });
+    let _ = receiver.await;
+}
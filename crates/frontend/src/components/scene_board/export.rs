@@ -0,0 +1,678 @@
+// Pure scene export plan: what a PNG snapshot of one scene contains and where,
+// in board pixels. The PNG export and the board's canvas renderer only replay
+// the shapes in order.
+// No signals, no Leptos, no web_sys.
+
+use super::levels::stairs_go_up;
+use super::model::{
+    BOARD_NOTE_PIN_SIZE_PX, TERRAIN_OVERLAY_OPACITY, board_note_world_position,
+    grid_line_width_screen, terrain_fill_color, token_rect, workspace_board_metrics,
+};
+use crate::components::notes::model::note_heading_and_body;
+use shared::events::{NotePayload, NoteVisibility, Scene, TerrainKind, Token};
+
+// --- Constants ---
+
+/// Export resolutions offered, as multiples of the board's own pixels.
+pub const EXPORT_SCALES: [f64; 3] = [1.0, 2.0, 4.0];
+pub const EXPORT_DEFAULT_SCALE: f64 = 2.0;
+/// Browsers refuse canvases much larger than this on either side.
+pub const EXPORT_MAX_SIDE_PX: f64 = 8192.0;
+const MINOR_GRID_STROKE: &str = "rgba(255,255,255,0.17)";
+const MAJOR_GRID_STROKE: &str = "rgba(255,255,255,0.06)";
+const TOKEN_BACKDROP: &str = "rgba(15,23,42,0.62)";
+const LABEL_BACKDROP: &str = "rgba(0,0,0,0.48)";
+const LABEL_COLOR: &str = "#f4efe6";
+const NOTE_TEXT_COLOR: &str = "#2f240d";
+const STAIRS_FILL: &str = "rgba(250,204,21,0.16)";
+const STAIRS_COLOR: &str = "#facc15";
+const WALL_STROKE: &str = "#f97316";
+const DOOR_CLOSED_STROKE: &str = "#38bdf8";
+const DOOR_OPEN_STROKE: &str = "#86efac";
+const LIGHT_OPACITY: f64 = 0.18;
+/// Note text line height relative to its font size.
+const NOTE_LINE_HEIGHT: f64 = 1.35;
+const NOTE_PADDING_PX: f64 = 12.0;
+
+// --- Types ---
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SceneExportOptions {
+    /// Output pixels per board pixel; lowered when the image would be too big.
+    pub scale: f64,
+    pub include_grid: bool,
+    /// Walls, doors, lights and notes that are not public.
+    pub include_gm_content: bool,
+}
+
+impl Default for SceneExportOptions {
+    fn default() -> Self {
+        Self {
+            scale: EXPORT_DEFAULT_SCALE,
+            include_grid: true,
+            include_gm_content: false,
+        }
+    }
+}
+
+/// One drawing step, in board pixels with the origin at the board's top-left.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExportShape {
+    /// Uploaded image centred on a point. `height: None` keeps the image's
+    /// own aspect ratio, as the board does for backgrounds.
+    Image {
+        hash: String,
+        center_x: f64,
+        center_y: f64,
+        width: f64,
+        height: Option<f64>,
+        rotation_deg: f64,
+        opacity: f64,
+    },
+    Rect {
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+        fill: String,
+        opacity: f64,
+        corner_radius: f64,
+    },
+    Line {
+        x1: f64,
+        y1: f64,
+        x2: f64,
+        y2: f64,
+        stroke: String,
+        width: f64,
+    },
+    Circle {
+        x: f64,
+        y: f64,
+        radius: f64,
+        fill: String,
+        opacity: f64,
+    },
+    /// Rounded rectangle border, stroked on the rectangle's edge.
+    Outline {
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+        stroke: String,
+        line_width: f64,
+        corner_radius: f64,
+    },
+    /// Single line of text; `x` is its left edge, or its centre when `centered`.
+    Text {
+        x: f64,
+        y: f64,
+        text: String,
+        font_px: f64,
+        color: String,
+        bold: bool,
+        centered: bool,
+        max_width: f64,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SceneExportPlan {
+    pub width_px: u32,
+    pub height_px: u32,
+    /// Scale actually used, after the size limit.
+    pub scale: f64,
+    /// Board size in board pixels.
+    pub board_width: f64,
+    pub board_height: f64,
+    pub shapes: Vec<ExportShape>,
+}
+
+/// Grid drawn over a board: stroke width in board pixels and whether the thin
+/// lines between every fifth one are drawn.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GridLines {
+    pub line_width: f64,
+    pub minor: bool,
+}
+
+// --- Pure functions ---
+
+/// Requested scale, lowered so neither side exceeds `EXPORT_MAX_SIDE_PX`.
+pub fn export_scale(board_width: f64, board_height: f64, requested: f64) -> f64 {
+    let longest = board_width.max(board_height).max(1.0);
+    requested.max(0.1).min(EXPORT_MAX_SIDE_PX / longest)
+}
+
+/// Whether a note may appear in an export without the game master's content.
+pub fn note_exported(note: &NotePayload, include_gm_content: bool) -> bool {
+    include_gm_content || note.visibility == NoteVisibility::Public
+}
+
+/// Download name for a scene snapshot: the scene name reduced to safe characters.
+pub fn scene_export_file_name(scene_name: &str) -> String {
+    let mut name = String::new();
+    for character in scene_name.trim().chars() {
+        if character.is_alphanumeric() || character == '_' {
+            name.push(character);
+        } else if !name.ends_with('-') {
+            name.push('-');
+        }
+    }
+    let name = name.trim_matches('-');
+    if name.is_empty() {
+        "scene.png".to_string()
+    } else {
+        format!("{name}.png")
+    }
+}
+
+/// Everything drawn for `scene` as seen on one floor (see `scene_level_view`):
+/// background, terrain, stairs, grid, tokens and board notes, plus walls,
+/// doors and lights when the game master's content is included. `notes` may
+/// hold notes of other scenes; only those over this board are kept.
+pub fn scene_export_plan(
+    scene: &Scene,
+    notes: &[NotePayload],
+    options: SceneExportOptions,
+) -> SceneExportPlan {
+    let (cell_size, board_width, board_height) =
+        workspace_board_metrics(scene.grid.columns, scene.grid.rows);
+    let scale = export_scale(board_width, board_height, options.scale);
+    let grid = options.include_grid.then(|| GridLines {
+        line_width: grid_line_width_screen(cell_size * scale) / scale,
+        minor: true,
+    });
+    let mut shapes = scene_base_shapes(scene, grid);
+
+    if options.include_gm_content {
+        for light in &scene.lights {
+            shapes.push(ExportShape::Circle {
+                x: f64::from(light.position.x) * cell_size,
+                y: f64::from(light.position.y) * cell_size,
+                radius: f64::from(light.range_cells) * cell_size,
+                fill: light.color.clone(),
+                opacity: LIGHT_OPACITY,
+            });
+        }
+        for wall in &scene.walls {
+            for segment in wall.points.windows(2) {
+                shapes.push(ExportShape::Line {
+                    x1: f64::from(segment[0].x) * cell_size,
+                    y1: f64::from(segment[0].y) * cell_size,
+                    x2: f64::from(segment[1].x) * cell_size,
+                    y2: f64::from(segment[1].y) * cell_size,
+                    stroke: WALL_STROKE.to_string(),
+                    width: 3.0,
+                });
+            }
+        }
+        for door in &scene.doors {
+            shapes.push(ExportShape::Line {
+                x1: f64::from(door.start.x) * cell_size,
+                y1: f64::from(door.start.y) * cell_size,
+                x2: f64::from(door.end.x) * cell_size,
+                y2: f64::from(door.end.y) * cell_size,
+                stroke: if door.closed {
+                    DOOR_CLOSED_STROKE
+                } else {
+                    DOOR_OPEN_STROKE
+                }
+                .to_string(),
+                width: 4.0,
+            });
+        }
+    }
+
+    for token in &scene.tokens {
+        shapes.extend(token_shapes(token, cell_size));
+    }
+
+    let board_left = f64::from(scene.workspace_x) - board_width / 2.0;
+    let board_top = f64::from(scene.workspace_y) - board_height / 2.0;
+    for note in notes
+        .iter()
+        .filter(|note| note_exported(note, options.include_gm_content))
+    {
+        let Some(position) = &note.board_position else {
+            continue;
+        };
+        let (world_x, world_y) = board_note_world_position(position, std::slice::from_ref(scene));
+        let x = world_x - board_left;
+        let y = world_y - board_top;
+        let style = &note.board_style;
+        if let Some(icon) = &style.pin_icon {
+            let radius = BOARD_NOTE_PIN_SIZE_PX / 2.0;
+            if !rects_overlap(
+                x,
+                y,
+                BOARD_NOTE_PIN_SIZE_PX,
+                BOARD_NOTE_PIN_SIZE_PX,
+                board_width,
+                board_height,
+            ) {
+                continue;
+            }
+            shapes.push(ExportShape::Circle {
+                x: x + radius,
+                y: y + radius,
+                radius,
+                fill: style.color.clone(),
+                opacity: 1.0,
+            });
+            shapes.push(ExportShape::Text {
+                x: x + radius,
+                y: y + radius,
+                text: icon.clone(),
+                font_px: radius,
+                color: NOTE_TEXT_COLOR.to_string(),
+                bold: false,
+                centered: true,
+                max_width: BOARD_NOTE_PIN_SIZE_PX,
+            });
+            continue;
+        }
+        if !rects_overlap(
+            x,
+            y,
+            style.width_px,
+            style.height_px,
+            board_width,
+            board_height,
+        ) {
+            continue;
+        }
+        shapes.push(ExportShape::Rect {
+            x,
+            y,
+            width: style.width_px,
+            height: style.height_px,
+            fill: style.color.clone(),
+            opacity: 1.0,
+            corner_radius: 14.0,
+        });
+        let font_px = style.font_size_pt * 4.0 / 3.0;
+        let line_height = font_px * NOTE_LINE_HEIGHT;
+        let text_width = style.width_px - NOTE_PADDING_PX * 2.0;
+        // Untitled notes use their first line as the heading, as on the board.
+        let (title, body) = if note.title.trim().is_empty() {
+            note_heading_and_body(&note.body)
+        } else {
+            (note.title.trim().to_string(), note.body.clone())
+        };
+        let mut lines = Vec::new();
+        if !title.is_empty() {
+            lines.push((title, true));
+        }
+        lines.extend(
+            body.lines()
+                .map(|line| (line.trim().to_string(), false))
+                .filter(|(line, _)| !line.is_empty()),
+        );
+        let fitting = ((style.height_px - NOTE_PADDING_PX * 2.0) / line_height)
+            .floor()
+            .max(0.0) as usize;
+        for (index, (text, bold)) in lines.into_iter().take(fitting).enumerate() {
+            shapes.push(ExportShape::Text {
+                x: x + NOTE_PADDING_PX,
+                y: y + NOTE_PADDING_PX + line_height * (index as f64 + 0.5),
+                text,
+                font_px,
+                color: NOTE_TEXT_COLOR.to_string(),
+                bold,
+                centered: false,
+                max_width: text_width,
+            });
+        }
+    }
+
+    SceneExportPlan {
+        width_px: (board_width * scale).round().max(1.0) as u32,
+        height_px: (board_height * scale).round().max(1.0) as u32,
+        scale,
+        board_width,
+        board_height,
+        shapes,
+    }
+}
+
+/// Background, terrain, stairs and grid of a board, in board pixels.
+pub fn scene_base_shapes(scene: &Scene, grid: Option<GridLines>) -> Vec<ExportShape> {
+    let (cell_size, board_width, board_height) =
+        workspace_board_metrics(scene.grid.columns, scene.grid.rows);
+    let mut shapes = Vec::new();
+
+    if let Some(background) = scene
+        .background
+        .as_ref()
+        .filter(|file| file.mime_type.starts_with("image/"))
+    {
+        let background_scale = f64::from(scene.background_scale).max(0.05);
+        shapes.push(ExportShape::Image {
+            hash: background.hash.clone(),
+            center_x: board_width / 2.0 + f64::from(scene.background_offset_x),
+            center_y: board_height / 2.0 + f64::from(scene.background_offset_y),
+            width: board_width * background_scale,
+            height: None,
+            rotation_deg: f64::from(scene.background_rotation_deg),
+            opacity: 0.92,
+        });
+    }
+
+    let label_font = (cell_size * 0.22).clamp(8.0, 14.0);
+    for cell in &scene.terrain {
+        let x = f64::from(cell.column) * cell_size;
+        let y = f64::from(cell.row) * cell_size;
+        let fill = terrain_fill_color(cell);
+        shapes.push(ExportShape::Rect {
+            x,
+            y,
+            width: cell_size,
+            height: cell_size,
+            fill: fill.clone(),
+            opacity: TERRAIN_OVERLAY_OPACITY,
+            corner_radius: 0.0,
+        });
+        if cell.kind == TerrainKind::Difficult {
+            shapes.push(ExportShape::Line {
+                x1: x,
+                y1: y + cell_size,
+                x2: x + cell_size,
+                y2: y,
+                stroke: fill,
+                width: 2.0,
+            });
+        }
+        if let Some(label) = &cell.label {
+            shapes.push(centered_text(
+                x + cell_size / 2.0,
+                y + cell_size / 2.0,
+                label,
+                label_font,
+                cell_size,
+            ));
+        }
+    }
+
+    for stairs in &scene.stairs {
+        let x = f64::from(stairs.column) * cell_size;
+        let y = f64::from(stairs.row) * cell_size;
+        shapes.push(ExportShape::Rect {
+            x,
+            y,
+            width: cell_size,
+            height: cell_size,
+            fill: STAIRS_FILL.to_string(),
+            opacity: 1.0,
+            corner_radius: 5.0,
+        });
+        shapes.push(ExportShape::Text {
+            x: x + cell_size / 2.0,
+            y: y + cell_size / 2.0,
+            text: if stairs_go_up(scene, stairs) {
+                "▲"
+            } else {
+                "▼"
+            }
+            .to_string(),
+            font_px: cell_size * 0.45,
+            color: STAIRS_COLOR.to_string(),
+            bold: false,
+            centered: true,
+            max_width: cell_size,
+        });
+    }
+
+    if let Some(GridLines { line_width, minor }) = grid {
+        let stroke = |index: u16| {
+            if index.is_multiple_of(5) {
+                MAJOR_GRID_STROKE
+            } else {
+                MINOR_GRID_STROKE
+            }
+            .to_string()
+        };
+        let drawn = |index: &u16| minor || index.is_multiple_of(5);
+        for column in (0..=scene.grid.columns).filter(drawn) {
+            let x = f64::from(column) * cell_size;
+            shapes.push(ExportShape::Line {
+                x1: x,
+                y1: 0.0,
+                x2: x,
+                y2: board_height,
+                stroke: stroke(column),
+                width: line_width,
+            });
+        }
+        for row in (0..=scene.grid.rows).filter(drawn) {
+            let y = f64::from(row) * cell_size;
+            shapes.push(ExportShape::Line {
+                x1: 0.0,
+                y1: y,
+                x2: board_width,
+                y2: y,
+                stroke: stroke(row),
+                width: line_width,
+            });
+        }
+    }
+
+    shapes
+}
+
+/// Backdrop, image and name label of a token, in board pixels.
+pub fn token_shapes(token: &Token, cell_size: f64) -> Vec<ExportShape> {
+    let token_label_font = (cell_size * 0.18).clamp(11.0, 15.0);
+    let mut shapes = Vec::new();
+    let (left, top, width, height) = token_rect(
+        0.0,
+        0.0,
+        cell_size,
+        token.x,
+        token.y,
+        token.width_cells,
+        token.height_cells,
+    );
+    shapes.push(ExportShape::Rect {
+        x: left,
+        y: top,
+        width,
+        height,
+        fill: TOKEN_BACKDROP.to_string(),
+        opacity: 1.0,
+        corner_radius: (cell_size * 0.18).clamp(8.0, 16.0),
+    });
+    shapes.push(ExportShape::Image {
+        hash: token.image.hash.clone(),
+        center_x: left + width / 2.0,
+        center_y: top + height / 2.0,
+        width,
+        height: Some(height),
+        rotation_deg: f64::from(token.rotation_deg),
+        opacity: 1.0,
+    });
+    let label_height = token_label_font * 1.6;
+    shapes.push(ExportShape::Rect {
+        x: left + 4.0,
+        y: top + height - label_height - 4.0,
+        width: (width - 8.0).max(0.0),
+        height: label_height,
+        fill: LABEL_BACKDROP.to_string(),
+        opacity: 1.0,
+        corner_radius: 5.0,
+    });
+    shapes.push(centered_text(
+        left + width / 2.0,
+        top + height - label_height / 2.0 - 4.0,
+        &token.name,
+        token_label_font,
+        width - 12.0,
+    ));
+    shapes
+}
+
+fn centered_text(x: f64, y: f64, text: &str, font_px: f64, max_width: f64) -> ExportShape {
+    ExportShape::Text {
+        x,
+        y,
+        text: text.to_string(),
+        font_px,
+        color: LABEL_COLOR.to_string(),
+        bold: true,
+        centered: true,
+        max_width: max_width.max(1.0),
+    }
+}
+
+fn rects_overlap(
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+    board_width: f64,
+    board_height: f64,
+) -> bool {
+    x < board_width && y < board_height && x + width > 0.0 && y + height > 0.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::events::{
+        FileRef, NoteBoardPosition, NoteBoardStyle, SceneGrid, ScenePoint, SceneWall,
+    };
+
+    fn scene() -> Scene {
+        Scene {
+            id: "crypt".to_string(),
+            name: "Crypt".to_string(),
+            grid: SceneGrid {
+                columns: 10,
+                rows: 5,
+                cell_size_feet: 5,
+            },
+            workspace_x: 0.0,
+            workspace_y: 0.0,
+            background: Some(FileRef {
+                hash: "map".to_string(),
+                mime_type: "image/png".to_string(),
+                file_name: "map.png".to_string(),
+                size: 1,
+            }),
+            background_scale: 1.0,
+            background_offset_x: 0.0,
+            background_offset_y: 0.0,
+            background_rotation_deg: 0.0,
+            tokens: Vec::new(),
+            terrain: Vec::new(),
+            walls: vec![SceneWall {
+                points: vec![ScenePoint { x: 0.0, y: 0.0 }, ScenePoint { x: 2.0, y: 0.0 }],
+            }],
+            doors: Vec::new(),
+            lights: Vec::new(),
+            folder: None,
+            sort_index: 0,
+            levels: Vec::new(),
+            stairs: Vec::new(),
+        }
+    }
+
+    fn note(id: &str, visibility: NoteVisibility, world_x: f64) -> NotePayload {
+        NotePayload {
+            id: id.to_string(),
+            author: "gm".to_string(),
+            visibility,
+            title: id.to_string(),
+            body: "line".to_string(),
+            created_at_ms: 1.0,
+            updated_at_ms: 1.0,
+            board_position: Some(NoteBoardPosition {
+                world_x,
+                world_y: 0.0,
+                anchor: None,
+            }),
+            board_style: NoteBoardStyle {
+                width_px: 120.0,
+                height_px: 100.0,
+                font_size_pt: 12.0,
+                color: "#F8EE96".to_string(),
+                pin_icon: None,
+            },
+            folder: String::new(),
+            tags: Vec::new(),
+            pinned: false,
+            edited_by: String::new(),
+            revisions: Vec::new(),
+        }
+    }
+
+    fn texts(plan: &SceneExportPlan) -> Vec<&str> {
+        plan.shapes
+            .iter()
+            .filter_map(|shape| match shape {
+                ExportShape::Text { text, .. } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn export_scale_keeps_images_within_the_canvas_limit() {
+        assert_eq!(export_scale(480.0, 240.0, 2.0), 2.0);
+        assert_eq!(export_scale(4096.0, 1024.0, 4.0), 2.0);
+        let plan = scene_export_plan(&scene(), &[], SceneExportOptions::default());
+        assert_eq!((plan.width_px, plan.height_px), (960, 480));
+    }
+
+    #[test]
+    fn grid_and_game_master_content_follow_the_options() {
+        let notes = vec![
+            note("public", NoteVisibility::Public, -100.0),
+            note("private", NoteVisibility::Private, -100.0),
+            note("far away", NoteVisibility::Public, 5_000.0),
+        ];
+        let count_lines = |plan: &SceneExportPlan| {
+            plan.shapes
+                .iter()
+                .filter(|shape| matches!(shape, ExportShape::Line { .. }))
+                .count()
+        };
+
+        let plain = scene_export_plan(
+            &scene(),
+            &notes,
+            SceneExportOptions {
+                include_grid: false,
+                ..SceneExportOptions::default()
+            },
+        );
+        assert_eq!(count_lines(&plain), 0);
+        assert_eq!(texts(&plain), vec!["public", "line"]);
+        assert!(matches!(
+            plain.shapes.first(),
+            Some(ExportShape::Image { hash, height: None, .. }) if hash == "map"
+        ));
+
+        let full = scene_export_plan(
+            &scene(),
+            &notes,
+            SceneExportOptions {
+                include_gm_content: true,
+                ..SceneExportOptions::default()
+            },
+        );
+        // 11 columns and 6 rows of grid lines plus the wall segment.
+        assert_eq!(count_lines(&full), 18);
+        assert_eq!(texts(&full), vec!["public", "line", "private", "line"]);
+    }
+
+    #[test]
+    fn export_file_name_is_safe_for_downloads() {
+        assert_eq!(
+            scene_export_file_name("Crypt of Bones"),
+            "Crypt-of-Bones.png"
+        );
+        assert_eq!(scene_export_file_name(" ../Tavern: 2/3 "), "Tavern-2-3.png");
+        assert_eq!(scene_export_file_name("???"), "scene.png");
+    }
+}
@@ -0,0 +1,449 @@
+// Scene PNG export: the board's export panel and the offscreen canvas that
+// replays a `SceneExportPlan`.
+
+use super::export::{
+    EXPORT_SCALES, ExportShape, SceneExportOptions, SceneExportPlan, scene_export_file_name,
+    scene_export_plan,
+};
+use super::scene_geometry::stop_pointer_propagation;
+use crate::components::websocket::bytes_to_blob;
+use crate::config::Theme;
+use crate::i18n::i18n::{t_string, use_i18n};
+use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
+use gloo_timers::future::TimeoutFuture;
+use leptos::ev::MouseEvent;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use leptos::wasm_bindgen::JsCast;
+use shared::events::{NotePayload, Scene};
+use std::collections::HashMap;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{CanvasRenderingContext2d, Event, HtmlCanvasElement, HtmlElement, HtmlImageElement};
+
+const EXPORT_ICON: &str = "🖼";
+/// Object URLs of exported images stay alive until the download has started.
+const DOWNLOAD_URL_LIFETIME_MS: u32 = 10_000;
+
+/// Panel state, owned by the board so it survives the board re-rendering its
+/// overlays on every camera move or scene change.
+#[derive(Clone, Copy)]
+pub struct SceneExportState {
+    pub is_open: RwSignal<bool>,
+    pub options: RwSignal<SceneExportOptions>,
+    pub is_exporting: RwSignal<bool>,
+    pub export_error: RwSignal<Option<String>>,
+}
+
+impl Default for SceneExportState {
+    fn default() -> Self {
+        Self {
+            is_open: RwSignal::new(false),
+            options: RwSignal::new(SceneExportOptions::default()),
+            is_exporting: RwSignal::new(false),
+            export_error: RwSignal::new(None),
+        }
+    }
+}
+
+/// Export button above the board toolbar and its options panel. Exports the
+/// scene the local user plays on, as they see it.
+#[component]
+pub fn SceneExportPanel(
+    /// Scenes as drawn on the local board, projected onto the viewed floors.
+    scenes: Signal<Vec<Scene>>,
+    /// Scene the local user plays on; the one exported.
+    active_scene_id: Signal<Option<String>>,
+    /// Board notes the local user can see.
+    notes: Signal<Vec<NotePayload>>,
+    file_urls: RwSignal<HashMap<String, String>>,
+    /// Only the game master may include walls, lights and hidden notes.
+    is_game_master: Signal<bool>,
+    state: SceneExportState,
+    theme: Theme,
+) -> impl IntoView {
+    let i18n = use_i18n();
+    let SceneExportState {
+        is_open,
+        options,
+        is_exporting,
+        export_error,
+    } = state;
+    let stop = move |ev: MouseEvent| ev.stop_propagation();
+
+    let run_export = move |_| {
+        if is_exporting.get_untracked() {
+            return;
+        }
+        let scene = active_scene_id.get_untracked().and_then(|scene_id| {
+            scenes.with_untracked(|items| items.iter().find(|scene| scene.id == scene_id).cloned())
+        });
+        let Some(scene) = scene else {
+            export_error.set(Some(
+                t_string!(i18n, scene_board.export_no_scene).to_string(),
+            ));
+            return;
+        };
+        let mut chosen = options.get_untracked();
+        chosen.include_gm_content &= is_game_master.get_untracked();
+        let plan = notes.with_untracked(|notes| scene_export_plan(&scene, notes, chosen));
+        let urls = file_urls.get_untracked();
+        let file_name = scene_export_file_name(&scene.name);
+        let base_fill = theme.ui_bg_primary;
+        is_exporting.set(true);
+        export_error.set(None);
+        spawn_local(async move {
+            let result = match render_scene_png(&plan, &urls, base_fill).await {
+                Ok(bytes) => download_png(&bytes, &file_name),
+                Err(error) => Err(error),
+            };
+            if let Err(error) = result {
+                log::error!("scene export failed: {error}");
+                export_error.set(Some(error));
+            }
+            is_exporting.set(false);
+        });
+    };
+
+    let toggle_style = move || {
+        let bg = if is_open.get() {
+            theme.ui_button_primary
+        } else {
+            theme.ui_bg_secondary
+        };
+        format!(
+            "position: absolute; right: 1rem; bottom: 13rem; display: flex; align-items: center; \
+             justify-content: center; width: 2.2rem; height: 2.2rem; border: 1px solid {}; \
+             border-radius: 0.5rem; background: {}; color: {}; cursor: pointer; font-size: 1.1rem; \
+             line-height: 1; z-index: 6;",
+            theme.ui_border, bg, theme.ui_text_primary,
+        )
+    };
+    let row_style = StoredValue::new(format!(
+        "display: flex; align-items: center; gap: 0.4rem; color: {}; font-size: 0.78rem; cursor: pointer;",
+        theme.ui_text_primary
+    ));
+
+    view! {
+        <button
+            title=move || t_string!(i18n, scene_board.export_title)
+            on:pointerdown=stop_pointer_propagation
+            on:mousedown=move |ev: MouseEvent| {
+                ev.stop_propagation();
+                ev.prevent_default();
+                is_open.update(|open| *open = !*open);
+            }
+            style=toggle_style
+        >
+            {EXPORT_ICON}
+        </button>
+        <Show when=move || is_open.get()>
+            <div
+                on:mousedown=stop
+                on:pointerdown=stop_pointer_propagation
+                on:click=stop
+                style=format!(
+                    "position: absolute; right: 4rem; bottom: 13rem; display: flex; flex-direction: column; \
+                     gap: 0.45rem; padding: 0.6rem; background: rgba(0,0,0,0.48); border: 1px solid {}; \
+                     border-radius: 0.65rem; backdrop-filter: blur(8px); z-index: 6; min-width: 11rem;",
+                    theme.ui_border
+                )
+            >
+                <div style=format!("color: {}; font-size: 0.82rem; font-weight: 700;", theme.ui_text_primary)>
+                    {move || t_string!(i18n, scene_board.export_title)}
+                </div>
+                <label style=row_style.get_value()>
+                    <input
+                        type="checkbox"
+                        prop:checked=move || options.get().include_grid
+                        on:change=move |_| options.update(|options| options.include_grid = !options.include_grid)
+                    />
+                    {move || t_string!(i18n, scene_board.export_include_grid)}
+                </label>
+                <Show when=move || is_game_master.get()>
+                    <label style=row_style.get_value()>
+                        <input
+                            type="checkbox"
+                            prop:checked=move || options.get().include_gm_content
+                            on:change=move |_| options.update(|options| {
+                                options.include_gm_content = !options.include_gm_content;
+                            })
+                        />
+                        {move || t_string!(i18n, scene_board.export_include_gm)}
+                    </label>
+                </Show>
+                <select
+                    title=move || t_string!(i18n, scene_board.export_scale_title)
+                    prop:value=move || options.get().scale.to_string()
+                    on:change=move |ev: Event| {
+                        if let Ok(scale) = event_target_value(&ev).parse::<f64>() {
+                            options.update(|options| options.scale = scale);
+                        }
+                    }
+                    style=format!(
+                        "padding: 0.25rem 0.4rem; border: 1px solid {}; border-radius: 0.45rem; \
+                         background: {}; color: {}; font-size: 0.76rem;",
+                        theme.ui_border, theme.ui_bg_primary, theme.ui_text_primary
+                    )
+                >
+                    {EXPORT_SCALES.into_iter().map(|scale| view! {
+                        <option value=scale.to_string()>{format!("{scale}x")}</option>
+                    }).collect_view()}
+                </select>
+                <button
+                    on:click=run_export
+                    disabled=move || is_exporting.get()
+                    style=format!(
+                        "padding: 0.3rem 0.6rem; border: none; border-radius: 0.45rem; background: {}; \
+                         color: {}; cursor: pointer; font-size: 0.78rem;",
+                        theme.ui_button_primary, theme.ui_text_primary
+                    )
+                >
+                    {move || if is_exporting.get() {
+                        t_string!(i18n, scene_board.export_running)
+                    } else {
+                        t_string!(i18n, scene_board.export_button)
+                    }}
+                </button>
+                {move || export_error.get().map(|error| view! {
+                    <div style=format!("color: {}; font-size: 0.74rem; max-width: 11rem;", theme.ui_button_danger)>
+                        {error}
+                    </div>
+                })}
+            </div>
+        </Show>
+    }
+}
+
+/// Draws `plan` on an offscreen canvas and returns PNG bytes. Images whose
+/// files have not been downloaded yet are left out rather than failing.
+async fn render_scene_png(
+    plan: &SceneExportPlan,
+    file_urls: &HashMap<String, String>,
+    base_fill: &str,
+) -> Result<Vec<u8>, String> {
+    let document = web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or_else(|| "document is unavailable".to_string())?;
+    let canvas: HtmlCanvasElement = document
+        .create_element("canvas")
+        .map_err(|error| format!("failed to create canvas: {error:?}"))?
+        .dyn_into()
+        .map_err(|_| "canvas element has an unexpected type".to_string())?;
+    canvas.set_width(plan.width_px);
+    canvas.set_height(plan.height_px);
+    let context: CanvasRenderingContext2d = canvas
+        .get_context("2d")
+        .map_err(|error| format!("failed to get canvas context: {error:?}"))?
+        .ok_or_else(|| "canvas 2d context is unavailable".to_string())?
+        .dyn_into()
+        .map_err(|_| "canvas context has an unexpected type".to_string())?;
+
+    let mut images = HashMap::<String, HtmlImageElement>::new();
+    for shape in &plan.shapes {
+        let ExportShape::Image { hash, .. } = shape else {
+            continue;
+        };
+        if images.contains_key(hash) {
+            continue;
+        }
+        let Some(url) = file_urls.get(hash) else {
+            continue;
+        };
+        if let Ok(image) = load_image(url).await {
+            images.insert(hash.clone(), image);
+        }
+    }
+
+    context.set_fill_style_str(base_fill);
+    context.fill_rect(
+        0.0,
+        0.0,
+        f64::from(plan.width_px),
+        f64::from(plan.height_px),
+    );
+    context
+        .scale(plan.scale, plan.scale)
+        .map_err(|error| format!("failed to scale canvas: {error:?}"))?;
+    context.begin_path();
+    context.rect(0.0, 0.0, plan.board_width, plan.board_height);
+    context.clip();
+
+    for shape in &plan.shapes {
+        draw_shape(&context, shape, &images)
+            .map_err(|error| format!("failed to draw scene: {error:?}"))?;
+    }
+
+    let data_url = canvas
+        .to_data_url_with_type("image/png")
+        .map_err(|error| format!("failed to encode scene image: {error:?}"))?;
+    let encoded = data_url
+        .split_once(',')
+        .map(|(_, encoded)| encoded)
+        .ok_or_else(|| "scene image data URL is malformed".to_string())?;
+    BASE64
+        .decode(encoded)
+        .map_err(|error| format!("failed to decode scene image: {error}"))
+}
+
+pub async fn load_image(url: &str) -> Result<HtmlImageElement, String> {
+    let image =
+        HtmlImageElement::new().map_err(|error| format!("failed to create image: {error:?}"))?;
+    image.set_src(url);
+    JsFuture::from(image.decode())
+        .await
+        .map_err(|error| format!("failed to decode image: {error:?}"))?;
+    Ok(image)
+}
+
+/// Draws one shape; its opacity multiplies the context's global alpha.
+pub fn draw_shape(
+    context: &CanvasRenderingContext2d,
+    shape: &ExportShape,
+    images: &HashMap<String, HtmlImageElement>,
+) -> Result<(), leptos::wasm_bindgen::JsValue> {
+    context.save();
+    match shape {
+        ExportShape::Image {
+            hash,
+            center_x,
+            center_y,
+            width,
+            height,
+            rotation_deg,
+            opacity,
+        } => {
+            if let Some(image) = images.get(hash) {
+                let natural_width = f64::from(image.natural_width().max(1));
+                let natural_height = f64::from(image.natural_height());
+                let height = height.unwrap_or(width * natural_height / natural_width);
+                context.set_global_alpha(context.global_alpha() * *opacity);
+                context.translate(*center_x, *center_y)?;
+                context.rotate(rotation_deg.to_radians())?;
+                context.draw_image_with_html_image_element_and_dw_and_dh(
+                    image,
+                    -width / 2.0,
+                    -height / 2.0,
+                    *width,
+                    height,
+                )?;
+            }
+        }
+        ExportShape::Rect {
+            x,
+            y,
+            width,
+            height,
+            fill,
+            opacity,
+            corner_radius,
+        } => {
+            context.set_global_alpha(context.global_alpha() * *opacity);
+            context.set_fill_style_str(fill);
+            rounded_rect_path(context, *x, *y, *width, *height, *corner_radius)?;
+            context.fill();
+        }
+        ExportShape::Outline {
+            x,
+            y,
+            width,
+            height,
+            stroke,
+            line_width,
+            corner_radius,
+        } => {
+            context.set_stroke_style_str(stroke);
+            context.set_line_width(*line_width);
+            rounded_rect_path(context, *x, *y, *width, *height, *corner_radius)?;
+            context.stroke();
+        }
+        ExportShape::Line {
+            x1,
+            y1,
+            x2,
+            y2,
+            stroke,
+            width,
+        } => {
+            context.set_stroke_style_str(stroke);
+            context.set_line_width(*width);
+            context.set_line_cap("round");
+            context.begin_path();
+            context.move_to(*x1, *y1);
+            context.line_to(*x2, *y2);
+            context.stroke();
+        }
+        ExportShape::Circle {
+            x,
+            y,
+            radius,
+            fill,
+            opacity,
+        } => {
+            context.set_global_alpha(context.global_alpha() * *opacity);
+            context.set_fill_style_str(fill);
+            context.begin_path();
+            context.arc(*x, *y, *radius, 0.0, std::f64::consts::TAU)?;
+            context.fill();
+        }
+        ExportShape::Text {
+            x,
+            y,
+            text,
+            font_px,
+            color,
+            bold,
+            centered,
+            max_width,
+        } => {
+            context.set_fill_style_str(color);
+            context.set_font(&format!(
+                "{}{font_px:.2}px sans-serif",
+                if *bold { "bold " } else { "" }
+            ));
+            context.set_text_align(if *centered { "center" } else { "left" });
+            context.set_text_baseline("middle");
+            context.fill_text_with_max_width(text, *x, *y, *max_width)?;
+        }
+    }
+    context.restore();
+    Ok(())
+}
+
+pub fn rounded_rect_path(
+    context: &CanvasRenderingContext2d,
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+    radius: f64,
+) -> Result<(), leptos::wasm_bindgen::JsValue> {
+    let radius = radius.min(width / 2.0).min(height / 2.0).max(0.0);
+    context.begin_path();
+    context.move_to(x + radius, y);
+    context.arc_to(x + width, y, x + width, y + height, radius)?;
+    context.arc_to(x + width, y + height, x, y + height, radius)?;
+    context.arc_to(x, y + height, x, y, radius)?;
+    context.arc_to(x, y, x + width, y, radius)?;
+    context.close_path();
+    Ok(())
+}
+
+/// Saves the exported image through a temporary download link.
+fn download_png(bytes: &[u8], file_name: &str) -> Result<(), String> {
+    let blob = bytes_to_blob(bytes, "image/png")?;
+    let url = web_sys::Url::create_object_url_with_blob(&blob)
+        .map_err(|error| format!("Failed to create download URL: {error:?}"))?;
+    let link = document()
+        .create_element("a")
+        .ok()
+        .and_then(|element| element.dyn_into::<HtmlElement>().ok())
+        .ok_or_else(|| "Failed to create download link".to_string())?;
+    let _ = link.set_attribute("href", &url);
+    let _ = link.set_attribute("download", file_name);
+    link.click();
+    spawn_local(async move {
+        TimeoutFuture::new(DOWNLOAD_URL_LIFETIME_MS).await;
+        let _ = web_sys::Url::revoke_object_url(&url);
+    });
+    Ok(())
+}
@@ -0,0 +1,188 @@
+// Floor glue for the scene board: the floor each scene shows the local user,
+// stairs that carry dropped tokens, the stairs overlay and the game master's
+// floor switchers.
+
+use super::board_toolbar::FloorSwitcher;
+use super::levels::{scene_level_view, stairs_go_up, token_level, viewed_level};
+use super::model::world_to_screen;
+use super::scene_geometry::{build_scene_layouts, take_token_stairs};
+use super::view_model::SceneBoardViewModel;
+use crate::config::Theme;
+use leptos::prelude::*;
+use shared::events::{Scene, acts_as_game_master};
+
+/// Floors on display, owned by the board so every layer and hit-test agrees
+/// on them.
+#[derive(Clone, Copy)]
+pub struct BoardLevels {
+    /// Scenes as drawn on the floor the local user sees; hit-tests use the
+    /// same projection so tokens on other floors cannot be picked up.
+    pub board_scenes: Signal<Vec<Scene>>,
+    vm: SceneBoardViewModel,
+    scenes: RwSignal<Vec<Scene>>,
+    username: ReadSignal<String>,
+    game_master: RwSignal<Option<String>>,
+}
+
+impl BoardLevels {
+    pub fn new(
+        vm: SceneBoardViewModel,
+        scenes: RwSignal<Vec<Scene>>,
+        username: ReadSignal<String>,
+        game_master: RwSignal<Option<String>>,
+    ) -> Self {
+        Self {
+            board_scenes: Signal::derive(move || {
+                scenes.with(|items| {
+                    items
+                        .iter()
+                        .map(|scene| {
+                            let level_id = local_level(vm, username, game_master, scene);
+                            scene_level_view(scene, level_id.as_deref())
+                        })
+                        .collect::<Vec<_>>()
+                })
+            }),
+            vm,
+            scenes,
+            username,
+            game_master,
+        }
+    }
+
+    fn viewer_is_game_master(self) -> bool {
+        acts_as_game_master(self.game_master.get().as_deref(), &self.username.get())
+    }
+
+    pub fn local_level(self, scene: &Scene) -> Option<String> {
+        local_level(self.vm, self.username, self.game_master, scene)
+    }
+
+    /// The same for event handlers, by scene id.
+    pub fn scene_level_untracked(self, scene_id: &str) -> Option<String> {
+        untrack(|| {
+            self.scenes.with(|items| {
+                items
+                    .iter()
+                    .find(|scene| scene.id == scene_id)
+                    .and_then(|scene| self.local_level(scene))
+            })
+        })
+    }
+
+    /// Switches the scene to the floor a token stands on.
+    pub fn show_token_floor(self, scene_id: &str, token_id: &str) {
+        let level_id = self.scenes.with_untracked(|scenes| {
+            let scene = scenes.iter().find(|scene| scene.id == scene_id)?;
+            let token = scene.tokens.iter().find(|token| token.id == token_id)?;
+            Some(token_level(scene, token))
+        });
+        if let Some(level_id) = level_id {
+            self.vm.view_level(scene_id, level_id);
+        }
+    }
+
+    /// Stairs under a dropped token carry it to the linked floor, which the
+    /// board then shows; returns the scene to share.
+    pub fn take_stairs(self, token_id: &str) -> Option<Scene> {
+        let (scene, level_id) = take_token_stairs(self.scenes, token_id)?;
+        self.vm.view_level(&scene.id, level_id);
+        Some(scene)
+    }
+}
+
+/// Floor of a scene the local user sees: the game master's pick, or the floor
+/// of the player's own token.
+fn local_level(
+    vm: SceneBoardViewModel,
+    username: ReadSignal<String>,
+    game_master: RwSignal<Option<String>>,
+    scene: &Scene,
+) -> Option<String> {
+    let current_user = username.get();
+    let is_game_master = acts_as_game_master(game_master.get().as_deref(), &current_user);
+    vm.viewed_levels.with(|levels| {
+        viewed_level(
+            scene,
+            levels.get(&scene.id).map(String::as_str),
+            &current_user,
+            is_game_master,
+        )
+    })
+}
+
+/// Stairs of the floor on display, drawn in board-local pixels.
+#[component]
+pub fn SceneStairsLayer(scene: Scene, cell_size: f64) -> impl IntoView {
+    scene
+        .stairs
+        .iter()
+        .map(|stairs| {
+            let icon = if stairs_go_up(&scene, stairs) { "▲" } else { "▼" };
+            view! {
+                <div
+                    title=stairs.label.clone().unwrap_or_default()
+                    style=format!(
+                        "position: absolute; left: {:.2}px; top: {:.2}px; width: {:.2}px; height: {:.2}px; \
+                         display: flex; align-items: center; justify-content: center; box-sizing: border-box; \
+                         border: 2px dashed rgba(250,204,21,0.85); border-radius: 0.35rem; \
+                         background: rgba(250,204,21,0.16); color: #facc15; font-size: {:.2}px; \
+                         pointer-events: none;",
+                        f64::from(stairs.column) * cell_size,
+                        f64::from(stairs.row) * cell_size,
+                        cell_size,
+                        cell_size,
+                        cell_size * 0.45
+                    )
+                >
+                    {icon}
+                </div>
+            }
+        })
+        .collect_view()
+}
+
+/// Floor switchers for the game master, above every multi-floor scene.
+#[component]
+pub fn BoardFloorSwitchers(levels: BoardLevels, theme: Theme) -> impl IntoView {
+    let vm = levels.vm;
+    move || {
+        if !levels.viewer_is_game_master() {
+            return ().into_any();
+        }
+        let cam_x = vm.camera_x.get();
+        let cam_y = vm.camera_y.get();
+        let zoom = vm.zoom.get();
+        let vw = vm.viewport_width.get();
+        let vh = vm.viewport_height.get();
+        build_scene_layouts(&levels.board_scenes.get())
+            .into_iter()
+            .filter(|layout| !layout.scene.levels.is_empty())
+            .map(|layout| {
+                let (sx, sy) = world_to_screen(
+                    layout.handle_left(),
+                    layout.handle_top(),
+                    vw,
+                    vh,
+                    cam_x,
+                    cam_y,
+                    zoom,
+                );
+                let scene_id = layout.scene.id.clone();
+                view! {
+                    <FloorSwitcher
+                        selected=levels.local_level(&layout.scene)
+                        levels=layout.scene.levels.clone()
+                        screen_x=sx
+                        screen_y=sy
+                        on_select=Callback::new(move |level_id| {
+                            vm.view_level(&scene_id, level_id);
+                        })
+                        theme=theme.clone()
+                    />
+                }
+            })
+            .collect_view()
+            .into_any()
+    }
+}
@@ -0,0 +1,350 @@
+// Pure multi-floor scene helpers: which floor a user sees and how stairs move tokens.
+// No signals, no Leptos, no web_sys.
+
+use super::model::clamp_token_position;
+use shared::events::{Scene, SceneLevel, SceneStairs, Token};
+
+// --- Pure functions ---
+
+/// Id of an existing extra floor; `None` for the base floor, including ids of
+/// floors that have since been removed.
+pub fn resolve_level(scene: &Scene, level_id: Option<&str>) -> Option<String> {
+    let level_id = level_id?;
+    scene
+        .levels
+        .iter()
+        .any(|level| level.id == level_id)
+        .then(|| level_id.to_string())
+}
+
+/// Floor a token stands on.
+pub fn token_level(scene: &Scene, token: &Token) -> Option<String> {
+    resolve_level(scene, token.level_id.as_deref())
+}
+
+/// The scene as drawn on one floor: that floor's background and fit, and only
+/// the tokens and stairs on it. Terrain, walls, doors and lights belong to the
+/// base floor.
+pub fn scene_level_view(scene: &Scene, level_id: Option<&str>) -> Scene {
+    let level_id = resolve_level(scene, level_id);
+    let mut view = scene.clone();
+    view.tokens
+        .retain(|token| token_level(scene, token) == level_id);
+    view.stairs
+        .retain(|stairs| resolve_level(scene, stairs.level_id.as_deref()) == level_id);
+    if let Some(level) = level_id
+        .as_deref()
+        .and_then(|id| scene.levels.iter().find(|level| level.id == id))
+    {
+        apply_level_background(&mut view, level);
+        view.terrain.clear();
+        view.walls.clear();
+        view.doors.clear();
+        view.lights.clear();
+    }
+    view
+}
+
+fn apply_level_background(scene: &mut Scene, level: &SceneLevel) {
+    scene.background = level.background.clone();
+    scene.background_scale = level.background_scale;
+    scene.background_offset_x = level.background_offset_x;
+    scene.background_offset_y = level.background_offset_y;
+    scene.background_rotation_deg = level.background_rotation_deg;
+}
+
+/// Floor of the first token `username` owns; players without a token see the
+/// base floor.
+pub fn player_level(scene: &Scene, username: &str) -> Option<String> {
+    scene
+        .tokens
+        .iter()
+        .find(|token| token.owner.as_deref() == Some(username))
+        .and_then(|token| token_level(scene, token))
+}
+
+/// Floor shown to a user: the game master picks one with the floor switcher,
+/// players follow their own token.
+pub fn viewed_level(
+    scene: &Scene,
+    chosen_level_id: Option<&str>,
+    username: &str,
+    is_game_master: bool,
+) -> Option<String> {
+    if is_game_master {
+        resolve_level(scene, chosen_level_id)
+    } else {
+        player_level(scene, username)
+    }
+}
+
+/// Stairs on `level_id` at a grid cell.
+pub fn stairs_at<'a>(
+    scene: &'a Scene,
+    level_id: Option<&str>,
+    column: u16,
+    row: u16,
+) -> Option<&'a SceneStairs> {
+    let level_id = resolve_level(scene, level_id);
+    scene.stairs.iter().find(|stairs| {
+        stairs.column == column
+            && stairs.row == row
+            && resolve_level(scene, stairs.level_id.as_deref()) == level_id
+    })
+}
+
+/// Whether stairs lead to a floor listed after their own; the base floor
+/// comes first.
+pub fn stairs_go_up(scene: &Scene, stairs: &SceneStairs) -> bool {
+    let index = |level_id: Option<&str>| {
+        resolve_level(scene, level_id).map_or(0, |id| {
+            1 + scene
+                .levels
+                .iter()
+                .position(|level| level.id == id)
+                .unwrap_or_default()
+        })
+    };
+    index(stairs.target_level_id.as_deref()) > index(stairs.level_id.as_deref())
+}
+
+/// Moves a token whose centre rests on stairs to the linked floor and cell.
+/// Returns the floor it arrived on, or `None` when it is not on stairs or the
+/// stairs lead to a floor that no longer exists.
+pub fn take_stairs(scene: &mut Scene, token_id: &str) -> Option<Option<String>> {
+    let token = scene.tokens.iter().find(|token| token.id == token_id)?;
+    let column = (token.x + f32::from(token.width_cells) / 2.0).floor();
+    let row = (token.y + f32::from(token.height_cells) / 2.0).floor();
+    if column < 0.0 || row < 0.0 {
+        return None;
+    }
+    let stairs = stairs_at(scene, token.level_id.as_deref(), column as u16, row as u16)?;
+    if stairs.target_level_id.is_some()
+        && resolve_level(scene, stairs.target_level_id.as_deref()).is_none()
+    {
+        return None;
+    }
+    let target_level_id = stairs.target_level_id.clone();
+    let (target_column, target_row) = (stairs.target_column, stairs.target_row);
+    let (columns, rows) = (scene.grid.columns, scene.grid.rows);
+    let token = scene.tokens.iter_mut().find(|token| token.id == token_id)?;
+    (token.x, token.y) = clamp_token_position(
+        f32::from(target_column),
+        f32::from(target_row),
+        columns,
+        rows,
+        token.width_cells,
+        token.height_cells,
+    );
+    token.level_id = target_level_id.clone();
+    Some(target_level_id)
+}
+
+/// Drops stairs that start on or lead to a floor missing from `levels`.
+pub fn retain_linked_stairs(levels: &[SceneLevel], stairs: &mut Vec<SceneStairs>) {
+    let exists = |level_id: &Option<String>| {
+        level_id
+            .as_deref()
+            .is_none_or(|id| levels.iter().any(|level| level.id == id))
+    };
+    stairs.retain(|stairs| exists(&stairs.level_id) && exists(&stairs.target_level_id));
+}
+
+/// Keeps stairs and their destinations inside a (possibly resized) grid.
+pub fn fit_stairs_to_grid(stairs: &mut [SceneStairs], columns: u16, rows: u16) {
+    let last_column = columns.max(1) - 1;
+    let last_row = rows.max(1) - 1;
+    for stairs in stairs {
+        stairs.column = stairs.column.min(last_column);
+        stairs.row = stairs.row.min(last_row);
+        stairs.target_column = stairs.target_column.min(last_column);
+        stairs.target_row = stairs.target_row.min(last_row);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::events::{FileRef, SceneGrid, TerrainCell, TerrainKind};
+
+    fn file(hash: &str) -> FileRef {
+        FileRef {
+            hash: hash.to_string(),
+            mime_type: "image/png".to_string(),
+            file_name: format!("{hash}.png"),
+            size: 1,
+        }
+    }
+
+    fn token(id: &str, owner: Option<&str>, level_id: Option<&str>, x: f32, y: f32) -> Token {
+        Token {
+            id: id.to_string(),
+            name: id.to_string(),
+            image: file("token"),
+            x,
+            y,
+            width_cells: 1,
+            height_cells: 1,
+            rotation_deg: 0.0,
+            elevation_feet: 0,
+            owner: owner.map(str::to_string),
+            auras: Vec::new(),
+            level_id: level_id.map(str::to_string),
+        }
+    }
+
+    fn tower() -> Scene {
+        Scene {
+            id: "tower".to_string(),
+            name: "Tower".to_string(),
+            grid: SceneGrid {
+                columns: 10,
+                rows: 10,
+                cell_size_feet: 5,
+            },
+            workspace_x: 0.0,
+            workspace_y: 0.0,
+            background: Some(file("ground")),
+            background_scale: 1.0,
+            background_offset_x: 0.0,
+            background_offset_y: 0.0,
+            background_rotation_deg: 0.0,
+            tokens: vec![
+                token("guard", None, None, 0.0, 0.0),
+                token("hero", Some("alice"), Some("roof"), 2.0, 2.0),
+                token("ghost", None, Some("removed"), 3.0, 3.0),
+            ],
+            terrain: vec![TerrainCell {
+                column: 1,
+                row: 1,
+                kind: TerrainKind::Water,
+                color: None,
+                label: None,
+            }],
+            walls: Vec::new(),
+            doors: Vec::new(),
+            lights: Vec::new(),
+            folder: None,
+            sort_index: 0,
+            levels: vec![SceneLevel {
+                id: "roof".to_string(),
+                name: "Roof".to_string(),
+                background: Some(file("roof")),
+                background_scale: 2.0,
+                background_offset_x: 10.0,
+                background_offset_y: 0.0,
+                background_rotation_deg: 0.0,
+            }],
+            stairs: vec![
+                SceneStairs {
+                    id: "up".to_string(),
+                    level_id: None,
+                    column: 5,
+                    row: 5,
+                    target_level_id: Some("roof".to_string()),
+                    target_column: 9,
+                    target_row: 12,
+                    label: None,
+                },
+                SceneStairs {
+                    id: "down".to_string(),
+                    level_id: Some("roof".to_string()),
+                    column: 5,
+                    row: 5,
+                    target_level_id: None,
+                    target_column: 4,
+                    target_row: 4,
+                    label: Some("Trapdoor".to_string()),
+                },
+            ],
+        }
+    }
+
+    fn ids(scene: &Scene) -> Vec<&str> {
+        scene.tokens.iter().map(|token| token.id.as_str()).collect()
+    }
+
+    #[test]
+    fn level_view_swaps_background_and_filters_tokens() {
+        let scene = tower();
+
+        let base = scene_level_view(&scene, None);
+        assert_eq!(ids(&base), vec!["guard", "ghost"]);
+        assert_eq!(base.terrain.len(), 1);
+        assert_eq!(base.stairs.len(), 1);
+        assert_eq!(base.background.as_ref().unwrap().hash, "ground");
+
+        let roof = scene_level_view(&scene, Some("roof"));
+        assert_eq!(ids(&roof), vec!["hero"]);
+        assert!(roof.terrain.is_empty());
+        assert_eq!(roof.stairs[0].id, "down");
+        assert_eq!(roof.background.unwrap().hash, "roof");
+        assert_eq!(roof.background_scale, 2.0);
+
+        // Unknown floors fall back to the base floor.
+        assert_eq!(ids(&scene_level_view(&scene, Some("cellar"))), ids(&base));
+    }
+
+    #[test]
+    fn players_see_the_floor_of_their_token() {
+        let scene = tower();
+        assert_eq!(player_level(&scene, "alice").as_deref(), Some("roof"));
+        assert_eq!(player_level(&scene, "bob"), None);
+        assert_eq!(
+            viewed_level(&scene, None, "alice", false).as_deref(),
+            Some("roof")
+        );
+        assert_eq!(viewed_level(&scene, Some("roof"), "bob", false), None);
+        assert_eq!(
+            viewed_level(&scene, Some("roof"), "gm", true).as_deref(),
+            Some("roof")
+        );
+        assert_eq!(viewed_level(&scene, Some("cellar"), "gm", true), None);
+    }
+
+    #[test]
+    fn stairs_move_tokens_between_floors() {
+        let mut scene = tower();
+        assert!(stairs_go_up(&scene, &scene.stairs[0]));
+        assert!(!stairs_go_up(&scene, &scene.stairs[1]));
+        assert_eq!(take_stairs(&mut scene, "guard"), None);
+
+        scene.tokens[0].x = 5.2;
+        scene.tokens[0].y = 4.6;
+        assert_eq!(
+            take_stairs(&mut scene, "guard"),
+            Some(Some("roof".to_string()))
+        );
+        let guard = &scene.tokens[0];
+        assert_eq!(guard.level_id.as_deref(), Some("roof"));
+        assert_eq!((guard.x, guard.y), (9.0, 9.0));
+
+        scene.tokens[0].x = 5.0;
+        scene.tokens[0].y = 5.0;
+        assert_eq!(take_stairs(&mut scene, "guard"), Some(None));
+        assert_eq!(scene.tokens[0].level_id, None);
+        assert_eq!((scene.tokens[0].x, scene.tokens[0].y), (4.0, 4.0));
+    }
+
+    #[test]
+    fn stairs_to_removed_floors_are_dropped() {
+        let mut scene = tower();
+        scene.levels.clear();
+        scene.tokens[0].x = 5.0;
+        scene.tokens[0].y = 5.0;
+        assert_eq!(take_stairs(&mut scene, "guard"), None);
+
+        let mut stairs = scene.stairs.clone();
+        retain_linked_stairs(&scene.levels, &mut stairs);
+        assert!(stairs.is_empty());
+        retain_linked_stairs(&tower().levels, &mut scene.stairs);
+        assert_eq!(scene.stairs.len(), 2);
+
+        fit_stairs_to_grid(&mut scene.stairs, 4, 6);
+        assert_eq!((scene.stairs[0].column, scene.stairs[0].row), (3, 5));
+        assert_eq!(
+            (scene.stairs[0].target_column, scene.stairs[0].target_row),
+            (3, 5)
+        );
+    }
+}
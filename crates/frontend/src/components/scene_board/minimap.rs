@@ -0,0 +1,293 @@
+// Pure minimap geometry: world bounds of the workspace, fitting them into the
+// minimap and back, and the viewports users share with each other.
+// No signals, no Leptos, no web_sys.
+
+use super::model::{
+    BOARD_HANDLE_GAP_PX, BOARD_HANDLE_HEIGHT_PX, board_note_footprint, board_note_world_position,
+    camera_centered_on, clamp_zoom, workspace_board_metrics,
+};
+use shared::events::{BoardViewportPayload, NotePayload, Scene, WorldPoint};
+
+// --- Constants ---
+
+pub const MINIMAP_WIDTH_PX: f64 = 220.0;
+pub const MINIMAP_HEIGHT_PX: f64 = 150.0;
+/// Empty world space kept around everything the minimap shows.
+const MINIMAP_MARGIN: f64 = 160.0;
+/// Share of the viewport left free around the scenes by "fit all".
+const FIT_ALL_FILL: f64 = 0.9;
+/// Minimum gap between two viewport broadcasts.
+pub const VIEWPORT_SHARE_INTERVAL_MS: f64 = 400.0;
+
+// --- Types ---
+
+/// Axis-aligned rectangle in world coordinates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WorldRect {
+    pub left: f64,
+    pub top: f64,
+    pub right: f64,
+    pub bottom: f64,
+}
+
+impl WorldRect {
+    pub fn width(&self) -> f64 {
+        self.right - self.left
+    }
+
+    pub fn height(&self) -> f64 {
+        self.bottom - self.top
+    }
+
+    pub fn center(&self) -> (f64, f64) {
+        (
+            (self.left + self.right) / 2.0,
+            (self.top + self.bottom) / 2.0,
+        )
+    }
+
+    pub fn union(&self, other: &WorldRect) -> WorldRect {
+        WorldRect {
+            left: self.left.min(other.left),
+            top: self.top.min(other.top),
+            right: self.right.max(other.right),
+            bottom: self.bottom.max(other.bottom),
+        }
+    }
+
+    fn padded(&self, margin: f64) -> WorldRect {
+        WorldRect {
+            left: self.left - margin,
+            top: self.top - margin,
+            right: self.right + margin,
+            bottom: self.bottom + margin,
+        }
+    }
+}
+
+/// World-to-minimap mapping: uniform scale plus offset, so shapes keep
+/// their proportions and the content sits centred in the minimap.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MinimapTransform {
+    pub scale: f64,
+    pub offset_x: f64,
+    pub offset_y: f64,
+}
+
+// --- Pure functions ---
+
+/// Board of a scene in world coordinates, including its drag handle above.
+pub fn scene_world_rect(scene: &Scene) -> WorldRect {
+    let (_, width, height) = workspace_board_metrics(scene.grid.columns, scene.grid.rows);
+    let (center_x, center_y) = (f64::from(scene.workspace_x), f64::from(scene.workspace_y));
+    WorldRect {
+        left: center_x - width / 2.0,
+        top: center_y - height / 2.0 - BOARD_HANDLE_HEIGHT_PX - BOARD_HANDLE_GAP_PX,
+        right: center_x + width / 2.0,
+        bottom: center_y + height / 2.0,
+    }
+}
+
+/// World rectangle of a collapsed board note; `None` for notes not on the board.
+pub fn note_world_rect(note: &NotePayload, scenes: &[Scene]) -> Option<WorldRect> {
+    let position = note.board_position.as_ref()?;
+    let (left, top) = board_note_world_position(position, scenes);
+    let (width, height) = board_note_footprint(note);
+    Some(WorldRect {
+        left,
+        top,
+        right: left + width,
+        bottom: top + height,
+    })
+}
+
+/// Area of the world a board shows for its camera and viewport size.
+pub fn viewport_world_rect(
+    camera_x: f64,
+    camera_y: f64,
+    zoom: f64,
+    viewport_width: f64,
+    viewport_height: f64,
+) -> WorldRect {
+    let zoom = zoom.max(f64::EPSILON);
+    let (center_x, center_y) = (-camera_x / zoom, -camera_y / zoom);
+    let (half_width, half_height) = (viewport_width / zoom / 2.0, viewport_height / zoom / 2.0);
+    WorldRect {
+        left: center_x - half_width,
+        top: center_y - half_height,
+        right: center_x + half_width,
+        bottom: center_y + half_height,
+    }
+}
+
+/// Bounds of every scene; `None` for an empty workspace.
+pub fn scenes_bounds(scenes: &[Scene]) -> Option<WorldRect> {
+    scenes
+        .iter()
+        .map(scene_world_rect)
+        .reduce(|bounds, rect| bounds.union(&rect))
+}
+
+/// What the minimap spans: scenes, notes and every viewport, with a margin.
+pub fn minimap_bounds(
+    scenes: &[Scene],
+    notes: &[NotePayload],
+    viewports: &[WorldRect],
+) -> Option<WorldRect> {
+    scenes
+        .iter()
+        .map(scene_world_rect)
+        .chain(
+            notes
+                .iter()
+                .filter_map(|note| note_world_rect(note, scenes)),
+        )
+        .chain(viewports.iter().copied())
+        .reduce(|bounds, rect| bounds.union(&rect))
+        .map(|bounds| bounds.padded(MINIMAP_MARGIN))
+}
+
+/// Fits `bounds` into a `width` x `height` minimap.
+pub fn minimap_transform(bounds: &WorldRect, width: f64, height: f64) -> MinimapTransform {
+    let scale = (width / bounds.width().max(1.0)).min(height / bounds.height().max(1.0));
+    MinimapTransform {
+        scale,
+        offset_x: (width - bounds.width() * scale) / 2.0 - bounds.left * scale,
+        offset_y: (height - bounds.height() * scale) / 2.0 - bounds.top * scale,
+    }
+}
+
+pub fn to_minimap(transform: &MinimapTransform, world_x: f64, world_y: f64) -> (f64, f64) {
+    (
+        world_x * transform.scale + transform.offset_x,
+        world_y * transform.scale + transform.offset_y,
+    )
+}
+
+pub fn from_minimap(transform: &MinimapTransform, minimap_x: f64, minimap_y: f64) -> (f64, f64) {
+    let scale = transform.scale.max(f64::EPSILON);
+    (
+        (minimap_x - transform.offset_x) / scale,
+        (minimap_y - transform.offset_y) / scale,
+    )
+}
+
+/// Camera offset and zoom showing every scene, or `None` without scenes.
+pub fn fit_all_camera(
+    scenes: &[Scene],
+    viewport_width: f64,
+    viewport_height: f64,
+) -> Option<(f64, f64, f64)> {
+    let bounds = scenes_bounds(scenes)?;
+    let zoom = clamp_zoom(
+        (viewport_width / bounds.width().max(1.0)).min(viewport_height / bounds.height().max(1.0))
+            * FIT_ALL_FILL,
+    );
+    let (center_x, center_y) = bounds.center();
+    let (camera_x, camera_y) = camera_centered_on(center_x, center_y, zoom);
+    Some((camera_x, camera_y, zoom))
+}
+
+pub fn viewport_share_due(last_shared_at_ms: Option<f64>, now_ms: f64) -> bool {
+    last_shared_at_ms.is_none_or(|last| now_ms - last >= VIEWPORT_SHARE_INTERVAL_MS)
+}
+
+pub fn viewport_payload(username: &str, rect: &WorldRect) -> BoardViewportPayload {
+    let (x, y) = rect.center();
+    BoardViewportPayload {
+        username: username.to_string(),
+        center: WorldPoint { x, y },
+        width: rect.width(),
+        height: rect.height(),
+    }
+}
+
+pub fn payload_rect(payload: &BoardViewportPayload) -> WorldRect {
+    let (half_width, half_height) = (payload.width / 2.0, payload.height / 2.0);
+    WorldRect {
+        left: payload.center.x - half_width,
+        top: payload.center.y - half_height,
+        right: payload.center.x + half_width,
+        bottom: payload.center.y + half_height,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::events::SceneGrid;
+
+    fn scene(workspace_x: f32, workspace_y: f32) -> Scene {
+        Scene {
+            id: format!("{workspace_x}"),
+            name: "scene".to_string(),
+            grid: SceneGrid {
+                columns: 10,
+                rows: 10,
+                cell_size_feet: 5,
+            },
+            workspace_x,
+            workspace_y,
+            background: None,
+            background_scale: 1.0,
+            background_offset_x: 0.0,
+            background_offset_y: 0.0,
+            background_rotation_deg: 0.0,
+            tokens: Vec::new(),
+            terrain: Vec::new(),
+            walls: Vec::new(),
+            doors: Vec::new(),
+            lights: Vec::new(),
+            folder: None,
+            sort_index: 0,
+            levels: Vec::new(),
+            stairs: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn minimap_round_trips_world_points() {
+        let scenes = vec![scene(0.0, 0.0), scene(2_000.0, 600.0)];
+        let bounds = minimap_bounds(&scenes, &[], &[]).expect("scenes give bounds");
+        let transform = minimap_transform(&bounds, MINIMAP_WIDTH_PX, MINIMAP_HEIGHT_PX);
+
+        let (mx, my) = to_minimap(&transform, 2_000.0, 600.0);
+        assert!((0.0..=MINIMAP_WIDTH_PX).contains(&mx));
+        assert!((0.0..=MINIMAP_HEIGHT_PX).contains(&my));
+        let (wx, wy) = from_minimap(&transform, mx, my);
+        assert!((wx - 2_000.0).abs() < 1e-6 && (wy - 600.0).abs() < 1e-6);
+
+        let (left, top) = to_minimap(&transform, bounds.left, bounds.top);
+        let (right, bottom) = to_minimap(&transform, bounds.right, bounds.bottom);
+        assert!(left >= -1e-9 && right <= MINIMAP_WIDTH_PX + 1e-9);
+        assert!(top >= -1e-9 && bottom <= MINIMAP_HEIGHT_PX + 1e-9);
+    }
+
+    #[test]
+    fn viewports_match_the_board_camera() {
+        let rect = viewport_world_rect(-200.0, 100.0, 2.0, 800.0, 600.0);
+        assert_eq!(
+            rect,
+            WorldRect {
+                left: -100.0,
+                top: -200.0,
+                right: 300.0,
+                bottom: 100.0,
+            }
+        );
+        assert_eq!(payload_rect(&viewport_payload("alice", &rect)), rect);
+        assert!(viewport_share_due(None, 0.0));
+        assert!(!viewport_share_due(Some(1_000.0), 1_300.0));
+    }
+
+    #[test]
+    fn fit_all_centres_every_scene() {
+        assert_eq!(fit_all_camera(&[], 800.0, 600.0), None);
+        let scenes = vec![scene(-300.0, 0.0), scene(300.0, 0.0)];
+        let (camera_x, camera_y, zoom) = fit_all_camera(&scenes, 1_600.0, 900.0).unwrap();
+        let view = viewport_world_rect(camera_x, camera_y, zoom, 1_600.0, 900.0);
+        let bounds = scenes_bounds(&scenes).unwrap();
+        assert!(view.left <= bounds.left && view.right >= bounds.right);
+        assert!(view.top <= bounds.top && view.bottom >= bounds.bottom);
+    }
+}
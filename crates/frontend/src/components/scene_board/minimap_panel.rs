@@ -0,0 +1,385 @@
+// Board minimap: every scene, board note and viewport on the workspace, with
+// click-to-jump, drag-to-pan and a "fit all scenes" button. `BoardMinimapState`
+// also shares the local viewport with other users' minimaps.
+
+use super::minimap::{
+    MINIMAP_HEIGHT_PX, MINIMAP_WIDTH_PX, VIEWPORT_SHARE_INTERVAL_MS, WorldRect, fit_all_camera,
+    from_minimap, minimap_bounds, minimap_transform, note_world_rect, payload_rect,
+    scene_world_rect, to_minimap, viewport_payload, viewport_share_due, viewport_world_rect,
+};
+use super::model::camera_centered_on;
+use super::scene_geometry::{send_event, stop_pointer_propagation};
+use super::view_model::SceneBoardViewModel;
+use crate::components::websocket::WsSender;
+use crate::config::Theme;
+use crate::i18n::i18n::{t_string, use_i18n};
+use leptos::ev::MouseEvent;
+use leptos::html;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use shared::events::{BoardViewportPayload, ClientEvent, NotePayload, Scene};
+use std::collections::HashMap;
+
+const MINIMAP_ICON: &str = "🗺";
+
+/// Minimap state, owned by the board so it survives the board re-rendering
+/// its overlays on every camera move.
+#[derive(Clone, Copy)]
+pub struct BoardMinimapState {
+    pub is_open: RwSignal<bool>,
+    pub is_dragging: RwSignal<bool>,
+    /// World area the local board shows.
+    pub own_viewport: Memo<WorldRect>,
+    /// Viewports of other users still on the board.
+    pub viewports: Signal<Vec<BoardViewportPayload>>,
+    vm: SceneBoardViewModel,
+    users: Signal<Vec<String>>,
+    last_share_ms: StoredValue<Option<f64>>,
+    share_pending: StoredValue<bool>,
+}
+
+impl BoardMinimapState {
+    /// `users` are the other users on the board; `shared_viewports` holds the
+    /// latest viewport each user sent.
+    pub fn new(
+        vm: SceneBoardViewModel,
+        users: Signal<Vec<String>>,
+        shared_viewports: RwSignal<HashMap<String, BoardViewportPayload>>,
+    ) -> Self {
+        Self {
+            is_open: RwSignal::new(true),
+            is_dragging: RwSignal::new(false),
+            own_viewport: Memo::new(move |_| {
+                viewport_world_rect(
+                    vm.camera_x.get(),
+                    vm.camera_y.get(),
+                    vm.zoom.get(),
+                    vm.viewport_width.get(),
+                    vm.viewport_height.get(),
+                )
+            }),
+            viewports: Signal::derive(move || {
+                let users = users.get();
+                shared_viewports.with(|viewports| {
+                    users
+                        .iter()
+                        .filter_map(|user| viewports.get(user).cloned())
+                        .collect::<Vec<_>>()
+                })
+            }),
+            vm,
+            users,
+            last_share_ms: StoredValue::new(None),
+            share_pending: StoredValue::new(false),
+        }
+    }
+
+    /// Shares the local viewport for other users' minimaps, throttled with a
+    /// trailing update; newcomers get it as soon as their cursor shows up.
+    pub fn share_viewport(
+        self,
+        ws_sender: ReadSignal<Option<WsSender>>,
+        username: ReadSignal<String>,
+    ) {
+        let share = move || {
+            self.last_share_ms.set_value(Some(js_sys::Date::now()));
+            send_event(
+                &ws_sender,
+                ClientEvent::BoardViewport(viewport_payload(
+                    &username.get_untracked(),
+                    &self.own_viewport.get_untracked(),
+                )),
+            );
+        };
+        Effect::new(move |_| {
+            self.own_viewport.track();
+            self.users.track();
+            if viewport_share_due(self.last_share_ms.get_value(), js_sys::Date::now()) {
+                share();
+            } else if !self.share_pending.get_value() {
+                self.share_pending.set_value(true);
+                spawn_local(async move {
+                    gloo_timers::future::TimeoutFuture::new(VIEWPORT_SHARE_INTERVAL_MS as u32)
+                        .await;
+                    self.share_pending.set_value(false);
+                    share();
+                });
+            }
+        });
+    }
+
+    /// Centres the board on a world point through `move_camera`, keeping the zoom.
+    pub fn jump_callback(self, move_camera: Callback<(f64, f64, f64)>) -> Callback<(f64, f64)> {
+        Callback::new(move |(world_x, world_y): (f64, f64)| {
+            let zoom = self.vm.zoom.get_untracked();
+            let (camera_x, camera_y) = camera_centered_on(world_x, world_y, zoom);
+            move_camera.run((camera_x, camera_y, zoom));
+        })
+    }
+
+    /// Fits every scene into the board through `move_camera`.
+    pub fn fit_all_callback(
+        self,
+        scenes: Signal<Vec<Scene>>,
+        move_camera: Callback<(f64, f64, f64)>,
+    ) -> Callback<()> {
+        Callback::new(move |_| {
+            let vm = self.vm;
+            if let Some(camera) = scenes.with_untracked(|items| {
+                fit_all_camera(
+                    items,
+                    vm.viewport_width.get_untracked(),
+                    vm.viewport_height.get_untracked(),
+                )
+            }) {
+                move_camera.run(camera);
+            }
+        })
+    }
+}
+
+/// Minimap overlay in the top-right corner of the board.
+#[component]
+pub fn BoardMinimap(
+    /// Scenes as drawn on the local board.
+    scenes: Signal<Vec<Scene>>,
+    /// Board notes the local user can see.
+    notes: Signal<Vec<NotePayload>>,
+    /// Scene the local user plays on; drawn highlighted.
+    active_scene_id: Signal<Option<String>>,
+    /// Navigation is disabled while the game master locks the camera.
+    locked: Signal<bool>,
+    /// Centres the board on a world point.
+    on_jump: Callback<(f64, f64)>,
+    on_fit_all: Callback<()>,
+    state: BoardMinimapState,
+    theme: Theme,
+) -> impl IntoView {
+    let i18n = use_i18n();
+    let BoardMinimapState {
+        is_open,
+        is_dragging,
+        own_viewport,
+        viewports,
+        ..
+    } = state;
+    let map_ref = NodeRef::<html::Div>::new();
+
+    let transform = Memo::new(move |_| {
+        let mut shown =
+            viewports.with(|viewports| viewports.iter().map(payload_rect).collect::<Vec<_>>());
+        shown.push(own_viewport.get());
+        let bounds =
+            scenes.with(|scenes| notes.with(|notes| minimap_bounds(scenes, notes, &shown)));
+        bounds.map(|bounds| minimap_transform(&bounds, MINIMAP_WIDTH_PX, MINIMAP_HEIGHT_PX))
+    });
+    let rect_attrs = move |rect: WorldRect| {
+        let transform = transform.get()?;
+        let (left, top) = to_minimap(&transform, rect.left, rect.top);
+        let (right, bottom) = to_minimap(&transform, rect.right, rect.bottom);
+        Some((left, top, (right - left).max(1.0), (bottom - top).max(1.0)))
+    };
+    let jump_to = move |ev: &MouseEvent| {
+        if locked.get_untracked() {
+            return;
+        }
+        let (Some(map), Some(transform)) = (map_ref.get_untracked(), transform.get_untracked())
+        else {
+            return;
+        };
+        let bounds = map.get_bounding_client_rect();
+        let (world_x, world_y) = from_minimap(
+            &transform,
+            f64::from(ev.client_x()) - bounds.left(),
+            f64::from(ev.client_y()) - bounds.top(),
+        );
+        on_jump.run((world_x, world_y));
+    };
+
+    let toggle_style = move || {
+        let bg = if is_open.get() {
+            theme.ui_button_primary
+        } else {
+            theme.ui_bg_secondary
+        };
+        format!(
+            "display: flex; align-items: center; justify-content: center; width: 1.7rem; height: 1.7rem; \
+             border: 1px solid {}; border-radius: 0.45rem; background: {}; color: {}; cursor: pointer; \
+             font-size: 0.95rem; line-height: 1;",
+            theme.ui_border, bg, theme.ui_text_primary,
+        )
+    };
+    let map_cursor = move || {
+        if locked.get() {
+            "not-allowed"
+        } else if is_dragging.get() {
+            "grabbing"
+        } else {
+            "crosshair"
+        }
+    };
+    let scene_rects = move || {
+        let active_scene_id = active_scene_id.get();
+        scenes
+            .get()
+            .into_iter()
+            .filter_map(|scene| {
+                let (x, y, width, height) = rect_attrs(scene_world_rect(&scene))?;
+                let is_active = active_scene_id.as_deref() == Some(scene.id.as_str());
+                Some(view! {
+                    <rect
+                        x=format!("{x:.2}")
+                        y=format!("{y:.2}")
+                        width=format!("{width:.2}")
+                        height=format!("{height:.2}")
+                        rx="1.5"
+                        fill=if is_active { theme.ui_button_primary } else { theme.ui_bg_secondary }
+                        fill-opacity="0.75"
+                        stroke=theme.ui_border
+                        stroke-width="0.8"
+                    />
+                })
+            })
+            .collect_view()
+    };
+    let note_rects = move || {
+        let scenes = scenes.get();
+        notes
+            .get()
+            .into_iter()
+            .filter_map(|note| {
+                let (x, y, width, height) = rect_attrs(note_world_rect(&note, &scenes)?)?;
+                Some(view! {
+                    <rect
+                        x=format!("{x:.2}")
+                        y=format!("{y:.2}")
+                        width=format!("{:.2}", width.max(2.0))
+                        height=format!("{:.2}", height.max(2.0))
+                        fill=note.board_style.color
+                    />
+                })
+            })
+            .collect_view()
+    };
+    let viewport_rects = move || {
+        viewports
+            .get()
+            .into_iter()
+            .filter_map(|viewport| {
+                let (x, y, width, height) = rect_attrs(payload_rect(&viewport))?;
+                Some(view! {
+                    <rect
+                        x=format!("{x:.2}")
+                        y=format!("{y:.2}")
+                        width=format!("{width:.2}")
+                        height=format!("{height:.2}")
+                        fill="none"
+                        stroke=theme.ui_text_secondary
+                        stroke-width="1"
+                        stroke-dasharray="3 2"
+                    />
+                    <text
+                        x=format!("{:.2}", x + 2.0)
+                        y=format!("{:.2}", y + 8.0)
+                        font-size="7"
+                        fill=theme.ui_text_secondary
+                    >
+                        {viewport.username}
+                    </text>
+                })
+            })
+            .collect_view()
+    };
+    let own_rect = move || {
+        rect_attrs(own_viewport.get()).map(|(x, y, width, height)| {
+            view! {
+                <rect
+                    x=format!("{x:.2}")
+                    y=format!("{y:.2}")
+                    width=format!("{width:.2}")
+                    height=format!("{height:.2}")
+                    fill="white"
+                    fill-opacity="0.08"
+                    stroke="white"
+                    stroke-width="1.4"
+                />
+            }
+        })
+    };
+
+    view! {
+        <div
+            on:mousedown=move |ev: MouseEvent| ev.stop_propagation()
+            on:pointerdown=stop_pointer_propagation
+            on:click=move |ev: MouseEvent| ev.stop_propagation()
+            on:wheel=move |ev: web_sys::WheelEvent| ev.stop_propagation()
+            style=format!(
+                "position: absolute; right: 1rem; top: 1rem; display: flex; flex-direction: column; \
+                 gap: 0.4rem; padding: 0.4rem; background: rgba(0,0,0,0.48); border: 1px solid {}; \
+                 border-radius: 0.65rem; backdrop-filter: blur(8px); z-index: 6;",
+                theme.ui_border
+            )
+        >
+            <div style="display: flex; align-items: center; justify-content: flex-end; gap: 0.35rem;">
+                <Show when=move || is_open.get()>
+                    <button
+                        title=move || t_string!(i18n, scene_board.minimap_fit_all_title)
+                        disabled=move || locked.get()
+                        on:click=move |_| on_fit_all.run(())
+                        style=format!(
+                            "padding: 0.2rem 0.5rem; border: 1px solid {}; border-radius: 0.45rem; \
+                             background: {}; color: {}; font-size: 0.74rem; cursor: pointer;",
+                            theme.ui_border, theme.ui_bg_secondary, theme.ui_text_primary
+                        )
+                    >
+                        {move || t_string!(i18n, scene_board.minimap_fit_all)}
+                    </button>
+                </Show>
+                <button
+                    title=move || t_string!(i18n, scene_board.minimap_title)
+                    on:click=move |_| is_open.update(|open| *open = !*open)
+                    style=toggle_style
+                >
+                    {MINIMAP_ICON}
+                </button>
+            </div>
+            <Show when=move || is_open.get()>
+                <div
+                    node_ref=map_ref
+                    on:mousedown=move |ev: MouseEvent| {
+                        if ev.button() != 0 {
+                            return;
+                        }
+                        ev.prevent_default();
+                        is_dragging.set(!locked.get_untracked());
+                        jump_to(&ev);
+                    }
+                    on:mousemove=move |ev: MouseEvent| {
+                        if is_dragging.get_untracked() {
+                            jump_to(&ev);
+                        }
+                    }
+                    on:mouseup=move |_| is_dragging.set(false)
+                    on:mouseleave=move |_| is_dragging.set(false)
+                    style=move || format!(
+                        "width: {MINIMAP_WIDTH_PX}px; height: {MINIMAP_HEIGHT_PX}px; border-radius: 0.4rem; \
+                         background: rgba(0,0,0,0.35); overflow: hidden; cursor: {};",
+                        map_cursor()
+                    )
+                >
+                    <svg
+                        width=MINIMAP_WIDTH_PX.to_string()
+                        height=MINIMAP_HEIGHT_PX.to_string()
+                        viewBox=format!("0 0 {MINIMAP_WIDTH_PX} {MINIMAP_HEIGHT_PX}")
+                        style="display: block; pointer-events: none;"
+                    >
+                        {scene_rects}
+                        {note_rects}
+                        {viewport_rects}
+                        {own_rect}
+                    </svg>
+                </div>
+            </Show>
+        </div>
+    }
+}
@@ -1,12 +1,26 @@
+mod aura_layer;
 mod board_note_helpers;
 mod board_toolbar;
+pub mod camera;
+pub mod canvas;
+mod canvas_layer;
+pub mod export;
+mod export_panel;
 mod interaction_state;
+mod level_view;
+pub mod levels;
+pub mod minimap;
+mod minimap_panel;
 pub mod model;
 mod scene_geometry;
 mod storage;
+mod terrain_input;
+mod terrain_layer;
 mod token_editor;
 mod token_layer;
 mod token_menu;
+pub mod touch;
+mod touch_input;
 mod view;
 pub mod view_model;
 mod workspace_hint;
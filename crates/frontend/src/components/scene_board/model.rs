@@ -1,6 +1,11 @@
 // Pure geometric types and constants for the scene board.
 // No signals, no Leptos, no web_sys.
 
+use shared::events::{
+    AuraVisibility, NoteBoardPosition, NotePayload, NoteSceneAnchor, Scene, TerrainCell,
+    TerrainKind, acts_as_game_master, is_hex_color, terrain_fits_message,
+};
+
 // --- Board tools ---
 
 /// The active board tool selected by the local user.
@@ -12,6 +17,8 @@ pub enum BoardTool {
     Ruler,
     /// Miro-style pointer: user's cursor leaves a fading trail visible to others.
     Pointer,
+    /// Terrain brush: click or drag over a scene to paint (or erase) grid cells.
+    Terrain,
 }
 
 /// Calculates the DnD ruler distance between two points given in **scene-local
@@ -49,6 +56,268 @@ pub fn world_to_scene_cells(wx: f64, wy: f64, board_left: f64, board_top: f64) -
     )
 }
 
+// --- Terrain ---
+
+pub const TERRAIN_OVERLAY_OPACITY: f64 = 0.38;
+pub const TERRAIN_DEFAULT_CUSTOM_COLOR: &str = "#a855f7";
+
+/// Movement multiplier for entering a cell of the given terrain kind.
+/// Only difficult terrain slows movement: every square costs two.
+pub fn terrain_movement_multiplier(kind: TerrainKind) -> f64 {
+    match kind {
+        TerrainKind::Difficult => 2.0,
+        TerrainKind::Hazard | TerrainKind::Water | TerrainKind::Custom => 1.0,
+    }
+}
+
+pub fn terrain_cell_at(terrain: &[TerrainCell], column: u16, row: u16) -> Option<&TerrainCell> {
+    terrain
+        .iter()
+        .find(|cell| cell.column == column && cell.row == row)
+}
+
+/// Returns the whole grid cell containing a scene-local cell coordinate,
+/// or `None` when the point is outside the scene grid.
+pub fn scene_cell_at(cell_x: f64, cell_y: f64, columns: u16, rows: u16) -> Option<(u16, u16)> {
+    if cell_x < 0.0 || cell_y < 0.0 {
+        return None;
+    }
+    let column = cell_x.floor();
+    let row = cell_y.floor();
+    if column >= f64::from(columns) || row >= f64::from(rows) {
+        return None;
+    }
+    Some((column as u16, row as u16))
+}
+
+/// Lists the cells entered while walking from start to end in Chebyshev steps.
+/// The start cell itself is not included.
+pub fn cells_along_path(
+    start_cell_x: f64,
+    start_cell_y: f64,
+    end_cell_x: f64,
+    end_cell_y: f64,
+) -> Vec<(i64, i64)> {
+    let dx = end_cell_x - start_cell_x;
+    let dy = end_cell_y - start_cell_y;
+    let steps = dx.abs().max(dy.abs()).ceil() as usize;
+    let mut previous = (start_cell_x.floor() as i64, start_cell_y.floor() as i64);
+    let mut cells = Vec::with_capacity(steps);
+    for step in 1..=steps {
+        let t = step as f64 / steps as f64;
+        let cell = (
+            (start_cell_x + dx * t).floor() as i64,
+            (start_cell_y + dy * t).floor() as i64,
+        );
+        if cell != previous {
+            cells.push(cell);
+            previous = cell;
+        }
+    }
+    cells
+}
+
+/// Terrain-aware variant of [`ruler_distance`]: each difficult cell entered
+/// along the path adds one extra square to the measured distance.
+pub fn terrain_ruler_distance(
+    start_cell_x: f64,
+    start_cell_y: f64,
+    end_cell_x: f64,
+    end_cell_y: f64,
+    cell_size_feet: u16,
+    terrain: &[TerrainCell],
+) -> (f64, f64) {
+    let (base_cells, _) = ruler_distance(
+        start_cell_x,
+        start_cell_y,
+        end_cell_x,
+        end_cell_y,
+        cell_size_feet,
+    );
+    let extra_cells: f64 = cells_along_path(start_cell_x, start_cell_y, end_cell_x, end_cell_y)
+        .into_iter()
+        .filter_map(|(column, row)| {
+            terrain_cell_at(
+                terrain,
+                u16::try_from(column).ok()?,
+                u16::try_from(row).ok()?,
+            )
+        })
+        .map(|cell| terrain_movement_multiplier(cell.kind) - 1.0)
+        .sum();
+    let cells = base_cells + extra_cells;
+    (cells, cells * f64::from(cell_size_feet))
+}
+
+/// Paints (`Some`) or erases (`None`) a terrain cell in place.
+/// Returns `true` when the terrain actually changed; paint that would no
+/// longer fit one scene update is refused.
+pub fn apply_terrain_brush(
+    terrain: &mut Vec<TerrainCell>,
+    column: u16,
+    row: u16,
+    brush: Option<TerrainCell>,
+) -> bool {
+    let existing = terrain
+        .iter()
+        .position(|cell| cell.column == column && cell.row == row);
+    match (existing, brush) {
+        (Some(index), Some(cell)) if terrain[index] == cell => false,
+        (Some(index), Some(cell)) => {
+            let previous = std::mem::replace(&mut terrain[index], cell);
+            if terrain_fits_message(terrain) {
+                true
+            } else {
+                terrain[index] = previous;
+                false
+            }
+        }
+        (None, Some(cell)) => {
+            terrain.push(cell);
+            if terrain_fits_message(terrain) {
+                true
+            } else {
+                terrain.pop();
+                false
+            }
+        }
+        (Some(index), None) => {
+            terrain.remove(index);
+            true
+        }
+        (None, None) => false,
+    }
+}
+
+/// Overlay fill color for a painted cell. Custom colors fall back to a
+/// default when missing or not a `#RRGGBB` value.
+pub fn terrain_fill_color(cell: &TerrainCell) -> String {
+    match cell.kind {
+        TerrainKind::Difficult => "#a16207".to_string(),
+        TerrainKind::Hazard => "#dc2626".to_string(),
+        TerrainKind::Water => "#2563eb".to_string(),
+        TerrainKind::Custom => cell
+            .color
+            .as_deref()
+            .filter(|color| is_hex_color(color))
+            .unwrap_or(TERRAIN_DEFAULT_CUSTOM_COLOR)
+            .to_string(),
+    }
+}
+
+// --- Token facing and elevation ---
+
+pub const TOKEN_ROTATION_SNAP_DEG: f32 = 45.0;
+pub const TOKEN_ROTATION_FINE_STEP_DEG: f32 = 5.0;
+pub const TOKEN_ROTATION_HANDLE_OFFSET_PX: f64 = 16.0;
+pub const TOKEN_ROTATION_HANDLE_RADIUS_PX: f64 = 7.0;
+pub const MAX_TOKEN_ELEVATION_FEET: i32 = 10_000;
+
+/// Wraps an angle into `[0, 360)`.
+pub fn normalize_rotation_deg(deg: f32) -> f32 {
+    let wrapped = deg.rem_euclid(360.0);
+    if wrapped >= 360.0 { 0.0 } else { wrapped }
+}
+
+pub fn snap_rotation_deg(deg: f32) -> f32 {
+    normalize_rotation_deg((deg / TOKEN_ROTATION_SNAP_DEG).round() * TOKEN_ROTATION_SNAP_DEG)
+}
+
+/// Facing angle from a token center towards a point: 0° is up, clockwise.
+pub fn rotation_towards(center_x: f64, center_y: f64, point_x: f64, point_y: f64) -> f32 {
+    let deg = (point_x - center_x).atan2(center_y - point_y).to_degrees();
+    normalize_rotation_deg(deg as f32)
+}
+
+/// Center of the rotation handle, placed just outside the token in its facing direction.
+pub fn rotation_handle_center(
+    left: f64,
+    top: f64,
+    width: f64,
+    height: f64,
+    rotation_deg: f32,
+) -> (f64, f64) {
+    let radius = width.max(height) / 2.0 + TOKEN_ROTATION_HANDLE_OFFSET_PX;
+    let radians = f64::from(rotation_deg).to_radians();
+    (
+        left + width / 2.0 + radius * radians.sin(),
+        top + height / 2.0 - radius * radians.cos(),
+    )
+}
+
+pub fn clamp_token_elevation(elevation_feet: i32) -> i32 {
+    elevation_feet.clamp(-MAX_TOKEN_ELEVATION_FEET, MAX_TOKEN_ELEVATION_FEET)
+}
+
+/// 3D distance in feet. The vertical gap follows the same "every diagonal
+/// counts once" rule as the grid, so the longer of the two legs wins.
+pub fn elevated_distance_feet(horizontal_feet: f64, elevation_delta_feet: f64) -> f64 {
+    horizontal_feet.max(elevation_delta_feet.abs())
+}
+
+/// Badge text for a token's elevation; `None` at ground level.
+pub fn elevation_badge(elevation_feet: i32) -> Option<String> {
+    match elevation_feet {
+        0 => None,
+        feet if feet > 0 => Some(format!("↑{feet} ft")),
+        feet => Some(format!("↓{} ft", feet.unsigned_abs())),
+    }
+}
+
+// --- Token auras ---
+
+pub const MAX_TOKEN_AURAS: usize = 8;
+pub const MAX_AURA_RADIUS_FEET: u16 = 1000;
+pub const AURA_DEFAULT_RADIUS_FEET: u16 = 10;
+pub const AURA_DEFAULT_COLOR: &str = "#facc15";
+pub const AURA_DEFAULT_OPACITY: f32 = 0.2;
+
+/// Aura circle radius in board pixels, measured from the token center: the
+/// reach is counted from the token's edge, like 5e emanations.
+pub fn aura_radius_px(
+    radius_feet: u16,
+    cell_size_feet: u16,
+    cell_size_px: f64,
+    width_cells: u16,
+    height_cells: u16,
+) -> f64 {
+    let token_half = f64::from(width_cells.max(height_cells)) * cell_size_px / 2.0;
+    let reach = f64::from(radius_feet) / f64::from(cell_size_feet.max(1)) * cell_size_px;
+    token_half + reach
+}
+
+/// Whether `viewer` sees an aura. The game master sees every aura.
+pub fn aura_visible_to(
+    visibility: AuraVisibility,
+    owner: Option<&str>,
+    game_master: Option<&str>,
+    viewer: &str,
+) -> bool {
+    if acts_as_game_master(game_master, viewer) {
+        return true;
+    }
+    match visibility {
+        AuraVisibility::All => true,
+        AuraVisibility::Owner => owner == Some(viewer),
+        AuraVisibility::GameMaster => false,
+    }
+}
+
+/// Radii of the hover range rings: every visible aura, or one grid cell of
+/// melee reach when the token has none.
+pub fn hover_range_radii_feet(
+    visible_aura_radii: impl IntoIterator<Item = u16>,
+    cell_size_feet: u16,
+) -> Vec<u16> {
+    let mut radii: Vec<u16> = visible_aura_radii.into_iter().collect();
+    if radii.is_empty() {
+        radii.push(cell_size_feet);
+    }
+    radii.sort_unstable();
+    radii.dedup();
+    radii
+}
+
 // --- Constants ---
 
 pub const MIN_ZOOM: f64 = 0.35;
@@ -146,6 +415,8 @@ pub fn board_background(theme_bg: &str) -> String {
     )
 }
 
+/// `active_scene_id` is the viewer's own active scene: their split-party
+/// assignment when they have one (see `assigned_scene_id`), else the room's.
 pub fn scene_shows_contents(
     scene_id: &str,
     active_scene_id: Option<&str>,
@@ -273,9 +544,169 @@ pub fn centered_token_offset(cell_size: f64, width_cells: u16, height_cells: u16
     )
 }
 
+// --- Board focus ---
+
+/// A scene or token to bring into view, e.g. from a note reference.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BoardFocus {
+    Scene(String),
+    Token { scene_id: String, token_id: String },
+}
+
+/// World point at the center of the focused scene or token.
+pub fn board_focus_point(scenes: &[Scene], focus: &BoardFocus) -> Option<(f64, f64)> {
+    let scene_id = match focus {
+        BoardFocus::Scene(scene_id) | BoardFocus::Token { scene_id, .. } => scene_id,
+    };
+    let scene = scenes.iter().find(|scene| &scene.id == scene_id)?;
+    let center = (f64::from(scene.workspace_x), f64::from(scene.workspace_y));
+    let BoardFocus::Token { token_id, .. } = focus else {
+        return Some(center);
+    };
+    let token = scene.tokens.iter().find(|token| &token.id == token_id)?;
+    let (cell_size, width, height) = workspace_board_metrics(scene.grid.columns, scene.grid.rows);
+    let (left, top, token_width, token_height) = token_rect(
+        center.0 - width / 2.0,
+        center.1 - height / 2.0,
+        cell_size,
+        token.x,
+        token.y,
+        token.width_cells,
+        token.height_cells,
+    );
+    Some((left + token_width / 2.0, top + token_height / 2.0))
+}
+
+/// Camera offset that puts a world point in the middle of the viewport.
+pub fn camera_centered_on(world_x: f64, world_y: f64, zoom: f64) -> (f64, f64) {
+    (-world_x * zoom, -world_y * zoom)
+}
+
+// --- Scene-anchored board notes ---
+
+pub const BOARD_NOTE_PIN_SIZE_PX: f64 = 36.0;
+pub const BOARD_NOTE_PIN_ICONS: [&str; 6] = ["📍", "⚔️", "💰", "💀", "🚪", "❓"];
+
+fn scene_center(scene: &Scene) -> (f64, f64) {
+    (f64::from(scene.workspace_x), f64::from(scene.workspace_y))
+}
+
+/// World position of a board note. Anchored notes follow their scene; when the
+/// scene is gone the note stays at its last known point.
+pub fn board_note_world_position(position: &NoteBoardPosition, scenes: &[Scene]) -> (f64, f64) {
+    position
+        .anchor
+        .as_ref()
+        .and_then(|anchor| {
+            let scene = scenes.iter().find(|scene| scene.id == anchor.scene_id)?;
+            let (center_x, center_y) = scene_center(scene);
+            Some((center_x + anchor.offset_x, center_y + anchor.offset_y))
+        })
+        .unwrap_or((position.world_x, position.world_y))
+}
+
+/// Pins the note to `scene` where it currently stands.
+pub fn anchor_board_note(position: &mut NoteBoardPosition, scene: &Scene) {
+    let (center_x, center_y) = scene_center(scene);
+    position.anchor = Some(NoteSceneAnchor {
+        scene_id: scene.id.clone(),
+        offset_x: position.world_x - center_x,
+        offset_y: position.world_y - center_y,
+    });
+}
+
+/// Moves a note to a world point, keeping its scene offset in step.
+pub fn move_board_note(position: &mut NoteBoardPosition, world_x: f64, world_y: f64) {
+    if let Some(anchor) = position.anchor.as_mut() {
+        anchor.offset_x += world_x - position.world_x;
+        anchor.offset_y += world_y - position.world_y;
+    }
+    position.world_x = world_x;
+    position.world_y = world_y;
+}
+
+/// Brings the world point of anchored notes in line with their scenes.
+/// Returns true when any note moved.
+pub fn sync_anchored_notes(notes: &mut [NotePayload], scenes: &[Scene]) -> bool {
+    let mut changed = false;
+    for position in notes
+        .iter_mut()
+        .filter_map(|note| note.board_position.as_mut())
+    {
+        let (world_x, world_y) = board_note_world_position(position, scenes);
+        if (world_x - position.world_x).abs() > f64::EPSILON
+            || (world_y - position.world_y).abs() > f64::EPSILON
+        {
+            position.world_x = world_x;
+            position.world_y = world_y;
+            changed = true;
+        }
+    }
+    changed
+}
+
+/// Anchored notes are hidden together with their scene's contents.
+pub fn board_note_visible(
+    note: &NotePayload,
+    scenes: &[Scene],
+    active_scene_id: Option<&str>,
+    show_inactive_scene_contents: bool,
+) -> bool {
+    let Some(anchor) = note
+        .board_position
+        .as_ref()
+        .and_then(|position| position.anchor.as_ref())
+    else {
+        return true;
+    };
+    !scenes.iter().any(|scene| scene.id == anchor.scene_id)
+        || scene_shows_contents(
+            &anchor.scene_id,
+            active_scene_id,
+            show_inactive_scene_contents,
+        )
+}
+
+/// Scene a note at the given point would be anchored to: the board under the
+/// point, preferring the active scene, or else the active scene itself.
+pub fn scene_for_note_anchor<'a>(
+    scenes: &'a [Scene],
+    world_x: f64,
+    world_y: f64,
+    active_scene_id: Option<&str>,
+) -> Option<&'a Scene> {
+    let is_active = |scene: &Scene| active_scene_id == Some(scene.id.as_str());
+    scenes
+        .iter()
+        .filter(|scene| {
+            let (_, width, height) = workspace_board_metrics(scene.grid.columns, scene.grid.rows);
+            let (center_x, center_y) = scene_center(scene);
+            point_inside_rect(
+                world_x,
+                world_y,
+                center_x - width / 2.0,
+                center_y - height / 2.0,
+                width,
+                height,
+            )
+        })
+        .max_by_key(|scene| is_active(scene))
+        .or_else(|| scenes.iter().find(|scene| is_active(scene)))
+}
+
+/// Size a board note takes up while collapsed: map pins shrink to their icon.
+pub fn board_note_footprint(note: &NotePayload) -> (f64, f64) {
+    if note.board_style.pin_icon.is_some() {
+        (BOARD_NOTE_PIN_SIZE_PX, BOARD_NOTE_PIN_SIZE_PX)
+    } else {
+        (note.board_style.width_px, note.board_style.height_px)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use shared::events::MAX_SCENE_TERRAIN_CELLS;
 
     #[test]
     fn screen_to_world_and_back_roundtrip() {
@@ -467,8 +898,316 @@ mod tests {
         assert!((feet - 50.0).abs() < 1e-9);
     }
 
+    // --- terrain ---
+
+    fn terrain_cell(column: u16, row: u16, kind: TerrainKind) -> TerrainCell {
+        TerrainCell {
+            column,
+            row,
+            kind,
+            color: None,
+            label: None,
+        }
+    }
+
+    #[test]
+    fn scene_cell_at_rejects_points_outside_grid() {
+        assert_eq!(scene_cell_at(2.7, 0.1, 10, 10), Some((2, 0)));
+        assert_eq!(scene_cell_at(-0.1, 1.0, 10, 10), None);
+        assert_eq!(scene_cell_at(10.0, 1.0, 10, 10), None);
+    }
+
+    #[test]
+    fn cells_along_path_skips_start_cell() {
+        let cells = cells_along_path(0.5, 0.5, 3.5, 0.5);
+        assert_eq!(cells, vec![(1, 0), (2, 0), (3, 0)]);
+    }
+
+    #[test]
+    fn terrain_ruler_distance_without_terrain_matches_ruler() {
+        let plain = ruler_distance(0.5, 0.5, 4.5, 2.5, 5);
+        let with_terrain = terrain_ruler_distance(0.5, 0.5, 4.5, 2.5, 5, &[]);
+        assert_eq!(plain, with_terrain);
+    }
+
+    #[test]
+    fn terrain_ruler_distance_counts_difficult_cells_double() {
+        let terrain = vec![
+            terrain_cell(1, 0, TerrainKind::Difficult),
+            terrain_cell(2, 0, TerrainKind::Difficult),
+            terrain_cell(3, 0, TerrainKind::Water),
+        ];
+        let (cells, feet) = terrain_ruler_distance(0.5, 0.5, 3.5, 0.5, 5, &terrain);
+        assert!((cells - 5.0).abs() < 1e-9, "expected 5 cells, got {cells}");
+        assert!((feet - 25.0).abs() < 1e-9, "expected 25 ft, got {feet}");
+    }
+
+    #[test]
+    fn apply_terrain_brush_paints_replaces_and_erases() {
+        let mut terrain = Vec::new();
+        assert!(apply_terrain_brush(
+            &mut terrain,
+            1,
+            1,
+            Some(terrain_cell(1, 1, TerrainKind::Hazard))
+        ));
+        assert!(!apply_terrain_brush(
+            &mut terrain,
+            1,
+            1,
+            Some(terrain_cell(1, 1, TerrainKind::Hazard))
+        ));
+        assert!(apply_terrain_brush(
+            &mut terrain,
+            1,
+            1,
+            Some(terrain_cell(1, 1, TerrainKind::Water))
+        ));
+        assert_eq!(terrain.len(), 1);
+        assert_eq!(terrain[0].kind, TerrainKind::Water);
+        assert!(apply_terrain_brush(&mut terrain, 1, 1, None));
+        assert!(terrain.is_empty());
+        assert!(!apply_terrain_brush(&mut terrain, 1, 1, None));
+    }
+
+    #[test]
+    fn apply_terrain_brush_stops_at_the_message_budget() {
+        let mut terrain = (0..MAX_SCENE_TERRAIN_CELLS)
+            .map(|index| {
+                let (column, row) = ((index % 200) as u16, (index / 200) as u16);
+                terrain_cell(column, row, TerrainKind::Water)
+            })
+            .collect::<Vec<_>>();
+        assert!(!apply_terrain_brush(
+            &mut terrain,
+            199,
+            199,
+            Some(terrain_cell(199, 199, TerrainKind::Water))
+        ));
+        assert_eq!(terrain.len(), MAX_SCENE_TERRAIN_CELLS);
+        assert!(apply_terrain_brush(&mut terrain, 0, 0, None));
+        assert!(apply_terrain_brush(
+            &mut terrain,
+            199,
+            199,
+            Some(terrain_cell(199, 199, TerrainKind::Water))
+        ));
+    }
+
+    #[test]
+    fn terrain_fill_color_rejects_invalid_custom_colors() {
+        let mut cell = terrain_cell(0, 0, TerrainKind::Custom);
+        cell.color = Some("#12ab9F".to_string());
+        assert_eq!(terrain_fill_color(&cell), "#12ab9F");
+        cell.color = Some("red;opacity:0".to_string());
+        assert_eq!(terrain_fill_color(&cell), TERRAIN_DEFAULT_CUSTOM_COLOR);
+    }
+
+    // --- token facing and elevation ---
+
+    #[test]
+    fn normalize_rotation_wraps_negative_and_full_turns() {
+        assert_eq!(normalize_rotation_deg(-90.0), 270.0);
+        assert_eq!(normalize_rotation_deg(360.0), 0.0);
+        assert_eq!(normalize_rotation_deg(725.0), 5.0);
+    }
+
+    #[test]
+    fn snap_rotation_rounds_to_nearest_eighth_turn() {
+        assert_eq!(snap_rotation_deg(20.0), 0.0);
+        assert_eq!(snap_rotation_deg(23.0), 45.0);
+        assert_eq!(snap_rotation_deg(350.0), 0.0);
+        assert_eq!(snap_rotation_deg(-50.0), 315.0);
+    }
+
+    #[test]
+    fn rotation_towards_uses_up_as_zero_clockwise() {
+        assert_eq!(rotation_towards(0.0, 0.0, 0.0, -10.0), 0.0);
+        assert_eq!(rotation_towards(0.0, 0.0, 10.0, 0.0), 90.0);
+        assert_eq!(rotation_towards(0.0, 0.0, 0.0, 10.0), 180.0);
+        assert_eq!(rotation_towards(0.0, 0.0, -10.0, 0.0), 270.0);
+    }
+
+    #[test]
+    fn rotation_handle_sits_above_unrotated_token() {
+        let (hx, hy) = rotation_handle_center(0.0, 0.0, 48.0, 48.0, 0.0);
+        assert!((hx - 24.0).abs() < 1e-9);
+        assert!((hy - (24.0 - 24.0 - TOKEN_ROTATION_HANDLE_OFFSET_PX)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn elevated_distance_uses_longer_leg() {
+        assert_eq!(elevated_distance_feet(15.0, 30.0), 30.0);
+        assert_eq!(elevated_distance_feet(15.0, -10.0), 15.0);
+    }
+
+    #[test]
+    fn elevation_badge_hides_ground_level() {
+        assert_eq!(elevation_badge(0), None);
+        assert_eq!(elevation_badge(15).as_deref(), Some("↑15 ft"));
+        assert_eq!(elevation_badge(-10).as_deref(), Some("↓10 ft"));
+    }
+
     #[test]
     fn centered_token_offset_uses_token_dimensions() {
         assert_eq!(centered_token_offset(48.0, 2, 3), (48.0, 72.0));
     }
+
+    #[test]
+    fn aura_radius_counts_from_token_edge() {
+        assert_eq!(aura_radius_px(10, 5, 48.0, 1, 1), 24.0 + 96.0);
+        assert_eq!(aura_radius_px(5, 5, 48.0, 2, 1), 48.0 + 48.0);
+    }
+
+    #[test]
+    fn aura_visibility_respects_owner_and_game_master() {
+        let (gm, alice) = (Some("gm"), Some("alice"));
+        let gm_only = AuraVisibility::GameMaster;
+        assert!(aura_visible_to(AuraVisibility::All, None, gm, "alice"));
+        assert!(aura_visible_to(AuraVisibility::Owner, alice, gm, "alice"));
+        assert!(!aura_visible_to(AuraVisibility::Owner, alice, gm, "bob"));
+        assert!(!aura_visible_to(gm_only, alice, gm, "alice"));
+        assert!(aura_visible_to(gm_only, alice, gm, "gm"));
+        assert!(aura_visible_to(gm_only, None, None, "bob"));
+    }
+
+    #[test]
+    fn hover_range_falls_back_to_one_cell() {
+        assert_eq!(hover_range_radii_feet([], 5), vec![5]);
+        assert_eq!(hover_range_radii_feet([30, 10, 10], 5), vec![10, 30]);
+    }
+
+    #[test]
+    fn board_focus_centers_on_scene_or_token() {
+        let scene: Scene = serde_json::from_value(serde_json::json!({
+            "id": "s1",
+            "name": "Crypt",
+            "grid": { "columns": 10, "rows": 4, "cell_size_feet": 5 },
+            "workspace_x": 100.0,
+            "workspace_y": 50.0,
+            "tokens": [{
+                "id": "t1",
+                "name": "Goblin",
+                "image": { "hash": "h", "mime_type": "image/png", "file_name": "g.png", "size": 1 },
+                "x": 2.0,
+                "y": 1.0,
+                "width_cells": 2,
+                "height_cells": 2
+            }]
+        }))
+        .unwrap();
+        let scenes = [scene];
+
+        let scene_point = board_focus_point(&scenes, &BoardFocus::Scene("s1".to_string()));
+        assert_eq!(scene_point, Some((100.0, 50.0)));
+
+        let token = BoardFocus::Token {
+            scene_id: "s1".to_string(),
+            token_id: "t1".to_string(),
+        };
+        // Board spans x -140..340, y -46..146; the 2x2 token starts at cell (2, 1).
+        assert_eq!(
+            board_focus_point(&scenes, &token),
+            Some((-140.0 + 144.0, -46.0 + 96.0))
+        );
+        let missing = BoardFocus::Scene("gone".to_string());
+        assert_eq!(board_focus_point(&scenes, &missing), None);
+
+        let (camera_x, camera_y) = camera_centered_on(10.0, -20.0, 2.0);
+        assert_eq!(
+            world_to_screen(10.0, -20.0, 800.0, 600.0, camera_x, camera_y, 2.0),
+            (400.0, 300.0)
+        );
+    }
+
+    fn anchor_test_scene(id: &str, workspace_x: f64, workspace_y: f64) -> Scene {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "name": id,
+            "grid": { "columns": 10, "rows": 4, "cell_size_feet": 5 },
+            "workspace_x": workspace_x,
+            "workspace_y": workspace_y
+        }))
+        .unwrap()
+    }
+
+    fn board_note(position: NoteBoardPosition) -> NotePayload {
+        serde_json::from_value(serde_json::json!({
+            "id": "n1",
+            "author": "gm",
+            "visibility": "Public",
+            "body": "Trap",
+            "created_at_ms": 1.0,
+            "updated_at_ms": 1.0,
+            "board_position": position
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn anchored_notes_follow_their_scene() {
+        let mut scenes = vec![anchor_test_scene("s1", 100.0, 50.0)];
+        let mut position = NoteBoardPosition {
+            world_x: 120.0,
+            world_y: 40.0,
+            anchor: None,
+        };
+        anchor_board_note(&mut position, &scenes[0]);
+        move_board_note(&mut position, 130.0, 45.0);
+        assert_eq!(board_note_world_position(&position, &scenes), (130.0, 45.0));
+
+        scenes[0].workspace_x = 300.0;
+        let mut notes = vec![board_note(position)];
+        assert!(sync_anchored_notes(&mut notes, &scenes));
+        assert!(!sync_anchored_notes(&mut notes, &scenes));
+        let synced = notes[0].board_position.as_ref().unwrap();
+        assert_eq!((synced.world_x, synced.world_y), (330.0, 45.0));
+
+        // Deleting the scene leaves the note where it was last seen.
+        assert_eq!(board_note_world_position(synced, &[]), (330.0, 45.0));
+    }
+
+    #[test]
+    fn anchored_notes_hide_with_scene_contents() {
+        let scenes = vec![
+            anchor_test_scene("s1", 0.0, 0.0),
+            anchor_test_scene("s2", 1000.0, 0.0),
+        ];
+        let mut position = NoteBoardPosition {
+            world_x: 1010.0,
+            world_y: 0.0,
+            anchor: None,
+        };
+        let free = board_note(position.clone());
+        assert!(board_note_visible(&free, &scenes, Some("s1"), false));
+
+        let scene = scene_for_note_anchor(&scenes, 1010.0, 0.0, Some("s1")).unwrap();
+        assert_eq!(scene.id, "s2");
+        anchor_board_note(&mut position, scene);
+        let pinned = board_note(position);
+        assert!(!board_note_visible(&pinned, &scenes, Some("s1"), false));
+        assert!(board_note_visible(&pinned, &scenes, Some("s2"), false));
+        assert!(board_note_visible(&pinned, &scenes, Some("s1"), true));
+        assert!(board_note_visible(&pinned, &scenes[..1], Some("s1"), false));
+
+        // Off every board, notes fall back to the active scene.
+        let fallback = scene_for_note_anchor(&scenes, 500.0, 900.0, Some("s1")).unwrap();
+        assert_eq!(fallback.id, "s1");
+        assert!(scene_for_note_anchor(&scenes, 500.0, 900.0, None).is_none());
+    }
+
+    #[test]
+    fn map_pins_collapse_to_their_icon() {
+        let mut note = board_note(NoteBoardPosition {
+            world_x: 0.0,
+            world_y: 0.0,
+            anchor: None,
+        });
+        assert_eq!(board_note_footprint(&note), (280.0, 220.0));
+        note.board_style.pin_icon = Some(BOARD_NOTE_PIN_ICONS[0].to_string());
+        assert_eq!(
+            board_note_footprint(&note),
+            (BOARD_NOTE_PIN_SIZE_PX, BOARD_NOTE_PIN_SIZE_PX)
+        );
+    }
 }
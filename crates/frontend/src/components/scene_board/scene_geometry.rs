@@ -4,14 +4,19 @@
 use super::interaction_state::{
     BOARD_NOTE_EDIT_PADDING_PX, BOARD_NOTE_RESIZE_HANDLE_PX, SceneLayout,
 };
+use super::levels::take_stairs;
 use super::model::{
     BOARD_HANDLE_GAP_PX, BOARD_HANDLE_HEIGHT_PX, BOARD_HANDLE_MAX_WIDTH_PX, SNAP_THRESHOLD_PX,
-    clamp_token_position, point_inside_rect, token_rect, workspace_board_metrics,
+    TOKEN_ROTATION_HANDLE_RADIUS_PX, apply_terrain_brush, board_note_footprint,
+    clamp_token_elevation, clamp_token_position, normalize_rotation_deg, point_inside_rect,
+    rotation_handle_center, snap_rotation_deg, token_rect, workspace_board_metrics,
 };
+use super::token_editor::SceneTokenEditorValue;
+use super::touch::distance_to_rect;
 use crate::components::websocket::{StoredTokenLibraryItem, WsSender};
 use leptos::html;
 use leptos::prelude::*;
-use shared::events::{ClientEvent, NotePayload, Scene, Token};
+use shared::events::{ClientEvent, NotePayload, Scene, TerrainCell, Token};
 use uuid::Uuid;
 
 // ---------------------------------------------------------------------------
@@ -81,6 +86,12 @@ pub fn viewport_local_point(
     Some((x, y))
 }
 
+/// Keeps presses on panels and notes from reaching the board viewport, which
+/// handles `pointerdown` for mouse, pen and touch alike.
+pub fn stop_pointer_propagation(event: web_sys::PointerEvent) {
+    event.stop_propagation();
+}
+
 // ---------------------------------------------------------------------------
 // Scene layout construction
 // ---------------------------------------------------------------------------
@@ -131,15 +142,9 @@ pub fn point_inside_handle(layout: &SceneLayout, wx: f64, wy: f64) -> bool {
 pub fn board_note_hit(notes: &[NotePayload], wx: f64, wy: f64) -> Option<NotePayload> {
     notes.iter().rev().find_map(|note| {
         let position = note.board_position.as_ref()?;
-        point_inside_rect(
-            wx,
-            wy,
-            position.world_x,
-            position.world_y,
-            note.board_style.width_px,
-            note.board_style.height_px,
-        )
-        .then(|| note.clone())
+        let (width, height) = board_note_footprint(note);
+        point_inside_rect(wx, wy, position.world_x, position.world_y, width, height)
+            .then(|| note.clone())
     })
 }
 
@@ -195,6 +200,58 @@ pub fn token_hit(layout: &SceneLayout, wx: f64, wy: f64) -> Option<Token> {
         .cloned()
 }
 
+/// Token under a finger: the topmost one containing the point, otherwise the
+/// closest one within `reach` world pixels, since fingers land less precisely.
+pub fn token_touch_hit(layout: &SceneLayout, wx: f64, wy: f64, reach: f64) -> Option<Token> {
+    token_hit(layout, wx, wy).or_else(|| {
+        layout
+            .scene
+            .tokens
+            .iter()
+            .map(|token| {
+                let (left, top, width, height) = token_rect(
+                    layout.left(),
+                    layout.top(),
+                    layout.cell_size,
+                    token.x,
+                    token.y,
+                    token.width_cells,
+                    token.height_cells,
+                );
+                (distance_to_rect(wx, wy, left, top, width, height), token)
+            })
+            .filter(|(distance, _)| *distance <= reach)
+            .min_by(|(a, _), (b, _)| a.total_cmp(b))
+            .map(|(_, token)| token.clone())
+    })
+}
+
+/// Hit-tests the rotation handle of the given token across all scene layouts.
+/// Returns the owning scene id and the token when the handle is under the point.
+pub fn rotation_handle_hit(
+    layouts: &[SceneLayout],
+    token_id: &str,
+    wx: f64,
+    wy: f64,
+) -> Option<(String, Token)> {
+    layouts.iter().find_map(|layout| {
+        let token = layout.scene.tokens.iter().find(|token| token.id == token_id)?;
+        let (left, top, width, height) = token_rect(
+            layout.left(),
+            layout.top(),
+            layout.cell_size,
+            token.x,
+            token.y,
+            token.width_cells,
+            token.height_cells,
+        );
+        let (handle_x, handle_y) =
+            rotation_handle_center(left, top, width, height, token.rotation_deg);
+        ((wx - handle_x).hypot(wy - handle_y) <= TOKEN_ROTATION_HANDLE_RADIUS_PX * 1.5)
+            .then(|| (layout.scene.id.clone(), token.clone()))
+    })
+}
+
 pub fn clamp_to_layout(wx: f64, wy: f64, layout: &SceneLayout) -> (f64, f64) {
     (
         wx.clamp(layout.left(), layout.right()),
@@ -284,12 +341,69 @@ pub fn update_token_position(scenes: RwSignal<Vec<Scene>>, id: &str, x: f32, y:
     });
 }
 
+pub fn update_token_rotation(scenes: RwSignal<Vec<Scene>>, id: &str, rotation_deg: f32) {
+    scenes.update(|items| {
+        for scene in items {
+            if let Some(token) = scene.tokens.iter_mut().find(|token| token.id == id) {
+                token.rotation_deg = normalize_rotation_deg(rotation_deg);
+                break;
+            }
+        }
+    });
+}
+
+/// Rotates a token by `delta_deg`, optionally snapping the result to 45° steps.
+pub fn rotate_token_by(
+    scenes: RwSignal<Vec<Scene>>,
+    token_id: &str,
+    delta_deg: f32,
+    snap: bool,
+) -> Option<Scene> {
+    let mut updated_scene = None::<Scene>;
+    scenes.update(|items| {
+        for scene in items.iter_mut() {
+            let Some(token) = scene.tokens.iter_mut().find(|token| token.id == token_id) else {
+                continue;
+            };
+            let rotated = token.rotation_deg + delta_deg;
+            token.rotation_deg = if snap {
+                snap_rotation_deg(rotated)
+            } else {
+                normalize_rotation_deg(rotated)
+            };
+            updated_scene = Some(scene.clone());
+            break;
+        }
+    });
+    updated_scene
+}
+
+/// Moves a token resting on stairs to the linked floor; returns its scene and
+/// the floor it arrived on.
+pub fn take_token_stairs(
+    scenes: RwSignal<Vec<Scene>>,
+    token_id: &str,
+) -> Option<(Scene, Option<String>)> {
+    let mut climbed = None;
+    scenes.update(|items| {
+        for scene in items.iter_mut() {
+            if let Some(level_id) = take_stairs(scene, token_id) {
+                climbed = Some((scene.clone(), level_id));
+                break;
+            }
+        }
+    });
+    climbed
+}
+
 pub fn place_library_token(
     scenes: RwSignal<Vec<Scene>>,
     scene_id: &str,
     token: &StoredTokenLibraryItem,
     x: f32,
     y: f32,
+    owner: Option<String>,
+    level_id: Option<String>,
 ) -> Option<Scene> {
     let mut updated_scene = None::<Scene>;
     scenes.update(|items| {
@@ -304,6 +418,11 @@ pub fn place_library_token(
             y,
             width_cells: token.width_cells,
             height_cells: token.height_cells,
+            rotation_deg: 0.0,
+            elevation_feet: 0,
+            owner,
+            auras: Vec::new(),
+            level_id,
         });
         updated_scene = Some(scene.clone());
     });
@@ -331,27 +450,37 @@ pub fn remove_token_from_scene(
 
 pub fn update_token_details(
     scenes: RwSignal<Vec<Scene>>,
-    scene_id: &str,
-    token_id: &str,
-    name: &str,
-    width_cells: u16,
-    height_cells: u16,
+    value: &SceneTokenEditorValue,
 ) -> Option<Scene> {
     let mut updated_scene = None::<Scene>;
     scenes.update(|items| {
-        let Some(scene) = items.iter_mut().find(|scene| scene.id == scene_id) else {
+        let Some(scene) = items.iter_mut().find(|scene| scene.id == value.scene_id) else {
             return;
         };
         let columns = scene.grid.columns;
         let rows = scene.grid.rows;
-        let Some(token) = scene.tokens.iter_mut().find(|token| token.id == token_id) else {
+        let Some(token) = scene
+            .tokens
+            .iter_mut()
+            .find(|token| token.id == value.token_id)
+        else {
             return;
         };
-        token.name = name.to_string();
-        token.width_cells = width_cells;
-        token.height_cells = height_cells;
-        let (x, y) =
-            clamp_token_position(token.x, token.y, columns, rows, width_cells, height_cells);
+        token.name = value.name.clone();
+        token.width_cells = value.width_cells;
+        token.height_cells = value.height_cells;
+        token.rotation_deg = normalize_rotation_deg(value.rotation_deg);
+        token.elevation_feet = clamp_token_elevation(value.elevation_feet);
+        token.owner = value.owner.clone();
+        token.auras = value.auras.clone();
+        let (x, y) = clamp_token_position(
+            token.x,
+            token.y,
+            columns,
+            rows,
+            value.width_cells,
+            value.height_cells,
+        );
         token.x = x;
         token.y = y;
         updated_scene = Some(scene.clone());
@@ -359,6 +488,24 @@ pub fn update_token_details(
     updated_scene
 }
 
+/// Paints or erases one terrain cell; only notifies subscribers when the terrain changed.
+pub fn paint_scene_terrain(
+    scenes: RwSignal<Vec<Scene>>,
+    scene_id: &str,
+    column: u16,
+    row: u16,
+    brush: Option<TerrainCell>,
+) -> bool {
+    let mut changed = false;
+    scenes.maybe_update(|items| {
+        if let Some(scene) = items.iter_mut().find(|scene| scene.id == scene_id) {
+            changed = apply_terrain_brush(&mut scene.terrain, column, row, brush);
+        }
+        changed
+    });
+    changed
+}
+
 pub fn sort_token_library_items(items: &mut [StoredTokenLibraryItem]) {
     items.sort_by(|left, right| {
         left.name
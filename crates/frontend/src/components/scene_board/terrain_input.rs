@@ -0,0 +1,115 @@
+// Terrain painting glue for the scene board: strokes of the terrain tool,
+// painted cell by cell and shared as one scene update when the button is
+// released.
+
+use super::interaction_state::SceneLayout;
+use super::level_view::BoardLevels;
+use super::model::{scene_allows_token_interaction, scene_cell_at, world_to_scene_cells};
+use super::scene_geometry::{
+    build_scene_layouts, paint_scene_terrain, point_inside_board, send_event,
+};
+use super::view_model::SceneBoardViewModel;
+use crate::components::websocket::WsSender;
+use leptos::prelude::*;
+use shared::events::{ClientEvent, Scene, SceneUpdatePayload};
+
+/// Terrain strokes of the local user.
+#[derive(Clone, Copy)]
+pub struct BoardTerrain {
+    vm: SceneBoardViewModel,
+    scenes: RwSignal<Vec<Scene>>,
+    levels: BoardLevels,
+    active_scene_id: Memo<Option<String>>,
+    show_inactive_scene_contents: RwSignal<bool>,
+}
+
+impl BoardTerrain {
+    pub fn new(
+        vm: SceneBoardViewModel,
+        scenes: RwSignal<Vec<Scene>>,
+        levels: BoardLevels,
+        active_scene_id: Memo<Option<String>>,
+        show_inactive_scene_contents: RwSignal<bool>,
+    ) -> Self {
+        Self {
+            vm,
+            scenes,
+            levels,
+            active_scene_id,
+            show_inactive_scene_contents,
+        }
+    }
+
+    /// Starts a stroke on the topmost scene under the point and paints its
+    /// first cell. Terrain belongs to the base floor.
+    pub fn press(self, world_x: f64, world_y: f64) {
+        let active_id = self.active_scene_id.get_untracked();
+        let allow_inactive = self.show_inactive_scene_contents.get_untracked();
+        let layouts = build_scene_layouts(&self.levels.board_scenes.get_untracked());
+        let Some(layout) = layouts.iter().rev().find(|l| {
+            point_inside_board(l, world_x, world_y)
+                && scene_allows_token_interaction(
+                    l.scene.id.as_str(),
+                    active_id.as_deref(),
+                    allow_inactive,
+                )
+        }) else {
+            return;
+        };
+        if self
+            .levels
+            .scene_level_untracked(&layout.scene.id)
+            .is_some()
+        {
+            return;
+        }
+        self.vm.start_terrain_stroke(layout.scene.id.clone());
+        self.paint(layout, world_x, world_y);
+    }
+
+    /// Keeps painting while the button is held; false when no stroke is in
+    /// progress.
+    pub fn drag(self, layouts: &[SceneLayout], world_x: f64, world_y: f64) -> bool {
+        let Some(scene_id) = self.vm.terrain_stroke_scene_id.get_untracked() else {
+            return false;
+        };
+        if let Some(layout) = layouts.iter().find(|l| l.scene.id == scene_id) {
+            self.paint(layout, world_x, world_y);
+        }
+        true
+    }
+
+    /// Ends the stroke and shares the painted scene, if anything changed.
+    pub fn release(self, ws_sender: &ReadSignal<Option<WsSender>>, actor: String) {
+        if let Some(scene_id) = self.vm.end_terrain_stroke()
+            && let Some(scene) = self
+                .scenes
+                .get_untracked()
+                .into_iter()
+                .find(|s| s.id == scene_id)
+        {
+            send_event(
+                ws_sender,
+                ClientEvent::SceneUpdate(SceneUpdatePayload { scene, actor }),
+            );
+        }
+    }
+
+    fn paint(self, layout: &SceneLayout, world_x: f64, world_y: f64) {
+        let (cell_x, cell_y) = world_to_scene_cells(world_x, world_y, layout.left(), layout.top());
+        if let Some((column, row)) = scene_cell_at(
+            cell_x,
+            cell_y,
+            layout.scene.grid.columns,
+            layout.scene.grid.rows,
+        ) && paint_scene_terrain(
+            self.scenes,
+            &layout.scene.id,
+            column,
+            row,
+            self.vm.terrain_brush_cell(column, row),
+        ) {
+            self.vm.mark_terrain_stroke_changed();
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use super::model::{TERRAIN_OVERLAY_OPACITY, terrain_fill_color};
+use leptos::prelude::*;
+use shared::events::{TerrainCell, TerrainKind};
+
+/// Translucent overlay of painted terrain cells, drawn in board-local pixels
+/// between the background image and the grid lines.
+#[component]
+pub fn SceneTerrainLayer(
+    terrain: Vec<TerrainCell>,
+    cell_size: f64,
+    board_width: f64,
+    board_height: f64,
+) -> impl IntoView {
+    if terrain.is_empty() {
+        return ().into_any();
+    }
+
+    let label_font = (cell_size * 0.22).clamp(8.0, 14.0);
+
+    view! {
+        <svg
+            viewBox=format!("0 0 {:.4} {:.4}", board_width, board_height)
+            preserveAspectRatio="none"
+            style="position: absolute; inset: 0; width: 100%; height: 100%; pointer-events: none;"
+        >
+            {terrain.into_iter().map(|cell| {
+                let x = f64::from(cell.column) * cell_size;
+                let y = f64::from(cell.row) * cell_size;
+                let fill = terrain_fill_color(&cell);
+                // Difficult terrain gets a diagonal stroke so it stays readable over busy maps.
+                let hatch = (cell.kind == TerrainKind::Difficult).then(|| view! {
+                    <line
+                        x1=format!("{x:.4}")
+                        y1=format!("{:.4}", y + cell_size)
+                        x2=format!("{:.4}", x + cell_size)
+                        y2=format!("{y:.4}")
+                        stroke=fill.clone()
+                        stroke-width="2"
+                        stroke-opacity="0.7"
+                    />
+                });
+                let label = cell.label.clone().map(|text| view! {
+                    <text
+                        x=format!("{:.4}", x + cell_size / 2.0)
+                        y=format!("{:.4}", y + cell_size / 2.0)
+                        text-anchor="middle"
+                        dominant-baseline="central"
+                        fill="#ffffff"
+                        font-size=format!("{label_font:.2}")
+                        font-weight="700"
+                    >
+                        {text}
+                    </text>
+                });
+                view! {
+                    <rect
+                        x=format!("{x:.4}")
+                        y=format!("{y:.4}")
+                        width=format!("{cell_size:.4}")
+                        height=format!("{cell_size:.4}")
+                        fill=fill
+                        fill-opacity=format!("{TERRAIN_OVERLAY_OPACITY:.2}")
+                    />
+                    {hatch}
+                    {label}
+                }
+            }).collect_view()}
+        </svg>
+    }
+    .into_any()
+}
@@ -1,9 +1,15 @@
-use super::model::{MAX_TOKEN_SIZE_CELLS, MIN_TOKEN_SIZE_CELLS};
+use super::model::{
+    AURA_DEFAULT_COLOR, AURA_DEFAULT_OPACITY, AURA_DEFAULT_RADIUS_FEET, MAX_AURA_RADIUS_FEET,
+    MAX_TOKEN_AURAS, MAX_TOKEN_ELEVATION_FEET, MAX_TOKEN_SIZE_CELLS, MIN_TOKEN_SIZE_CELLS,
+    normalize_rotation_deg,
+};
 use crate::config::Theme;
 use crate::i18n::i18n::{t, t_string, use_i18n};
 use leptos::ev::MouseEvent;
 use leptos::portal::Portal;
 use leptos::prelude::*;
+use shared::events::{AuraVisibility, Token, TokenAura, is_hex_color};
+use uuid::Uuid;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SceneTokenEditorDraft {
@@ -12,21 +18,124 @@ pub struct SceneTokenEditorDraft {
     pub name: String,
     pub width_cells: String,
     pub height_cells: String,
+    pub rotation_deg: String,
+    pub elevation_feet: String,
+    /// Owning player; empty means the token belongs to the game master.
+    pub owner: String,
+    pub auras: Vec<SceneTokenAuraDraft>,
+}
+
+impl SceneTokenEditorDraft {
+    pub fn from_token(scene_id: &str, token: &Token) -> Self {
+        Self {
+            scene_id: scene_id.to_string(),
+            token_id: token.id.clone(),
+            name: token.name.clone(),
+            width_cells: token.width_cells.to_string(),
+            height_cells: token.height_cells.to_string(),
+            rotation_deg: format!("{:.0}", token.rotation_deg),
+            elevation_feet: token.elevation_feet.to_string(),
+            owner: token.owner.clone().unwrap_or_default(),
+            auras: token
+                .auras
+                .iter()
+                .map(SceneTokenAuraDraft::from_aura)
+                .collect(),
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SceneTokenAuraDraft {
+    pub id: String,
+    pub label: String,
+    pub radius_feet: String,
+    pub color: String,
+    /// Opacity in whole percent, as edited by the range input.
+    pub opacity_percent: String,
+    pub visibility: AuraVisibility,
+}
+
+impl SceneTokenAuraDraft {
+    fn new_default() -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            label: String::new(),
+            radius_feet: AURA_DEFAULT_RADIUS_FEET.to_string(),
+            color: AURA_DEFAULT_COLOR.to_string(),
+            opacity_percent: format!("{:.0}", AURA_DEFAULT_OPACITY * 100.0),
+            visibility: AuraVisibility::All,
+        }
+    }
+
+    fn from_aura(aura: &TokenAura) -> Self {
+        Self {
+            id: aura.id.clone(),
+            label: aura.label.clone().unwrap_or_default(),
+            radius_feet: aura.radius_feet.to_string(),
+            color: aura.color.clone(),
+            opacity_percent: format!("{:.0}", aura.opacity * 100.0),
+            visibility: aura.visibility,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct SceneTokenEditorValue {
     pub scene_id: String,
     pub token_id: String,
     pub name: String,
     pub width_cells: u16,
     pub height_cells: u16,
+    pub rotation_deg: f32,
+    pub elevation_feet: i32,
+    pub owner: Option<String>,
+    pub auras: Vec<TokenAura>,
+}
+
+fn aura_visibility_value(visibility: AuraVisibility) -> &'static str {
+    match visibility {
+        AuraVisibility::All => "all",
+        AuraVisibility::Owner => "owner",
+        AuraVisibility::GameMaster => "gm",
+    }
+}
+
+fn parse_aura_visibility(value: &str) -> AuraVisibility {
+    match value {
+        "owner" => AuraVisibility::Owner,
+        "gm" => AuraVisibility::GameMaster,
+        _ => AuraVisibility::All,
+    }
+}
+
+fn validate_aura_draft(draft: &SceneTokenAuraDraft) -> Option<TokenAura> {
+    let radius_feet = draft.radius_feet.trim().parse::<u16>().ok()?;
+    let opacity_percent = draft.opacity_percent.trim().parse::<f32>().ok()?;
+    if !(1..=MAX_AURA_RADIUS_FEET).contains(&radius_feet)
+        || !(0.0..=100.0).contains(&opacity_percent)
+        || !is_hex_color(&draft.color)
+    {
+        return None;
+    }
+
+    let label = draft.label.trim();
+    Some(TokenAura {
+        id: draft.id.clone(),
+        label: (!label.is_empty()).then(|| label.to_string()),
+        radius_feet,
+        color: draft.color.clone(),
+        opacity: opacity_percent / 100.0,
+        visibility: draft.visibility,
+    })
 }
 
 fn validate_editor_draft(
     draft: &SceneTokenEditorDraft,
     empty_name_error: &str,
     invalid_dimensions_error: &str,
+    invalid_facing_error: &str,
+    invalid_aura_error: &str,
 ) -> Result<SceneTokenEditorValue, String> {
     let name = draft.name.trim();
     if name.is_empty() {
@@ -46,12 +155,39 @@ fn validate_editor_draft(
         return Err(invalid_dimensions_error.to_string());
     }
 
+    let Ok(rotation_deg) = draft.rotation_deg.trim().parse::<f32>() else {
+        return Err(invalid_facing_error.to_string());
+    };
+    let Ok(elevation_feet) = draft.elevation_feet.trim().parse::<i32>() else {
+        return Err(invalid_facing_error.to_string());
+    };
+    if !rotation_deg.is_finite() || elevation_feet.abs() > MAX_TOKEN_ELEVATION_FEET {
+        return Err(invalid_facing_error.to_string());
+    }
+
+    if draft.auras.len() > MAX_TOKEN_AURAS {
+        return Err(invalid_aura_error.to_string());
+    }
+    let Some(auras) = draft
+        .auras
+        .iter()
+        .map(validate_aura_draft)
+        .collect::<Option<Vec<_>>>()
+    else {
+        return Err(invalid_aura_error.to_string());
+    };
+
+    let owner = draft.owner.trim();
     Ok(SceneTokenEditorValue {
         scene_id: draft.scene_id.clone(),
         token_id: draft.token_id.clone(),
         name: name.to_string(),
         width_cells,
         height_cells,
+        rotation_deg: normalize_rotation_deg(rotation_deg),
+        elevation_feet,
+        owner: (!owner.is_empty()).then(|| owner.to_string()),
+        auras,
     })
 }
 
@@ -60,10 +196,57 @@ pub fn SceneTokenEditor(
     #[prop(into)] draft: RwSignal<Option<SceneTokenEditorDraft>>,
     on_save: Callback<SceneTokenEditorValue>,
     on_close: Callback<()>,
+    /// Only the game master may hand tokens to players.
+    #[prop(into)]
+    can_assign_owner: Signal<bool>,
     theme: Theme,
 ) -> impl IntoView {
     let i18n = use_i18n();
     let error = RwSignal::new(None::<String>);
+    let input_style = StoredValue::new(format!(
+        "padding: 0.75rem; background: {}; color: {}; border: 0.0625rem solid {}; border-radius: 0.6rem; font-size: 0.95rem;",
+        theme.ui_bg_secondary, theme.ui_text_primary, theme.ui_border
+    ));
+    let compact_input_style = StoredValue::new(format!(
+        "padding: 0.45rem 0.55rem; background: {}; color: {}; border: 0.0625rem solid {}; border-radius: 0.5rem; font-size: 0.85rem; min-width: 0;",
+        theme.ui_bg_primary, theme.ui_text_primary, theme.ui_border
+    ));
+    let aura_card_style = StoredValue::new(format!(
+        "display: grid; grid-template-columns: minmax(0, 1fr) 4.5rem 2.75rem; gap: 0.5rem; padding: 0.6rem; \
+         border-radius: 0.7rem; background: {}; border: 0.0625rem solid {};",
+        theme.ui_bg_secondary, theme.ui_border
+    ));
+    let aura_ids = move || {
+        draft
+            .get()
+            .map(|draft| {
+                draft
+                    .auras
+                    .into_iter()
+                    .map(|aura| aura.id)
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default()
+    };
+    let aura_field = move |aura_id: &str, read: fn(&SceneTokenAuraDraft) -> String| {
+        draft.with(|draft| {
+            draft
+                .as_ref()
+                .and_then(|draft| draft.auras.iter().find(|aura| aura.id == aura_id))
+                .map(read)
+                .unwrap_or_default()
+        })
+    };
+    let update_aura = move |aura_id: &str, apply: &dyn Fn(&mut SceneTokenAuraDraft)| {
+        draft.update(|draft| {
+            if let Some(aura) = draft
+                .as_mut()
+                .and_then(|draft| draft.auras.iter_mut().find(|aura| aura.id == aura_id))
+            {
+                apply(aura);
+            }
+        });
+    };
 
     Effect::new(move |_| {
         if draft.get().is_none() {
@@ -84,7 +267,8 @@ pub fn SceneTokenEditor(
                         on:mousedown=move |event: MouseEvent| event.stop_propagation()
                         on:click=move |event| event.stop_propagation()
                         style=format!(
-                            "width: min(30rem, calc(100vw - 2.5rem)); padding: 1.35rem; border-radius: 1rem; \
+                            "width: min(30rem, calc(100vw - 2.5rem)); max-height: calc(100vh - 2.5rem); overflow-y: auto; \
+                             padding: 1.35rem; border-radius: 1rem; \
                              border: 0.0625rem solid {}; background: linear-gradient(180deg, {}, {}); \
                              box-shadow: 0 1.5rem 4rem rgba(0,0,0,0.38); display: flex; flex-direction: column; gap: 1rem;",
                             theme.ui_border, theme.ui_bg_primary, theme.ui_bg_secondary
@@ -181,6 +365,196 @@ pub fn SceneTokenEditor(
                                     />
                                 </label>
                             </div>
+
+                            <div style="display: grid; grid-template-columns: repeat(2, minmax(0, 1fr)); gap: 0.8rem;">
+                                <label style=format!("color: {}; display: flex; flex-direction: column; gap: 0.35rem;", theme.ui_text_secondary)>
+                                    <span>{move || t!(i18n, tokens.rotation_label)}</span>
+                                    <input
+                                        type="number"
+                                        min="0"
+                                        max="359"
+                                        step="1"
+                                        prop:value=move || draft.get().map(|draft| draft.rotation_deg).unwrap_or_default()
+                                        on:input=move |event| {
+                                            let value = event_target_value(&event);
+                                            draft.update(|draft| {
+                                                if let Some(draft) = draft.as_mut() {
+                                                    draft.rotation_deg = value.clone();
+                                                }
+                                            });
+                                        }
+                                        style=format!(
+                                            "padding: 0.75rem; background: {}; color: {}; border: 0.0625rem solid {}; border-radius: 0.6rem; font-size: 0.95rem;",
+                                            theme.ui_bg_secondary, theme.ui_text_primary, theme.ui_border
+                                        )
+                                    />
+                                </label>
+
+                                <label style=format!("color: {}; display: flex; flex-direction: column; gap: 0.35rem;", theme.ui_text_secondary)>
+                                    <span>{move || t!(i18n, tokens.elevation_label)}</span>
+                                    <input
+                                        type="number"
+                                        min=(-MAX_TOKEN_ELEVATION_FEET).to_string()
+                                        max=MAX_TOKEN_ELEVATION_FEET.to_string()
+                                        step="5"
+                                        prop:value=move || draft.get().map(|draft| draft.elevation_feet).unwrap_or_default()
+                                        on:input=move |event| {
+                                            let value = event_target_value(&event);
+                                            draft.update(|draft| {
+                                                if let Some(draft) = draft.as_mut() {
+                                                    draft.elevation_feet = value.clone();
+                                                }
+                                            });
+                                        }
+                                        style=format!(
+                                            "padding: 0.75rem; background: {}; color: {}; border: 0.0625rem solid {}; border-radius: 0.6rem; font-size: 0.95rem;",
+                                            theme.ui_bg_secondary, theme.ui_text_primary, theme.ui_border
+                                        )
+                                    />
+                                </label>
+                            </div>
+
+                            <label style=format!("color: {}; display: flex; flex-direction: column; gap: 0.35rem;", theme.ui_text_secondary)>
+                                <span>{move || t!(i18n, tokens.owner_label)}</span>
+                                <input
+                                    type="text"
+                                    placeholder=move || t_string!(i18n, tokens.owner_placeholder)
+                                    prop:disabled=move || !can_assign_owner.get()
+                                    prop:value=move || draft.get().map(|draft| draft.owner).unwrap_or_default()
+                                    on:input=move |event| {
+                                        let value = event_target_value(&event);
+                                        draft.update(|draft| {
+                                            if let Some(draft) = draft.as_mut() {
+                                                draft.owner = value.clone();
+                                            }
+                                        });
+                                    }
+                                    style=input_style.get_value()
+                                />
+                            </label>
+
+                            <div style="display: flex; flex-direction: column; gap: 0.5rem;">
+                                <div style="display: flex; justify-content: space-between; align-items: center; gap: 0.75rem;">
+                                    <span style=format!("color: {};", theme.ui_text_secondary)>
+                                        {move || t!(i18n, tokens.auras_label)}
+                                    </span>
+                                    <button
+                                        type="button"
+                                        prop:disabled=move || {
+                                            draft.with(|draft| {
+                                                draft.as_ref().is_none_or(|draft| draft.auras.len() >= MAX_TOKEN_AURAS)
+                                            })
+                                        }
+                                        on:click=move |_| {
+                                            draft.update(|draft| {
+                                                if let Some(draft) = draft.as_mut()
+                                                    && draft.auras.len() < MAX_TOKEN_AURAS
+                                                {
+                                                    draft.auras.push(SceneTokenAuraDraft::new_default());
+                                                }
+                                            });
+                                        }
+                                        style=format!(
+                                            "padding: 0.4rem 0.7rem; background: {}; color: {}; border: none; border-radius: 0.5rem; cursor: pointer; font-size: 0.82rem; font-weight: 700;",
+                                            theme.ui_button_primary, theme.ui_text_primary
+                                        )
+                                    >
+                                        {move || t!(i18n, tokens.aura_add_button)}
+                                    </button>
+                                </div>
+                                <For
+                                    each=aura_ids
+                                    key=|aura_id| aura_id.clone()
+                                    children=move |aura_id: String| {
+                                        let aura_id = StoredValue::new(aura_id);
+                                        view! {
+                                            <div style=aura_card_style.get_value()>
+                                                <input
+                                                    type="text"
+                                                    placeholder=move || t_string!(i18n, tokens.aura_label_placeholder)
+                                                    prop:value=move || aura_id.with_value(|id| aura_field(id, |aura| aura.label.clone()))
+                                                    on:input=move |event| {
+                                                        let value = event_target_value(&event);
+                                                        aura_id.with_value(|id| update_aura(id, &|aura| aura.label = value.clone()));
+                                                    }
+                                                    style=compact_input_style.get_value()
+                                                />
+                                                <input
+                                                    type="number"
+                                                    min="1"
+                                                    max=MAX_AURA_RADIUS_FEET.to_string()
+                                                    step="5"
+                                                    title=move || t_string!(i18n, tokens.aura_radius_label)
+                                                    prop:value=move || aura_id.with_value(|id| aura_field(id, |aura| aura.radius_feet.clone()))
+                                                    on:input=move |event| {
+                                                        let value = event_target_value(&event);
+                                                        aura_id.with_value(|id| update_aura(id, &|aura| aura.radius_feet = value.clone()));
+                                                    }
+                                                    style=compact_input_style.get_value()
+                                                />
+                                                <input
+                                                    type="color"
+                                                    title=move || t_string!(i18n, tokens.aura_color_label)
+                                                    prop:value=move || aura_id.with_value(|id| aura_field(id, |aura| aura.color.clone()))
+                                                    on:input=move |event| {
+                                                        let value = event_target_value(&event);
+                                                        aura_id.with_value(|id| update_aura(id, &|aura| aura.color = value.clone()));
+                                                    }
+                                                    style="width: 100%; height: 2.1rem; padding: 0; border: none; background: transparent; cursor: pointer;"
+                                                />
+                                                <input
+                                                    type="range"
+                                                    min="0"
+                                                    max="100"
+                                                    title=move || t_string!(i18n, tokens.aura_opacity_label)
+                                                    prop:value=move || aura_id.with_value(|id| aura_field(id, |aura| aura.opacity_percent.clone()))
+                                                    on:input=move |event| {
+                                                        let value = event_target_value(&event);
+                                                        aura_id.with_value(|id| update_aura(id, &|aura| aura.opacity_percent = value.clone()));
+                                                    }
+                                                    style="min-width: 0;"
+                                                />
+                                                <select
+                                                    title=move || t_string!(i18n, tokens.aura_visibility_label)
+                                                    prop:value=move || {
+                                                        aura_id.with_value(|id| {
+                                                            aura_field(id, |aura| aura_visibility_value(aura.visibility).to_string())
+                                                        })
+                                                    }
+                                                    on:change=move |event| {
+                                                        let visibility = parse_aura_visibility(&event_target_value(&event));
+                                                        aura_id.with_value(|id| update_aura(id, &|aura| aura.visibility = visibility));
+                                                    }
+                                                    style=compact_input_style.get_value()
+                                                >
+                                                    <option value="all">{move || t!(i18n, tokens.aura_visibility_all)}</option>
+                                                    <option value="owner">{move || t!(i18n, tokens.aura_visibility_owner)}</option>
+                                                    <option value="gm">{move || t!(i18n, tokens.aura_visibility_gm)}</option>
+                                                </select>
+                                                <button
+                                                    type="button"
+                                                    title=move || t_string!(i18n, tokens.aura_remove_button)
+                                                    on:click=move |_| {
+                                                        aura_id.with_value(|id| {
+                                                            draft.update(|draft| {
+                                                                if let Some(draft) = draft.as_mut() {
+                                                                    draft.auras.retain(|aura| aura.id != *id);
+                                                                }
+                                                            });
+                                                        });
+                                                    }
+                                                    style=format!(
+                                                        "background: {}; color: {}; border: none; border-radius: 0.5rem; cursor: pointer; font-weight: 700;",
+                                                        theme.ui_button_danger, theme.ui_text_primary
+                                                    )
+                                                >
+                                                    "x"
+                                                </button>
+                                            </div>
+                                        }
+                                    }
+                                />
+                            </div>
                         </div>
 
                         {move || {
@@ -221,8 +595,10 @@ pub fn SceneTokenEditor(
                                     };
                                     match validate_editor_draft(
                                         &current_draft,
-                                        &t_string!(i18n, tokens.error_name_required),
-                                        &t_string!(i18n, tokens.error_dimensions_invalid),
+                                        t_string!(i18n, tokens.error_name_required),
+                                        t_string!(i18n, tokens.error_dimensions_invalid),
+                                        t_string!(i18n, tokens.error_facing_invalid),
+                                        t_string!(i18n, tokens.error_aura_invalid),
                                     ) {
                                         Ok(value) => {
                                             error.set(None);
@@ -258,12 +634,19 @@ mod tests {
             name: "Ogre".to_string(),
             width_cells: "2".to_string(),
             height_cells: "3".to_string(),
+            rotation_deg: "-45".to_string(),
+            elevation_feet: "15".to_string(),
+            owner: "  alice ".to_string(),
+            auras: Vec::new(),
         };
 
-        let value = validate_editor_draft(&draft, "name", "dimensions").unwrap();
+        let value = validate_editor_draft(&draft, "name", "dimensions", "facing", "aura").unwrap();
         assert_eq!(value.name, "Ogre");
         assert_eq!(value.width_cells, 2);
         assert_eq!(value.height_cells, 3);
+        assert_eq!(value.rotation_deg, 315.0);
+        assert_eq!(value.elevation_feet, 15);
+        assert_eq!(value.owner.as_deref(), Some("alice"));
     }
 
     #[test]
@@ -274,11 +657,85 @@ mod tests {
             name: "Ogre".to_string(),
             width_cells: "0".to_string(),
             height_cells: "3".to_string(),
+            rotation_deg: "0".to_string(),
+            elevation_feet: "0".to_string(),
+            owner: String::new(),
+            auras: Vec::new(),
         };
 
         assert_eq!(
-            validate_editor_draft(&draft, "name", "dimensions").unwrap_err(),
+            validate_editor_draft(&draft, "name", "dimensions", "facing", "aura").unwrap_err(),
             "dimensions"
         );
     }
+
+    #[test]
+    fn validate_editor_draft_rejects_out_of_range_elevation() {
+        let draft = SceneTokenEditorDraft {
+            scene_id: "scene-1".to_string(),
+            token_id: "token-1".to_string(),
+            name: "Ogre".to_string(),
+            width_cells: "1".to_string(),
+            height_cells: "1".to_string(),
+            rotation_deg: "90".to_string(),
+            elevation_feet: "20000".to_string(),
+            owner: String::new(),
+            auras: Vec::new(),
+        };
+
+        assert_eq!(
+            validate_editor_draft(&draft, "name", "dimensions", "facing", "aura").unwrap_err(),
+            "facing"
+        );
+    }
+
+    #[test]
+    fn validate_editor_draft_converts_auras() {
+        let mut aura = SceneTokenAuraDraft::new_default();
+        aura.label = " Spirit Guardians ".to_string();
+        aura.radius_feet = "15".to_string();
+        aura.opacity_percent = "35".to_string();
+        aura.visibility = AuraVisibility::Owner;
+        let draft = SceneTokenEditorDraft {
+            scene_id: "scene-1".to_string(),
+            token_id: "token-1".to_string(),
+            name: "Cleric".to_string(),
+            width_cells: "1".to_string(),
+            height_cells: "1".to_string(),
+            rotation_deg: "0".to_string(),
+            elevation_feet: "0".to_string(),
+            owner: String::new(),
+            auras: vec![aura],
+        };
+
+        let value = validate_editor_draft(&draft, "name", "dimensions", "facing", "aura").unwrap();
+        assert_eq!(value.owner, None);
+        assert_eq!(value.auras.len(), 1);
+        assert_eq!(value.auras[0].label.as_deref(), Some("Spirit Guardians"));
+        assert_eq!(value.auras[0].radius_feet, 15);
+        assert!((value.auras[0].opacity - 0.35).abs() < 1e-6);
+        assert_eq!(value.auras[0].visibility, AuraVisibility::Owner);
+    }
+
+    #[test]
+    fn validate_editor_draft_rejects_invalid_aura() {
+        let mut aura = SceneTokenAuraDraft::new_default();
+        aura.color = "yellow".to_string();
+        let draft = SceneTokenEditorDraft {
+            scene_id: "scene-1".to_string(),
+            token_id: "token-1".to_string(),
+            name: "Paladin".to_string(),
+            width_cells: "1".to_string(),
+            height_cells: "1".to_string(),
+            rotation_deg: "0".to_string(),
+            elevation_feet: "0".to_string(),
+            owner: String::new(),
+            auras: vec![aura],
+        };
+
+        assert_eq!(
+            validate_editor_draft(&draft, "name", "dimensions", "facing", "aura").unwrap_err(),
+            "aura"
+        );
+    }
 }
@@ -1,4 +1,6 @@
-use super::model::token_rect;
+use super::model::{
+    TOKEN_ROTATION_HANDLE_RADIUS_PX, elevation_badge, rotation_handle_center, token_rect,
+};
 use crate::config::Theme;
 use leptos::prelude::*;
 use shared::events::Token;
@@ -9,6 +11,8 @@ pub fn SceneTokenLayer(
     tokens: Vec<Token>,
     cell_size: f64,
     dragging_token_id: Option<String>,
+    /// Token showing the rotation handle.
+    focused_token_id: Option<String>,
     file_urls: HashMap<String, String>,
     theme: Theme,
 ) -> impl IntoView {
@@ -26,7 +30,8 @@ pub fn SceneTokenLayer(
                 );
                 let image_url = file_urls.get(&token.image.hash).cloned();
                 let is_dragging = dragging_token_id.as_deref() == Some(token.id.as_str());
-                let border = if is_dragging { theme.ui_button_primary } else { theme.ui_border };
+                let is_focused = focused_token_id.as_deref() == Some(token.id.as_str());
+                let border = if is_dragging || is_focused { theme.ui_button_primary } else { theme.ui_border };
                 let shadow = if is_dragging {
                     "0 18px 36px rgba(0,0,0,0.32), 0 0 0 2px rgba(255,255,255,0.08)"
                 } else {
@@ -38,8 +43,25 @@ pub fn SceneTokenLayer(
                     "left 180ms ease, top 180ms ease, box-shadow 180ms ease, border-color 180ms ease"
                 };
                 let label_font = (cell_size * 0.18).clamp(11.0, 15.0);
+                let badge = elevation_badge(token.elevation_feet);
+                let handle = is_focused.then(|| {
+                    let (handle_x, handle_y) =
+                        rotation_handle_center(left, top, width, height, token.rotation_deg);
+                    view! {
+                        <div style=format!(
+                            "position: absolute; left: {:.2}px; top: {:.2}px; width: {:.2}px; height: {:.2}px; \
+                             transform: translate(-50%, -50%); border-radius: 50%; background: {}; \
+                             border: 2px solid rgba(255,255,255,0.85); z-index: 5; pointer-events: none; \
+                             box-shadow: 0 4px 10px rgba(0,0,0,0.35);",
+                            handle_x, handle_y,
+                            TOKEN_ROTATION_HANDLE_RADIUS_PX * 2.0, TOKEN_ROTATION_HANDLE_RADIUS_PX * 2.0,
+                            theme.ui_button_primary
+                        ) />
+                    }
+                });
 
                 view! {
+                    {handle}
                     <div style=format!(
                         "position: absolute; left: {:.2}px; top: {:.2}px; width: {:.2}px; height: {:.2}px; \
                          border: 2px solid {}; border-radius: {:.2}px; overflow: hidden; box-shadow: {}; \
@@ -47,22 +69,45 @@ pub fn SceneTokenLayer(
                         left, top, width, height, border, (cell_size * 0.18).clamp(8.0, 16.0), shadow,
                         if is_dragging { 4 } else { 3 }, transition
                     )>
-                        {match image_url {
-                            Some(url) => view! {
-                                <img
-                                    src=url
-                                    alt=token.name.clone()
-                                    style="width: 100%; height: 100%; object-fit: cover; display: block;"
-                                />
-                            }.into_any(),
-                            None => view! {
-                                <div style="width: 100%; height: 100%; display: flex; align-items: center; justify-content: center; background: linear-gradient(135deg, rgba(255,255,255,0.08), rgba(255,255,255,0.02));">
-                                    <span style=format!("font-size: {:.2}px; color: {}; font-weight: 700;", label_font, theme.ui_text_secondary)>
-                                        {token.name.chars().next().unwrap_or('?').to_string()}
-                                    </span>
-                                </div>
-                            }.into_any(),
-                        }}
+                        // Image and facing notch rotate together; labels stay upright.
+                        <div style=format!(
+                            "position: absolute; inset: 0; transform: rotate({:.2}deg); transition: {};",
+                            token.rotation_deg,
+                            if is_dragging { "none" } else { "transform 180ms ease" }
+                        )>
+                            {match image_url {
+                                Some(url) => view! {
+                                    <img
+                                        src=url
+                                        alt=token.name.clone()
+                                        style="width: 100%; height: 100%; object-fit: cover; display: block;"
+                                    />
+                                }.into_any(),
+                                None => view! {
+                                    <div style="width: 100%; height: 100%; display: flex; align-items: center; justify-content: center; background: linear-gradient(135deg, rgba(255,255,255,0.08), rgba(255,255,255,0.02));">
+                                        <span style=format!("font-size: {:.2}px; color: {}; font-weight: 700;", label_font, theme.ui_text_secondary)>
+                                            {token.name.chars().next().unwrap_or('?').to_string()}
+                                        </span>
+                                    </div>
+                                }.into_any(),
+                            }}
+                            <div style=format!(
+                                "position: absolute; left: 50%; top: 0; transform: translateX(-50%); width: 0; height: 0; \
+                                 border-left: {0:.2}px solid transparent; border-right: {0:.2}px solid transparent; \
+                                 border-top: {0:.2}px solid {1};",
+                                (cell_size * 0.12).clamp(4.0, 9.0), border
+                            ) />
+                        </div>
+                        {badge.map(|text| view! {
+                            <div style=format!(
+                                "position: absolute; top: 0.25rem; right: 0.25rem; padding: 0.08rem 0.3rem; \
+                                 background: rgba(0,0,0,0.62); border-radius: 0.35rem; font-size: {:.2}px; \
+                                 font-weight: 700; color: {}; white-space: nowrap;",
+                                label_font * 0.85, theme.ui_text_primary
+                            )>
+                                {text}
+                            </div>
+                        })}
                         <div style="position: absolute; left: 0.35rem; right: 0.35rem; bottom: 0.35rem; padding: 0.22rem 0.35rem; background: rgba(0,0,0,0.48); border-radius: 0.45rem; backdrop-filter: blur(6px);">
                             <div style=format!("font-size: {:.2}px; font-weight: 700; color: {}; overflow: hidden; text-overflow: ellipsis; white-space: nowrap;", label_font, theme.ui_text_primary)>
                                 {token.name.clone()}
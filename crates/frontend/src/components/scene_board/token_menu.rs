@@ -10,6 +10,7 @@ pub fn SceneTokenMenu(
     screen_x: f64,
     screen_y: f64,
     on_edit: Callback<()>,
+    on_open_character_sheet: Callback<()>,
     on_save_to_library: Callback<()>,
     on_delete: Callback<()>,
     on_close: Callback<()>,
@@ -56,6 +57,21 @@ pub fn SceneTokenMenu(
                 >
                     {t!(i18n, tokens.edit_button)}
                 </button>
+                <button
+                    on:mousedown=move |event: MouseEvent| {
+                        event.prevent_default();
+                        event.stop_propagation();
+                        on_open_character_sheet.run(());
+                    }
+                    style=format!(
+                        "width: 100%; margin-bottom: 0.45rem; padding: 0.6rem 0.75rem; border: 1px solid {}; \
+                         border-radius: 0.7rem; background: rgba(255,255,255,0.04); color: {}; cursor: pointer; \
+                         text-align: left; font-weight: 700;",
+                        theme.ui_border, theme.ui_text_primary
+                    )
+                >
+                    {t!(i18n, tokens.character_sheet_button)}
+                </button>
                 <button
                     on:mousedown=move |event: MouseEvent| {
                         event.prevent_default();
@@ -0,0 +1,366 @@
+// Pure touch gestures on the board: which finger drives the pointer, pinch
+// zoom combined with two-finger pan, and when a still press is a long press.
+// No signals, no Leptos, no web_sys.
+
+use super::model::{clamp_zoom, screen_to_world};
+
+// --- Constants ---
+
+/// How long a finger rests on a token before its menu opens.
+pub const LONG_PRESS_MS: u64 = 500;
+/// Finger travel, in screen pixels, that turns a press into a drag.
+pub const LONG_PRESS_SLOP_PX: f64 = 10.0;
+/// Extra reach around tokens for fingers, in screen pixels.
+pub const TOUCH_TOKEN_REACH_PX: f64 = 18.0;
+
+// --- Types ---
+
+/// Camera and viewport size when a gesture starts.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TouchCamera {
+    pub camera_x: f64,
+    pub camera_y: f64,
+    pub zoom: f64,
+    pub viewport_width: f64,
+    pub viewport_height: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct TouchPoint {
+    pointer_id: i32,
+    x: f64,
+    y: f64,
+}
+
+/// Two fingers on the board: the world point under their midpoint stays
+/// under it while the fingers spread, pinch and move together.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct PinchGesture {
+    start_distance: f64,
+    start_zoom: f64,
+    anchor_x: f64,
+    anchor_y: f64,
+    viewport_width: f64,
+    viewport_height: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct LongPress {
+    pointer_id: i32,
+    origin_x: f64,
+    origin_y: f64,
+}
+
+/// What the board does with a touch event.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TouchAction {
+    /// The finger drives the board like a mouse pointer.
+    Pointer,
+    /// A second finger landed: end whatever the first one started.
+    StartPinch,
+    /// Camera for the current pinch.
+    Pinch {
+        camera_x: f64,
+        camera_y: f64,
+        zoom: f64,
+    },
+    Ignore,
+}
+
+/// Fingers that went down on the board, in viewport-local coordinates.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TouchTracker {
+    points: Vec<TouchPoint>,
+    pinch: Option<PinchGesture>,
+    /// A finger left over from a pinch; it only moves the board again after
+    /// every finger has lifted.
+    settling: bool,
+    long_press: Option<LongPress>,
+}
+
+// --- Pure functions ---
+
+impl TouchTracker {
+    pub fn press(&mut self, pointer_id: i32, x: f64, y: f64, camera: TouchCamera) -> TouchAction {
+        if self
+            .points
+            .iter()
+            .any(|point| point.pointer_id == pointer_id)
+        {
+            return TouchAction::Ignore;
+        }
+        let point = TouchPoint { pointer_id, x, y };
+        match self.points.as_slice() {
+            [] => {
+                self.points.push(point);
+                self.settling = false;
+                self.long_press = Some(LongPress {
+                    pointer_id,
+                    origin_x: x,
+                    origin_y: y,
+                });
+                TouchAction::Pointer
+            }
+            [first] => {
+                self.pinch = Some(pinch_gesture(*first, point, camera));
+                self.points.push(point);
+                self.long_press = None;
+                TouchAction::StartPinch
+            }
+            _ => TouchAction::Ignore,
+        }
+    }
+
+    pub fn move_to(&mut self, pointer_id: i32, x: f64, y: f64) -> TouchAction {
+        let Some(point) = self
+            .points
+            .iter_mut()
+            .find(|point| point.pointer_id == pointer_id)
+        else {
+            return self.untracked_action();
+        };
+        point.x = x;
+        point.y = y;
+        if let (Some(pinch), [first, second]) = (self.pinch, self.points.as_slice()) {
+            let (camera_x, camera_y, zoom) = pinch_camera(&pinch, *first, *second);
+            return TouchAction::Pinch {
+                camera_x,
+                camera_y,
+                zoom,
+            };
+        }
+        if self.settling {
+            return TouchAction::Ignore;
+        }
+        if self.long_press.is_some_and(|press| {
+            press.pointer_id == pointer_id
+                && (x - press.origin_x).hypot(y - press.origin_y) > LONG_PRESS_SLOP_PX
+        }) {
+            self.long_press = None;
+        }
+        TouchAction::Pointer
+    }
+
+    /// A finger lifted or was cancelled by the browser.
+    pub fn release(&mut self, pointer_id: i32) -> TouchAction {
+        let count = self.points.len();
+        self.points.retain(|point| point.pointer_id != pointer_id);
+        if self.points.len() == count {
+            return self.untracked_action();
+        }
+        if self
+            .long_press
+            .is_some_and(|press| press.pointer_id == pointer_id)
+        {
+            self.long_press = None;
+        }
+        if self.pinch.take().is_some() || self.settling {
+            self.settling = !self.points.is_empty();
+            return TouchAction::Ignore;
+        }
+        TouchAction::Pointer
+    }
+
+    /// Where the finger went down, while it still rests there.
+    pub fn long_press_point(&self, pointer_id: i32) -> Option<(f64, f64)> {
+        self.long_press
+            .filter(|press| press.pointer_id == pointer_id)
+            .map(|press| (press.origin_x, press.origin_y))
+    }
+
+    pub fn cancel_long_press(&mut self) {
+        self.long_press = None;
+    }
+
+    /// Fingers that went down on something above the board, e.g. a note
+    /// header, move and release like a mouse unless a pinch owns the board.
+    fn untracked_action(&self) -> TouchAction {
+        if self.pinch.is_some() || self.settling {
+            TouchAction::Ignore
+        } else {
+            TouchAction::Pointer
+        }
+    }
+}
+
+fn pinch_gesture(first: TouchPoint, second: TouchPoint, camera: TouchCamera) -> PinchGesture {
+    let (mid_x, mid_y) = midpoint(first, second);
+    let (anchor_x, anchor_y) = screen_to_world(
+        mid_x,
+        mid_y,
+        camera.viewport_width,
+        camera.viewport_height,
+        camera.camera_x,
+        camera.camera_y,
+        camera.zoom,
+    );
+    PinchGesture {
+        start_distance: distance(first, second).max(1.0),
+        start_zoom: camera.zoom,
+        anchor_x,
+        anchor_y,
+        viewport_width: camera.viewport_width,
+        viewport_height: camera.viewport_height,
+    }
+}
+
+/// Camera offset and zoom that keep the pinch anchor under the midpoint.
+fn pinch_camera(pinch: &PinchGesture, first: TouchPoint, second: TouchPoint) -> (f64, f64, f64) {
+    let zoom = clamp_zoom(pinch.start_zoom * distance(first, second) / pinch.start_distance);
+    let (mid_x, mid_y) = midpoint(first, second);
+    (
+        mid_x - pinch.viewport_width / 2.0 - pinch.anchor_x * zoom,
+        mid_y - pinch.viewport_height / 2.0 - pinch.anchor_y * zoom,
+        zoom,
+    )
+}
+
+fn midpoint(first: TouchPoint, second: TouchPoint) -> (f64, f64) {
+    ((first.x + second.x) / 2.0, (first.y + second.y) / 2.0)
+}
+
+fn distance(first: TouchPoint, second: TouchPoint) -> f64 {
+    (second.x - first.x).hypot(second.y - first.y)
+}
+
+/// Distance from a point to a rectangle; zero inside it.
+pub fn distance_to_rect(x: f64, y: f64, left: f64, top: f64, width: f64, height: f64) -> f64 {
+    let dx = (left - x).max(x - (left + width)).max(0.0);
+    let dy = (top - y).max(y - (top + height)).max(0.0);
+    dx.hypot(dy)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::scene_board::model::{MAX_ZOOM, world_to_screen};
+
+    const CAMERA: TouchCamera = TouchCamera {
+        camera_x: 40.0,
+        camera_y: -20.0,
+        zoom: 1.0,
+        viewport_width: 800.0,
+        viewport_height: 600.0,
+    };
+
+    fn pinch_view(action: TouchAction) -> (f64, f64, f64) {
+        match action {
+            TouchAction::Pinch {
+                camera_x,
+                camera_y,
+                zoom,
+            } => (camera_x, camera_y, zoom),
+            other => panic!("expected a pinch, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn first_finger_drives_the_pointer_and_second_starts_a_pinch() {
+        let mut tracker = TouchTracker::default();
+        assert_eq!(tracker.press(1, 100.0, 100.0, CAMERA), TouchAction::Pointer);
+        assert_eq!(tracker.move_to(1, 104.0, 100.0), TouchAction::Pointer);
+        assert_eq!(
+            tracker.press(2, 300.0, 100.0, CAMERA),
+            TouchAction::StartPinch
+        );
+        assert_eq!(tracker.press(3, 50.0, 50.0, CAMERA), TouchAction::Ignore);
+        assert_eq!(tracker.move_to(3, 60.0, 50.0), TouchAction::Ignore);
+    }
+
+    #[test]
+    fn fingers_pressed_elsewhere_act_as_a_pointer_outside_gestures() {
+        let mut tracker = TouchTracker::default();
+        assert_eq!(tracker.move_to(7, 10.0, 10.0), TouchAction::Pointer);
+        assert_eq!(tracker.release(7), TouchAction::Pointer);
+
+        tracker.press(1, 300.0, 300.0, CAMERA);
+        tracker.press(2, 500.0, 300.0, CAMERA);
+        assert_eq!(tracker.move_to(7, 10.0, 10.0), TouchAction::Ignore);
+        assert_eq!(tracker.release(7), TouchAction::Ignore);
+    }
+
+    #[test]
+    fn spreading_fingers_zooms_around_their_midpoint() {
+        let mut tracker = TouchTracker::default();
+        tracker.press(1, 300.0, 300.0, CAMERA);
+        tracker.press(2, 500.0, 300.0, CAMERA);
+        let anchor = screen_to_world(400.0, 300.0, 800.0, 600.0, 40.0, -20.0, 1.0);
+
+        let (camera_x, camera_y, zoom) = pinch_view(tracker.move_to(2, 700.0, 300.0));
+        assert!((zoom - 2.0).abs() < 1e-9);
+        let (screen_x, screen_y) =
+            world_to_screen(anchor.0, anchor.1, 800.0, 600.0, camera_x, camera_y, zoom);
+        assert!((screen_x - 500.0).abs() < 1e-9);
+        assert!((screen_y - 300.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn moving_both_fingers_pans_without_zooming() {
+        let mut tracker = TouchTracker::default();
+        tracker.press(1, 300.0, 300.0, CAMERA);
+        tracker.press(2, 500.0, 300.0, CAMERA);
+        tracker.move_to(1, 350.0, 260.0);
+        let (camera_x, camera_y, zoom) = pinch_view(tracker.move_to(2, 550.0, 260.0));
+        assert!((zoom - 1.0).abs() < 1e-9);
+        assert!((camera_x - 90.0).abs() < 1e-9);
+        assert!((camera_y + 60.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn pinch_zoom_is_clamped() {
+        let mut tracker = TouchTracker::default();
+        tracker.press(1, 390.0, 300.0, CAMERA);
+        tracker.press(2, 410.0, 300.0, CAMERA);
+        let (_, _, zoom) = pinch_view(tracker.move_to(2, 790.0, 300.0));
+        assert_eq!(zoom, MAX_ZOOM);
+    }
+
+    #[test]
+    fn finger_left_after_a_pinch_is_ignored_until_all_lift() {
+        let mut tracker = TouchTracker::default();
+        tracker.press(1, 300.0, 300.0, CAMERA);
+        tracker.press(2, 500.0, 300.0, CAMERA);
+        assert_eq!(tracker.release(2), TouchAction::Ignore);
+        assert_eq!(tracker.move_to(1, 320.0, 300.0), TouchAction::Ignore);
+        assert_eq!(
+            tracker.press(3, 500.0, 300.0, CAMERA),
+            TouchAction::StartPinch
+        );
+        assert_eq!(tracker.release(3), TouchAction::Ignore);
+        assert_eq!(tracker.release(1), TouchAction::Ignore);
+        assert_eq!(tracker.press(4, 100.0, 100.0, CAMERA), TouchAction::Pointer);
+        assert_eq!(tracker.release(4), TouchAction::Pointer);
+    }
+
+    #[test]
+    fn long_press_survives_jitter_but_not_a_drag() {
+        let mut tracker = TouchTracker::default();
+        tracker.press(1, 100.0, 100.0, CAMERA);
+        tracker.move_to(1, 104.0, 103.0);
+        assert_eq!(tracker.long_press_point(1), Some((100.0, 100.0)));
+        assert_eq!(tracker.long_press_point(2), None);
+        tracker.move_to(1, 120.0, 100.0);
+        assert_eq!(tracker.long_press_point(1), None);
+        tracker.move_to(1, 100.0, 100.0);
+        assert_eq!(tracker.long_press_point(1), None);
+    }
+
+    #[test]
+    fn long_press_ends_with_the_press() {
+        let mut tracker = TouchTracker::default();
+        tracker.press(1, 100.0, 100.0, CAMERA);
+        tracker.release(1);
+        assert_eq!(tracker.long_press_point(1), None);
+
+        tracker.press(2, 100.0, 100.0, CAMERA);
+        tracker.press(3, 200.0, 100.0, CAMERA);
+        assert_eq!(tracker.long_press_point(2), None);
+    }
+
+    #[test]
+    fn distance_to_rect_is_zero_inside_and_euclidean_outside() {
+        assert_eq!(distance_to_rect(15.0, 15.0, 10.0, 10.0, 20.0, 20.0), 0.0);
+        assert_eq!(distance_to_rect(5.0, 20.0, 10.0, 10.0, 20.0, 20.0), 5.0);
+        assert_eq!(distance_to_rect(33.0, 34.0, 10.0, 10.0, 20.0, 20.0), 5.0);
+    }
+}
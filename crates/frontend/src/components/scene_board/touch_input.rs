@@ -0,0 +1,85 @@
+// Touch glue for the scene board: feeds the pointer events of fingers to the
+// pure `TouchTracker` and schedules long presses.
+
+use super::touch::{LONG_PRESS_MS, TouchAction, TouchCamera, TouchTracker};
+use super::view_model::SceneBoardViewModel;
+use leptos::prelude::*;
+use std::time::Duration;
+use web_sys::PointerEvent;
+
+pub fn is_touch(event: &PointerEvent) -> bool {
+    event.pointer_type() == "touch"
+}
+
+/// Fingers on the board, owned by the board so gestures survive its re-renders.
+#[derive(Clone, Copy)]
+pub struct BoardTouch {
+    tracker: StoredValue<TouchTracker>,
+}
+
+impl Default for BoardTouch {
+    fn default() -> Self {
+        Self {
+            tracker: StoredValue::new(TouchTracker::default()),
+        }
+    }
+}
+
+impl BoardTouch {
+    /// A finger went down at a viewport-local point.
+    pub fn press(
+        self,
+        event: &PointerEvent,
+        local_x: f64,
+        local_y: f64,
+        vm: SceneBoardViewModel,
+    ) -> TouchAction {
+        let camera = TouchCamera {
+            camera_x: vm.camera_x.get_untracked(),
+            camera_y: vm.camera_y.get_untracked(),
+            zoom: vm.zoom.get_untracked(),
+            viewport_width: vm.viewport_width.get_untracked(),
+            viewport_height: vm.viewport_height.get_untracked(),
+        };
+        self.tracker
+            .try_update_value(|tracker| tracker.press(event.pointer_id(), local_x, local_y, camera))
+            .unwrap_or(TouchAction::Ignore)
+    }
+
+    pub fn move_to(self, event: &PointerEvent, local_x: f64, local_y: f64) -> TouchAction {
+        self.tracker
+            .try_update_value(|tracker| tracker.move_to(event.pointer_id(), local_x, local_y))
+            .unwrap_or(TouchAction::Ignore)
+    }
+
+    pub fn release(self, event: &PointerEvent) -> TouchAction {
+        self.tracker
+            .try_update_value(|tracker| tracker.release(event.pointer_id()))
+            .unwrap_or(TouchAction::Ignore)
+    }
+
+    /// Runs `on_long_press` with the press point once the finger has rested
+    /// there for `LONG_PRESS_MS`; returning `true` consumes the long press.
+    pub fn watch_long_press(
+        self,
+        event: &PointerEvent,
+        on_long_press: impl FnOnce(f64, f64) -> bool + 'static,
+    ) {
+        let pointer_id = event.pointer_id();
+        set_timeout(
+            move || {
+                let Some((local_x, local_y)) = self
+                    .tracker
+                    .try_with_value(|tracker| tracker.long_press_point(pointer_id))
+                    .flatten()
+                else {
+                    return;
+                };
+                if on_long_press(local_x, local_y) {
+                    self.tracker.update_value(TouchTracker::cancel_long_press);
+                }
+            },
+            Duration::from_millis(LONG_PRESS_MS),
+        );
+    }
+}
@@ -1,12 +1,22 @@
 use super::board_note_helpers::{
     apply_local_note_delete, apply_local_note_upsert, board_note_body_height, board_note_meta,
-    board_note_title_font_size_pt, clear_board_note_editor_state, collect_board_notes,
-    commit_board_note_draft, current_time_ms, find_matching_note, find_note_by_ref,
-    note_matches, persist_note_delete, persist_note_upsert,
+    board_note_title_font_size_pt, clear_board_note_editor_state, commit_board_note_draft,
+    current_time_ms, find_matching_note, find_note_by_ref, note_matches, persist_note_delete,
+    persist_note_upsert, visible_board_notes,
 };
 use super::board_toolbar::{
-    AttentionPingAnimation, BoardToolbar, PointerTrailOverlay, RulerOverlay,
+    AttentionPingAnimation, BoardToolbar, CameraControls, PointerTrailOverlay, RenderStatsPanel,
+    RulerOverlay, TerrainPalette,
 };
+use super::camera::{
+    CAMERA_FOLLOW_DURATION_MS, CAMERA_PULL_DURATION_MS, CAMERA_SHARE_INTERVAL_MS, CameraGlide,
+    camera_input_locked, camera_lock_after, camera_moves_board, camera_payload, camera_share_due,
+    camera_transform, camera_view, glide_view, payload_view,
+};
+use super::canvas::BoardRenderer;
+use super::canvas_layer::BoardCanvasState;
+use super::export_panel::{SceneExportPanel, SceneExportState};
+use super::minimap_panel::{BoardMinimap, BoardMinimapState};
 use super::interaction_state::{
     BOARD_NOTE_COLORS, BOARD_NOTE_DOUBLE_CLICK_MS, BOARD_NOTE_FONT_SIZE_STEP_PT,
     BOARD_NOTE_MAX_FONT_SIZE_PT, BOARD_NOTE_MAX_HEIGHT_PX, BOARD_NOTE_MAX_WIDTH_PX,
@@ -15,29 +25,41 @@ use super::interaction_state::{
     BoardNoteResizeState, BoardNoteSelection, TOKEN_DRAG_EPSILON_CELLS, TokenMenuState,
 };
 use super::model::{
-    BOARD_HANDLE_HEIGHT_PX, BoardTool, DRAG_EPSILON_PX, WORKSPACE_GRID_STEP_PX, ZOOM_STEP,
-    board_background, centered_token_offset, clamp_zoom, grid_line_width_screen, ruler_distance,
-    scene_allows_token_interaction, scene_shows_contents, selection_box, should_broadcast_cursor,
-    snap_token_position_to_grid, token_position_from_world, token_rect, world_to_scene_cells,
-    world_to_screen,
+    BOARD_HANDLE_HEIGHT_PX, BOARD_NOTE_PIN_ICONS, BOARD_NOTE_PIN_SIZE_PX, BoardFocus, BoardTool,
+    DRAG_EPSILON_PX, TOKEN_ROTATION_FINE_STEP_DEG, TOKEN_ROTATION_SNAP_DEG, WORKSPACE_GRID_STEP_PX,
+    ZOOM_STEP, anchor_board_note, board_background, board_focus_point, camera_centered_on,
+    centered_token_offset, clamp_zoom, elevated_distance_feet, grid_line_width_screen,
+    move_board_note, rotation_towards, scene_allows_token_interaction, scene_for_note_anchor,
+    scene_shows_contents, selection_box, should_broadcast_cursor, snap_rotation_deg,
+    snap_token_position_to_grid, sync_anchored_notes, terrain_ruler_distance,
+    token_position_from_world, token_rect, world_to_scene_cells, world_to_screen,
 };
 use super::scene_geometry::{
-    board_note_hit, build_scene_layouts, clamp_to_layout, place_library_token,
-    point_inside_board, point_inside_board_note_content, point_inside_handle,
-    remove_token_from_scene, send_event, snap_scene_position, sort_token_library_items, token_hit,
-    update_scene_position, update_token_details, update_token_position, viewport_local_point,
+    board_note_hit, build_scene_layouts, clamp_to_layout, place_library_token, point_inside_board,
+    point_inside_board_note_content, point_inside_handle, remove_token_from_scene, rotate_token_by,
+    rotation_handle_hit, send_event, snap_scene_position, sort_token_library_items,
+    stop_pointer_propagation, token_hit, token_touch_hit, update_scene_position,
+    update_token_details, update_token_position, update_token_rotation, viewport_local_point,
     viewport_size,
 };
+use super::level_view::{BoardFloorSwitchers, BoardLevels, SceneStairsLayer};
 use super::storage::{StoredCameraPosition, load_camera_position, save_camera_position};
+use super::aura_layer::SceneAuraLayer;
+use super::terrain_input::BoardTerrain;
+use super::terrain_layer::SceneTerrainLayer;
 use super::token_editor::{SceneTokenEditor, SceneTokenEditorDraft, SceneTokenEditorValue};
 use super::token_layer::SceneTokenLayer;
 use super::token_menu::SceneTokenMenu;
+use super::touch::{TOUCH_TOKEN_REACH_PX, TouchAction};
+use super::touch_input::{BoardTouch, is_touch};
 use super::view_model::SceneBoardViewModel;
 use super::workspace_hint::WorkspaceHintCard;
 use crate::components::app::mouse_handler::{
     send_mouse_event_throttled, update_local_cursor_world,
 };
+use crate::components::compendium::model::{CompendiumMonster, MonsterPlacement};
 use crate::components::cursor::Cursor;
+use crate::components::notes::embeds::resolve_file_embeds;
 use crate::components::notes::model::{
     BOARD_NOTE_DRAG_MIME, can_delete_note, can_edit_note, note_heading_and_body,
     render_note_html,
@@ -53,13 +75,16 @@ use leptos::ev;
 use leptos::html;
 use leptos::prelude::*;
 use leptos::task::spawn_local;
+use leptos::wasm_bindgen::JsCast;
 use shared::events::{
-    AttentionPingPayload, ClientEvent, DirectMessagePayload, NoteBoardPosition, NotePayload,
-    NoteVisibility, Scene, SceneUpdatePayload, TokenMovePayload,
+    AttentionPingPayload, BoardCameraMode, BoardCameraPayload, BoardFollowPayload,
+    BoardViewportPayload, ClientEvent, DirectMessagePayload, NoteBoardPosition, NotePayload,
+    NoteVisibility, Scene, SceneUpdatePayload, TokenMovePayload, WorldPoint, acts_as_game_master,
+    assigned_scene_id,
 };
 #[cfg(test)]
 use shared::events::NoteBoardStyle;
-use web_sys::{DragEvent, MouseEvent, WheelEvent};
+use web_sys::{DragEvent, KeyboardEvent, MouseEvent, PointerEvent, WheelEvent};
 
 
 // ---------------------------------------------------------------------------
@@ -71,28 +96,66 @@ pub fn SceneBoard(
     room_id: ReadSignal<String>,
     #[prop(into)] scenes: RwSignal<Vec<Scene>>,
     #[prop(into)] active_scene_id: RwSignal<Option<String>>,
+    /// Split-party scene assignments (username -> scene id).
+    #[prop(into)] scene_assignments: RwSignal<std::collections::BTreeMap<String, String>>,
     #[prop(into)] public_notes: RwSignal<Vec<NotePayload>>,
     #[prop(into)] private_notes: RwSignal<Vec<NotePayload>>,
     #[prop(into)] direct_notes: RwSignal<Vec<NotePayload>>,
     #[prop(into)] show_workspace_hint: RwSignal<bool>,
     #[prop(into)] show_inactive_scene_contents: RwSignal<bool>,
+    /// How the board draws backgrounds, grids and tokens.
+    #[prop(into)] board_renderer: RwSignal<BoardRenderer>,
+    /// Shows frame times and the renderer benchmark on the board.
+    #[prop(into)] show_render_stats: RwSignal<bool>,
     #[prop(into)] token_library_items: RwSignal<Vec<StoredTokenLibraryItem>>,
     #[prop(into)] dragging_library_token_id: RwSignal<Option<String>>,
+    /// Compendium monster currently dragged from the compendium window.
+    #[prop(into)] dragging_compendium_monster: RwSignal<Option<CompendiumMonster>>,
     cursors: ReadSignal<std::collections::HashMap<String, CursorSignals>>,
     set_cursors: WriteSignal<std::collections::HashMap<String, CursorSignals>>,
     file_transfer: FileTransferState,
     ws_sender: ReadSignal<Option<WsSender>>,
     username: ReadSignal<String>,
+    /// Room game master; `None` while nobody holds the role.
+    #[prop(into)] game_master: RwSignal<Option<String>>,
+    /// Opens the character sheet linked to the given token id.
+    #[prop(optional)]
+    on_open_character_sheet: Option<Callback<String>>,
+    /// Creates a token for a compendium monster dropped onto a scene.
+    #[prop(optional)]
+    on_compendium_monster_drop: Option<Callback<MonsterPlacement>>,
     config: config::Config,
     theme: Theme,
     /// Usernames of remote users who have activated the pointer tool.
     #[prop(into)] board_pointers: RwSignal<std::collections::HashSet<String>>,
     #[prop(into)] attention_pings: RwSignal<Vec<AttentionPingPayload>>,
+    /// Latest camera shared by another user (pull, lock or live follow).
+    #[prop(into)] board_camera: RwSignal<Option<BoardCameraPayload>>,
+    /// Users following the local user's camera.
+    #[prop(into)] camera_followers: RwSignal<std::collections::HashSet<String>>,
+    /// Viewports other users share for the minimap, by username.
+    #[prop(into)] board_viewports: RwSignal<std::collections::HashMap<String, BoardViewportPayload>>,
+    /// Scene or token to bring into view, e.g. from a note reference; cleared once handled.
+    #[prop(into)] board_focus: RwSignal<Option<BoardFocus>>,
     /// Received direct messages; available for a future DM panel component.
     #[allow(unused_variables)]
     #[prop(into)] direct_messages: RwSignal<Vec<DirectMessagePayload>>,
 ) -> impl IntoView {
     let i18n = use_i18n();
+    // Scene the local user plays on: their own split-party assignment, or the
+    // room's active scene. Content visibility and token rules follow it.
+    let room_active_scene_id = active_scene_id;
+    let active_scene_id = Memo::new(move |_| {
+        let current_user = username.get();
+        scene_assignments.with(|assignments| {
+            assigned_scene_id(
+                room_active_scene_id.get().as_deref(),
+                assignments,
+                &current_user,
+            )
+            .map(str::to_string)
+        })
+    });
     let (initial_vw, initial_vh) = viewport_size();
     let initial_room_id = room_id.get_untracked();
     let initial_camera = load_camera_position(&initial_room_id).unwrap_or(StoredCameraPosition {
@@ -124,6 +187,72 @@ pub fn SceneBoard(
     let board_note_resize_did_move = RwSignal::new(false);
     let board_note_last_click = RwSignal::new(None::<BoardNoteClickState>);
     let board_note_focus_request = RwSignal::new(None::<BoardNoteSelection>);
+    // Shared camera: whose camera the local board follows, the game master's
+    // lock as received, the local game master's own lock toggle and the glide
+    // currently easing the board towards a received view.
+    let followed_user = RwSignal::new(None::<String>);
+    let camera_lock = RwSignal::new(None::<String>);
+    let camera_locking = RwSignal::new(false);
+    let camera_glide = RwSignal::new(None::<CameraGlide>);
+    let camera_glide_running = StoredValue::new(false);
+    let last_camera_share_ms = StoredValue::new(None::<f64>);
+    let camera_share_pending = StoredValue::new(false);
+    let export_state = SceneExportState::default();
+    let board_canvas = BoardCanvasState::new(board_renderer, show_render_stats);
+    let board_touch = BoardTouch::default();
+    let viewer_is_game_master =
+        Signal::derive(move || acts_as_game_master(game_master.get().as_deref(), &username.get()));
+    // Pulls and locks are only honoured from an appointed game master.
+    let viewer_steers_cameras = Signal::derive(move || {
+        game_master
+            .get()
+            .is_some_and(|game_master| game_master == username.get())
+    });
+    let camera_locked = Memo::new(move |_| {
+        let game_master = game_master.get();
+        let lock_holder = camera_lock
+            .get()
+            .filter(|holder| game_master.as_deref() == Some(holder.as_str()));
+        camera_input_locked(
+            lock_holder.as_deref(),
+            game_master.as_deref(),
+            &username.get(),
+        )
+    });
+    // Users whose camera can be followed: everyone with a cursor on the board.
+    let camera_users = Signal::derive(move || {
+        let current_user = username.get();
+        let mut users = cursors.with(|cursors| {
+            cursors
+                .keys()
+                .filter(|user| **user != current_user)
+                .cloned()
+                .collect::<Vec<_>>()
+        });
+        users.sort();
+        users
+    });
+    let minimap = BoardMinimapState::new(vm, camera_users, board_viewports);
+    let levels = BoardLevels::new(vm, scenes, username, game_master);
+    let board_scenes = levels.board_scenes;
+    let terrain = BoardTerrain::new(
+        vm,
+        scenes,
+        levels,
+        active_scene_id,
+        show_inactive_scene_contents,
+    );
+    // Board notes the local user sees, as exported to PNG and on the minimap.
+    let board_notes = Signal::derive(move || {
+        visible_board_notes(
+            &public_notes.get(),
+            &private_notes.get(),
+            &direct_notes.get(),
+            &scenes.get(),
+            active_scene_id.get().as_deref(),
+            show_inactive_scene_contents.get(),
+        )
+    });
 
     // How long a trail point stays visible (milliseconds).
     const TRAIL_LIFETIME_MS: f64 = 150.0;
@@ -155,6 +284,12 @@ pub fn SceneBoard(
         board_note_drag.set(None);
         board_note_resize.set(None);
         board_note_last_click.set(None);
+        followed_user.set(None);
+        camera_lock.set(None);
+        camera_locking.set(false);
+        camera_glide.set(None);
+        camera_followers.set(std::collections::HashSet::new());
+        board_viewports.set(std::collections::HashMap::new());
     });
 
     Effect::new(move |_| {
@@ -193,6 +328,214 @@ pub fn SceneBoard(
         });
     }
 
+    // Center the camera on a requested scene or token and mark the spot with a local ping.
+    Effect::new(move |_| {
+        let Some(focus) = board_focus.get() else {
+            return;
+        };
+        board_focus.set(None);
+        if let BoardFocus::Token { scene_id, token_id } = &focus {
+            levels.show_token_floor(scene_id, token_id);
+        }
+        let Some((world_x, world_y)) =
+            scenes.with_untracked(|scenes| board_focus_point(scenes, &focus))
+        else {
+            return;
+        };
+        let zoom = vm.zoom.get_untracked();
+        let (camera_x, camera_y) = camera_centered_on(world_x, world_y, zoom);
+        vm.set_view_transform(camera_x, camera_y, zoom);
+        attention_pings.update(|pings| {
+            pings.push(AttentionPingPayload {
+                username: username.get_untracked(),
+                position: WorldPoint {
+                    x: world_x,
+                    y: world_y,
+                },
+            });
+        });
+        spawn_local(async move {
+            gloo_timers::future::TimeoutFuture::new(3_500).await;
+            attention_pings.update(|pings| {
+                if !pings.is_empty() {
+                    pings.remove(0);
+                }
+            });
+        });
+    });
+
+    // Apply cameras shared by others: the game master's pull and lock, or the
+    // live camera of the followed user. The board glides instead of jumping.
+    Effect::new(move |_| {
+        let Some(payload) = board_camera.get() else {
+            return;
+        };
+        let current_game_master = game_master.get_untracked();
+        camera_lock.update(|lock| {
+            *lock = camera_lock_after(lock.take(), &payload, current_game_master.as_deref());
+        });
+        if !camera_moves_board(
+            &payload,
+            followed_user.get_untracked().as_deref(),
+            current_game_master.as_deref(),
+            &username.get_untracked(),
+        ) {
+            return;
+        }
+        let duration_ms = if payload.mode == BoardCameraMode::Follow {
+            CAMERA_FOLLOW_DURATION_MS
+        } else {
+            CAMERA_PULL_DURATION_MS
+        };
+        camera_glide.set(Some(CameraGlide {
+            from: camera_view(
+                vm.camera_x.get_untracked(),
+                vm.camera_y.get_untracked(),
+                vm.zoom.get_untracked(),
+            ),
+            to: payload_view(&payload),
+            started_at_ms: js_sys::Date::now(),
+            duration_ms,
+        }));
+    });
+
+    // Drive the glide frame by frame; a new target replaces the running one.
+    Effect::new(move |_| {
+        if camera_glide.with(Option::is_none) || camera_glide_running.get_value() {
+            return;
+        }
+        camera_glide_running.set_value(true);
+        spawn_local(async move {
+            while let Some(glide) = camera_glide.get_untracked() {
+                let (view, done) = glide_view(&glide, js_sys::Date::now());
+                let (camera_x, camera_y, zoom) = camera_transform(view);
+                vm.set_view_transform(camera_x, camera_y, zoom);
+                if done {
+                    camera_glide.set(None);
+                    break;
+                }
+                gloo_timers::future::TimeoutFuture::new(16).await;
+            }
+            camera_glide_running.set_value(false);
+        });
+    });
+
+    // Tell the followed user to start or stop sharing their camera.
+    Effect::new(move |previous: Option<Option<String>>| {
+        let followed = followed_user.get();
+        let previous = previous.flatten();
+        if previous != followed {
+            let follower = username.get_untracked();
+            for (target, active) in [(previous, false), (followed.clone(), true)] {
+                if let Some(target) = target {
+                    send_event(
+                        &ws_sender,
+                        ClientEvent::BoardFollow(BoardFollowPayload {
+                            follower: follower.clone(),
+                            target,
+                            active,
+                        }),
+                    );
+                }
+            }
+        }
+        followed
+    });
+
+    // Share the local camera while someone follows it or the game master
+    // holds the lock, throttled with a trailing update so the final view lands.
+    let share_camera = move || {
+        let locking = camera_locking.get_untracked();
+        if !locking && camera_followers.with_untracked(|followers| followers.is_empty()) {
+            return;
+        }
+        let mode = if locking {
+            BoardCameraMode::Lock
+        } else {
+            BoardCameraMode::Follow
+        };
+        let view = camera_view(
+            vm.camera_x.get_untracked(),
+            vm.camera_y.get_untracked(),
+            vm.zoom.get_untracked(),
+        );
+        last_camera_share_ms.set_value(Some(js_sys::Date::now()));
+        send_event(
+            &ws_sender,
+            ClientEvent::BoardCamera(camera_payload(&username.get_untracked(), view, mode)),
+        );
+    };
+    Effect::new(move |_| {
+        vm.camera_x.track();
+        vm.camera_y.track();
+        vm.zoom.track();
+        camera_locking.track();
+        camera_followers.track();
+        if camera_share_due(last_camera_share_ms.get_value(), js_sys::Date::now()) {
+            share_camera();
+        } else if !camera_share_pending.get_value() {
+            camera_share_pending.set_value(true);
+            spawn_local(async move {
+                gloo_timers::future::TimeoutFuture::new(CAMERA_SHARE_INTERVAL_MS as u32).await;
+                camera_share_pending.set_value(false);
+                share_camera();
+            });
+        }
+    });
+
+    // Releasing the lock is sent once, outside the throttle.
+    Effect::new(move |was_locking: Option<bool>| {
+        let locking = camera_locking.get();
+        if was_locking == Some(true) && !locking {
+            let view = camera_view(
+                vm.camera_x.get_untracked(),
+                vm.camera_y.get_untracked(),
+                vm.zoom.get_untracked(),
+            );
+            send_event(
+                &ws_sender,
+                ClientEvent::BoardCamera(camera_payload(
+                    &username.get_untracked(),
+                    view,
+                    BoardCameraMode::Release,
+                )),
+            );
+        }
+        locking
+    });
+
+    minimap.share_viewport(ws_sender, username);
+    // Camera moves the user makes themselves, e.g. on the minimap or with two
+    // fingers, stop following anyone.
+    let steer_camera = Callback::new(move |(camera_x, camera_y, zoom): (f64, f64, f64)| {
+        if camera_locked.get_untracked() {
+            return;
+        }
+        followed_user.set(None);
+        camera_glide.set(None);
+        vm.set_view_transform(camera_x, camera_y, zoom);
+    });
+    let jump_to_world_point = minimap.jump_callback(steer_camera);
+    let fit_all_scenes = minimap.fit_all_callback(board_scenes, steer_camera);
+
+    // The benchmark moves the camera itself, so it drops any followed camera too.
+    let run_render_benchmark = Callback::new(move |_| {
+        if board_canvas.benchmark_running.get_untracked() || camera_locked.get_untracked() {
+            return;
+        }
+        followed_user.set(None);
+        camera_glide.set(None);
+        board_canvas.run_benchmark(vm);
+    });
+    board_canvas.paint_board(
+        vm,
+        board_scenes,
+        active_scene_id,
+        show_inactive_scene_contents,
+        file_transfer.file_urls,
+        &theme,
+    );
+
     // Broadcast BoardPointer toggle events when the tool is activated or deactivated.
     // Only ONE event per toggle — no per-move data; trail is derived locally from cursors.
     {
@@ -291,6 +634,261 @@ pub fn SceneBoard(
         });
     }
 
+    // Camera gestures the user makes themselves stop following anyone.
+    let start_board_pan = move |local_x: f64, local_y: f64| {
+        if camera_locked.get_untracked() {
+            return;
+        }
+        followed_user.set(None);
+        camera_glide.set(None);
+        vm.is_selecting.set(false);
+        vm.end_scene_drag();
+        vm.end_token_drag();
+        dragging_library_token_id.set(None);
+        dragging_compendium_monster.set(None);
+        vm.start_pan(local_x, local_y);
+    };
+
+    let open_token_menu_at = move |token_id: String, local_x: f64, local_y: f64| {
+        let Some((scene_id, token)) = scenes.get_untracked().iter().find_map(|scene| {
+            scene
+                .tokens
+                .iter()
+                .find(|token| token.id == token_id)
+                .map(|token| (scene.id.clone(), token.clone()))
+        }) else {
+            return;
+        };
+        token_menu.set(Some(TokenMenuState {
+            scene_id,
+            token_id,
+            token_name: token.name.clone(),
+            token,
+            screen_x: local_x + 14.0,
+            screen_y: local_y + 14.0,
+        }));
+    };
+    // A finger resting on a token opens its menu, as a mouse click in place does.
+    let open_long_press_menu = move |local_x: f64, local_y: f64| {
+        let Some(token_id) = vm.dragging_token_id.get_untracked() else {
+            return false;
+        };
+        if token_drag_did_move.get_untracked() {
+            return false;
+        }
+        vm.end_token_drag();
+        open_token_menu_at(token_id, local_x, local_y);
+        true
+    };
+
+    // Ends whatever the pointer started: commits drags and drops, and opens the
+    // token menu for a token clicked in place. Fingers open it with a long press
+    // instead, so touch releases pass `open_token_menu: false`.
+    let release_pointer = move |event: &MouseEvent, open_token_menu: bool| {
+        let local_point = viewport_local_point(&viewport_ref, event.client_x(), event.client_y());
+
+        if let Some(token_id) = vm.end_token_rotation()
+            && let Some(scene) = scenes
+                .get_untracked()
+                .into_iter()
+                .find(|scene| scene.tokens.iter().any(|token| token.id == token_id))
+        {
+            send_event(
+                &ws_sender,
+                ClientEvent::SceneUpdate(SceneUpdatePayload {
+                    scene,
+                    actor: username.get_untracked(),
+                }),
+            );
+        }
+
+        terrain.release(&ws_sender, username.get_untracked());
+
+        if let Some(scene_id) = vm.dragging_scene_id.get_untracked()
+            && drag_did_move.get_untracked()
+            && let Some(scene) = scenes
+                .get_untracked()
+                .into_iter()
+                .find(|s| s.id == scene_id)
+        {
+            send_event(
+                &ws_sender,
+                ClientEvent::SceneUpdate(SceneUpdatePayload {
+                    scene,
+                    actor: username.get_untracked(),
+                }),
+            );
+        }
+
+        if let Some(token_id) = vm.dragging_token_id.get_untracked() {
+            if token_drag_did_move.get_untracked() {
+                if let Some(token) = scenes
+                    .get_untracked()
+                    .iter()
+                    .flat_map(|scene| scene.tokens.iter())
+                    .find(|token| token.id == token_id)
+                    .cloned()
+                {
+                    // Stairs under the dropped token carry it to the linked floor.
+                    if let Some(scene) = levels.take_stairs(&token_id) {
+                        send_event(
+                            &ws_sender,
+                            ClientEvent::SceneUpdate(SceneUpdatePayload {
+                                scene,
+                                actor: username.get_untracked(),
+                            }),
+                        );
+                    } else {
+                        send_event(
+                            &ws_sender,
+                            ClientEvent::TokenMove(TokenMovePayload {
+                                token_id,
+                                x: token.x,
+                                y: token.y,
+                                actor: username.get_untracked(),
+                            }),
+                        );
+                    }
+                }
+            } else if open_token_menu && let Some((local_x, local_y)) = local_point {
+                open_token_menu_at(token_id, local_x, local_y);
+            }
+        }
+
+        // Scene and snapped position for a footprint dropped under the pointer.
+        let drop_target = |width_cells: u16, height_cells: u16| {
+            let layouts = build_scene_layouts(&board_scenes.get_untracked());
+            let (world_x, world_y) = super::model::screen_to_world(
+                vm.pointer_local_x.get_untracked(),
+                vm.pointer_local_y.get_untracked(),
+                vm.viewport_width.get_untracked(),
+                vm.viewport_height.get_untracked(),
+                vm.camera_x.get_untracked(),
+                vm.camera_y.get_untracked(),
+                vm.zoom.get_untracked(),
+            );
+            let active_id = active_scene_id.get_untracked();
+            let allow_inactive = show_inactive_scene_contents.get_untracked();
+            let target_layout = layouts.iter().rev().find(|layout| {
+                point_inside_board(layout, world_x, world_y)
+                    && scene_allows_token_interaction(
+                        layout.scene.id.as_str(),
+                        active_id.as_deref(),
+                        allow_inactive,
+                    )
+            })?;
+            let (offset_x, offset_y) =
+                centered_token_offset(target_layout.cell_size, width_cells, height_cells);
+            let (mut token_x, mut token_y) = token_position_from_world(
+                world_x,
+                world_y,
+                target_layout.left(),
+                target_layout.top(),
+                target_layout.cell_size,
+                target_layout.scene.grid.columns,
+                target_layout.scene.grid.rows,
+                width_cells,
+                height_cells,
+                offset_x,
+                offset_y,
+            );
+            if !event.ctrl_key() {
+                (token_x, token_y) = snap_token_position_to_grid(
+                    token_x,
+                    token_y,
+                    target_layout.scene.grid.columns,
+                    target_layout.scene.grid.rows,
+                    width_cells,
+                    height_cells,
+                );
+            }
+            Some((target_layout.scene.id.clone(), token_x, token_y))
+        };
+
+        if let Some(library_token_id) = dragging_library_token_id.get_untracked()
+            && let Some(item) = token_library_items
+                .get_untracked()
+                .into_iter()
+                .find(|item| item.id == library_token_id)
+            && let Some((scene_id, token_x, token_y)) =
+                drop_target(item.width_cells, item.height_cells)
+        {
+            // Pawns dropped by players belong to them; the game master's stay unowned.
+            let current_user = username.get_untracked();
+            let owner =
+                (!acts_as_game_master(game_master.get_untracked().as_deref(), &current_user))
+                    .then_some(current_user);
+            let level_id = levels.scene_level_untracked(&scene_id);
+            if let Some(scene) =
+                place_library_token(scenes, &scene_id, &item, token_x, token_y, owner, level_id)
+            {
+                send_event(
+                    &ws_sender,
+                    ClientEvent::SceneUpdate(SceneUpdatePayload {
+                        scene,
+                        actor: username.get_untracked(),
+                    }),
+                );
+            }
+        }
+
+        if let Some(monster) = dragging_compendium_monster.get_untracked()
+            && let Some(on_drop) = on_compendium_monster_drop
+        {
+            let cells = monster.footprint_cells();
+            if let Some((scene_id, x, y)) = drop_target(cells, cells) {
+                on_drop.run(MonsterPlacement {
+                    name: monster.name.clone(),
+                    image: None,
+                    monster,
+                    level_id: levels.scene_level_untracked(&scene_id),
+                    scene_id,
+                    x,
+                    y,
+                });
+            }
+        }
+
+        if let Some(drag_state) = board_note_drag.get_untracked()
+            && board_note_drag_did_move.get_untracked()
+            && let Some(note) = find_note_by_ref(
+                &drag_state.note_id,
+                &drag_state.visibility,
+                &public_notes.get_untracked(),
+                &private_notes.get_untracked(),
+                &direct_notes.get_untracked(),
+            )
+        {
+            persist_note_upsert(&ws_sender, &room_id, &username, note);
+        }
+
+        if let Some(resize_state) = board_note_resize.get_untracked()
+            && board_note_resize_did_move.get_untracked()
+            && let Some(note) = find_note_by_ref(
+                &resize_state.note_id,
+                &resize_state.visibility,
+                &public_notes.get_untracked(),
+                &private_notes.get_untracked(),
+                &direct_notes.get_untracked(),
+            )
+        {
+            persist_note_upsert(&ws_sender, &room_id, &username, note);
+        }
+
+        vm.end_scene_drag();
+        vm.end_token_drag();
+        board_note_drag.set(None);
+        board_note_resize.set(None);
+        dragging_library_token_id.set(None);
+        dragging_compendium_monster.set(None);
+        drag_did_move.set(false);
+        token_drag_did_move.set(false);
+        board_note_drag_did_move.set(false);
+        board_note_resize_did_move.set(false);
+        vm.end_pan();
+        vm.is_selecting.set(false);
+    };
+
     // Global event listeners
     Effect::new(move |_| {
         let resize_handle = window_event_listener(ev::resize, move |_| {
@@ -299,12 +897,26 @@ pub fn SceneBoard(
             vm.viewport_height.set(h);
         });
 
-        let mouse_move_handle = window_event_listener(ev::mousemove, move |event: MouseEvent| {
+        let pointer_move = move |event: PointerEvent| {
             let Some((local_x, local_y)) =
                 viewport_local_point(&viewport_ref, event.client_x(), event.client_y())
             else {
                 return;
             };
+            if is_touch(&event) {
+                match board_touch.move_to(&event, local_x, local_y) {
+                    TouchAction::Pointer => {}
+                    TouchAction::Pinch {
+                        camera_x,
+                        camera_y,
+                        zoom,
+                    } => {
+                        steer_camera.run((camera_x, camera_y, zoom));
+                        return;
+                    }
+                    TouchAction::StartPinch | TouchAction::Ignore => return,
+                }
+            }
 
             vm.update_pointer(local_x, local_y);
             vm.update_pan(local_x, local_y);
@@ -319,7 +931,7 @@ pub fn SceneBoard(
                 vm.zoom.get(),
             );
 
-            let layouts = build_scene_layouts(&scenes.get_untracked());
+            let layouts = build_scene_layouts(&board_scenes.get_untracked());
             let hovered_scene_id = layouts
                 .iter()
                 .rev()
@@ -341,9 +953,60 @@ pub fn SceneBoard(
                 );
             }
 
+            let hovered_token_id = layouts
+                .iter()
+                .rev()
+                .find(|layout| {
+                    point_inside_board(layout, world_x, world_y)
+                        && scene_shows_contents(
+                            layout.scene.id.as_str(),
+                            active_scene_id.get_untracked().as_deref(),
+                            show_inactive_scene_contents.get_untracked(),
+                        )
+                })
+                .and_then(|layout| token_hit(layout, world_x, world_y))
+                .map(|token| token.id);
+            vm.set_hovered_token(hovered_token_id);
+
             // Pointer tool: no per-move event needed.
             // The trail is accumulated locally on each receiver from the MOUSE_EVENT stream.
 
+            if let Some(token_id) = vm.rotating_token_id.get_untracked() {
+                if let Some((layout, token)) = layouts.iter().find_map(|layout| {
+                    layout
+                        .scene
+                        .tokens
+                        .iter()
+                        .find(|token| token.id == token_id)
+                        .map(|token| (layout, token))
+                }) {
+                    let (left, top, width, height) = token_rect(
+                        layout.left(),
+                        layout.top(),
+                        layout.cell_size,
+                        token.x,
+                        token.y,
+                        token.width_cells,
+                        token.height_cells,
+                    );
+                    let mut rotation =
+                        rotation_towards(left + width / 2.0, top + height / 2.0, world_x, world_y);
+                    // Ctrl rotates freely, same as Ctrl for free token movement.
+                    if !event.ctrl_key() {
+                        rotation = snap_rotation_deg(rotation);
+                    }
+                    if (rotation - token.rotation_deg).abs() > f32::EPSILON {
+                        update_token_rotation(scenes, &token_id, rotation);
+                        vm.mark_token_rotation_changed();
+                    }
+                }
+                return;
+            }
+
+            if terrain.drag(&layouts, world_x, world_y) {
+                return;
+            }
+
             if let Some(scene_id) = vm.dragging_scene_id.get() {
                 let Some((_, candidate_x, candidate_y)) =
                     vm.compute_scene_drag_position(world_x, world_y)
@@ -427,17 +1090,19 @@ pub fn SceneBoard(
                     return;
                 };
 
-                note.board_position = Some(NoteBoardPosition {
-                    world_x: world_x - drag_state.pointer_offset_x,
-                    world_y: world_y - drag_state.pointer_offset_y,
-                });
+                let Some(position) = note.board_position.as_mut() else {
+                    return;
+                };
+                move_board_note(
+                    position,
+                    world_x - drag_state.pointer_offset_x,
+                    world_y - drag_state.pointer_offset_y,
+                );
+                board_note_drag_did_move.set(
+                    (position.world_x - drag_state.start_note_x).abs() > DRAG_EPSILON_PX
+                        || (position.world_y - drag_state.start_note_y).abs() > DRAG_EPSILON_PX,
+                );
                 note.updated_at_ms = current_time_ms();
-                if let Some(position) = note.board_position.as_ref() {
-                    board_note_drag_did_move.set(
-                        (position.world_x - drag_state.start_note_x).abs() > DRAG_EPSILON_PX
-                            || (position.world_y - drag_state.start_note_y).abs() > DRAG_EPSILON_PX,
-                    );
-                }
                 apply_local_note_upsert(public_notes, private_notes, direct_notes, note);
                 return;
             }
@@ -480,19 +1145,49 @@ pub fn SceneBoard(
                 vm.selection_end_x.set(cx);
                 vm.selection_end_y.set(cy);
             }
-        });
-
-        let mouse_up_handle = window_event_listener(ev::mouseup, move |event: MouseEvent| {
-            let local_point =
-                viewport_local_point(&viewport_ref, event.client_x(), event.client_y());
+        };
+        let pointer_move_handle = window_event_listener(ev::pointermove, pointer_move);
 
-            if let Some(scene_id) = vm.dragging_scene_id.get_untracked()
-                && drag_did_move.get_untracked()
-                && let Some(scene) = scenes
-                    .get_untracked()
-                    .into_iter()
-                    .find(|s| s.id == scene_id)
+        let pointer_up = move |event: PointerEvent| {
+            if is_touch(&event) {
+                if board_touch.release(&event) == TouchAction::Pointer {
+                    release_pointer(&event, false);
+                }
+                return;
+            }
+            release_pointer(&event, true);
+        };
+        let pointer_up_handle = window_event_listener(ev::pointerup, pointer_up);
+        let pointer_cancel_handle = window_event_listener(ev::pointercancel, pointer_up);
+
+        // Q / E rotate the focused token by 45°; with Shift by a fine free step.
+        let key_down_handle = window_event_listener(ev::keydown, move |event: KeyboardEvent| {
+            let direction = match event.code().as_str() {
+                "KeyQ" => -1.0,
+                "KeyE" => 1.0,
+                _ => return,
+            };
+            if event.ctrl_key() || event.meta_key() || event.alt_key() {
+                return;
+            }
+            if let Some(target) = event.target()
+                && let Some(element) = target.dyn_ref::<web_sys::HtmlElement>()
             {
+                let tag = element.tag_name().to_lowercase();
+                if tag == "input" || tag == "textarea" {
+                    return;
+                }
+            }
+            let Some(token_id) = vm.focused_token_id.get_untracked() else {
+                return;
+            };
+            let fine = event.shift_key();
+            let step = if fine {
+                TOKEN_ROTATION_FINE_STEP_DEG
+            } else {
+                TOKEN_ROTATION_SNAP_DEG
+            };
+            if let Some(scene) = rotate_token_by(scenes, &token_id, direction * step, !fine) {
                 send_event(
                     &ws_sender,
                     ClientEvent::SceneUpdate(SceneUpdatePayload {
@@ -501,162 +1196,14 @@ pub fn SceneBoard(
                     }),
                 );
             }
-
-            if let Some(token_id) = vm.dragging_token_id.get_untracked() {
-                if token_drag_did_move.get_untracked() {
-                    if let Some(token) = scenes
-                        .get_untracked()
-                        .iter()
-                        .flat_map(|scene| scene.tokens.iter())
-                        .find(|token| token.id == token_id)
-                        .cloned()
-                    {
-                        send_event(
-                            &ws_sender,
-                            ClientEvent::TokenMove(TokenMovePayload {
-                                token_id,
-                                x: token.x,
-                                y: token.y,
-                                actor: username.get_untracked(),
-                            }),
-                        );
-                    }
-                } else if let Some((local_x, local_y)) = local_point
-                    && let Some((scene_id, token)) =
-                        scenes.get_untracked().iter().find_map(|scene| {
-                            scene
-                                .tokens
-                                .iter()
-                                .find(|token| token.id == token_id)
-                                .map(|token| (scene.id.clone(), token.clone()))
-                        })
-                {
-                    token_menu.set(Some(TokenMenuState {
-                        scene_id,
-                        token_id,
-                        token_name: token.name.clone(),
-                        token,
-                        screen_x: local_x + 14.0,
-                        screen_y: local_y + 14.0,
-                    }));
-                }
-            }
-
-            if let Some(library_token_id) = dragging_library_token_id.get_untracked()
-                && let Some(item) = token_library_items
-                    .get_untracked()
-                    .into_iter()
-                    .find(|item| item.id == library_token_id)
-            {
-                let layouts = build_scene_layouts(&scenes.get_untracked());
-                let (world_x, world_y) = super::model::screen_to_world(
-                    vm.pointer_local_x.get_untracked(),
-                    vm.pointer_local_y.get_untracked(),
-                    vm.viewport_width.get_untracked(),
-                    vm.viewport_height.get_untracked(),
-                    vm.camera_x.get_untracked(),
-                    vm.camera_y.get_untracked(),
-                    vm.zoom.get_untracked(),
-                );
-                let active_id = active_scene_id.get_untracked();
-                let allow_inactive = show_inactive_scene_contents.get_untracked();
-                if let Some(target_layout) = layouts.iter().rev().find(|layout| {
-                    point_inside_board(layout, world_x, world_y)
-                        && scene_allows_token_interaction(
-                            layout.scene.id.as_str(),
-                            active_id.as_deref(),
-                            allow_inactive,
-                        )
-                }) {
-                    let (offset_x, offset_y) = centered_token_offset(
-                        target_layout.cell_size,
-                        item.width_cells,
-                        item.height_cells,
-                    );
-                    let (mut token_x, mut token_y) = token_position_from_world(
-                        world_x,
-                        world_y,
-                        target_layout.left(),
-                        target_layout.top(),
-                        target_layout.cell_size,
-                        target_layout.scene.grid.columns,
-                        target_layout.scene.grid.rows,
-                        item.width_cells,
-                        item.height_cells,
-                        offset_x,
-                        offset_y,
-                    );
-                    if !event.ctrl_key() {
-                        (token_x, token_y) = snap_token_position_to_grid(
-                            token_x,
-                            token_y,
-                            target_layout.scene.grid.columns,
-                            target_layout.scene.grid.rows,
-                            item.width_cells,
-                            item.height_cells,
-                        );
-                    }
-                    if let Some(scene) = place_library_token(
-                        scenes,
-                        &target_layout.scene.id,
-                        &item,
-                        token_x,
-                        token_y,
-                    ) {
-                        send_event(
-                            &ws_sender,
-                            ClientEvent::SceneUpdate(SceneUpdatePayload {
-                                scene,
-                                actor: username.get_untracked(),
-                            }),
-                        );
-                    }
-                }
-            }
-
-            if let Some(drag_state) = board_note_drag.get_untracked()
-                && board_note_drag_did_move.get_untracked()
-                && let Some(note) = find_note_by_ref(
-                    &drag_state.note_id,
-                    &drag_state.visibility,
-                    &public_notes.get_untracked(),
-                    &private_notes.get_untracked(),
-                    &direct_notes.get_untracked(),
-                )
-            {
-                persist_note_upsert(&ws_sender, &room_id, &username, note);
-            }
-
-            if let Some(resize_state) = board_note_resize.get_untracked()
-                && board_note_resize_did_move.get_untracked()
-                && let Some(note) = find_note_by_ref(
-                    &resize_state.note_id,
-                    &resize_state.visibility,
-                    &public_notes.get_untracked(),
-                    &private_notes.get_untracked(),
-                    &direct_notes.get_untracked(),
-                )
-            {
-                persist_note_upsert(&ws_sender, &room_id, &username, note);
-            }
-
-            vm.end_scene_drag();
-            vm.end_token_drag();
-            board_note_drag.set(None);
-            board_note_resize.set(None);
-            dragging_library_token_id.set(None);
-            drag_did_move.set(false);
-            token_drag_did_move.set(false);
-            board_note_drag_did_move.set(false);
-            board_note_resize_did_move.set(false);
-            vm.end_pan();
-            vm.is_selecting.set(false);
         });
 
         on_cleanup(move || {
             drop(resize_handle);
-            drop(mouse_move_handle);
-            drop(mouse_up_handle);
+            drop(pointer_move_handle);
+            drop(pointer_up_handle);
+            drop(pointer_cancel_handle);
+            drop(key_down_handle);
         });
     });
 
@@ -665,7 +1212,10 @@ pub fn SceneBoard(
         let _ = active_scene_id.get();
         vm.is_selecting.set(false);
         vm.end_token_drag();
+        vm.end_token_rotation();
+        vm.focused_token_id.set(None);
         dragging_library_token_id.set(None);
+        dragging_compendium_monster.set(None);
         token_menu.set(None);
         token_editor.set(None);
         selected_board_note.set(None);
@@ -679,11 +1229,25 @@ pub fn SceneBoard(
         board_note_last_click.set(None);
     });
 
+    // Notes anchored to a scene follow it while it is dragged or moved by others.
     Effect::new(move |_| {
-        let current_notes = collect_board_notes(
+        let scene_items = scenes.get();
+        for notes in [public_notes, private_notes, direct_notes] {
+            let mut current = notes.get();
+            if sync_anchored_notes(&mut current, &scene_items) {
+                notes.set(current);
+            }
+        }
+    });
+
+    Effect::new(move |_| {
+        let current_notes = visible_board_notes(
             &public_notes.get(),
             &private_notes.get(),
             &direct_notes.get(),
+            &scenes.get(),
+            active_scene_id.get().as_deref(),
+            show_inactive_scene_contents.get(),
         );
         let selected = selected_board_note.get();
         if let Some(selected) = selected
@@ -705,9 +1269,11 @@ pub fn SceneBoard(
 
     view! {
         {move || {
+            board_canvas.start_probe();
             let active_id = active_scene_id.get();
             let show_inactive_contents = show_inactive_scene_contents.get();
-            let scene_items = scenes.get();
+            let canvas_renderer = board_canvas.is_canvas();
+            let scene_items = board_scenes.get();
             if scene_items.is_empty() {
                 return ().into_any();
             }
@@ -744,6 +1310,12 @@ pub fn SceneBoard(
             let toolbar_theme_main = theme.clone();
             let toolbar_theme_pointer = theme.clone();
             let toolbar_theme_ping = theme.clone();
+            let toolbar_theme_terrain = theme.clone();
+            let toolbar_theme_camera = theme.clone();
+            let toolbar_theme_export = theme.clone();
+            let toolbar_theme_minimap = theme.clone();
+            let render_stats_theme = theme.clone();
+            let floor_switcher_theme = theme.clone();
             let file_urls = file_transfer.file_urls.get();
 
             let selection_overlay = if vm.is_selecting.get() {
@@ -805,7 +1377,16 @@ pub fn SceneBoard(
                         ) else {
                             return;
                         };
-                        updated_note.board_position = Some(NoteBoardPosition { world_x, world_y });
+                        match updated_note.board_position.as_mut() {
+                            Some(position) => move_board_note(position, world_x, world_y),
+                            None => {
+                                updated_note.board_position = Some(NoteBoardPosition {
+                                    world_x,
+                                    world_y,
+                                    anchor: None,
+                                });
+                            }
+                        }
                         updated_note.updated_at_ms = current_time_ms();
                         let dropped_note_selection = BoardNoteSelection {
                             note_id: updated_note.id.clone(),
@@ -826,10 +1407,25 @@ pub fn SceneBoard(
                             board_note_focus_request,
                         );
                     }
-                    on:mousedown=move |event: MouseEvent| {
+                    on:pointerdown=move |event: PointerEvent| {
                         let Some((local_x, local_y)) =
                             viewport_local_point(&viewport_ref, event.client_x(), event.client_y())
                         else { return; };
+                        let is_touch = is_touch(&event);
+                        if is_touch {
+                            // Fingers skip the emulated mouse events, so a tap on the
+                            // board does not also land on whatever sits above it.
+                            event.prevent_default();
+                            match board_touch.press(&event, local_x, local_y, vm) {
+                                TouchAction::Pointer => {}
+                                // The second finger takes over from whatever the first started.
+                                TouchAction::StartPinch => {
+                                    release_pointer(&event, false);
+                                    return;
+                                }
+                                TouchAction::Pinch { .. } | TouchAction::Ignore => return,
+                            }
+                        }
                         vm.update_pointer(local_x, local_y);
                         token_menu.set(None);
 
@@ -837,11 +1433,7 @@ pub fn SceneBoard(
                             // Middle-click pan
                             1 => {
                                 event.prevent_default();
-                                vm.is_selecting.set(false);
-                                vm.end_scene_drag();
-                                vm.end_token_drag();
-                                dragging_library_token_id.set(None);
-                                vm.start_pan(local_x, local_y);
+                                start_board_pan(local_x, local_y);
                             }
                             // Left-click: drag handle or start selection
                             0 => {
@@ -874,7 +1466,7 @@ pub fn SceneBoard(
                                 // the start point so the ruler never leaves a single scene.
                                 if vm.active_tool.get_untracked() == BoardTool::Ruler {
                                     event.prevent_default();
-                                    let ruler_layouts = build_scene_layouts(&scenes.get_untracked());
+                                    let ruler_layouts = build_scene_layouts(&board_scenes.get_untracked());
                                     match (
                                         vm.ruler_start.get_untracked(),
                                         vm.ruler_end.get_untracked(),
@@ -902,10 +1494,40 @@ pub fn SceneBoard(
                                     return;
                                 }
 
-                                let board_notes = collect_board_notes(
+                                // Terrain tool: paint the cell under the cursor and keep
+                                // painting while the button is held (see pointermove).
+                                if vm.active_tool.get_untracked() == BoardTool::Terrain {
+                                    event.prevent_default();
+                                    terrain.press(world_x, world_y);
+                                    return;
+                                }
+
+                                if let Some(focused_id) = vm.focused_token_id.get_untracked()
+                                    && let Some((scene_id, token)) = rotation_handle_hit(
+                                        &build_scene_layouts(&board_scenes.get_untracked()),
+                                        &focused_id,
+                                        world_x,
+                                        world_y,
+                                    )
+                                    && scene_allows_token_interaction(
+                                        scene_id.as_str(),
+                                        active_scene_id.get_untracked().as_deref(),
+                                        show_inactive_scene_contents.get_untracked(),
+                                    )
+                                {
+                                    event.prevent_default();
+                                    vm.is_selecting.set(false);
+                                    vm.start_token_rotation(token.id);
+                                    return;
+                                }
+
+                                let board_notes = visible_board_notes(
                                     &public_notes.get_untracked(),
                                     &private_notes.get_untracked(),
                                     &direct_notes.get_untracked(),
+                                    &scenes.get_untracked(),
+                                    active_scene_id.get_untracked().as_deref(),
+                                    show_inactive_scene_contents.get_untracked(),
                                 );
                                 if let Some(note) = board_note_hit(&board_notes, world_x, world_y) {
                                     if let Some(draft) = board_note_editor.get_untracked()
@@ -935,6 +1557,7 @@ pub fn SceneBoard(
                                     vm.end_scene_drag();
                                     vm.end_token_drag();
                                     dragging_library_token_id.set(None);
+                                    dragging_compendium_monster.set(None);
                                     let note_selection = BoardNoteSelection {
                                         note_id: note.id.clone(),
                                         visibility: note.visibility.clone(),
@@ -992,7 +1615,7 @@ pub fn SceneBoard(
                                     board_note_editor_error,
                                     board_note_focus_request,
                                 );
-                                let layouts = build_scene_layouts(&scenes.get_untracked());
+                                let layouts = build_scene_layouts(&board_scenes.get_untracked());
                                 let mut ordered = layouts;
                                 ordered.sort_by_key(|l| {
                                     active_scene_id.get_untracked().as_deref() == Some(l.scene.id.as_str())
@@ -1024,7 +1647,17 @@ pub fn SceneBoard(
                                         show_inactive_scene_contents.get_untracked(),
                                     );
 
-                                    if let Some(token) = token_hit(layout, world_x, world_y) {
+                                    let token = if is_touch {
+                                        token_touch_hit(
+                                            layout,
+                                            world_x,
+                                            world_y,
+                                            TOUCH_TOKEN_REACH_PX / vm.zoom.get_untracked(),
+                                        )
+                                    } else {
+                                        token_hit(layout, world_x, world_y)
+                                    };
+                                    if let Some(token) = token {
                                         if !can_interact {
                                             return;
                                         }
@@ -1040,6 +1673,8 @@ pub fn SceneBoard(
                                         vm.is_selecting.set(false);
                                         vm.end_scene_drag();
                                         dragging_library_token_id.set(None);
+                                        dragging_compendium_monster.set(None);
+                                        vm.focused_token_id.set(Some(token.id.clone()));
                                         vm.start_token_drag(
                                             token.id.clone(),
                                             token.width_cells,
@@ -1050,10 +1685,18 @@ pub fn SceneBoard(
                                             token.y,
                                         );
                                         token_drag_did_move.set(false);
+                                        if is_touch {
+                                            board_touch.watch_long_press(&event, open_long_press_menu);
+                                        }
                                         return;
                                     }
 
+                                    vm.focused_token_id.set(None);
                                     if !is_active {
+                                        // One finger pans wherever it cannot select.
+                                        if is_touch {
+                                            start_board_pan(local_x, local_y);
+                                        }
                                         return;
                                     }
 
@@ -1062,10 +1705,13 @@ pub fn SceneBoard(
                                     vm.end_scene_drag();
                                     vm.end_token_drag();
                                     dragging_library_token_id.set(None);
+                                    dragging_compendium_monster.set(None);
                                     vm.selection_start_x.set(cx);
                                     vm.selection_start_y.set(cy);
                                     vm.selection_end_x.set(cx);
                                     vm.selection_end_y.set(cy);
+                                } else if is_touch {
+                                    start_board_pan(local_x, local_y);
                                 }
                             }
                             _ => {}
@@ -1073,6 +1719,11 @@ pub fn SceneBoard(
                     }
                     on:wheel=move |event: WheelEvent| {
                         event.prevent_default();
+                        if camera_locked.get_untracked() {
+                            return;
+                        }
+                        followed_user.set(None);
+                        camera_glide.set(None);
                         let Some((local_x, local_y)) =
                             viewport_local_point(&viewport_ref, event.client_x(), event.client_y())
                         else { return; };
@@ -1098,12 +1749,15 @@ pub fn SceneBoard(
                     on:contextmenu=move |event: MouseEvent| event.prevent_default()
                     style=move || format!(
                         "position: absolute; inset: 0; z-index: 1; overflow: hidden; \
-                         pointer-events: auto; user-select: none; cursor: {}; background: {};",
+                         pointer-events: auto; user-select: none; touch-action: none; \
+                         -webkit-touch-callout: none; cursor: {}; background: {};",
                         if vm.is_panning.get() { "grabbing" }
                         else if vm.dragging_scene_id.get().is_some() { "move" }
                         else if vm.dragging_token_id.get().is_some() { "grabbing" }
-                        else if dragging_library_token_id.get().is_some() { "copy" }
+                        else if dragging_library_token_id.get().is_some()
+                            || dragging_compendium_monster.get().is_some() { "copy" }
                         else if vm.is_selecting.get() { "crosshair" }
+                        else if vm.active_tool.get() == BoardTool::Terrain { "cell" }
                         else { "grab" },
                         theme.background_color
                     )
@@ -1130,6 +1784,9 @@ pub fn SceneBoard(
                         )
                     />
                     <div style="position: absolute; inset: 0; background: radial-gradient(circle at top, rgba(255,255,255,0.07), transparent 45%), radial-gradient(circle at bottom right, rgba(0,0,0,0.16), transparent 35%);" />
+                    {canvas_renderer.then(|| view! {
+                        <div node_ref=board_canvas.host style="position: absolute; inset: 0; pointer-events: none;" />
+                    })}
 
                     // World-space container (all boards)
                     <div style=format!(
@@ -1144,7 +1801,12 @@ pub fn SceneBoard(
                                 active_id.as_deref(),
                                 show_inactive_contents,
                             );
-                            let board_bg = board_background(theme.ui_bg_primary);
+                            // The canvas paints board backgrounds; the tile keeps its border.
+                            let board_bg = if canvas_renderer {
+                                "transparent".to_string()
+                            } else {
+                                board_background(theme.ui_bg_primary)
+                            };
                             let board_border = if is_active { theme.ui_success } else { theme.ui_border };
                             let handle_background = if is_active { "rgba(0,0,0,0.56)" } else { "rgba(0,0,0,0.42)" };
                             let blur_filter = if show_scene_contents { "none" } else { "blur(6px) saturate(0.72) brightness(0.7)" };
@@ -1156,7 +1818,7 @@ pub fn SceneBoard(
                             let minor_stroke = if is_active { "rgba(255,255,255,0.17)" } else { "rgba(255,255,255,0.12)" };
                             let major_stroke = if is_active { "rgba(255,255,255,0.06)" } else { "rgba(255,255,255,0.04)" };
                             let background_image = layout.scene.background.as_ref().and_then(|file| {
-                                if file.mime_type.starts_with("image/") {
+                                if file.mime_type.starts_with("image/") && !canvas_renderer {
                                     file_urls.get(&file.hash).cloned()
                                 } else {
                                     None
@@ -1245,7 +1907,25 @@ pub fn SceneBoard(
                                             }.into_any(),
                                             None => ().into_any(),
                                         }}
-                                        {if show_scene_contents {
+                                        {if show_scene_contents && !canvas_renderer {
+                                            view! {
+                                                <SceneTerrainLayer
+                                                    terrain=layout.scene.terrain.clone()
+                                                    cell_size=layout.cell_size
+                                                    board_width=layout.board_width
+                                                    board_height=layout.board_height
+                                                />
+                                            }.into_any()
+                                        } else { ().into_any() }}
+                                        {if show_scene_contents && !canvas_renderer {
+                                            view! {
+                                                <SceneStairsLayer
+                                                    scene=layout.scene.clone()
+                                                    cell_size=layout.cell_size
+                                                />
+                                            }.into_any()
+                                        } else { ().into_any() }}
+                                        {if show_scene_contents && !canvas_renderer {
                                             view! {
                                                 <svg
                                                     viewBox=format!("0 0 {:.4} {:.4}", layout.board_width, layout.board_height)
@@ -1301,10 +1981,26 @@ pub fn SceneBoard(
                                                 </svg>
                                             }.into_any()
                                         } else { ().into_any() }}
+                                        {if show_scene_contents {
+                                            view! {
+                                                <SceneAuraLayer
+                                                    tokens=layout.scene.tokens.clone()
+                                                    cell_size=layout.cell_size
+                                                    cell_size_feet=layout.scene.grid.cell_size_feet
+                                                    board_width=layout.board_width
+                                                    board_height=layout.board_height
+                                                    viewer=username.get()
+                                                    game_master=game_master.get()
+                                                    hovered_token_id=vm.hovered_token_id.get()
+                                                    ring_color=theme.ui_text_primary
+                                                />
+                                            }.into_any()
+                                        } else { ().into_any() }}
                                         <SceneTokenLayer
-                                            tokens=if show_scene_contents { layout.scene.tokens.clone() } else { Vec::new() }
+                                            tokens=if show_scene_contents && !canvas_renderer { layout.scene.tokens.clone() } else { Vec::new() }
                                             cell_size=layout.cell_size
                                             dragging_token_id=vm.dragging_token_id.get()
+                                            focused_token_id=vm.focused_token_id.get()
                                             file_urls=file_urls.clone()
                                             theme=theme.clone()
                                         />
@@ -1314,10 +2010,13 @@ pub fn SceneBoard(
                             }
                         }).collect_view()}
 
-                        {collect_board_notes(
+                        {visible_board_notes(
                             &public_notes.get(),
                             &private_notes.get(),
                             &direct_notes.get(),
+                            &scene_items,
+                            active_id.as_deref(),
+                            show_inactive_contents,
                         )
                         .into_iter()
                         .map(|note| {
@@ -1327,7 +2026,10 @@ pub fn SceneBoard(
                             let can_drag = can_edit_note(&note, &username.get_untracked());
                             let (note_title_for_display, note_body_for_display) =
                                 note_heading_and_body(&note.body);
-                            let rendered_html = render_note_html(&note_body_for_display);
+                            let rendered_html = resolve_file_embeds(
+                                &render_note_html(&note_body_for_display),
+                                &file_urls,
+                            );
                             let note_position = position.clone();
                             let note_style = note.board_style.clone();
                             let note_style_color = note.board_style.color.clone();
@@ -1350,8 +2052,15 @@ pub fn SceneBoard(
                             let open_editor_note_id = note.id.clone();
                             let open_editor_note_visibility = note.visibility.clone();
                             let open_editor_note_body = note.body.clone();
+                            let is_pin = note.board_style.pin_icon.is_some();
+                            let pin_icon = note.board_style.pin_icon.clone();
+                            let pin_title = note_title_for_display.clone();
+                            let pin_color = note.board_style.color.clone();
+                            let note_id_for_pin = note.id.clone();
+                            let note_visibility_for_pin = note.visibility.clone();
                             view! {
                                 <article
+                                    on:pointerdown=stop_pointer_propagation
                                     on:mousedown=move |event: MouseEvent| {
                                         event.stop_propagation();
                                         selected_board_note.set(Some(note_for_select.clone()));
@@ -1428,21 +2137,24 @@ pub fn SceneBoard(
                                                 draft.note_id == note_id_for_container
                                                     && draft.visibility == note_visibility_for_container
                                             });
+                                        // Map pins stay collapsed to their icon until selected.
+                                        let is_collapsed = is_pin && !is_selected;
                                         format!(
                                             "position: absolute; left: {:.2}px; top: {:.2}px; width: {:.2}px; height: {:.2}px; \
-                                         background: {}; border: 1px solid {}; border-radius: 0.9rem; \
+                                         background: {}; border: 1px solid {}; border-radius: {}; \
                                          box-shadow: {}; color: #2f240d; z-index: {}; overflow: hidden; user-select: {}; \
                                          pointer-events: auto;",
                                             note_position.world_x,
                                             note_position.world_y,
-                                            note_style.width_px,
-                                            note_style.height_px,
+                                            if is_collapsed { BOARD_NOTE_PIN_SIZE_PX } else { note_style.width_px },
+                                            if is_collapsed { BOARD_NOTE_PIN_SIZE_PX } else { note_style.height_px },
                                             note_style_color,
                                             if is_selected {
                                                 &theme.ui_button_primary
                                             } else {
                                                 "rgba(123,95,32,0.35)"
                                             },
+                                            if is_collapsed { "999px" } else { "0.9rem" },
                                             if is_selected {
                                                 "0 0 0 2px rgba(37,99,235,0.18), 0 16px 28px rgba(0,0,0,0.22)"
                                             } else {
@@ -1453,6 +2165,32 @@ pub fn SceneBoard(
                                         )
                                     }
                                 >
+                                    {move || {
+                                        let Some(icon) = pin_icon.clone() else {
+                                            return ().into_any();
+                                        };
+                                        let is_selected = selected_board_note
+                                            .get()
+                                            .as_ref()
+                                            .is_some_and(|selected| {
+                                                selected.note_id == note_id_for_pin
+                                                    && selected.visibility == note_visibility_for_pin
+                                            });
+                                        if is_selected {
+                                            return ().into_any();
+                                        }
+                                        view! {
+                                            <div
+                                                title=pin_title.clone()
+                                                style=format!(
+                                                    "position: absolute; inset: 0; z-index: 3; display: flex; align-items: center; justify-content: center; background: {}; font-size: 1.2rem; line-height: 1; cursor: pointer;",
+                                                    pin_color
+                                                )
+                                            >
+                                                {icon}
+                                            </div>
+                                        }.into_any()
+                                    }}
                                     {move || {
                                         let is_selected = selected_board_note
                                             .get()
@@ -1583,6 +2321,7 @@ pub fn SceneBoard(
                                                         {if !note_body_for_display.trim().is_empty() {
                                                             view! {
                                                                 <div
+                                                                    class="note-markdown"
                                                                     inner_html=rendered_html.clone()
                                                                     style=format!("font-size: {:.2}pt; line-height: 1.35; word-break: break-word; width: 100%;", body_font_size_pt)
                                                                 ></div>
@@ -1614,7 +2353,7 @@ pub fn SceneBoard(
 
                                         view! {
                                             <button
-                                                on:mousedown=move |event: MouseEvent| {
+                                                on:pointerdown=move |event: PointerEvent| {
                                                     event.stop_propagation();
                                                     if event.button() != 0 {
                                                         return;
@@ -1698,7 +2437,10 @@ pub fn SceneBoard(
                         let can_delete = can_delete_note(&note, &current_username);
                         let can_delete_direct_for_recipient = can_delete
                             && !can_drag
-                            && matches!(note.visibility, NoteVisibility::Direct(_));
+                            && matches!(
+                                note.visibility,
+                                NoteVisibility::Direct(_) | NoteVisibility::Group(_)
+                            );
                         let is_editing = board_note_editor
                             .get()
                             .as_ref()
@@ -1718,6 +2460,12 @@ pub fn SceneBoard(
                         let font_down_note_visibility = note.visibility.clone();
                         let font_up_note_id = note.id.clone();
                         let font_up_note_visibility = note.visibility.clone();
+                        let anchor_note_id = note.id.clone();
+                        let anchor_note_visibility = note.visibility.clone();
+                        let is_anchored = position.anchor.is_some();
+                        let pin_note_id = note.id.clone();
+                        let pin_note_visibility = note.visibility.clone();
+                        let current_pin_icon = note.board_style.pin_icon.clone();
                         let (screen_x, screen_y) = world_to_screen(
                             position.world_x,
                             position.world_y,
@@ -1730,7 +2478,7 @@ pub fn SceneBoard(
 
                         view! {
                             <div
-                                on:mousedown=move |event: MouseEvent| {
+                                on:pointerdown=move |event: PointerEvent| {
                                     event.stop_propagation();
                                     if event.button() != 0 || !can_drag {
                                         return;
@@ -1786,6 +2534,7 @@ pub fn SceneBoard(
                                         let note_for_color = note.clone();
                                         view! {
                                             <button
+                                                on:pointerdown=stop_pointer_propagation
                                                 on:mousedown=move |event: MouseEvent| {
                                                     event.prevent_default();
                                                     event.stop_propagation();
@@ -1826,6 +2575,7 @@ pub fn SceneBoard(
                                     view! {
                                         <>
                                             <button
+                                                on:pointerdown=stop_pointer_propagation
                                                 on:mousedown=move |event: MouseEvent| {
                                                     event.prevent_default();
                                                     event.stop_propagation();
@@ -1868,6 +2618,7 @@ pub fn SceneBoard(
                                                 {format!("{:.0}pt", note_font_size_pt)}
                                             </span>
                                             <button
+                                                on:pointerdown=stop_pointer_propagation
                                                 on:mousedown=move |event: MouseEvent| {
                                                     event.prevent_default();
                                                     event.stop_propagation();
@@ -1907,6 +2658,99 @@ pub fn SceneBoard(
                                                 {"A+"}
                                             </button>
                                             <button
+                                                on:pointerdown=stop_pointer_propagation
+                                                on:mousedown=move |event: MouseEvent| {
+                                                    event.prevent_default();
+                                                    event.stop_propagation();
+                                                    let Some(mut updated_note) = find_note_by_ref(
+                                                        &anchor_note_id,
+                                                        &anchor_note_visibility,
+                                                        &public_notes.get_untracked(),
+                                                        &private_notes.get_untracked(),
+                                                        &direct_notes.get_untracked(),
+                                                    ) else {
+                                                        return;
+                                                    };
+                                                    let Some(position) = updated_note.board_position.as_mut() else {
+                                                        return;
+                                                    };
+                                                    if position.anchor.take().is_none() {
+                                                        let scene_items = scenes.get_untracked();
+                                                        let Some(scene) = scene_for_note_anchor(
+                                                            &scene_items,
+                                                            position.world_x,
+                                                            position.world_y,
+                                                            active_scene_id.get_untracked().as_deref(),
+                                                        ) else {
+                                                            return;
+                                                        };
+                                                        anchor_board_note(position, scene);
+                                                    }
+                                                    updated_note.updated_at_ms = current_time_ms();
+                                                    apply_local_note_upsert(
+                                                        public_notes,
+                                                        private_notes,
+                                                        direct_notes,
+                                                        updated_note.clone(),
+                                                    );
+                                                    persist_note_upsert(&ws_sender, &room_id, &username, updated_note);
+                                                }
+                                                style=format!(
+                                                    "padding: 0.35rem 0.6rem; background: {}; color: #1f2937; border: none; border-radius: 0.5rem; cursor: pointer; font-size: 0.72rem; white-space: nowrap;",
+                                                    if is_anchored { "rgba(108,124,255,0.22)" } else { "rgba(148,163,184,0.16)" }
+                                                )
+                                            >
+                                                {if is_anchored {
+                                                    t!(i18n, notes.detach_from_scene_button).into_any()
+                                                } else {
+                                                    t!(i18n, notes.anchor_to_scene_button).into_any()
+                                                }}
+                                            </button>
+                                            <select
+                                                on:pointerdown=stop_pointer_propagation
+                                                on:mousedown=move |event: MouseEvent| {
+                                                    event.stop_propagation();
+                                                }
+                                                on:change=move |event| {
+                                                    let value = event_target_value(&event);
+                                                    let Some(mut updated_note) = find_note_by_ref(
+                                                        &pin_note_id,
+                                                        &pin_note_visibility,
+                                                        &public_notes.get_untracked(),
+                                                        &private_notes.get_untracked(),
+                                                        &direct_notes.get_untracked(),
+                                                    ) else {
+                                                        return;
+                                                    };
+                                                    updated_note.board_style.pin_icon = (!value.is_empty()).then_some(value);
+                                                    updated_note.updated_at_ms = current_time_ms();
+                                                    apply_local_note_upsert(
+                                                        public_notes,
+                                                        private_notes,
+                                                        direct_notes,
+                                                        updated_note.clone(),
+                                                    );
+                                                    persist_note_upsert(&ws_sender, &room_id, &username, updated_note);
+                                                }
+                                                style="padding: 0.25rem 0.35rem; background: rgba(148,163,184,0.16); color: #1f2937; border: none; border-radius: 0.5rem; cursor: pointer; font-size: 0.78rem;"
+                                            >
+                                                <option value="" selected=current_pin_icon.is_none()>
+                                                    {t!(i18n, notes.pin_icon_none)}
+                                                </option>
+                                                {BOARD_NOTE_PIN_ICONS
+                                                    .iter()
+                                                    .copied()
+                                                    .map(|icon| {
+                                                        view! {
+                                                            <option value=icon selected=current_pin_icon.as_deref() == Some(icon)>
+                                                                {icon}
+                                                            </option>
+                                                        }
+                                                    })
+                                                    .collect_view()}
+                                            </select>
+                                            <button
+                                                on:pointerdown=stop_pointer_propagation
                                                 on:mousedown=move |event: MouseEvent| {
                                                     event.prevent_default();
                                                     event.stop_propagation();
@@ -1948,6 +2792,7 @@ pub fn SceneBoard(
                                     let note_for_delete = note.clone();
                                     view! {
                                         <button
+                                            on:pointerdown=stop_pointer_propagation
                                             on:mousedown=move |event: MouseEvent| {
                                                 event.prevent_default();
                                                 event.stop_propagation();
@@ -1996,6 +2841,7 @@ pub fn SceneBoard(
                         token_menu.get().map(|menu| {
                             let menu_for_save = menu.clone();
                             let menu_for_delete = menu.clone();
+                            let sheet_token_id = menu.token_id.clone();
                             let menu_theme = token_menu_theme.clone();
                             view! {
                                 <SceneTokenMenu
@@ -2003,13 +2849,16 @@ pub fn SceneBoard(
                                     screen_x=menu.screen_x
                                     screen_y=menu.screen_y
                                     on_edit=Callback::new(move |_| {
-                                        token_editor.set(Some(SceneTokenEditorDraft {
-                                            scene_id: menu.scene_id.clone(),
-                                            token_id: menu.token_id.clone(),
-                                            name: menu.token.name.clone(),
-                                            width_cells: menu.token.width_cells.to_string(),
-                                            height_cells: menu.token.height_cells.to_string(),
-                                        }));
+                                        token_editor.set(Some(SceneTokenEditorDraft::from_token(
+                                            &menu.scene_id,
+                                            &menu.token,
+                                        )));
+                                        token_menu.set(None);
+                                    })
+                                    on_open_character_sheet=Callback::new(move |_| {
+                                        if let Some(callback) = on_open_character_sheet {
+                                            callback.run(sheet_token_id.clone());
+                                        }
                                         token_menu.set(None);
                                     })
                                     on_save_to_library=Callback::new(move |_| {
@@ -2066,15 +2915,18 @@ pub fn SceneBoard(
 
                     <SceneTokenEditor
                         draft=token_editor
-                        on_save=Callback::new(move |value: SceneTokenEditorValue| {
-                            if let Some(scene) = update_token_details(
-                                scenes,
-                                &value.scene_id,
-                                &value.token_id,
-                                &value.name,
-                                value.width_cells,
-                                value.height_cells,
-                            ) {
+                        on_save=Callback::new(move |mut value: SceneTokenEditorValue| {
+                            // Players can edit auras, but only the game master reassigns owners.
+                            if !acts_as_game_master(game_master.get_untracked().as_deref(), &username.get_untracked()) {
+                                value.owner = scenes.with_untracked(|items| {
+                                    items
+                                        .iter()
+                                        .find(|scene| scene.id == value.scene_id)
+                                        .and_then(|scene| scene.tokens.iter().find(|token| token.id == value.token_id))
+                                        .and_then(|token| token.owner.clone())
+                                });
+                            }
+                            if let Some(scene) = update_token_details(scenes, &value) {
                                 send_event(
                                     &ws_sender,
                                     ClientEvent::SceneUpdate(SceneUpdatePayload {
@@ -2086,6 +2938,9 @@ pub fn SceneBoard(
                             token_editor.set(None);
                         })
                         on_close=Callback::new(move |_| token_editor.set(None))
+                        can_assign_owner=Signal::derive(move || {
+                            acts_as_game_master(game_master.get().as_deref(), &username.get())
+                        })
                         theme=token_editor_theme
                     />
 
@@ -2170,7 +3025,7 @@ pub fn SceneBoard(
                             return ().into_any();
                         };
                         // Determine end: either anchored or live cursor clamped to start's scene.
-                        let ruler_scene_layout = build_scene_layouts(&scenes.get())
+                        let ruler_scene_layout = build_scene_layouts(&board_scenes.get())
                             .into_iter()
                             .find(|l| point_inside_board(l, start.0, start.1));
                         let (end_wx, end_wy) = vm.ruler_end.get().unwrap_or_else(|| {
@@ -2193,7 +3048,7 @@ pub fn SceneBoard(
                         // Find the scene that contains the ruler start point, get
                         // its cell_size_feet, and convert both endpoints to scene-local
                         // cell coordinates for a proper DnD distance calculation.
-                        let scene_layouts_for_ruler = build_scene_layouts(&scenes.get());
+                        let scene_layouts_for_ruler = build_scene_layouts(&board_scenes.get());
                         let ruler_layout = scene_layouts_for_ruler
                             .iter()
                             .find(|l| point_inside_board(l, start.0, start.1));
@@ -2206,9 +3061,30 @@ pub fn SceneBoard(
                         let (end_cx, end_cy) = ruler_layout
                             .map(|l| world_to_scene_cells(end_wx, end_wy, l.left(), l.top()))
                             .unwrap_or((0.0, 0.0));
-                        let (dcells, dfeet) = ruler_distance(
-                            start_cx, start_cy, end_cx, end_cy, cell_size_feet,
+                        let (flat_cells, flat_feet) = terrain_ruler_distance(
+                            start_cx,
+                            start_cy,
+                            end_cx,
+                            end_cy,
+                            cell_size_feet,
+                            ruler_layout.map(|l| l.scene.terrain.as_slice()).unwrap_or_default(),
                         );
+                        // Endpoints on tokens measure between their elevations.
+                        let elevation_delta = ruler_layout
+                            .map(|l| {
+                                let start_elevation =
+                                    token_hit(l, start.0, start.1).map_or(0, |t| t.elevation_feet);
+                                let end_elevation =
+                                    token_hit(l, end_wx, end_wy).map_or(0, |t| t.elevation_feet);
+                                f64::from(end_elevation - start_elevation)
+                            })
+                            .unwrap_or(0.0);
+                        let dfeet = elevated_distance_feet(flat_feet, elevation_delta);
+                        let dcells = if elevation_delta == 0.0 {
+                            flat_cells
+                        } else {
+                            dfeet / f64::from(cell_size_feet.max(1))
+                        };
 
                         let cam_x = vm.camera_x.get();
                         let cam_y = vm.camera_y.get();
@@ -2230,6 +3106,69 @@ pub fn SceneBoard(
                         }.into_any()
                     }}
 
+                    // Token movement distance: origin → current position, terrain-aware.
+                    {move || {
+                        let Some(token_id) = vm.dragging_token_id.get() else {
+                            return ().into_any();
+                        };
+                        if !token_drag_did_move.get() {
+                            return ().into_any();
+                        }
+                        let layouts = build_scene_layouts(&board_scenes.get());
+                        let Some((layout, token)) = layouts.iter().find_map(|layout| {
+                            layout
+                                .scene
+                                .tokens
+                                .iter()
+                                .find(|token| token.id == token_id)
+                                .map(|token| (layout, token.clone()))
+                        }) else {
+                            return ().into_any();
+                        };
+                        let half_w = f64::from(token.width_cells) / 2.0;
+                        let half_h = f64::from(token.height_cells) / 2.0;
+                        let (start_cx, start_cy) = (
+                            f64::from(vm.token_drag_origin_x()) + half_w,
+                            f64::from(vm.token_drag_origin_y()) + half_h,
+                        );
+                        let (end_cx, end_cy) =
+                            (f64::from(token.x) + half_w, f64::from(token.y) + half_h);
+                        let (dcells, dfeet) = terrain_ruler_distance(
+                            start_cx,
+                            start_cy,
+                            end_cx,
+                            end_cy,
+                            layout.scene.grid.cell_size_feet,
+                            &layout.scene.terrain,
+                        );
+
+                        let cam_x = vm.camera_x.get();
+                        let cam_y = vm.camera_y.get();
+                        let zoom = vm.zoom.get();
+                        let vw = vm.viewport_width.get();
+                        let vh = vm.viewport_height.get();
+                        let to_screen = |cx: f64, cy: f64| {
+                            world_to_screen(
+                                layout.left() + cx * layout.cell_size,
+                                layout.top() + cy * layout.cell_size,
+                                vw, vh, cam_x, cam_y, zoom,
+                            )
+                        };
+                        let (ssx, ssy) = to_screen(start_cx, start_cy);
+                        let (sex, sey) = to_screen(end_cx, end_cy);
+
+                        view! {
+                            <RulerOverlay
+                                start_screen_x=ssx
+                                start_screen_y=ssy
+                                end_screen_x=sex
+                                end_screen_y=sey
+                                distance_cells=dcells
+                                distance_feet=dfeet
+                            />
+                        }.into_any()
+                    }}
+
                     // Remote board pointer trails: accumulated from cursor positions.
                     {move || {
                         let cam_x = vm.camera_x.get();
@@ -2275,11 +3214,75 @@ pub fn SceneBoard(
                         }).collect_view()
                     }}
 
+                    // Floor switcher for the game master (multi-floor scenes only)
+                    <BoardFloorSwitchers levels=levels theme=floor_switcher_theme.clone()/>
+
                     // Board toolbar (above workspace hint)
                     <BoardToolbar
                         active_tool=vm.active_tool
                         theme=toolbar_theme_main.clone()
                     />
+                    <CameraControls
+                        users=camera_users
+                        followed_user=followed_user
+                        is_game_master=viewer_steers_cameras
+                        locking=camera_locking
+                        locked=camera_locked.into()
+                        on_pull=Callback::new(move |_| {
+                            let view = camera_view(
+                                vm.camera_x.get_untracked(),
+                                vm.camera_y.get_untracked(),
+                                vm.zoom.get_untracked(),
+                            );
+                            send_event(
+                                &ws_sender,
+                                ClientEvent::BoardCamera(camera_payload(
+                                    &username.get_untracked(),
+                                    view,
+                                    BoardCameraMode::Pull,
+                                )),
+                            );
+                        })
+                        theme=toolbar_theme_camera
+                    />
+                    <SceneExportPanel
+                        scenes=board_scenes
+                        active_scene_id=active_scene_id.into()
+                        notes=board_notes
+                        file_urls=file_transfer.file_urls
+                        is_game_master=viewer_is_game_master
+                        state=export_state
+                        theme=toolbar_theme_export
+                    />
+                    <BoardMinimap
+                        scenes=board_scenes
+                        notes=board_notes
+                        active_scene_id=active_scene_id.into()
+                        locked=camera_locked.into()
+                        on_jump=jump_to_world_point
+                        on_fit_all=fit_all_scenes
+                        state=minimap
+                        theme=toolbar_theme_minimap
+                    />
+                    <Show when=move || board_canvas.shows_stats()>
+                        <RenderStatsPanel
+                            renderer=board_renderer.into()
+                            stats=board_canvas.stats
+                            results=board_canvas.benchmark_results
+                            running=board_canvas.benchmark_running.into()
+                            locked=camera_locked.into()
+                            on_benchmark=run_render_benchmark
+                            theme=render_stats_theme.clone()
+                        />
+                    </Show>
+                    <Show when=move || vm.active_tool.get() == BoardTool::Terrain>
+                        <TerrainPalette
+                            brush=vm.terrain_brush
+                            custom_color=vm.terrain_custom_color
+                            custom_label=vm.terrain_custom_label
+                            theme=toolbar_theme_terrain.clone()
+                        />
+                    </Show>
 
                     <Show when=move || show_workspace_hint.get()>
                         <WorkspaceHintCard
@@ -2308,13 +3311,23 @@ mod tests {
             body: "body".to_string(),
             created_at_ms: 1.0,
             updated_at_ms: 1.0,
-            board_position: Some(NoteBoardPosition { world_x, world_y }),
+            board_position: Some(NoteBoardPosition {
+                world_x,
+                world_y,
+                anchor: None,
+            }),
             board_style: NoteBoardStyle {
                 width_px: 100.0,
                 height_px: 100.0,
                 font_size_pt: 14.0,
                 color: "#F8EE96".to_string(),
+                pin_icon: None,
             },
+            folder: String::new(),
+            tags: Vec::new(),
+            pinned: false,
+            edited_by: String::new(),
+            revisions: Vec::new(),
         }
     }
 
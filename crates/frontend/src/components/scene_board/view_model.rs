@@ -1,5 +1,7 @@
-use super::model::BoardTool;
+use super::model::{BoardTool, TERRAIN_DEFAULT_CUSTOM_COLOR};
 use leptos::prelude::*;
+use shared::events::{TerrainCell, TerrainKind};
+use std::collections::HashMap;
 
 /// Reactive state for the scene board's camera, panning, and drag interactions.
 #[derive(Clone, Copy)]
@@ -37,6 +39,14 @@ pub struct SceneBoardViewModel {
     token_drag_origin_x: RwSignal<f32>,
     token_drag_origin_y: RwSignal<f32>,
 
+    // Token facing
+    /// Token last clicked on the board; shows the rotation handle and takes Q/E rotation.
+    pub focused_token_id: RwSignal<Option<String>>,
+    pub rotating_token_id: RwSignal<Option<String>>,
+    token_rotation_changed: RwSignal<bool>,
+    /// Token under the pointer; shows its range rings.
+    pub hovered_token_id: RwSignal<Option<String>>,
+
     pub pointer_local_x: RwSignal<f64>,
     pub pointer_local_y: RwSignal<f64>,
 
@@ -50,6 +60,18 @@ pub struct SceneBoardViewModel {
     /// Derived from `active_tool`; kept as a signal for external observers.
     #[allow(dead_code)]
     pub pointer_active: RwSignal<bool>,
+    /// Terrain brush kind; `None` erases painted cells.
+    pub terrain_brush: RwSignal<Option<TerrainKind>>,
+    pub terrain_custom_color: RwSignal<String>,
+    pub terrain_custom_label: RwSignal<String>,
+    /// Scene painted by the terrain stroke in progress, if any.
+    pub terrain_stroke_scene_id: RwSignal<Option<String>>,
+    terrain_stroke_changed: RwSignal<bool>,
+
+    // Floors
+    /// Floor the game master looks at per scene id; scenes missing here show
+    /// their base floor.
+    pub viewed_levels: RwSignal<HashMap<String, String>>,
 
     // Selection box
     pub is_selecting: RwSignal<bool>,
@@ -91,12 +113,22 @@ impl SceneBoardViewModel {
             token_drag_offset_y: RwSignal::new(0.0),
             token_drag_origin_x: RwSignal::new(0.0),
             token_drag_origin_y: RwSignal::new(0.0),
+            focused_token_id: RwSignal::new(None),
+            rotating_token_id: RwSignal::new(None),
+            token_rotation_changed: RwSignal::new(false),
+            hovered_token_id: RwSignal::new(None),
             pointer_local_x: RwSignal::new(0.0),
             pointer_local_y: RwSignal::new(0.0),
             active_tool: RwSignal::new(BoardTool::None),
             ruler_start: RwSignal::new(None),
             ruler_end: RwSignal::new(None),
             pointer_active: RwSignal::new(false),
+            terrain_brush: RwSignal::new(Some(TerrainKind::Difficult)),
+            terrain_custom_color: RwSignal::new(TERRAIN_DEFAULT_CUSTOM_COLOR.to_string()),
+            terrain_custom_label: RwSignal::new(String::new()),
+            terrain_stroke_scene_id: RwSignal::new(None),
+            terrain_stroke_changed: RwSignal::new(false),
+            viewed_levels: RwSignal::new(HashMap::new()),
             is_selecting: RwSignal::new(false),
             selection_start_x: RwSignal::new(0.0),
             selection_start_y: RwSignal::new(0.0),
@@ -217,11 +249,89 @@ impl SceneBoardViewModel {
         self.token_drag_origin_y.get_untracked()
     }
 
+    pub fn start_token_rotation(&self, token_id: String) {
+        self.focused_token_id.set(Some(token_id.clone()));
+        self.rotating_token_id.set(Some(token_id));
+        self.token_rotation_changed.set(false);
+    }
+
+    pub fn mark_token_rotation_changed(&self) {
+        self.token_rotation_changed.set(true);
+    }
+
+    /// Ends the rotation drag and returns the token id when its facing changed.
+    pub fn end_token_rotation(&self) -> Option<String> {
+        let token_id = self.rotating_token_id.get_untracked();
+        let changed = self.token_rotation_changed.get_untracked();
+        self.rotating_token_id.set(None);
+        self.token_rotation_changed.set(false);
+        token_id.filter(|_| changed)
+    }
+
+    /// Updates the hovered token, skipping the write while it stays the same
+    /// so pointer moves do not re-render the board.
+    pub fn set_hovered_token(&self, token_id: Option<String>) {
+        if self.hovered_token_id.get_untracked() != token_id {
+            self.hovered_token_id.set(token_id);
+        }
+    }
+
     pub fn update_pointer(&self, local_x: f64, local_y: f64) {
         self.pointer_local_x.set(local_x);
         self.pointer_local_y.set(local_y);
     }
 
+    /// Builds the cell painted by the current brush, or `None` when erasing.
+    pub fn terrain_brush_cell(&self, column: u16, row: u16) -> Option<TerrainCell> {
+        let kind = self.terrain_brush.get_untracked()?;
+        let (color, label) = if kind == TerrainKind::Custom {
+            let label = self.terrain_custom_label.get_untracked().trim().to_string();
+            (
+                Some(self.terrain_custom_color.get_untracked()),
+                (!label.is_empty()).then_some(label),
+            )
+        } else {
+            (None, None)
+        };
+        Some(TerrainCell {
+            column,
+            row,
+            kind,
+            color,
+            label,
+        })
+    }
+
+    /// Shows `level_id` of a scene to the game master; `None` is the base floor.
+    pub fn view_level(&self, scene_id: &str, level_id: Option<String>) {
+        self.viewed_levels.update(|levels| match level_id {
+            Some(level_id) => {
+                levels.insert(scene_id.to_string(), level_id);
+            }
+            None => {
+                levels.remove(scene_id);
+            }
+        });
+    }
+
+    pub fn start_terrain_stroke(&self, scene_id: String) {
+        self.terrain_stroke_scene_id.set(Some(scene_id));
+        self.terrain_stroke_changed.set(false);
+    }
+
+    pub fn mark_terrain_stroke_changed(&self) {
+        self.terrain_stroke_changed.set(true);
+    }
+
+    /// Ends the stroke and returns the painted scene id when anything changed.
+    pub fn end_terrain_stroke(&self) -> Option<String> {
+        let scene_id = self.terrain_stroke_scene_id.get_untracked();
+        let changed = self.terrain_stroke_changed.get_untracked();
+        self.terrain_stroke_scene_id.set(None);
+        self.terrain_stroke_changed.set(false);
+        scene_id.filter(|_| changed)
+    }
+
     pub fn drag_origin_scene_x(&self) -> f64 {
         self.drag_origin_scene_x.get_untracked()
     }
@@ -322,4 +432,66 @@ mod tests {
             assert_eq!(vm.token_drag_origin_y(), 4.0);
         });
     }
+
+    #[test]
+    fn token_rotation_focuses_token_and_reports_changes() {
+        let owner = Owner::new();
+        owner.with(|| {
+            let vm = make_vm();
+            vm.start_token_rotation("token-1".into());
+            assert_eq!(vm.focused_token_id.get_untracked(), Some("token-1".into()));
+            assert_eq!(vm.end_token_rotation(), None);
+
+            vm.start_token_rotation("token-1".into());
+            vm.mark_token_rotation_changed();
+            assert_eq!(vm.end_token_rotation(), Some("token-1".into()));
+            assert_eq!(vm.rotating_token_id.get_untracked(), None);
+            assert_eq!(vm.focused_token_id.get_untracked(), Some("token-1".into()));
+        });
+    }
+
+    #[test]
+    fn terrain_stroke_reports_scene_only_when_changed() {
+        let owner = Owner::new();
+        owner.with(|| {
+            let vm = make_vm();
+            vm.start_terrain_stroke("scene-1".into());
+            assert_eq!(vm.end_terrain_stroke(), None);
+
+            vm.start_terrain_stroke("scene-1".into());
+            vm.mark_terrain_stroke_changed();
+            assert_eq!(vm.end_terrain_stroke(), Some("scene-1".into()));
+            assert_eq!(vm.terrain_stroke_scene_id.get_untracked(), None);
+        });
+    }
+
+    #[test]
+    fn terrain_brush_cell_uses_custom_color_and_label() {
+        let owner = Owner::new();
+        owner.with(|| {
+            let vm = make_vm();
+            vm.terrain_brush.set(Some(TerrainKind::Custom));
+            vm.terrain_custom_color.set("#00ff00".into());
+            vm.terrain_custom_label.set("  Lava  ".into());
+            let cell = vm.terrain_brush_cell(2, 3).expect("custom brush paints");
+            assert_eq!((cell.column, cell.row), (2, 3));
+            assert_eq!(cell.color.as_deref(), Some("#00ff00"));
+            assert_eq!(cell.label.as_deref(), Some("Lava"));
+
+            vm.terrain_brush.set(None);
+            assert!(vm.terrain_brush_cell(2, 3).is_none());
+        });
+    }
+
+    #[test]
+    fn hovered_token_tracks_pointer_target() {
+        let owner = Owner::new();
+        owner.with(|| {
+            let vm = make_vm();
+            vm.set_hovered_token(Some("token-1".into()));
+            assert_eq!(vm.hovered_token_id.get_untracked(), Some("token-1".into()));
+            vm.set_hovered_token(None);
+            assert_eq!(vm.hovered_token_id.get_untracked(), None);
+        });
+    }
 }
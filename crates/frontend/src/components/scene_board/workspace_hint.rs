@@ -1,3 +1,4 @@
+use super::scene_geometry::stop_pointer_propagation;
 use crate::config::Theme;
 use crate::i18n::i18n::{t, t_string, use_i18n};
 use leptos::ev::MouseEvent;
@@ -14,6 +15,7 @@ pub fn WorkspaceHintCard(
     view! {
         <div
             on:mousedown=move |event: MouseEvent| event.stop_propagation()
+            on:pointerdown=stop_pointer_propagation
             on:click=move |event: MouseEvent| event.stop_propagation()
             style=format!(
                 "position: absolute; right: 1rem; bottom: 1rem; display: flex; flex-direction: column; gap: 0.65rem; \
@@ -50,7 +52,11 @@ pub fn WorkspaceHintCard(
                 <span>{t!(i18n, scene_board.board_select)}</span>
                 <span>{t!(i18n, scene_board.board_drag)}</span>
                 <span>{t!(i18n, scene_board.token_place)}</span>
+                <span>{t!(i18n, scene_board.token_rotate)}</span>
+                <span>{t!(i18n, scene_board.token_range)}</span>
                 <span>{t!(i18n, scene_board.inactive_blur)}</span>
+                <span>{t!(i18n, scene_board.terrain_paint)}</span>
+                <span>{t!(i18n, scene_board.stairs_move)}</span>
             </div>
         </div>
     }
@@ -1,9 +1,11 @@
 // Full-screen background fit editor modal for a scene.
 // Shown when the user wants to adjust scale/position/rotation of a scene background.
 
+use super::background_pixels::load_background_pixels;
+use super::grid_detect::{GRID_DETECT_MAX_SIDE_PX, detect_grid, grid_cells};
 use super::model::{
     MAX_BACKGROUND_ROTATION_DEG, MAX_BACKGROUND_SCALE, MIN_BACKGROUND_ROTATION_DEG,
-    MIN_BACKGROUND_SCALE, fit_preview_layout,
+    MIN_BACKGROUND_SCALE, background_fit_for_grid, fit_preview_layout,
 };
 use super::view_model::ScenesWindowViewModel;
 use crate::components::websocket::{FileTransferStage, FileTransferState};
@@ -11,6 +13,7 @@ use crate::config::Theme;
 use crate::i18n::i18n::{t, t_string, use_i18n};
 use leptos::ev::MouseEvent;
 use leptos::prelude::*;
+use leptos::task::spawn_local;
 use shared::events::Scene;
 
 const TITLE_FONT_SIZE: &str = "clamp(1rem, 0.95rem + 0.22vw, 1.18rem)";
@@ -47,6 +50,74 @@ pub fn BackgroundFitEditor(
 ) -> impl IntoView {
     let i18n = use_i18n();
 
+    // Looks for a printed grid on the background and proposes columns, rows
+    // and a fit that lines it up with the scene grid. The GM can still tweak
+    // or reset the result before saving.
+    let auto_fit_grid = {
+        let file_transfer = file_transfer.clone();
+        move |_| {
+            let Some(url) = vm.draft_background.get_untracked().and_then(|background| {
+                file_transfer
+                    .file_urls
+                    .get_untracked()
+                    .get(&background.hash)
+                    .cloned()
+            }) else {
+                return;
+            };
+            let found = t_string!(i18n, scenes.auto_fit_grid_result).to_string();
+            let failed = t_string!(i18n, scenes.auto_fit_grid_failed).to_string();
+            vm.is_detecting_grid.set(true);
+            vm.grid_detect_status.set(None);
+            spawn_local(async move {
+                let pixels = load_background_pixels(&url, GRID_DETECT_MAX_SIDE_PX).await;
+                vm.is_detecting_grid.set(false);
+                let pixels = match pixels {
+                    Ok(pixels) => pixels,
+                    Err(error) => {
+                        log::warn!("grid detection failed: {error}");
+                        vm.grid_detect_status.set(Some(failed));
+                        return;
+                    }
+                };
+                let Some(estimate) =
+                    detect_grid(&pixels.rgba, pixels.width as usize, pixels.height as usize)
+                else {
+                    vm.grid_detect_status.set(Some(failed));
+                    return;
+                };
+                // Back from the analysed size to the image's own pixels.
+                let natural_width = f64::from(pixels.natural_width);
+                let natural_height = f64::from(pixels.natural_height);
+                let factor = natural_width / f64::from(pixels.width);
+                let cell_px = estimate.cell_px * factor;
+                let phase_x = estimate.phase_x * factor;
+                let phase_y = estimate.phase_y * factor;
+                let columns = grid_cells(natural_width, phase_x, cell_px);
+                let rows = grid_cells(natural_height, phase_y, cell_px);
+                let fit = background_fit_for_grid(
+                    natural_width,
+                    natural_height,
+                    cell_px,
+                    phase_x,
+                    phase_y,
+                    columns,
+                    rows,
+                );
+                vm.draft_columns.set(columns.to_string());
+                vm.draft_rows.set(rows.to_string());
+                vm.draft_background_scale.set(fit.scale);
+                vm.draft_background_offset_x.set(fit.offset_x);
+                vm.draft_background_offset_y.set(fit.offset_y);
+                vm.draft_background_rotation_deg.set(0.0);
+                vm.grid_detect_status.set(Some(format!(
+                    "{found}: {cell_px:.1}px, {columns} x {rows} ({:.0}%)",
+                    estimate.confidence * 100.0
+                )));
+            });
+        }
+    };
+
     view! {
         <div style="position: fixed; inset: 0; background: rgba(5,10,18,0.72); backdrop-filter: blur(6px); z-index: 2200; display: flex; align-items: stretch; justify-content: stretch;">
             <div style="flex: 1; display: flex; align-items: stretch; justify-content: stretch;"
@@ -339,6 +410,30 @@ pub fn BackgroundFitEditor(
                             </div>
                         </label>
 
+                        // Grid detection
+                        <div style="display: flex; gap: 0.75rem; align-items: center; flex-wrap: wrap;">
+                            <button type="button"
+                                on:click=auto_fit_grid
+                                disabled=move || vm.is_detecting_grid.get() || vm.draft_background.get().is_none()
+                                style=format!(
+                                    "padding: 0.75rem 1rem; background: {}; color: {}; border: none; border-radius: 0.5rem; cursor: pointer; font-size: {};",
+                                    theme.ui_button_primary, theme.ui_text_primary, BUTTON_FONT_SIZE
+                                )
+                            >
+                                {move || if vm.is_detecting_grid.get() {
+                                    t_string!(i18n, scenes.auto_fit_grid_running).to_string()
+                                } else {
+                                    t_string!(i18n, scenes.auto_fit_grid_button).to_string()
+                                }}
+                            </button>
+                            <div style=format!(
+                                "color: {}; font-size: {}; line-height: 1.45;",
+                                theme.ui_text_muted, META_FONT_SIZE
+                            )>
+                                {move || vm.grid_detect_status.get().unwrap_or_default()}
+                            </div>
+                        </div>
+
                         // Footer buttons
                         <div style=format!("margin-top: auto; display: flex; gap: 0.75rem; justify-content: flex-end; padding-top: 0.5rem; border-top: 0.0625rem solid {}; flex-wrap: wrap;", theme.ui_border)>
                             <button type="button"
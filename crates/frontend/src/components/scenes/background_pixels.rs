@@ -0,0 +1,66 @@
+use leptos::wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement, HtmlImageElement};
+
+/// RGBA pixels of a background, downscaled so its longest side fits the
+/// analysis limit. `natural_*` is the size of the original image.
+pub struct BackgroundPixels {
+    pub rgba: Vec<u8>,
+    pub width: u32,
+    pub height: u32,
+    pub natural_width: u32,
+    pub natural_height: u32,
+}
+
+/// Decodes the image behind `url` and reads its pixels through a canvas.
+pub async fn load_background_pixels(url: &str, max_side: u32) -> Result<BackgroundPixels, String> {
+    let image =
+        HtmlImageElement::new().map_err(|error| format!("failed to create image: {error:?}"))?;
+    image.set_src(url);
+    JsFuture::from(image.decode())
+        .await
+        .map_err(|error| format!("failed to decode background: {error:?}"))?;
+    let (natural_width, natural_height) = (image.natural_width(), image.natural_height());
+    if natural_width == 0 || natural_height == 0 {
+        return Err("background image is empty".to_string());
+    }
+    let ratio = (f64::from(max_side) / f64::from(natural_width.max(natural_height))).min(1.0);
+    let width = ((f64::from(natural_width) * ratio).round() as u32).max(1);
+    let height = ((f64::from(natural_height) * ratio).round() as u32).max(1);
+
+    let document = web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or_else(|| "document is unavailable".to_string())?;
+    let canvas: HtmlCanvasElement = document
+        .create_element("canvas")
+        .map_err(|error| format!("failed to create canvas: {error:?}"))?
+        .dyn_into()
+        .map_err(|_| "canvas element has an unexpected type".to_string())?;
+    canvas.set_width(width);
+    canvas.set_height(height);
+    let context: CanvasRenderingContext2d = canvas
+        .get_context("2d")
+        .map_err(|error| format!("failed to get canvas context: {error:?}"))?
+        .ok_or_else(|| "canvas 2d context is unavailable".to_string())?
+        .dyn_into()
+        .map_err(|_| "canvas context has an unexpected type".to_string())?;
+    context
+        .draw_image_with_html_image_element_and_dw_and_dh(
+            &image,
+            0.0,
+            0.0,
+            f64::from(width),
+            f64::from(height),
+        )
+        .map_err(|error| format!("failed to draw background: {error:?}"))?;
+    let data = context
+        .get_image_data(0.0, 0.0, f64::from(width), f64::from(height))
+        .map_err(|error| format!("failed to read background pixels: {error:?}"))?;
+    Ok(BackgroundPixels {
+        rgba: data.data().0,
+        width,
+        height,
+        natural_width,
+        natural_height,
+    })
+}
@@ -0,0 +1,270 @@
+// Pure grid detection for scene backgrounds: finds the cell size and phase of
+// a map's printed grid from the periodicity of its edges.
+// No signals, no Leptos, no web_sys.
+
+// --- Constants ---
+
+/// Longest image side analysed; bigger backgrounds are downscaled first.
+pub const GRID_DETECT_MAX_SIDE_PX: u32 = 2048;
+const MIN_CELL_PX: usize = 8;
+const MAX_CELL_PX: usize = 512;
+/// Autocorrelation below this is treated as "no grid".
+const MIN_CONFIDENCE: f64 = 0.15;
+/// The shortest period scoring this share of the strongest one wins.
+const HARMONIC_SHARE: f64 = 0.8;
+/// Horizontal and vertical periods this close are one square grid.
+const SQUARE_TOLERANCE: f64 = 0.08;
+
+// --- Types ---
+
+/// A detected grid in image pixels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GridEstimate {
+    pub cell_px: f64,
+    /// Position of the first vertical grid line, `0..cell_px`.
+    pub phase_x: f64,
+    /// Position of the first horizontal grid line, `0..cell_px`.
+    pub phase_y: f64,
+    /// Periodicity strength in `0..=1`.
+    pub confidence: f64,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct AxisPeriod {
+    period: f64,
+    score: f64,
+}
+
+// --- Pure functions ---
+
+/// Estimates the square grid printed on an RGBA image. `None` when neither
+/// axis shows a clear period.
+pub fn detect_grid(rgba: &[u8], width: usize, height: usize) -> Option<GridEstimate> {
+    if width < MIN_CELL_PX * 2 || height < MIN_CELL_PX * 2 || rgba.len() < width * height * 4 {
+        return None;
+    }
+    let (profile_x, profile_y) = edge_profiles(rgba, width, height);
+    let period_x = axis_period(&profile_x);
+    let period_y = axis_period(&profile_y);
+    let (cell_px, confidence) = match (period_x, period_y) {
+        (Some(x), Some(y))
+            if (x.period - y.period).abs() <= x.period.max(y.period) * SQUARE_TOLERANCE =>
+        {
+            let weight = x.score + y.score;
+            (
+                (x.period * x.score + y.period * y.score) / weight,
+                weight / 2.0,
+            )
+        }
+        (Some(x), Some(y)) if y.score > x.score => (y.period, y.score / 2.0),
+        (Some(x), Some(_)) => (x.period, x.score / 2.0),
+        (Some(axis), None) | (None, Some(axis)) => (axis.period, axis.score / 2.0),
+        (None, None) => return None,
+    };
+    if confidence < MIN_CONFIDENCE {
+        return None;
+    }
+    Some(GridEstimate {
+        cell_px,
+        phase_x: grid_phase(&profile_x, cell_px),
+        phase_y: grid_phase(&profile_y, cell_px),
+        confidence: confidence.min(1.0),
+    })
+}
+
+/// Cells of `cell_px` between the first grid line and the image edge, rounded
+/// and clamped to `1..=200`.
+pub fn grid_cells(extent_px: f64, phase_px: f64, cell_px: f64) -> u16 {
+    (((extent_px - phase_px) / cell_px.max(1.0)).round() as i64).clamp(1, 200) as u16
+}
+
+/// Sums of absolute luminance steps per column (`x`) and per row (`y`):
+/// grid lines show up as regularly spaced peaks.
+fn edge_profiles(rgba: &[u8], width: usize, height: usize) -> (Vec<f64>, Vec<f64>) {
+    let luminance = rgba
+        .chunks_exact(4)
+        .take(width * height)
+        .map(|pixel| {
+            (u32::from(pixel[0]) * 299 + u32::from(pixel[1]) * 587 + u32::from(pixel[2]) * 114)
+                as i32
+                / 1000
+        })
+        .collect::<Vec<_>>();
+    let mut profile_x = vec![0.0; width];
+    let mut profile_y = vec![0.0; height];
+    for y in 0..height {
+        let row = &luminance[y * width..(y + 1) * width];
+        for x in 1..width {
+            profile_x[x] += f64::from((row[x] - row[x - 1]).abs());
+            if y > 0 {
+                let above = luminance[(y - 1) * width + x];
+                profile_y[y] += f64::from((row[x] - above).abs());
+            }
+        }
+    }
+    (profile_x, profile_y)
+}
+
+/// Strongest period of a profile by normalised autocorrelation, preferring
+/// the fundamental over its multiples and refined to sub-pixel precision.
+fn axis_period(profile: &[f64]) -> Option<AxisPeriod> {
+    let max_lag = (profile.len() / 2).min(MAX_CELL_PX);
+    if max_lag <= MIN_CELL_PX {
+        return None;
+    }
+    // Grid lines drawn at fractional cell sizes land a pixel early or late;
+    // a light blur keeps them correlating.
+    let profile = (0..profile.len())
+        .map(|index| {
+            let at = |offset: isize| {
+                profile
+                    .get(index.wrapping_add_signed(offset))
+                    .copied()
+                    .unwrap_or(profile[index])
+            };
+            (at(-1) + 2.0 * at(0) + at(1)) / 4.0
+        })
+        .collect::<Vec<_>>();
+    let mean = profile.iter().sum::<f64>() / profile.len() as f64;
+    let centred = profile.iter().map(|value| value - mean).collect::<Vec<_>>();
+    let variance = centred.iter().map(|value| value * value).sum::<f64>() / centred.len() as f64;
+    if variance <= f64::EPSILON {
+        return None;
+    }
+    let correlation = |lag: usize| {
+        let pairs = centred.len() - lag;
+        centred
+            .iter()
+            .zip(&centred[lag..])
+            .map(|(a, b)| a * b)
+            .sum::<f64>()
+            / pairs as f64
+            / variance
+    };
+    let scores = (0..=max_lag + 1)
+        .map(|lag| {
+            if lag < MIN_CELL_PX - 1 || lag >= centred.len() {
+                0.0
+            } else {
+                correlation(lag)
+            }
+        })
+        .collect::<Vec<_>>();
+    // Multiples of the cell size correlate as well as the cell itself, so the
+    // first local peak close to the strongest one is the fundamental.
+    let strongest = (MIN_CELL_PX..=max_lag)
+        .map(|lag| scores[lag])
+        .fold(f64::MIN, f64::max);
+    let best = (MIN_CELL_PX..=max_lag).find(|lag| {
+        let score = scores[*lag];
+        score >= strongest * HARMONIC_SHARE && score >= scores[lag - 1] && score >= scores[lag + 1]
+    })?;
+    let score = scores[best];
+    if score <= 0.0 {
+        return None;
+    }
+    // Parabolic peak interpolation, then average over multiples of the
+    // period for a more precise estimate on large images.
+    let (before, after) = (scores[best - 1], scores[best + 1]);
+    let curvature = before - 2.0 * score + after;
+    let shift = if curvature < 0.0 {
+        (0.5 * (before - after) / curvature).clamp(-0.5, 0.5)
+    } else {
+        0.0
+    };
+    let mut period = best as f64 + shift;
+    let multiple = (max_lag as f64 / period).floor() as usize;
+    if multiple >= 2 {
+        let lag = (period * multiple as f64).round() as usize;
+        let refined = (lag.saturating_sub(multiple)..=(lag + multiple).min(max_lag))
+            .filter(|lag| *lag >= MIN_CELL_PX)
+            .max_by(|a, b| scores[*a].total_cmp(&scores[*b]));
+        if let Some(refined) = refined
+            && scores[refined] >= score * HARMONIC_SHARE
+        {
+            period = refined as f64 / multiple as f64;
+        }
+    }
+    Some(AxisPeriod { period, score })
+}
+
+/// Offset in `0..cell_px` whose comb of grid positions collects the most edges.
+fn grid_phase(profile: &[f64], cell_px: f64) -> f64 {
+    let steps = cell_px.ceil() as usize;
+    (0..steps)
+        .map(|phase| {
+            let mut total = 0.0;
+            let mut position = phase as f64;
+            while position < profile.len() as f64 {
+                let index = position.round() as usize;
+                total += profile.get(index).copied().unwrap_or(0.0);
+                position += cell_px;
+            }
+            (phase, total)
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(phase, _)| phase as f64)
+        .unwrap_or(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A textured background with dark grid lines every `cell` pixels.
+    fn grid_image(width: usize, height: usize, cell: f64, phase: f64) -> Vec<u8> {
+        let mut seed = 0x2545_f491_u32;
+        let mut rgba = Vec::with_capacity(width * height * 4);
+        let on_line = |position: usize| {
+            let offset = (position as f64 - phase).rem_euclid(cell);
+            offset < 1.0 || cell - offset < 0.5
+        };
+        for y in 0..height {
+            for x in 0..width {
+                seed ^= seed << 13;
+                seed ^= seed >> 17;
+                seed ^= seed << 5;
+                let noise = (seed % 40) as u8;
+                let value = if on_line(x) || on_line(y) {
+                    30 + noise / 2
+                } else {
+                    150 + noise + ((x / 3 + y / 5) % 20) as u8
+                };
+                rgba.extend_from_slice(&[value, value, value, 255]);
+            }
+        }
+        rgba
+    }
+
+    #[test]
+    fn finds_cell_size_and_phase_of_a_printed_grid() {
+        let rgba = grid_image(640, 480, 37.0, 11.0);
+        let estimate = detect_grid(&rgba, 640, 480).unwrap();
+        assert!((estimate.cell_px - 37.0).abs() < 0.3, "{estimate:?}");
+        assert!((estimate.phase_x - 11.0).abs() <= 1.0, "{estimate:?}");
+        assert!((estimate.phase_y - 11.0).abs() <= 1.0, "{estimate:?}");
+        assert!(estimate.confidence > 0.3);
+        assert_eq!(grid_cells(640.0, estimate.phase_x, estimate.cell_px), 17);
+    }
+
+    #[test]
+    fn fractional_cells_are_estimated_to_sub_pixel_precision() {
+        let rgba = grid_image(1000, 600, 70.4, 0.0);
+        let estimate = detect_grid(&rgba, 1000, 600).unwrap();
+        assert!((estimate.cell_px - 70.4).abs() < 0.25, "{estimate:?}");
+    }
+
+    #[test]
+    fn plain_images_have_no_grid() {
+        let mut seed = 7_u32;
+        let rgba = (0..320 * 240)
+            .flat_map(|_| {
+                seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+                let value = (seed >> 24) as u8;
+                [value, value, value, 255]
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(detect_grid(&rgba, 320, 240), None);
+        assert_eq!(detect_grid(&[0; 16], 2, 2), None);
+    }
+}
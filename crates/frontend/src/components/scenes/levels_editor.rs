@@ -0,0 +1,395 @@
+// Floors and stairs section of the scene editor.
+// Edits the draft floors of the selected scene; nothing is sent until the scene is saved.
+
+use super::model::{
+    FILE_INPUT_ACCEPT, MAX_BACKGROUND_OFFSET_PX, MAX_BACKGROUND_SCALE, MAX_SCENE_LEVELS,
+    MAX_SCENE_STAIRS, MIN_BACKGROUND_OFFSET_PX, MIN_BACKGROUND_SCALE,
+};
+use super::view_model::ScenesWindowViewModel;
+use crate::components::websocket::{FileTransferState, WsSender};
+use crate::config::Theme;
+use crate::i18n::i18n::{t, t_string, use_i18n};
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use leptos::wasm_bindgen::JsCast;
+use shared::events::{SceneLevel, SceneStairs};
+use uuid::Uuid;
+use web_sys::{Event, HtmlInputElement};
+
+const BODY_FONT_SIZE: &str = "clamp(0.9rem, 0.87rem + 0.12vw, 0.98rem)";
+const META_FONT_SIZE: &str = "clamp(0.74rem, 0.71rem + 0.12vw, 0.82rem)";
+const BUTTON_FONT_SIZE: &str = "clamp(0.84rem, 0.81rem + 0.12vw, 0.94rem)";
+
+/// Parses a 1-based cell number typed by the GM into a 0-based grid index.
+fn parse_cell(value: &str) -> Option<u16> {
+    value
+        .trim()
+        .parse::<u16>()
+        .ok()
+        .map(|cell| cell.saturating_sub(1))
+}
+
+/// Floor picker value: the base floor is the empty string.
+fn level_from_value(value: String) -> Option<String> {
+    (!value.is_empty()).then_some(value)
+}
+
+#[component]
+pub fn LevelsEditor(
+    vm: ScenesWindowViewModel,
+    file_transfer: FileTransferState,
+    ws_sender: ReadSignal<Option<WsSender>>,
+    username: ReadSignal<String>,
+    theme: Theme,
+) -> impl IntoView {
+    let i18n = use_i18n();
+
+    let input_style = format!(
+        "padding: 0.45rem; background: {}; color: {}; border: 0.0625rem solid {}; border-radius: 0.5rem; font-size: {}; min-width: 0;",
+        theme.ui_bg_secondary, theme.ui_text_primary, theme.ui_border, META_FONT_SIZE
+    );
+    let button_style = format!(
+        "padding: 0.45rem 0.75rem; background: {}; color: {}; border: none; border-radius: 0.5rem; cursor: pointer; font-size: {};",
+        theme.ui_bg_secondary, theme.ui_text_primary, BUTTON_FONT_SIZE
+    );
+    let primary_button_style = format!(
+        "padding: 0.45rem 0.75rem; background: {}; color: {}; border: none; border-radius: 0.5rem; cursor: pointer; font-size: {};",
+        theme.ui_button_primary, theme.ui_text_primary, BUTTON_FONT_SIZE
+    );
+    let meta_style = format!(
+        "color: {}; font-size: {};",
+        theme.ui_text_secondary, META_FONT_SIZE
+    );
+
+    let on_level_background_selected = {
+        let file_transfer = file_transfer.clone();
+        move |level_id: String, event: Event| {
+            let Some(input) = event
+                .target()
+                .and_then(|t| t.dyn_into::<HtmlInputElement>().ok())
+            else {
+                return;
+            };
+            let Some(file) = input.files().and_then(|files| files.get(0)) else {
+                return;
+            };
+            let file_transfer = file_transfer.clone();
+            let uname = username.get_untracked();
+            let sender = ws_sender.get_untracked();
+            spawn_local(async move {
+                match file_transfer.import_browser_file(file, uname, sender).await {
+                    Ok(file_ref) if file_ref.mime_type.starts_with("image/") => {
+                        vm.update_level(&level_id, |level| {
+                            level.background = Some(file_ref);
+                            level.background_scale = 1.0;
+                            level.background_offset_x = 0.0;
+                            level.background_offset_y = 0.0;
+                            level.background_rotation_deg = 0.0;
+                        });
+                        vm.editor_error.set(None);
+                    }
+                    Ok(_) => vm
+                        .editor_error
+                        .set(Some("Scene background must be an image".to_string())),
+                    Err(e) => vm.editor_error.set(Some(e)),
+                }
+            });
+            input.set_value("");
+        }
+    };
+
+    let add_level = move |_| {
+        let count = vm.draft_levels.get_untracked().len();
+        if count >= MAX_SCENE_LEVELS {
+            return;
+        }
+        vm.add_level(
+            Uuid::new_v4().to_string(),
+            format!(
+                "{} {}",
+                t_string!(i18n, scenes.level_default_name),
+                count + 2
+            ),
+        );
+    };
+
+    let add_stairs = move |_| {
+        if vm.draft_stairs.get_untracked().len() < MAX_SCENE_STAIRS {
+            vm.add_stairs(Uuid::new_v4().to_string());
+        }
+    };
+
+    // Options of a floor picker with `selected` preselected.
+    let level_options = move |levels: &[SceneLevel], selected: Option<&str>| {
+        let base = view! {
+            <option value="" selected=selected.is_none()>
+                {t_string!(i18n, scenes.base_level)}
+            </option>
+        };
+        let extra = levels
+            .iter()
+            .map(|level| {
+                view! {
+                    <option value=level.id.clone() selected=selected == Some(level.id.as_str())>
+                        {level.name.clone()}
+                    </option>
+                }
+            })
+            .collect_view();
+        (base, extra)
+    };
+
+    let level_rows = {
+        let input_style = input_style.clone();
+        let button_style = button_style.clone();
+        let meta_style = meta_style.clone();
+        move || {
+            vm.draft_levels
+                .get()
+                .into_iter()
+                .map(|level| {
+                    let on_background_selected = on_level_background_selected.clone();
+                    let upload_level_id = level.id.clone();
+                    let name_level_id = level.id.clone();
+                    let clear_level_id = level.id.clone();
+                    let scale_level_id = level.id.clone();
+                    let offset_x_level_id = level.id.clone();
+                    let offset_y_level_id = level.id.clone();
+                    let remove_level_id = level.id.clone();
+                    view! {
+                        <div style=format!(
+                            "display: flex; flex-direction: column; gap: 0.45rem; padding: 0.6rem; border: 0.0625rem solid {}; border-radius: 0.5rem;",
+                            theme.ui_border
+                        )>
+                            <div style="display: flex; gap: 0.5rem; align-items: center;">
+                                <input type="text" maxlength="64"
+                                    placeholder=move || t_string!(i18n, scenes.level_name_placeholder)
+                                    prop:value=level.name.clone()
+                                    on:change=move |ev| {
+                                        let name = event_target_value(&ev).trim().chars().take(64).collect::<String>();
+                                        if !name.is_empty() {
+                                            vm.update_level(&name_level_id, |level| level.name = name);
+                                        }
+                                    }
+                                    style=format!("{} flex: 1; font-size: {};", input_style, BODY_FONT_SIZE)
+                                />
+                                <button type="button"
+                                    on:click=move |_| vm.remove_level(&remove_level_id)
+                                    style=button_style.clone()
+                                >
+                                    {move || t!(i18n, scenes.level_remove_button)}
+                                </button>
+                            </div>
+                            <div style="display: flex; gap: 0.5rem; align-items: center; flex-wrap: wrap;">
+                                <span style=meta_style.clone()>
+                                    {match &level.background {
+                                        Some(file_ref) => file_ref.file_name.clone(),
+                                        None => t_string!(i18n, scenes.background_empty).to_string(),
+                                    }}
+                                </span>
+                                <label style=format!("{} display: inline-flex;", button_style)>
+                                    {move || t!(i18n, scenes.background_upload_button)}
+                                    <input type="file"
+                                        accept=FILE_INPUT_ACCEPT
+                                        on:change=move |ev| on_background_selected(upload_level_id.clone(), ev)
+                                        style="display: none;"
+                                    />
+                                </label>
+                                {level.background.is_some().then(|| view! {
+                                    <button type="button"
+                                        on:click=move |_| vm.update_level(&clear_level_id, |level| level.background = None)
+                                        style=button_style.clone()
+                                    >
+                                        {move || t!(i18n, scenes.background_remove_button)}
+                                    </button>
+                                })}
+                            </div>
+                            <div style="display: grid; grid-template-columns: repeat(3, minmax(0, 1fr)); gap: 0.5rem;">
+                                <label style=format!("display: flex; flex-direction: column; gap: 0.25rem; {}", meta_style)>
+                                    <span>{move || t!(i18n, scenes.background_scale_label)}</span>
+                                    <input type="number" step="0.01"
+                                        min=MIN_BACKGROUND_SCALE max=MAX_BACKGROUND_SCALE
+                                        prop:value=level.background_scale.to_string()
+                                        on:change=move |ev| {
+                                            if let Ok(scale) = event_target_value(&ev).parse::<f32>() {
+                                                vm.update_level(&scale_level_id, |level| {
+                                                    level.background_scale = scale.clamp(MIN_BACKGROUND_SCALE, MAX_BACKGROUND_SCALE);
+                                                });
+                                            }
+                                        }
+                                        style=input_style.clone()
+                                    />
+                                </label>
+                                <label style=format!("display: flex; flex-direction: column; gap: 0.25rem; {}", meta_style)>
+                                    <span>{move || t!(i18n, scenes.background_offset_x_label)}</span>
+                                    <input type="number" step="1"
+                                        prop:value=level.background_offset_x.to_string()
+                                        on:change=move |ev| {
+                                            if let Ok(offset) = event_target_value(&ev).parse::<f32>() {
+                                                vm.update_level(&offset_x_level_id, |level| {
+                                                    level.background_offset_x = offset.clamp(MIN_BACKGROUND_OFFSET_PX, MAX_BACKGROUND_OFFSET_PX);
+                                                });
+                                            }
+                                        }
+                                        style=input_style.clone()
+                                    />
+                                </label>
+                                <label style=format!("display: flex; flex-direction: column; gap: 0.25rem; {}", meta_style)>
+                                    <span>{move || t!(i18n, scenes.background_offset_y_label)}</span>
+                                    <input type="number" step="1"
+                                        prop:value=level.background_offset_y.to_string()
+                                        on:change=move |ev| {
+                                            if let Ok(offset) = event_target_value(&ev).parse::<f32>() {
+                                                vm.update_level(&offset_y_level_id, |level| {
+                                                    level.background_offset_y = offset.clamp(MIN_BACKGROUND_OFFSET_PX, MAX_BACKGROUND_OFFSET_PX);
+                                                });
+                                            }
+                                        }
+                                        style=input_style.clone()
+                                    />
+                                </label>
+                            </div>
+                        </div>
+                    }
+                })
+                .collect_view()
+        }
+    };
+
+    let stairs_rows = {
+        let input_style = input_style.clone();
+        let button_style = button_style.clone();
+        let meta_style = meta_style.clone();
+        move || {
+            let levels = vm.draft_levels.get();
+            vm.draft_stairs
+                .get()
+                .into_iter()
+                .map(|stairs| {
+                    let (from_base, from_extra) = level_options(&levels, stairs.level_id.as_deref());
+                    let (to_base, to_extra) = level_options(&levels, stairs.target_level_id.as_deref());
+                    let id = stairs.id.clone();
+                    let (from_id, column_id, row_id) = (id.clone(), id.clone(), id.clone());
+                    let (to_id, target_column_id, target_row_id) = (id.clone(), id.clone(), id.clone());
+                    let (label_id, remove_id) = (id.clone(), id);
+                    let cell_input = {
+                        let input_style = input_style.clone();
+                        move |value: u16, stairs_id: String, apply: fn(&mut SceneStairs, u16)| {
+                            view! {
+                                <input type="number" min="1" max="200"
+                                    prop:value=(value + 1).to_string()
+                                    on:change=move |ev| {
+                                        if let Some(cell) = parse_cell(&event_target_value(&ev)) {
+                                            vm.update_stairs(&stairs_id, |stairs| apply(stairs, cell));
+                                        }
+                                    }
+                                    style=format!("{} width: 4rem;", input_style)
+                                />
+                            }
+                        }
+                    };
+                    view! {
+                        <div style=format!(
+                            "display: flex; flex-direction: column; gap: 0.45rem; padding: 0.6rem; border: 0.0625rem solid {}; border-radius: 0.5rem;",
+                            theme.ui_border
+                        )>
+                            <div style="display: flex; gap: 0.4rem; align-items: center; flex-wrap: wrap;">
+                                <span style=meta_style.clone()>{move || t!(i18n, scenes.stairs_from_label)}</span>
+                                <select
+                                    on:change=move |ev| {
+                                        let level_id = level_from_value(event_target_value(&ev));
+                                        vm.update_stairs(&from_id, |stairs| stairs.level_id = level_id);
+                                    }
+                                    style=input_style.clone()
+                                >
+                                    {from_base}
+                                    {from_extra}
+                                </select>
+                                {cell_input(stairs.column, column_id, |stairs, cell| stairs.column = cell)}
+                                {cell_input(stairs.row, row_id, |stairs, cell| stairs.row = cell)}
+                            </div>
+                            <div style="display: flex; gap: 0.4rem; align-items: center; flex-wrap: wrap;">
+                                <span style=meta_style.clone()>{move || t!(i18n, scenes.stairs_to_label)}</span>
+                                <select
+                                    on:change=move |ev| {
+                                        let level_id = level_from_value(event_target_value(&ev));
+                                        vm.update_stairs(&to_id, |stairs| stairs.target_level_id = level_id);
+                                    }
+                                    style=input_style.clone()
+                                >
+                                    {to_base}
+                                    {to_extra}
+                                </select>
+                                {cell_input(stairs.target_column, target_column_id, |stairs, cell| stairs.target_column = cell)}
+                                {cell_input(stairs.target_row, target_row_id, |stairs, cell| stairs.target_row = cell)}
+                            </div>
+                            <div style="display: flex; gap: 0.4rem; align-items: center;">
+                                <input type="text" maxlength="64"
+                                    placeholder=move || t_string!(i18n, scenes.stairs_label_placeholder)
+                                    prop:value=stairs.label.clone().unwrap_or_default()
+                                    on:change=move |ev| {
+                                        let label = event_target_value(&ev).trim().chars().take(64).collect::<String>();
+                                        vm.update_stairs(&label_id, |stairs| {
+                                            stairs.label = (!label.is_empty()).then_some(label);
+                                        });
+                                    }
+                                    style=format!("{} flex: 1;", input_style)
+                                />
+                                <button type="button"
+                                    on:click=move |_| vm.remove_stairs(&remove_id)
+                                    style=button_style.clone()
+                                >
+                                    {move || t!(i18n, scenes.stairs_remove_button)}
+                                </button>
+                            </div>
+                        </div>
+                    }
+                })
+                .collect_view()
+        }
+    };
+
+    let stairs_title_style = meta_style.clone();
+    let stairs_button_style = primary_button_style.clone();
+    let levels_full = move || vm.draft_levels.get().len() >= MAX_SCENE_LEVELS;
+    let stairs_full = move || vm.draft_stairs.get().len() >= MAX_SCENE_STAIRS;
+
+    view! {
+        <div style=format!(
+            "display: flex; flex-direction: column; gap: 0.6rem; padding: 0.85rem; border: 0.0625rem solid {}; border-radius: 0.5rem; background: {};",
+            theme.ui_border, theme.ui_bg_primary
+        )>
+            <div style="display: flex; justify-content: space-between; align-items: center; gap: 0.5rem;">
+                <span style=meta_style.clone()>{move || t!(i18n, scenes.levels_title)}</span>
+                <button type="button"
+                    on:click=add_level
+                    disabled=levels_full
+                    style=primary_button_style.clone()
+                >
+                    {move || t!(i18n, scenes.level_add_button)}
+                </button>
+            </div>
+            <div style=format!("color: {}; font-size: {}; line-height: 1.45;", theme.ui_text_muted, META_FONT_SIZE)>
+                {move || t!(i18n, scenes.levels_hint)}
+            </div>
+            {level_rows}
+
+            <Show when=move || !vm.draft_levels.get().is_empty()>
+                <div style="display: flex; justify-content: space-between; align-items: center; gap: 0.5rem; margin-top: 0.35rem;">
+                    <span style=stairs_title_style.clone()>{move || t!(i18n, scenes.stairs_title)}</span>
+                    <button type="button"
+                        on:click=add_stairs
+                        disabled=stairs_full
+                        style=stairs_button_style.clone()
+                    >
+                        {move || t!(i18n, scenes.stairs_add_button)}
+                    </button>
+                </div>
+                <div style=format!("color: {}; font-size: {}; line-height: 1.45;", theme.ui_text_muted, META_FONT_SIZE)>
+                    {move || t!(i18n, scenes.stairs_hint)}
+                </div>
+            </Show>
+            {stairs_rows}
+        </div>
+    }
+}
@@ -1,5 +1,12 @@
 mod background_fit_editor;
+mod background_pixels;
+pub mod grid_detect;
+mod levels_editor;
 pub mod model;
+pub mod organize;
+pub mod party;
+mod split_party;
+pub mod uvtt;
 mod view;
 pub mod view_model;
 
@@ -6,6 +6,8 @@ use crate::components::scene_board::model::WORKSPACE_SCENE_CELL_SIZE_PX;
 // --- Constants ---
 
 pub const MAX_SCENES_PER_ROOM: usize = 50;
+pub const MAX_SCENE_LEVELS: usize = 16;
+pub const MAX_SCENE_STAIRS: usize = 64;
 pub const DEFAULT_COLUMNS: &str = "24";
 pub const DEFAULT_ROWS: &str = "16";
 pub const DEFAULT_CELL_SIZE_FEET: &str = "5";
@@ -42,6 +44,14 @@ pub enum SceneValidationError {
     LimitReached,
 }
 
+/// Background scale and offsets that put an image's own grid on the scene grid.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BackgroundFit {
+    pub scale: f32,
+    pub offset_x: f32,
+    pub offset_y: f32,
+}
+
 #[derive(Clone, Copy)]
 pub struct FitPreviewLayout {
     pub cell_size: f64,
@@ -90,6 +100,38 @@ pub fn validate_grid(
     }
 }
 
+/// Fits a background whose printed grid has `cell_px` image pixels per cell and
+/// a grid line at (`phase_x`, `phase_y`). The board draws the image as wide as
+/// the grid and centred, so the image is scaled to its width in cells and
+/// shifted until that grid line sits on the scene's top-left corner.
+pub fn background_fit_for_grid(
+    image_width: f64,
+    image_height: f64,
+    cell_px: f64,
+    phase_x: f64,
+    phase_y: f64,
+    columns: u16,
+    rows: u16,
+) -> BackgroundFit {
+    let board_width = f64::from(columns.max(1)) * WORKSPACE_SCENE_CELL_SIZE_PX;
+    let board_height = f64::from(rows.max(1)) * WORKSPACE_SCENE_CELL_SIZE_PX;
+    let image_columns = image_width / cell_px.max(1.0);
+    let scale = ((image_columns / f64::from(columns.max(1))) as f32)
+        .clamp(MIN_BACKGROUND_SCALE, MAX_BACKGROUND_SCALE);
+    // Board pixels per image pixel once scaled.
+    let ratio = board_width * f64::from(scale) / image_width.max(1.0);
+    let offset = |image_extent: f64, board_extent: f64, phase: f64| {
+        ((image_extent * ratio - board_extent) / 2.0 - phase * ratio) as f32
+    };
+    BackgroundFit {
+        scale,
+        offset_x: offset(image_width, board_width, phase_x)
+            .clamp(MIN_BACKGROUND_OFFSET_PX, MAX_BACKGROUND_OFFSET_PX),
+        offset_y: offset(image_height, board_height, phase_y)
+            .clamp(MIN_BACKGROUND_OFFSET_PX, MAX_BACKGROUND_OFFSET_PX),
+    }
+}
+
 /// Computes board metrics (cell_size, board_width, board_height) for display.
 pub fn board_metrics(
     columns: u16,
@@ -178,6 +220,29 @@ mod tests {
         assert!(matches!(result, Err(SceneValidationError::InvalidGrid)));
     }
 
+    #[test]
+    fn background_fit_puts_the_image_grid_on_the_scene_grid() {
+        let cell = WORKSPACE_SCENE_CELL_SIZE_PX as f32;
+        // 20 x 10 cells of 100 px fill the grid exactly.
+        let fit = background_fit_for_grid(2000.0, 1000.0, 100.0, 0.0, 0.0, 20, 10);
+        assert_eq!(
+            fit,
+            BackgroundFit {
+                scale: 1.0,
+                offset_x: 0.0,
+                offset_y: 0.0
+            }
+        );
+
+        // Half a cell of margin on the left and top moves the image up-left.
+        let fit = background_fit_for_grid(2050.0, 1050.0, 100.0, 50.0, 50.0, 20, 10);
+        assert!((fit.scale - 1.025).abs() < 1e-6);
+        assert!((fit.offset_x + 0.25 * cell).abs() < 1e-3);
+        assert!((fit.offset_y + 0.25 * cell).abs() < 1e-3);
+        let fit = background_fit_for_grid(2050.0, 1000.0, 100.0, 0.0, 0.0, 20, 10);
+        assert!((fit.offset_x - 0.25 * cell).abs() < 1e-3);
+    }
+
     #[test]
     fn board_metrics_clamps_cell_size() {
         let (cell, _, _) = board_metrics(1, 1, 320.0, 240.0);
@@ -0,0 +1,330 @@
+// Pure scene list organisation: duplicates, templates, folders and ordering.
+// No signals, no Leptos, no web_sys.
+
+use super::model::default_scene_position;
+use shared::events::Scene;
+
+// --- Constants ---
+
+pub const MAX_SCENE_FOLDER_CHARS: usize = 64;
+pub const MAX_SCENE_TEMPLATES: usize = 100;
+const MAX_SCENE_NAME_CHARS: usize = 255;
+
+// --- Types ---
+
+/// Scenes sharing a folder, in display order. `folder` is `None` for the
+/// scenes outside any folder, which are listed first.
+#[derive(Debug, Clone)]
+pub struct SceneFolderGroup {
+    pub folder: Option<String>,
+    pub scenes: Vec<Scene>,
+}
+
+// --- Pure functions ---
+
+/// Trims a folder name typed by the GM; blank means "no folder".
+pub fn normalize_folder_name(name: &str) -> Option<String> {
+    let name = name.trim();
+    (!name.is_empty()).then(|| name.chars().take(MAX_SCENE_FOLDER_CHARS).collect())
+}
+
+/// Groups scenes by folder: unfiled scenes first, then folders by name, each
+/// ordered by `sort_index` and then by creation order.
+pub fn scene_folder_groups(scenes: &[Scene]) -> Vec<SceneFolderGroup> {
+    let mut groups: Vec<SceneFolderGroup> = Vec::new();
+    for scene in scenes {
+        match groups.iter_mut().find(|group| group.folder == scene.folder) {
+            Some(group) => group.scenes.push(scene.clone()),
+            None => groups.push(SceneFolderGroup {
+                folder: scene.folder.clone(),
+                scenes: vec![scene.clone()],
+            }),
+        }
+    }
+    groups.sort_by(|left, right| match (&left.folder, &right.folder) {
+        (None, None) => std::cmp::Ordering::Equal,
+        (None, Some(_)) => std::cmp::Ordering::Less,
+        (Some(_), None) => std::cmp::Ordering::Greater,
+        (Some(left), Some(right)) => left.to_lowercase().cmp(&right.to_lowercase()),
+    });
+    for group in &mut groups {
+        group.scenes.sort_by_key(|scene| scene.sort_index);
+    }
+    groups
+}
+
+/// Folder names in use, sorted for the folder picker.
+pub fn scene_folders(scenes: &[Scene]) -> Vec<String> {
+    scene_folder_groups(scenes)
+        .into_iter()
+        .filter_map(|group| group.folder)
+        .collect()
+}
+
+/// `sort_index` that puts a scene after every other scene of `folder`.
+pub fn next_sort_index(scenes: &[Scene], folder: Option<&str>) -> u32 {
+    scenes
+        .iter()
+        .filter(|scene| scene.folder.as_deref() == folder)
+        .map(|scene| scene.sort_index.saturating_add(1))
+        .max()
+        .unwrap_or(0)
+}
+
+/// Copies `source` under a new id next to it in its folder. Tokens are kept
+/// with fresh ids when `with_tokens` is set, since token ids are room-wide.
+pub fn duplicate_scene(
+    source: &Scene,
+    scenes: &[Scene],
+    name: &str,
+    with_tokens: bool,
+    mut new_id: impl FnMut() -> String,
+) -> Scene {
+    let mut scene = source.clone();
+    scene.id = new_id();
+    scene.name = name.chars().take(MAX_SCENE_NAME_CHARS).collect();
+    if with_tokens {
+        for token in &mut scene.tokens {
+            token.id = new_id();
+        }
+    } else {
+        scene.tokens.clear();
+    }
+    let (workspace_x, workspace_y) =
+        default_scene_position(scenes.len(), scene.grid.columns, scene.grid.rows);
+    scene.workspace_x = workspace_x;
+    scene.workspace_y = workspace_y;
+    scene.sort_index = next_sort_index(scenes, scene.folder.as_deref());
+    scene
+}
+
+/// The reusable layout of a scene: everything but its tokens and its place
+/// in this room.
+pub fn scene_template(scene: &Scene) -> Scene {
+    let mut template = scene.clone();
+    template.tokens.clear();
+    template.workspace_x = 0.0;
+    template.workspace_y = 0.0;
+    template.folder = None;
+    template.sort_index = 0;
+    template
+}
+
+/// A new scene in this room built from a template layout.
+pub fn scene_from_template(template: &Scene, scenes: &[Scene], id: String) -> Scene {
+    let mut scene = scene_template(template);
+    scene.id = id;
+    let (workspace_x, workspace_y) =
+        default_scene_position(scenes.len(), scene.grid.columns, scene.grid.rows);
+    scene.workspace_x = workspace_x;
+    scene.workspace_y = workspace_y;
+    scene.sort_index = next_sort_index(scenes, None);
+    scene
+}
+
+/// Moves a scene one place up (`-1`) or down (`1`) within its folder and
+/// returns the scenes whose `sort_index` changed.
+pub fn move_scene(scenes: &[Scene], scene_id: &str, step: isize) -> Vec<Scene> {
+    let Some(folder) = scenes
+        .iter()
+        .find(|scene| scene.id == scene_id)
+        .map(|scene| scene.folder.clone())
+    else {
+        return Vec::new();
+    };
+    let Some(group) = scene_folder_groups(scenes)
+        .into_iter()
+        .find(|group| group.folder == folder)
+    else {
+        return Vec::new();
+    };
+    let mut ordered = group.scenes;
+    let Some(from) = ordered.iter().position(|scene| scene.id == scene_id) else {
+        return Vec::new();
+    };
+    let Some(to) = from
+        .checked_add_signed(step)
+        .filter(|to| *to < ordered.len())
+    else {
+        return Vec::new();
+    };
+    ordered.swap(from, to);
+    ordered
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, mut scene)| {
+            let index = index as u32;
+            (scene.sort_index != index).then(|| {
+                scene.sort_index = index;
+                scene
+            })
+        })
+        .collect()
+}
+
+/// Puts the scenes in `scene_ids` into `folder`, after its current scenes,
+/// and returns those that changed.
+pub fn move_scenes_to_folder(
+    scenes: &[Scene],
+    scene_ids: &[String],
+    folder: Option<String>,
+) -> Vec<Scene> {
+    let mut next_index = next_sort_index(scenes, folder.as_deref());
+    scene_folder_groups(scenes)
+        .into_iter()
+        .flat_map(|group| group.scenes)
+        .filter(|scene| scene_ids.contains(&scene.id) && scene.folder != folder)
+        .map(|mut scene| {
+            scene.folder = folder.clone();
+            scene.sort_index = next_index;
+            next_index = next_index.saturating_add(1);
+            scene
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::events::{FileRef, SceneGrid, Token};
+
+    fn scene(id: &str, folder: Option<&str>, sort_index: u32) -> Scene {
+        Scene {
+            id: id.to_string(),
+            name: id.to_uppercase(),
+            grid: SceneGrid {
+                columns: 10,
+                rows: 8,
+                cell_size_feet: 5,
+            },
+            workspace_x: 0.0,
+            workspace_y: 0.0,
+            background: None,
+            background_scale: 1.0,
+            background_offset_x: 0.0,
+            background_offset_y: 0.0,
+            background_rotation_deg: 0.0,
+            tokens: vec![Token {
+                id: format!("{id}-token"),
+                name: "Goblin".to_string(),
+                image: FileRef {
+                    hash: "img".to_string(),
+                    mime_type: "image/png".to_string(),
+                    file_name: "img.png".to_string(),
+                    size: 1,
+                },
+                x: 1.0,
+                y: 1.0,
+                width_cells: 1,
+                height_cells: 1,
+                rotation_deg: 0.0,
+                elevation_feet: 0,
+                owner: None,
+                auras: Vec::new(),
+                level_id: None,
+            }],
+            terrain: Vec::new(),
+            walls: Vec::new(),
+            doors: Vec::new(),
+            lights: Vec::new(),
+            folder: folder.map(str::to_string),
+            sort_index,
+            levels: Vec::new(),
+            stairs: Vec::new(),
+        }
+    }
+
+    fn ids(scenes: &[Scene]) -> Vec<&str> {
+        scenes.iter().map(|scene| scene.id.as_str()).collect()
+    }
+
+    #[test]
+    fn folders_group_and_order_scenes() {
+        let scenes = vec![
+            scene("b", Some("Dungeon"), 1),
+            scene("a", None, 0),
+            scene("c", Some("dungeon"), 0),
+            scene("d", Some("Dungeon"), 0),
+            scene("e", Some("Caves"), 0),
+        ];
+
+        let groups = scene_folder_groups(&scenes);
+        let folders = groups
+            .iter()
+            .map(|group| group.folder.as_deref())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            folders,
+            vec![None, Some("Caves"), Some("Dungeon"), Some("dungeon")]
+        );
+        assert_eq!(ids(&groups[2].scenes), vec!["d", "b"]);
+        assert_eq!(scene_folders(&scenes), vec!["Caves", "Dungeon", "dungeon"]);
+        assert_eq!(next_sort_index(&scenes, Some("Dungeon")), 2);
+        assert_eq!(next_sort_index(&scenes, Some("Forest")), 0);
+        assert_eq!(normalize_folder_name("  Act 1 "), Some("Act 1".to_string()));
+        assert_eq!(normalize_folder_name("   "), None);
+    }
+
+    #[test]
+    fn duplicates_get_new_ids_and_optionally_keep_tokens() {
+        let scenes = vec![scene("a", Some("Act 1"), 0)];
+        let mut counter = 0;
+        let mut new_id = || {
+            counter += 1;
+            format!("id-{counter}")
+        };
+
+        let copy = duplicate_scene(&scenes[0], &scenes, "A (copy)", true, &mut new_id);
+        assert_eq!(copy.id, "id-1");
+        assert_eq!(copy.name, "A (copy)");
+        assert_eq!(copy.tokens[0].id, "id-2");
+        assert_eq!(copy.folder.as_deref(), Some("Act 1"));
+        assert_eq!(copy.sort_index, 1);
+        assert!(copy.workspace_x > 0.0);
+
+        let empty = duplicate_scene(&scenes[0], &scenes, "A (copy)", false, &mut new_id);
+        assert!(empty.tokens.is_empty());
+    }
+
+    #[test]
+    fn templates_drop_tokens_and_room_placement() {
+        let scenes = vec![scene("a", Some("Act 1"), 3)];
+        let template = scene_template(&scenes[0]);
+        assert!(template.tokens.is_empty());
+        assert_eq!(template.folder, None);
+
+        let created = scene_from_template(&template, &scenes, "new".to_string());
+        assert_eq!(created.id, "new");
+        assert_eq!(created.name, "A");
+        assert_eq!(created.sort_index, 0);
+        assert!(created.workspace_x > 0.0);
+    }
+
+    #[test]
+    fn moving_scenes_renumbers_their_folder() {
+        let scenes = vec![
+            scene("a", None, 0),
+            scene("b", None, 0),
+            scene("c", None, 1),
+            scene("d", Some("Act 1"), 0),
+        ];
+
+        // `a` and `b` tie at 0, so the folder is renumbered to 0, 1, 2.
+        let moved = move_scene(&scenes, "c", -1);
+        assert_eq!(ids(&moved), vec!["b"]);
+        assert_eq!(moved[0].sort_index, 2);
+        let moved = move_scene(&scenes, "a", 1);
+        assert_eq!(ids(&moved), vec!["a", "c"]);
+        assert!(move_scene(&scenes, "a", -1).is_empty());
+        assert!(move_scene(&scenes, "d", 1).is_empty());
+
+        let filed = move_scenes_to_folder(
+            &scenes,
+            &["a".to_string(), "d".to_string(), "b".to_string()],
+            Some("Act 1".to_string()),
+        );
+        assert_eq!(ids(&filed), vec!["a", "b"]);
+        assert_eq!(filed[0].sort_index, 1);
+        assert_eq!(filed[1].sort_index, 2);
+    }
+}
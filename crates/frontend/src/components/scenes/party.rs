@@ -0,0 +1,172 @@
+// Pure split-party helpers: who plays on which scene.
+// No signals, no Leptos, no web_sys.
+
+use shared::events::{Scene, assigned_scene_id};
+use std::collections::{BTreeMap, BTreeSet};
+
+// --- Types ---
+
+/// Players sharing a scene in the game master's overview.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SceneParty {
+    /// `None` gathers players left without any scene.
+    pub scene_id: Option<String>,
+    /// Whether the scene is the room's active one rather than a split-party pick.
+    pub is_room_active: bool,
+    pub players: Vec<String>,
+}
+
+// --- Pure functions ---
+
+/// Everyone the game master can move between scenes: token owners, players
+/// already assigned and users seen online, sorted and without the game master.
+pub fn party_members(
+    scenes: &[Scene],
+    scene_assignments: &BTreeMap<String, String>,
+    online_users: &[String],
+    game_master: Option<&str>,
+) -> Vec<String> {
+    let owners = scenes
+        .iter()
+        .flat_map(|scene| scene.tokens.iter())
+        .filter_map(|token| token.owner.clone());
+    owners
+        .chain(scene_assignments.keys().cloned())
+        .chain(online_users.iter().cloned())
+        .filter(|user| Some(user.as_str()) != game_master)
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+/// Groups `players` by the scene each of them plays on, in scene order; the
+/// group without a scene comes last. Empty groups are left out.
+pub fn party_overview(
+    scenes: &[Scene],
+    active_scene_id: Option<&str>,
+    scene_assignments: &BTreeMap<String, String>,
+    players: &[String],
+) -> Vec<SceneParty> {
+    let scene_of = |player: &str| {
+        assigned_scene_id(active_scene_id, scene_assignments, player)
+            .filter(|scene_id| scenes.iter().any(|scene| scene.id == *scene_id))
+    };
+    let group = |scene_id: Option<&str>| SceneParty {
+        scene_id: scene_id.map(str::to_string),
+        is_room_active: scene_id.is_some() && scene_id == active_scene_id,
+        players: players
+            .iter()
+            .filter(|player| scene_of(player) == scene_id)
+            .cloned()
+            .collect(),
+    };
+    scenes
+        .iter()
+        .map(|scene| group(Some(scene.id.as_str())))
+        .chain(std::iter::once(group(None)))
+        .filter(|party| !party.players.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::events::{FileRef, SceneGrid, Token};
+
+    fn scene(id: &str, owners: &[&str]) -> Scene {
+        Scene {
+            id: id.to_string(),
+            name: id.to_string(),
+            grid: SceneGrid {
+                columns: 10,
+                rows: 10,
+                cell_size_feet: 5,
+            },
+            workspace_x: 0.0,
+            workspace_y: 0.0,
+            background: None,
+            background_scale: 1.0,
+            background_offset_x: 0.0,
+            background_offset_y: 0.0,
+            background_rotation_deg: 0.0,
+            tokens: owners
+                .iter()
+                .map(|owner| Token {
+                    id: format!("{owner}-token"),
+                    name: owner.to_string(),
+                    image: FileRef {
+                        hash: "token".to_string(),
+                        mime_type: "image/png".to_string(),
+                        file_name: "token.png".to_string(),
+                        size: 1,
+                    },
+                    x: 0.0,
+                    y: 0.0,
+                    width_cells: 1,
+                    height_cells: 1,
+                    rotation_deg: 0.0,
+                    elevation_feet: 0,
+                    owner: Some(owner.to_string()),
+                    auras: Vec::new(),
+                    level_id: None,
+                })
+                .collect(),
+            terrain: Vec::new(),
+            walls: Vec::new(),
+            doors: Vec::new(),
+            lights: Vec::new(),
+            folder: None,
+            sort_index: 0,
+            levels: Vec::new(),
+            stairs: Vec::new(),
+        }
+    }
+
+    fn assignments(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(user, scene_id)| (user.to_string(), scene_id.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn party_members_collects_owners_assignments_and_online_users() {
+        let scenes = vec![scene("tavern", &["gm", "bob"]), scene("crypt", &["alice"])];
+        let members = party_members(
+            &scenes,
+            &assignments(&[("carol", "crypt")]),
+            &["dave".to_string(), "bob".to_string()],
+            Some("gm"),
+        );
+        assert_eq!(members, vec!["alice", "bob", "carol", "dave"]);
+    }
+
+    #[test]
+    fn party_overview_groups_players_by_their_scene() {
+        let scenes = vec![scene("tavern", &[]), scene("crypt", &[])];
+        let players = ["alice", "bob", "carol"].map(str::to_string);
+        let split = assignments(&[("alice", "crypt"), ("carol", "removed")]);
+
+        let overview = party_overview(&scenes, Some("tavern"), &split, &players);
+        assert_eq!(
+            overview,
+            vec![
+                SceneParty {
+                    scene_id: Some("tavern".to_string()),
+                    is_room_active: true,
+                    players: vec!["bob".to_string()],
+                },
+                SceneParty {
+                    scene_id: Some("crypt".to_string()),
+                    is_room_active: false,
+                    players: vec!["alice".to_string()],
+                },
+                SceneParty {
+                    scene_id: None,
+                    is_room_active: false,
+                    players: vec!["carol".to_string()],
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,208 @@
+// Split-party section of the scenes window: the game master's overview of who
+// plays on which scene, and moving players or groups between scenes.
+
+use super::party::{party_members, party_overview};
+use crate::components::websocket::WsSender;
+use crate::config::Theme;
+use crate::i18n::i18n::{t, t_string, use_i18n};
+use leptos::prelude::*;
+use shared::events::{ClientEvent, Scene, SceneAssignPayload, acts_as_game_master};
+use std::collections::BTreeMap;
+
+const BODY_FONT_SIZE: &str = "clamp(0.9rem, 0.87rem + 0.12vw, 0.98rem)";
+const META_FONT_SIZE: &str = "clamp(0.74rem, 0.71rem + 0.12vw, 0.82rem)";
+const BUTTON_FONT_SIZE: &str = "clamp(0.84rem, 0.81rem + 0.12vw, 0.94rem)";
+
+#[component]
+pub fn SplitPartyPanel(
+    #[prop(into)] scenes: RwSignal<Vec<Scene>>,
+    #[prop(into)] active_scene_id: RwSignal<Option<String>>,
+    #[prop(into)] scene_assignments: RwSignal<BTreeMap<String, String>>,
+    #[prop(into)] game_master: RwSignal<Option<String>>,
+    /// Users last reported online, used alongside token owners.
+    #[prop(into)]
+    online_users: Signal<Vec<String>>,
+    ws_sender: ReadSignal<Option<WsSender>>,
+    username: ReadSignal<String>,
+    theme: Theme,
+) -> impl IntoView {
+    let i18n = use_i18n();
+    let is_expanded = RwSignal::new(false);
+    let selected_players = RwSignal::new(Vec::<String>::new());
+    // Empty string sends the players back to the room's active scene.
+    let target_scene_id = RwSignal::new(String::new());
+
+    let viewer_is_gm =
+        Memo::new(move |_| acts_as_game_master(game_master.get().as_deref(), &username.get()));
+    let players = Memo::new(move |_| {
+        party_members(
+            &scenes.get(),
+            &scene_assignments.get(),
+            &online_users.get(),
+            game_master.get().as_deref(),
+        )
+    });
+    let overview = Memo::new(move |_| {
+        party_overview(
+            &scenes.get(),
+            active_scene_id.get().as_deref(),
+            &scene_assignments.get(),
+            &players.get(),
+        )
+    });
+
+    let scene_name = move |scene_id: &str| {
+        scenes.with(|scenes| {
+            scenes
+                .iter()
+                .find(|scene| scene.id == scene_id)
+                .map(|scene| scene.name.clone())
+                .unwrap_or_default()
+        })
+    };
+
+    let toggle_player = move |player: String| {
+        selected_players.update(|selected| {
+            if let Some(index) = selected.iter().position(|name| *name == player) {
+                selected.remove(index);
+            } else {
+                selected.push(player);
+            }
+        });
+    };
+
+    let assign_selected = move |_| {
+        let usernames = selected_players.get_untracked();
+        if usernames.is_empty() {
+            return;
+        }
+        let target = target_scene_id.get_untracked();
+        let Some(sender) = ws_sender.get_untracked() else {
+            return;
+        };
+        let _ = sender.try_send_event(ClientEvent::SceneAssign(SceneAssignPayload {
+            usernames,
+            scene_id: (!target.is_empty()).then_some(target),
+            actor: username.get_untracked(),
+        }));
+        selected_players.set(Vec::new());
+    };
+
+    let button_style = StoredValue::new(format!(
+        "padding: 0.3rem 0.6rem; background: {}; color: {}; border: none; border-radius: 0.5rem; cursor: pointer; font-size: {};",
+        theme.ui_button_primary, theme.ui_text_primary, BUTTON_FONT_SIZE
+    ));
+    let select_style = StoredValue::new(format!(
+        "flex: 1; min-width: 6rem; padding: 0.3rem 0.5rem; background: {}; color: {}; border: 0.0625rem solid {}; border-radius: 0.5rem; font-size: {};",
+        theme.ui_bg_primary, theme.ui_text_primary, theme.ui_border, META_FONT_SIZE
+    ));
+    let meta_style = StoredValue::new(format!(
+        "color: {}; font-size: {};",
+        theme.ui_text_secondary, META_FONT_SIZE
+    ));
+
+    view! {
+        <Show when=move || viewer_is_gm.get()>
+            <div style=format!(
+                "padding: 0.6rem; border: 0.0625rem solid {}; border-radius: 0.5rem; margin-bottom: 0.75rem; display: flex; flex-direction: column; gap: 0.45rem;",
+                theme.ui_border
+            )>
+                <button
+                    on:click=move |_| is_expanded.update(|open| *open = !*open)
+                    style=format!(
+                        "display: flex; justify-content: space-between; align-items: center; padding: 0; background: none; border: none; cursor: pointer; color: {}; font-size: {}; font-weight: 600;",
+                        theme.ui_text_primary, BODY_FONT_SIZE
+                    )
+                >
+                    <span>{move || t!(i18n, scenes.split_party_title)}</span>
+                    <span style=meta_style.get_value()>
+                        {move || if is_expanded.get() { "▾" } else { "▸" }}
+                    </span>
+                </button>
+
+                <Show when=move || is_expanded.get()>
+                    <div style=meta_style.get_value()>{move || t!(i18n, scenes.split_party_hint)}</div>
+
+                    // Who is where
+                    {move || {
+                        let overview = overview.get();
+                        if overview.is_empty() {
+                            return view! {
+                                <div style=format!(
+                                    "color: {}; font-size: {}; font-style: italic;",
+                                    theme.ui_text_muted, META_FONT_SIZE
+                                )>
+                                    {t!(i18n, scenes.split_party_empty)}
+                                </div>
+                            }.into_any();
+                        }
+                        overview.into_iter().map(|party| {
+                            let title = match party.scene_id.as_deref() {
+                                Some(scene_id) if party.is_room_active => format!(
+                                    "{} · {}",
+                                    scene_name(scene_id),
+                                    t_string!(i18n, scenes.active_badge)
+                                ),
+                                Some(scene_id) => scene_name(scene_id),
+                                None => t_string!(i18n, scenes.split_party_no_scene).to_string(),
+                            };
+                            let chips = party.players.into_iter().map(|player| {
+                                let toggle_name = player.clone();
+                                let checked_name = player.clone();
+                                view! {
+                                    <label style=format!(
+                                        "display: inline-flex; align-items: center; gap: 0.25rem; padding: 0.15rem 0.45rem; background: {}; border-radius: 999px; color: {}; font-size: {}; cursor: pointer;",
+                                        theme.ui_bg_secondary, theme.ui_text_primary, META_FONT_SIZE
+                                    )>
+                                        <input
+                                            type="checkbox"
+                                            prop:checked=move || selected_players.with(|selected| selected.contains(&checked_name))
+                                            on:change=move |_| toggle_player(toggle_name.clone())
+                                        />
+                                        {player}
+                                    </label>
+                                }
+                            }).collect_view();
+                            view! {
+                                <div style="display: flex; flex-direction: column; gap: 0.25rem;">
+                                    <div style=format!(
+                                        "color: {}; font-size: {};",
+                                        theme.ui_text_secondary, META_FONT_SIZE
+                                    )>
+                                        {title}
+                                    </div>
+                                    <div style="display: flex; flex-wrap: wrap; gap: 0.3rem;">{chips}</div>
+                                </div>
+                            }
+                        }).collect_view().into_any()
+                    }}
+
+                    // Move the ticked players
+                    <div style="display: flex; flex-wrap: wrap; gap: 0.4rem; align-items: center;">
+                        <select
+                            on:change=move |ev| target_scene_id.set(event_target_value(&ev))
+                            prop:value=move || target_scene_id.get()
+                            style=select_style.get_value()
+                        >
+                            <option value="">{move || t_string!(i18n, scenes.split_party_follow_active)}</option>
+                            {move || scenes.get().into_iter().map(|scene| view! {
+                                <option value=scene.id.clone()>{scene.name.clone()}</option>
+                            }).collect_view()}
+                        </select>
+                        <button
+                            on:click=assign_selected
+                            disabled=move || selected_players.with(Vec::is_empty)
+                            style=button_style.get_value()
+                        >
+                            {move || format!(
+                                "{} ({})",
+                                t_string!(i18n, scenes.split_party_move_button),
+                                selected_players.with(Vec::len)
+                            )}
+                        </button>
+                    </div>
+                </Show>
+            </div>
+        </Show>
+    }
+}
@@ -0,0 +1,406 @@
+// Pure Universal VTT map import (`.dd2vtt`, `.uvtt`, `.df2vtt`): the JSON
+// exported by Dungeondraft, Dungeon Alchemist and friends with an embedded
+// base64 image, grid resolution, walls, doors and lights.
+// No signals, no Leptos, no web_sys.
+
+use super::model::background_fit_for_grid;
+use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
+use serde::Deserialize;
+use shared::events::{SceneDoor, SceneGrid, SceneLight, ScenePoint, SceneWall};
+
+// --- Constants ---
+
+pub const UVTT_FILE_ACCEPT: &str = ".dd2vtt,.uvtt,.df2vtt";
+const MAX_GRID_CELLS: u16 = 200;
+const MAX_SCENE_WALLS: usize = 4096;
+const MAX_WALL_POINTS: usize = 1024;
+const MAX_SCENE_DOORS: usize = 1024;
+const MAX_SCENE_LIGHTS: usize = 256;
+const MAX_LIGHT_INTENSITY: f32 = 10.0;
+const DEFAULT_LIGHT_COLOR: &str = "#ffffff";
+
+// --- Types ---
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UvttError {
+    InvalidJson,
+    MissingImage,
+    InvalidGrid,
+}
+
+/// A map ready to become a scene: background bytes plus grid fit and
+/// line-of-sight data in cells from the grid's top-left corner.
+#[derive(Debug, Clone)]
+pub struct UvttMap {
+    pub image: Vec<u8>,
+    pub mime_type: &'static str,
+    pub grid: SceneGrid,
+    pub background_scale: f32,
+    pub background_offset_x: f32,
+    pub background_offset_y: f32,
+    pub walls: Vec<SceneWall>,
+    pub doors: Vec<SceneDoor>,
+    pub lights: Vec<SceneLight>,
+}
+
+#[derive(Deserialize)]
+struct RawMap {
+    resolution: RawResolution,
+    #[serde(default)]
+    line_of_sight: Vec<Vec<RawPoint>>,
+    #[serde(default)]
+    objects_line_of_sight: Vec<Vec<RawPoint>>,
+    #[serde(default)]
+    portals: Vec<RawPortal>,
+    #[serde(default)]
+    lights: Vec<RawLight>,
+    #[serde(default)]
+    image: String,
+}
+
+#[derive(Deserialize)]
+struct RawResolution {
+    #[serde(default)]
+    map_origin: RawPoint,
+    map_size: RawPoint,
+    pixels_per_grid: f32,
+}
+
+#[derive(Deserialize, Default, Clone, Copy)]
+struct RawPoint {
+    x: f32,
+    y: f32,
+}
+
+#[derive(Deserialize)]
+struct RawPortal {
+    #[serde(default)]
+    bounds: Vec<RawPoint>,
+    #[serde(default = "default_portal_closed")]
+    closed: bool,
+}
+
+#[derive(Deserialize)]
+struct RawLight {
+    position: RawPoint,
+    range: f32,
+    #[serde(default = "default_light_intensity")]
+    intensity: f32,
+    #[serde(default)]
+    color: String,
+}
+
+const fn default_portal_closed() -> bool {
+    true
+}
+
+const fn default_light_intensity() -> f32 {
+    1.0
+}
+
+// --- Pure functions ---
+
+/// Parses a Universal VTT file. The grid covers `map_size` rounded to whole
+/// cells and the background is fitted so `pixels_per_grid` image pixels make
+/// one board cell, starting at the grid origin.
+pub fn parse_uvtt(bytes: &[u8], cell_size_feet: u16) -> Result<UvttMap, UvttError> {
+    let raw = serde_json::from_slice::<RawMap>(bytes).map_err(|_| UvttError::InvalidJson)?;
+    let image = BASE64
+        .decode(raw.image.trim())
+        .map_err(|_| UvttError::MissingImage)?;
+    let mime_type = image_mime_type(&image).ok_or(UvttError::MissingImage)?;
+
+    let resolution = raw.resolution;
+    let size = resolution.map_size;
+    let pixels_per_grid = resolution.pixels_per_grid;
+    if !(size.x > 0.0 && size.y > 0.0 && pixels_per_grid > 0.0) {
+        return Err(UvttError::InvalidGrid);
+    }
+    let grid = SceneGrid {
+        columns: whole_cells(size.x),
+        rows: whole_cells(size.y),
+        cell_size_feet: cell_size_feet.clamp(1, 100),
+    };
+
+    let pixels_per_grid = f64::from(pixels_per_grid);
+    let (image_width, image_height) = image_dimensions(&image)
+        .map(|(width, height)| (f64::from(width), f64::from(height)))
+        .unwrap_or((
+            f64::from(size.x) * pixels_per_grid,
+            f64::from(size.y) * pixels_per_grid,
+        ));
+    let fit = background_fit_for_grid(
+        image_width,
+        image_height,
+        pixels_per_grid,
+        0.0,
+        0.0,
+        grid.columns,
+        grid.rows,
+    );
+
+    let origin = resolution.map_origin;
+    let point = |raw: RawPoint| ScenePoint {
+        x: raw.x - origin.x,
+        y: raw.y - origin.y,
+    };
+    let walls = raw
+        .line_of_sight
+        .iter()
+        .chain(&raw.objects_line_of_sight)
+        .flat_map(|line| {
+            // Long outlines become several walls sharing their joints.
+            line.chunks(MAX_WALL_POINTS - 1)
+                .enumerate()
+                .map(|(index, chunk)| {
+                    let start = index * (MAX_WALL_POINTS - 1);
+                    let end = (start + chunk.len() + 1).min(line.len());
+                    line[start..end]
+                        .iter()
+                        .copied()
+                        .map(point)
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>()
+        })
+        .filter(|points| points.len() >= 2)
+        .take(MAX_SCENE_WALLS)
+        .map(|points| SceneWall { points })
+        .collect();
+    let doors = raw
+        .portals
+        .iter()
+        .filter_map(|portal| match portal.bounds.as_slice() {
+            [start, .., end] => Some(SceneDoor {
+                start: point(*start),
+                end: point(*end),
+                closed: portal.closed,
+            }),
+            _ => None,
+        })
+        .take(MAX_SCENE_DOORS)
+        .collect();
+    let lights = raw
+        .lights
+        .iter()
+        .filter(|light| light.range > 0.0)
+        .take(MAX_SCENE_LIGHTS)
+        .map(|light| SceneLight {
+            position: point(light.position),
+            range_cells: light.range.min(1000.0),
+            intensity: light.intensity.clamp(0.0, MAX_LIGHT_INTENSITY),
+            color: light_color(&light.color),
+        })
+        .collect();
+
+    Ok(UvttMap {
+        image,
+        mime_type,
+        grid,
+        background_scale: fit.scale,
+        background_offset_x: fit.offset_x,
+        background_offset_y: fit.offset_y,
+        walls,
+        doors,
+        lights,
+    })
+}
+
+/// Scene name from the map's file name: `Crypt_of_Ash.dd2vtt` → `Crypt of Ash`.
+pub fn uvtt_scene_name(file_name: &str) -> String {
+    let stem = file_name
+        .rsplit_once('.')
+        .map_or(file_name, |(stem, _)| stem);
+    let name = stem.replace(['_', '-'], " ");
+    let name = name.split_whitespace().collect::<Vec<_>>().join(" ");
+    if name.is_empty() {
+        "Map".to_string()
+    } else {
+        name.chars().take(255).collect()
+    }
+}
+
+/// Whether a picked file should go through the Universal VTT importer.
+pub fn is_uvtt_file_name(file_name: &str) -> bool {
+    let lower = file_name.to_ascii_lowercase();
+    [".dd2vtt", ".uvtt", ".df2vtt"]
+        .iter()
+        .any(|extension| lower.ends_with(extension))
+}
+
+fn whole_cells(cells: f32) -> u16 {
+    (cells.round() as u16).clamp(1, MAX_GRID_CELLS)
+}
+
+/// `ffRRGGBB` (alpha first) or `RRGGBB` → `#RRGGBB`.
+fn light_color(raw: &str) -> String {
+    let hex = raw.trim().trim_start_matches('#');
+    // `get` rather than slicing: byte 2 may fall inside a multibyte character.
+    let rgb = match hex.len() {
+        8 => hex.get(2..),
+        6 => Some(hex),
+        _ => None,
+    };
+    match rgb {
+        Some(rgb) if rgb.chars().all(|c| c.is_ascii_hexdigit()) => {
+            format!("#{}", rgb.to_ascii_lowercase())
+        }
+        _ => DEFAULT_LIGHT_COLOR.to_string(),
+    }
+}
+
+fn image_mime_type(bytes: &[u8]) -> Option<&'static str> {
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some("image/png")
+    } else if bytes.starts_with(&[0xff, 0xd8, 0xff]) {
+        Some("image/jpeg")
+    } else if bytes.len() >= 12 && &bytes[0..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+        Some("image/webp")
+    } else {
+        None
+    }
+}
+
+/// Pixel size of a PNG or WebP image, read from its header.
+fn image_dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    let u32_be = |at: usize| Some(u32::from_be_bytes(bytes.get(at..at + 4)?.try_into().ok()?));
+    let u24_le = |at: usize| {
+        let b = bytes.get(at..at + 3)?;
+        Some(u32::from(b[0]) | u32::from(b[1]) << 8 | u32::from(b[2]) << 16)
+    };
+    match image_mime_type(bytes)? {
+        "image/png" => Some((u32_be(16)?, u32_be(20)?)),
+        "image/webp" => match bytes.get(12..16)? {
+            b"VP8X" => Some((u24_le(24)? + 1, u24_le(27)? + 1)),
+            b"VP8L" => {
+                let b = bytes.get(21..25)?;
+                let bits = u32::from_le_bytes(b.try_into().ok()?);
+                Some(((bits & 0x3fff) + 1, ((bits >> 14) & 0x3fff) + 1))
+            }
+            b"VP8 " => {
+                let b = bytes.get(26..30)?;
+                let width = u32::from(u16::from_le_bytes([b[0], b[1]]) & 0x3fff);
+                let height = u32::from(u16::from_le_bytes([b[2], b[3]]) & 0x3fff);
+                Some((width, height))
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+    .filter(|(width, height)| *width > 0 && *height > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::scene_board::model::WORKSPACE_SCENE_CELL_SIZE_PX;
+
+    /// PNG signature and IHDR chunk of a `width`×`height` image.
+    fn png_header(width: u32, height: u32) -> Vec<u8> {
+        let mut png = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+        png.extend_from_slice(&width.to_be_bytes());
+        png.extend_from_slice(&height.to_be_bytes());
+        png.extend_from_slice(&[8, 6, 0, 0, 0]);
+        png
+    }
+
+    fn uvtt_json(image: &[u8], extra: &str) -> Vec<u8> {
+        format!(
+            r#"{{
+                "format": 0.3,
+                "resolution": {{
+                    "map_origin": {{"x": 0, "y": 0}},
+                    "map_size": {{"x": 20, "y": 10}},
+                    "pixels_per_grid": 100
+                }},
+                {extra}
+                "image": "{}"
+            }}"#,
+            BASE64.encode(image)
+        )
+        .into_bytes()
+    }
+
+    #[test]
+    fn grid_and_background_follow_pixels_per_grid() {
+        let map = parse_uvtt(&uvtt_json(&png_header(2000, 1000), ""), 5).unwrap();
+        assert_eq!(map.mime_type, "image/png");
+        assert_eq!((map.grid.columns, map.grid.rows), (20, 10));
+        assert_eq!(map.grid.cell_size_feet, 5);
+        assert_eq!(map.background_scale, 1.0);
+        assert_eq!(
+            (map.background_offset_x, map.background_offset_y),
+            (0.0, 0.0)
+        );
+
+        // A 2050 px image at 100 px per cell overhangs the 20-column grid.
+        let map = parse_uvtt(&uvtt_json(&png_header(2050, 1000), ""), 5).unwrap();
+        assert!((map.background_scale - 1.025).abs() < 1e-6);
+        let cell = WORKSPACE_SCENE_CELL_SIZE_PX as f32;
+        assert!((map.background_offset_x - 0.25 * cell).abs() < 1e-3);
+        assert!(map.background_offset_y.abs() < 1e-3);
+    }
+
+    #[test]
+    fn walls_doors_and_lights_become_scene_data() {
+        let extra = r#"
+            "line_of_sight": [[{"x": 1, "y": 1}, {"x": 5, "y": 1}, {"x": 5, "y": 4}], [{"x": 9, "y": 9}]],
+            "objects_line_of_sight": [[{"x": 2, "y": 2}, {"x": 3, "y": 2}]],
+            "portals": [
+                {"position": {"x": 3, "y": 1}, "bounds": [{"x": 2.5, "y": 1}, {"x": 3.5, "y": 1}], "rotation": 0, "closed": false, "freestanding": false}
+            ],
+            "lights": [
+                {"position": {"x": 4, "y": 2}, "range": 6, "intensity": 1, "color": "ffeccd8b", "shadows": true},
+                {"position": {"x": 1, "y": 1}, "range": 0, "intensity": 1, "color": "ffffffff", "shadows": true}
+            ],
+        "#;
+        let map = parse_uvtt(&uvtt_json(&png_header(2000, 1000), extra), 10).unwrap();
+        assert_eq!(map.walls.len(), 2);
+        assert_eq!(map.walls[0].points[2], ScenePoint { x: 5.0, y: 4.0 });
+        assert_eq!(
+            map.doors,
+            vec![SceneDoor {
+                start: ScenePoint { x: 2.5, y: 1.0 },
+                end: ScenePoint { x: 3.5, y: 1.0 },
+                closed: false,
+            }]
+        );
+        assert_eq!(map.lights.len(), 1);
+        assert_eq!(map.lights[0].color, "#eccd8b");
+        assert_eq!(map.lights[0].range_cells, 6.0);
+    }
+
+    #[test]
+    fn malformed_light_colors_fall_back_to_white() {
+        assert_eq!(light_color("#aé12345"), DEFAULT_LIGHT_COLOR);
+        assert_eq!(light_color("ffééé"), DEFAULT_LIGHT_COLOR);
+        assert_eq!(light_color("zz123456"), "#123456");
+        assert_eq!(light_color("#12345g"), DEFAULT_LIGHT_COLOR);
+    }
+
+    #[test]
+    fn long_outlines_split_into_joined_walls() {
+        let points = (0..MAX_WALL_POINTS + 10)
+            .map(|index| format!(r#"{{"x": {index}, "y": 0}}"#))
+            .collect::<Vec<_>>()
+            .join(",");
+        let extra = format!(r#""line_of_sight": [[{points}]],"#);
+        let map = parse_uvtt(&uvtt_json(&png_header(2000, 1000), &extra), 5).unwrap();
+        assert_eq!(map.walls.len(), 2);
+        assert_eq!(map.walls[0].points.len(), MAX_WALL_POINTS);
+        assert_eq!(map.walls[1].points.first(), map.walls[0].points.last());
+        assert_eq!(map.walls[1].points.len(), 11);
+    }
+
+    #[test]
+    fn broken_files_are_rejected() {
+        assert_eq!(parse_uvtt(b"{", 5).err(), Some(UvttError::InvalidJson));
+        assert_eq!(
+            parse_uvtt(&uvtt_json(b"not an image", ""), 5).err(),
+            Some(UvttError::MissingImage)
+        );
+        assert_eq!(uvtt_scene_name("Crypt_of_Ash.dd2vtt"), "Crypt of Ash");
+        assert!(is_uvtt_file_name("Map.DD2VTT"));
+        assert!(!is_uvtt_file_name("map.png"));
+    }
+}
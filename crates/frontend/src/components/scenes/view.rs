@@ -1,23 +1,36 @@
 use super::background_fit_editor::BackgroundFitEditor;
+use super::levels_editor::LevelsEditor;
 use super::model::{
-    FILE_INPUT_ACCEPT, MAX_BACKGROUND_OFFSET_PX, MAX_SCENES_PER_ROOM, MIN_BACKGROUND_OFFSET_PX,
-    default_scene_position,
+    DEFAULT_CELL_SIZE_FEET, FILE_INPUT_ACCEPT, MAX_BACKGROUND_OFFSET_PX, MAX_SCENES_PER_ROOM,
+    MIN_BACKGROUND_OFFSET_PX, default_scene_position,
 };
+use super::organize::{
+    MAX_SCENE_FOLDER_CHARS, MAX_SCENE_TEMPLATES, duplicate_scene, move_scene,
+    move_scenes_to_folder, next_sort_index, normalize_folder_name, scene_folder_groups,
+    scene_folders, scene_from_template, scene_template,
+};
+use super::split_party::SplitPartyPanel;
+use super::uvtt::{UVTT_FILE_ACCEPT, is_uvtt_file_name, parse_uvtt, uvtt_scene_name};
 use super::view_model::ScenesWindowViewModel;
 use crate::components::draggable_window::DraggableWindow;
-use crate::components::websocket::{FileTransferStage, FileTransferState, WsSender};
+use crate::components::websocket::{
+    FileTransferStage, FileTransferState, StoredSceneTemplate, WsSender, blob_to_bytes,
+    delete_scene_template, load_scene_templates, save_scene_template,
+};
 use crate::config::Theme;
 use crate::i18n::i18n::{t, t_string, use_i18n};
 use leptos::ev;
 use leptos::ev::MouseEvent;
 use leptos::html;
+use leptos::logging::log;
 use leptos::prelude::*;
 use leptos::task::spawn_local;
 use leptos::wasm_bindgen::JsCast;
 use shared::events::{
-    ClientEvent, Scene, SceneActivatePayload, SceneCreatePayload, SceneDeletePayload,
-    SceneUpdatePayload,
+    ClientEvent, MAX_WIRE_MESSAGE_BYTES, Scene, SceneActivatePayload, SceneCreatePayload,
+    SceneDeletePayload, SceneUpdatePayload, acts_as_game_master,
 };
+use std::collections::BTreeMap;
 use uuid::Uuid;
 use web_sys::{Event, HtmlInputElement};
 
@@ -31,6 +44,13 @@ pub fn ScenesWindow(
     #[prop(into)] is_open: RwSignal<bool>,
     #[prop(into)] scenes: RwSignal<Vec<Scene>>,
     #[prop(into)] active_scene_id: RwSignal<Option<String>>,
+    /// Split-party scene assignments (username -> scene id).
+    #[prop(into)]
+    scene_assignments: RwSignal<BTreeMap<String, String>>,
+    #[prop(into)] game_master: RwSignal<Option<String>>,
+    /// Users last reported online, offered in the split-party overview.
+    #[prop(into)]
+    online_users: Signal<Vec<String>>,
     file_transfer: FileTransferState,
     ws_sender: ReadSignal<Option<WsSender>>,
     username: ReadSignal<String>,
@@ -63,6 +83,9 @@ pub fn ScenesWindow(
         };
         let (workspace_x, workspace_y) =
             default_scene_position(scenes.get_untracked().len(), grid.columns, grid.rows);
+        let folder = normalize_folder_name(&vm.draft_folder.get_untracked());
+        let sort_index = next_sort_index(&scenes.get_untracked(), folder.as_deref());
+        let stairs = vm.stairs_for_grid(&grid);
         let scene = Scene {
             id: Uuid::new_v4().to_string(),
             name: vm.draft_name.get_untracked().trim().to_string(),
@@ -82,6 +105,14 @@ pub fn ScenesWindow(
             background_rotation_deg: vm
                 .clamp_background_rotation(vm.draft_background_rotation_deg.get_untracked()),
             tokens: Vec::new(),
+            terrain: Vec::new(),
+            walls: Vec::new(),
+            doors: Vec::new(),
+            lights: Vec::new(),
+            folder,
+            sort_index,
+            levels: vm.draft_levels.get_untracked(),
+            stairs,
         };
         send_event(ClientEvent::SceneCreate(SceneCreatePayload {
             scene,
@@ -105,6 +136,13 @@ pub fn ScenesWindow(
             .get_untracked()
             .into_iter()
             .find(|s| s.id == scene_id);
+        let folder = normalize_folder_name(&vm.draft_folder.get_untracked());
+        // A scene moved to another folder goes to the end of it.
+        let sort_index = match &existing {
+            Some(scene) if scene.folder == folder => scene.sort_index,
+            _ => next_sort_index(&scenes.get_untracked(), folder.as_deref()),
+        };
+        let stairs = vm.stairs_for_grid(&grid);
         let scene = Scene {
             id: scene_id,
             name: vm.draft_name.get_untracked().trim().to_string(),
@@ -123,7 +161,27 @@ pub fn ScenesWindow(
                 .clamp(MIN_BACKGROUND_OFFSET_PX, MAX_BACKGROUND_OFFSET_PX),
             background_rotation_deg: vm
                 .clamp_background_rotation(vm.draft_background_rotation_deg.get_untracked()),
-            tokens: existing.map(|scene| scene.tokens).unwrap_or_default(),
+            tokens: existing
+                .as_ref()
+                .map(|scene| scene.tokens.clone())
+                .unwrap_or_default(),
+            terrain: existing
+                .as_ref()
+                .map(|scene| scene.terrain.clone())
+                .unwrap_or_default(),
+            walls: existing
+                .as_ref()
+                .map(|scene| scene.walls.clone())
+                .unwrap_or_default(),
+            doors: existing
+                .as_ref()
+                .map(|scene| scene.doors.clone())
+                .unwrap_or_default(),
+            lights: existing.map(|scene| scene.lights).unwrap_or_default(),
+            folder,
+            sort_index,
+            levels: vm.draft_levels.get_untracked(),
+            stairs,
         };
         send_event(ClientEvent::SceneUpdate(SceneUpdatePayload {
             scene,
@@ -166,6 +224,402 @@ pub fn ScenesWindow(
         }
     };
 
+    // Universal VTT maps (.dd2vtt/.uvtt) become a new scene with the grid,
+    // background fit, walls, doors and lights taken from the file.
+    let on_map_selected = {
+        let file_transfer = file_transfer.clone();
+        move |event: Event| {
+            let Some(input) = event
+                .target()
+                .and_then(|t| t.dyn_into::<HtmlInputElement>().ok())
+            else {
+                return;
+            };
+            let Some(file) = input.files().and_then(|files| files.get(0)) else {
+                return;
+            };
+            input.set_value("");
+            if scenes.get_untracked().len() >= MAX_SCENES_PER_ROOM {
+                vm.editor_error
+                    .set(Some(t_string!(i18n, scenes.error_limit).to_string()));
+                return;
+            }
+            let invalid_map = t_string!(i18n, scenes.error_map_import).to_string();
+            // `accept` is only a hint to the file picker.
+            if !is_uvtt_file_name(&file.name()) {
+                vm.editor_error.set(Some(invalid_map));
+                return;
+            }
+            let file_transfer = file_transfer.clone();
+            let uname = username.get_untracked();
+            let sender = ws_sender.get_untracked();
+            vm.is_importing_map.set(true);
+            vm.editor_error.set(None);
+            spawn_local(async move {
+                let imported = async {
+                    let bytes = blob_to_bytes(&file).await?;
+                    let cell_size_feet = DEFAULT_CELL_SIZE_FEET.parse().unwrap_or(5);
+                    let map = parse_uvtt(&bytes, cell_size_feet).map_err(|_| invalid_map)?;
+                    let name = uvtt_scene_name(&file.name());
+                    let extension = map.mime_type.trim_start_matches("image/");
+                    let background = file_transfer
+                        .import_bytes(
+                            &map.image,
+                            format!("{name}.{extension}"),
+                            map.mime_type,
+                            uname.clone(),
+                            sender,
+                        )
+                        .await?;
+                    Ok::<_, String>((name, map, background))
+                }
+                .await;
+                vm.is_importing_map.set(false);
+                let (name, map, background) = match imported {
+                    Ok(imported) => imported,
+                    Err(error) => {
+                        vm.editor_error.set(Some(error));
+                        return;
+                    }
+                };
+                let (workspace_x, workspace_y) = default_scene_position(
+                    scenes.get_untracked().len(),
+                    map.grid.columns,
+                    map.grid.rows,
+                );
+                let scene = Scene {
+                    id: Uuid::new_v4().to_string(),
+                    name,
+                    grid: map.grid,
+                    workspace_x,
+                    workspace_y,
+                    background: Some(background),
+                    background_scale: vm.clamp_background_scale(map.background_scale),
+                    background_offset_x: map
+                        .background_offset_x
+                        .clamp(MIN_BACKGROUND_OFFSET_PX, MAX_BACKGROUND_OFFSET_PX),
+                    background_offset_y: map
+                        .background_offset_y
+                        .clamp(MIN_BACKGROUND_OFFSET_PX, MAX_BACKGROUND_OFFSET_PX),
+                    background_rotation_deg: 0.0,
+                    tokens: Vec::new(),
+                    terrain: Vec::new(),
+                    walls: map.walls,
+                    doors: map.doors,
+                    lights: map.lights,
+                    folder: None,
+                    sort_index: next_sort_index(&scenes.get_untracked(), None),
+                    levels: Vec::new(),
+                    stairs: Vec::new(),
+                };
+                let event = ClientEvent::SceneCreate(SceneCreatePayload {
+                    scene,
+                    actor: uname,
+                });
+                // Walls and lights travel with the scene, in one message.
+                let fits = serde_json::to_vec(&event)
+                    .is_ok_and(|json| json.len() <= MAX_WIRE_MESSAGE_BYTES);
+                if !fits {
+                    let too_large = t_string!(i18n, scenes.error_map_too_large).to_string();
+                    vm.editor_error.set(Some(too_large));
+                    return;
+                }
+                send_event(event);
+            });
+        }
+    };
+
+    let send_scene_updates = move |updated: Vec<Scene>| {
+        for scene in updated {
+            send_event(ClientEvent::SceneUpdate(SceneUpdatePayload {
+                scene,
+                actor: username.get_untracked(),
+            }));
+        }
+    };
+
+    let duplicate = move |scene_id: String, with_tokens: bool| {
+        let current_scenes = scenes.get_untracked();
+        if current_scenes.len() >= MAX_SCENES_PER_ROOM {
+            vm.editor_error
+                .set(Some(t_string!(i18n, scenes.error_limit).to_string()));
+            return;
+        }
+        let Some(source) = current_scenes.iter().find(|scene| scene.id == scene_id) else {
+            return;
+        };
+        let name = format!("{} {}", source.name, t_string!(i18n, scenes.copy_suffix));
+        let mut scene = duplicate_scene(source, &current_scenes, &name, with_tokens, || {
+            Uuid::new_v4().to_string()
+        });
+        // Terrain is painted by the GM, so a player's copy starts without it.
+        if !acts_as_game_master(
+            game_master.get_untracked().as_deref(),
+            &username.get_untracked(),
+        ) {
+            scene.terrain.clear();
+        }
+        send_event(ClientEvent::SceneCreate(SceneCreatePayload {
+            scene,
+            actor: username.get_untracked(),
+        }));
+    };
+
+    // Templates live in this browser's IndexedDB, so they follow the GM
+    // into every room; backgrounds are served from the same local file store.
+    spawn_local(async move {
+        match load_scene_templates().await {
+            Ok(templates) => vm.templates.set(templates),
+            Err(error) => log!("Failed to load scene templates: {}", error),
+        }
+    });
+
+    let save_as_template = move |scene_id: String| {
+        let Some(scene) = scenes
+            .get_untracked()
+            .into_iter()
+            .find(|scene| scene.id == scene_id)
+        else {
+            return;
+        };
+        if vm.templates.get_untracked().len() >= MAX_SCENE_TEMPLATES {
+            vm.editor_error.set(Some(
+                t_string!(i18n, scenes.error_template_limit).to_string(),
+            ));
+            return;
+        }
+        let template = StoredSceneTemplate {
+            id: Uuid::new_v4().to_string(),
+            name: scene.name.clone(),
+            scene: scene_template(&scene),
+            created_at_ms: js_sys::Date::now(),
+        };
+        spawn_local(async move {
+            match save_scene_template(&template).await {
+                Ok(()) => {
+                    vm.templates.update(|templates| {
+                        templates.push(template);
+                        templates.sort_by(|left, right| {
+                            left.name.to_lowercase().cmp(&right.name.to_lowercase())
+                        });
+                    });
+                    vm.is_templates_open.set(true);
+                }
+                Err(error) => vm.editor_error.set(Some(error)),
+            }
+        });
+    };
+
+    let create_from_template = move |template_id: String| {
+        let current_scenes = scenes.get_untracked();
+        if current_scenes.len() >= MAX_SCENES_PER_ROOM {
+            vm.editor_error
+                .set(Some(t_string!(i18n, scenes.error_limit).to_string()));
+            return;
+        }
+        let Some(template) = vm
+            .templates
+            .get_untracked()
+            .into_iter()
+            .find(|template| template.id == template_id)
+        else {
+            return;
+        };
+        let scene =
+            scene_from_template(&template.scene, &current_scenes, Uuid::new_v4().to_string());
+        send_event(ClientEvent::SceneCreate(SceneCreatePayload {
+            scene,
+            actor: username.get_untracked(),
+        }));
+    };
+
+    let remove_template = move |template_id: String| {
+        spawn_local(async move {
+            match delete_scene_template(&template_id).await {
+                Ok(()) => vm
+                    .templates
+                    .update(|templates| templates.retain(|template| template.id != template_id)),
+                Err(error) => vm.editor_error.set(Some(error)),
+            }
+        });
+    };
+
+    let bulk_delete = move |_| {
+        for scene_id in vm.bulk_selection.get_untracked() {
+            send_event(ClientEvent::SceneDelete(SceneDeletePayload {
+                scene_id,
+                actor: username.get_untracked(),
+            }));
+        }
+        vm.bulk_selection.set(Vec::new());
+    };
+
+    // Only one scene is active at a time, so activation needs a single tick.
+    let bulk_activate = move |_| {
+        let selection = vm.bulk_selection.get_untracked();
+        let [scene_id] = selection.as_slice() else {
+            return;
+        };
+        send_event(ClientEvent::SceneActivate(SceneActivatePayload {
+            scene_id: scene_id.clone(),
+            actor: username.get_untracked(),
+        }));
+    };
+
+    let bulk_move = move |_| {
+        let folder = normalize_folder_name(&vm.bulk_folder.get_untracked());
+        send_scene_updates(move_scenes_to_folder(
+            &scenes.get_untracked(),
+            &vm.bulk_selection.get_untracked(),
+            folder,
+        ));
+        vm.bulk_folder.set(String::new());
+    };
+
+    Effect::new(move |_| {
+        let scene_ids = scenes
+            .get()
+            .into_iter()
+            .map(|scene| scene.id)
+            .collect::<Vec<_>>();
+        vm.retain_bulk_selection(&scene_ids);
+    });
+
+    let scene_card = move |scene: Scene, index: usize, group_len: usize| {
+        let scene_id = scene.id.clone();
+        let select_id = scene.id.clone();
+        let activate_id = scene.id.clone();
+        let edit_scene = scene.clone();
+        let duplicate_id = scene.id.clone();
+        let duplicate_with_tokens_id = scene.id.clone();
+        let template_id = scene.id.clone();
+        let move_up_id = scene.id.clone();
+        let move_down_id = scene.id.clone();
+        let delete_id = scene.id.clone();
+        let is_last = index + 1 >= group_len;
+        let is_active_scene = Signal::derive({
+            let id = scene_id.clone();
+            move || active_scene_id.get() == Some(id.clone())
+        });
+        let is_selected = Signal::derive({
+            let id = scene_id;
+            move || vm.bulk_selection.get().contains(&id)
+        });
+        let button_style = move |background: &str| {
+            format!(
+                "padding: 0.4rem 0.7rem; background: {}; color: {}; border: none; border-radius: 0.5rem; cursor: pointer; font-size: {};",
+                background, theme.ui_text_primary, SCENES_BUTTON_FONT_SIZE
+            )
+        };
+        view! {
+            <div style=move || format!(
+                "padding: 0.75rem; background: {}; border: 0.0625rem solid {}; border-radius: 0.5rem; margin-bottom: 0.625rem;",
+                if is_active_scene.get() { theme.ui_bg_secondary } else { theme.ui_bg_primary },
+                if is_active_scene.get() { theme.ui_success } else { theme.ui_border }
+            )>
+                <div style="display: flex; justify-content: space-between; gap: 0.75rem; align-items: flex-start;">
+                    <label style="display: flex; gap: 0.5rem; align-items: flex-start; cursor: pointer;">
+                        <input
+                            type="checkbox"
+                            title=move || t_string!(i18n, scenes.select_title)
+                            prop:checked=move || is_selected.get()
+                            on:change=move |_| vm.toggle_bulk_selection(&select_id)
+                            style="margin-top: 0.2rem;"
+                        />
+                        <div>
+                            <div style=format!("color: {}; font-weight: 700;", theme.ui_text_primary)>
+                                {scene.name.clone()}
+                            </div>
+                            <div style=format!(
+                                "color: {}; font-size: {}; margin-top: 0.25rem;",
+                                theme.ui_text_secondary, SCENES_META_FONT_SIZE
+                            )>
+                                {format!("{} x {} · {} ft", scene.grid.columns, scene.grid.rows, scene.grid.cell_size_feet)}
+                            </div>
+                        </div>
+                    </label>
+                    <div style="display: flex; gap: 0.35rem; align-items: center;">
+                        {move || if is_active_scene.get() {
+                            view! {
+                                <span style=format!(
+                                    "background: {}; color: {}; padding: 0.2rem 0.5rem; border-radius: 999px; font-size: {};",
+                                    theme.ui_success, theme.ui_text_primary, SCENES_META_FONT_SIZE
+                                )>
+                                    {t!(i18n, scenes.active_badge)}
+                                </span>
+                            }.into_any()
+                        } else { ().into_any() }}
+                        <button
+                            title=move || t_string!(i18n, scenes.move_up_title)
+                            disabled=index == 0
+                            on:click=move |_| send_scene_updates(move_scene(&scenes.get_untracked(), &move_up_id, -1))
+                            style=button_style(theme.ui_bg_secondary)
+                        >
+                            "↑"
+                        </button>
+                        <button
+                            title=move || t_string!(i18n, scenes.move_down_title)
+                            disabled=is_last
+                            on:click=move |_| send_scene_updates(move_scene(&scenes.get_untracked(), &move_down_id, 1))
+                            style=button_style(theme.ui_bg_secondary)
+                        >
+                            "↓"
+                        </button>
+                    </div>
+                </div>
+
+                <div style="display: flex; gap: 0.5rem; flex-wrap: wrap; margin-top: 0.75rem;">
+                    <button
+                        on:click=move |_| vm.apply_scene(&edit_scene)
+                        style=button_style(theme.ui_button_primary)
+                    >
+                        {move || t!(i18n, scenes.edit_button)}
+                    </button>
+                    <button
+                        on:click=move |_| {
+                            send_event(ClientEvent::SceneActivate(SceneActivatePayload {
+                                scene_id: activate_id.clone(),
+                                actor: username.get_untracked(),
+                            }));
+                        }
+                        style=button_style(theme.ui_success)
+                    >
+                        {move || t!(i18n, scenes.activate_button)}
+                    </button>
+                    <button
+                        on:click=move |_| duplicate(duplicate_id.clone(), false)
+                        style=button_style(theme.ui_bg_secondary)
+                    >
+                        {move || t!(i18n, scenes.duplicate_button)}
+                    </button>
+                    <button
+                        on:click=move |_| duplicate(duplicate_with_tokens_id.clone(), true)
+                        style=button_style(theme.ui_bg_secondary)
+                    >
+                        {move || t!(i18n, scenes.duplicate_with_tokens_button)}
+                    </button>
+                    <button
+                        on:click=move |_| save_as_template(template_id.clone())
+                        style=button_style(theme.ui_bg_secondary)
+                    >
+                        {move || t!(i18n, scenes.save_template_button)}
+                    </button>
+                    <button
+                        on:click=move |_| {
+                            send_event(ClientEvent::SceneDelete(SceneDeletePayload {
+                                scene_id: delete_id.clone(),
+                                actor: username.get_untracked(),
+                            }));
+                        }
+                        style=button_style(theme.ui_button_danger)
+                    >
+                        {move || t!(i18n, scenes.delete_button)}
+                    </button>
+                </div>
+            </div>
+        }
+    };
+
     // Close fit editor when window is closed
     Effect::new(move |_| {
         if !is_open.get() {
@@ -184,6 +638,8 @@ pub fn ScenesWindow(
                 vm.draft_rows.set(scene.grid.rows.to_string());
                 vm.draft_cell_size_feet
                     .set(scene.grid.cell_size_feet.to_string());
+                vm.draft_folder
+                    .set(scene.folder.clone().unwrap_or_default());
                 vm.draft_background.set(scene.background.clone());
                 vm.draft_background_scale.set(scene.background_scale);
                 vm.draft_background_offset_x.set(scene.background_offset_x);
@@ -214,6 +670,10 @@ pub fn ScenesWindow(
         });
     });
 
+    // The window body takes its own copies, so the fit editor needs separate ones.
+    let fit_editor_file_transfer = file_transfer.clone();
+    let fit_editor_theme = theme.clone();
+
     view! {
         <>
             <DraggableWindow
@@ -239,121 +699,245 @@ pub fn ScenesWindow(
                             <h4 style=format!("margin: 0; color: {};", theme.ui_text_primary)>
                                 {move || t_string!(i18n, scenes.list_title)}
                             </h4>
+                            <div style="display: flex; gap: 0.5rem; align-items: center;">
+                                <label style=format!(
+                                    "padding: 0.45rem 0.75rem; background: {}; color: {}; border: 0.0625rem solid {}; border-radius: 0.5rem; cursor: pointer; font-size: {};",
+                                    theme.ui_bg_secondary, theme.ui_text_primary, theme.ui_border, SCENES_BUTTON_FONT_SIZE
+                                )>
+                                    {move || if vm.is_importing_map.get() {
+                                        t_string!(i18n, scenes.importing_map).to_string()
+                                    } else {
+                                        t_string!(i18n, scenes.import_map_button).to_string()
+                                    }}
+                                    <input
+                                        type="file"
+                                        accept=UVTT_FILE_ACCEPT
+                                        on:change=on_map_selected
+                                        disabled=move || vm.is_importing_map.get()
+                                        style="display: none;"
+                                    />
+                                </label>
+                                <button
+                                    on:click=move |_| vm.reset()
+                                    style=format!(
+                                        "padding: 0.45rem 0.75rem; background: {}; color: {}; border: none; border-radius: 0.5rem; cursor: pointer; font-size: {};",
+                                        theme.ui_button_primary, theme.ui_text_primary, SCENES_BUTTON_FONT_SIZE
+                                    )
+                                >
+                                    {move || t!(i18n, scenes.new_button)}
+                                </button>
+                            </div>
+                        </div>
+
+                        <div style="display: flex; justify-content: space-between; align-items: center; gap: 0.5rem; margin-bottom: 0.75rem;">
+                            <div style=format!(
+                                "color: {}; font-size: {};",
+                                theme.ui_text_secondary, SCENES_META_FONT_SIZE
+                            )>
+                                {move || format!("{}/{}", scenes.get().len(), MAX_SCENES_PER_ROOM)}
+                            </div>
                             <button
-                                on:click=move |_| vm.reset()
-                                style=format!(
-                                    "padding: 0.45rem 0.75rem; background: {}; color: {}; border: none; border-radius: 0.5rem; cursor: pointer; font-size: {};",
-                                    theme.ui_button_primary, theme.ui_text_primary, SCENES_BUTTON_FONT_SIZE
+                                on:click=move |_| vm.is_templates_open.update(|open| *open = !*open)
+                                style=move || format!(
+                                    "padding: 0.3rem 0.6rem; background: {}; color: {}; border: 0.0625rem solid {}; border-radius: 0.5rem; cursor: pointer; font-size: {};",
+                                    if vm.is_templates_open.get() { theme.ui_bg_secondary } else { theme.ui_bg_primary },
+                                    theme.ui_text_primary, theme.ui_border, SCENES_META_FONT_SIZE
                                 )
                             >
-                                {move || t!(i18n, scenes.new_button)}
+                                {move || format!("{} ({})", t_string!(i18n, scenes.templates_button), vm.templates.get().len())}
                             </button>
                         </div>
 
-                        <div style=format!(
-                            "color: {}; font-size: {}; margin-bottom: 0.75rem;",
-                            theme.ui_text_secondary, SCENES_META_FONT_SIZE
-                        )>
-                            {move || format!("{}/{}", scenes.get().len(), MAX_SCENES_PER_ROOM)}
-                        </div>
+                        // Templates saved on this device
+                        <Show when=move || vm.is_templates_open.get()>
+                            <div style=format!(
+                                "padding: 0.6rem; border: 0.0625rem solid {}; border-radius: 0.5rem; margin-bottom: 0.75rem; display: flex; flex-direction: column; gap: 0.4rem;",
+                                theme.ui_border
+                            )>
+                                {move || {
+                                    let templates = vm.templates.get();
+                                    if templates.is_empty() {
+                                        return view! {
+                                            <div style=format!(
+                                                "color: {}; font-size: {}; font-style: italic;",
+                                                theme.ui_text_muted, SCENES_META_FONT_SIZE
+                                            )>
+                                                {t!(i18n, scenes.templates_empty)}
+                                            </div>
+                                        }.into_any();
+                                    }
+                                    templates.into_iter().map(|template| {
+                                        let use_id = template.id.clone();
+                                        let delete_id = template.id.clone();
+                                        view! {
+                                            <div style="display: flex; justify-content: space-between; align-items: center; gap: 0.5rem;">
+                                                <div style="min-width: 0;">
+                                                    <div style=format!("color: {}; font-size: {};", theme.ui_text_primary, SCENES_BODY_FONT_SIZE)>
+                                                        {template.name.clone()}
+                                                    </div>
+                                                    <div style=format!("color: {}; font-size: {};", theme.ui_text_secondary, SCENES_META_FONT_SIZE)>
+                                                        {format!("{} x {} · {} ft", template.scene.grid.columns, template.scene.grid.rows, template.scene.grid.cell_size_feet)}
+                                                    </div>
+                                                </div>
+                                                <div style="display: flex; gap: 0.35rem;">
+                                                    <button
+                                                        on:click=move |_| create_from_template(use_id.clone())
+                                                        style=format!(
+                                                            "padding: 0.3rem 0.6rem; background: {}; color: {}; border: none; border-radius: 0.5rem; cursor: pointer; font-size: {};",
+                                                            theme.ui_button_primary, theme.ui_text_primary, SCENES_BUTTON_FONT_SIZE
+                                                        )
+                                                    >
+                                                        {move || t!(i18n, scenes.use_template_button)}
+                                                    </button>
+                                                    <button
+                                                        on:click=move |_| remove_template(delete_id.clone())
+                                                        style=format!(
+                                                            "padding: 0.3rem 0.6rem; background: {}; color: {}; border: none; border-radius: 0.5rem; cursor: pointer; font-size: {};",
+                                                            theme.ui_button_danger, theme.ui_text_primary, SCENES_BUTTON_FONT_SIZE
+                                                        )
+                                                    >
+                                                        {move || t!(i18n, scenes.delete_button)}
+                                                    </button>
+                                                </div>
+                                            </div>
+                                        }
+                                    }).collect_view().into_any()
+                                }}
+                            </div>
+                        </Show>
+
+                        // Who plays where when the party splits up
+                        <SplitPartyPanel
+                            scenes=scenes
+                            active_scene_id=active_scene_id
+                            scene_assignments=scene_assignments
+                            game_master=game_master
+                            online_users=online_users
+                            ws_sender=ws_sender
+                            username=username
+                            theme=theme.clone()
+                        />
+
+                        // Bulk actions for ticked scenes
+                        <Show when=move || !vm.bulk_selection.get().is_empty()>
+                            <div style=format!(
+                                "padding: 0.6rem; background: {}; border: 0.0625rem solid {}; border-radius: 0.5rem; margin-bottom: 0.75rem; display: flex; flex-wrap: wrap; gap: 0.4rem; align-items: center;",
+                                theme.ui_bg_secondary, theme.ui_border
+                            )>
+                                <span style=format!("color: {}; font-size: {};", theme.ui_text_primary, SCENES_META_FONT_SIZE)>
+                                    {move || format!("{} {}", vm.bulk_selection.get().len(), t_string!(i18n, scenes.bulk_selected))}
+                                </span>
+                                <input
+                                    type="text"
+                                    list="scene-folders"
+                                    placeholder=move || t_string!(i18n, scenes.folder_placeholder)
+                                    prop:value=move || vm.bulk_folder.get()
+                                    on:input=move |ev| vm.bulk_folder.set(event_target_value(&ev))
+                                    style=format!(
+                                        "flex: 1; min-width: 6rem; padding: 0.3rem 0.5rem; background: {}; color: {}; border: 0.0625rem solid {}; border-radius: 0.5rem; font-size: {};",
+                                        theme.ui_bg_primary, theme.ui_text_primary, theme.ui_border, SCENES_META_FONT_SIZE
+                                    )
+                                />
+                                <button
+                                    on:click=bulk_move
+                                    style=format!(
+                                        "padding: 0.3rem 0.6rem; background: {}; color: {}; border: none; border-radius: 0.5rem; cursor: pointer; font-size: {};",
+                                        theme.ui_button_primary, theme.ui_text_primary, SCENES_BUTTON_FONT_SIZE
+                                    )
+                                >
+                                    {move || t!(i18n, scenes.bulk_move_button)}
+                                </button>
+                                <button
+                                    on:click=bulk_activate
+                                    disabled=move || vm.bulk_selection.get().len() != 1
+                                    style=format!(
+                                        "padding: 0.3rem 0.6rem; background: {}; color: {}; border: none; border-radius: 0.5rem; cursor: pointer; font-size: {};",
+                                        theme.ui_success, theme.ui_text_primary, SCENES_BUTTON_FONT_SIZE
+                                    )
+                                >
+                                    {move || t!(i18n, scenes.activate_button)}
+                                </button>
+                                <button
+                                    on:click=bulk_delete
+                                    style=format!(
+                                        "padding: 0.3rem 0.6rem; background: {}; color: {}; border: none; border-radius: 0.5rem; cursor: pointer; font-size: {};",
+                                        theme.ui_button_danger, theme.ui_text_primary, SCENES_BUTTON_FONT_SIZE
+                                    )
+                                >
+                                    {move || t!(i18n, scenes.delete_button)}
+                                </button>
+                                <button
+                                    on:click=move |_| vm.bulk_selection.set(Vec::new())
+                                    style=format!(
+                                        "padding: 0.3rem 0.6rem; background: {}; color: {}; border: 0.0625rem solid {}; border-radius: 0.5rem; cursor: pointer; font-size: {};",
+                                        theme.ui_bg_primary, theme.ui_text_primary, theme.ui_border, SCENES_BUTTON_FONT_SIZE
+                                    )
+                                >
+                                    {move || t!(i18n, scenes.bulk_clear_button)}
+                                </button>
+                            </div>
+                        </Show>
+
+                        <datalist id="scene-folders">
+                            {move || scene_folders(&scenes.get()).into_iter().map(|folder| view! {
+                                <option value=folder />
+                            }).collect_view()}
+                        </datalist>
 
                         {move || {
                             let current_scenes = scenes.get();
                             if current_scenes.is_empty() {
-                                view! {
+                                return view! {
                                     <div style=format!("color: {}; font-style: italic;", theme.ui_text_muted)>
                                         {t!(i18n, scenes.empty)}
                                     </div>
-                                }.into_any()
-                            } else {
-                                view! {
-                                    <For
-                                        each=move || { scenes.get() }
-                                        key=|scene| scene.id.clone()
-                                        children=move |scene| {
-                                            let scene_id = scene.id.clone();
-                                            let activate_id = scene.id.clone();
-                                            let edit_scene = scene.clone();
-                                            let delete_id = scene.id.clone();
-                                            let is_active_scene = Signal::derive({
-                                                let id = scene_id.clone();
-                                                move || active_scene_id.get() == Some(id.clone())
-                                            });
-                                            view! {
-                                                <div style=move || format!(
-                                                    "padding: 0.75rem; background: {}; border: 0.0625rem solid {}; border-radius: 0.5rem; margin-bottom: 0.625rem;",
-                                                    if is_active_scene.get() { theme.ui_bg_secondary } else { theme.ui_bg_primary },
-                                                    if is_active_scene.get() { theme.ui_success } else { theme.ui_border }
-                                                )>
-                                                    <div style="display: flex; justify-content: space-between; gap: 0.75rem; align-items: flex-start;">
-                                                        <div>
-                                                            <div style=format!("color: {}; font-weight: 700;", theme.ui_text_primary)>
-                                                                {scene.name.clone()}
-                                                            </div>
-                                                            <div style=format!(
-                                                                "color: {}; font-size: {}; margin-top: 0.25rem;",
-                                                                theme.ui_text_secondary, SCENES_META_FONT_SIZE
-                                                            )>
-                                                                {format!("{} x {} · {} ft", scene.grid.columns, scene.grid.rows, scene.grid.cell_size_feet)}
-                                                            </div>
-                                                        </div>
-                                                        {move || if is_active_scene.get() {
-                                                            view! {
-                                                                <span style=format!(
-                                                                    "background: {}; color: {}; padding: 0.2rem 0.5rem; border-radius: 999px; font-size: {};",
-                                                                    theme.ui_success, theme.ui_text_primary, SCENES_META_FONT_SIZE
-                                                                )>
-                                                                    {t!(i18n, scenes.active_badge)}
-                                                                </span>
-                                                            }.into_any()
-                                                        } else { ().into_any() }}
-                                                    </div>
-
-                                                    <div style="display: flex; gap: 0.5rem; flex-wrap: wrap; margin-top: 0.75rem;">
-                                                        <button
-                                                            on:click=move |_| vm.apply_scene(&edit_scene)
-                                                            style=format!(
-                                                                "padding: 0.4rem 0.7rem; background: {}; color: {}; border: none; border-radius: 0.5rem; cursor: pointer; font-size: {};",
-                                                                theme.ui_button_primary, theme.ui_text_primary, SCENES_BUTTON_FONT_SIZE
-                                                            )
-                                                        >
-                                                            {move || t!(i18n, scenes.edit_button)}
-                                                        </button>
-                                                        <button
-                                                            on:click=move |_| {
-                                                                send_event(ClientEvent::SceneActivate(SceneActivatePayload {
-                                                                    scene_id: activate_id.clone(),
-                                                                    actor: username.get_untracked(),
-                                                                }));
-                                                            }
-                                                            style=format!(
-                                                                "padding: 0.4rem 0.7rem; background: {}; color: {}; border: none; border-radius: 0.5rem; cursor: pointer; font-size: {};",
-                                                                theme.ui_success, theme.ui_text_primary, SCENES_BUTTON_FONT_SIZE
-                                                            )
-                                                        >
-                                                            {move || t!(i18n, scenes.activate_button)}
-                                                        </button>
-                                                        <button
-                                                            on:click=move |_| {
-                                                                send_event(ClientEvent::SceneDelete(SceneDeletePayload {
-                                                                    scene_id: delete_id.clone(),
-                                                                    actor: username.get_untracked(),
-                                                                }));
-                                                            }
-                                                            style=format!(
-                                                                "padding: 0.4rem 0.7rem; background: {}; color: {}; border: none; border-radius: 0.5rem; cursor: pointer; font-size: {};",
-                                                                theme.ui_button_danger, theme.ui_text_primary, SCENES_BUTTON_FONT_SIZE
-                                                            )
-                                                        >
-                                                            {move || t!(i18n, scenes.delete_button)}
-                                                        </button>
-                                                    </div>
-                                                </div>
-                                            }
-                                        }
-                                    />
-                                }.into_any()
+                                }.into_any();
                             }
+                            let groups = scene_folder_groups(&current_scenes);
+                            let has_folders = groups.iter().any(|group| group.folder.is_some());
+                            groups.into_iter().map(|group| {
+                                let is_collapsed = group
+                                    .folder
+                                    .as_ref()
+                                    .is_some_and(|folder| vm.collapsed_folders.get().contains(folder));
+                                let header = has_folders.then(|| {
+                                    let toggle_folder = group.folder.clone();
+                                    let title = group
+                                        .folder
+                                        .clone()
+                                        .unwrap_or_else(|| t_string!(i18n, scenes.no_folder).to_string());
+                                    view! {
+                                        <button
+                                            on:click=move |_| {
+                                                if let Some(folder) = &toggle_folder {
+                                                    vm.toggle_folder_collapsed(folder);
+                                                }
+                                            }
+                                            style=format!(
+                                                "width: 100%; text-align: left; padding: 0.35rem 0; margin-bottom: 0.4rem; background: none; border: none; border-bottom: 0.0625rem solid {}; color: {}; cursor: pointer; font-weight: 700; font-size: {};",
+                                                theme.ui_border, theme.ui_text_secondary, SCENES_META_FONT_SIZE
+                                            )
+                                        >
+                                            {format!("{} {} ({})", if is_collapsed { "▸" } else { "▾" }, title, group.scenes.len())}
+                                        </button>
+                                    }
+                                });
+                                let group_len = group.scenes.len();
+                                let cards = (!is_collapsed).then(|| {
+                                    group
+                                        .scenes
+                                        .into_iter()
+                                        .enumerate()
+                                        .map(|(index, scene)| scene_card(scene, index, group_len))
+                                        .collect_view()
+                                });
+                                view! {
+                                    <div style="margin-bottom: 0.5rem;">
+                                        {header}
+                                        {cards}
+                                    </div>
+                                }
+                            }).collect_view().into_any()
                         }}
                     </div>
 
@@ -387,6 +971,23 @@ pub fn ScenesWindow(
                                 />
                             </label>
 
+                            // Folder
+                            <label style=format!("color: {}; display: flex; flex-direction: column; gap: 0.35rem;", theme.ui_text_secondary)>
+                                <span>{move || t!(i18n, scenes.folder_label)}</span>
+                                <input
+                                    type="text"
+                                    list="scene-folders"
+                                    maxlength=MAX_SCENE_FOLDER_CHARS.to_string()
+                                    placeholder=move || t_string!(i18n, scenes.folder_placeholder)
+                                    prop:value=move || vm.draft_folder.get()
+                                    on:input=move |ev| vm.draft_folder.set(event_target_value(&ev))
+                                    style=format!(
+                                        "padding: 0.625rem; background: {}; color: {}; border: 0.0625rem solid {}; border-radius: 0.5rem; font-size: {};",
+                                        theme.ui_bg_secondary, theme.ui_text_primary, theme.ui_border, SCENES_BODY_FONT_SIZE
+                                    )
+                                />
+                            </label>
+
                             // Grid dimensions
                             <div style="display: grid; grid-template-columns: repeat(3, 1fr); gap: 0.75rem;">
                                 <label style=format!("color: {}; display: flex; flex-direction: column; gap: 0.35rem;", theme.ui_text_secondary)>
@@ -539,6 +1140,15 @@ pub fn ScenesWindow(
                                 </div>
                             </div>
 
+                            // Floors and stairs
+                            <LevelsEditor
+                                vm=vm
+                                file_transfer=file_transfer.clone()
+                                ws_sender=ws_sender
+                                username=username
+                                theme=theme.clone()
+                            />
+
                             // Active scene hint
                             <div style=format!(
                                 "color: {}; font-size: {};",
@@ -615,9 +1225,9 @@ pub fn ScenesWindow(
                 <BackgroundFitEditor
                     vm=vm
                     scenes=scenes
-                    file_transfer=file_transfer.clone()
+                    file_transfer=fit_editor_file_transfer.clone()
                     on_save=Callback::new(move |_| save_scene(()))
-                    theme=theme.clone()
+                    theme=fit_editor_theme.clone()
                 />
             </Show>
         </>
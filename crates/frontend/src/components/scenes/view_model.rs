@@ -5,8 +5,10 @@ use super::model::{
     MIN_BACKGROUND_OFFSET_PX, MIN_BACKGROUND_ROTATION_DEG, MIN_BACKGROUND_SCALE,
     SceneValidationError, validate_grid,
 };
+use crate::components::scene_board::levels::{fit_stairs_to_grid, retain_linked_stairs};
+use crate::components::websocket::StoredSceneTemplate;
 use leptos::prelude::*;
-use shared::events::{FileRef, SceneGrid};
+use shared::events::{FileRef, SceneGrid, SceneLevel, SceneStairs};
 
 /// Reactive state for the scene list + editor form.
 #[derive(Clone, Copy)]
@@ -19,11 +21,15 @@ pub struct ScenesWindowViewModel {
     pub draft_columns: RwSignal<String>,
     pub draft_rows: RwSignal<String>,
     pub draft_cell_size_feet: RwSignal<String>,
+    pub draft_folder: RwSignal<String>,
     pub draft_background: RwSignal<Option<FileRef>>,
     pub draft_background_scale: RwSignal<f32>,
     pub draft_background_offset_x: RwSignal<f32>,
     pub draft_background_offset_y: RwSignal<f32>,
     pub draft_background_rotation_deg: RwSignal<f32>,
+    /// Extra floors above or below the base one, in display order.
+    pub draft_levels: RwSignal<Vec<SceneLevel>>,
+    pub draft_stairs: RwSignal<Vec<SceneStairs>>,
 
     // Background fit editor state
     pub is_background_fit_editor_open: RwSignal<bool>,
@@ -33,9 +39,25 @@ pub struct ScenesWindowViewModel {
     bg_drag_origin_offset_x: RwSignal<f32>,
     bg_drag_origin_offset_y: RwSignal<f32>,
     pub bg_drag_preview_scale: RwSignal<f64>,
+    /// Set while the background is analysed for a printed grid.
+    pub is_detecting_grid: RwSignal<bool>,
+    /// Outcome of the last grid detection shown under the fit controls.
+    pub grid_detect_status: RwSignal<Option<String>>,
 
     // Validation error
     pub editor_error: RwSignal<Option<String>>,
+
+    /// Set while a Universal VTT map is being imported.
+    pub is_importing_map: RwSignal<bool>,
+
+    // Scene list organisation
+    /// Scenes ticked for bulk actions, in the order they were ticked.
+    pub bulk_selection: RwSignal<Vec<String>>,
+    pub bulk_folder: RwSignal<String>,
+    pub collapsed_folders: RwSignal<Vec<String>>,
+    /// Templates saved on this device; shared by every room.
+    pub templates: RwSignal<Vec<StoredSceneTemplate>>,
+    pub is_templates_open: RwSignal<bool>,
 }
 
 impl ScenesWindowViewModel {
@@ -46,11 +68,14 @@ impl ScenesWindowViewModel {
             draft_columns: RwSignal::new(DEFAULT_COLUMNS.to_string()),
             draft_rows: RwSignal::new(DEFAULT_ROWS.to_string()),
             draft_cell_size_feet: RwSignal::new(DEFAULT_CELL_SIZE_FEET.to_string()),
+            draft_folder: RwSignal::new(String::new()),
             draft_background: RwSignal::new(None),
             draft_background_scale: RwSignal::new(DEFAULT_BACKGROUND_SCALE),
             draft_background_offset_x: RwSignal::new(DEFAULT_BACKGROUND_OFFSET_X),
             draft_background_offset_y: RwSignal::new(DEFAULT_BACKGROUND_OFFSET_Y),
             draft_background_rotation_deg: RwSignal::new(DEFAULT_BACKGROUND_ROTATION_DEG),
+            draft_levels: RwSignal::new(Vec::new()),
+            draft_stairs: RwSignal::new(Vec::new()),
             is_background_fit_editor_open: RwSignal::new(false),
             is_dragging_background: RwSignal::new(false),
             bg_drag_start_client_x: RwSignal::new(0),
@@ -58,7 +83,15 @@ impl ScenesWindowViewModel {
             bg_drag_origin_offset_x: RwSignal::new(DEFAULT_BACKGROUND_OFFSET_X),
             bg_drag_origin_offset_y: RwSignal::new(DEFAULT_BACKGROUND_OFFSET_Y),
             bg_drag_preview_scale: RwSignal::new(1.0),
+            is_detecting_grid: RwSignal::new(false),
+            grid_detect_status: RwSignal::new(None),
             editor_error: RwSignal::new(None),
+            is_importing_map: RwSignal::new(false),
+            bulk_selection: RwSignal::new(Vec::new()),
+            bulk_folder: RwSignal::new(String::new()),
+            collapsed_folders: RwSignal::new(Vec::new()),
+            templates: RwSignal::new(Vec::new()),
+            is_templates_open: RwSignal::new(false),
         }
     }
 
@@ -75,6 +108,7 @@ impl ScenesWindowViewModel {
     pub fn close_background_fit_editor(&self) {
         self.is_background_fit_editor_open.set(false);
         self.is_dragging_background.set(false);
+        self.grid_detect_status.set(None);
     }
 
     pub fn reset(&self) {
@@ -84,7 +118,10 @@ impl ScenesWindowViewModel {
         self.draft_rows.set(DEFAULT_ROWS.to_string());
         self.draft_cell_size_feet
             .set(DEFAULT_CELL_SIZE_FEET.to_string());
+        self.draft_folder.set(String::new());
         self.draft_background.set(None);
+        self.draft_levels.set(Vec::new());
+        self.draft_stairs.set(Vec::new());
         self.reset_background_fit();
         self.close_background_fit_editor();
         self.editor_error.set(None);
@@ -97,6 +134,8 @@ impl ScenesWindowViewModel {
         self.draft_rows.set(scene.grid.rows.to_string());
         self.draft_cell_size_feet
             .set(scene.grid.cell_size_feet.to_string());
+        self.draft_folder
+            .set(scene.folder.clone().unwrap_or_default());
         self.draft_background.set(scene.background.clone());
         self.draft_background_scale.set(scene.background_scale);
         self.draft_background_offset_x
@@ -105,6 +144,8 @@ impl ScenesWindowViewModel {
             .set(scene.background_offset_y);
         self.draft_background_rotation_deg
             .set(scene.background_rotation_deg);
+        self.draft_levels.set(scene.levels.clone());
+        self.draft_stairs.set(scene.stairs.clone());
         self.close_background_fit_editor();
         self.editor_error.set(None);
     }
@@ -175,6 +216,112 @@ impl ScenesWindowViewModel {
     pub fn clamp_background_rotation(&self, value: f32) -> f32 {
         value.clamp(MIN_BACKGROUND_ROTATION_DEG, MAX_BACKGROUND_ROTATION_DEG)
     }
+
+    pub fn add_level(&self, id: String, name: String) {
+        self.draft_levels.update(|levels| {
+            levels.push(SceneLevel {
+                id,
+                name,
+                background: None,
+                background_scale: DEFAULT_BACKGROUND_SCALE,
+                background_offset_x: DEFAULT_BACKGROUND_OFFSET_X,
+                background_offset_y: DEFAULT_BACKGROUND_OFFSET_Y,
+                background_rotation_deg: DEFAULT_BACKGROUND_ROTATION_DEG,
+            });
+        });
+    }
+
+    pub fn update_level(&self, level_id: &str, apply: impl FnOnce(&mut SceneLevel)) {
+        self.draft_levels.update(|levels| {
+            if let Some(level) = levels.iter_mut().find(|level| level.id == level_id) {
+                apply(level);
+            }
+        });
+    }
+
+    /// Removes a floor together with the stairs on it or leading to it.
+    pub fn remove_level(&self, level_id: &str) {
+        self.draft_levels
+            .update(|levels| levels.retain(|level| level.id != level_id));
+        let levels = self.draft_levels.get_untracked();
+        self.draft_stairs
+            .update(|stairs| retain_linked_stairs(&levels, stairs));
+    }
+
+    /// Adds stairs from the base floor to the first extra floor.
+    pub fn add_stairs(&self, id: String) {
+        let target_level_id = self
+            .draft_levels
+            .get_untracked()
+            .first()
+            .map(|level| level.id.clone());
+        self.draft_stairs.update(|stairs| {
+            stairs.push(SceneStairs {
+                id,
+                level_id: None,
+                column: 0,
+                row: 0,
+                target_level_id,
+                target_column: 0,
+                target_row: 0,
+                label: None,
+            });
+        });
+    }
+
+    pub fn update_stairs(&self, stairs_id: &str, apply: impl FnOnce(&mut SceneStairs)) {
+        self.draft_stairs.update(|stairs| {
+            if let Some(stairs) = stairs.iter_mut().find(|stairs| stairs.id == stairs_id) {
+                apply(stairs);
+            }
+        });
+    }
+
+    pub fn remove_stairs(&self, stairs_id: &str) {
+        self.draft_stairs
+            .update(|stairs| stairs.retain(|stairs| stairs.id != stairs_id));
+    }
+
+    /// Draft stairs between existing draft floors, kept inside `grid`.
+    pub fn stairs_for_grid(&self, grid: &SceneGrid) -> Vec<SceneStairs> {
+        let mut stairs = self.draft_stairs.get_untracked();
+        retain_linked_stairs(&self.draft_levels.get_untracked(), &mut stairs);
+        fit_stairs_to_grid(&mut stairs, grid.columns, grid.rows);
+        stairs
+    }
+
+    pub fn toggle_bulk_selection(&self, scene_id: &str) {
+        self.bulk_selection.update(|selection| {
+            if let Some(index) = selection.iter().position(|id| id == scene_id) {
+                selection.remove(index);
+            } else {
+                selection.push(scene_id.to_string());
+            }
+        });
+    }
+
+    /// Drops ticks on scenes that no longer exist.
+    pub fn retain_bulk_selection(&self, scene_ids: &[String]) {
+        if self
+            .bulk_selection
+            .get_untracked()
+            .iter()
+            .any(|id| !scene_ids.contains(id))
+        {
+            self.bulk_selection
+                .update(|selection| selection.retain(|id| scene_ids.contains(id)));
+        }
+    }
+
+    pub fn toggle_folder_collapsed(&self, folder: &str) {
+        self.collapsed_folders.update(|folders| {
+            if let Some(index) = folders.iter().position(|name| name == folder) {
+                folders.remove(index);
+            } else {
+                folders.push(folder.to_string());
+            }
+        });
+    }
 }
 
 #[cfg(test)]
@@ -247,6 +394,44 @@ mod tests {
         });
     }
 
+    #[test]
+    fn bulk_selection_toggles_and_forgets_deleted_scenes() {
+        let owner = Owner::new();
+        owner.with(|| {
+            let vm = ScenesWindowViewModel::new();
+            vm.toggle_bulk_selection("a");
+            vm.toggle_bulk_selection("b");
+            vm.toggle_bulk_selection("a");
+            assert_eq!(vm.bulk_selection.get_untracked(), vec!["b".to_string()]);
+
+            vm.toggle_bulk_selection("c");
+            vm.retain_bulk_selection(&["c".to_string()]);
+            assert_eq!(vm.bulk_selection.get_untracked(), vec!["c".to_string()]);
+        });
+    }
+
+    #[test]
+    fn removing_a_level_drops_its_stairs() {
+        let owner = Owner::new();
+        owner.with(|| {
+            let vm = ScenesWindowViewModel::new();
+            vm.add_level("roof".to_string(), "Roof".to_string());
+            vm.add_stairs("up".to_string());
+            vm.update_level("roof", |level| level.name = "Attic".to_string());
+            assert_eq!(vm.draft_levels.get_untracked()[0].name, "Attic");
+            assert_eq!(
+                vm.draft_stairs.get_untracked()[0]
+                    .target_level_id
+                    .as_deref(),
+                Some("roof")
+            );
+
+            vm.remove_level("roof");
+            assert!(vm.draft_levels.get_untracked().is_empty());
+            assert!(vm.draft_stairs.get_untracked().is_empty());
+        });
+    }
+
     #[test]
     fn reset_background_fit_restores_defaults() {
         let owner = Owner::new();
@@ -3,7 +3,8 @@ mod view;
 pub mod view_model;
 
 pub(crate) use storage::{
-    load_inactive_scene_contents_visibility, load_workspace_hint_visibility,
-    save_inactive_scene_contents_visibility, save_workspace_hint_visibility,
+    load_board_renderer, load_inactive_scene_contents_visibility, load_render_stats_visibility,
+    load_workspace_hint_visibility, save_board_renderer, save_inactive_scene_contents_visibility,
+    save_render_stats_visibility, save_workspace_hint_visibility,
 };
 pub use view::Settings;
@@ -1,5 +1,9 @@
+use crate::components::scene_board::canvas::BoardRenderer;
+
 const WORKSPACE_HINT_STORAGE_KEY: &str = "settings:show_workspace_hint";
 const INACTIVE_SCENE_CONTENTS_STORAGE_KEY: &str = "settings:show_inactive_scene_contents";
+const BOARD_RENDERER_STORAGE_KEY: &str = "settings:board_renderer";
+const RENDER_STATS_STORAGE_KEY: &str = "settings:show_render_stats";
 
 fn decode_bool(value: &str) -> Option<bool> {
     match value {
@@ -51,6 +55,44 @@ pub fn save_inactive_scene_contents_visibility(is_visible: bool) {
     let _ = storage.set_item(INACTIVE_SCENE_CONTENTS_STORAGE_KEY, value);
 }
 
+pub fn load_board_renderer() -> Option<BoardRenderer> {
+    let window = web_sys::window()?;
+    let storage = window.local_storage().ok().flatten()?;
+    let value = storage
+        .get_item(BOARD_RENDERER_STORAGE_KEY)
+        .ok()
+        .flatten()?;
+    BoardRenderer::from_storage_value(&value)
+}
+
+pub fn save_board_renderer(renderer: BoardRenderer) {
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+    let Some(storage) = window.local_storage().ok().flatten() else {
+        return;
+    };
+    let _ = storage.set_item(BOARD_RENDERER_STORAGE_KEY, renderer.storage_value());
+}
+
+pub fn load_render_stats_visibility() -> Option<bool> {
+    let window = web_sys::window()?;
+    let storage = window.local_storage().ok().flatten()?;
+    let value = storage.get_item(RENDER_STATS_STORAGE_KEY).ok().flatten()?;
+    decode_bool(&value)
+}
+
+pub fn save_render_stats_visibility(is_visible: bool) {
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+    let Some(storage) = window.local_storage().ok().flatten() else {
+        return;
+    };
+    let value = if is_visible { "true" } else { "false" };
+    let _ = storage.set_item(RENDER_STATS_STORAGE_KEY, value);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
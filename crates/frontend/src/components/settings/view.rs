@@ -1,20 +1,44 @@
 use super::view_model::{SettingsViewModel, apply_language_change};
+use crate::components::scene_board::canvas::BoardRenderer;
+use crate::components::websocket::WsSender;
 use crate::config::Theme;
-use crate::i18n::i18n::{Locale, t, use_i18n};
+use crate::i18n::i18n::{Locale, t, t_string, use_i18n};
 use leptos::prelude::*;
+use shared::events::{ClientEvent, GameMasterSetPayload};
 
 #[component]
 pub fn Settings(
     #[prop(into)] is_open: RwSignal<bool>,
     #[prop(into)] show_workspace_hint: RwSignal<bool>,
     #[prop(into)] show_inactive_scene_contents: RwSignal<bool>,
+    #[prop(into)] board_renderer: RwSignal<BoardRenderer>,
+    #[prop(into)] show_render_stats: RwSignal<bool>,
     on_clear_room_local_state: Callback<()>,
     current_room: ReadSignal<String>,
+    #[prop(into)] game_master: RwSignal<Option<String>>,
+    username: ReadSignal<String>,
+    ws_sender: ReadSignal<Option<WsSender>>,
     theme: Theme,
 ) -> impl IntoView {
     let i18n = use_i18n();
     let vm = SettingsViewModel::new(is_open);
     let current_locale = i18n.get_locale();
+    let is_game_master = move || game_master.get().is_some_and(|gm| gm == username.get());
+    let can_toggle_game_master = move || game_master.get().is_none() || is_game_master();
+    let toggle_game_master = move |_| {
+        let actor = username.get_untracked();
+        let next = if game_master.get_untracked().is_some() {
+            None
+        } else {
+            Some(actor.clone())
+        };
+        if let Some(sender) = ws_sender.get_untracked() {
+            let _ = sender.try_send_event(ClientEvent::GameMasterSet(GameMasterSetPayload {
+                game_master: next,
+                actor,
+            }));
+        }
+    };
 
     view! {
         <Show when=move || vm.is_open.get()>
@@ -100,6 +124,81 @@ pub fn Settings(
                             </span>
                         </label>
 
+                        <div style=format!(
+                            "display: flex; flex-direction: column; gap: 0.55rem; padding: 0.9rem 1rem; \
+                             border-radius: 0.625rem; background: {}; color: {};",
+                            theme.ui_bg_secondary, theme.ui_text_primary
+                        )>
+                            <span>{t!(i18n, settings.board_renderer)}</span>
+                            <span style=format!("color: {}; font-size: 0.82rem; line-height: 1.45;", theme.ui_text_secondary)>
+                                {t!(i18n, settings.board_renderer_hint)}
+                            </span>
+                            <select
+                                on:change=move |ev| {
+                                    if let Some(renderer) = BoardRenderer::from_storage_value(&event_target_value(&ev)) {
+                                        board_renderer.set(renderer);
+                                    }
+                                }
+                                prop:value=move || board_renderer.get().storage_value()
+                                style=format!(
+                                    "padding: 0.75rem; border-radius: 0.3125rem; border: 0.0625rem solid {}; \
+                                     background: {}; color: {}; font-size: 1rem; cursor: pointer;",
+                                    theme.ui_border, theme.ui_bg_primary, theme.ui_text_primary
+                                )
+                            >
+                                <option value=BoardRenderer::Dom.storage_value()>{t!(i18n, settings.board_renderer_dom)}</option>
+                                <option value=BoardRenderer::Canvas.storage_value()>{t!(i18n, settings.board_renderer_canvas)}</option>
+                            </select>
+                            <label style="display: flex; gap: 0.75rem; align-items: flex-start; cursor: pointer;">
+                                <input
+                                    type="checkbox"
+                                    prop:checked=move || show_render_stats.get()
+                                    on:change=move |ev| show_render_stats.set(event_target_checked(&ev))
+                                    style="margin-top: 0.2rem; width: 1rem; height: 1rem;"
+                                />
+                                <span style="display: flex; flex-direction: column; gap: 0.3rem;">
+                                    <span>{t!(i18n, settings.show_render_stats)}</span>
+                                    <span style=format!("color: {}; font-size: 0.82rem; line-height: 1.45;", theme.ui_text_secondary)>
+                                        {t!(i18n, settings.show_render_stats_hint)}
+                                    </span>
+                                </span>
+                            </label>
+                        </div>
+
+                        <div style=format!(
+                            "display: flex; flex-direction: column; gap: 0.55rem; padding: 0.9rem 1rem; \
+                             border-radius: 0.625rem; background: {}; color: {};",
+                            theme.ui_bg_secondary, theme.ui_text_primary
+                        )>
+                            <span>
+                                {t!(i18n, settings.game_master)} ": "
+                                <strong>
+                                    {move || game_master.get().unwrap_or_else(|| t_string!(i18n, settings.game_master_none).to_string())}
+                                </strong>
+                            </span>
+                            <span style=format!("color: {}; font-size: 0.82rem; line-height: 1.45;", theme.ui_text_secondary)>
+                                {t!(i18n, settings.game_master_hint)}
+                            </span>
+                            <button
+                                on:click=toggle_game_master
+                                prop:disabled=move || !can_toggle_game_master() || ws_sender.get().is_none()
+                                style=move || format!(
+                                    "padding: 0.75rem; background: {}; color: {}; border: none; \
+                                     border-radius: 0.3125rem; font-size: 1rem; cursor: {}; font-weight: 700; opacity: {};",
+                                    theme.ui_button_primary,
+                                    theme.ui_text_primary,
+                                    if can_toggle_game_master() { "pointer" } else { "not-allowed" },
+                                    if can_toggle_game_master() { "1" } else { "0.55" },
+                                )
+                            >
+                                {move || if is_game_master() {
+                                    t!(i18n, settings.game_master_release).into_any()
+                                } else {
+                                    t!(i18n, settings.game_master_claim).into_any()
+                                }}
+                            </button>
+                        </div>
+
                         <div style=format!(
                             "display: flex; flex-direction: column; gap: 0.55rem; padding: 0.9rem 1rem; \
                              border-radius: 0.625rem; background: {}; color: {};",
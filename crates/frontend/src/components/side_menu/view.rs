@@ -1,6 +1,7 @@
 use super::view_model::SideMenuViewModel;
 use crate::config::Theme;
 use crate::i18n::i18n::{t_string, use_i18n};
+use crate::utils::viewport::{is_compact_viewport, use_viewport_size};
 use leptos::prelude::*;
 
 const MENU_BUTTON_FONT_SIZE: &str = "clamp(0.875rem, 0.84rem + 0.16vw, 1rem)";
@@ -14,6 +15,10 @@ pub fn SideMenu(
     on_notes_open: Callback<()>,
     on_scenes_open: Callback<()>,
     on_tokens_open: Callback<()>,
+    on_characters_open: Callback<()>,
+    on_compendium_open: Callback<()>,
+    on_encounters_open: Callback<()>,
+    on_handouts_open: Callback<()>,
     on_settings_open: Callback<()>,
     on_statistics_open: Callback<()>,
     on_voting_open: Callback<()>,
@@ -32,6 +37,10 @@ pub fn SideMenu(
     let form_bg = theme.ui_bg_primary;
     let menu_width_open = "15.625rem";
     let menu_width_closed = "3.75rem";
+    // Compact viewports hide the closed menu entirely and open it over the
+    // page, closing it again once something is picked.
+    let viewport = use_viewport_size();
+    let is_compact = move || is_compact_viewport(viewport.get().0);
 
     view! {
         <div>
@@ -48,11 +57,25 @@ pub fn SideMenu(
                 "☰"
             </button>
 
+            <Show when=move || is_compact() && vm.is_open.get()>
+                <div
+                    on:click=move |_| vm.close()
+                    style="position: fixed; inset: 0; z-index: 998; background: rgba(0,0,0,0.45);"
+                />
+            </Show>
+
             // Slide-in menu panel
             <div
                 style=move || {
+                    let width = if is_compact() {
+                        format!("min({}, 85vw)", menu_width_open)
+                    } else {
+                        menu_width_open.to_string()
+                    };
                     let left = if vm.is_open.get() {
                         "0".to_string()
+                    } else if is_compact() {
+                        "-100vw".to_string()
                     } else {
                         format!("calc(-{} + {})", menu_width_open, menu_width_closed)
                     };
@@ -61,12 +84,15 @@ pub fn SideMenu(
                         "position: fixed; top: 0; left: {}; width: {}; height: 100vh; \
                          background: {}; box-shadow: 0.125rem 0 0.625rem rgba(0,0,0,0.3); \
                          transition: all 0.3s ease; z-index: 999; \
-                         padding: 4.375rem {} 1.25rem {}; overflow: hidden;",
-                        left, menu_width_open, form_bg, pr, pl
+                         padding: 4.375rem {} 1.25rem {}; overflow: hidden auto;",
+                        left, width, form_bg, pr, pl
                     )
                 }
             >
-                <div style="display: flex; flex-direction: column; gap: 0.625rem;">
+                <div
+                    on:click=move |_| if is_compact() { vm.close() }
+                    style="display: flex; flex-direction: column; gap: 0.625rem;"
+                >
 
                     // Chat button
                     <button
@@ -211,6 +237,134 @@ pub fn SideMenu(
                         } else { ().into_any() }}
                     </button>
 
+                    // Characters button
+                    <button
+                        on:click=move |_| on_characters_open.run(())
+                        style=format!(
+                            "padding: 0.75rem; background: {}; color: {}; border: none; border-radius: 0.3125rem; \
+                             cursor: pointer; display: flex; justify-content: space-between; align-items: center; \
+                             transition: background 0.2s; min-width: 0; font-size: {};",
+                            button_bg, theme.ui_text_primary, MENU_BUTTON_FONT_SIZE
+                        )
+                        onmouseover=format!("this.style.background='{}'", button_hover)
+                        onmouseout=format!("this.style.background='{}'", button_bg)
+                    >
+                        <span style=move || if vm.is_open.get() {
+                            "white-space: nowrap;".to_string()
+                        } else {
+                            "white-space: nowrap; overflow: hidden; text-overflow: ellipsis;".to_string()
+                        }>
+                            {"📜 "}
+                            {move || if vm.is_open.get() { t_string!(i18n, menu.characters) } else { "" }}
+                        </span>
+                        {move || if !vm.is_open.get() {
+                            view! {
+                                <span style=format!(
+                                    "color: {}; font-size: {}; margin-left: 0.5rem;",
+                                    theme.ui_text_secondary, MENU_META_FONT_SIZE
+                                )>
+                                    {t_string!(i18n, menu.hotkey_characters)}
+                                </span>
+                            }.into_any()
+                        } else { ().into_any() }}
+                    </button>
+
+                    // Compendium button
+                    <button
+                        on:click=move |_| on_compendium_open.run(())
+                        style=format!(
+                            "padding: 0.75rem; background: {}; color: {}; border: none; border-radius: 0.3125rem; \
+                             cursor: pointer; display: flex; justify-content: space-between; align-items: center; \
+                             transition: background 0.2s; min-width: 0; font-size: {};",
+                            button_bg, theme.ui_text_primary, MENU_BUTTON_FONT_SIZE
+                        )
+                        onmouseover=format!("this.style.background='{}'", button_hover)
+                        onmouseout=format!("this.style.background='{}'", button_bg)
+                    >
+                        <span style=move || if vm.is_open.get() {
+                            "white-space: nowrap;".to_string()
+                        } else {
+                            "white-space: nowrap; overflow: hidden; text-overflow: ellipsis;".to_string()
+                        }>
+                            {"📚 "}
+                            {move || if vm.is_open.get() { t_string!(i18n, menu.compendium) } else { "" }}
+                        </span>
+                        {move || if !vm.is_open.get() {
+                            view! {
+                                <span style=format!(
+                                    "color: {}; font-size: {}; margin-left: 0.5rem;",
+                                    theme.ui_text_secondary, MENU_META_FONT_SIZE
+                                )>
+                                    {t_string!(i18n, menu.hotkey_compendium)}
+                                </span>
+                            }.into_any()
+                        } else { ().into_any() }}
+                    </button>
+
+                    // Encounters button
+                    <button
+                        on:click=move |_| on_encounters_open.run(())
+                        style=format!(
+                            "padding: 0.75rem; background: {}; color: {}; border: none; border-radius: 0.3125rem; \
+                             cursor: pointer; display: flex; justify-content: space-between; align-items: center; \
+                             transition: background 0.2s; min-width: 0; font-size: {};",
+                            button_bg, theme.ui_text_primary, MENU_BUTTON_FONT_SIZE
+                        )
+                        onmouseover=format!("this.style.background='{}'", button_hover)
+                        onmouseout=format!("this.style.background='{}'", button_bg)
+                    >
+                        <span style=move || if vm.is_open.get() {
+                            "white-space: nowrap;".to_string()
+                        } else {
+                            "white-space: nowrap; overflow: hidden; text-overflow: ellipsis;".to_string()
+                        }>
+                            {"⚔️ "}
+                            {move || if vm.is_open.get() { t_string!(i18n, menu.encounters) } else { "" }}
+                        </span>
+                        {move || if !vm.is_open.get() {
+                            view! {
+                                <span style=format!(
+                                    "color: {}; font-size: {}; margin-left: 0.5rem;",
+                                    theme.ui_text_secondary, MENU_META_FONT_SIZE
+                                )>
+                                    {t_string!(i18n, menu.hotkey_encounters)}
+                                </span>
+                            }.into_any()
+                        } else { ().into_any() }}
+                    </button>
+
+                    // Handouts button
+                    <button
+                        on:click=move |_| on_handouts_open.run(())
+                        style=format!(
+                            "padding: 0.75rem; background: {}; color: {}; border: none; border-radius: 0.3125rem; \
+                             cursor: pointer; display: flex; justify-content: space-between; align-items: center; \
+                             transition: background 0.2s; min-width: 0; font-size: {};",
+                            button_bg, theme.ui_text_primary, MENU_BUTTON_FONT_SIZE
+                        )
+                        onmouseover=format!("this.style.background='{}'", button_hover)
+                        onmouseout=format!("this.style.background='{}'", button_bg)
+                    >
+                        <span style=move || if vm.is_open.get() {
+                            "white-space: nowrap;".to_string()
+                        } else {
+                            "white-space: nowrap; overflow: hidden; text-overflow: ellipsis;".to_string()
+                        }>
+                            {"📜 "}
+                            {move || if vm.is_open.get() { t_string!(i18n, menu.handouts) } else { "" }}
+                        </span>
+                        {move || if !vm.is_open.get() {
+                            view! {
+                                <span style=format!(
+                                    "color: {}; font-size: {}; margin-left: 0.5rem;",
+                                    theme.ui_text_secondary, MENU_META_FONT_SIZE
+                                )>
+                                    {t_string!(i18n, menu.hotkey_handouts)}
+                                </span>
+                            }.into_any()
+                        } else { ().into_any() }}
+                    </button>
+
                     // Voting button
                     <button
                         on:click=move |_| on_voting_open.run(())
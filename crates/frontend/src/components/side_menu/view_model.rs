@@ -14,6 +14,10 @@ impl SideMenuViewModel {
     pub fn toggle(&self) {
         self.is_open.update(|open| *open = !*open);
     }
+
+    pub fn close(&self) {
+        self.is_open.set(false);
+    }
 }
 
 #[cfg(test)]
@@ -54,4 +58,15 @@ mod tests {
             assert!(!vm.is_open.get_untracked());
         });
     }
+
+    #[test]
+    fn close_closes_open_menu() {
+        let owner = Owner::new();
+        owner.with(|| {
+            let is_open = RwSignal::new(true);
+            let vm = SideMenuViewModel::new(is_open);
+            vm.close();
+            assert!(!vm.is_open.get_untracked());
+        });
+    }
 }
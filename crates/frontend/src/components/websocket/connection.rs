@@ -1,3 +1,4 @@
+use crate::components::notes::collab::NoteEditSession;
 use crate::components::statistics::StateEvent;
 use crate::components::voting::VotingState;
 use crate::components::websocket::{
@@ -12,11 +13,12 @@ use leptos::prelude::*;
 use leptos::task::spawn_local;
 use rand::seq::IndexedRandom;
 use shared::events::{
-    ChatMessagePayload, ClientEvent, EncryptedPayloadKind, NotePayload, RoomState, Scene,
-    SyncSnapshotRequestPayload, VotingResultPayload,
+    CharacterSheet, ChatMessagePayload, ClientEvent, CompendiumSource, EncryptedPayloadKind,
+    Handout, NotePayload, RoomState, Scene, SheetTemplate, SyncSnapshotRequestPayload,
+    VotingResultPayload,
 };
 use std::cell::RefCell;
-use std::collections::{HashMap, VecDeque};
+use std::collections::{BTreeMap, HashMap, VecDeque};
 use std::rc::Rc;
 use std::sync::{Arc, Mutex};
 
@@ -123,6 +125,8 @@ impl WsSender {
             ClientEvent::Ping
             | ClientEvent::ChatMessage(_)
             | ClientEvent::MouseClickPayload(_)
+            | ClientEvent::BoardCamera(_)
+            | ClientEvent::BoardViewport(_)
             | ClientEvent::VotingCast(_)
             | ClientEvent::SyncSnapshotRequest(_)
             | ClientEvent::SyncSnapshot(_) => OutboundPriority::High,
@@ -132,11 +136,15 @@ impl WsSender {
                 EncryptedPayloadKind::Chat
                 | EncryptedPayloadKind::Note
                 | EncryptedPayloadKind::Sync => OutboundPriority::High,
-                EncryptedPayloadKind::FileControl => OutboundPriority::Normal,
+                EncryptedPayloadKind::FileControl
+                | EncryptedPayloadKind::Character
+                | EncryptedPayloadKind::Handout => OutboundPriority::Normal,
             },
             ClientEvent::RoomState(_)
             | ClientEvent::NoteUpsert(_)
             | ClientEvent::NoteDelete(_)
+            | ClientEvent::NoteEdit(_)
+            | ClientEvent::NoteCaret(_)
             | ClientEvent::FileAnnounce(_)
             | ClientEvent::FileRequest(_)
             | ClientEvent::FileAbort(_)
@@ -144,7 +152,17 @@ impl WsSender {
             | ClientEvent::SceneUpdate(_)
             | ClientEvent::SceneDelete(_)
             | ClientEvent::SceneActivate(_)
+            | ClientEvent::SceneAssign(_)
             | ClientEvent::TokenMove(_)
+            | ClientEvent::GameMasterSet(_)
+            | ClientEvent::CharacterSheetUpsert(_)
+            | ClientEvent::CharacterSheetDelete(_)
+            | ClientEvent::CharacterSheetSecrets(_)
+            | ClientEvent::SheetTemplateUpsert(_)
+            | ClientEvent::CompendiumImport(_)
+            | ClientEvent::CompendiumRemove(_)
+            | ClientEvent::HandoutShow(_)
+            | ClientEvent::HandoutDelete(_)
             | ClientEvent::SyncRequest
             | ClientEvent::SyncVersionAnnounce(_)
             | ClientEvent::VotingStart(_)
@@ -153,10 +171,12 @@ impl WsSender {
             | ClientEvent::PresenceRequest(_)
             | ClientEvent::PresenceResponse(_)
             | ClientEvent::PresenceAnnounce(_)
+            | ClientEvent::PresenceLeave(_)
             | ClientEvent::CryptoKeyAnnounce(_)
             | ClientEvent::CryptoKeyWrap(_)
             | ClientEvent::BoardPointer(_)
             | ClientEvent::AttentionPing(_)
+            | ClientEvent::BoardFollow(_)
             | ClientEvent::DirectMessage(_) => OutboundPriority::Normal,
         }
     }
@@ -178,6 +198,14 @@ pub struct ConnectWebSocketArgs {
     pub state_events: RwSignal<Vec<StateEvent>>,
     pub scenes_signal: RwSignal<Vec<Scene>>,
     pub active_scene_id_signal: RwSignal<Option<String>>,
+    pub scene_assignments_signal: RwSignal<BTreeMap<String, String>>,
+    pub game_master_signal: RwSignal<Option<String>>,
+    pub character_sheets_signal: RwSignal<Vec<CharacterSheet>>,
+    pub sheet_secrets_signal: RwSignal<BTreeMap<String, BTreeMap<String, String>>>,
+    pub sheet_templates_signal: RwSignal<Vec<SheetTemplate>>,
+    pub compendium_sources_signal: RwSignal<Vec<CompendiumSource>>,
+    pub handouts_signal: RwSignal<Vec<Handout>>,
+    pub private_handouts_signal: RwSignal<Vec<Handout>>,
     pub conflict_signal: RwSignal<Option<SyncConflict>>,
     pub votings: RwSignal<HashMap<String, VotingState>>,
     pub voting_results: RwSignal<HashMap<String, VotingResultPayload>>,
@@ -189,7 +217,12 @@ pub struct ConnectWebSocketArgs {
     pub conflict_resolution_handle: ConflictResolutionHandle,
     pub board_pointers: RwSignal<std::collections::HashSet<String>>,
     pub attention_pings: RwSignal<Vec<shared::events::AttentionPingPayload>>,
+    pub board_camera: RwSignal<Option<shared::events::BoardCameraPayload>>,
+    pub camera_followers: RwSignal<std::collections::HashSet<String>>,
+    pub board_viewports: RwSignal<HashMap<String, shared::events::BoardViewportPayload>>,
+    pub presented_handout: RwSignal<Option<String>>,
     pub direct_messages: RwSignal<Vec<shared::events::DirectMessagePayload>>,
+    pub note_edit_session: RwSignal<Option<NoteEditSession>>,
 }
 
 struct MessageProcessingContext {
@@ -212,6 +245,14 @@ struct MessageProcessingContext {
     state_events: RwSignal<Vec<StateEvent>>,
     scenes_signal: RwSignal<Vec<Scene>>,
     active_scene_id_signal: RwSignal<Option<String>>,
+    scene_assignments_signal: RwSignal<BTreeMap<String, String>>,
+    game_master_signal: RwSignal<Option<String>>,
+    character_sheets_signal: RwSignal<Vec<CharacterSheet>>,
+    sheet_secrets_signal: RwSignal<BTreeMap<String, BTreeMap<String, String>>>,
+    sheet_templates_signal: RwSignal<Vec<SheetTemplate>>,
+    compendium_sources_signal: RwSignal<Vec<CompendiumSource>>,
+    handouts_signal: RwSignal<Vec<Handout>>,
+    private_handouts_signal: RwSignal<Vec<Handout>>,
     conflict_signal: RwSignal<Option<SyncConflict>>,
     votings: RwSignal<HashMap<String, VotingState>>,
     voting_results: RwSignal<HashMap<String, VotingResultPayload>>,
@@ -226,7 +267,12 @@ struct MessageProcessingContext {
     is_collecting_announces: Rc<RefCell<bool>>,
     board_pointers: RwSignal<std::collections::HashSet<String>>,
     attention_pings: RwSignal<Vec<shared::events::AttentionPingPayload>>,
+    board_camera: RwSignal<Option<shared::events::BoardCameraPayload>>,
+    camera_followers: RwSignal<std::collections::HashSet<String>>,
+    board_viewports: RwSignal<HashMap<String, shared::events::BoardViewportPayload>>,
+    presented_handout: RwSignal<Option<String>>,
     direct_messages: RwSignal<Vec<shared::events::DirectMessagePayload>>,
+    note_edit_session: RwSignal<Option<NoteEditSession>>,
 }
 
 impl MessageProcessingContext {
@@ -252,6 +298,14 @@ impl MessageProcessingContext {
             state_events: self.state_events,
             scenes_signal: self.scenes_signal,
             active_scene_id_signal: self.active_scene_id_signal,
+            scene_assignments_signal: self.scene_assignments_signal,
+            game_master_signal: self.game_master_signal,
+            character_sheets_signal: self.character_sheets_signal,
+            sheet_secrets_signal: self.sheet_secrets_signal,
+            sheet_templates_signal: self.sheet_templates_signal,
+            compendium_sources_signal: self.compendium_sources_signal,
+            handouts_signal: self.handouts_signal,
+            private_handouts_signal: self.private_handouts_signal,
             conflict_signal: self.conflict_signal,
             votings: self.votings,
             voting_results: self.voting_results,
@@ -266,7 +320,12 @@ impl MessageProcessingContext {
             is_collecting_announces: &self.is_collecting_announces,
             board_pointers: self.board_pointers,
             attention_pings: self.attention_pings,
+            board_camera: self.board_camera,
+            camera_followers: self.camera_followers,
+            board_viewports: self.board_viewports,
+            presented_handout: self.presented_handout,
             direct_messages: self.direct_messages,
+            note_edit_session: self.note_edit_session,
         }
     }
 }
@@ -288,6 +347,14 @@ pub fn connect_websocket(args: ConnectWebSocketArgs) {
         state_events,
         scenes_signal,
         active_scene_id_signal,
+        scene_assignments_signal,
+        game_master_signal,
+        character_sheets_signal,
+        sheet_secrets_signal,
+        sheet_templates_signal,
+        compendium_sources_signal,
+        handouts_signal,
+        private_handouts_signal,
         conflict_signal,
         votings,
         voting_results,
@@ -299,7 +366,12 @@ pub fn connect_websocket(args: ConnectWebSocketArgs) {
         conflict_resolution_handle,
         board_pointers,
         attention_pings,
+        board_camera,
+        camera_followers,
+        board_viewports,
+        presented_handout,
         direct_messages,
+        note_edit_session,
     } = args;
 
     // Инициализация состояния
@@ -344,6 +416,12 @@ pub fn connect_websocket(args: ConnectWebSocketArgs) {
                 voting_results.set(data.state.voting_results.clone());
                 scenes_signal.set(data.state.scenes.clone());
                 active_scene_id_signal.set(data.state.active_scene_id.clone());
+                scene_assignments_signal.set(data.state.scene_assignments.clone());
+                game_master_signal.set(data.state.game_master.clone());
+                character_sheets_signal.set(data.state.character_sheets.clone());
+                sheet_templates_signal.set(data.state.sheet_templates.clone());
+                compendium_sources_signal.set(data.state.compendium_sources.clone());
+                handouts_signal.set(data.state.handouts.clone());
             }
             Ok(None) => {}
             Err(error) => log!("Failed to load state from IndexedDB: {}", error),
@@ -399,6 +477,12 @@ pub fn connect_websocket(args: ConnectWebSocketArgs) {
                 let public_notes_signal_for_callback = public_notes_signal;
                 let scenes_signal_for_callback = scenes_signal;
                 let active_scene_id_signal_for_callback = active_scene_id_signal;
+                let scene_assignments_signal_for_callback = scene_assignments_signal;
+                let game_master_signal_for_callback = game_master_signal;
+                let character_sheets_signal_for_callback = character_sheets_signal;
+                let sheet_templates_signal_for_callback = sheet_templates_signal;
+                let compendium_sources_signal_for_callback = compendium_sources_signal;
+                let handouts_signal_for_callback = handouts_signal;
                 let voting_results_for_callback = voting_results;
                 let conflict_signal_for_callback = conflict_signal;
                 let state_events_for_callback = state_events;
@@ -414,6 +498,12 @@ pub fn connect_websocket(args: ConnectWebSocketArgs) {
                     public_notes_signal_for_callback.set(Vec::new());
                     scenes_signal_for_callback.set(Vec::new());
                     active_scene_id_signal_for_callback.set(None);
+                    scene_assignments_signal_for_callback.set(BTreeMap::new());
+                    game_master_signal_for_callback.set(None);
+                    character_sheets_signal_for_callback.set(Vec::new());
+                    sheet_templates_signal_for_callback.set(Vec::new());
+                    compendium_sources_signal_for_callback.set(Vec::new());
+                    handouts_signal_for_callback.set(Vec::new());
                     voting_results_for_callback.set(HashMap::new());
                     conflict_signal_for_callback.set(None);
                     file_transfer_for_callback.reset();
@@ -488,6 +578,14 @@ pub fn connect_websocket(args: ConnectWebSocketArgs) {
                         state_events,
                         scenes_signal,
                         active_scene_id_signal,
+                        scene_assignments_signal,
+                        game_master_signal,
+                        character_sheets_signal,
+                        sheet_secrets_signal,
+                        sheet_templates_signal,
+                        compendium_sources_signal,
+                        handouts_signal,
+                        private_handouts_signal,
                         conflict_signal,
                         votings,
                         voting_results,
@@ -502,7 +600,12 @@ pub fn connect_websocket(args: ConnectWebSocketArgs) {
                         is_collecting_announces: is_collecting_announces_it,
                         board_pointers,
                         attention_pings,
+                        board_camera,
+                        camera_followers,
+                        board_viewports,
+                        presented_handout,
                         direct_messages,
+                        note_edit_session,
                     },
                 )
                 .await;
@@ -6,7 +6,7 @@ use chacha20poly1305::{
 use sha2::{Digest, Sha256};
 use shared::events::{
     ClientEvent, CryptoKeyAnnouncePayload, CryptoKeyWrapPayload, CryptoPayload,
-    EncryptedPayloadKind,
+    EncryptedPayloadKind, handout_audience,
 };
 use std::collections::{HashMap, HashSet};
 use x25519_dalek::{PublicKey, StaticSecret};
@@ -16,6 +16,15 @@ const KEY_WRAP_CONTEXT: &[u8] = b"dnd-vtt-key-wrap-v1";
 const PAYLOAD_CONTEXT: &[u8] = b"dnd-vtt-payload-v1";
 const MAX_PENDING_PER_KEY: usize = 64;
 
+/// Key for events addressed to a fixed set of users (handouts, direct and group
+/// notes); it is wrapped only for those users, so nobody else in the room can
+/// read them.
+struct AudienceKey {
+    key_id: String,
+    key: [u8; 32],
+    wrapped_for: HashSet<String>,
+}
+
 pub struct RoomCryptoState {
     room_name: String,
     my_username: String,
@@ -25,6 +34,12 @@ pub struct RoomCryptoState {
     outbound_key_id: Option<String>,
     outbound_room_key: Option<[u8; 32]>,
     outbound_wrapped_for: HashSet<String>,
+    /// Keyed by the sorted audience, author included.
+    audience_keys: HashMap<Vec<String>, AudienceKey>,
+    /// Audience each note, handout or sheet secret was last sent to; a key
+    /// no item uses any more is dropped, so members removed from a group
+    /// never get its key back.
+    item_audiences: HashMap<String, Vec<String>>,
     inbound_keys: HashMap<String, [u8; 32]>,
     pending_payloads: HashMap<String, Vec<CryptoPayload>>,
 }
@@ -44,6 +59,8 @@ impl RoomCryptoState {
             outbound_key_id: None,
             outbound_room_key: None,
             outbound_wrapped_for: HashSet::new(),
+            audience_keys: HashMap::new(),
+            item_audiences: HashMap::new(),
             inbound_keys: HashMap::new(),
             pending_payloads: HashMap::new(),
         }
@@ -118,14 +135,25 @@ impl RoomCryptoState {
     ) -> Result<Vec<ClientEvent>, String> {
         let kind =
             Self::kind_for_event(event).ok_or_else(|| "event is not encryptable".to_string())?;
-        self.ensure_outbound_key();
-        let key_id = self
-            .outbound_key_id
-            .clone()
-            .ok_or_else(|| "missing outbound key id".to_string())?;
-        let room_key = self
-            .outbound_room_key
-            .ok_or_else(|| "missing outbound room key".to_string())?;
+        let (key_id, room_key) = match self.audience_for_event(event) {
+            Some(audience) => {
+                if let Some(item) = Self::audience_item_for_event(event) {
+                    self.track_item_audience(item, &audience);
+                }
+                self.ensure_audience_key(audience)
+            }
+            None => {
+                self.ensure_outbound_key();
+                let key_id = self
+                    .outbound_key_id
+                    .clone()
+                    .ok_or_else(|| "missing outbound key id".to_string())?;
+                let room_key = self
+                    .outbound_room_key
+                    .ok_or_else(|| "missing outbound room key".to_string())?;
+                (key_id, room_key)
+            }
+        };
 
         let mut events = self.wrap_events_for_known_peers()?;
         let plaintext = serde_json::to_vec(event)
@@ -143,6 +171,13 @@ impl RoomCryptoState {
             nonce_b64: BASE64.encode(nonce),
             ciphertext_b64: BASE64.encode(ciphertext),
         }));
+        if matches!(
+            event,
+            ClientEvent::NoteDelete(_) | ClientEvent::HandoutDelete(_)
+        ) && let Some(item) = Self::audience_item_for_event(event)
+        {
+            self.forget_item_audience(&item);
+        }
 
         Ok(events)
     }
@@ -172,6 +207,13 @@ impl RoomCryptoState {
         {
             return Some(key);
         }
+        if let Some(audience_key) = self
+            .audience_keys
+            .values()
+            .find(|audience_key| audience_key.key_id == key_id)
+        {
+            return Some(audience_key.key);
+        }
         self.inbound_keys.get(key_id).copied()
     }
 
@@ -204,13 +246,6 @@ impl RoomCryptoState {
     }
 
     fn wrap_events_for_known_peers(&mut self) -> Result<Vec<ClientEvent>, String> {
-        let Some(key_id) = self.outbound_key_id.clone() else {
-            return Ok(Vec::new());
-        };
-        let Some(room_key) = self.outbound_room_key else {
-            return Ok(Vec::new());
-        };
-
         let peers = self
             .peers
             .iter()
@@ -218,18 +253,47 @@ impl RoomCryptoState {
             .collect::<Vec<_>>();
         let mut events = Vec::new();
 
-        for (peer_username, peer_public) in peers {
-            if self.outbound_wrapped_for.contains(&peer_username) {
-                continue;
+        if let (Some(key_id), Some(room_key)) =
+            (self.outbound_key_id.clone(), self.outbound_room_key)
+        {
+            for (peer_username, peer_public) in &peers {
+                if self.outbound_wrapped_for.contains(peer_username) {
+                    continue;
+                }
+
+                events.push(self.build_key_wrap_event(
+                    &key_id,
+                    room_key,
+                    peer_username,
+                    *peer_public,
+                )?);
+                self.outbound_wrapped_for.insert(peer_username.clone());
             }
+        }
 
+        let mut audience_wraps = Vec::new();
+        for (audience, audience_key) in &self.audience_keys {
+            for (peer_username, peer_public) in &peers {
+                if audience.contains(peer_username)
+                    && !audience_key.wrapped_for.contains(peer_username)
+                {
+                    audience_wraps.push((audience.clone(), peer_username.clone(), *peer_public));
+                }
+            }
+        }
+        for (audience, peer_username, peer_public) in audience_wraps {
+            let Some(audience_key) = self.audience_keys.get(&audience) else {
+                continue;
+            };
             events.push(self.build_key_wrap_event(
-                &key_id,
-                room_key,
+                &audience_key.key_id,
+                audience_key.key,
                 &peer_username,
                 peer_public,
             )?);
-            self.outbound_wrapped_for.insert(peer_username);
+            if let Some(audience_key) = self.audience_keys.get_mut(&audience) {
+                audience_key.wrapped_for.insert(peer_username);
+            }
         }
 
         Ok(events)
@@ -274,6 +338,44 @@ impl RoomCryptoState {
         self.outbound_wrapped_for.clear();
     }
 
+    fn ensure_audience_key(&mut self, audience: Vec<String>) -> (String, [u8; 32]) {
+        let audience_key = self
+            .audience_keys
+            .entry(audience)
+            .or_insert_with(|| AudienceKey {
+                key_id: uuid::Uuid::new_v4().to_string(),
+                key: rand::random(),
+                wrapped_for: HashSet::new(),
+            });
+        (audience_key.key_id.clone(), audience_key.key)
+    }
+
+    /// Records the audience `item` is sent to now; when it changed, the key of
+    /// the previous audience is dropped unless another item still uses it.
+    fn track_item_audience(&mut self, item: String, audience: &[String]) {
+        if let Some(previous) = self.item_audiences.insert(item, audience.to_vec())
+            && previous != audience
+        {
+            self.prune_audience_key(&previous);
+        }
+    }
+
+    fn forget_item_audience(&mut self, item: &str) {
+        if let Some(previous) = self.item_audiences.remove(item) {
+            self.prune_audience_key(&previous);
+        }
+    }
+
+    fn prune_audience_key(&mut self, audience: &[String]) {
+        if !self
+            .item_audiences
+            .values()
+            .any(|other| other.as_slice() == audience)
+        {
+            self.audience_keys.remove(audience);
+        }
+    }
+
     fn store_pending(&mut self, payload: &CryptoPayload) {
         let pending = self
             .pending_payloads
@@ -303,19 +405,63 @@ impl RoomCryptoState {
         Ok(decrypted)
     }
 
+    /// Users allowed to read `event`, or `None` when the whole room may.
+    fn audience_for_event(&self, event: &ClientEvent) -> Option<Vec<String>> {
+        match event {
+            ClientEvent::NoteUpsert(payload) => payload.visibility.audience(&payload.author),
+            ClientEvent::NoteDelete(payload) => payload.visibility.audience(&payload.author),
+            ClientEvent::CharacterSheetSecrets(payload) => payload.audience.clone(),
+            ClientEvent::HandoutShow(payload) => payload.handout.audience(&payload.actor),
+            ClientEvent::HandoutDelete(payload) => {
+                handout_audience(&payload.recipients, &payload.actor)
+            }
+            // Chunks are only for the requester, so files shared with a few
+            // players never become readable by the rest of the room.
+            ClientEvent::FileChunk(payload) => {
+                let mut audience = vec![self.my_username.clone(), payload.requester.clone()];
+                audience.sort();
+                audience.dedup();
+                Some(audience)
+            }
+            _ => None,
+        }
+    }
+
+    /// Note, handout or sheet whose audience `event` is encrypted for.
+    fn audience_item_for_event(event: &ClientEvent) -> Option<String> {
+        match event {
+            ClientEvent::NoteUpsert(payload) => Some(format!("note:{}", payload.id)),
+            ClientEvent::NoteDelete(payload) => Some(format!("note:{}", payload.id)),
+            ClientEvent::CharacterSheetSecrets(payload) => {
+                Some(format!("sheet:{}", payload.sheet_id))
+            }
+            ClientEvent::HandoutShow(payload) => Some(format!("handout:{}", payload.handout.id)),
+            ClientEvent::HandoutDelete(payload) => Some(format!("handout:{}", payload.id)),
+            _ => None,
+        }
+    }
+
     fn kind_for_event(event: &ClientEvent) -> Option<EncryptedPayloadKind> {
         match event {
             ClientEvent::ChatMessage(_) | ClientEvent::DirectMessage(_) => {
                 Some(EncryptedPayloadKind::Chat)
             }
-            ClientEvent::NoteUpsert(_) | ClientEvent::NoteDelete(_) => {
-                Some(EncryptedPayloadKind::Note)
-            }
+            ClientEvent::NoteUpsert(_)
+            | ClientEvent::NoteDelete(_)
+            | ClientEvent::NoteEdit(_)
+            | ClientEvent::NoteCaret(_) => Some(EncryptedPayloadKind::Note),
             ClientEvent::SyncSnapshot(_) => Some(EncryptedPayloadKind::Sync),
             ClientEvent::FileAnnounce(_)
             | ClientEvent::FileRequest(_)
             | ClientEvent::FileAbort(_) => Some(EncryptedPayloadKind::FileControl),
             ClientEvent::FileChunk(_) => Some(EncryptedPayloadKind::FileChunk),
+            ClientEvent::CharacterSheetUpsert(_)
+            | ClientEvent::CharacterSheetDelete(_)
+            | ClientEvent::CharacterSheetSecrets(_)
+            | ClientEvent::SheetTemplateUpsert(_) => Some(EncryptedPayloadKind::Character),
+            ClientEvent::HandoutShow(_) | ClientEvent::HandoutDelete(_) => {
+                Some(EncryptedPayloadKind::Handout)
+            }
             _ => None,
         }
     }
@@ -425,6 +571,8 @@ fn payload_aad(
         EncryptedPayloadKind::Sync => b"SYNC".as_slice(),
         EncryptedPayloadKind::FileControl => b"FILE_CONTROL".as_slice(),
         EncryptedPayloadKind::FileChunk => b"FILE_CHUNK".as_slice(),
+        EncryptedPayloadKind::Character => b"CHARACTER".as_slice(),
+        EncryptedPayloadKind::Handout => b"HANDOUT".as_slice(),
     };
     let mut hasher = Sha256::new();
     hasher.update(PAYLOAD_CONTEXT);
@@ -441,7 +589,17 @@ fn payload_aad(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use shared::events::{ChatMessagePayload, SyncSnapshotPackedStatePayload, SyncSnapshotPayload};
+    use shared::events::{
+        ChatMessagePayload, FileChunkPayload, NoteDeletePayload, NoteGroup, NoteVisibility,
+        SyncSnapshotPackedStatePayload, SyncSnapshotPayload,
+    };
+
+    fn announce(state: &RoomCryptoState) -> CryptoKeyAnnouncePayload {
+        match state.key_announce_event() {
+            ClientEvent::CryptoKeyAnnounce(payload) => payload,
+            _ => unreachable!(),
+        }
+    }
 
     #[test]
     fn key_wrap_then_payload_round_trip_between_clients() {
@@ -492,6 +650,133 @@ mod tests {
         }
     }
 
+    #[test]
+    fn group_notes_are_only_readable_by_the_group() {
+        let mut alice = RoomCryptoState::new("room-alpha", "alice");
+        let mut bob = RoomCryptoState::new("room-alpha", "bob");
+        let mut carol = RoomCryptoState::new("room-alpha", "carol");
+        for peer in [&bob, &carol] {
+            alice.handle_key_announce(&announce(peer)).unwrap();
+        }
+
+        let note = ClientEvent::NoteDelete(NoteDeletePayload {
+            id: "note-1".to_string(),
+            author: "alice".to_string(),
+            visibility: NoteVisibility::Group(NoteGroup::new("Party A", ["bob".to_string()])),
+        });
+        let outbound = alice.prepare_encrypted_events(&note).unwrap();
+        let wrapped_for = outbound
+            .iter()
+            .filter_map(|event| match event {
+                ClientEvent::CryptoKeyWrap(payload) => Some(payload.recipient_username.as_str()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(wrapped_for, vec!["bob"]);
+
+        let mut readers = Vec::new();
+        for (name, state) in [("bob", &mut bob), ("carol", &mut carol)] {
+            let mut decrypted = None;
+            for event in &outbound {
+                match event {
+                    ClientEvent::CryptoKeyWrap(payload) => {
+                        state.handle_key_wrap(payload).unwrap();
+                    }
+                    ClientEvent::CryptoPayload(payload) => {
+                        decrypted = state.decrypt_payload(payload).unwrap();
+                    }
+                    _ => {}
+                }
+            }
+            if decrypted.is_some() {
+                readers.push(name);
+            }
+        }
+        assert_eq!(readers, vec!["bob"]);
+    }
+
+    #[test]
+    fn removed_group_members_cannot_read_later_notes() {
+        let mut alice = RoomCryptoState::new("room-alpha", "alice");
+        let mut bob = RoomCryptoState::new("room-alpha", "bob");
+        alice.handle_key_announce(&announce(&bob)).unwrap();
+        alice
+            .handle_key_announce(&announce(&RoomCryptoState::new("room-alpha", "carol")))
+            .unwrap();
+
+        let note = |members: &[&str]| {
+            let visibility = NoteVisibility::Group(NoteGroup::new(
+                "Party A",
+                members.iter().map(|member| member.to_string()),
+            ));
+            ClientEvent::NoteUpsert(
+                serde_json::from_value(serde_json::json!({
+                    "id": "note-1",
+                    "author": "alice",
+                    "visibility": visibility,
+                    "body": "Loot",
+                    "created_at_ms": 0.0,
+                    "updated_at_ms": 0.0,
+                }))
+                .unwrap(),
+            )
+        };
+        let payload_key = |events: &[ClientEvent]| {
+            events
+                .iter()
+                .find_map(|event| match event {
+                    ClientEvent::CryptoPayload(payload) => Some(payload.key_id.clone()),
+                    _ => None,
+                })
+                .unwrap()
+        };
+
+        let first = alice
+            .prepare_encrypted_events(&note(&["bob", "carol"]))
+            .unwrap();
+        for event in &first {
+            if let ClientEvent::CryptoKeyWrap(payload) = event {
+                bob.handle_key_wrap(payload).unwrap();
+            }
+        }
+        let without_bob = alice.prepare_encrypted_events(&note(&["carol"])).unwrap();
+        let again = alice
+            .prepare_encrypted_events(&note(&["bob", "carol"]))
+            .unwrap();
+
+        assert!(bob.inbound_keys.contains_key(&payload_key(&first)));
+        assert!(!bob.inbound_keys.contains_key(&payload_key(&without_bob)));
+        assert_ne!(payload_key(&again), payload_key(&first));
+    }
+
+    #[test]
+    fn file_chunks_are_wrapped_only_for_the_requester() {
+        let mut alice = RoomCryptoState::new("room-alpha", "alice");
+        for peer in ["bob", "carol"] {
+            alice
+                .handle_key_announce(&announce(&RoomCryptoState::new("room-alpha", peer)))
+                .unwrap();
+        }
+
+        let chunk = ClientEvent::FileChunk(FileChunkPayload {
+            hash: "abc".to_string(),
+            requester: "carol".to_string(),
+            chunk_index: 0,
+            total_chunks: 1,
+            data: "AA==".to_string(),
+        });
+        let wrapped_for = alice
+            .prepare_encrypted_events(&chunk)
+            .unwrap()
+            .into_iter()
+            .filter_map(|event| match event {
+                ClientEvent::CryptoKeyWrap(payload) => Some(payload.recipient_username),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(wrapped_for, vec!["carol".to_string()]);
+    }
+
     #[test]
     fn sync_snapshot_is_marked_as_encryptable() {
         let snapshot = ClientEvent::SyncSnapshot(SyncSnapshotPayload {
@@ -1,7 +1,8 @@
 mod utils;
+pub(crate) use utils::{blob_to_bytes, bytes_to_blob};
 use utils::{
-    blob_to_bytes, bytes_to_blob, collect_chat_files, collect_scene_files,
-    deterministic_holder_index, sha256_hex, validate_browser_file,
+    collect_chat_files, collect_scene_files, deterministic_holder_index, scene_backgrounds,
+    sha256_hex, sniff_mime_type, validate_browser_file,
 };
 
 use super::{OutboundPriority, WsSender, storage};
@@ -110,6 +111,9 @@ pub struct FileTransferState {
     known_files: Rc<RefCell<HashMap<String, FileRef>>>,
     requested_at: Rc<RefCell<HashMap<String, f64>>>,
     announced_local_hashes: Rc<RefCell<HashSet<String>>>,
+    /// Files shared with a fixed set of users: never announced and only sent
+    /// to those users.
+    restricted: Rc<RefCell<HashMap<String, BTreeSet<String>>>>,
     incoming: Rc<RefCell<HashMap<String, IncomingTransfer>>>,
     outgoing_queue: Rc<RefCell<VecDeque<OutgoingTransferJob>>>,
     active_outgoing: Rc<Cell<usize>>,
@@ -141,6 +145,7 @@ impl FileTransferState {
             known_files: Rc::new(RefCell::new(HashMap::new())),
             requested_at: Rc::new(RefCell::new(HashMap::new())),
             announced_local_hashes: Rc::new(RefCell::new(HashSet::new())),
+            restricted: Rc::new(RefCell::new(HashMap::new())),
             incoming: Rc::new(RefCell::new(HashMap::new())),
             outgoing_queue: Rc::new(RefCell::new(VecDeque::new())),
             active_outgoing: Rc::new(Cell::new(0)),
@@ -160,6 +165,8 @@ impl FileTransferState {
         self.known_files.borrow_mut().clear();
         self.requested_at.borrow_mut().clear();
         self.announced_local_hashes.borrow_mut().clear();
+        // `restricted` is kept: it mirrors handouts stored on this device,
+        // which a resync does not touch.
         self.incoming.borrow_mut().clear();
         self.outgoing_queue.borrow_mut().clear();
         self.active_outgoing.set(0);
@@ -168,7 +175,7 @@ impl FileTransferState {
     pub fn note_scenes(&self, scenes: &[Scene]) {
         let mut known_files = self.known_files.borrow_mut();
         for scene in scenes {
-            if let Some(background) = &scene.background {
+            for background in scene_backgrounds(scene) {
                 known_files.insert(background.hash.clone(), background.clone());
             }
             for token in &scene.tokens {
@@ -371,7 +378,7 @@ impl FileTransferState {
         username: String,
         ws_sender: Option<WsSender>,
     ) {
-        if payload.requester == username {
+        if payload.requester == username || !self.may_receive(&payload.hash, &payload.requester) {
             return;
         }
 
@@ -556,6 +563,82 @@ impl FileTransferState {
             size: bytes.len() as u64,
         };
 
+        self.store_local_blob(&file_ref, &blob).await?;
+        if announce_immediately {
+            self.announce_local_file(file_ref.clone(), username, ws_sender, false);
+        }
+
+        Ok(file_ref)
+    }
+
+    /// Stores generated bytes (e.g. a rendered token image) like a picked file.
+    pub async fn import_bytes(
+        &self,
+        bytes: &[u8],
+        file_name: String,
+        mime_type: &str,
+        username: String,
+        ws_sender: Option<WsSender>,
+    ) -> Result<FileRef, String> {
+        let blob = bytes_to_blob(bytes, mime_type)?;
+        let file_ref = FileRef {
+            hash: sha256_hex(bytes),
+            mime_type: mime_type.to_string(),
+            file_name,
+            size: bytes.len() as u64,
+        };
+        self.store_local_blob(&file_ref, &blob).await?;
+        self.announce_local_file(file_ref.clone(), username, ws_sender, false);
+        Ok(file_ref)
+    }
+
+    /// Stores generated bytes that only `audience` may fetch; nothing is
+    /// announced, so the rest of the room never learns about the file.
+    pub async fn import_restricted_bytes(
+        &self,
+        bytes: &[u8],
+        file_name: String,
+        mime_type: &str,
+        audience: &[String],
+    ) -> Result<FileRef, String> {
+        let blob = bytes_to_blob(bytes, mime_type)?;
+        let file_ref = FileRef {
+            hash: sha256_hex(bytes),
+            mime_type: mime_type.to_string(),
+            file_name,
+            size: bytes.len() as u64,
+        };
+        self.restrict_file(&file_ref.hash, audience);
+        self.store_local_blob(&file_ref, &blob).await?;
+        Ok(file_ref)
+    }
+
+    /// Limits a file to `audience`. A file someone has already announced to
+    /// the room stays public.
+    pub fn restrict_file(&self, hash: &str, audience: &[String]) {
+        let is_announced = self
+            .announcers
+            .borrow()
+            .get(hash)
+            .is_some_and(|announcers| !announcers.is_empty());
+        if is_announced {
+            return;
+        }
+        self.restricted
+            .borrow_mut()
+            .entry(hash.to_string())
+            .or_default()
+            .extend(audience.iter().cloned());
+    }
+
+    fn may_receive(&self, hash: &str, user: &str) -> bool {
+        self.restricted
+            .borrow()
+            .get(hash)
+            .is_none_or(|audience| audience.contains(user))
+    }
+
+    async fn store_local_blob(&self, file_ref: &FileRef, blob: &Blob) -> Result<(), String> {
         self.known_files
             .borrow_mut()
             .insert(file_ref.hash.clone(), file_ref.clone());
@@ -568,16 +651,42 @@ impl FileTransferState {
                     blob: blob.clone(),
                 })
                 .await?;
-                self.ensure_file_url_from_blob(&file_ref.hash, &blob);
+                self.ensure_file_url_from_blob(&file_ref.hash, blob);
             }
         }
 
         self.set_status(&file_ref.hash, FileTransferStatus::complete());
-        if announce_immediately {
-            self.announce_local_file(file_ref.clone(), username, ws_sender, false);
+        Ok(())
+    }
+
+    /// Reads a file from local IndexedDB; `None` while it has not been received.
+    pub async fn load_local_bytes(&self, hash: &str) -> Result<Option<Vec<u8>>, String> {
+        match storage::load_file(hash).await? {
+            Some(record) => blob_to_bytes(&record.blob).await.map(Some),
+            None => Ok(None),
         }
+    }
 
-        Ok(file_ref)
+    /// Metadata of a room file seen in a scene, chat message or local import.
+    pub fn file_ref(&self, hash: &str) -> Option<FileRef> {
+        self.known_files.borrow().get(hash).cloned()
+    }
+
+    /// Room files with a local blob URL, sorted by file name.
+    pub fn known_files(&self) -> Vec<FileRef> {
+        let file_urls = self.file_urls.get();
+        let restricted = self.restricted.borrow();
+        let mut files = self
+            .known_files
+            .borrow()
+            .values()
+            .filter(|file| {
+                file_urls.contains_key(&file.hash) && !restricted.contains_key(&file.hash)
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        files.sort_by(|left, right| left.file_name.cmp(&right.file_name));
+        files
     }
 
     pub fn announce_local_files(
@@ -595,6 +704,51 @@ impl FileTransferState {
         self.request_file_if_needed(file, username, ws_sender);
     }
 
+    /// Resolves a file referenced only by hash (e.g. an image embedded in a
+    /// note): loads it from IndexedDB or asks peers for it. Metadata the room
+    /// has not announced is filled in once the bytes arrive.
+    pub fn request_file_by_hash(&self, hash: &str, username: String, ws_sender: Option<WsSender>) {
+        let is_request_in_flight = self
+            .transfer_statuses
+            .get_untracked()
+            .get(hash)
+            .is_some_and(|status| {
+                matches!(
+                    status.stage,
+                    FileTransferStage::Requested | FileTransferStage::Receiving
+                )
+            });
+        if is_request_in_flight || self.file_urls.get_untracked().contains_key(hash) {
+            return;
+        }
+        let file = self
+            .known_files
+            .borrow()
+            .get(hash)
+            .cloned()
+            .unwrap_or_else(|| FileRef {
+                hash: hash.to_string(),
+                mime_type: String::new(),
+                file_name: hash.to_string(),
+                size: 0,
+            });
+
+        let this = self.clone();
+        spawn_local(async move {
+            match storage::load_file(&file.hash).await {
+                Ok(Some(record)) => {
+                    this.known_files
+                        .borrow_mut()
+                        .insert(record.file.hash.clone(), record.file.clone());
+                    this.ensure_file_url_from_blob(&record.file.hash, &record.blob);
+                    this.set_status(&record.file.hash, FileTransferStatus::complete());
+                }
+                Ok(None) => this.request_file_if_needed(file, username, ws_sender),
+                Err(error) => log!("Failed to load embedded file '{}': {}", file.hash, error),
+            }
+        });
+    }
+
     fn announce_local_file(
         &self,
         file: FileRef,
@@ -606,7 +760,9 @@ impl FileTransferState {
             return;
         };
 
-        if !force && self.announced_local_hashes.borrow().contains(&file.hash) {
+        if (!force && self.announced_local_hashes.borrow().contains(&file.hash))
+            || self.restricted.borrow().contains_key(&file.hash)
+        {
             return;
         }
 
@@ -815,17 +971,28 @@ impl FileTransferState {
             return Err("Hash mismatch after reassembly".to_string());
         }
 
-        let blob = bytes_to_blob(&bytes, &transfer.file.mime_type)?;
+        let mut file = transfer.file;
+        if file.mime_type.is_empty() {
+            file.mime_type = sniff_mime_type(&bytes)
+                .unwrap_or("application/octet-stream")
+                .to_string();
+            file.size = bytes.len() as u64;
+            self.known_files
+                .borrow_mut()
+                .insert(file.hash.clone(), file.clone());
+        }
+
+        let blob = bytes_to_blob(&bytes, &file.mime_type)?;
         storage::save_file(&storage::StoredFile {
-            file: transfer.file.clone(),
+            file: file.clone(),
             blob: blob.clone(),
         })
         .await?;
 
-        self.ensure_file_url_from_blob(&transfer.file.hash, &blob);
-        self.requested_at.borrow_mut().remove(&transfer.file.hash);
-        self.set_status(&transfer.file.hash, FileTransferStatus::complete());
-        self.announce_local_file(transfer.file, username, ws_sender, false);
+        self.ensure_file_url_from_blob(&file.hash, &blob);
+        self.requested_at.borrow_mut().remove(&file.hash);
+        self.set_status(&file.hash, FileTransferStatus::complete());
+        self.announce_local_file(file, username, ws_sender, false);
 
         Ok(())
     }
@@ -11,16 +11,16 @@ use std::collections::HashSet;
 use wasm_bindgen_futures::JsFuture;
 use web_sys::{Blob, BlobPropertyBag, File};
 
-/// Collects unique file references from all scene backgrounds and tokens.
+/// Collects unique file references from all scene and floor backgrounds and tokens.
 pub fn collect_scene_files(scenes: &[Scene]) -> Vec<FileRef> {
     let mut seen = HashSet::new();
     let mut files = Vec::new();
 
     for scene in scenes {
-        if let Some(background) = &scene.background
-            && seen.insert(background.hash.clone())
-        {
-            files.push(background.clone());
+        for background in scene_backgrounds(scene) {
+            if seen.insert(background.hash.clone()) {
+                files.push(background.clone());
+            }
         }
 
         for token in &scene.tokens {
@@ -33,6 +33,15 @@ pub fn collect_scene_files(scenes: &[Scene]) -> Vec<FileRef> {
     files
 }
 
+/// Backgrounds of a scene and of its extra floors.
+pub fn scene_backgrounds(scene: &Scene) -> impl Iterator<Item = &FileRef> {
+    let levels = scene
+        .levels
+        .iter()
+        .filter_map(|level| level.background.as_ref());
+    scene.background.iter().chain(levels)
+}
+
 /// Collects unique file references from chat message attachments.
 pub fn collect_chat_files(messages: &[ChatMessagePayload]) -> Vec<FileRef> {
     let mut seen = HashSet::new();
@@ -102,8 +111,40 @@ pub fn sha256_hex(bytes: &[u8]) -> String {
     format!("{:x}", hasher.finalize())
 }
 
+/// Recognises the supported image and PDF formats by their magic bytes, for
+/// files requested by hash alone.
+pub fn sniff_mime_type(bytes: &[u8]) -> Option<&'static str> {
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some("image/png")
+    } else if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some("image/jpeg")
+    } else if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+        Some("image/gif")
+    } else if bytes.len() >= 12 && bytes.starts_with(b"RIFF") && &bytes[8..12] == b"WEBP" {
+        Some("image/webp")
+    } else if bytes.starts_with(b"%PDF-") {
+        Some("application/pdf")
+    } else {
+        None
+    }
+}
+
 /// Returns the maximum number of bytes in a chunk, for use in tests.
 #[cfg(test)]
 pub fn chunk_size() -> usize {
     CHUNK_SIZE_BYTES
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn image_types_are_sniffed_from_magic_bytes() {
+        assert_eq!(sniff_mime_type(b"\x89PNG\r\n\x1a\n\0\0"), Some("image/png"));
+        assert_eq!(sniff_mime_type(b"RIFF\0\0\0\0WEBPVP8 "), Some("image/webp"));
+        assert_eq!(sniff_mime_type(b"%PDF-1.7"), Some("application/pdf"));
+        assert_eq!(sniff_mime_type(b"RIFF"), None);
+        assert_eq!(sniff_mime_type(b"plain text"), None);
+    }
+}
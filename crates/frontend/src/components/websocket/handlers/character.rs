@@ -0,0 +1,187 @@
+use crate::components::characters::model::{
+    available_templates, find_template, split_private_values, validate_template,
+};
+use crate::components::websocket::{storage, utils};
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use log::warn;
+use shared::events::{
+    CharacterSheetDeletePayload, CharacterSheetSecretsPayload, CharacterSheetUpsertPayload,
+    SheetTemplateUpsertPayload, acts_as_game_master,
+};
+
+use super::HandlerContext;
+
+pub fn handle_character_sheet_upsert(
+    payload: CharacterSheetUpsertPayload,
+    ctx: &HandlerContext<'_>,
+) {
+    let (current_ver, sheets) = {
+        let mut state = ctx.room_state.borrow_mut();
+        if !state.can_upsert_character_sheet(&payload.sheet, &payload.actor) {
+            return;
+        }
+
+        // Private values travel in CHARACTER_SHEET_SECRETS; never keep them
+        // in the room state, which is synced to everyone.
+        let mut incoming = payload.sheet.clone();
+        let templates = available_templates(&state.sheet_templates);
+        let (public_values, _) = split_private_values(
+            find_template(&templates, &incoming.template_id),
+            &incoming.values,
+        );
+        incoming.values = public_values;
+
+        match state
+            .character_sheets
+            .iter_mut()
+            .find(|sheet| sheet.id == incoming.id)
+        {
+            Some(sheet) => *sheet = incoming,
+            None => state.character_sheets.push(incoming),
+        }
+        state.commit_changes();
+        (state.version, state.character_sheets.clone())
+    };
+
+    *ctx.local_version.borrow_mut() = current_ver;
+    if payload.actor != ctx.my_username {
+        *ctx.last_synced_version.borrow_mut() = current_ver;
+    }
+
+    ctx.character_sheets_signal.set(sheets);
+    storage::save_state_in_background(ctx.room_name, &ctx.room_state.borrow());
+    utils::log_event(
+        ctx.state_events,
+        current_ver,
+        "CHARACTER_SHEET_UPSERT",
+        &format!(
+            "{} saved character sheet '{}'",
+            payload.actor, payload.sheet.name
+        ),
+    );
+}
+
+pub fn handle_character_sheet_secrets(
+    payload: CharacterSheetSecretsPayload,
+    ctx: &HandlerContext<'_>,
+) {
+    {
+        let state = ctx.room_state.borrow();
+        let game_master = state.game_master.as_deref();
+        let Some(sheet) = state
+            .character_sheets
+            .iter()
+            .find(|sheet| sheet.id == payload.sheet_id)
+        else {
+            return;
+        };
+        if !sheet.can_be_edited_by(&payload.actor, game_master)
+            || !sheet.shows_private_fields_to(ctx.my_username, game_master)
+        {
+            return;
+        }
+    }
+
+    ctx.sheet_secrets_signal.update(|secrets| {
+        secrets.insert(payload.sheet_id.clone(), payload.values.clone());
+    });
+
+    let record = storage::StoredSheetSecrets {
+        key: storage::sheet_secrets_key(ctx.room_name, ctx.my_username, &payload.sheet_id),
+        room_name: ctx.room_name.to_string(),
+        owner_username: ctx.my_username.to_string(),
+        sheet_id: payload.sheet_id,
+        values: payload.values,
+    };
+    spawn_local(async move {
+        if let Err(error) = storage::save_sheet_secrets(&record).await {
+            warn!("Failed to persist sheet secrets to IndexedDB: {}", error);
+        }
+    });
+}
+
+pub fn handle_character_sheet_delete(
+    payload: CharacterSheetDeletePayload,
+    ctx: &HandlerContext<'_>,
+) {
+    let (current_ver, sheets, name) = {
+        let mut state = ctx.room_state.borrow_mut();
+        let game_master = state.game_master.clone();
+        let Some(index) = state
+            .character_sheets
+            .iter()
+            .position(|sheet| sheet.id == payload.sheet_id)
+        else {
+            return;
+        };
+        if !state.character_sheets[index].can_be_edited_by(&payload.actor, game_master.as_deref()) {
+            return;
+        }
+
+        let removed = state.character_sheets.remove(index);
+        state.commit_changes();
+        (state.version, state.character_sheets.clone(), removed.name)
+    };
+
+    *ctx.local_version.borrow_mut() = current_ver;
+    if payload.actor != ctx.my_username {
+        *ctx.last_synced_version.borrow_mut() = current_ver;
+    }
+
+    ctx.character_sheets_signal.set(sheets);
+    storage::save_state_in_background(ctx.room_name, &ctx.room_state.borrow());
+    utils::log_event(
+        ctx.state_events,
+        current_ver,
+        "CHARACTER_SHEET_DELETE",
+        &format!("{} deleted character sheet '{}'", payload.actor, name),
+    );
+}
+
+pub fn handle_sheet_template_upsert(payload: SheetTemplateUpsertPayload, ctx: &HandlerContext<'_>) {
+    // Peers only send templates that passed the editor, but a broken one
+    // would fail on every sheet that uses it; drop it instead.
+    if let Err(error) = validate_template(&payload.template) {
+        warn!(
+            "Ignoring invalid sheet template {}: {:?}",
+            payload.template.id, error
+        );
+        return;
+    }
+
+    let (current_ver, templates) = {
+        let mut state = ctx.room_state.borrow_mut();
+        if !acts_as_game_master(state.game_master.as_deref(), &payload.actor) {
+            return;
+        }
+
+        match state
+            .sheet_templates
+            .iter_mut()
+            .find(|template| template.id == payload.template.id)
+        {
+            Some(template) => *template = payload.template.clone(),
+            None => state.sheet_templates.push(payload.template.clone()),
+        }
+        state.commit_changes();
+        (state.version, state.sheet_templates.clone())
+    };
+
+    *ctx.local_version.borrow_mut() = current_ver;
+    if payload.actor != ctx.my_username {
+        *ctx.last_synced_version.borrow_mut() = current_ver;
+    }
+
+    ctx.sheet_templates_signal.set(templates);
+    storage::save_state_in_background(ctx.room_name, &ctx.room_state.borrow());
+    utils::log_event(
+        ctx.state_events,
+        current_ver,
+        "SHEET_TEMPLATE_UPSERT",
+        &format!(
+            "{} uploaded sheet template '{}'",
+            payload.actor, payload.template.name
+        ),
+    );
+}
@@ -0,0 +1,80 @@
+use crate::components::websocket::{storage, utils};
+use leptos::prelude::*;
+use shared::events::{CompendiumImportPayload, CompendiumRemovePayload, acts_as_game_master};
+
+use super::HandlerContext;
+
+pub fn handle_compendium_import(payload: CompendiumImportPayload, ctx: &HandlerContext<'_>) {
+    let (current_ver, sources) = {
+        let mut state = ctx.room_state.borrow_mut();
+        if !acts_as_game_master(state.game_master.as_deref(), &payload.actor) {
+            return;
+        }
+
+        match state
+            .compendium_sources
+            .iter_mut()
+            .find(|source| source.file.hash == payload.source.file.hash)
+        {
+            Some(source) => *source = payload.source.clone(),
+            None => state.compendium_sources.push(payload.source.clone()),
+        }
+        state.commit_changes();
+        (state.version, state.compendium_sources.clone())
+    };
+
+    *ctx.local_version.borrow_mut() = current_ver;
+    if payload.actor != ctx.my_username {
+        *ctx.last_synced_version.borrow_mut() = current_ver;
+    }
+
+    ctx.compendium_sources_signal.set(sources);
+    storage::save_state_in_background(ctx.room_name, &ctx.room_state.borrow());
+    utils::log_event(
+        ctx.state_events,
+        current_ver,
+        "COMPENDIUM_IMPORT",
+        &format!(
+            "{} imported compendium '{}'",
+            payload.actor, payload.source.title
+        ),
+    );
+}
+
+pub fn handle_compendium_remove(payload: CompendiumRemovePayload, ctx: &HandlerContext<'_>) {
+    let (current_ver, sources, title) = {
+        let mut state = ctx.room_state.borrow_mut();
+        if !acts_as_game_master(state.game_master.as_deref(), &payload.actor) {
+            return;
+        }
+        let Some(index) = state
+            .compendium_sources
+            .iter()
+            .position(|source| source.file.hash == payload.hash)
+        else {
+            return;
+        };
+
+        let removed = state.compendium_sources.remove(index);
+        state.commit_changes();
+        (
+            state.version,
+            state.compendium_sources.clone(),
+            removed.title,
+        )
+    };
+
+    *ctx.local_version.borrow_mut() = current_ver;
+    if payload.actor != ctx.my_username {
+        *ctx.last_synced_version.borrow_mut() = current_ver;
+    }
+
+    ctx.compendium_sources_signal.set(sources);
+    storage::save_state_in_background(ctx.room_name, &ctx.room_state.borrow());
+    utils::log_event(
+        ctx.state_events,
+        current_ver,
+        "COMPENDIUM_REMOVE",
+        &format!("{} removed compendium '{}'", payload.actor, title),
+    );
+}
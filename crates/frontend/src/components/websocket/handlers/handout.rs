@@ -0,0 +1,166 @@
+use crate::components::websocket::{storage, utils};
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use log::warn;
+use shared::events::{Handout, HandoutDeletePayload, HandoutShowPayload, acts_as_game_master};
+
+use super::HandlerContext;
+
+pub fn handle_handout_show(payload: HandoutShowPayload, ctx: &HandlerContext<'_>) {
+    let is_restricted = !payload.handout.recipients.is_empty();
+    let (current_ver, handouts, game_master) = {
+        let mut state = ctx.room_state.borrow_mut();
+        if !acts_as_game_master(state.game_master.as_deref(), &payload.actor) {
+            return;
+        }
+
+        // Handouts for chosen players never enter the synced room state.
+        let index = state
+            .handouts
+            .iter()
+            .position(|handout| handout.id == payload.handout.id);
+        match (index, is_restricted) {
+            (Some(index), true) => {
+                state.handouts.remove(index);
+            }
+            (Some(index), false) => state.handouts[index] = payload.handout.clone(),
+            (None, true) => {}
+            (None, false) => state.handouts.push(payload.handout.clone()),
+        }
+        if index.is_some() || !is_restricted {
+            state.commit_changes();
+        }
+        (
+            state.version,
+            state.handouts.clone(),
+            state.game_master.clone(),
+        )
+    };
+
+    *ctx.local_version.borrow_mut() = current_ver;
+    if payload.actor != ctx.my_username {
+        *ctx.last_synced_version.borrow_mut() = current_ver;
+    }
+
+    ctx.handouts_signal.set(handouts);
+    storage::save_state_in_background(ctx.room_name, &ctx.room_state.borrow());
+
+    let is_visible = payload
+        .handout
+        .is_visible_to(ctx.my_username, game_master.as_deref());
+    if is_restricted && is_visible {
+        let mut audience = payload.handout.recipients.clone();
+        audience.push(payload.actor.clone());
+        ctx.file_transfer
+            .restrict_file(&payload.handout.file.hash, &audience);
+        upsert_private_handout(payload.handout.clone(), ctx);
+    } else {
+        remove_private_handout(&payload.handout.id, ctx);
+    }
+
+    if payload.actor != ctx.my_username && is_visible {
+        ctx.file_transfer.request_file(
+            payload.handout.file.clone(),
+            ctx.my_username.to_string(),
+            Some(ctx.tx.clone()),
+        );
+        ctx.presented_handout.set(Some(payload.handout.id.clone()));
+    }
+
+    utils::log_event(
+        ctx.state_events,
+        current_ver,
+        "HANDOUT_SHOW",
+        &format!(
+            "{} showed handout '{}'",
+            payload.actor, payload.handout.title
+        ),
+    );
+}
+
+pub fn handle_handout_delete(payload: HandoutDeletePayload, ctx: &HandlerContext<'_>) {
+    let (current_ver, handouts, removed) = {
+        let mut state = ctx.room_state.borrow_mut();
+        if !acts_as_game_master(state.game_master.as_deref(), &payload.actor) {
+            return;
+        }
+        let removed = state
+            .handouts
+            .iter()
+            .position(|handout| handout.id == payload.id)
+            .map(|index| state.handouts.remove(index));
+        if removed.is_some() {
+            state.commit_changes();
+        }
+        (state.version, state.handouts.clone(), removed)
+    };
+    let Some(title) = removed
+        .map(|handout| handout.title)
+        .or_else(|| remove_private_handout(&payload.id, ctx))
+    else {
+        return;
+    };
+
+    *ctx.local_version.borrow_mut() = current_ver;
+    if payload.actor != ctx.my_username {
+        *ctx.last_synced_version.borrow_mut() = current_ver;
+    }
+
+    ctx.handouts_signal.set(handouts);
+    if ctx.presented_handout.get_untracked().as_deref() == Some(payload.id.as_str()) {
+        ctx.presented_handout.set(None);
+    }
+    storage::save_state_in_background(ctx.room_name, &ctx.room_state.borrow());
+    utils::log_event(
+        ctx.state_events,
+        current_ver,
+        "HANDOUT_DELETE",
+        &format!("{} removed handout '{}'", payload.actor, title),
+    );
+}
+
+fn upsert_private_handout(handout: Handout, ctx: &HandlerContext<'_>) {
+    ctx.private_handouts_signal.update(|handouts| {
+        match handouts
+            .iter_mut()
+            .find(|existing| existing.id == handout.id)
+        {
+            Some(existing) => *existing = handout.clone(),
+            None => handouts.push(handout.clone()),
+        }
+    });
+    let record = storage::StoredHandout {
+        key: storage::handout_key(ctx.room_name, ctx.my_username, &handout.id),
+        room_name: ctx.room_name.to_string(),
+        owner_username: ctx.my_username.to_string(),
+        handout,
+    };
+    spawn_local(async move {
+        if let Err(error) = storage::save_private_handout(&record).await {
+            warn!("Failed to persist handout to IndexedDB: {}", error);
+        }
+    });
+}
+
+/// Drops a locally stored handout; returns its title when there was one.
+fn remove_private_handout(id: &str, ctx: &HandlerContext<'_>) -> Option<String> {
+    let title = ctx.private_handouts_signal.with_untracked(|handouts| {
+        handouts
+            .iter()
+            .find(|handout| handout.id == id)
+            .map(|handout| handout.title.clone())
+    })?;
+    ctx.private_handouts_signal
+        .update(|handouts| handouts.retain(|handout| handout.id != id));
+
+    let room_name = ctx.room_name.to_string();
+    let owner_username = ctx.my_username.to_string();
+    let id = id.to_string();
+    spawn_local(async move {
+        if let Err(error) = storage::delete_private_handout(&room_name, &owner_username, &id).await
+        {
+            warn!("Failed to delete handout from IndexedDB: {}", error);
+        }
+    });
+    Some(title)
+}
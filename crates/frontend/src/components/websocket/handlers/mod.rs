@@ -1,8 +1,12 @@
+mod character;
 mod chat;
+mod compendium;
 mod file;
+mod handout;
 mod mouse;
 mod note;
 mod presence;
+mod room;
 mod scene;
 mod sync;
 pub mod sync_discard;
@@ -10,16 +14,18 @@ mod voting;
 
 use leptos::task::spawn_local;
 
+use crate::components::notes::collab::NoteEditSession;
 use crate::components::statistics::StateEvent;
 use crate::components::voting::VotingState;
 use crate::components::websocket::{FileTransferState, SnapshotCodec, WsSender, types::*};
 use leptos::prelude::*;
 use shared::events::{
-    AttentionPingPayload, ChatMessagePayload, ClientEvent, DirectMessagePayload, NotePayload,
-    RoomState, Scene, VotingResultPayload,
+    AttentionPingPayload, BoardCameraPayload, BoardViewportPayload, CharacterSheet,
+    ChatMessagePayload, ClientEvent, CompendiumSource, DirectMessagePayload, Handout, NotePayload,
+    RoomState, Scene, SheetTemplate, VotingResultPayload,
 };
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::rc::Rc;
 
 pub struct HandlerContext<'a> {
@@ -42,6 +48,16 @@ pub struct HandlerContext<'a> {
     pub state_events: RwSignal<Vec<StateEvent>>,
     pub scenes_signal: RwSignal<Vec<Scene>>,
     pub active_scene_id_signal: RwSignal<Option<String>>,
+    pub scene_assignments_signal: RwSignal<BTreeMap<String, String>>,
+    pub game_master_signal: RwSignal<Option<String>>,
+    pub character_sheets_signal: RwSignal<Vec<CharacterSheet>>,
+    /// Private sheet values this user may see, by sheet id; kept off the room state.
+    pub sheet_secrets_signal: RwSignal<BTreeMap<String, BTreeMap<String, String>>>,
+    pub sheet_templates_signal: RwSignal<Vec<SheetTemplate>>,
+    pub compendium_sources_signal: RwSignal<Vec<CompendiumSource>>,
+    pub handouts_signal: RwSignal<Vec<Handout>>,
+    /// Handouts addressed to chosen players; kept off the room state.
+    pub private_handouts_signal: RwSignal<Vec<Handout>>,
     pub conflict_signal: RwSignal<Option<SyncConflict>>,
     pub votings: RwSignal<HashMap<String, VotingState>>,
     pub voting_results: RwSignal<HashMap<String, VotingResultPayload>>,
@@ -58,8 +74,18 @@ pub struct HandlerContext<'a> {
     pub board_pointers: RwSignal<std::collections::HashSet<String>>,
     /// Queued attention pings waiting to be animated.
     pub attention_pings: RwSignal<Vec<AttentionPingPayload>>,
+    /// Latest camera shared by another user; the board decides whether it applies.
+    pub board_camera: RwSignal<Option<BoardCameraPayload>>,
+    /// Users following this user's camera.
+    pub camera_followers: RwSignal<std::collections::HashSet<String>>,
+    /// Latest visible area of every other user's board, for the minimap.
+    pub board_viewports: RwSignal<HashMap<String, BoardViewportPayload>>,
+    /// Handout id popped up full-screen for this user.
+    pub presented_handout: RwSignal<Option<String>>,
     /// Received direct messages (to == my_username).
     pub direct_messages: RwSignal<Vec<DirectMessagePayload>>,
+    /// Live collaborative edit of a public note, if this user has one open.
+    pub note_edit_session: RwSignal<Option<NoteEditSession>>,
 }
 
 pub fn handle_event(event: ClientEvent, ctx: &HandlerContext<'_>) {
@@ -67,6 +93,8 @@ pub fn handle_event(event: ClientEvent, ctx: &HandlerContext<'_>) {
         ClientEvent::ChatMessage(msg) => chat::handle_chat_message(msg, ctx),
         ClientEvent::NoteUpsert(payload) => note::handle_note_upsert(payload, ctx),
         ClientEvent::NoteDelete(payload) => note::handle_note_delete(payload, ctx),
+        ClientEvent::NoteEdit(payload) => note::handle_note_edit(payload, ctx),
+        ClientEvent::NoteCaret(payload) => note::handle_note_caret(payload, ctx),
         ClientEvent::FileAnnounce(payload) => {
             file::handle_file_announce(payload, ctx.file_transfer, ctx.my_username, ctx.tx)
         }
@@ -91,7 +119,29 @@ pub fn handle_event(event: ClientEvent, ctx: &HandlerContext<'_>) {
         ClientEvent::SceneUpdate(payload) => scene::handle_scene_update(payload, ctx),
         ClientEvent::SceneDelete(payload) => scene::handle_scene_delete(payload, ctx),
         ClientEvent::SceneActivate(payload) => scene::handle_scene_activate(payload, ctx),
+        ClientEvent::SceneAssign(payload) => scene::handle_scene_assign(payload, ctx),
         ClientEvent::TokenMove(payload) => scene::handle_token_move(payload, ctx),
+        ClientEvent::GameMasterSet(payload) => room::handle_game_master_set(payload, ctx),
+        ClientEvent::CharacterSheetUpsert(payload) => {
+            character::handle_character_sheet_upsert(payload, ctx)
+        }
+        ClientEvent::CharacterSheetDelete(payload) => {
+            character::handle_character_sheet_delete(payload, ctx)
+        }
+        ClientEvent::CharacterSheetSecrets(payload) => {
+            character::handle_character_sheet_secrets(payload, ctx)
+        }
+        ClientEvent::SheetTemplateUpsert(payload) => {
+            character::handle_sheet_template_upsert(payload, ctx)
+        }
+        ClientEvent::CompendiumImport(payload) => {
+            compendium::handle_compendium_import(payload, ctx)
+        }
+        ClientEvent::CompendiumRemove(payload) => {
+            compendium::handle_compendium_remove(payload, ctx)
+        }
+        ClientEvent::HandoutShow(payload) => handout::handle_handout_show(payload, ctx),
+        ClientEvent::HandoutDelete(payload) => handout::handle_handout_delete(payload, ctx),
         ClientEvent::MouseClickPayload(mouse_event) => {
             mouse::handle_mouse_event(mouse_event, ctx.my_username, ctx.set_cursors)
         }
@@ -154,16 +204,26 @@ pub fn handle_event(event: ClientEvent, ctx: &HandlerContext<'_>) {
             ctx.local_version,
             ctx.state_events,
         ),
-        ClientEvent::BoardPointer(payload) => {
-            if payload.username != ctx.my_username {
-                ctx.board_pointers.update(|set| {
-                    if payload.active {
-                        set.insert(payload.username.clone());
-                    } else {
-                        set.remove(&payload.username);
-                    }
-                });
-            }
+        ClientEvent::PresenceLeave(payload) => {
+            // A departed user can no longer follow this camera or point at the board.
+            ctx.camera_followers.update(|followers| {
+                followers.remove(&payload.user);
+            });
+            ctx.board_pointers.update(|set| {
+                set.remove(&payload.user);
+            });
+            ctx.board_viewports.update(|viewports| {
+                viewports.remove(&payload.user);
+            });
+        }
+        ClientEvent::BoardPointer(payload) if payload.username != ctx.my_username => {
+            ctx.board_pointers.update(|set| {
+                if payload.active {
+                    set.insert(payload.username.clone());
+                } else {
+                    set.remove(&payload.username);
+                }
+            });
         }
         ClientEvent::AttentionPing(payload) => {
             let pings_signal = ctx.attention_pings;
@@ -181,19 +241,35 @@ pub fn handle_event(event: ClientEvent, ctx: &HandlerContext<'_>) {
                 });
             });
         }
-        ClientEvent::DirectMessage(payload) => {
+        ClientEvent::BoardCamera(payload) if payload.username != ctx.my_username => {
+            ctx.board_camera.set(Some(payload));
+        }
+        ClientEvent::BoardFollow(payload) if payload.target == ctx.my_username => {
+            ctx.camera_followers.update(|followers| {
+                if payload.active {
+                    followers.insert(payload.follower.clone());
+                } else {
+                    followers.remove(&payload.follower);
+                }
+            });
+        }
+        ClientEvent::BoardViewport(payload) if payload.username != ctx.my_username => {
+            ctx.board_viewports.update(|viewports| {
+                viewports.insert(payload.username.clone(), payload);
+            });
+        }
+        ClientEvent::DirectMessage(payload)
             if payload.to == ctx.my_username
                 // Suppress the relay echo when the user DM'd themselves:
                 // the sender-side entry was already added in `do_send`.
-                && payload.from != ctx.my_username
-            {
-                // Show chat notification badge.
-                ctx.has_chat_notification.set(true);
-                ctx.chat_notification_count.update(|c| *c += 1);
-                // Store in the dedicated DM signal — it is never overwritten
-                // by snapshot restores so DMs persist for the full session.
-                ctx.direct_messages.update(|msgs| msgs.push(payload));
-            }
+                && payload.from != ctx.my_username =>
+        {
+            // Show chat notification badge.
+            ctx.has_chat_notification.set(true);
+            ctx.chat_notification_count.update(|c| *c += 1);
+            // Store in the dedicated DM signal — it is never overwritten
+            // by snapshot restores so DMs persist for the full session.
+            ctx.direct_messages.update(|msgs| msgs.push(payload));
         }
         _ => {}
     }
@@ -1,7 +1,10 @@
+use crate::components::notes::collab::op_in_bounds;
 use crate::components::websocket::{self, storage, utils};
 use leptos::prelude::*;
 use leptos::task::spawn_local;
-use shared::events::{NoteDeletePayload, NotePayload, NoteVisibility};
+use shared::events::{
+    ClientEvent, NoteCaretPayload, NoteDeletePayload, NoteEditPayload, NotePayload, NoteVisibility,
+};
 
 use super::HandlerContext;
 
@@ -29,18 +32,18 @@ fn remove_note(notes: &mut Vec<NotePayload>, id: &str) {
 
 fn is_direct_note_for_current_user(note: &NotePayload, current_username: &str) -> bool {
     match &note.visibility {
-        NoteVisibility::Direct(recipient) => {
-            note.author == current_username || recipient == current_username
-        }
+        NoteVisibility::Direct(_) | NoteVisibility::Group(_) => note
+            .visibility
+            .is_visible_to(&note.author, current_username),
         _ => false,
     }
 }
 
 fn is_direct_delete_for_current_user(payload: &NoteDeletePayload, current_username: &str) -> bool {
     match &payload.visibility {
-        NoteVisibility::Direct(recipient) => {
-            payload.author == current_username || recipient == current_username
-        }
+        NoteVisibility::Direct(_) | NoteVisibility::Group(_) => payload
+            .visibility
+            .is_visible_to(&payload.author, current_username),
         _ => false,
     }
 }
@@ -76,7 +79,7 @@ pub fn handle_note_upsert(payload: NotePayload, ctx: &HandlerContext<'_>) {
                 &format!("{} updated note '{}'", payload.author, payload.title),
             );
         }
-        NoteVisibility::Direct(_) => {
+        NoteVisibility::Direct(_) | NoteVisibility::Group(_) => {
             if !is_direct_note_for_current_user(&payload, ctx.my_username) {
                 return;
             }
@@ -141,7 +144,7 @@ pub fn handle_note_delete(payload: NoteDeletePayload, ctx: &HandlerContext<'_>)
                 &format!("{} deleted note {}", payload.author, payload.id),
             );
         }
-        NoteVisibility::Direct(_) => {
+        NoteVisibility::Direct(_) | NoteVisibility::Group(_) => {
             if !is_direct_delete_for_current_user(&payload, ctx.my_username) {
                 return;
             }
@@ -166,3 +169,49 @@ pub fn handle_note_delete(payload: NoteDeletePayload, ctx: &HandlerContext<'_>)
         NoteVisibility::Private => {}
     }
 }
+
+fn is_edit_session_for(ctx: &HandlerContext<'_>, note_id: &str) -> bool {
+    ctx.note_edit_session
+        .with_untracked(|session| session.as_ref().is_some_and(|s| s.note_id == note_id))
+}
+
+/// Only public notes are co-edited, matching `can_edit_note_body`.
+fn is_public_note(ctx: &HandlerContext<'_>, note_id: &str) -> bool {
+    ctx.public_notes_signal.with_untracked(|notes| {
+        notes
+            .iter()
+            .any(|note| note.id == note_id && note.visibility == NoteVisibility::Public)
+    })
+}
+
+pub fn handle_note_edit(payload: NoteEditPayload, ctx: &HandlerContext<'_>) {
+    if !is_edit_session_for(ctx, &payload.note_id)
+        || !is_public_note(ctx, &payload.note_id)
+        || !payload.ops.iter().all(op_in_bounds)
+    {
+        return;
+    }
+    // Re-applied echoes of our own operations leave the text unchanged.
+    ctx.note_edit_session.maybe_update(|session| {
+        session
+            .as_mut()
+            .is_some_and(|session| session.receive_edit(payload))
+    });
+}
+
+pub fn handle_note_caret(payload: NoteCaretPayload, ctx: &HandlerContext<'_>) {
+    if payload.username == ctx.my_username || !is_edit_session_for(ctx, &payload.note_id) {
+        return;
+    }
+    let mut snapshot = Vec::new();
+    ctx.note_edit_session.update(|session| {
+        if let Some(session) = session
+            && session.receive_caret(payload)
+        {
+            snapshot = session.snapshot_payloads(ctx.my_username);
+        }
+    });
+    for payload in snapshot {
+        let _ = ctx.tx.try_send_event(ClientEvent::NoteEdit(payload));
+    }
+}
@@ -0,0 +1,36 @@
+use crate::components::websocket::{storage, utils};
+use leptos::prelude::*;
+use shared::events::GameMasterSetPayload;
+
+use super::HandlerContext;
+
+pub fn handle_game_master_set(payload: GameMasterSetPayload, ctx: &HandlerContext<'_>) {
+    let current_ver = {
+        let mut state = ctx.room_state.borrow_mut();
+        if !state.can_set_game_master(&payload.actor) || state.game_master == payload.game_master {
+            return;
+        }
+
+        state.game_master = payload.game_master.clone();
+        state.commit_changes();
+        state.version
+    };
+
+    *ctx.local_version.borrow_mut() = current_ver;
+    if payload.actor != ctx.my_username {
+        *ctx.last_synced_version.borrow_mut() = current_ver;
+    }
+
+    ctx.game_master_signal.set(payload.game_master.clone());
+    storage::save_state_in_background(ctx.room_name, &ctx.room_state.borrow());
+    let description = match payload.game_master.as_deref() {
+        Some(game_master) => format!("{} made {} the game master", payload.actor, game_master),
+        None => format!("{} stepped down as game master", payload.actor),
+    };
+    utils::log_event(
+        ctx.state_events,
+        current_ver,
+        "GAME_MASTER_SET",
+        &description,
+    );
+}
@@ -1,10 +1,11 @@
 use crate::components::websocket::{storage, utils};
 use leptos::prelude::*;
 use shared::events::{
-    RoomState, Scene, SceneActivatePayload, SceneCreatePayload, SceneDeletePayload,
-    SceneUpdatePayload, TokenMovePayload,
+    RoomState, Scene, SceneActivatePayload, SceneAssignPayload, SceneCreatePayload,
+    SceneDeletePayload, SceneUpdatePayload, TokenMovePayload, acts_as_game_master,
 };
 use std::cell::RefCell;
+use std::collections::BTreeMap;
 use std::rc::Rc;
 
 use super::HandlerContext;
@@ -16,10 +17,12 @@ fn sync_scene_signals(
     room_state: &Rc<RefCell<RoomState>>,
     scenes_signal: RwSignal<Vec<Scene>>,
     active_scene_id_signal: RwSignal<Option<String>>,
+    scene_assignments_signal: RwSignal<BTreeMap<String, String>>,
 ) {
     let state = room_state.borrow();
     scenes_signal.set(state.scenes.clone());
     active_scene_id_signal.set(state.active_scene_id.clone());
+    scene_assignments_signal.set(state.scene_assignments.clone());
 }
 
 pub fn handle_scene_create(payload: SceneCreatePayload, ctx: &HandlerContext<'_>) {
@@ -30,6 +33,7 @@ pub fn handle_scene_create(payload: SceneCreatePayload, ctx: &HandlerContext<'_>
             .iter()
             .any(|scene| scene.id == payload.scene.id)
             || state.scenes.len() >= MAX_SCENES_PER_ROOM
+            || !state.can_upsert_scene(&payload.scene, &payload.actor)
         {
             return;
         }
@@ -52,6 +56,7 @@ pub fn handle_scene_create(payload: SceneCreatePayload, ctx: &HandlerContext<'_>
         ctx.room_state,
         ctx.scenes_signal,
         ctx.active_scene_id_signal,
+        ctx.scene_assignments_signal,
     );
     storage::save_state_in_background(ctx.room_name, &ctx.room_state.borrow());
     utils::log_event(
@@ -65,6 +70,9 @@ pub fn handle_scene_create(payload: SceneCreatePayload, ctx: &HandlerContext<'_>
 pub fn handle_scene_update(payload: SceneUpdatePayload, ctx: &HandlerContext<'_>) {
     let current_ver = {
         let mut state = ctx.room_state.borrow_mut();
+        if !state.can_upsert_scene(&payload.scene, &payload.actor) {
+            return;
+        }
         let Some(scene) = state
             .scenes
             .iter_mut()
@@ -87,6 +95,7 @@ pub fn handle_scene_update(payload: SceneUpdatePayload, ctx: &HandlerContext<'_>
         ctx.room_state,
         ctx.scenes_signal,
         ctx.active_scene_id_signal,
+        ctx.scene_assignments_signal,
     );
     storage::save_state_in_background(ctx.room_name, &ctx.room_state.borrow());
     utils::log_event(
@@ -113,6 +122,7 @@ pub fn handle_scene_delete(payload: SceneDeletePayload, ctx: &HandlerContext<'_>
         if state.active_scene_id.as_deref() == Some(payload.scene_id.as_str()) {
             state.active_scene_id = state.scenes.first().map(|scene| scene.id.clone());
         }
+        state.forget_scene_assignments(&payload.scene_id);
 
         state.commit_changes();
         (state.version, deleted_scene.name)
@@ -127,6 +137,7 @@ pub fn handle_scene_delete(payload: SceneDeletePayload, ctx: &HandlerContext<'_>
         ctx.room_state,
         ctx.scenes_signal,
         ctx.active_scene_id_signal,
+        ctx.scene_assignments_signal,
     );
     storage::save_state_in_background(ctx.room_name, &ctx.room_state.borrow());
     utils::log_event(
@@ -167,6 +178,7 @@ pub fn handle_scene_activate(payload: SceneActivatePayload, ctx: &HandlerContext
         ctx.room_state,
         ctx.scenes_signal,
         ctx.active_scene_id_signal,
+        ctx.scene_assignments_signal,
     );
     storage::save_state_in_background(ctx.room_name, &ctx.room_state.borrow());
     utils::log_event(
@@ -177,6 +189,57 @@ pub fn handle_scene_activate(payload: SceneActivatePayload, ctx: &HandlerContext
     );
 }
 
+pub fn handle_scene_assign(payload: SceneAssignPayload, ctx: &HandlerContext<'_>) {
+    let (current_ver, scene_name) = {
+        let mut state = ctx.room_state.borrow_mut();
+        if !acts_as_game_master(state.game_master.as_deref(), &payload.actor) {
+            return;
+        }
+
+        let scene_name = match payload.scene_id.as_deref() {
+            Some(scene_id) => {
+                let Some(scene) = state.scenes.iter().find(|scene| scene.id == scene_id) else {
+                    return;
+                };
+                Some(scene.name.clone())
+            }
+            None => None,
+        };
+
+        if !state.assign_scene(&payload.usernames, payload.scene_id.as_deref()) {
+            return;
+        }
+        state.commit_changes();
+        (state.version, scene_name)
+    };
+
+    *ctx.local_version.borrow_mut() = current_ver;
+    if payload.actor != ctx.my_username {
+        *ctx.last_synced_version.borrow_mut() = current_ver;
+    }
+
+    sync_scene_signals(
+        ctx.room_state,
+        ctx.scenes_signal,
+        ctx.active_scene_id_signal,
+        ctx.scene_assignments_signal,
+    );
+    storage::save_state_in_background(ctx.room_name, &ctx.room_state.borrow());
+    let players = payload.usernames.join(", ");
+    utils::log_event(
+        ctx.state_events,
+        current_ver,
+        "SCENE_ASSIGN",
+        &match scene_name {
+            Some(scene_name) => format!(
+                "{} moved {} to scene '{}'",
+                payload.actor, players, scene_name
+            ),
+            None => format!("{} returned {} to the active scene", payload.actor, players),
+        },
+    );
+}
+
 pub fn handle_token_move(payload: TokenMovePayload, ctx: &HandlerContext<'_>) {
     let (current_ver, moved_token_name) = {
         let mut state = ctx.room_state.borrow_mut();
@@ -220,6 +283,7 @@ pub fn handle_token_move(payload: TokenMovePayload, ctx: &HandlerContext<'_>) {
         ctx.room_state,
         ctx.scenes_signal,
         ctx.active_scene_id_signal,
+        ctx.scene_assignments_signal,
     );
     storage::save_state_in_background(ctx.room_name, &ctx.room_state.borrow());
     utils::log_event(
@@ -352,6 +352,17 @@ pub fn handle_snapshot(payload: SyncSnapshotPayload, ctx: &HandlerContext<'_>) {
         ctx.scenes_signal.set(decoded_state.scenes.clone());
         ctx.active_scene_id_signal
             .set(decoded_state.active_scene_id.clone());
+        ctx.scene_assignments_signal
+            .set(decoded_state.scene_assignments.clone());
+        ctx.game_master_signal
+            .set(decoded_state.game_master.clone());
+        ctx.character_sheets_signal
+            .set(decoded_state.character_sheets.clone());
+        ctx.sheet_templates_signal
+            .set(decoded_state.sheet_templates.clone());
+        ctx.compendium_sources_signal
+            .set(decoded_state.compendium_sources.clone());
+        ctx.handouts_signal.set(decoded_state.handouts.clone());
         storage::save_state_in_background(ctx.room_name, &decoded_state);
 
         // Очищаем ожидание и закрываем окно конфликта
@@ -389,6 +400,17 @@ pub fn handle_snapshot(payload: SyncSnapshotPayload, ctx: &HandlerContext<'_>) {
         ctx.scenes_signal.set(decoded_state.scenes.clone());
         ctx.active_scene_id_signal
             .set(decoded_state.active_scene_id.clone());
+        ctx.scene_assignments_signal
+            .set(decoded_state.scene_assignments.clone());
+        ctx.game_master_signal
+            .set(decoded_state.game_master.clone());
+        ctx.character_sheets_signal
+            .set(decoded_state.character_sheets.clone());
+        ctx.sheet_templates_signal
+            .set(decoded_state.sheet_templates.clone());
+        ctx.compendium_sources_signal
+            .set(decoded_state.compendium_sources.clone());
+        ctx.handouts_signal.set(decoded_state.handouts.clone());
         storage::save_state_in_background(ctx.room_name, &decoded_state);
 
         ctx.conflict_signal.set(None);
@@ -453,6 +475,17 @@ pub fn handle_snapshot(payload: SyncSnapshotPayload, ctx: &HandlerContext<'_>) {
                 ctx.scenes_signal.set(decoded_state.scenes.clone());
                 ctx.active_scene_id_signal
                     .set(decoded_state.active_scene_id.clone());
+                ctx.scene_assignments_signal
+                    .set(decoded_state.scene_assignments.clone());
+                ctx.game_master_signal
+                    .set(decoded_state.game_master.clone());
+                ctx.character_sheets_signal
+                    .set(decoded_state.character_sheets.clone());
+                ctx.sheet_templates_signal
+                    .set(decoded_state.sheet_templates.clone());
+                ctx.compendium_sources_signal
+                    .set(decoded_state.compendium_sources.clone());
+                ctx.handouts_signal.set(decoded_state.handouts.clone());
                 storage::save_state_in_background(ctx.room_name, &decoded_state);
                 ctx.conflict_signal.set(None);
 
@@ -496,6 +529,17 @@ pub fn handle_snapshot(payload: SyncSnapshotPayload, ctx: &HandlerContext<'_>) {
             ctx.scenes_signal.set(decoded_state.scenes.clone());
             ctx.active_scene_id_signal
                 .set(decoded_state.active_scene_id.clone());
+            ctx.scene_assignments_signal
+                .set(decoded_state.scene_assignments.clone());
+            ctx.game_master_signal
+                .set(decoded_state.game_master.clone());
+            ctx.character_sheets_signal
+                .set(decoded_state.character_sheets.clone());
+            ctx.sheet_templates_signal
+                .set(decoded_state.sheet_templates.clone());
+            ctx.compendium_sources_signal
+                .set(decoded_state.compendium_sources.clone());
+            ctx.handouts_signal.set(decoded_state.handouts.clone());
             storage::save_state_in_background(ctx.room_name, &decoded_state);
 
             // Очищаем конфликт при успешной синхронизации
@@ -60,6 +60,17 @@ pub fn handle_hash_selection_voting_result(payload: VotingResultPayload, ctx: &H
                 ctx.scenes_signal.set(chosen_state.scenes.clone());
                 ctx.active_scene_id_signal
                     .set(chosen_state.active_scene_id.clone());
+                ctx.scene_assignments_signal
+                    .set(chosen_state.scene_assignments.clone());
+                ctx.game_master_signal
+                    .set(chosen_state.game_master.clone());
+                ctx.character_sheets_signal
+                    .set(chosen_state.character_sheets.clone());
+                ctx.sheet_templates_signal
+                    .set(chosen_state.sheet_templates.clone());
+                ctx.compendium_sources_signal
+                    .set(chosen_state.compendium_sources.clone());
+                ctx.handouts_signal.set(chosen_state.handouts.clone());
                 ctx.voting_results.set(chosen_state.voting_results.clone());
                 storage::save_state_in_background(ctx.room_name, chosen_state);
 
@@ -11,10 +11,15 @@ mod utils;
 pub use connection::{ConnectWebSocketArgs, OutboundPriority, WsSender, connect_websocket};
 pub(crate) use crypto::RoomCryptoState;
 pub use file_transfer::{CHAT_FILE_INPUT_ACCEPT, FileTransferStage, FileTransferState};
+pub(crate) use file_transfer::{blob_to_bytes, bytes_to_blob};
 pub(crate) use snapshot_codec::SnapshotCodec;
 pub(crate) use storage::{
-    StoredNoteBucket, StoredTokenLibraryItem, delete_note, delete_state, delete_token_library_item,
-    load_notes, load_token_library, move_state, save_note, save_token_library_item,
+    StoredEncounter, StoredHandoutSource, StoredNoteBucket, StoredSceneTemplate,
+    StoredTokenLibraryItem, delete_encounter, delete_handout_source, delete_note,
+    delete_scene_template, delete_state, delete_token_library_item, encounter_key,
+    handout_source_key, load_encounters, load_handout_sources, load_notes, load_private_handouts,
+    load_scene_templates, load_sheet_secrets, load_token_library, move_state, save_encounter,
+    save_handout_source, save_note, save_scene_template, save_token_library_item,
     token_library_key,
 };
 pub use types::{ConflictResolutionHandle, ConflictType, CursorSignals, SyncConflict};
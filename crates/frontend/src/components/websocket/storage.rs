@@ -1,18 +1,25 @@
+use crate::components::encounters::model::EncounterCreature;
 use js_sys::{Object, Reflect};
 use leptos::task::spawn_local;
 use log::warn;
 use rexie::{ObjectStore, Rexie, TransactionMode};
 use serde::{Deserialize, Serialize};
-use shared::events::{FileRef, NotePayload, RoomState};
+use shared::events::{FileRef, Handout, NotePayload, RoomState, Scene};
+use std::collections::{BTreeMap, HashMap};
 use web_sys::wasm_bindgen::{JsCast, JsValue};
 use web_sys::{Blob, Url};
 
 const DATABASE_NAME: &str = "dnd_vtt";
-const DATABASE_VERSION: u32 = 3;
+const DATABASE_VERSION: u32 = 7;
 const ROOM_STATES_STORE: &str = "room_states";
 const FILES_STORE: &str = "files";
 const TOKEN_LIBRARY_STORE: &str = "token_library";
 const NOTES_STORE: &str = "notes";
+const ENCOUNTERS_STORE: &str = "encounters";
+const SCENE_TEMPLATES_STORE: &str = "scene_templates";
+const SHEET_SECRETS_STORE: &str = "sheet_secrets";
+const HANDOUTS_STORE: &str = "handouts";
+const HANDOUT_SOURCES_STORE: &str = "handout_sources";
 
 type StorageResult<T> = Result<T, String>;
 
@@ -39,6 +46,59 @@ pub struct StoredTokenLibraryItem {
     pub height_cells: u16,
 }
 
+/// An encounter prepared by the game master; kept on this device per room.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub struct StoredEncounter {
+    pub key: String,
+    pub room_name: String,
+    pub id: String,
+    pub name: String,
+    pub creatures: Vec<EncounterCreature>,
+    #[serde(default)]
+    pub party_levels: Vec<u8>,
+    pub updated_at_ms: f64,
+}
+
+/// A scene layout saved on this device for reuse in any room.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct StoredSceneTemplate {
+    pub id: String,
+    pub name: String,
+    pub scene: Scene,
+    pub created_at_ms: f64,
+}
+
+/// Private field values of a character sheet; only the owner and the game
+/// master receive them, so they live on this device instead of the room state.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct StoredSheetSecrets {
+    pub key: String,
+    pub room_name: String,
+    pub owner_username: String,
+    pub sheet_id: String,
+    pub values: BTreeMap<String, String>,
+}
+
+/// A handout addressed to chosen players; only they and the game master
+/// receive it, so it lives on this device instead of the room state.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub struct StoredHandout {
+    pub key: String,
+    pub room_name: String,
+    pub owner_username: String,
+    pub handout: Handout,
+}
+
+/// The original image a handout is revealed from; only the game master's
+/// device has it, players get rendered crops.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct StoredHandoutSource {
+    pub key: String,
+    pub room_name: String,
+    pub handout_id: String,
+    pub source: FileRef,
+}
+
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
 pub enum StoredNoteBucket {
     Private,
@@ -70,6 +130,11 @@ async fn open_database() -> StorageResult<Rexie> {
         .add_object_store(ObjectStore::new(FILES_STORE).key_path("hash"))
         .add_object_store(ObjectStore::new(TOKEN_LIBRARY_STORE).key_path("key"))
         .add_object_store(ObjectStore::new(NOTES_STORE).key_path("key"))
+        .add_object_store(ObjectStore::new(ENCOUNTERS_STORE).key_path("key"))
+        .add_object_store(ObjectStore::new(SCENE_TEMPLATES_STORE).key_path("id"))
+        .add_object_store(ObjectStore::new(SHEET_SECRETS_STORE).key_path("key"))
+        .add_object_store(ObjectStore::new(HANDOUTS_STORE).key_path("key"))
+        .add_object_store(ObjectStore::new(HANDOUT_SOURCES_STORE).key_path("key"))
         .build()
         .await
         .map_err(|error| format!("failed to open IndexedDB: {error:?}"))
@@ -240,6 +305,426 @@ pub async fn delete_token_library_item(room_name: &str, token_id: &str) -> Stora
     Ok(())
 }
 
+pub fn encounter_key(room_name: &str, encounter_id: &str) -> String {
+    format!("{room_name}:{encounter_id}")
+}
+
+fn sort_encounters(encounters: &mut [StoredEncounter]) {
+    encounters.sort_by(|left, right| {
+        left.name
+            .to_lowercase()
+            .cmp(&right.name.to_lowercase())
+            .then_with(|| left.id.cmp(&right.id))
+    });
+}
+
+pub async fn load_encounters(room_name: &str) -> StorageResult<Vec<StoredEncounter>> {
+    let database = open_database().await?;
+    let transaction = database
+        .transaction(&[ENCOUNTERS_STORE], TransactionMode::ReadOnly)
+        .map_err(|error| format!("failed to open encounters read transaction: {error:?}"))?;
+    let store = transaction
+        .store(ENCOUNTERS_STORE)
+        .map_err(|error| format!("failed to open encounters store: {error:?}"))?;
+
+    let values = store
+        .get_all(None, None)
+        .await
+        .map_err(|error| format!("failed to read encounters from IndexedDB: {error:?}"))?;
+
+    transaction
+        .done()
+        .await
+        .map_err(|error| format!("encounters read transaction failed: {error:?}"))?;
+
+    let mut encounters = values
+        .into_iter()
+        .map(|value| {
+            serde_wasm_bindgen::from_value::<StoredEncounter>(value)
+                .map_err(|error| format!("failed to decode encounter: {error}"))
+        })
+        .collect::<StorageResult<Vec<_>>>()?;
+    encounters.retain(|encounter| encounter.room_name == room_name);
+    sort_encounters(&mut encounters);
+    Ok(encounters)
+}
+
+pub async fn save_encounter(encounter: &StoredEncounter) -> StorageResult<()> {
+    let database = open_database().await?;
+    let transaction = database
+        .transaction(&[ENCOUNTERS_STORE], TransactionMode::ReadWrite)
+        .map_err(|error| format!("failed to open encounters write transaction: {error:?}"))?;
+    let store = transaction
+        .store(ENCOUNTERS_STORE)
+        .map_err(|error| format!("failed to open encounters store: {error:?}"))?;
+
+    let value = serde_wasm_bindgen::to_value(encounter)
+        .map_err(|error| format!("failed to encode encounter for IndexedDB: {error}"))?;
+
+    store
+        .put(&value, None)
+        .await
+        .map_err(|error| format!("failed to save encounter to IndexedDB: {error:?}"))?;
+
+    transaction
+        .done()
+        .await
+        .map_err(|error| format!("encounters write transaction failed: {error:?}"))?;
+
+    Ok(())
+}
+
+pub async fn delete_encounter(room_name: &str, encounter_id: &str) -> StorageResult<()> {
+    let database = open_database().await?;
+    let transaction = database
+        .transaction(&[ENCOUNTERS_STORE], TransactionMode::ReadWrite)
+        .map_err(|error| format!("failed to open encounters delete transaction: {error:?}"))?;
+    let store = transaction
+        .store(ENCOUNTERS_STORE)
+        .map_err(|error| format!("failed to open encounters store: {error:?}"))?;
+
+    store
+        .delete(JsValue::from_str(&encounter_key(room_name, encounter_id)))
+        .await
+        .map_err(|error| format!("failed to delete encounter from IndexedDB: {error:?}"))?;
+
+    transaction
+        .done()
+        .await
+        .map_err(|error| format!("encounters delete transaction failed: {error:?}"))?;
+
+    Ok(())
+}
+
+pub fn sheet_secrets_key(room_name: &str, owner_username: &str, sheet_id: &str) -> String {
+    format!("{room_name}:{owner_username}:{sheet_id}")
+}
+
+pub async fn load_sheet_secrets(
+    room_name: &str,
+    owner_username: &str,
+) -> StorageResult<BTreeMap<String, BTreeMap<String, String>>> {
+    let database = open_database().await?;
+    let transaction = database
+        .transaction(&[SHEET_SECRETS_STORE], TransactionMode::ReadOnly)
+        .map_err(|error| format!("failed to open sheet secrets read transaction: {error:?}"))?;
+    let store = transaction
+        .store(SHEET_SECRETS_STORE)
+        .map_err(|error| format!("failed to open sheet_secrets store: {error:?}"))?;
+
+    let values = store
+        .get_all(None, None)
+        .await
+        .map_err(|error| format!("failed to read sheet secrets from IndexedDB: {error:?}"))?;
+
+    transaction
+        .done()
+        .await
+        .map_err(|error| format!("sheet secrets read transaction failed: {error:?}"))?;
+
+    let records = values
+        .into_iter()
+        .map(|value| {
+            serde_wasm_bindgen::from_value::<StoredSheetSecrets>(value)
+                .map_err(|error| format!("failed to decode sheet secrets: {error}"))
+        })
+        .collect::<StorageResult<Vec<_>>>()?;
+    Ok(records
+        .into_iter()
+        .filter(|record| record.room_name == room_name && record.owner_username == owner_username)
+        .map(|record| (record.sheet_id, record.values))
+        .collect())
+}
+
+pub async fn save_sheet_secrets(record: &StoredSheetSecrets) -> StorageResult<()> {
+    let database = open_database().await?;
+    let transaction = database
+        .transaction(&[SHEET_SECRETS_STORE], TransactionMode::ReadWrite)
+        .map_err(|error| format!("failed to open sheet secrets write transaction: {error:?}"))?;
+    let store = transaction
+        .store(SHEET_SECRETS_STORE)
+        .map_err(|error| format!("failed to open sheet_secrets store: {error:?}"))?;
+
+    let value = serde_wasm_bindgen::to_value(record)
+        .map_err(|error| format!("failed to encode sheet secrets for IndexedDB: {error}"))?;
+
+    store
+        .put(&value, None)
+        .await
+        .map_err(|error| format!("failed to save sheet secrets to IndexedDB: {error:?}"))?;
+
+    transaction
+        .done()
+        .await
+        .map_err(|error| format!("sheet secrets write transaction failed: {error:?}"))?;
+
+    Ok(())
+}
+
+pub fn handout_key(room_name: &str, owner_username: &str, handout_id: &str) -> String {
+    format!("{room_name}:{owner_username}:{handout_id}")
+}
+
+pub async fn load_private_handouts(
+    room_name: &str,
+    owner_username: &str,
+) -> StorageResult<Vec<Handout>> {
+    let database = open_database().await?;
+    let transaction = database
+        .transaction(&[HANDOUTS_STORE], TransactionMode::ReadOnly)
+        .map_err(|error| format!("failed to open handouts read transaction: {error:?}"))?;
+    let store = transaction
+        .store(HANDOUTS_STORE)
+        .map_err(|error| format!("failed to open handouts store: {error:?}"))?;
+
+    let values = store
+        .get_all(None, None)
+        .await
+        .map_err(|error| format!("failed to read handouts from IndexedDB: {error:?}"))?;
+
+    transaction
+        .done()
+        .await
+        .map_err(|error| format!("handouts read transaction failed: {error:?}"))?;
+
+    let records = values
+        .into_iter()
+        .map(|value| {
+            serde_wasm_bindgen::from_value::<StoredHandout>(value)
+                .map_err(|error| format!("failed to decode handout: {error}"))
+        })
+        .collect::<StorageResult<Vec<_>>>()?;
+    Ok(records
+        .into_iter()
+        .filter(|record| record.room_name == room_name && record.owner_username == owner_username)
+        .map(|record| record.handout)
+        .collect())
+}
+
+pub async fn save_private_handout(record: &StoredHandout) -> StorageResult<()> {
+    let database = open_database().await?;
+    let transaction = database
+        .transaction(&[HANDOUTS_STORE], TransactionMode::ReadWrite)
+        .map_err(|error| format!("failed to open handouts write transaction: {error:?}"))?;
+    let store = transaction
+        .store(HANDOUTS_STORE)
+        .map_err(|error| format!("failed to open handouts store: {error:?}"))?;
+
+    let value = serde_wasm_bindgen::to_value(record)
+        .map_err(|error| format!("failed to encode handout for IndexedDB: {error}"))?;
+
+    store
+        .put(&value, None)
+        .await
+        .map_err(|error| format!("failed to save handout to IndexedDB: {error:?}"))?;
+
+    transaction
+        .done()
+        .await
+        .map_err(|error| format!("handouts write transaction failed: {error:?}"))?;
+
+    Ok(())
+}
+
+pub async fn delete_private_handout(
+    room_name: &str,
+    owner_username: &str,
+    handout_id: &str,
+) -> StorageResult<()> {
+    let database = open_database().await?;
+    let transaction = database
+        .transaction(&[HANDOUTS_STORE], TransactionMode::ReadWrite)
+        .map_err(|error| format!("failed to open handouts delete transaction: {error:?}"))?;
+    let store = transaction
+        .store(HANDOUTS_STORE)
+        .map_err(|error| format!("failed to open handouts store: {error:?}"))?;
+
+    store
+        .delete(JsValue::from_str(&handout_key(
+            room_name,
+            owner_username,
+            handout_id,
+        )))
+        .await
+        .map_err(|error| format!("failed to delete handout from IndexedDB: {error:?}"))?;
+
+    transaction
+        .done()
+        .await
+        .map_err(|error| format!("handouts delete transaction failed: {error:?}"))?;
+
+    Ok(())
+}
+
+pub fn handout_source_key(room_name: &str, handout_id: &str) -> String {
+    format!("{room_name}:{handout_id}")
+}
+
+/// Original images of the room's handouts, keyed by handout id.
+pub async fn load_handout_sources(room_name: &str) -> StorageResult<HashMap<String, FileRef>> {
+    let database = open_database().await?;
+    let transaction = database
+        .transaction(&[HANDOUT_SOURCES_STORE], TransactionMode::ReadOnly)
+        .map_err(|error| format!("failed to open handout sources read transaction: {error:?}"))?;
+    let store = transaction
+        .store(HANDOUT_SOURCES_STORE)
+        .map_err(|error| format!("failed to open handout_sources store: {error:?}"))?;
+
+    let values = store
+        .get_all(None, None)
+        .await
+        .map_err(|error| format!("failed to read handout sources from IndexedDB: {error:?}"))?;
+
+    transaction
+        .done()
+        .await
+        .map_err(|error| format!("handout sources read transaction failed: {error:?}"))?;
+
+    let records = values
+        .into_iter()
+        .map(|value| {
+            serde_wasm_bindgen::from_value::<StoredHandoutSource>(value)
+                .map_err(|error| format!("failed to decode handout source: {error}"))
+        })
+        .collect::<StorageResult<Vec<_>>>()?;
+    Ok(records
+        .into_iter()
+        .filter(|record| record.room_name == room_name)
+        .map(|record| (record.handout_id, record.source))
+        .collect())
+}
+
+pub async fn save_handout_source(record: &StoredHandoutSource) -> StorageResult<()> {
+    let database = open_database().await?;
+    let transaction = database
+        .transaction(&[HANDOUT_SOURCES_STORE], TransactionMode::ReadWrite)
+        .map_err(|error| format!("failed to open handout sources write transaction: {error:?}"))?;
+    let store = transaction
+        .store(HANDOUT_SOURCES_STORE)
+        .map_err(|error| format!("failed to open handout_sources store: {error:?}"))?;
+
+    let value = serde_wasm_bindgen::to_value(record)
+        .map_err(|error| format!("failed to encode handout source for IndexedDB: {error}"))?;
+
+    store
+        .put(&value, None)
+        .await
+        .map_err(|error| format!("failed to save handout source to IndexedDB: {error:?}"))?;
+
+    transaction
+        .done()
+        .await
+        .map_err(|error| format!("handout sources write transaction failed: {error:?}"))?;
+
+    Ok(())
+}
+
+pub async fn delete_handout_source(room_name: &str, handout_id: &str) -> StorageResult<()> {
+    let database = open_database().await?;
+    let transaction = database
+        .transaction(&[HANDOUT_SOURCES_STORE], TransactionMode::ReadWrite)
+        .map_err(|error| format!("failed to open handout sources delete transaction: {error:?}"))?;
+    let store = transaction
+        .store(HANDOUT_SOURCES_STORE)
+        .map_err(|error| format!("failed to open handout_sources store: {error:?}"))?;
+
+    store
+        .delete(JsValue::from_str(&handout_source_key(
+            room_name, handout_id,
+        )))
+        .await
+        .map_err(|error| format!("failed to delete handout source from IndexedDB: {error:?}"))?;
+
+    transaction
+        .done()
+        .await
+        .map_err(|error| format!("handout sources delete transaction failed: {error:?}"))?;
+
+    Ok(())
+}
+
+pub async fn load_scene_templates() -> StorageResult<Vec<StoredSceneTemplate>> {
+    let database = open_database().await?;
+    let transaction = database
+        .transaction(&[SCENE_TEMPLATES_STORE], TransactionMode::ReadOnly)
+        .map_err(|error| format!("failed to open scene templates read transaction: {error:?}"))?;
+    let store = transaction
+        .store(SCENE_TEMPLATES_STORE)
+        .map_err(|error| format!("failed to open scene_templates store: {error:?}"))?;
+
+    let values = store
+        .get_all(None, None)
+        .await
+        .map_err(|error| format!("failed to read scene templates from IndexedDB: {error:?}"))?;
+
+    transaction
+        .done()
+        .await
+        .map_err(|error| format!("scene templates read transaction failed: {error:?}"))?;
+
+    let mut templates = values
+        .into_iter()
+        .map(|value| {
+            serde_wasm_bindgen::from_value::<StoredSceneTemplate>(value)
+                .map_err(|error| format!("failed to decode scene template: {error}"))
+        })
+        .collect::<StorageResult<Vec<_>>>()?;
+    templates.sort_by(|left, right| {
+        left.name
+            .to_lowercase()
+            .cmp(&right.name.to_lowercase())
+            .then_with(|| left.id.cmp(&right.id))
+    });
+    Ok(templates)
+}
+
+pub async fn save_scene_template(template: &StoredSceneTemplate) -> StorageResult<()> {
+    let database = open_database().await?;
+    let transaction = database
+        .transaction(&[SCENE_TEMPLATES_STORE], TransactionMode::ReadWrite)
+        .map_err(|error| format!("failed to open scene templates write transaction: {error:?}"))?;
+    let store = transaction
+        .store(SCENE_TEMPLATES_STORE)
+        .map_err(|error| format!("failed to open scene_templates store: {error:?}"))?;
+
+    let value = serde_wasm_bindgen::to_value(template)
+        .map_err(|error| format!("failed to encode scene template for IndexedDB: {error}"))?;
+
+    store
+        .put(&value, None)
+        .await
+        .map_err(|error| format!("failed to save scene template to IndexedDB: {error:?}"))?;
+
+    transaction
+        .done()
+        .await
+        .map_err(|error| format!("scene templates write transaction failed: {error:?}"))?;
+
+    Ok(())
+}
+
+pub async fn delete_scene_template(template_id: &str) -> StorageResult<()> {
+    let database = open_database().await?;
+    let transaction = database
+        .transaction(&[SCENE_TEMPLATES_STORE], TransactionMode::ReadWrite)
+        .map_err(|error| format!("failed to open scene templates delete transaction: {error:?}"))?;
+    let store = transaction
+        .store(SCENE_TEMPLATES_STORE)
+        .map_err(|error| format!("failed to open scene_templates store: {error:?}"))?;
+
+    store
+        .delete(JsValue::from_str(template_id))
+        .await
+        .map_err(|error| format!("failed to delete scene template from IndexedDB: {error:?}"))?;
+
+    transaction
+        .done()
+        .await
+        .map_err(|error| format!("scene templates delete transaction failed: {error:?}"))?;
+
+    Ok(())
+}
+
 fn sort_notes(notes: &mut [NotePayload]) {
     notes.sort_by(|left, right| {
         right
@@ -587,4 +1072,18 @@ mod tests {
             "room-a:gm:direct:note-1"
         );
     }
+
+    #[test]
+    fn sheet_secrets_key_is_namespaced_by_viewer() {
+        assert_eq!(
+            sheet_secrets_key("room-a", "gm", "sheet-1"),
+            "room-a:gm:sheet-1"
+        );
+    }
+
+    #[test]
+    fn handout_key_is_namespaced_by_viewer() {
+        assert_eq!(handout_key("room-a", "bob", "h1"), "room-a:bob:h1");
+        assert_eq!(handout_source_key("room-a", "h1"), "room-a:h1");
+    }
 }
@@ -35,6 +35,10 @@ leptos_i18n::declare_locales! {
             notes: "Notes",
             scenes: "Scenes",
             tokens: "Tokens",
+            characters: "Characters",
+            compendium: "Compendium",
+            encounters: "Encounters",
+            handouts: "Handouts",
             settings: "Settings",
             statistics: "Statistics",
             voting: "Voting",
@@ -42,6 +46,10 @@ leptos_i18n::declare_locales! {
             hotkey_notes: "N",
             hotkey_scenes: "G",
             hotkey_tokens: "T",
+            hotkey_characters: "H",
+            hotkey_compendium: "K",
+            hotkey_encounters: "E",
+            hotkey_handouts: "O",
             hotkey_settings: "S",
             hotkey_voting: "V",
             hotkey_statistics: "St",
@@ -60,15 +68,33 @@ leptos_i18n::declare_locales! {
             save_button: "Save",
             cancel_button: "Cancel",
             edit_button: "Edit",
+            character_sheet_button: "Character sheet",
             delete_button: "Delete",
             empty_state: "No tokens yet. Create one and drag it onto the board.",
             cells_suffix: "cells",
             name_label: "Name",
             width_label: "Width (cells)",
             height_label: "Height (cells)",
+            rotation_label: "Facing (degrees)",
+            elevation_label: "Elevation (ft)",
             error_name_required: "Token name is required",
             error_image_required: "Token image is required",
             error_dimensions_invalid: "Token width and height must be numbers between 1 and 16",
+            error_facing_invalid: "Facing must be a number and elevation a whole number between -10000 and 10000 ft",
+            error_aura_invalid: "Each aura needs a radius of 1-1000 ft, a #RRGGBB color and opacity of 0-100%; up to 8 auras per token",
+            owner_label: "Owner",
+            owner_placeholder: "Player username (empty: game master)",
+            auras_label: "Auras",
+            aura_add_button: "Add aura",
+            aura_remove_button: "Remove aura",
+            aura_label_placeholder: "Label (e.g. Aura of Protection)",
+            aura_radius_label: "Radius, ft",
+            aura_color_label: "Color",
+            aura_opacity_label: "Opacity",
+            aura_visibility_label: "Visible to",
+            aura_visibility_all: "Everyone",
+            aura_visibility_owner: "Owner",
+            aura_visibility_gm: "Game master",
             error_image_must_be_image: "Token image must be an image file",
             menu_label: "Token",
             editor_title: "Edit Token",
@@ -76,6 +102,114 @@ leptos_i18n::declare_locales! {
             save_to_library: "Save to library",
             delete_from_scene: "Delete from scene",
         },
+        characters: {
+            title: "Character Sheets",
+            list_title: "Sheets",
+            empty: "No character sheets yet",
+            name_placeholder: "Character name",
+            template_label: "Template",
+            create_button: "Create sheet",
+            upload_template: "Upload JSON template",
+            error_template: "Invalid sheet template",
+            error_roll: "This roll formula cannot be evaluated",
+            select_hint: "Select a sheet or create a new one",
+            missing_template: "The template of this sheet is not available in this room",
+            owner_label: "Player",
+            owner_gm: "Game master",
+            token_label: "Linked token",
+            token_none: "No token",
+            delete_button: "Delete sheet",
+        },
+        compendium: {
+            title: "Compendium",
+            import_button: "Import JSON / ZIP",
+            importing: "Importing…",
+            sources_title: "Sources",
+            sources_empty: "No compendium imported yet. The game master can import the SRD or any compendium in the documented JSON format.",
+            loading: "Downloading…",
+            remove_button: "Remove",
+            tab_monsters: "Monsters",
+            tab_spells: "Spells",
+            search_placeholder: "Search by name",
+            min_cr_placeholder: "CR from",
+            max_cr_placeholder: "CR to",
+            any_type: "Any type",
+            any_level: "Any level",
+            any_school: "Any school",
+            any_class: "Any class",
+            no_results: "Nothing found",
+            more_results: "Refine the search to see more results",
+            drag_hint: "Drag a monster onto a scene to place a token with its stats",
+            spell_hint: "Click a spell to post its card to chat",
+            armor_class: "AC",
+            hit_points: "HP",
+            speed: "Speed",
+            challenge: "CR",
+            actions: "Actions",
+            add_to_encounter: "Add to encounter",
+            error_import: "This file is not a compendium in a supported format",
+        },
+        encounters: {
+            title: "Encounter Builder",
+            new_encounter: "New encounter",
+            untitled: "Encounter",
+            name_placeholder: "Encounter name",
+            save_button: "Save",
+            delete_button: "Delete encounter",
+            creatures_title: "Creatures",
+            creatures_empty: "No creatures yet",
+            compendium_hint: "Add monsters from the compendium, a token from the library or a custom creature",
+            custom_name_placeholder: "Creature name",
+            cr_placeholder: "CR",
+            library_placeholder: "Token from library",
+            add_button: "Add",
+            party_title: "Party",
+            party_placeholder: "Character levels, e.g. 3, 3, 4, 5",
+            party_from_sheets: "From characters",
+            total_xp: "XP",
+            adjusted_xp: "Adjusted XP",
+            thresholds: "Easy / Medium / Hard / Deadly",
+            difficulty_trivial: "Trivial",
+            difficulty_easy: "Easy",
+            difficulty_medium: "Medium",
+            difficulty_hard: "Hard",
+            difficulty_deadly: "Deadly",
+            difficulty_unknown: "Enter the party to see the difficulty",
+            deploy_button: "Deploy",
+            deploying: "Deploying…",
+            deploy_gm_only: "Only the game master can deploy encounters",
+            error_deploy: "Could not deploy the encounter",
+            error_too_many: "Too many creatures to deploy at once (100 at most)",
+        },
+        handouts: {
+            title: "Handouts",
+            compose_title: "Show a handout",
+            file_placeholder: "Choose an image or PDF",
+            files_empty: "No images or PDFs in this room yet. Upload one to a scene or attach it in chat first.",
+            title_placeholder: "Title",
+            reveal_label: "Reveal",
+            reveal_whole: "Whole",
+            reveal_top: "Top half",
+            reveal_bottom: "Bottom half",
+            reveal_left: "Left half",
+            reveal_right: "Right half",
+            reveal_center: "Center",
+            recipients_label: "Show to:",
+            everyone: "Everyone",
+            recipients_refresh: "Refresh players",
+            recipients_loading: "Looking for players…",
+            show_button: "Show",
+            error_no_file: "Choose a file to show",
+            gallery_title: "Gallery",
+            gallery_empty: "Nothing has been shown yet",
+            revealed: "revealed",
+            reveal_more_button: "Reveal more",
+            reveal_all_button: "Reveal all",
+            show_again_button: "Show again",
+            remove_button: "Remove",
+            loading: "Downloading…",
+            close_button: "Close",
+        },
         scenes: {
             title: "Scenes",
             list_title: "Room Scenes",
@@ -112,6 +246,10 @@ leptos_i18n::declare_locales! {
             background_offset_y_label: "Offset Y (px)",
             background_rotation_label: "Rotation (deg)",
             background_reset_fit_button: "Reset fit",
+            auto_fit_grid_button: "Auto-fit grid",
+            auto_fit_grid_running: "Looking for a grid...",
+            auto_fit_grid_result: "Grid found",
+            auto_fit_grid_failed: "No grid found on the background",
             current_active: "Active scene",
             no_active: "No active scene selected",
             create_button: "Create Scene",
@@ -120,6 +258,47 @@ leptos_i18n::declare_locales! {
             error_empty_name: "Scene name is required",
             error_invalid_grid: "Grid values must be valid positive numbers",
             error_limit: "The room already has the maximum number of scenes",
+            folder_label: "Folder",
+            folder_placeholder: "No folder",
+            no_folder: "No folder",
+            duplicate_button: "Duplicate",
+            duplicate_with_tokens_button: "Duplicate with tokens",
+            copy_suffix: "(copy)",
+            save_template_button: "Save as template",
+            templates_button: "Templates",
+            templates_empty: "No templates yet. Save a scene as a template to reuse its layout in any room.",
+            use_template_button: "Use",
+            error_template_limit: "The template limit has been reached",
+            select_title: "Select for bulk actions",
+            bulk_selected: "selected",
+            bulk_move_button: "Move to folder",
+            bulk_clear_button: "Clear",
+            move_up_title: "Move up",
+            move_down_title: "Move down",
+            import_map_button: "Import map",
+            importing_map: "Importing map...",
+            error_map_import: "The file is not a Universal VTT map (.dd2vtt, .uvtt)",
+            error_map_too_large: "The map has too many walls, doors or lights to share as one scene",
+            levels_title: "Floors",
+            levels_hint: "The scene background is the ground floor. Extra floors share its grid and show only the pawns standing on them; players see the floor of their own pawn.",
+            level_add_button: "Add floor",
+            level_default_name: "Floor",
+            level_name_placeholder: "Floor name",
+            level_remove_button: "Remove floor",
+            base_level: "Ground floor",
+            stairs_title: "Stairs and portals",
+            stairs_hint: "A pawn dropped on stairs moves to the linked floor and cell. Cells are counted from 1 at the top-left corner.",
+            stairs_add_button: "Add stairs",
+            stairs_from_label: "From",
+            stairs_to_label: "To",
+            stairs_label_placeholder: "Label",
+            stairs_remove_button: "Remove",
+            split_party_title: "Split party",
+            split_party_hint: "Tick players and move them to their own scene. Players follow the active scene unless moved.",
+            split_party_empty: "No players yet. Players appear once they own a token or come online.",
+            split_party_no_scene: "No scene",
+            split_party_follow_active: "Follow the active scene",
+            split_party_move_button: "Move",
         },
         settings: {
             title: "Settings",
@@ -128,9 +307,20 @@ leptos_i18n::declare_locales! {
             show_workspace_hint_hint: "Controls the help card in the bottom-right corner of the board.",
             show_inactive_scene_contents: "Show inactive scene contents",
             show_inactive_scene_contents_hint: "Disables blur on inactive scenes, shows their pawns, and allows local editing there.",
+            board_renderer: "Board renderer",
+            board_renderer_hint: "Canvas paints backgrounds, grids and tokens on one canvas and only repaints what changed; faster on large scenes.",
+            board_renderer_dom: "DOM",
+            board_renderer_canvas: "Canvas",
+            show_render_stats: "Show render statistics",
+            show_render_stats_hint: "Shows frame times on the board and a benchmark for comparing renderers.",
             clear_room_local_state: "Current room local state",
             clear_room_local_state_hint: "Deletes the local snapshot for the current room and asks peers for the state again.",
             clear_room_local_state_button: "Clear and resync room",
+            game_master: "Game master",
+            game_master_none: "not assigned",
+            game_master_hint: "The game master sees hidden auras and private token details. Anyone can take a free seat; only the current game master can step down.",
+            game_master_claim: "Become game master",
+            game_master_release: "Step down",
             close: "Close",
         },
         scene_board: {
@@ -142,7 +332,44 @@ leptos_i18n::declare_locales! {
             board_select: "LMB on board: select area",
             board_drag: "Drag board header: move + snap",
             token_place: "Drag from token library: place pawn",
+            token_rotate: "Q / E: rotate selected pawn (Shift: fine), drag its handle to rotate (Ctrl: free)",
+            token_range: "Hover a pawn: show its auras and reach rings",
             inactive_blur: "Inactive boards are blurred",
+            terrain_paint: "Terrain tool: LMB drag paints cells, difficult costs double",
+            terrain_difficult: "Difficult",
+            terrain_hazard: "Hazard",
+            terrain_water: "Water",
+            terrain_custom: "Custom",
+            terrain_erase: "Erase",
+            terrain_label_placeholder: "Label",
+            base_floor: "Ground floor",
+            floor_switch_title: "Floor shown on your board",
+            stairs_move: "Drop a pawn on stairs: move it to the linked floor",
+            camera_follow_title: "Follow another user's camera",
+            camera_follow_none: "Own view",
+            camera_pull: "Pull view",
+            camera_pull_title: "Move every player's board to your view",
+            camera_lock: "Lock view",
+            camera_lock_title: "Hold players' boards on your view until released",
+            camera_release: "Release view",
+            camera_locked: "View locked by the GM",
+            export_title: "Export scene as PNG",
+            export_include_grid: "Grid",
+            export_include_gm: "GM content (walls, lights, hidden notes)",
+            export_scale_title: "Image resolution",
+            export_button: "Export PNG",
+            export_running: "Exporting…",
+            export_no_scene: "No active scene to export",
+            minimap_title: "Minimap",
+            minimap_fit_all: "Fit all",
+            minimap_fit_all_title: "Zoom out to show every scene",
+            render_stats_title: "Render stats",
+            render_stats_average: "avg",
+            render_stats_frames: "frames",
+            render_stats_empty: "Move the board to collect frame times",
+            render_stats_benchmark: "Benchmark",
+            render_stats_benchmark_title: "Pan and zoom the board for a fixed number of frames and keep the result for this renderer",
+            render_stats_benchmark_running: "Running…",
         },
         statistics: {
             title: "Statistics",
@@ -171,14 +398,15 @@ leptos_i18n::declare_locales! {
             tab_private: "Private",
             tab_direct: "Direct",
             title_placeholder: "Note title (optional)",
-            recipient_placeholder: "Select recipient",
+            recipient_placeholder: "Send to:",
+            group_name_placeholder: "Group name (optional), e.g. Party A",
             refresh_recipients_button: "Refresh",
             recipients_loading: "Refreshing active users...",
             no_active_recipients: "No active users found",
             active_recipients_count: "active users",
             recipients_updated_prefix: "Updated",
             recipients_not_loaded: "List not loaded yet",
-            body_placeholder: "Write Markdown here...",
+            body_placeholder: "Write Markdown here... Drop or paste files to embed them.",
             create_button: "Create note",
             save_button: "Save note",
             cancel_button: "Cancel",
@@ -189,7 +417,37 @@ leptos_i18n::declare_locales! {
             on_board_badge: "On board",
             edit_button: "Edit",
             remove_from_board_button: "Remove from board",
+            anchor_to_scene_button: "Attach to scene",
+            detach_from_scene_button: "Detach from scene",
+            pin_icon_none: "No pin",
             delete_button: "Delete",
+            view_notes: "Notes",
+            view_index: "Index",
+            view_graph: "Graph",
+            link_hint: "[[Note title]] links a note, @token and #scene point at the board.",
+            linked_from: "Linked from:",
+            journal_empty: "The journal is empty.",
+            links_out: "links",
+            links_in: "backlinks",
+            folder_placeholder: "Folder, e.g. Campaign/NPCs",
+            tags_placeholder: "Tags, comma-separated",
+            pinned_label: "Pinned",
+            pin_button: "Pin",
+            unpin_button: "Unpin",
+            search_placeholder: "Search all notes... (#tag filters by tag)",
+            all_folders: "All folders",
+            all_tags: "All tags",
+            clear_filters: "Clear filters",
+            no_matches: "No notes match the filters.",
+            history_button: "History",
+            restore_button: "Restore",
+            co_editing_with: "Also editing:",
+            diff_hint: "Changes from this revision to the current text",
+            export_journal_button: "Export journal",
+            import_journal_button: "Import journal",
+            journal_exported: "Journal exported",
+            journal_imported: "Notes imported:",
+            journal_skipped: "skipped:",
         },
         voting: {
             title: "Voting",
@@ -283,6 +541,10 @@ leptos_i18n::declare_locales! {
             notes: "Заметки",
             scenes: "Сцены",
             tokens: "Пешки",
+            characters: "Персонажи",
+            compendium: "Компендиум",
+            encounters: "Столкновения",
+            handouts: "Раздаточные материалы",
             settings: "Настройки",
             statistics: "Статистика",
             voting: "Голосование",
@@ -290,6 +552,10 @@ leptos_i18n::declare_locales! {
             hotkey_notes: "Т",
             hotkey_scenes: "П",
             hotkey_tokens: "Е",
+            hotkey_characters: "Р",
+            hotkey_compendium: "Л",
+            hotkey_encounters: "У",
+            hotkey_handouts: "Щ",
             hotkey_settings: "Ы",
             hotkey_voting: "М",
             hotkey_statistics: "Ст",
@@ -308,15 +574,33 @@ leptos_i18n::declare_locales! {
             save_button: "Сохранить",
             cancel_button: "Отмена",
             edit_button: "Изменить",
+            character_sheet_button: "Лист персонажа",
             delete_button: "Удалить",
             empty_state: "Пешек пока нет. Создайте одну и перетащите её на поле.",
             cells_suffix: "клетки",
             name_label: "Название",
             width_label: "Ширина (клетки)",
             height_label: "Высота (клетки)",
+            rotation_label: "Направление (градусы)",
+            elevation_label: "Высота над полем (фт)",
             error_name_required: "Название пешки обязательно",
             error_image_required: "Изображение пешки обязательно",
             error_dimensions_invalid: "Ширина и высота пешки должны быть числами от 1 до 16",
+            error_facing_invalid: "Направление должно быть числом, а высота — целым числом от -10000 до 10000 фт",
+            error_aura_invalid: "У каждой ауры должен быть радиус 1–1000 фт, цвет #RRGGBB и непрозрачность 0–100%; не больше 8 аур на токен",
+            owner_label: "Владелец",
+            owner_placeholder: "Имя игрока (пусто: мастер)",
+            auras_label: "Ауры",
+            aura_add_button: "Добавить ауру",
+            aura_remove_button: "Удалить ауру",
+            aura_label_placeholder: "Название (например, Аура защиты)",
+            aura_radius_label: "Радиус, фт",
+            aura_color_label: "Цвет",
+            aura_opacity_label: "Непрозрачность",
+            aura_visibility_label: "Кому видна",
+            aura_visibility_all: "Всем",
+            aura_visibility_owner: "Владельцу",
+            aura_visibility_gm: "Мастеру",
             error_image_must_be_image: "Изображение пешки должно быть файлом изображения",
             menu_label: "Пешка",
             editor_title: "Редактирование пешки",
@@ -324,6 +608,114 @@ leptos_i18n::declare_locales! {
             save_to_library: "Сохранить в библиотеку",
             delete_from_scene: "Удалить со сцены",
         },
+        characters: {
+            title: "Листы персонажей",
+            list_title: "Листы",
+            empty: "Листов персонажей пока нет",
+            name_placeholder: "Имя персонажа",
+            template_label: "Шаблон",
+            create_button: "Создать лист",
+            upload_template: "Загрузить JSON-шаблон",
+            error_template: "Некорректный шаблон листа",
+            error_roll: "Формулу броска не удалось вычислить",
+            select_hint: "Выберите лист или создайте новый",
+            missing_template: "Шаблон этого листа недоступен в комнате",
+            owner_label: "Игрок",
+            owner_gm: "Мастер",
+            token_label: "Привязанная пешка",
+            token_none: "Без пешки",
+            delete_button: "Удалить лист",
+        },
+        compendium: {
+            title: "Компендиум",
+            import_button: "Импорт JSON / ZIP",
+            importing: "Импорт…",
+            sources_title: "Источники",
+            sources_empty: "Компендиум ещё не импортирован. Мастер может загрузить SRD или любой компендиум в документированном JSON-формате.",
+            loading: "Загрузка…",
+            remove_button: "Убрать",
+            tab_monsters: "Монстры",
+            tab_spells: "Заклинания",
+            search_placeholder: "Поиск по названию",
+            min_cr_placeholder: "ПО от",
+            max_cr_placeholder: "ПО до",
+            any_type: "Любой тип",
+            any_level: "Любой круг",
+            any_school: "Любая школа",
+            any_class: "Любой класс",
+            no_results: "Ничего не найдено",
+            more_results: "Уточните поиск, чтобы увидеть остальные результаты",
+            drag_hint: "Перетащите монстра на сцену, чтобы поставить токен с его характеристиками",
+            spell_hint: "Нажмите на заклинание, чтобы отправить его карточку в чат",
+            armor_class: "КД",
+            hit_points: "ХП",
+            speed: "Скорость",
+            challenge: "ПО",
+            actions: "Действия",
+            add_to_encounter: "В столкновение",
+            error_import: "Файл не является компендиумом в поддерживаемом формате",
+        },
+        encounters: {
+            title: "Конструктор столкновений",
+            new_encounter: "Новое столкновение",
+            untitled: "Столкновение",
+            name_placeholder: "Название столкновения",
+            save_button: "Сохранить",
+            delete_button: "Удалить столкновение",
+            creatures_title: "Существа",
+            creatures_empty: "Существ пока нет",
+            compendium_hint: "Добавляйте монстров из компендиума, пешку из библиотеки или своё существо",
+            custom_name_placeholder: "Имя существа",
+            cr_placeholder: "ПО",
+            library_placeholder: "Пешка из библиотеки",
+            add_button: "Добавить",
+            party_title: "Группа",
+            party_placeholder: "Уровни персонажей, например 3, 3, 4, 5",
+            party_from_sheets: "Из персонажей",
+            total_xp: "Опыт",
+            adjusted_xp: "Скорректированный опыт",
+            thresholds: "Лёгкое / Среднее / Сложное / Смертельное",
+            difficulty_trivial: "Пустяковое",
+            difficulty_easy: "Лёгкое",
+            difficulty_medium: "Среднее",
+            difficulty_hard: "Сложное",
+            difficulty_deadly: "Смертельное",
+            difficulty_unknown: "Укажите группу, чтобы увидеть сложность",
+            deploy_button: "Расставить",
+            deploying: "Расставляем…",
+            deploy_gm_only: "Расставлять столкновения может только мастер",
+            error_deploy: "Не удалось расставить столкновение",
+            error_too_many: "Слишком много существ за раз (не больше 100)",
+        },
+        handouts: {
+            title: "Раздаточные материалы",
+            compose_title: "Показать материал",
+            file_placeholder: "Выберите изображение или PDF",
+            files_empty: "В комнате пока нет изображений и PDF. Сначала загрузите файл на сцену или прикрепите его в чате.",
+            title_placeholder: "Название",
+            reveal_label: "Открыть",
+            reveal_whole: "Целиком",
+            reveal_top: "Верхнюю половину",
+            reveal_bottom: "Нижнюю половину",
+            reveal_left: "Левую половину",
+            reveal_right: "Правую половину",
+            reveal_center: "Центр",
+            recipients_label: "Кому:",
+            everyone: "Всем",
+            recipients_refresh: "Обновить игроков",
+            recipients_loading: "Ищем игроков…",
+            show_button: "Показать",
+            error_no_file: "Выберите файл для показа",
+            gallery_title: "Галерея",
+            gallery_empty: "Пока ничего не показано",
+            revealed: "открыто",
+            reveal_more_button: "Открыть больше",
+            reveal_all_button: "Открыть всё",
+            show_again_button: "Показать снова",
+            remove_button: "Удалить",
+            loading: "Загрузка…",
+            close_button: "Закрыть",
+        },
         scenes: {
             title: "Сцены",
             list_title: "Сцены комнаты",
@@ -360,6 +752,10 @@ leptos_i18n::declare_locales! {
             background_offset_y_label: "Смещение Y (px)",
             background_rotation_label: "Поворот (градусы)",
             background_reset_fit_button: "Сбросить подгонку",
+            auto_fit_grid_button: "Подогнать по сетке",
+            auto_fit_grid_running: "Поиск сетки...",
+            auto_fit_grid_result: "Сетка найдена",
+            auto_fit_grid_failed: "На фоне не найдена сетка",
             current_active: "Активная сцена",
             no_active: "Активная сцена не выбрана",
             create_button: "Создать сцену",
@@ -368,6 +764,47 @@ leptos_i18n::declare_locales! {
             error_empty_name: "Нужно указать название сцены",
             error_invalid_grid: "Параметры сетки должны быть корректными положительными числами",
             error_limit: "В комнате уже достигнут лимит сцен",
+            folder_label: "Папка",
+            folder_placeholder: "Без папки",
+            no_folder: "Без папки",
+            duplicate_button: "Дублировать",
+            duplicate_with_tokens_button: "Дублировать с токенами",
+            copy_suffix: "(копия)",
+            save_template_button: "Сохранить как шаблон",
+            templates_button: "Шаблоны",
+            templates_empty: "Шаблонов пока нет. Сохраните сцену как шаблон, чтобы использовать её раскладку в любой комнате.",
+            use_template_button: "Создать",
+            error_template_limit: "Достигнут лимит шаблонов",
+            select_title: "Выбрать для массовых действий",
+            bulk_selected: "выбрано",
+            bulk_move_button: "В папку",
+            bulk_clear_button: "Снять выбор",
+            move_up_title: "Выше",
+            move_down_title: "Ниже",
+            import_map_button: "Импорт карты",
+            importing_map: "Импорт карты...",
+            error_map_import: "Файл не является картой Universal VTT (.dd2vtt, .uvtt)",
+            error_map_too_large: "В карте слишком много стен, дверей или источников света для одной сцены",
+            levels_title: "Этажи",
+            levels_hint: "Фон сцены — первый этаж. Дополнительные этажи используют ту же сетку и показывают только стоящие на них пешки; игроки видят этаж своей пешки.",
+            level_add_button: "Добавить этаж",
+            level_default_name: "Этаж",
+            level_name_placeholder: "Название этажа",
+            level_remove_button: "Удалить этаж",
+            base_level: "Первый этаж",
+            stairs_title: "Лестницы и порталы",
+            stairs_hint: "Пешка, оставленная на лестнице, переходит на связанный этаж и клетку. Клетки считаются от 1 с левого верхнего угла.",
+            stairs_add_button: "Добавить лестницу",
+            stairs_from_label: "Откуда",
+            stairs_to_label: "Куда",
+            stairs_label_placeholder: "Подпись",
+            stairs_remove_button: "Удалить",
+            split_party_title: "Разделение группы",
+            split_party_hint: "Отметьте игроков и перенесите их на отдельную сцену. Без назначения игроки видят активную сцену.",
+            split_party_empty: "Игроков пока нет. Они появятся, когда получат токен или зайдут в комнату.",
+            split_party_no_scene: "Без сцены",
+            split_party_follow_active: "Следовать за активной сценой",
+            split_party_move_button: "Перенести",
         },
         settings: {
             title: "Настройки",
@@ -376,9 +813,20 @@ leptos_i18n::declare_locales! {
             show_workspace_hint_hint: "Управляет карточкой помощи в правом нижнем углу поля.",
             show_inactive_scene_contents: "Показывать содержимое неактивных сцен",
             show_inactive_scene_contents_hint: "Убирает блюр с неактивных сцен, показывает их пешки и разрешает локально редактировать их.",
+            board_renderer: "Отрисовка поля",
+            board_renderer_hint: "Canvas рисует фоны, сетку и токены на одном холсте и перерисовывает только изменения; быстрее на больших сценах.",
+            board_renderer_dom: "DOM",
+            board_renderer_canvas: "Canvas",
+            show_render_stats: "Показывать статистику отрисовки",
+            show_render_stats_hint: "Показывает время кадров на поле и бенчмарк для сравнения способов отрисовки.",
             clear_room_local_state: "Локальный стейт текущей комнаты",
             clear_room_local_state_hint: "Удаляет локальный снапшот текущей комнаты и заново запрашивает состояние у остальных участников.",
             clear_room_local_state_button: "Очистить и пересинхронизировать",
+            game_master: "Мастер",
+            game_master_none: "не назначен",
+            game_master_hint: "Мастер видит скрытые ауры и закрытые данные токенов. Свободное место может занять любой, сложить роль — только текущий мастер.",
+            game_master_claim: "Стать мастером",
+            game_master_release: "Сложить роль",
             close: "Закрыть",
         },
         scene_board: {
@@ -390,7 +838,44 @@ leptos_i18n::declare_locales! {
             board_select: "ЛКМ по полю: выделение области",
             board_drag: "Перетаскивание заголовка поля: перемещение и снап",
             token_place: "Перетаскивание из библиотеки пешек: поставить пешку",
+            token_rotate: "Q / E: поворот выбранной пешки (Shift: точно), перетаскивание ручки: поворот (Ctrl: свободно)",
+            token_range: "Наведение на пешку: кольца её аур и досягаемости",
             inactive_blur: "Неактивные поля размыты",
+            terrain_paint: "Инструмент местности: ЛКМ с перетаскиванием красит клетки, труднопроходимые стоят вдвое",
+            terrain_difficult: "Труднопроходимая",
+            terrain_hazard: "Опасность",
+            terrain_water: "Вода",
+            terrain_custom: "Своя",
+            terrain_erase: "Стереть",
+            terrain_label_placeholder: "Подпись",
+            base_floor: "Первый этаж",
+            floor_switch_title: "Этаж на вашем поле",
+            stairs_move: "Пешка на лестнице: переход на связанный этаж",
+            camera_follow_title: "Следовать за камерой другого пользователя",
+            camera_follow_none: "Свой вид",
+            camera_pull: "Показать всем",
+            camera_pull_title: "Перевести поля всех игроков к вашему виду",
+            camera_lock: "Закрепить вид",
+            camera_lock_title: "Удерживать поля игроков на вашем виде до снятия",
+            camera_release: "Отпустить вид",
+            camera_locked: "Вид закреплён мастером",
+            export_title: "Экспорт сцены в PNG",
+            export_include_grid: "Сетка",
+            export_include_gm: "Контент мастера (стены, свет, скрытые заметки)",
+            export_scale_title: "Разрешение изображения",
+            export_button: "Экспорт PNG",
+            export_running: "Экспорт…",
+            export_no_scene: "Нет активной сцены для экспорта",
+            minimap_title: "Миникарта",
+            minimap_fit_all: "Все сцены",
+            minimap_fit_all_title: "Отдалить камеру, чтобы показать все сцены",
+            render_stats_title: "Отрисовка",
+            render_stats_average: "в среднем",
+            render_stats_frames: "кадров",
+            render_stats_empty: "Подвигайте поле, чтобы собрать время кадров",
+            render_stats_benchmark: "Бенчмарк",
+            render_stats_benchmark_title: "Прокрутить и приблизить поле за фиксированное число кадров и сохранить результат для этого способа отрисовки",
+            render_stats_benchmark_running: "Идёт замер…",
         },
         statistics: {
             title: "Статистика",
@@ -419,14 +904,15 @@ leptos_i18n::declare_locales! {
             tab_private: "Личные",
             tab_direct: "Адресные",
             title_placeholder: "Заголовок заметки (необязательно)",
-            recipient_placeholder: "Выберите получателя",
+            recipient_placeholder: "Кому:",
+            group_name_placeholder: "Название группы (необязательно), например «Отряд А»",
             refresh_recipients_button: "Обновить",
             recipients_loading: "Обновляю активных пользователей...",
             no_active_recipients: "Активные пользователи не найдены",
             active_recipients_count: "активных пользователей",
             recipients_updated_prefix: "Обновлено",
             recipients_not_loaded: "Список ещё не загружен",
-            body_placeholder: "Пишите Markdown здесь...",
+            body_placeholder: "Пишите Markdown здесь... Перетащите или вставьте файл, чтобы встроить его.",
             create_button: "Создать заметку",
             save_button: "Сохранить заметку",
             cancel_button: "Отмена",
@@ -437,7 +923,37 @@ leptos_i18n::declare_locales! {
             on_board_badge: "На поле",
             edit_button: "Изменить",
             remove_from_board_button: "Убрать с поля",
+            anchor_to_scene_button: "Привязать к сцене",
+            detach_from_scene_button: "Отвязать от сцены",
+            pin_icon_none: "Без метки",
             delete_button: "Удалить",
+            view_notes: "Заметки",
+            view_index: "Указатель",
+            view_graph: "Граф",
+            link_hint: "[[Название заметки]] ссылается на заметку, @токен и #сцена — на поле.",
+            linked_from: "Ссылаются:",
+            journal_empty: "Журнал пуст.",
+            links_out: "ссылок",
+            links_in: "обратных ссылок",
+            folder_placeholder: "Папка, например Кампания/НИП",
+            tags_placeholder: "Теги через запятую",
+            pinned_label: "Закрепить",
+            pin_button: "Закрепить",
+            unpin_button: "Открепить",
+            search_placeholder: "Поиск по всем заметкам... (#тег — фильтр по тегу)",
+            all_folders: "Все папки",
+            all_tags: "Все теги",
+            clear_filters: "Сбросить фильтры",
+            no_matches: "Нет заметок, подходящих под фильтры.",
+            history_button: "История",
+            restore_button: "Восстановить",
+            co_editing_with: "Также редактируют:",
+            diff_hint: "Изменения от этой версии до текущего текста",
+            export_journal_button: "Экспорт журнала",
+            import_journal_button: "Импорт журнала",
+            journal_exported: "Журнал выгружен",
+            journal_imported: "Импортировано заметок:",
+            journal_skipped: "пропущено:",
         },
         voting: {
             title: "Голосование",
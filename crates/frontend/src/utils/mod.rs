@@ -1,2 +1,4 @@
 pub mod auth;
 pub mod token_refresh;
+pub mod viewport;
+pub mod zip;
@@ -0,0 +1,36 @@
+use leptos::ev;
+use leptos::prelude::*;
+
+/// Widest viewport, in CSS pixels, laid out for phones and portrait tablets.
+pub const COMPACT_VIEWPORT_MAX_WIDTH_PX: f64 = 768.0;
+
+pub fn is_compact_viewport(viewport_width: f64) -> bool {
+    viewport_width <= COMPACT_VIEWPORT_MAX_WIDTH_PX
+}
+
+/// Inner size of the browser window in CSS pixels.
+pub fn window_inner_size() -> (f64, f64) {
+    let window = window();
+    let width = window
+        .inner_width()
+        .ok()
+        .and_then(|value| value.as_f64())
+        .unwrap_or(1280.0);
+    let height = window
+        .inner_height()
+        .ok()
+        .and_then(|value| value.as_f64())
+        .unwrap_or(720.0);
+    (width, height)
+}
+
+/// Window inner size, kept current across resizes and rotations while the
+/// calling component is mounted.
+pub fn use_viewport_size() -> ReadSignal<(f64, f64)> {
+    let (size, set_size) = signal(window_inner_size());
+    let resize_handle = window_event_listener(ev::resize, move |_| {
+        set_size.set(window_inner_size());
+    });
+    on_cleanup(move || drop(resize_handle));
+    size
+}
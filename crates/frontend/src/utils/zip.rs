@@ -0,0 +1,323 @@
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use flate2::{Compression, Crc};
+use std::io::{Read, Write};
+
+const LOCAL_HEADER_SIGNATURE: u32 = 0x0403_4b50;
+const CENTRAL_HEADER_SIGNATURE: u32 = 0x0201_4b50;
+const END_OF_CENTRAL_DIRECTORY_SIGNATURE: u32 = 0x0605_4b50;
+const END_OF_CENTRAL_DIRECTORY_LEN: usize = 22;
+const METHOD_STORED: u16 = 0;
+const METHOD_DEFLATE: u16 = 8;
+const VERSION_NEEDED: u16 = 20;
+/// Имена записей в UTF-8 (бит 11 флагов общего назначения).
+const FLAG_UTF8_NAMES: u16 = 0x0800;
+/// 1 января 1980 года — самая ранняя дата в формате DOS.
+const DOS_DATE_1980_01_01: u16 = 0x0021;
+
+/// Файл, извлечённый из ZIP-архива.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZipEntry {
+    pub name: String,
+    pub data: Vec<u8>,
+}
+
+/// Читает все файлы архива (методы stored и deflate), пропуская каталоги.
+///
+/// `max_total_bytes` ограничивает суммарный размер распакованных данных,
+/// чтобы «ZIP-бомба» не съела память вкладки.
+pub fn read_zip(bytes: &[u8], max_total_bytes: usize) -> Result<Vec<ZipEntry>, String> {
+    let eocd = find_end_of_central_directory(bytes)?;
+    let entry_count = read_u16(bytes, at(eocd, 10)?)? as usize;
+    let mut cursor = read_u32(bytes, at(eocd, 16)?)? as usize;
+
+    let mut entries = Vec::with_capacity(entry_count);
+    let mut total = 0usize;
+    for _ in 0..entry_count {
+        if read_u32(bytes, cursor)? != CENTRAL_HEADER_SIGNATURE {
+            return Err("zip central directory is corrupted".to_string());
+        }
+        let method = read_u16(bytes, at(cursor, 10)?)?;
+        let compressed_size = read_u32(bytes, at(cursor, 20)?)? as usize;
+        let name_len = read_u16(bytes, at(cursor, 28)?)? as usize;
+        let extra_len = read_u16(bytes, at(cursor, 30)?)? as usize;
+        let comment_len = read_u16(bytes, at(cursor, 32)?)? as usize;
+        let local_offset = read_u32(bytes, at(cursor, 42)?)? as usize;
+        let name_bytes = slice(bytes, at(cursor, 46)?, name_len)?;
+        let name = String::from_utf8_lossy(name_bytes).into_owned();
+        cursor = at(at(at(at(cursor, 46)?, name_len)?, extra_len)?, comment_len)?;
+
+        if name.ends_with('/') {
+            continue;
+        }
+
+        if read_u32(bytes, local_offset)? != LOCAL_HEADER_SIGNATURE {
+            return Err(format!("zip entry '{name}' has no local header"));
+        }
+        let local_name_len = read_u16(bytes, at(local_offset, 26)?)? as usize;
+        let local_extra_len = read_u16(bytes, at(local_offset, 28)?)? as usize;
+        let data_start = at(at(at(local_offset, 30)?, local_name_len)?, local_extra_len)?;
+        let compressed = slice(bytes, data_start, compressed_size)?;
+
+        let remaining = max_total_bytes.saturating_sub(total);
+        let data = match method {
+            METHOD_STORED => compressed.to_vec(),
+            METHOD_DEFLATE => {
+                let mut data = Vec::new();
+                DeflateDecoder::new(compressed)
+                    .take(remaining as u64 + 1)
+                    .read_to_end(&mut data)
+                    .map_err(|error| format!("failed to inflate zip entry '{name}': {error}"))?;
+                data
+            }
+            other => {
+                return Err(format!(
+                    "zip entry '{name}' uses unsupported compression method {other}"
+                ));
+            }
+        };
+        if data.len() > remaining {
+            return Err("zip archive is too large once unpacked".to_string());
+        }
+        total += data.len();
+        entries.push(ZipEntry { name, data });
+    }
+
+    Ok(entries)
+}
+
+/// Собирает ZIP-архив из файлов. Записи сжимаются deflate, если это их
+/// уменьшает, иначе сохраняются как есть.
+pub fn write_zip(entries: &[ZipEntry]) -> Result<Vec<u8>, String> {
+    let mut out = Vec::new();
+    let mut central = Vec::new();
+    for entry in entries {
+        let mut crc = Crc::new();
+        crc.update(&entry.data);
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+        encoder
+            .write_all(&entry.data)
+            .map_err(|error| format!("failed to deflate zip entry '{}': {error}", entry.name))?;
+        let deflated = encoder
+            .finish()
+            .map_err(|error| format!("failed to deflate zip entry '{}': {error}", entry.name))?;
+        let (method, payload) = if deflated.len() < entry.data.len() {
+            (METHOD_DEFLATE, deflated.as_slice())
+        } else {
+            (METHOD_STORED, entry.data.as_slice())
+        };
+        let name = entry.name.as_bytes();
+        let name_len = u16::try_from(name.len())
+            .map_err(|_| format!("zip entry name '{}' is too long", entry.name))?;
+        let compressed_size = zip_u32(payload.len())?;
+        let size = zip_u32(entry.data.len())?;
+        let offset = zip_u32(out.len())?;
+
+        // Поля, общие для локального и центрального заголовков.
+        let mut common = Vec::with_capacity(26);
+        common.extend_from_slice(&FLAG_UTF8_NAMES.to_le_bytes());
+        common.extend_from_slice(&method.to_le_bytes());
+        common.extend_from_slice(&0u16.to_le_bytes());
+        common.extend_from_slice(&DOS_DATE_1980_01_01.to_le_bytes());
+        common.extend_from_slice(&crc.sum().to_le_bytes());
+        common.extend_from_slice(&compressed_size.to_le_bytes());
+        common.extend_from_slice(&size.to_le_bytes());
+        common.extend_from_slice(&name_len.to_le_bytes());
+        common.extend_from_slice(&0u16.to_le_bytes());
+
+        out.extend_from_slice(&LOCAL_HEADER_SIGNATURE.to_le_bytes());
+        out.extend_from_slice(&VERSION_NEEDED.to_le_bytes());
+        out.extend_from_slice(&common);
+        out.extend_from_slice(name);
+        out.extend_from_slice(payload);
+
+        central.extend_from_slice(&CENTRAL_HEADER_SIGNATURE.to_le_bytes());
+        central.extend_from_slice(&VERSION_NEEDED.to_le_bytes());
+        central.extend_from_slice(&VERSION_NEEDED.to_le_bytes());
+        central.extend_from_slice(&common);
+        // Комментарий, номер диска, внутренние и внешние атрибуты.
+        central.extend_from_slice(&[0; 10]);
+        central.extend_from_slice(&offset.to_le_bytes());
+        central.extend_from_slice(name);
+    }
+
+    let entry_count =
+        u16::try_from(entries.len()).map_err(|_| "zip archive has too many files".to_string())?;
+    let central_len = zip_u32(central.len())?;
+    let central_offset = zip_u32(out.len())?;
+    out.extend_from_slice(&central);
+    out.extend_from_slice(&END_OF_CENTRAL_DIRECTORY_SIGNATURE.to_le_bytes());
+    out.extend_from_slice(&[0; 4]);
+    out.extend_from_slice(&entry_count.to_le_bytes());
+    out.extend_from_slice(&entry_count.to_le_bytes());
+    out.extend_from_slice(&central_len.to_le_bytes());
+    out.extend_from_slice(&central_offset.to_le_bytes());
+    out.extend_from_slice(&0u16.to_le_bytes());
+    Ok(out)
+}
+
+/// Похоже ли содержимое на ZIP-архив (по сигнатуре первого заголовка).
+pub fn looks_like_zip(bytes: &[u8]) -> bool {
+    read_u32(bytes, 0).is_ok_and(|signature| {
+        signature == LOCAL_HEADER_SIGNATURE || signature == END_OF_CENTRAL_DIRECTORY_SIGNATURE
+    })
+}
+
+fn zip_u32(value: usize) -> Result<u32, String> {
+    u32::try_from(value).map_err(|_| "zip archive is too large".to_string())
+}
+
+fn find_end_of_central_directory(bytes: &[u8]) -> Result<usize, String> {
+    if bytes.len() < END_OF_CENTRAL_DIRECTORY_LEN {
+        return Err("file is too short to be a zip archive".to_string());
+    }
+    // Запись лежит в конце файла, за ней может идти комментарий до 64 КиБ.
+    let last = bytes.len() - END_OF_CENTRAL_DIRECTORY_LEN;
+    let first = last.saturating_sub(u16::MAX as usize);
+    (first..=last)
+        .rev()
+        .find(|&offset| read_u32(bytes, offset) == Ok(END_OF_CENTRAL_DIRECTORY_SIGNATURE))
+        .ok_or_else(|| "zip end of central directory not found".to_string())
+}
+
+/// Смещение `base + delta`; переполнение означает повреждённый архив.
+fn at(base: usize, delta: usize) -> Result<usize, String> {
+    base.checked_add(delta)
+        .ok_or_else(|| "zip archive is truncated".to_string())
+}
+
+fn slice(bytes: &[u8], offset: usize, len: usize) -> Result<&[u8], String> {
+    offset
+        .checked_add(len)
+        .and_then(|end| bytes.get(offset..end))
+        .ok_or_else(|| "zip archive is truncated".to_string())
+}
+
+fn read_u16(bytes: &[u8], offset: usize) -> Result<u16, String> {
+    let raw = slice(bytes, offset, 2)?;
+    Ok(u16::from_le_bytes([raw[0], raw[1]]))
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Result<u32, String> {
+    let raw = slice(bytes, offset, 4)?;
+    Ok(u32::from_le_bytes([raw[0], raw[1], raw[2], raw[3]]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::{Compression, write::DeflateEncoder};
+    use std::io::Write;
+
+    /// Собирает минимальный архив; CRC не заполняется, читатель его не проверяет.
+    fn build_zip(files: &[(&str, &[u8], bool)]) -> Vec<u8> {
+        let mut out = Vec::new();
+        let mut central = Vec::new();
+        for (name, data, deflate) in files {
+            let payload = if *deflate {
+                let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(data).unwrap();
+                encoder.finish().unwrap()
+            } else {
+                data.to_vec()
+            };
+            let method = if *deflate {
+                METHOD_DEFLATE
+            } else {
+                METHOD_STORED
+            };
+            let offset = out.len() as u32;
+
+            out.extend_from_slice(&LOCAL_HEADER_SIGNATURE.to_le_bytes());
+            out.extend_from_slice(&[20, 0, 0, 0]);
+            out.extend_from_slice(&method.to_le_bytes());
+            out.extend_from_slice(&[0; 8]);
+            out.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+            out.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            out.extend_from_slice(&(name.len() as u16).to_le_bytes());
+            out.extend_from_slice(&0u16.to_le_bytes());
+            out.extend_from_slice(name.as_bytes());
+            out.extend_from_slice(&payload);
+
+            central.extend_from_slice(&CENTRAL_HEADER_SIGNATURE.to_le_bytes());
+            central.extend_from_slice(&[20, 0, 20, 0, 0, 0]);
+            central.extend_from_slice(&method.to_le_bytes());
+            central.extend_from_slice(&[0; 8]);
+            central.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+            central.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            central.extend_from_slice(&(name.len() as u16).to_le_bytes());
+            central.extend_from_slice(&[0; 12]);
+            central.extend_from_slice(&offset.to_le_bytes());
+            central.extend_from_slice(name.as_bytes());
+        }
+        let central_offset = out.len() as u32;
+        out.extend_from_slice(&central);
+        out.extend_from_slice(&END_OF_CENTRAL_DIRECTORY_SIGNATURE.to_le_bytes());
+        out.extend_from_slice(&[0; 4]);
+        out.extend_from_slice(&(files.len() as u16).to_le_bytes());
+        out.extend_from_slice(&(files.len() as u16).to_le_bytes());
+        out.extend_from_slice(&(central.len() as u32).to_le_bytes());
+        out.extend_from_slice(&central_offset.to_le_bytes());
+        out.extend_from_slice(&0u16.to_le_bytes());
+        out
+    }
+
+    #[test]
+    fn reads_stored_and_deflated_entries() {
+        let text = b"{\"monsters\": []}".repeat(20);
+        let archive = build_zip(&[
+            ("dir/", b"", false),
+            ("dir/a.json", b"plain", false),
+            ("b.json", &text, true),
+        ]);
+
+        assert!(looks_like_zip(&archive));
+        let entries = read_zip(&archive, 1024).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].name, "dir/a.json");
+        assert_eq!(entries[0].data, b"plain");
+        assert_eq!(entries[1].data, text);
+    }
+
+    #[test]
+    fn rejects_archives_over_the_unpacked_limit() {
+        let archive = build_zip(&[("big.json", &[b'x'; 4096], true)]);
+
+        assert!(read_zip(&archive, 1024).is_err());
+        assert!(read_zip(b"not a zip archive at all....", 1024).is_err());
+        assert!(!looks_like_zip(b"{}"));
+    }
+
+    #[test]
+    fn rejects_offsets_that_overflow() {
+        assert!(at(usize::MAX - 9, 10).is_err());
+        assert_eq!(at(30, 16), Ok(46));
+
+        let mut archive = build_zip(&[("a.json", b"plain", false)]);
+        let eocd = archive.len() - END_OF_CENTRAL_DIRECTORY_LEN;
+        archive[eocd + 16..eocd + 20].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(read_zip(&archive, 1024).is_err());
+    }
+
+    #[test]
+    fn written_archives_read_back_with_checksums() {
+        let notes = "# Лор\n".repeat(50).into_bytes();
+        let entries = vec![
+            ZipEntry {
+                name: "hello.txt".to_string(),
+                data: b"hello".to_vec(),
+            },
+            ZipEntry {
+                name: "Кампания/лор.md".to_string(),
+                data: notes,
+            },
+        ];
+
+        let archive = write_zip(&entries).unwrap();
+        assert!(looks_like_zip(&archive));
+        assert_eq!(read_zip(&archive, 1024 * 1024).unwrap(), entries);
+        // Первая запись хранится без сжатия; CRC-32 от "hello" известен.
+        assert_eq!(read_u16(&archive, 8), Ok(METHOD_STORED));
+        assert_eq!(read_u32(&archive, 14), Ok(0x3610_A686));
+    }
+}
@@ -41,6 +41,78 @@ pub struct AttentionPingPayload {
     pub position: WorldPoint,
 }
 
+/// What a shared camera asks of the boards that receive it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemas", derive(ToSchema))]
+pub enum BoardCameraMode {
+    /// Live camera of a followed user; only their followers move along.
+    #[default]
+    Follow,
+    /// The game master pulls every player's board to this view once.
+    Pull,
+    /// Like `Pull`, and players cannot pan or zoom until `Release`.
+    Lock,
+    /// Ends a `Lock`; boards stay where they are.
+    Release,
+}
+
+/// Broadcast camera of a user's board: the world point in the middle of the
+/// viewport and the zoom. Viewport sizes differ, so no screen offsets are sent.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "validation", derive(Validate))]
+#[cfg_attr(feature = "schemas", derive(ToSchema))]
+pub struct BoardCameraPayload {
+    /// The username whose camera this is.
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 255)))]
+    pub username: String,
+
+    /// World position in the middle of the sender's viewport.
+    pub center: WorldPoint,
+
+    #[cfg_attr(feature = "validation", validate(range(min = 0.01, max = 100.0)))]
+    pub zoom: f64,
+
+    #[serde(default)]
+    pub mode: BoardCameraMode,
+}
+
+/// Sent when a user starts or stops following another user's camera.
+/// The followed board shares its camera only while someone follows it.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "validation", derive(Validate))]
+#[cfg_attr(feature = "schemas", derive(ToSchema))]
+pub struct BoardFollowPayload {
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 255)))]
+    pub follower: String,
+
+    /// The user being followed.
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 255)))]
+    pub target: String,
+
+    /// `true` = started following; `false` = stopped.
+    pub active: bool,
+}
+
+/// Area of the world a user's board shows, drawn on other users' minimaps.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "validation", derive(Validate))]
+#[cfg_attr(feature = "schemas", derive(ToSchema))]
+pub struct BoardViewportPayload {
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 255)))]
+    pub username: String,
+
+    /// World position in the middle of the viewport.
+    pub center: WorldPoint,
+
+    /// Visible width in world units.
+    #[cfg_attr(feature = "validation", validate(range(min = 0.0, max = 1_000_000.0)))]
+    pub width: f64,
+
+    /// Visible height in world units.
+    #[cfg_attr(feature = "validation", validate(range(min = 0.0, max = 1_000_000.0)))]
+    pub height: f64,
+}
+
 /// A direct message sent from one user to a specific recipient via "@nick message".
 /// The backend broadcasts this to the whole room; only the addressed recipient
 /// processes and stores it.  The content is always encrypted.
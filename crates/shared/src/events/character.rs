@@ -0,0 +1,278 @@
+use crate::events::room::acts_as_game_master;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+#[cfg(feature = "schemas")]
+use utoipa::ToSchema;
+#[cfg(feature = "validation")]
+use validator::{Validate, ValidationError};
+
+/// Наибольшая длина значения поля листа в символах.
+pub const MAX_SHEET_VALUE_CHARS: usize = 2000;
+#[cfg(feature = "validation")]
+const MAX_SHEET_VALUES: usize = 512;
+
+#[cfg(feature = "validation")]
+fn validate_sheet_values(values: &BTreeMap<String, String>) -> Result<(), ValidationError> {
+    let valid = values.len() <= MAX_SHEET_VALUES
+        && values.iter().all(|(key, value)| {
+            (1..=64).contains(&key.len()) && value.chars().count() <= MAX_SHEET_VALUE_CHARS
+        });
+    if valid {
+        Ok(())
+    } else {
+        Err(ValidationError::new("sheet_values"))
+    }
+}
+
+/// Тип поля в шаблоне листа персонажа.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemas", derive(ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum SheetFieldKind {
+    Text,
+    TextArea,
+    Number,
+    Checkbox,
+    /// Значение вычисляется по `formula` из других полей.
+    Computed,
+    /// Кнопка броска: `formula` — выражение с костями, например `1d20 + dex_mod`.
+    Roll,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(feature = "schemas", derive(ToSchema))]
+#[cfg_attr(feature = "validation", derive(Validate))]
+pub struct SheetField {
+    /// Ключ поля: латиница в нижнем регистре, цифры и `_`.
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 64)))]
+    pub key: String,
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 120)))]
+    pub label: String,
+    pub kind: SheetFieldKind,
+    #[serde(default)]
+    #[cfg_attr(feature = "validation", validate(length(max = 500)))]
+    pub formula: Option<String>,
+    #[serde(default)]
+    #[cfg_attr(feature = "validation", validate(length(max = 2000)))]
+    pub default: Option<String>,
+    /// Показывать знак у чисел (`+2`), удобно для модификаторов.
+    #[serde(default)]
+    pub signed: bool,
+    /// Приватное поле видно только владельцу листа и мастеру.
+    #[serde(default)]
+    pub private: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(feature = "schemas", derive(ToSchema))]
+#[cfg_attr(feature = "validation", derive(Validate))]
+pub struct SheetSection {
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 120)))]
+    pub title: String,
+    /// Количество колонок сетки полей в секции.
+    #[serde(default = "default_section_columns")]
+    #[cfg_attr(feature = "validation", validate(range(min = 1, max = 6)))]
+    pub columns: u8,
+    #[cfg_attr(feature = "validation", validate(length(max = 128), nested))]
+    pub fields: Vec<SheetField>,
+}
+
+const fn default_section_columns() -> u8 {
+    2
+}
+
+/// JSON-шаблон листа персонажа, независимый от игровой системы.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(feature = "schemas", derive(ToSchema))]
+#[cfg_attr(feature = "validation", derive(Validate))]
+pub struct SheetTemplate {
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 64)))]
+    pub id: String,
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 120)))]
+    pub name: String,
+    #[cfg_attr(feature = "validation", validate(length(max = 32), nested))]
+    pub sections: Vec<SheetSection>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(feature = "schemas", derive(ToSchema))]
+#[cfg_attr(feature = "validation", derive(Validate))]
+pub struct CharacterSheet {
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 255)))]
+    pub id: String,
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 120)))]
+    pub name: String,
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 64)))]
+    pub template_id: String,
+    /// Игрок, которому принадлежит лист; `None` — лист мастера.
+    #[serde(default)]
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 255)))]
+    pub owner: Option<String>,
+    /// Токен, к которому привязан лист.
+    #[serde(default)]
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 255)))]
+    pub token_id: Option<String>,
+    /// Значения полей по ключам шаблона, кроме приватных: те передаются
+    /// отдельно в `CharacterSheetSecretsPayload`.
+    #[serde(default)]
+    #[cfg_attr(
+        feature = "validation",
+        validate(custom(function = "validate_sheet_values"))
+    )]
+    pub values: BTreeMap<String, String>,
+    pub updated_at_ms: f64,
+}
+
+impl CharacterSheet {
+    /// Редактировать и удалять лист могут его владелец и мастер.
+    pub fn can_be_edited_by(&self, actor: &str, game_master: Option<&str>) -> bool {
+        self.owner.as_deref() == Some(actor) || acts_as_game_master(game_master, actor)
+    }
+
+    /// Видит ли участник приватные поля листа.
+    pub fn shows_private_fields_to(&self, viewer: &str, game_master: Option<&str>) -> bool {
+        self.can_be_edited_by(viewer, game_master)
+    }
+
+    /// Кому адресованы приватные поля: владельцу листа и мастеру. `None`, пока
+    /// мастер не назначен: тогда мастером считается каждый и приватные поля видят все.
+    pub fn private_audience(&self, game_master: Option<&str>) -> Option<Vec<String>> {
+        let mut audience = vec![game_master?.to_string()];
+        audience.extend(self.owner.clone());
+        audience.sort();
+        audience.dedup();
+        Some(audience)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemas", derive(ToSchema))]
+#[cfg_attr(feature = "validation", derive(Validate))]
+pub struct CharacterSheetUpsertPayload {
+    #[cfg_attr(feature = "validation", validate(nested))]
+    pub sheet: CharacterSheet,
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 255)))]
+    pub actor: String,
+}
+
+/// Значения приватных полей листа. В состояние комнаты они не входят: их
+/// шифруют ключом для `audience`, и каждый получатель хранит их у себя.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemas", derive(ToSchema))]
+#[cfg_attr(feature = "validation", derive(Validate))]
+pub struct CharacterSheetSecretsPayload {
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 255)))]
+    pub sheet_id: String,
+    /// Владелец листа и мастер (см. `CharacterSheet::private_audience`).
+    #[serde(default)]
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 2)))]
+    pub audience: Option<Vec<String>>,
+    #[serde(default)]
+    #[cfg_attr(
+        feature = "validation",
+        validate(custom(function = "validate_sheet_values"))
+    )]
+    pub values: BTreeMap<String, String>,
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 255)))]
+    pub actor: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemas", derive(ToSchema))]
+#[cfg_attr(feature = "validation", derive(Validate))]
+pub struct CharacterSheetDeletePayload {
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 255)))]
+    pub sheet_id: String,
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 255)))]
+    pub actor: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemas", derive(ToSchema))]
+#[cfg_attr(feature = "validation", derive(Validate))]
+pub struct SheetTemplateUpsertPayload {
+    #[cfg_attr(feature = "validation", validate(nested))]
+    pub template: SheetTemplate,
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 255)))]
+    pub actor: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sheet_field_flags_default_to_false() {
+        let raw = r#"{"key":"str","label":"Strength","kind":"number"}"#;
+
+        let field: SheetField = serde_json::from_str(raw).unwrap();
+        assert_eq!(field.kind, SheetFieldKind::Number);
+        assert!(!field.private);
+        assert!(!field.signed);
+        assert_eq!(field.formula, None);
+    }
+
+    #[test]
+    fn sheet_section_columns_default_to_two() {
+        let raw = r#"{"title":"Abilities","fields":[]}"#;
+
+        let section: SheetSection = serde_json::from_str(raw).unwrap();
+        assert_eq!(section.columns, 2);
+    }
+
+    #[test]
+    fn sheet_is_editable_by_owner_and_game_master_only() {
+        let sheet = CharacterSheet {
+            id: "sheet-1".to_string(),
+            name: "Aria".to_string(),
+            template_id: "dnd5e".to_string(),
+            owner: Some("alice".to_string()),
+            token_id: None,
+            values: BTreeMap::new(),
+            updated_at_ms: 0.0,
+        };
+
+        assert!(sheet.can_be_edited_by("alice", Some("gm")));
+        assert!(sheet.can_be_edited_by("gm", Some("gm")));
+        assert!(!sheet.can_be_edited_by("bob", Some("gm")));
+        assert!(sheet.can_be_edited_by("bob", None));
+        assert!(!sheet.shows_private_fields_to("bob", Some("gm")));
+    }
+
+    #[test]
+    fn private_audience_is_owner_and_game_master() {
+        let mut sheet = CharacterSheet {
+            id: "sheet-1".to_string(),
+            name: "Aria".to_string(),
+            template_id: "dnd5e".to_string(),
+            owner: Some("alice".to_string()),
+            token_id: None,
+            values: BTreeMap::new(),
+            updated_at_ms: 0.0,
+        };
+
+        let audience = sheet.private_audience(Some("gm"));
+        assert_eq!(audience, Some(vec!["alice".to_string(), "gm".to_string()]));
+        assert_eq!(sheet.private_audience(None), None);
+
+        sheet.owner = None;
+        assert_eq!(
+            sheet.private_audience(Some("gm")),
+            Some(vec!["gm".to_string()])
+        );
+    }
+
+    #[cfg(feature = "validation")]
+    #[test]
+    fn sheet_values_are_bounded() {
+        let mut values = BTreeMap::from([("hp".to_string(), "12".to_string())]);
+        assert!(validate_sheet_values(&values).is_ok());
+
+        values.insert("notes".to_string(), "x".repeat(MAX_SHEET_VALUE_CHARS + 1));
+        assert!(validate_sheet_values(&values).is_err());
+
+        values.clear();
+        values.insert(String::new(), "12".to_string());
+        assert!(validate_sheet_values(&values).is_err());
+    }
+}
@@ -0,0 +1,59 @@
+use crate::events::scene::FileRef;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "schemas")]
+use utoipa::ToSchema;
+#[cfg(feature = "validation")]
+use validator::Validate;
+
+/// Импортированный в комнату компендиум (монстры и заклинания).
+///
+/// Само содержимое не хранится в состоянии комнаты: участники скачивают файл
+/// по `file` через обмен файлами и разбирают его локально.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(feature = "schemas", derive(ToSchema))]
+#[cfg_attr(feature = "validation", derive(Validate))]
+pub struct CompendiumSource {
+    #[cfg_attr(feature = "validation", validate(nested))]
+    pub file: FileRef,
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 120)))]
+    pub title: String,
+    #[serde(default)]
+    pub monster_count: u32,
+    #[serde(default)]
+    pub spell_count: u32,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemas", derive(ToSchema))]
+#[cfg_attr(feature = "validation", derive(Validate))]
+pub struct CompendiumImportPayload {
+    #[cfg_attr(feature = "validation", validate(nested))]
+    pub source: CompendiumSource,
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 255)))]
+    pub actor: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemas", derive(ToSchema))]
+#[cfg_attr(feature = "validation", derive(Validate))]
+pub struct CompendiumRemovePayload {
+    /// Хеш файла удаляемого компендиума.
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 255)))]
+    pub hash: String,
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 255)))]
+    pub actor: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compendium_source_counts_default_to_zero() {
+        let raw = r#"{"file":{"hash":"abc","mime_type":"application/json","file_name":"srd.json","size":10},"title":"SRD"}"#;
+
+        let source: CompendiumSource = serde_json::from_str(raw).unwrap();
+        assert_eq!(source.monster_count, 0);
+        assert_eq!(source.spell_count, 0);
+    }
+}
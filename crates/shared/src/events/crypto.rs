@@ -45,6 +45,10 @@ pub enum EncryptedPayloadKind {
     FileControl,
     #[serde(rename = "FILE_CHUNK")]
     FileChunk,
+    #[serde(rename = "CHARACTER")]
+    Character,
+    #[serde(rename = "HANDOUT")]
+    Handout,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
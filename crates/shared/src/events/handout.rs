@@ -0,0 +1,140 @@
+use crate::events::room::acts_as_game_master;
+use crate::events::scene::FileRef;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "schemas")]
+use utoipa::ToSchema;
+#[cfg(feature = "validation")]
+use validator::Validate;
+
+/// Открытая часть раздаточного материала в долях от размера изображения.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(feature = "schemas", derive(ToSchema))]
+#[cfg_attr(feature = "validation", derive(Validate))]
+pub struct HandoutCrop {
+    #[cfg_attr(feature = "validation", validate(range(min = 0.0, max = 1.0)))]
+    pub x: f32,
+    #[cfg_attr(feature = "validation", validate(range(min = 0.0, max = 1.0)))]
+    pub y: f32,
+    #[cfg_attr(feature = "validation", validate(range(min = 0.0, max = 1.0)))]
+    pub width: f32,
+    #[cfg_attr(feature = "validation", validate(range(min = 0.0, max = 1.0)))]
+    pub height: f32,
+}
+
+/// Раздаточный материал: изображение или PDF, показанный игрокам.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(feature = "schemas", derive(ToSchema))]
+#[cfg_attr(feature = "validation", derive(Validate))]
+pub struct Handout {
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 255)))]
+    pub id: String,
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 120)))]
+    pub title: String,
+    /// Показываемый файл; при частичном открытии — отрисованная открытая часть,
+    /// исходное изображение остаётся у мастера.
+    #[cfg_attr(feature = "validation", validate(nested))]
+    pub file: FileRef,
+    /// Кому показан материал; пустой список — всем игрокам.
+    #[serde(default)]
+    #[cfg_attr(feature = "validation", validate(length(max = 64)))]
+    pub recipients: Vec<String>,
+    /// Открытая часть изображения; `None` — материал открыт целиком.
+    #[serde(default)]
+    #[cfg_attr(feature = "validation", validate(nested))]
+    pub reveal: Option<HandoutCrop>,
+    pub shown_at_ms: f64,
+}
+
+impl Handout {
+    /// Мастер видит все материалы, игроки — адресованные им.
+    pub fn is_visible_to(&self, user: &str, game_master: Option<&str>) -> bool {
+        self.recipients.is_empty()
+            || self.recipients.iter().any(|recipient| recipient == user)
+            || acts_as_game_master(game_master, user)
+    }
+
+    /// Получатели и показавший мастер по алфавиту; `None` — материал для всех.
+    pub fn audience(&self, actor: &str) -> Option<Vec<String>> {
+        handout_audience(&self.recipients, actor)
+    }
+}
+
+/// Кто может прочитать событие о материале с такими получателями.
+pub fn handout_audience(recipients: &[String], actor: &str) -> Option<Vec<String>> {
+    if recipients.is_empty() {
+        return None;
+    }
+    let mut audience = recipients.to_vec();
+    audience.push(actor.to_string());
+    audience.sort();
+    audience.dedup();
+    Some(audience)
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemas", derive(ToSchema))]
+#[cfg_attr(feature = "validation", derive(Validate))]
+pub struct HandoutShowPayload {
+    #[cfg_attr(feature = "validation", validate(nested))]
+    pub handout: Handout,
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 255)))]
+    pub actor: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemas", derive(ToSchema))]
+#[cfg_attr(feature = "validation", derive(Validate))]
+pub struct HandoutDeletePayload {
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 255)))]
+    pub id: String,
+    /// Получатели удаляемого материала: событие шифруется для них же.
+    #[serde(default)]
+    #[cfg_attr(feature = "validation", validate(length(max = 64)))]
+    pub recipients: Vec<String>,
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 255)))]
+    pub actor: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn handout(recipients: &[&str]) -> Handout {
+        Handout {
+            id: "letter".to_string(),
+            title: "Letter".to_string(),
+            file: FileRef {
+                hash: "abc".to_string(),
+                mime_type: "image/png".to_string(),
+                file_name: "letter.png".to_string(),
+                size: 10,
+            },
+            recipients: recipients.iter().map(|user| user.to_string()).collect(),
+            reveal: None,
+            shown_at_ms: 0.0,
+        }
+    }
+
+    #[test]
+    fn handout_visibility_follows_recipients() {
+        assert!(handout(&[]).is_visible_to("alice", Some("gm")));
+
+        let private = handout(&["alice"]);
+        assert!(private.is_visible_to("alice", Some("gm")));
+        assert!(!private.is_visible_to("bob", Some("gm")));
+        assert!(private.is_visible_to("gm", Some("gm")));
+    }
+
+    #[test]
+    fn handout_audience_is_recipients_and_actor() {
+        assert_eq!(handout(&[]).audience("gm"), None);
+        assert_eq!(
+            handout(&["bob", "alice"]).audience("gm"),
+            Some(vec![
+                "alice".to_string(),
+                "bob".to_string(),
+                "gm".to_string()
+            ])
+        );
+    }
+}
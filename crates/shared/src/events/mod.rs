@@ -1,7 +1,10 @@
 pub mod board;
+pub mod character;
 pub mod chat;
+pub mod compendium;
 pub mod crypto;
 pub mod file;
+pub mod handout;
 pub mod mouse;
 pub mod note;
 pub mod params;
@@ -11,32 +14,50 @@ pub mod sync;
 pub mod voting;
 
 pub use crate::events::board::{
-    AttentionPingPayload, BoardPointerPayload, DirectMessagePayload, WorldPoint,
+    AttentionPingPayload, BoardCameraMode, BoardCameraPayload, BoardFollowPayload,
+    BoardPointerPayload, BoardViewportPayload, DirectMessagePayload, WorldPoint,
+};
+pub use crate::events::character::{
+    CharacterSheet, CharacterSheetDeletePayload, CharacterSheetSecretsPayload,
+    CharacterSheetUpsertPayload, MAX_SHEET_VALUE_CHARS, SheetField, SheetFieldKind, SheetSection,
+    SheetTemplate, SheetTemplateUpsertPayload,
 };
 pub use crate::events::chat::ChatMessagePayload;
+pub use crate::events::compendium::{
+    CompendiumImportPayload, CompendiumRemovePayload, CompendiumSource,
+};
 pub use crate::events::crypto::{
     CryptoKeyAnnouncePayload, CryptoKeyWrapPayload, CryptoPayload, EncryptedPayloadKind,
 };
 pub use crate::events::file::{
     FileAbortPayload, FileAnnouncePayload, FileChunkPayload, FileRequestPayload,
 };
+pub use crate::events::handout::{
+    Handout, HandoutCrop, HandoutDeletePayload, HandoutShowPayload, handout_audience,
+};
 pub use crate::events::mouse::MouseClickPayload;
 pub use crate::events::note::{
-    NoteBoardPosition, NoteBoardStyle, NoteDeletePayload, NotePayload, NoteVisibility,
+    MAX_NOTE_GROUP_MEMBERS, NoteBoardPosition, NoteBoardStyle, NoteCaretPayload, NoteCharId,
+    NoteDeletePayload, NoteEditPayload, NoteGroup, NotePayload, NoteRevision, NoteSceneAnchor,
+    NoteTextOp, NoteVisibility,
 };
 pub use crate::events::params::Params;
-pub use crate::events::room::RoomState;
+pub use crate::events::room::{
+    GameMasterSetPayload, RoomState, acts_as_game_master, assigned_scene_id,
+};
 pub use crate::events::scene::{
-    FileRef, Scene, SceneActivatePayload, SceneCreatePayload, SceneDeletePayload, SceneGrid,
-    SceneUpdatePayload, Token, TokenMovePayload,
+    AuraVisibility, FileRef, MAX_SCENE_TERRAIN_CELLS, Scene, SceneActivatePayload,
+    SceneAssignPayload, SceneCreatePayload, SceneDeletePayload, SceneDoor, SceneGrid, SceneLevel,
+    SceneLight, ScenePoint, SceneStairs, SceneUpdatePayload, SceneWall, TerrainCell, TerrainKind,
+    Token, TokenAura, TokenMovePayload, is_hex_color, terrain_fits_message,
 };
 pub use crate::events::sync::{
     SyncSnapshotPackedStatePayload, SyncSnapshotPayload, SyncSnapshotRequestPayload,
     SyncVersionPayload,
 };
 pub use crate::events::voting::{
-    PresenceAnnouncePayload, PresenceRequestPayload, PresenceResponsePayload, VotingCastPayload,
-    VotingEndPayload, VotingResultPayload, VotingStartPayload,
+    PresenceAnnouncePayload, PresenceLeavePayload, PresenceRequestPayload, PresenceResponsePayload,
+    VotingCastPayload, VotingEndPayload, VotingResultPayload, VotingStartPayload,
 };
 use serde::{Deserialize, Serialize};
 #[cfg(feature = "schemas")]
@@ -44,6 +65,9 @@ use utoipa::ToSchema;
 #[cfg(feature = "validation")]
 use validator::Validate;
 
+/// Наибольший размер одного сообщения WebSocket, которое принимает сервер.
+pub const MAX_WIRE_MESSAGE_BYTES: usize = 1024 * 1024;
+
 #[derive(Debug, Deserialize, Serialize)]
 #[cfg_attr(feature = "schemas", derive(ToSchema))]
 #[serde(tag = "type", content = "data")]
@@ -58,6 +82,10 @@ pub enum ClientEvent {
     NoteUpsert(NotePayload),
     #[serde(rename = "NOTE_DELETE")]
     NoteDelete(NoteDeletePayload),
+    #[serde(rename = "NOTE_EDIT")]
+    NoteEdit(NoteEditPayload),
+    #[serde(rename = "NOTE_CARET")]
+    NoteCaret(NoteCaretPayload),
     #[serde(rename = "FILE_ANNOUNCE")]
     FileAnnounce(FileAnnouncePayload),
     #[serde(rename = "FILE_REQUEST")]
@@ -74,8 +102,28 @@ pub enum ClientEvent {
     SceneDelete(SceneDeletePayload),
     #[serde(rename = "SCENE_ACTIVATE")]
     SceneActivate(SceneActivatePayload),
+    #[serde(rename = "SCENE_ASSIGN")]
+    SceneAssign(SceneAssignPayload),
     #[serde(rename = "TOKEN_MOVE")]
     TokenMove(TokenMovePayload),
+    #[serde(rename = "GAME_MASTER_SET")]
+    GameMasterSet(GameMasterSetPayload),
+    #[serde(rename = "CHARACTER_SHEET_UPSERT")]
+    CharacterSheetUpsert(CharacterSheetUpsertPayload),
+    #[serde(rename = "CHARACTER_SHEET_DELETE")]
+    CharacterSheetDelete(CharacterSheetDeletePayload),
+    #[serde(rename = "CHARACTER_SHEET_SECRETS")]
+    CharacterSheetSecrets(CharacterSheetSecretsPayload),
+    #[serde(rename = "SHEET_TEMPLATE_UPSERT")]
+    SheetTemplateUpsert(SheetTemplateUpsertPayload),
+    #[serde(rename = "COMPENDIUM_IMPORT")]
+    CompendiumImport(CompendiumImportPayload),
+    #[serde(rename = "COMPENDIUM_REMOVE")]
+    CompendiumRemove(CompendiumRemovePayload),
+    #[serde(rename = "HANDOUT_SHOW")]
+    HandoutShow(HandoutShowPayload),
+    #[serde(rename = "HANDOUT_DELETE")]
+    HandoutDelete(HandoutDeletePayload),
 
     /// Sync events
     #[serde(rename = "SYNC_REQUEST")]
@@ -104,6 +152,8 @@ pub enum ClientEvent {
     PresenceResponse(PresenceResponsePayload),
     #[serde(rename = "PRESENCE_ANNOUNCE")]
     PresenceAnnounce(PresenceAnnouncePayload),
+    #[serde(rename = "PRESENCE_LEAVE")]
+    PresenceLeave(PresenceLeavePayload),
 
     #[serde(rename = "CRYPTO_KEY_ANNOUNCE")]
     CryptoKeyAnnounce(CryptoKeyAnnouncePayload),
@@ -117,6 +167,12 @@ pub enum ClientEvent {
     BoardPointer(BoardPointerPayload),
     #[serde(rename = "ATTENTION_PING")]
     AttentionPing(AttentionPingPayload),
+    #[serde(rename = "BOARD_CAMERA")]
+    BoardCamera(BoardCameraPayload),
+    #[serde(rename = "BOARD_FOLLOW")]
+    BoardFollow(BoardFollowPayload),
+    #[serde(rename = "BOARD_VIEWPORT")]
+    BoardViewport(BoardViewportPayload),
     #[serde(rename = "DIRECT_MESSAGE")]
     DirectMessage(DirectMessagePayload),
 
@@ -133,6 +189,8 @@ impl ClientEvent {
             ClientEvent::ChatMessage(p) => p.validate(),
             ClientEvent::NoteUpsert(p) => p.validate(),
             ClientEvent::NoteDelete(p) => p.validate(),
+            ClientEvent::NoteEdit(p) => p.validate(),
+            ClientEvent::NoteCaret(p) => p.validate(),
             ClientEvent::FileAnnounce(p) => p.validate(),
             ClientEvent::FileRequest(p) => p.validate(),
             ClientEvent::FileChunk(p) => p.validate(),
@@ -141,7 +199,17 @@ impl ClientEvent {
             ClientEvent::SceneUpdate(p) => p.validate(),
             ClientEvent::SceneDelete(p) => p.validate(),
             ClientEvent::SceneActivate(p) => p.validate(),
+            ClientEvent::SceneAssign(p) => p.validate(),
             ClientEvent::TokenMove(p) => p.validate(),
+            ClientEvent::GameMasterSet(p) => p.validate(),
+            ClientEvent::CharacterSheetUpsert(p) => p.validate(),
+            ClientEvent::CharacterSheetDelete(p) => p.validate(),
+            ClientEvent::CharacterSheetSecrets(p) => p.validate(),
+            ClientEvent::SheetTemplateUpsert(p) => p.validate(),
+            ClientEvent::CompendiumImport(p) => p.validate(),
+            ClientEvent::CompendiumRemove(p) => p.validate(),
+            ClientEvent::HandoutShow(p) => p.validate(),
+            ClientEvent::HandoutDelete(p) => p.validate(),
             ClientEvent::SyncVersionAnnounce(p) => p.validate(),
             ClientEvent::SyncSnapshotRequest(p) => p.validate(),
             ClientEvent::SyncSnapshot(p) => p.validate(),
@@ -152,6 +220,7 @@ impl ClientEvent {
             ClientEvent::PresenceRequest(p) => p.validate(),
             ClientEvent::PresenceResponse(p) => p.validate(),
             ClientEvent::PresenceAnnounce(p) => p.validate(),
+            ClientEvent::PresenceLeave(p) => p.validate(),
             ClientEvent::CryptoKeyAnnounce(p) => p.validate(),
             ClientEvent::CryptoKeyWrap(p) => p.validate(),
             ClientEvent::CryptoPayload(p) => p.validate(),
@@ -159,6 +228,9 @@ impl ClientEvent {
             ClientEvent::Ping => Ok(()),
             ClientEvent::BoardPointer(p) => p.validate(),
             ClientEvent::AttentionPing(p) => p.validate(),
+            ClientEvent::BoardCamera(p) => p.validate(),
+            ClientEvent::BoardFollow(p) => p.validate(),
+            ClientEvent::BoardViewport(p) => p.validate(),
             ClientEvent::DirectMessage(p) => p.validate(),
         }
     }
@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 #[cfg(feature = "schemas")]
 use utoipa::ToSchema;
 #[cfg(feature = "validation")]
-use validator::Validate;
+use validator::{Validate, ValidationError};
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
 #[cfg_attr(feature = "schemas", derive(ToSchema))]
@@ -10,16 +10,132 @@ pub enum NoteVisibility {
     Public,
     Private,
     Direct(String),
+    /// Заметка для нескольких участников или именованной группы.
+    Group(NoteGroup),
+}
+
+/// Получатели групповой заметки. Автор видит заметку, даже если его нет в `members`.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schemas", derive(ToSchema))]
+pub struct NoteGroup {
+    /// Название группы (например, `Отряд А`); пустая строка — безымянная группа.
+    #[serde(default)]
+    pub name: String,
+    /// Имена участников по алфавиту, без повторов.
+    pub members: Vec<String>,
+}
+
+const MAX_NOTE_GROUP_NAME_CHARS: usize = 60;
+/// Наибольшее число участников группы.
+pub const MAX_NOTE_GROUP_MEMBERS: usize = 32;
+
+impl NoteGroup {
+    /// Группа с отсортированным списком участников без повторов и пустых имён.
+    pub fn new(name: &str, members: impl IntoIterator<Item = String>) -> Self {
+        let mut members = members
+            .into_iter()
+            .map(|member| member.trim().to_string())
+            .filter(|member| !member.is_empty())
+            .collect::<Vec<_>>();
+        members.sort();
+        members.dedup();
+        Self {
+            name: name
+                .trim()
+                .chars()
+                .take(MAX_NOTE_GROUP_NAME_CHARS)
+                .collect(),
+            members,
+        }
+    }
+
+    /// Название группы или перечень участников через ` + `.
+    pub fn label(&self) -> String {
+        if self.name.is_empty() {
+            self.members.join(" + ")
+        } else {
+            self.name.clone()
+        }
+    }
+}
+
+impl NoteVisibility {
+    /// Может ли `user` видеть заметку автора `author`.
+    pub fn is_visible_to(&self, author: &str, user: &str) -> bool {
+        match self {
+            NoteVisibility::Public => true,
+            NoteVisibility::Private => author == user,
+            NoteVisibility::Direct(recipient) => author == user || recipient == user,
+            NoteVisibility::Group(group) => {
+                author == user || group.members.iter().any(|member| member == user)
+            }
+        }
+    }
+
+    /// Все, кому адресована заметка, включая автора, по алфавиту; `None` —
+    /// публичные и личные заметки, у которых нет списка получателей.
+    pub fn audience(&self, author: &str) -> Option<Vec<String>> {
+        let mut audience = match self {
+            NoteVisibility::Public | NoteVisibility::Private => return None,
+            NoteVisibility::Direct(recipient) => vec![recipient.clone()],
+            NoteVisibility::Group(group) => group.members.clone(),
+        };
+        audience.push(author.to_string());
+        audience.sort();
+        audience.dedup();
+        Some(audience)
+    }
+}
+
+#[cfg(feature = "validation")]
+fn validate_note_visibility(visibility: &NoteVisibility) -> Result<(), ValidationError> {
+    let valid = match visibility {
+        NoteVisibility::Public | NoteVisibility::Private => true,
+        NoteVisibility::Direct(recipient) => (1..=255).contains(&recipient.len()),
+        NoteVisibility::Group(group) => {
+            group.name.chars().count() <= MAX_NOTE_GROUP_NAME_CHARS
+                && (1..=MAX_NOTE_GROUP_MEMBERS).contains(&group.members.len())
+                && group
+                    .members
+                    .iter()
+                    .all(|member| (1..=255).contains(&member.len()))
+        }
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(ValidationError::new("note_visibility"))
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(feature = "validation", derive(Validate))]
 #[cfg_attr(feature = "schemas", derive(ToSchema))]
 pub struct NoteBoardPosition {
     pub world_x: f64,
     pub world_y: f64,
+    /// Сцена, к которой привязана заметка. Если сцена существует, положение
+    /// считается от её центра, а `world_x`/`world_y` — последняя известная точка.
+    #[serde(default)]
+    #[cfg_attr(feature = "validation", validate(nested))]
+    pub anchor: Option<NoteSceneAnchor>,
+}
+
+/// Привязка заметки к сцене: заметка двигается вместе со сценой и скрывается
+/// вместе с её содержимым.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(feature = "validation", derive(Validate))]
+#[cfg_attr(feature = "schemas", derive(ToSchema))]
+pub struct NoteSceneAnchor {
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 255)))]
+    pub scene_id: String,
+    /// Смещение левого верхнего угла заметки от центра сцены.
+    pub offset_x: f64,
+    pub offset_y: f64,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(feature = "validation", derive(Validate))]
 #[cfg_attr(feature = "schemas", derive(ToSchema))]
 pub struct NoteBoardStyle {
     #[serde(default = "default_note_board_width_px")]
@@ -30,6 +146,11 @@ pub struct NoteBoardStyle {
     pub font_size_pt: f64,
     #[serde(default = "default_note_board_color")]
     pub color: String,
+    /// Значок метки на карте (например, `📍`): такая заметка показывается
+    /// значком и раскрывается, когда её выбирают.
+    #[serde(default)]
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 16)))]
+    pub pin_icon: Option<String>,
 }
 
 const fn default_note_board_width_px() -> f64 {
@@ -55,6 +176,7 @@ impl Default for NoteBoardStyle {
             height_px: default_note_board_height_px(),
             font_size_pt: default_note_board_font_size_pt(),
             color: default_note_board_color(),
+            pin_icon: None,
         }
     }
 }
@@ -67,6 +189,10 @@ pub struct NotePayload {
     pub id: String,
     #[cfg_attr(feature = "validation", validate(length(min = 1, max = 255)))]
     pub author: String,
+    #[cfg_attr(
+        feature = "validation",
+        validate(custom(function = "validate_note_visibility"))
+    )]
     pub visibility: NoteVisibility,
     #[serde(default)]
     #[cfg_attr(feature = "validation", validate(length(max = 120)))]
@@ -76,9 +202,105 @@ pub struct NotePayload {
     pub created_at_ms: f64,
     pub updated_at_ms: f64,
     #[serde(default)]
+    #[cfg_attr(feature = "validation", validate(nested))]
     pub board_position: Option<NoteBoardPosition>,
     #[serde(default)]
+    #[cfg_attr(feature = "validation", validate(nested))]
     pub board_style: NoteBoardStyle,
+    /// Путь папки через `/` (например, `Кампания/НИП`); пустая строка — корень.
+    #[serde(default)]
+    #[cfg_attr(feature = "validation", validate(length(max = 255)))]
+    pub folder: String,
+    #[serde(default)]
+    #[cfg_attr(feature = "validation", validate(length(max = 16)))]
+    pub tags: Vec<String>,
+    /// Закреплённые заметки показываются первыми.
+    #[serde(default)]
+    pub pinned: bool,
+    /// Кто сохранил эту версию; пустая строка — сам автор.
+    #[serde(default)]
+    #[cfg_attr(feature = "validation", validate(length(max = 255)))]
+    pub edited_by: String,
+    /// Предыдущие версии заметки, от старых к новым; текущий текст сюда не входит.
+    #[serde(default)]
+    #[cfg_attr(feature = "validation", validate(length(max = 10), nested))]
+    pub revisions: Vec<NoteRevision>,
+}
+
+/// Сохранённая версия заметки: кто и когда её записал и что в ней было.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(feature = "validation", derive(Validate))]
+#[cfg_attr(feature = "schemas", derive(ToSchema))]
+pub struct NoteRevision {
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 255)))]
+    pub author: String,
+    pub saved_at_ms: f64,
+    #[serde(default)]
+    #[cfg_attr(feature = "validation", validate(length(max = 120)))]
+    pub title: String,
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 20_000)))]
+    pub body: String,
+}
+
+/// Идентификатор символа в совместно редактируемом тексте заметки:
+/// логические часы Лэмпорта и имя участника, вставившего символ.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "validation", derive(Validate))]
+#[cfg_attr(feature = "schemas", derive(ToSchema))]
+pub struct NoteCharId {
+    #[cfg_attr(feature = "validation", validate(length(max = 50)))]
+    pub site: String,
+    pub clock: u64,
+}
+
+/// Операция над текстом заметки (RGA).
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(feature = "schemas", derive(ToSchema))]
+pub enum NoteTextOp {
+    /// Вставка `text` после символа `after` (`None` — в начало); символы
+    /// получают идентификаторы `id`, `id.clock + 1`, … того же участника.
+    Insert {
+        id: NoteCharId,
+        after: Option<NoteCharId>,
+        text: String,
+    },
+    /// Удаление `len` символов с идентификаторами `id.clock .. id.clock + len`.
+    Delete { id: NoteCharId, len: u32 },
+}
+
+/// Пакет операций совместного редактирования публичной заметки.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(feature = "validation", derive(Validate))]
+#[cfg_attr(feature = "schemas", derive(ToSchema))]
+pub struct NoteEditPayload {
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 255)))]
+    pub note_id: String,
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 50)))]
+    pub sender: String,
+    /// `updated_at_ms` версии заметки, с которой началась сессия правки.
+    pub base_updated_at_ms: f64,
+    /// `true` — полный снимок документа для нового участника сессии.
+    #[serde(default)]
+    pub full: bool,
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 4096)))]
+    pub ops: Vec<NoteTextOp>,
+}
+
+/// Положение курсора участника в совместно редактируемой заметке.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(feature = "validation", derive(Validate))]
+#[cfg_attr(feature = "schemas", derive(ToSchema))]
+pub struct NoteCaretPayload {
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 255)))]
+    pub note_id: String,
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 50)))]
+    pub username: String,
+    pub base_updated_at_ms: f64,
+    /// Курсор стоит после этого символа; `None` — в начале текста.
+    #[serde(default)]
+    pub after: Option<NoteCharId>,
+    /// `false` — участник закрыл редактор.
+    pub editing: bool,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
@@ -89,6 +311,10 @@ pub struct NoteDeletePayload {
     pub id: String,
     #[cfg_attr(feature = "validation", validate(length(min = 1, max = 255)))]
     pub author: String,
+    #[cfg_attr(
+        feature = "validation",
+        validate(custom(function = "validate_note_visibility"))
+    )]
     pub visibility: NoteVisibility,
 }
 
@@ -111,5 +337,110 @@ mod tests {
         let note: NotePayload = serde_json::from_str(raw).unwrap();
         assert_eq!(note.board_position, None);
         assert_eq!(note.board_style, NoteBoardStyle::default());
+        assert_eq!(note.folder, "");
+        assert!(note.tags.is_empty());
+        assert!(!note.pinned);
+        assert!(note.revisions.is_empty());
+        assert_eq!(note.edited_by, "");
+    }
+
+    #[test]
+    fn note_text_ops_round_trip() {
+        let ops = vec![
+            NoteTextOp::Insert {
+                id: NoteCharId {
+                    site: "gm".into(),
+                    clock: 4,
+                },
+                after: None,
+                text: "Hi".into(),
+            },
+            NoteTextOp::Delete {
+                id: NoteCharId {
+                    site: String::new(),
+                    clock: 1,
+                },
+                len: 2,
+            },
+        ];
+        let json = serde_json::to_string(&ops).unwrap();
+        assert_eq!(serde_json::from_str::<Vec<NoteTextOp>>(&json).unwrap(), ops);
+    }
+
+    #[test]
+    fn group_visibility_covers_author_and_members() {
+        let group = NoteGroup::new(" Party A ", ["bob".into(), "alice".into(), "bob".into()]);
+        assert_eq!(group.members, vec!["alice".to_string(), "bob".to_string()]);
+        assert_eq!(group.label(), "Party A");
+        assert_eq!(
+            NoteGroup::new("", group.members.clone()).label(),
+            "alice + bob"
+        );
+
+        let visibility = NoteVisibility::Group(group);
+        assert!(visibility.is_visible_to("gm", "gm"));
+        assert!(visibility.is_visible_to("gm", "bob"));
+        assert!(!visibility.is_visible_to("gm", "carol"));
+        assert_eq!(
+            visibility.audience("gm"),
+            Some(vec!["alice".into(), "bob".into(), "gm".into()])
+        );
+        assert_eq!(NoteVisibility::Public.audience("gm"), None);
+
+        let json = serde_json::to_string(&visibility).unwrap();
+        assert_eq!(
+            serde_json::from_str::<NoteVisibility>(&json).unwrap(),
+            visibility
+        );
+    }
+
+    #[cfg(feature = "validation")]
+    #[test]
+    fn group_notes_need_members() {
+        let delete = NoteDeletePayload {
+            id: "note-1".into(),
+            author: "gm".into(),
+            visibility: NoteVisibility::Group(NoteGroup::new("Party A", Vec::new())),
+        };
+        assert!(delete.validate().is_err());
+
+        let delete = NoteDeletePayload {
+            visibility: NoteVisibility::Group(NoteGroup::new("", ["alice".to_string()])),
+            ..delete
+        };
+        assert!(delete.validate().is_ok());
+    }
+
+    #[test]
+    fn board_position_anchor_is_optional() {
+        let position: NoteBoardPosition =
+            serde_json::from_str(r#"{"world_x":10.0,"world_y":20.0}"#).unwrap();
+        assert_eq!(position.anchor, None);
+
+        let anchored = NoteBoardPosition {
+            anchor: Some(NoteSceneAnchor {
+                scene_id: "scene-1".into(),
+                offset_x: -40.0,
+                offset_y: 12.5,
+            }),
+            ..position
+        };
+        let json = serde_json::to_string(&anchored).unwrap();
+        assert_eq!(
+            serde_json::from_str::<NoteBoardPosition>(&json).unwrap(),
+            anchored
+        );
+    }
+
+    #[cfg(feature = "validation")]
+    #[test]
+    fn board_pins_need_a_short_icon() {
+        let mut style = NoteBoardStyle {
+            pin_icon: Some("📍".into()),
+            ..NoteBoardStyle::default()
+        };
+        assert!(style.validate().is_ok());
+        style.pin_icon = Some(String::new());
+        assert!(style.validate().is_err());
     }
 }
@@ -1,10 +1,13 @@
+use crate::events::character::{CharacterSheet, SheetTemplate};
 use crate::events::chat::ChatMessagePayload;
+use crate::events::compendium::CompendiumSource;
+use crate::events::handout::Handout;
 use crate::events::note::NotePayload;
 use crate::events::scene::Scene;
 use crate::events::voting::VotingResultPayload;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 #[cfg(feature = "schemas")]
 use utoipa::ToSchema;
 #[cfg(feature = "validation")]
@@ -12,6 +15,36 @@ use validator::Validate;
 
 const MAX_HISTORY_LOG_ENTRIES: usize = 500;
 
+/// Действует ли участник как мастер: пока мастер не назначен, права мастера
+/// есть у всех, как и до появления этой роли.
+pub fn acts_as_game_master(game_master: Option<&str>, username: &str) -> bool {
+    game_master.is_none_or(|game_master| game_master == username)
+}
+
+/// Активная сцена участника: назначенная ему лично, иначе общая для комнаты.
+pub fn assigned_scene_id<'a>(
+    active_scene_id: Option<&'a str>,
+    scene_assignments: &'a BTreeMap<String, String>,
+    username: &str,
+) -> Option<&'a str> {
+    scene_assignments
+        .get(username)
+        .map(String::as_str)
+        .or(active_scene_id)
+}
+
+/// Назначение или снятие мастера комнаты
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemas", derive(ToSchema))]
+#[cfg_attr(feature = "validation", derive(Validate))]
+pub struct GameMasterSetPayload {
+    #[serde(default)]
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 255)))]
+    pub game_master: Option<String>,
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 255)))]
+    pub actor: String,
+}
+
 /// Полное состояние комнаты, которое мы синхронизируем
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "validation", derive(Validate))]
@@ -36,6 +69,31 @@ pub struct RoomState {
     #[serde(default)]
     pub active_scene_id: Option<String>,
 
+    /// Сцены, назначенные отдельным игрокам (имя -> id сцены), когда группа
+    /// разделилась; остальные видят общую активную сцену
+    #[serde(default)]
+    pub scene_assignments: BTreeMap<String, String>,
+
+    /// Мастер комнаты; `None`, пока никто не занял эту роль
+    #[serde(default)]
+    pub game_master: Option<String>,
+
+    /// Листы персонажей комнаты
+    #[serde(default)]
+    pub character_sheets: Vec<CharacterSheet>,
+
+    /// Пользовательские шаблоны листов (встроенные шаблоны здесь не хранятся)
+    #[serde(default)]
+    pub sheet_templates: Vec<SheetTemplate>,
+
+    /// Импортированные компендиумы (ссылки на файлы)
+    #[serde(default)]
+    pub compendium_sources: Vec<CompendiumSource>,
+
+    /// Показанные игрокам раздаточные материалы
+    #[serde(default)]
+    pub handouts: Vec<Handout>,
+
     /// Версия состояния
     pub version: u64,
 
@@ -55,6 +113,12 @@ impl Default for RoomState {
             public_notes: Vec::new(),
             scenes: Vec::new(),
             active_scene_id: None,
+            scene_assignments: BTreeMap::new(),
+            game_master: None,
+            character_sheets: Vec::new(),
+            sheet_templates: Vec::new(),
+            compendium_sources: Vec::new(),
+            handouts: Vec::new(),
             version: 0,
             current_hash: String::new(),
             history_log: Vec::new(),
@@ -92,6 +156,30 @@ impl RoomState {
             hasher.update(active_scene_json.as_bytes());
         }
 
+        if let Ok(assignments_json) = serde_json::to_string(&self.scene_assignments) {
+            hasher.update(assignments_json.as_bytes());
+        }
+
+        if let Ok(game_master_json) = serde_json::to_string(&self.game_master) {
+            hasher.update(game_master_json.as_bytes());
+        }
+
+        if let Ok(sheets_json) = serde_json::to_string(&self.character_sheets) {
+            hasher.update(sheets_json.as_bytes());
+        }
+
+        if let Ok(templates_json) = serde_json::to_string(&self.sheet_templates) {
+            hasher.update(templates_json.as_bytes());
+        }
+
+        if let Ok(compendium_json) = serde_json::to_string(&self.compendium_sources) {
+            hasher.update(compendium_json.as_bytes());
+        }
+
+        if let Ok(handouts_json) = serde_json::to_string(&self.handouts) {
+            hasher.update(handouts_json.as_bytes());
+        }
+
         // Добавляем ссылку на предыдущий хеш (как цепочку блоков)
         hasher.update(self.current_hash.as_bytes());
 
@@ -115,6 +203,67 @@ impl RoomState {
         self.current_hash = new_hash;
     }
 
+    /// Может ли участник назначить или снять мастера: свободную роль занимает
+    /// любой, а передать или сложить её может только текущий мастер.
+    pub fn can_set_game_master(&self, actor: &str) -> bool {
+        acts_as_game_master(self.game_master.as_deref(), actor)
+    }
+
+    /// Может ли участник сохранить лист: новый лист игрок заводит только на себя,
+    /// существующий правят владелец и мастер, а сменить владельца может только мастер.
+    pub fn can_upsert_character_sheet(&self, sheet: &CharacterSheet, actor: &str) -> bool {
+        let game_master = self.game_master.as_deref();
+        if acts_as_game_master(game_master, actor) {
+            return true;
+        }
+        match self
+            .character_sheets
+            .iter()
+            .find(|existing| existing.id == sheet.id)
+        {
+            Some(existing) => {
+                existing.can_be_edited_by(actor, game_master) && existing.owner == sheet.owner
+            }
+            None => sheet.owner.as_deref() == Some(actor),
+        }
+    }
+
+    /// Может ли участник сохранить сцену: рельеф рисует только мастер, поэтому
+    /// остальные не меняют его у существующих сцен и создают сцены без рельефа.
+    pub fn can_upsert_scene(&self, scene: &Scene, actor: &str) -> bool {
+        if acts_as_game_master(self.game_master.as_deref(), actor) {
+            return true;
+        }
+        let existing_terrain = self
+            .scenes
+            .iter()
+            .find(|existing| existing.id == scene.id)
+            .map_or(&[][..], |existing| existing.terrain.as_slice());
+        existing_terrain == scene.terrain.as_slice()
+    }
+
+    /// Переносит игроков на сцену или, при `None`, возвращает их к общей
+    /// активной сцене. Возвращает `false`, если ничего не изменилось.
+    pub fn assign_scene(&mut self, usernames: &[String], scene_id: Option<&str>) -> bool {
+        let mut changed = false;
+        for username in usernames {
+            let previous = match scene_id {
+                Some(scene_id) => self
+                    .scene_assignments
+                    .insert(username.clone(), scene_id.to_string()),
+                None => self.scene_assignments.remove(username),
+            };
+            changed |= previous.as_deref() != scene_id;
+        }
+        changed
+    }
+
+    /// Снимает назначения на удалённую сцену.
+    pub fn forget_scene_assignments(&mut self, scene_id: &str) {
+        self.scene_assignments
+            .retain(|_, assigned_scene_id| assigned_scene_id != scene_id);
+    }
+
     /// Проверяет, есть ли версия с данным хешом в истории
     pub fn has_version_with_hash(&self, version: u64, hash: &str) -> bool {
         self.history_log
@@ -122,3 +271,117 @@ impl RoomState {
             .any(|(v, h)| *v == version && h == hash)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::scene::{TerrainCell, TerrainKind};
+
+    #[test]
+    fn game_master_role_is_claimable_only_while_free() {
+        let mut state = RoomState::default();
+        assert!(state.can_set_game_master("alice"));
+
+        state.game_master = Some("alice".to_string());
+        assert!(state.can_set_game_master("alice"));
+        assert!(!state.can_set_game_master("bob"));
+    }
+
+    #[test]
+    fn game_master_defaults_to_none_on_legacy_state() {
+        let raw = r#"{"chat_history":[],"version":3,"current_hash":"abc"}"#;
+
+        let state: RoomState = serde_json::from_str(raw).unwrap();
+        assert_eq!(state.game_master, None);
+    }
+
+    #[test]
+    fn players_edit_only_their_own_character_sheets() {
+        let sheet = CharacterSheet {
+            id: "sheet-1".to_string(),
+            name: "Aria".to_string(),
+            template_id: "dnd5e".to_string(),
+            owner: Some("alice".to_string()),
+            token_id: None,
+            values: Default::default(),
+            updated_at_ms: 0.0,
+        };
+        let mut state = RoomState {
+            game_master: Some("gm".to_string()),
+            ..RoomState::default()
+        };
+        assert!(state.can_upsert_character_sheet(&sheet, "alice"));
+        assert!(!state.can_upsert_character_sheet(&sheet, "bob"));
+
+        state.character_sheets.push(sheet.clone());
+        let mut reassigned = sheet.clone();
+        reassigned.owner = Some("bob".to_string());
+        assert!(!state.can_upsert_character_sheet(&reassigned, "alice"));
+        assert!(state.can_upsert_character_sheet(&reassigned, "gm"));
+        assert!(state.can_upsert_character_sheet(&sheet, "alice"));
+    }
+
+    #[test]
+    fn only_the_game_master_paints_terrain() {
+        let mut scene: Scene = serde_json::from_str(
+            r#"{"id":"s1","name":"Crypt","grid":{"columns":8,"rows":8,"cell_size_feet":5}}"#,
+        )
+        .unwrap();
+        let mut state = RoomState {
+            game_master: Some("gm".to_string()),
+            ..RoomState::default()
+        };
+        assert!(state.can_upsert_scene(&scene, "alice"));
+
+        scene.terrain.push(TerrainCell {
+            column: 1,
+            row: 2,
+            kind: TerrainKind::Water,
+            color: None,
+            label: None,
+        });
+        assert!(!state.can_upsert_scene(&scene, "alice"));
+        assert!(state.can_upsert_scene(&scene, "gm"));
+
+        state.scenes.push(scene.clone());
+        scene.name = "Flooded crypt".to_string();
+        assert!(state.can_upsert_scene(&scene, "alice"));
+        scene.terrain.clear();
+        assert!(!state.can_upsert_scene(&scene, "alice"));
+    }
+
+    #[test]
+    fn split_party_players_follow_their_assigned_scene() {
+        let mut state = RoomState {
+            active_scene_id: Some("tavern".to_string()),
+            ..RoomState::default()
+        };
+        let party = ["alice".to_string(), "bob".to_string()];
+        assert!(state.assign_scene(&party, Some("crypt")));
+        assert!(!state.assign_scene(&party[..1], Some("crypt")));
+
+        let active = state.active_scene_id.as_deref();
+        let assignments = &state.scene_assignments;
+        assert_eq!(
+            assigned_scene_id(active, assignments, "alice"),
+            Some("crypt")
+        );
+        assert_eq!(
+            assigned_scene_id(active, assignments, "carol"),
+            Some("tavern")
+        );
+
+        assert!(state.assign_scene(&party[1..], None));
+        assert_eq!(
+            assigned_scene_id(
+                state.active_scene_id.as_deref(),
+                &state.scene_assignments,
+                "bob"
+            ),
+            Some("tavern")
+        );
+
+        state.forget_scene_assignments("crypt");
+        assert!(state.scene_assignments.is_empty());
+    }
+}
@@ -1,8 +1,25 @@
+use crate::events::MAX_WIRE_MESSAGE_BYTES;
 use serde::{Deserialize, Serialize};
 #[cfg(feature = "schemas")]
 use utoipa::ToSchema;
 #[cfg(feature = "validation")]
-use validator::Validate;
+use validator::{Validate, ValidationError};
+
+/// Цвет в формате `#RRGGBB`.
+pub fn is_hex_color(value: &str) -> bool {
+    value.len() == 7
+        && value.starts_with('#')
+        && value[1..].chars().all(|ch| ch.is_ascii_hexdigit())
+}
+
+#[cfg(feature = "validation")]
+fn validate_hex_color(color: &str) -> Result<(), ValidationError> {
+    if is_hex_color(color) {
+        Ok(())
+    } else {
+        Err(ValidationError::new("hex_color"))
+    }
+}
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
 #[cfg_attr(feature = "schemas", derive(ToSchema))]
@@ -47,6 +64,202 @@ pub struct Token {
     pub width_cells: u16,
     #[cfg_attr(feature = "validation", validate(range(min = 1, max = 16)))]
     pub height_cells: u16,
+    /// Направление взгляда токена в градусах по часовой стрелке, 0 — вверх.
+    #[serde(default)]
+    #[cfg_attr(feature = "validation", validate(range(min = 0.0, max = 360.0)))]
+    pub rotation_deg: f32,
+    /// Высота над уровнем сцены в футах (отрицательная — под землёй).
+    #[serde(default)]
+    #[cfg_attr(feature = "validation", validate(range(min = -10000, max = 10000)))]
+    pub elevation_feet: i32,
+    /// Игрок, управляющий токеном; `None` — токен мастера.
+    #[serde(default)]
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 255)))]
+    pub owner: Option<String>,
+    /// Ауры и радиусы вокруг токена.
+    #[serde(default)]
+    #[cfg_attr(feature = "validation", validate(length(max = 8), nested))]
+    pub auras: Vec<TokenAura>,
+    /// Этаж сцены, на котором стоит токен; `None` — основной этаж.
+    #[serde(default)]
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 64)))]
+    pub level_id: Option<String>,
+}
+
+/// Кому видна аура токена.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "schemas", derive(ToSchema))]
+pub enum AuraVisibility {
+    #[default]
+    All,
+    Owner,
+    GameMaster,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(feature = "schemas", derive(ToSchema))]
+#[cfg_attr(feature = "validation", derive(Validate))]
+pub struct TokenAura {
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 64)))]
+    pub id: String,
+    #[serde(default)]
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 64)))]
+    pub label: Option<String>,
+    /// Радиус ауры в футах от края токена.
+    #[cfg_attr(feature = "validation", validate(range(min = 1, max = 1000)))]
+    pub radius_feet: u16,
+    /// Цвет в формате `#RRGGBB`.
+    #[cfg_attr(
+        feature = "validation",
+        validate(custom(function = "validate_hex_color"))
+    )]
+    pub color: String,
+    #[cfg_attr(feature = "validation", validate(range(min = 0.0, max = 1.0)))]
+    pub opacity: f32,
+    #[serde(default)]
+    pub visibility: AuraVisibility,
+}
+
+/// Доля сообщения, отведённая рельефу сцены; остальное занимают токены,
+/// стены и прочие поля сцены.
+pub const MAX_SCENE_TERRAIN_BYTES: usize = MAX_WIRE_MESSAGE_BYTES / 2;
+/// Самая длинная клетка без цвета и подписи:
+/// `{"column":65535,"row":65535,"kind":"Difficult"},`.
+const MAX_PLAIN_TERRAIN_CELL_BYTES: usize = 48;
+pub const MAX_SCENE_TERRAIN_CELLS: usize = MAX_SCENE_TERRAIN_BYTES / MAX_PLAIN_TERRAIN_CELL_BYTES;
+
+/// Помещается ли рельеф в отведённую ему долю сообщения.
+pub fn terrain_fits_message(terrain: &[TerrainCell]) -> bool {
+    terrain.len() <= MAX_SCENE_TERRAIN_CELLS
+        && serde_json::to_vec(terrain).is_ok_and(|json| json.len() <= MAX_SCENE_TERRAIN_BYTES)
+}
+
+#[cfg(feature = "validation")]
+fn validate_scene_terrain(terrain: &[TerrainCell]) -> Result<(), ValidationError> {
+    if terrain_fits_message(terrain) {
+        Ok(())
+    } else {
+        Err(ValidationError::new("terrain_size"))
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemas", derive(ToSchema))]
+pub enum TerrainKind {
+    Difficult,
+    Hazard,
+    Water,
+    Custom,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemas", derive(ToSchema))]
+#[cfg_attr(feature = "validation", derive(Validate))]
+pub struct TerrainCell {
+    pub column: u16,
+    pub row: u16,
+    pub kind: TerrainKind,
+    /// Цвет заливки для `TerrainKind::Custom` в формате `#RRGGBB`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 32)))]
+    pub color: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 64)))]
+    pub label: Option<String>,
+}
+
+/// Точка на сцене в клетках от левого верхнего угла сетки.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(feature = "schemas", derive(ToSchema))]
+pub struct ScenePoint {
+    pub x: f32,
+    pub y: f32,
+}
+
+/// Стена, перекрывающая обзор: ломаная из двух и более точек.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(feature = "schemas", derive(ToSchema))]
+#[cfg_attr(feature = "validation", derive(Validate))]
+pub struct SceneWall {
+    #[cfg_attr(feature = "validation", validate(length(min = 2, max = 1024)))]
+    pub points: Vec<ScenePoint>,
+}
+
+/// Дверь или окно между двумя точками.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(feature = "schemas", derive(ToSchema))]
+pub struct SceneDoor {
+    pub start: ScenePoint,
+    pub end: ScenePoint,
+    /// Закрытая дверь перекрывает обзор.
+    #[serde(default = "default_door_closed")]
+    pub closed: bool,
+}
+
+const fn default_door_closed() -> bool {
+    true
+}
+
+/// Источник света на сцене.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(feature = "schemas", derive(ToSchema))]
+#[cfg_attr(feature = "validation", derive(Validate))]
+pub struct SceneLight {
+    pub position: ScenePoint,
+    /// Радиус света в клетках.
+    #[cfg_attr(feature = "validation", validate(range(min = 0.0, max = 1000.0)))]
+    pub range_cells: f32,
+    #[cfg_attr(feature = "validation", validate(range(min = 0.0, max = 10.0)))]
+    pub intensity: f32,
+    /// Цвет в формате `#RRGGBB`.
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 32)))]
+    pub color: String,
+}
+
+/// Дополнительный этаж сцены со своим фоном. Сетка общая со сценой.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(feature = "schemas", derive(ToSchema))]
+#[cfg_attr(feature = "validation", derive(Validate))]
+pub struct SceneLevel {
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 64)))]
+    pub id: String,
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 64)))]
+    pub name: String,
+    #[serde(default)]
+    #[cfg_attr(feature = "validation", validate(nested))]
+    pub background: Option<FileRef>,
+    #[serde(default = "default_background_scale")]
+    pub background_scale: f32,
+    #[serde(default)]
+    pub background_offset_x: f32,
+    #[serde(default)]
+    pub background_offset_y: f32,
+    #[serde(default)]
+    pub background_rotation_deg: f32,
+}
+
+/// Лестница или портал: токен, оставленный на клетке, переходит на связанный этаж.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(feature = "schemas", derive(ToSchema))]
+#[cfg_attr(feature = "validation", derive(Validate))]
+pub struct SceneStairs {
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 64)))]
+    pub id: String,
+    /// Этаж, на котором стоит лестница; `None` — основной этаж.
+    #[serde(default)]
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 64)))]
+    pub level_id: Option<String>,
+    pub column: u16,
+    pub row: u16,
+    /// Этаж назначения; `None` — основной этаж.
+    #[serde(default)]
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 64)))]
+    pub target_level_id: Option<String>,
+    pub target_column: u16,
+    pub target_row: u16,
+    #[serde(default)]
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 64)))]
+    pub label: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -77,6 +290,37 @@ pub struct Scene {
     #[serde(default)]
     #[cfg_attr(feature = "validation", validate(nested))]
     pub tokens: Vec<Token>,
+    /// Рельеф должен поместиться в `MAX_SCENE_TERRAIN_BYTES`.
+    #[serde(default)]
+    #[cfg_attr(
+        feature = "validation",
+        validate(custom(function = "validate_scene_terrain"), nested)
+    )]
+    pub terrain: Vec<TerrainCell>,
+    /// Стены, двери и свет из импортированной карты (например, `.dd2vtt`).
+    #[serde(default)]
+    #[cfg_attr(feature = "validation", validate(length(max = 4096), nested))]
+    pub walls: Vec<SceneWall>,
+    #[serde(default)]
+    #[cfg_attr(feature = "validation", validate(length(max = 1024)))]
+    pub doors: Vec<SceneDoor>,
+    #[serde(default)]
+    #[cfg_attr(feature = "validation", validate(length(max = 256), nested))]
+    pub lights: Vec<SceneLight>,
+    /// Папка в списке сцен; `None` — без папки.
+    #[serde(default)]
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 64)))]
+    pub folder: Option<String>,
+    /// Порядок сцены внутри папки.
+    #[serde(default)]
+    pub sort_index: u32,
+    /// Дополнительные этажи по порядку; основной этаж описан полями самой сцены.
+    #[serde(default)]
+    #[cfg_attr(feature = "validation", validate(length(max = 16), nested))]
+    pub levels: Vec<SceneLevel>,
+    #[serde(default)]
+    #[cfg_attr(feature = "validation", validate(length(max = 64), nested))]
+    pub stairs: Vec<SceneStairs>,
 }
 
 const fn default_background_scale() -> f32 {
@@ -123,6 +367,22 @@ pub struct SceneActivatePayload {
     pub actor: String,
 }
 
+/// Назначение отдельным игрокам своей активной сцены, когда группа разделилась
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemas", derive(ToSchema))]
+#[cfg_attr(feature = "validation", derive(Validate))]
+pub struct SceneAssignPayload {
+    /// Игроки, которых переносят на сцену
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 64)))]
+    pub usernames: Vec<String>,
+    /// Сцена игроков; `None` возвращает их к общей активной сцене
+    #[serde(default)]
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 255)))]
+    pub scene_id: Option<String>,
+    #[cfg_attr(feature = "validation", validate(length(min = 1, max = 255)))]
+    pub actor: String,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[cfg_attr(feature = "schemas", derive(ToSchema))]
 #[cfg_attr(feature = "validation", derive(Validate))]
@@ -141,6 +401,15 @@ pub struct TokenMovePayload {
 mod tests {
     use super::*;
 
+    #[test]
+    fn hex_colors_are_six_digit_rgb() {
+        assert!(is_hex_color("#facc15"));
+        assert!(is_hex_color("#FACC15"));
+        assert!(!is_hex_color("facc15"));
+        assert!(!is_hex_color("#fff"));
+        assert!(!is_hex_color("red; background: url(x)"));
+    }
+
     #[test]
     fn scene_tokens_default_to_empty_on_legacy_payloads() {
         let raw = r#"{
@@ -151,6 +420,108 @@ mod tests {
 
         let scene: Scene = serde_json::from_str(raw).unwrap();
         assert!(scene.tokens.is_empty());
+        assert!(scene.terrain.is_empty());
+        assert!(scene.walls.is_empty());
+        assert!(scene.doors.is_empty());
+        assert!(scene.lights.is_empty());
+        assert_eq!(scene.folder, None);
+        assert_eq!(scene.sort_index, 0);
+        assert!(scene.levels.is_empty());
+        assert!(scene.stairs.is_empty());
         assert_eq!(scene.background_scale, 1.0);
     }
+
+    #[test]
+    fn token_facing_and_elevation_default_on_legacy_payloads() {
+        let raw = r#"{
+            "id":"token-1",
+            "name":"Goblin",
+            "image":{"hash":"abc","mime_type":"image/png","file_name":"goblin.png","size":1},
+            "x":1.0,
+            "y":2.0,
+            "width_cells":1,
+            "height_cells":1
+        }"#;
+
+        let token: Token = serde_json::from_str(raw).unwrap();
+        assert_eq!(token.rotation_deg, 0.0);
+        assert_eq!(token.elevation_feet, 0);
+        assert_eq!(token.owner, None);
+        assert!(token.auras.is_empty());
+        assert_eq!(token.level_id, None);
+    }
+
+    #[test]
+    fn token_aura_visibility_defaults_to_all() {
+        let raw = r##"{"id":"aura-1","radius_feet":10,"color":"#22c55e","opacity":0.3}"##;
+
+        let aura: TokenAura = serde_json::from_str(raw).unwrap();
+        assert_eq!(aura.visibility, AuraVisibility::All);
+        assert_eq!(aura.label, None);
+    }
+
+    #[test]
+    fn terrain_cell_custom_fields_are_optional() {
+        let raw = r#"{"column":3,"row":4,"kind":"Difficult"}"#;
+
+        let cell: TerrainCell = serde_json::from_str(raw).unwrap();
+        assert_eq!(cell.kind, TerrainKind::Difficult);
+        assert_eq!(cell.color, None);
+        assert_eq!(cell.label, None);
+    }
+
+    #[test]
+    fn maximum_terrain_fits_one_message() {
+        use crate::events::ClientEvent;
+
+        let message_bytes = |terrain: Vec<TerrainCell>| {
+            let mut scene: Scene = serde_json::from_str(
+                r#"{"id":"scene-1","name":"Arena","grid":{"columns":200,"rows":200,"cell_size_feet":5}}"#,
+            )
+            .unwrap();
+            scene.terrain = terrain;
+            serde_json::to_vec(&ClientEvent::SceneUpdate(SceneUpdatePayload {
+                scene,
+                actor: "g".repeat(50),
+            }))
+            .unwrap()
+            .len()
+        };
+
+        let plain = (0..MAX_SCENE_TERRAIN_CELLS)
+            .map(|index| TerrainCell {
+                column: u16::MAX - (index % 100) as u16,
+                row: u16::MAX - (index / 100) as u16,
+                kind: TerrainKind::Difficult,
+                color: None,
+                label: None,
+            })
+            .collect::<Vec<_>>();
+        assert!(terrain_fits_message(&plain));
+        assert!(message_bytes(plain) < MAX_WIRE_MESSAGE_BYTES);
+
+        let labelled = TerrainCell {
+            column: u16::MAX,
+            row: u16::MAX,
+            kind: TerrainKind::Custom,
+            color: Some("#".repeat(32)),
+            label: Some("\"".repeat(64)),
+        };
+        let cell_bytes = serde_json::to_vec(&labelled).unwrap().len() + 1;
+        let mut custom = vec![labelled.clone(); MAX_SCENE_TERRAIN_BYTES / cell_bytes];
+        assert!(terrain_fits_message(&custom));
+        custom.push(labelled);
+        assert!(!terrain_fits_message(&custom));
+        custom.pop();
+        assert!(message_bytes(custom) < MAX_WIRE_MESSAGE_BYTES);
+    }
+
+    #[test]
+    fn scene_doors_default_to_closed() {
+        let raw = r#"{"start":{"x":1.0,"y":2.0},"end":{"x":2.0,"y":2.0}}"#;
+
+        let door: SceneDoor = serde_json::from_str(raw).unwrap();
+        assert!(door.closed);
+        assert_eq!(door.end, ScenePoint { x: 2.0, y: 2.0 });
+    }
 }
@@ -98,3 +98,11 @@ pub struct PresenceAnnouncePayload {
     pub request_id: String,
     pub online_users: Vec<String>,
 }
+
+/// Рассылается сервером, когда пользователь отключился от комнаты.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemas", derive(ToSchema))]
+#[cfg_attr(feature = "validation", derive(Validate))]
+pub struct PresenceLeavePayload {
+    pub user: String,
+}